@@ -0,0 +1,194 @@
+//! Allocation count for headless event emission
+//!
+//! Runs `emit_game_events` for a simulated match's worth of frames two ways:
+//! rebuilding the snapshot vectors and AI goal strings every frame (how the
+//! emitters used to work) and with a reused `SnapshotPool` plus interned goal
+//! names (how they work now). Prints allocations per frame for both and fails
+//! if pooling doesn't allocate less.
+//!
+//! Run: cargo bench --bench event_alloc
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use ballgame::events::{
+    BallSnapshot, BasketSnapshot, EventBuffer, EventEmitterState, GameEvent, PlayerId,
+    PlayerSnapshot, SnapshotPool, emit_game_events, intern,
+};
+use ballgame::{BallState, Basket, Score, StealContest, Team};
+use bevy::prelude::Entity;
+
+/// Counts every allocation made through the global allocator
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// 60 seconds at 60 fps
+const FRAMES: usize = 3600;
+const DT: f32 = 1.0 / 60.0;
+const GOALS: [&str; 4] = [
+    "ChaseBall",
+    "AttackWithBall",
+    "InterceptDefense",
+    "PressureDefense",
+];
+
+fn player(index: u32, frame: usize) -> PlayerSnapshot {
+    let t = frame as f32 * DT;
+    PlayerSnapshot {
+        entity: Entity::from_raw_u32(index + 1).unwrap(),
+        team: if index == 0 { Team::Left } else { Team::Right },
+        position: (t.sin() * 300.0, -200.0),
+        velocity: (t.cos() * 300.0, 0.0),
+        charge_time: 0.0,
+        target_basket: if index == 0 {
+            Basket::Right
+        } else {
+            Basket::Left
+        },
+        // Goals change every couple of seconds, like a real match
+        ai_goal: GOALS[(frame / 120 + index as usize) % GOALS.len()],
+        steal_cooldown: 0.0,
+        is_holding_ball: false,
+        input_move_x: t.sin(),
+        input_jump: false,
+        input_throw: false,
+        input_pickup: false,
+    }
+}
+
+fn baskets() -> [BasketSnapshot; 2] {
+    [
+        BasketSnapshot {
+            basket: Basket::Left,
+            position: (-600.0, 0.0),
+        },
+        BasketSnapshot {
+            basket: Basket::Right,
+            position: (600.0, 0.0),
+        },
+    ]
+}
+
+fn ball(frame: usize) -> BallSnapshot {
+    BallSnapshot {
+        position: ((frame as f32 * DT).cos() * 200.0, -250.0),
+        velocity: (0.0, 0.0),
+        state: BallState::Free,
+    }
+}
+
+/// Allocations per frame with fresh vectors and `String` goal names each frame
+fn run_unpooled() -> f64 {
+    let mut state = EventEmitterState::default();
+    let mut buffer = EventBuffer::new();
+    let (score, steal) = (Score::default(), StealContest::default());
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    for frame in 0..FRAMES {
+        let players: Vec<_> = (0..2).map(|i| player(i, frame)).collect();
+        let basket_snapshots: Vec<_> = baskets().into_iter().collect();
+        let goal_names: Vec<String> = players.iter().map(|p| p.ai_goal.to_string()).collect();
+        emit_game_events(
+            &mut state,
+            &mut buffer,
+            frame as f32 * DT,
+            &score,
+            &steal,
+            &players,
+            &basket_snapshots,
+            Some(&ball(frame)),
+            None,
+        );
+        // Goal-change events used to carry an owned copy of the name
+        if frame % 120 == 0 {
+            for (i, name) in goal_names.iter().enumerate() {
+                buffer.log(
+                    frame as f32 * DT,
+                    GameEvent::AiGoal {
+                        player: if i == 0 { PlayerId::L } else { PlayerId::R },
+                        goal: Arc::from(name.as_str()),
+                    },
+                );
+            }
+        }
+    }
+    (ALLOCATIONS.load(Ordering::Relaxed) - before) as f64 / FRAMES as f64
+}
+
+/// Allocations per frame with a reused `SnapshotPool` and interned goal names
+fn run_pooled() -> f64 {
+    let mut state = EventEmitterState::default();
+    let mut buffer = EventBuffer::new();
+    let (score, steal) = (Score::default(), StealContest::default());
+    let mut pool = SnapshotPool::default();
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    for frame in 0..FRAMES {
+        pool.clear();
+        pool.players.extend((0..2).map(|i| player(i, frame)));
+        pool.baskets.extend(baskets());
+        emit_game_events(
+            &mut state,
+            &mut buffer,
+            frame as f32 * DT,
+            &score,
+            &steal,
+            &pool.players,
+            &pool.baskets,
+            Some(&ball(frame)),
+            None,
+        );
+        if frame % 120 == 0 {
+            for (i, snapshot) in pool.players.iter().enumerate() {
+                buffer.log(
+                    frame as f32 * DT,
+                    GameEvent::AiGoal {
+                        player: if i == 0 { PlayerId::L } else { PlayerId::R },
+                        goal: intern(snapshot.ai_goal),
+                    },
+                );
+            }
+        }
+    }
+    (ALLOCATIONS.load(Ordering::Relaxed) - before) as f64 / FRAMES as f64
+}
+
+fn main() {
+    // Warm the intern table so both runs see the same steady state
+    for goal in GOALS {
+        intern(goal);
+    }
+
+    let unpooled = run_unpooled();
+    let pooled = run_pooled();
+    println!("event emission allocations per frame ({} frames)", FRAMES);
+    println!("  unpooled: {:>6.2}", unpooled);
+    println!("  pooled:   {:>6.2}", pooled);
+    println!("  saved:    {:>6.2}", unpooled - pooled);
+
+    assert!(
+        pooled < unpooled,
+        "pooled emission should allocate less per frame ({:.2} >= {:.2})",
+        pooled,
+        unpooled
+    );
+}
@@ -5,7 +5,31 @@
 
 use bevy::prelude::*;
 
+use crate::calculate_shot_trajectory;
 use crate::constants::*;
+use crate::shooting::ideal_charge_pct;
+use crate::tuning::PhysicsTweaks;
+
+/// Seconds after takeoff before a jump shot starts charging
+pub const JUMP_SHOT_CHARGE_DELAY: f32 = 0.1;
+
+/// How to time a jump shot so the ball is aimed from where it actually leaves
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JumpShotPlan {
+    /// Seconds after takeoff to keep jump held
+    pub hold: f32,
+    /// Seconds of charge (starting JUMP_SHOT_CHARGE_DELAY after takeoff)
+    pub charge: f32,
+    /// Height of the release point above the takeoff position
+    pub release_height: f32,
+}
+
+impl JumpShotPlan {
+    /// Seconds after takeoff the ball is released
+    pub fn release_time(&self) -> f32 {
+        JUMP_SHOT_CHARGE_DELAY + self.charge
+    }
+}
 
 /// Precomputed physics capabilities for AI decision-making.
 /// Created once at startup, used by navigation and decision systems.
@@ -52,6 +76,89 @@ impl AiCapabilities {
         (ratio * 1.2).clamp(0.1, 1.0)
     }
 
+    /// Height above takeoff `t` seconds into a jump with the button held for `hold`
+    /// seconds. Steps the fixed 60 Hz player physics: rise/fall gravity and the
+    /// jump cut when the button is let go while rising. 0 once landed.
+    pub fn jump_height_at(&self, hold: f32, t: f32) -> f32 {
+        let dt = 1.0 / 60.0;
+        let (mut y, mut vy) = (0.0, JUMP_VELOCITY);
+        let mut cut = false;
+        for step in 0..(t / dt).round() as usize {
+            if !cut && vy > 0.0 && step as f32 * dt >= hold {
+                vy *= JUMP_CUT_MULTIPLIER;
+                cut = true;
+            }
+            vy -= if vy > 0.0 { GRAVITY_RISE } else { GRAVITY_FALL } * dt;
+            y += vy * dt;
+            if y <= 0.0 {
+                return 0.0;
+            }
+        }
+        y
+    }
+
+    /// Plan a jump shot from `shooter` (standing) at `basket`. The charge is the
+    /// least that keeps the shot's spread in the basket as aimed from the release
+    /// point (within `charge_range`), and the release height follows from the
+    /// charge, so the two are solved together. Jump is held up to the release
+    /// (or the apex) for the highest release. `penalties` is the variance already
+    /// expected on the shot (airborne, charging while moving). None when no
+    /// trajectory reaches the basket from the release point, or the charge can't
+    /// finish before landing.
+    pub fn plan_jump_shot(
+        &self,
+        tweaks: &PhysicsTweaks,
+        shooter: Vec2,
+        basket: Vec2,
+        charge_range: (f32, f32),
+        penalties: f32,
+    ) -> Option<JumpShotPlan> {
+        // Release a little before a full jump lands
+        let air_time = self.time_to_peak + (2.0 * self.max_jump_height / GRAVITY_FALL).sqrt();
+        let (min_charge, max_charge) = charge_range;
+        let max_charge = max_charge
+            .min(air_time - JUMP_SHOT_CHARGE_DELAY - 0.1)
+            .max(min_charge);
+        let plan_for = |charge: f32| {
+            let release = JUMP_SHOT_CHARGE_DELAY + charge;
+            let hold = release.min(self.time_to_peak);
+            JumpShotPlan {
+                hold,
+                charge,
+                release_height: self.jump_height_at(hold, release),
+            }
+        };
+
+        let mut plan = plan_for(min_charge);
+        // Converges in a couple of rounds: height changes slowly near the apex
+        for _ in 0..3 {
+            let from = shooter + Vec2::Y * plan.release_height;
+            let trajectory = calculate_shot_trajectory(
+                from.x,
+                from.y,
+                basket.x,
+                basket.y,
+                BALL_GRAVITY,
+                tweaks.shot_distance_variance,
+                None,
+            )?;
+            let ideal = ideal_charge_pct(
+                tweaks,
+                trajectory.angle,
+                trajectory.required_speed,
+                basket.x - from.x,
+                basket.y - from.y,
+                penalties + trajectory.distance_variance,
+            );
+            let charge = (ideal * tweaks.shot_charge_time).clamp(min_charge, max_charge);
+            if (charge - plan.charge).abs() < 1e-3 {
+                break;
+            }
+            plan = plan_for(charge);
+        }
+        (plan.release_height > 0.0).then_some(plan)
+    }
+
     /// Check if there's ceiling clearance to jump at this position.
     /// Uses platform bounds to determine if a jump would be blocked.
     pub fn has_ceiling_clearance(&self, pos: Vec2, platforms: &[(Vec2, Vec2)]) -> bool {
@@ -162,6 +269,30 @@ mod tests {
         assert!(caps.jump_hold_for_height(200.0) > 0.8);
     }
 
+    #[test]
+    fn test_jump_shot_releases_from_planned_height() {
+        let caps = AiCapabilities::default();
+        // Held through the apex: reaches (nearly) the full jump height, then lands
+        let apex = caps.jump_height_at(1.0, caps.time_to_peak);
+        assert!((apex - caps.max_jump_height).abs() < 10.0, "apex {}", apex);
+        assert_eq!(caps.jump_height_at(1.0, 2.0), 0.0);
+        // Letting go early cuts the jump short
+        assert!(caps.jump_height_at(0.1, caps.time_to_peak) < apex * 0.5);
+
+        let tweaks = PhysicsTweaks::default();
+        let shooter = Vec2::new(0.0, -380.0);
+        let basket = Vec2::new(600.0, 100.0);
+        let plan = caps
+            .plan_jump_shot(&tweaks, shooter, basket, (0.2, 1.2), 0.0)
+            .unwrap();
+        assert!((0.2..=1.2).contains(&plan.charge));
+        assert!(plan.release_height > 0.0);
+        assert_eq!(
+            plan.release_height,
+            caps.jump_height_at(plan.hold, plan.release_time())
+        );
+    }
+
     #[test]
     fn test_ceiling_clearance() {
         let caps = AiCapabilities::default();
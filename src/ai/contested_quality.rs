@@ -0,0 +1,327 @@
+//! Contested shot quality - how much a nearby defender costs a shot
+//!
+//! `evaluate_shot_quality` only knows about open shots. The shot test's contested
+//! pass (`simulate --shot-test N --contested`) fires the same shots with a
+//! defender placed in front of the shooter and records the make rate for each
+//! placement in `config/contested_quality/<level>.json`. The AI scales shot
+//! quality by the measured make rate relative to open shots for the nearest
+//! placement; levels with no table are treated as uncontested.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::heatmaps::sanitize_level_name;
+
+/// Root directory for contested quality tables
+pub const CONTESTED_QUALITY_DIR: &str = "config/contested_quality";
+
+/// Defenders further away than this (horizontally) don't contest
+pub const CONTEST_MAX_DISTANCE: f32 = 300.0;
+
+/// What the defender does while the shot goes up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DefenderPattern {
+    /// Stays on the ground
+    Stand,
+    /// Jumps once when the shooter starts charging
+    Jump,
+    /// Jumps again every time it lands
+    Hop,
+}
+
+impl DefenderPattern {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.trim() {
+            "stand" => Ok(Self::Stand),
+            "jump" => Ok(Self::Jump),
+            "hop" => Ok(Self::Hop),
+            other => Err(format!(
+                "unknown defender pattern '{}' (use stand, jump, hop)",
+                other
+            )),
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Stand => "stand",
+            Self::Jump => "jump",
+            Self::Hop => "hop",
+        }
+    }
+}
+
+/// Where a test defender stands relative to the shooter
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DefenderPlacement {
+    /// Horizontal distance toward the basket
+    pub distance: f32,
+    /// Height of the defender's feet above the shooter's
+    pub height: f32,
+    pub pattern: DefenderPattern,
+}
+
+impl DefenderPlacement {
+    /// Parse `<distance>[,<height>[,<pattern>]]`, e.g. `120,0,jump`
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut parts = spec.split(',').map(str::trim);
+        let number = |part: Option<&str>, what: &str, default: Option<f32>| match part {
+            Some(p) if !p.is_empty() => p
+                .parse::<f32>()
+                .map_err(|_| format!("bad defender {} '{}'", what, p)),
+            _ => default.ok_or_else(|| format!("defender spec '{}' needs a {}", spec, what)),
+        };
+        let distance = number(parts.next(), "distance", None)?;
+        let height = number(parts.next(), "height", Some(0.0))?;
+        let pattern = match parts.next() {
+            Some(p) if !p.is_empty() => DefenderPattern::parse(p)?,
+            _ => DefenderPattern::Stand,
+        };
+        Ok(Self {
+            distance,
+            height,
+            pattern,
+        })
+    }
+
+    pub fn label(&self) -> String {
+        format!(
+            "{:.0}px, {:+.0}px, {}",
+            self.distance,
+            self.height,
+            self.pattern.label()
+        )
+    }
+}
+
+/// Placements used by `--contested`: close/mid/far, each pattern on the ground,
+/// plus a defender up on a ledge
+pub fn default_placements() -> Vec<DefenderPlacement> {
+    let mut placements = Vec::new();
+    for distance in [60.0, 120.0, 240.0] {
+        for pattern in [
+            DefenderPattern::Stand,
+            DefenderPattern::Jump,
+            DefenderPattern::Hop,
+        ] {
+            placements.push(DefenderPlacement {
+                distance,
+                height: 0.0,
+                pattern,
+            });
+        }
+    }
+    placements.push(DefenderPlacement {
+        distance: 120.0,
+        height: 100.0,
+        pattern: DefenderPattern::Stand,
+    });
+    placements
+}
+
+/// Measured shots for one placement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContestedRate {
+    pub placement: DefenderPlacement,
+    pub shots: u32,
+    pub goals: u32,
+}
+
+impl ContestedRate {
+    pub fn make_rate(&self) -> f32 {
+        if self.shots == 0 {
+            0.0
+        } else {
+            self.goals as f32 / self.shots as f32
+        }
+    }
+}
+
+/// Open and contested make rates for one level
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContestedQualityTable {
+    pub level: String,
+    /// Creation time (YYYYMMDD_HHMMSS)
+    pub created: String,
+    pub open_shots: u32,
+    pub open_goals: u32,
+    pub rates: Vec<ContestedRate>,
+}
+
+impl ContestedQualityTable {
+    pub fn open_rate(&self) -> f32 {
+        if self.open_shots == 0 {
+            0.0
+        } else {
+            self.open_goals as f32 / self.open_shots as f32
+        }
+    }
+
+    /// Make rate over all contested shots
+    pub fn contested_rate(&self) -> f32 {
+        let shots: u32 = self.rates.iter().map(|r| r.shots).sum();
+        let goals: u32 = self.rates.iter().map(|r| r.goals).sum();
+        if shots == 0 {
+            0.0
+        } else {
+            goals as f32 / shots as f32
+        }
+    }
+
+    /// Quality multiplier (0-1) for a defender `distance` ahead and `height` above:
+    /// the nearest measured placement's make rate over the open rate, averaged
+    /// over jumping patterns (the AI can't know which one it will face).
+    /// 1.0 when there's nothing to compare against.
+    pub fn factor(&self, distance: f32, height: f32) -> f32 {
+        let open = self.open_rate();
+        if open <= 0.0 || distance > CONTEST_MAX_DISTANCE {
+            return 1.0;
+        }
+        let gap = |p: &DefenderPlacement| (p.distance - distance).hypot(p.height - height);
+        let Some(nearest) = self
+            .rates
+            .iter()
+            .filter(|r| r.shots > 0)
+            .min_by(|a, b| gap(&a.placement).total_cmp(&gap(&b.placement)))
+            .map(|r| (r.placement.distance, r.placement.height))
+        else {
+            return 1.0;
+        };
+        let (shots, goals) = self
+            .rates
+            .iter()
+            .filter(|r| (r.placement.distance, r.placement.height) == nearest)
+            .fold((0, 0), |(s, g), r| (s + r.shots, g + r.goals));
+        (goals as f32 / shots as f32 / open).clamp(0.0, 1.0)
+    }
+
+    pub fn path(dir: &Path, level: &str) -> PathBuf {
+        dir.join(format!("{}.json", sanitize_level_name(level)))
+    }
+
+    /// Write as `<dir>/<level>.json`
+    pub fn save(&self, dir: &Path) -> Result<PathBuf, String> {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        let path = Self::path(dir, &self.level);
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize contested table: {}", e))?;
+        fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        Ok(path)
+    }
+
+    pub fn load(dir: &Path, level: &str) -> Result<Self, String> {
+        let path = Self::path(dir, level);
+        let contents = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+    }
+}
+
+/// Contested quality tables by level name (levels without a table are uncontested)
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ContestedQuality {
+    pub tables: HashMap<String, ContestedQualityTable>,
+}
+
+impl ContestedQuality {
+    /// Load every table in `dir` (empty if the directory doesn't exist)
+    pub fn load_dir(dir: &Path) -> Self {
+        let mut tables = HashMap::new();
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Self { tables };
+        };
+        for path in entries.flatten().map(|e| e.path()) {
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let parsed = fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|c| {
+                    serde_json::from_str::<ContestedQualityTable>(&c).map_err(|e| e.to_string())
+                });
+            match parsed {
+                Ok(table) => {
+                    tables.insert(table.level.clone(), table);
+                }
+                Err(e) => warn!("Skipping contested table {}: {}", path.display(), e),
+            }
+        }
+        Self { tables }
+    }
+
+    /// Quality multiplier for a defender at `defender` against a shot from
+    /// `shooter` at `basket`. Only defenders between shooter and basket contest.
+    pub fn factor(&self, level: &str, shooter: Vec2, basket: Vec2, defender: Vec2) -> f32 {
+        let Some(table) = self.tables.get(level) else {
+            return 1.0;
+        };
+        let ahead = (defender.x - shooter.x) * (basket.x - shooter.x).signum();
+        if ahead < 0.0 {
+            return 1.0;
+        }
+        table.factor(ahead, defender.y - shooter.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contested_factor_uses_nearest_placement() {
+        let placement = |spec: &str| DefenderPlacement::parse(spec).unwrap();
+        assert_eq!(
+            placement("120"),
+            DefenderPlacement {
+                distance: 120.0,
+                height: 0.0,
+                pattern: DefenderPattern::Stand
+            }
+        );
+        assert_eq!(placement("60,40,hop").pattern, DefenderPattern::Hop);
+        assert!(DefenderPlacement::parse("60,0,dance").is_err());
+        assert!(DefenderPlacement::parse("").is_err());
+
+        let rate = |spec: &str, goals| ContestedRate {
+            placement: placement(spec),
+            shots: 10,
+            goals,
+        };
+        let table = ContestedQualityTable {
+            level: "Islands".to_string(),
+            created: String::new(),
+            open_shots: 10,
+            open_goals: 8,
+            rates: vec![
+                rate("60,0,stand", 4),
+                rate("60,0,jump", 2),
+                rate("240,0,stand", 8),
+            ],
+        };
+        // Close defender: patterns averaged, (4 + 2) / 20 vs 0.8 open
+        assert!((table.factor(70.0, 0.0) - 0.375).abs() < 1e-6);
+        assert_eq!(table.factor(230.0, 0.0), 1.0);
+        assert_eq!(table.factor(CONTEST_MAX_DISTANCE + 1.0, 0.0), 1.0);
+        assert!((table.contested_rate() - 14.0 / 30.0).abs() < 1e-6);
+
+        let mut quality = ContestedQuality::default();
+        quality.tables.insert(table.level.clone(), table);
+        let (shooter, basket) = (Vec2::ZERO, Vec2::new(500.0, 200.0));
+        assert!(quality.factor("Islands", shooter, basket, Vec2::new(60.0, 0.0)) < 1.0);
+        // Behind the shooter, or a level with no table: open shot
+        assert_eq!(
+            quality.factor("Islands", shooter, basket, Vec2::new(-60.0, 0.0)),
+            1.0
+        );
+        assert_eq!(
+            quality.factor("Other", shooter, basket, Vec2::new(60.0, 0.0)),
+            1.0
+        );
+    }
+}
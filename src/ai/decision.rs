@@ -3,18 +3,31 @@
 use bevy::prelude::*;
 use rand::Rng;
 
+use crate::ai::capabilities::JUMP_SHOT_CHARGE_DELAY;
+use crate::ai::contested_quality::ContestedQuality;
+use crate::ai::nav_metrics::{EdgeOutcome, NavEdgeStats, report_edge_outcome};
 use crate::ai::navigation::{find_escape_x, has_ceiling_above};
+use crate::ai::off_ball::{OffBallContext, choose_off_ball_spot, off_ball_candidates};
+use crate::ai::risk::{BASE_MAX_HOLD_TIME, GameSituation, MatchClock};
 use crate::ai::{
     AiCapabilities, AiGoal, AiNavState, AiProfileDatabase, AiState, HeatmapBundle, InputState,
-    NavAction, NavGraph, find_path, find_path_to_shoot,
+    NavAction, NavGraph, TeamBlackboard, find_path, find_path_to_shoot,
     shot_quality::{evaluate_shot_quality, scale_min_quality_for_level},
 };
 use crate::ball::{Ball, BallState};
+use crate::config::{BallConfig, ShotConfig, StealConfig};
 use crate::constants::*;
-use crate::events::{ControllerSource, EventBus, GameEvent, PlayerId};
-use crate::levels::LevelDatabase;
-use crate::player::{Grounded, HoldingBall, HumanControlled, Player, TargetBasket, Team};
-use crate::scoring::CurrentLevel;
+use crate::events::{ControllerSource, EventBus, GameEvent, PlayerId, intern};
+use crate::levels::{ArenaDims, LevelDatabase};
+use crate::momentum::Momentum;
+use crate::player::{Grounded, HoldingBall, HumanControlled, Player, TargetBasket, Team, Velocity};
+use crate::scoring::{
+    BallReset, CurrentLevel, Score, ShotClock, carry_in_threat, covers_rim, inbound_setup_x,
+    rim_block_spot,
+};
+use crate::shooting::is_unsteady;
+use crate::steal::LooseBall;
+use crate::tuning::PhysicsTweaks;
 use crate::world::Basket;
 
 /// Calculate the interception position on the line between ball carrier and defender's basket.
@@ -25,6 +38,7 @@ fn calculate_interception_position(
     basket_pos: Vec2,
     pressure_distance: f32,
     defensive_iq: f32,
+    arena: &ArenaDims,
 ) -> Vec2 {
     // Direction from opponent to basket (the shot line)
     let shot_direction = (basket_pos - opponent_pos).normalize_or_zero();
@@ -48,9 +62,9 @@ fn calculate_interception_position(
     // Clamp to valid arena bounds to prevent AI from targeting unreachable positions
     // Leave margin for wall thickness and corner steps
     let margin = WALL_THICKNESS + CORNER_STEP_TOTAL_WIDTH + PLAYER_SIZE.x;
-    let min_x = -ARENA_WIDTH / 2.0 + margin;
-    let max_x = ARENA_WIDTH / 2.0 - margin;
-    let min_y = ARENA_FLOOR_Y + PLAYER_SIZE.y / 2.0;
+    let min_x = -arena.width / 2.0 + margin;
+    let max_x = arena.width / 2.0 - margin;
+    let min_y = arena.floor_y + PLAYER_SIZE.y / 2.0;
 
     Vec2::new(unclamped.x.clamp(min_x, max_x), unclamped.y.max(min_y))
 }
@@ -88,15 +102,21 @@ pub fn defender_in_shot_path(
 
 /// Update AI navigation paths based on current goals.
 /// Runs before ai_decision_update to set up paths that the decision system will execute.
+#[allow(clippy::too_many_arguments)]
 pub fn ai_navigation_update(
     nav_graph: Res<NavGraph>,
     profile_db: Res<AiProfileDatabase>,
+    shot_config: Res<ShotConfig>,
+    tweaks: Res<PhysicsTweaks>,
+    heatmaps: Res<HeatmapBundle>,
+    level_db: Res<LevelDatabase>,
+    current_level: Res<CurrentLevel>,
     mut ai_query: Query<
         (
             Entity,
             &Transform,
             &Team,
-            &AiState,
+            &mut AiState,
             &mut AiNavState,
             &TargetBasket,
             Option<&HoldingBall>,
@@ -110,25 +130,37 @@ pub fn ai_navigation_update(
             &Transform,
             Option<&HoldingBall>,
             Option<&HumanControlled>,
+            &Team,
         ),
         With<Player>,
     >,
     ball_query: Query<(&Transform, &BallState), With<Ball>>,
     basket_query: Query<(&Transform, &Basket)>,
+    blackboard: Option<Res<TeamBlackboard>>,
 ) {
+    let arena = nav_graph.level_geometry.arena;
+
     // Skip if nav graph not built
     if nav_graph.nodes.is_empty() {
         return;
     }
 
+    let level_score_weight = level_db
+        .get_by_id(&current_level.0)
+        .or_else(|| level_db.get_by_name(&current_level.0))
+        .map(|level| level.heatmap_score_weight)
+        .unwrap_or(1.0);
+    // Off-ball spots only matter in 2v2; built lazily once per frame
+    let mut off_ball_spots: Option<Vec<Vec2>> = None;
+
     for (
         ai_entity,
         ai_transform,
         team,
-        ai_state,
+        mut ai_state,
         mut nav_state,
         target_basket,
-        _holding,
+        holding,
         _grounded,
     ) in &mut ai_query
     {
@@ -155,12 +187,20 @@ pub fn ai_navigation_update(
             .find(|(_, b)| **b == target_basket.0)
             .map(|(t, _)| t.translation.truncate());
 
-        // Find opponent position (prefer human if present, otherwise any other player)
-        let opponent_pos = all_players
-            .iter()
-            .find(|(e, _, _, human)| *e != ai_entity && human.is_some())
-            .or_else(|| all_players.iter().find(|(e, _, _, _)| *e != ai_entity))
-            .map(|(_, t, _, _)| t.translation.truncate());
+        // Find opponent position: our man in 2v2 (team blackboard), otherwise
+        // prefer human if present, otherwise any opponent
+        let opponent_pos = blackboard
+            .as_deref()
+            .filter(|_| holding.is_none())
+            .and_then(|b| b.mark(*team, ai_entity))
+            .and_then(|mark| all_players.get(mark).ok())
+            .or_else(|| {
+                all_players
+                    .iter()
+                    .find(|(_, _, _, human, t)| *t != team && human.is_some())
+            })
+            .or_else(|| all_players.iter().find(|(_, _, _, _, t)| *t != team))
+            .map(|(_, t, _, _, _)| t.translation.truncate());
 
         // Get the AI's own basket (the one they're defending)
         // AI defends the opposite basket from what they're targeting
@@ -173,7 +213,7 @@ pub fn ai_navigation_update(
         let intercept_pos =
             if let (Some(opp_pos), Some(own_basket)) = (opponent_pos, own_basket_pos) {
                 // Check if opponent is significantly elevated above floor
-                let floor_y = ARENA_FLOOR_Y + PLAYER_SIZE.y / 2.0;
+                let floor_y = arena.floor_y + PLAYER_SIZE.y / 2.0;
                 let opp_elevated = opp_pos.y > floor_y + PLAYER_SIZE.y * 2.0;
 
                 if opp_elevated {
@@ -185,7 +225,7 @@ pub fn ai_navigation_update(
                         opp_pos.x + profile.pressure_distance
                     };
                     Vec2::new(
-                        intercept_x.clamp(-ARENA_WIDTH / 2.0 + 100.0, ARENA_WIDTH / 2.0 - 100.0),
+                        intercept_x.clamp(-arena.width / 2.0 + 100.0, arena.width / 2.0 - 100.0),
                         opp_pos.y,
                     )
                 } else {
@@ -195,6 +235,7 @@ pub fn ai_navigation_update(
                         own_basket,
                         profile.pressure_distance,
                         profile.defensive_iq,
+                        &arena,
                     )
                 }
             } else if let Some(opp_pos) = opponent_pos {
@@ -202,30 +243,76 @@ pub fn ai_navigation_update(
                 opp_pos
             } else {
                 // Fallback defensive position (old logic)
-                let defensive_y = ARENA_FLOOR_Y + 50.0;
+                let defensive_y = arena.floor_y + 50.0;
                 match *team {
                     Team::Left => Vec2::new(-profile.defense_offset, defensive_y),
                     Team::Right => Vec2::new(profile.defense_offset, defensive_y),
                 }
             };
 
+        if ai_state.current_goal != AiGoal::GetOpen && ai_state.off_ball.is_some() {
+            ai_state.off_ball = None;
+        }
+
         // Determine navigation target based on goal
-        let desired_target: Option<Vec2> = match ai_state.current_goal {
+        let goal = ai_state.current_goal;
+        let desired_target: Option<Vec2> = match goal {
             AiGoal::Idle => None,
 
             AiGoal::ChaseBall => ball_pos,
 
+            // Off the ball in 2v2: cut, spot up or space away from the carrier
+            AiGoal::GetOpen => {
+                if let Some(basket_pos) = target_basket_pos {
+                    let carrier_pos = all_players
+                        .iter()
+                        .find(|(e, _, h, _, t)| *e != ai_entity && *t == team && h.is_some())
+                        .map(|(_, t, _, _, _)| t.translation.truncate())
+                        .or(ball_pos)
+                        .unwrap_or(ai_pos);
+                    let defenders: Vec<Vec2> = all_players
+                        .iter()
+                        .filter(|(_, _, _, _, t)| *t != team)
+                        .map(|(_, t, _, _, _)| t.translation.truncate())
+                        .collect();
+                    let ctx = OffBallContext {
+                        ai_pos,
+                        carrier_pos,
+                        basket_pos,
+                        defenders: &defenders,
+                        min_quality: profile.min_shot_quality,
+                        current: ai_state.off_ball.map(|spot| spot.pos),
+                    };
+                    let candidates =
+                        off_ball_spots.get_or_insert_with(|| off_ball_candidates(&nav_graph));
+                    let spot = choose_off_ball_spot(&ctx, candidates, |pos| {
+                        let heatmap = heatmaps.score_for_basket(target_basket.0, pos);
+                        evaluate_shot_quality(pos, basket_pos, 0.0)
+                            * (1.0 + HEATMAP_SCORE_WEIGHT_DEFAULT * level_score_weight * heatmap)
+                    });
+                    ai_state.off_ball = spot;
+                    spot.map(|spot| spot.pos)
+                } else {
+                    None
+                }
+            }
+
             AiGoal::AttackWithBall => {
                 // Navigate to a position within shooting range of basket
                 // Pass min_shot_quality to avoid navigating to positions where shots are low quality
                 // (e.g., directly under the basket)
                 if let Some(basket_pos) = target_basket_pos {
+                    let shoot_range = profile.shoot_range_for(
+                        basket_pos.y - arena.floor_top(),
+                        shot_config.hard_cap,
+                        tweaks.ball_gravity,
+                    );
                     // Try to find a shooting position that meets quality threshold
                     if let Some(path_result) = find_path_to_shoot(
                         &nav_graph,
                         ai_pos,
                         basket_pos,
-                        profile.shoot_range,
+                        shoot_range,
                         profile.min_shot_quality,
                     ) {
                         Some(nav_graph.nodes[path_result.goal_node].center)
@@ -348,7 +435,7 @@ pub fn ai_navigation_update(
 
                 if needs_navigation {
                     if let Some(path_result) = find_path(&nav_graph, ai_pos, target) {
-                        nav_state.set_path(path_result.actions, target);
+                        nav_state.set_path(path_result.actions, path_result.edges, target);
                     } else {
                         // No path found - clear and let simple movement take over
                         nav_state.clear();
@@ -387,8 +474,13 @@ pub fn ai_decision_update(
             &TargetBasket,
             Option<&HoldingBall>,
             &Grounded,
+            &Velocity,
+        ),
+        (
+            With<Player>,
+            Without<HumanControlled>,
+            Without<super::PolicyControlled>,
         ),
-        (With<Player>, Without<HumanControlled>),
     >,
     all_players: Query<
         (
@@ -396,11 +488,38 @@ pub fn ai_decision_update(
             &Transform,
             Option<&HoldingBall>,
             Option<&HumanControlled>,
+            &Team,
         ),
         With<Player>,
     >,
     ball_query: Query<(&Transform, &BallState), With<Ball>>,
     basket_query: Query<(&Transform, &Basket)>,
+    momentum: Option<Res<Momentum>>,
+    mut nav_edge_stats: Option<ResMut<NavEdgeStats>>,
+    loose_ball: Option<Res<LooseBall>>,
+    (
+        score,
+        match_clock,
+        ball_reset,
+        ball_config,
+        steal_config,
+        shot_config,
+        tweaks,
+        contested,
+        shot_clock,
+        blackboard,
+    ): (
+        Res<Score>,
+        Option<Res<MatchClock>>,
+        Option<Res<BallReset>>,
+        Res<BallConfig>,
+        Res<StealConfig>,
+        Res<ShotConfig>,
+        Res<PhysicsTweaks>,
+        Option<Res<ContestedQuality>>,
+        Option<Res<ShotClock>>,
+        Option<Res<TeamBlackboard>>,
+    ),
 ) {
     let level_settings = level_db
         .get_by_id(&current_level.0)
@@ -414,6 +533,13 @@ pub fn ai_decision_update(
     let los_margin = level_settings
         .map(|level| level.heatmap_los_margin)
         .unwrap_or(HEATMAP_LOS_MARGIN_DEFAULT);
+    let arena = level_settings
+        .map(|level| level.arena())
+        .unwrap_or_default();
+    // A steal just knocked the ball loose: chase it harder than a normal free ball
+    let scrambling = loose_ball.as_deref().is_some_and(LooseBall::is_scrambling);
+    // Team inbounding from its baseline after conceding (the other side sets up)
+    let inbounding = ball_reset.as_deref().and_then(BallReset::inbounding);
 
     for (
         ai_entity,
@@ -425,13 +551,22 @@ pub fn ai_decision_update(
         target_basket,
         holding,
         grounded,
+        velocity,
     ) in &mut ai_query
     {
+        let player_id = match team {
+            Team::Left => PlayerId::L,
+            Team::Right => PlayerId::R,
+        };
+        // Scooping is a human assist; don't keep a held button from before a control swap
+        input.pickup_held = false;
+
         // Idle goal: do nothing, skip all AI logic
         if ai_state.current_goal == AiGoal::Idle {
             input.move_x = 0.0;
             input.jump_held = false;
             input.pickup_pressed = false;
+            input.pass_pressed = false;
             input.throw_held = false;
             input.throw_released = false;
             continue;
@@ -441,6 +576,24 @@ pub fn ai_decision_update(
         let profile = profile_db
             .get_by_id(&ai_state.profile_id)
             .unwrap_or_else(|| profile_db.default_profile());
+        // Defend tighter and more aggressively against an opponent on a hot streak
+        let momentum_profile = momentum
+            .as_deref()
+            .and_then(|m| m.adjust_profile(*team, profile));
+        let profile = momentum_profile.as_ref().unwrap_or(profile);
+        // Gamble when trailing late, protect a late lead
+        let mut situation = GameSituation::evaluate(*team, &score, match_clock.as_deref());
+        situation.shot_clock = shot_clock.as_deref().and_then(|c| c.remaining_for(*team));
+        if situation.mode != ai_state.risk_mode {
+            ai_state.risk_mode = situation.mode;
+            event_bus.emit(GameEvent::AiRisk {
+                player: player_id,
+                mode: intern(situation.mode.as_str()),
+                diff: situation.diff,
+            });
+        }
+        let risk_profile = situation.adjust_profile(profile);
+        let profile = risk_profile.as_ref().unwrap_or(profile);
 
         // Decrement button press cooldown (simulates human mashing speed limit)
         // Use a minimum dt of 1/60 to handle headless mode where delta can be tiny
@@ -469,17 +622,40 @@ pub fn ai_decision_update(
             ai_state.ball_hold_time = 0.0;
         }
 
-        // Check if opponent (any other player) has ball
+        // Check if opponent (anyone on the other side) has ball
         let opponent_has_ball = all_players
             .iter()
-            .filter(|(e, _, _, _)| *e != ai_entity)
-            .any(|(_, _, h, _)| h.is_some());
+            .filter(|(_, _, _, _, t)| *t != team)
+            .any(|(_, _, h, _, _)| h.is_some());
+
+        // 2v2 defense: our man from the team blackboard and whether he has the ball
+        let mark = blackboard
+            .as_deref()
+            .filter(|_| !ai_has_ball)
+            .and_then(|b| b.mark(*team, ai_entity))
+            .and_then(|mark| all_players.get(mark).ok())
+            .map(|(_, t, h, _, _)| (t.translation.truncate(), h.is_some()));
+
+        // Find opponent position (for defense/steal decisions): our man, otherwise
+        // the ball carrier, otherwise the nearest opponent
+        let opponent_pos = mark.map(|(pos, _)| pos).or_else(|| {
+            all_players
+                .iter()
+                .filter(|(_, _, _, _, t)| *t != team)
+                .map(|(_, t, h, _, _)| (h.is_some(), t.translation.truncate()))
+                .min_by(|(a_holds, a), (b_holds, b)| {
+                    b_holds
+                        .cmp(a_holds)
+                        .then(ai_pos.distance(*a).total_cmp(&ai_pos.distance(*b)))
+                })
+                .map(|(_, pos)| pos)
+        });
 
-        // Find opponent position (for defense/steal decisions)
-        let opponent_pos = all_players
+        // Teammate position and whether they hold the ball (2v2 only)
+        let teammate = all_players
             .iter()
-            .find(|(e, _, _, _)| *e != ai_entity)
-            .map(|(_, t, _, _)| t.translation.truncate());
+            .find(|(e, _, _, _, t)| *e != ai_entity && *t == team)
+            .map(|(_, t, h, _, _)| (t.translation.truncate(), h.is_some()));
 
         // Determine the target basket position based on team
         let target_basket_type = target_basket.0;
@@ -503,10 +679,11 @@ pub fn ai_decision_update(
                 own_basket_pos,
                 profile.pressure_distance,
                 profile.defensive_iq,
+                &arena,
             )
         } else {
             // Fallback defensive position
-            let defensive_y = ARENA_FLOOR_Y + 50.0;
+            let defensive_y = arena.floor_y + 50.0;
             match *team {
                 Team::Left => Vec2::new(-profile.defense_offset, defensive_y),
                 Team::Right => Vec2::new(profile.defense_offset, defensive_y),
@@ -517,9 +694,9 @@ pub fn ai_decision_update(
         let new_goal = if ai_has_ball {
             // Check if AI is in "front court" (front 1/3 of arena, close to target basket)
             // Front court = near target basket, where shots are too close/easy to block
-            // If targeting right basket (x > 0): front court = right 1/3 (x > ARENA_WIDTH/6)
-            // If targeting left basket (x < 0): front court = left 1/3 (x < -ARENA_WIDTH/6)
-            let front_court_threshold = ARENA_WIDTH / 6.0;
+            // If targeting right basket (x > 0): front court = right 1/3 (x > width/6)
+            // If targeting left basket (x < 0): front court = left 1/3 (x < -width/6)
+            let front_court_threshold = arena.width / 6.0;
             let in_front_court = if target_basket_pos.x > 0.0 {
                 ai_pos.x > front_court_threshold // Right 1/3 when targeting right
             } else {
@@ -532,7 +709,14 @@ pub fn ai_decision_update(
 
             // Force shot after holding ball for 2+ seconds (prevents stalling)
             // Reduced from 3s to increase shooting activity
-            if ai_state.ball_hold_time > 2.0 {
+            // (longer when protecting a late lead - running the clock is the point)
+            let max_hold_time = situation.max_hold_time(profile);
+            // Hold time on the neutral 2s scale, for the desperation ramp below
+            let hold_progress = ai_state.ball_hold_time * BASE_MAX_HOLD_TIME / max_hold_time;
+            // Shot clock running out: get something up rather than turn it over
+            if ai_state.ball_hold_time > max_hold_time
+                || situation.beat_shot_clock(profile.charge_max)
+            {
                 AiGoal::ChargeShot
             } else {
                 let horizontal_distance = (ai_pos.x - target_basket_pos.x).abs();
@@ -549,13 +733,27 @@ pub fn ai_decision_update(
 
                 // Evaluate shot quality based on position (heatmap-derived)
                 // Apply front-court penalty to discourage close-range shots
-                let base_quality = (evaluate_shot_quality(ai_pos, target_basket_pos)
+                // Charging on the move costs accuracy, so wait to land / slow down
+                let unsteady = if is_unsteady(velocity.0, grounded.0, &shot_config) {
+                    1.0
+                } else {
+                    0.0
+                };
+                let base_quality = (evaluate_shot_quality(ai_pos, target_basket_pos, unsteady)
                     - front_court_quality_penalty)
                     .clamp(0.0, 1.0);
                 let score_heatmap = heatmaps.score_for_basket(target_basket_type, ai_pos);
                 let heatmap_multiplier =
                     1.0 + (HEATMAP_SCORE_WEIGHT_DEFAULT * level_score_weight * score_heatmap);
-                let shot_quality = (base_quality * heatmap_multiplier).clamp(0.0, 1.0);
+                // A defender between us and the basket costs what the shot test measured
+                let contest_factor = match (contested.as_deref(), opponent_pos, level_settings) {
+                    (Some(contested), Some(opp_pos), Some(level)) => {
+                        contested.factor(&level.name, ai_pos, target_basket_pos, opp_pos)
+                    }
+                    _ => 1.0,
+                };
+                let shot_quality =
+                    (base_quality * heatmap_multiplier * contest_factor).clamp(0.0, 1.0);
 
                 let los_value = heatmaps.line_of_sight_for_basket(target_basket_type, ai_pos);
                 let los_ok = los_value + los_margin >= los_threshold;
@@ -570,8 +768,8 @@ pub fn ai_decision_update(
                 // Desperation factor: after 1 second holding ball, gradually lower threshold
                 // Ramps from 1.0 at 1s to 0.5 at 2s (when force shot kicks in)
                 // This encourages shooting sooner rather than waiting for perfect position
-                let desperation_factor = if ai_state.ball_hold_time > 1.0 {
-                    1.0 - ((ai_state.ball_hold_time - 1.0) * 0.5).min(0.5)
+                let desperation_factor = if hold_progress > 1.0 {
+                    1.0 - ((hold_progress - 1.0) * 0.5).min(0.5)
                 } else {
                     1.0
                 };
@@ -586,17 +784,22 @@ pub fn ai_decision_update(
                 let nav_complete = nav_state.path_complete() || !nav_state.active;
 
                 // Position-based conditions to consider shooting
-                let in_shoot_range = effective_distance < profile.shoot_range;
+                let shoot_range = profile.shoot_range_for(
+                    target_basket_pos.y - nav_graph.level_geometry.arena.floor_top(),
+                    shot_config.hard_cap,
+                    tweaks.ball_gravity,
+                );
+                let in_shoot_range = effective_distance < shoot_range;
                 let reached_target = at_nav_target && nav_complete;
 
                 // Safety check: don't START charging if opponent is very close (steal risk)
                 // But if already charging, COMMIT to the shot - aborting leads to steals anyway
-                // Use actual STEAL_RANGE constant * 1.5 (90px) rather than profile.steal_range
+                // Use the game's actual steal range * 1.5 (90px) rather than profile.steal_range
                 // which can be much larger and block shooting opportunities unnecessarily
                 let already_charging = ai_state.current_goal == AiGoal::ChargeShot;
                 let opponent_too_close = !already_charging
                     && opponent_pos
-                        .map(|opp| ai_pos.distance(opp) < STEAL_RANGE * 1.5)
+                        .map(|opp| ai_pos.distance(opp) < steal_config.range * 1.5)
                         .unwrap_or(false);
 
                 // Calculate utility of seeking a better position vs shooting now
@@ -627,14 +830,14 @@ pub fn ai_decision_update(
                             // Tournament data shows elevated shots (Y > -300) have 20-35% success
                             // vs floor shots (Y < -350) at only 3-10% success
                             let height_above_floor =
-                                (best_node.top_y - ARENA_FLOOR_Y - 50.0).max(0.0);
+                                (best_node.top_y - arena.floor_y - 50.0).max(0.0);
                             let height_bonus = (height_above_floor / 200.0).min(0.5) * 0.6; // Up to +0.30
 
                             // 4. Floor penalty - urgently seek height if currently at floor level
                             // This helps even low-patience profiles climb before shooting
                             // Increased from 0.15 to 0.25 based on tournament data showing
                             // floor shots have 9-13% success vs elevated shots at 20-35%
-                            let current_height = ai_pos.y - ARENA_FLOOR_Y;
+                            let current_height = ai_pos.y - arena.floor_y;
                             let at_floor_level = current_height < 100.0;
                             let floor_urgency = if at_floor_level { 0.25 } else { 0.0 };
 
@@ -684,7 +887,7 @@ pub fn ai_decision_update(
                             los_threshold,
                             los_ok,
                             effective_distance,
-                            profile.shoot_range,
+                            shoot_range,
                             in_shoot_range,
                             opponent_pos.map(|o| ai_pos.distance(o)).unwrap_or(999.0),
                             opponent_too_close,
@@ -694,14 +897,18 @@ pub fn ai_decision_update(
                     AiGoal::AttackWithBall
                 }
             } // End of else block for forced shot after 12s
+        } else if opponent_has_ball && mark.is_some_and(|(_, has_ball)| !has_ball) {
+            // Our man is off the ball: deny him (stay between him and our basket),
+            // the teammate on the carrier plays the ball
+            AiGoal::InterceptDefense
         } else if opponent_has_ball {
             // Update steal proximity tracking BEFORE goal decision
             // This ensures timer persists across goal switches
-            // IMPORTANT: Use the game's STEAL_RANGE constant (60px), not profile.steal_range
-            // The profile.steal_range is for goal selection, but actual steals require STEAL_RANGE
+            // IMPORTANT: Use the game's steal range (StealConfig, 60px), not profile.steal_range
+            // The profile.steal_range is for goal selection, but actual steals require StealConfig::range
             if let Some(opp_pos) = opponent_pos {
                 let distance = ai_pos.distance(opp_pos);
-                let in_steal_range = distance < STEAL_RANGE;
+                let in_steal_range = distance < steal_config.range;
 
                 if in_steal_range {
                     if !ai_state.was_in_steal_range {
@@ -756,11 +963,44 @@ pub fn ai_decision_update(
             } else {
                 AiGoal::InterceptDefense
             }
+        } else if matches!(ball_state, BallState::Passed { target, .. } if *target == ai_entity) {
+            // A pass is on its way: go meet it
+            AiGoal::ChaseBall
+        } else if teammate.is_some_and(|(_, holds)| holds)
+            || matches!(ball_state, BallState::Passed { passer, .. } if *passer == ai_entity)
+        {
+            AiGoal::GetOpen
+        } else if teammate
+            .is_some_and(|(pos, _)| pos.distance(ball_pos) < ai_pos.distance(ball_pos))
+        {
+            // Ball is free but the teammate is closer: let them chase, cover the basket
+            AiGoal::InterceptDefense
         } else {
             // Ball is free
             AiGoal::ChaseBall
         };
 
+        // 2v2: hand the ball to a teammate with a clearly better look
+        let may_pass = new_goal == AiGoal::AttackWithBall
+            && ai_state.ball_hold_time > AI_PASS_MIN_HOLD_TIME
+            && ai_state.button_press_cooldown <= 0.0;
+        if let Some((teammate_pos, _)) = teammate.filter(|_| may_pass) {
+            let look = |pos: Vec2| {
+                let heatmap = heatmaps.score_for_basket(target_basket_type, pos);
+                evaluate_shot_quality(pos, target_basket_pos, 0.0)
+                    * (1.0 + HEATMAP_SCORE_WEIGHT_DEFAULT * level_score_weight * heatmap)
+            };
+            // Nobody from the other side close enough to steal it on arrival
+            let open_distance = steal_config.range * 1.5;
+            let teammate_open = !all_players.iter().any(|(_, t, _, _, other)| {
+                other != team && t.translation.truncate().distance(teammate_pos) < open_distance
+            });
+            if teammate_open && look(teammate_pos) >= look(ai_pos) + AI_PASS_QUALITY_MARGIN {
+                input.pass_pressed = true;
+                ai_state.button_press_cooldown = 1.0 / profile.button_presses_per_sec;
+            }
+        }
+
         // Update goal (and randomize charge target when starting to charge)
         if new_goal != ai_state.current_goal {
             // Track defensive mode switches for hysteresis
@@ -813,7 +1053,22 @@ pub fn ai_decision_update(
 
         if nav_controlling {
             // Execute navigation actions
-            execute_nav_action(&mut input, &mut nav_state, ai_pos, grounded.0, &time);
+            if let Some(outcome) = execute_nav_action(
+                &mut input,
+                &mut nav_state,
+                &nav_graph,
+                ai_pos,
+                grounded.0,
+                &time,
+            ) {
+                report_edge_outcome(
+                    &mut event_bus,
+                    nav_edge_stats.as_deref_mut(),
+                    &nav_graph,
+                    player_id,
+                    outcome,
+                );
+            }
             // Auto-clear navigation when path completes
             nav_state.update_completion();
         } else {
@@ -825,15 +1080,25 @@ pub fn ai_decision_update(
                 }
 
                 AiGoal::ChaseBall => {
-                    // Move toward ball
+                    // Move toward ball (tighter during a scramble - every pixel counts)
                     let dx = ball_pos.x - ai_pos.x;
-                    if dx.abs() > profile.position_tolerance {
+                    let tolerance = if scrambling {
+                        profile.position_tolerance * 0.5
+                    } else {
+                        profile.position_tolerance
+                    };
+                    if dx.abs() > tolerance {
                         input.move_x = dx.signum();
                     }
 
                     // Jump if ball is above us and we're close horizontally
+                    // (go up for a popped ball from further out)
                     let dy = ball_pos.y - ai_pos.y;
-                    if dy > PLAYER_SIZE.y && dx.abs() < BALL_PICKUP_RADIUS * 2.0 && grounded.0 {
+                    let jump_reach = if scrambling { 3.0 } else { 2.0 };
+                    if dy > PLAYER_SIZE.y
+                        && dx.abs() < ball_config.pickup_radius * jump_reach
+                        && grounded.0
+                    {
                         input.jump_buffer_timer = JUMP_BUFFER_TIME;
                         input.jump_held = true;
                     }
@@ -845,20 +1110,31 @@ pub fn ai_decision_update(
 
                     // Try to pick up ball when close (respecting button cooldown)
                     let distance_to_ball = ai_pos.distance(ball_pos);
-                    if distance_to_ball < BALL_PICKUP_RADIUS
+                    if distance_to_ball < ball_config.pickup_radius
                         && matches!(ball_state, BallState::Free)
                         && ai_state.button_press_cooldown <= 0.0
                     {
                         input.pickup_pressed = true;
-                        ai_state.button_press_cooldown = 1.0 / profile.button_presses_per_sec;
+                        // Mash faster in a scramble
+                        let press_rate = if scrambling {
+                            profile.button_presses_per_sec * LOOSE_BALL_PRESS_RATE_SCALE
+                        } else {
+                            profile.button_presses_per_sec
+                        };
+                        ai_state.button_press_cooldown = 1.0 / press_rate;
                     }
 
                     input.throw_held = false;
                 }
 
-                AiGoal::AttackWithBall => {
-                    // Move toward target basket (simple horizontal movement)
-                    let dx = target_basket_pos.x - ai_pos.x;
+                AiGoal::AttackWithBall | AiGoal::GetOpen => {
+                    // Move toward target basket (simple horizontal movement),
+                    // or the off-ball spot on our level
+                    let target_x = ai_state
+                        .off_ball
+                        .filter(|_| ai_state.current_goal == AiGoal::GetOpen)
+                        .map_or(target_basket_pos.x, |spot| spot.pos.x);
+                    let dx = target_x - ai_pos.x;
                     if dx.abs() > profile.position_tolerance {
                         input.move_x = dx.signum();
                     }
@@ -870,14 +1146,29 @@ pub fn ai_decision_update(
                 AiGoal::ChargeShot => {
                     input.pickup_pressed = false;
 
-                    // Check if we should do a jump shot (basket is above us)
+                    // Jump shot when the basket is above us: plan the charge from
+                    // the height the ball will actually leave at, not from the ground
                     let height_to_basket = target_basket_pos.y - ai_pos.y;
-                    let should_jump_shot = height_to_basket > PLAYER_SIZE.y;
-
-                    if should_jump_shot && grounded.0 && !ai_state.jump_shot_active {
+                    let jump_plan = (height_to_basket > PLAYER_SIZE.y
+                        && grounded.0
+                        && !ai_state.jump_shot_active)
+                        .then(|| {
+                            capabilities.plan_jump_shot(
+                                &tweaks,
+                                ai_pos,
+                                target_basket_pos,
+                                (profile.charge_min, profile.charge_max),
+                                tweaks.shot_air_variance_penalty + shot_config.unsteady_variance,
+                            )
+                        })
+                        .flatten();
+
+                    if let Some(plan) = jump_plan {
                         // Start jump shot sequence - jump first
                         ai_state.jump_shot_active = true;
                         ai_state.jump_shot_timer = 0.0;
+                        ai_state.jump_shot_hold = plan.hold;
+                        ai_state.shot_charge_target = plan.charge;
                         input.jump_buffer_timer = JUMP_BUFFER_TIME;
                         input.jump_held = true;
                         // Move toward basket while jumping
@@ -889,20 +1180,14 @@ pub fn ai_decision_update(
                         // Jump shot in progress
                         ai_state.jump_shot_timer += dt;
 
-                        // Hold jump for height (same as player capability)
-                        if ai_state.jump_shot_timer < 0.25 {
-                            input.jump_held = true;
-                        } else {
-                            input.jump_held = false;
-                        }
+                        // Hold jump as planned (up to the release or the apex)
+                        input.jump_held = ai_state.jump_shot_timer < ai_state.jump_shot_hold;
 
-                        // Start charging after initial jump (around peak)
-                        if ai_state.jump_shot_timer > 0.1 {
+                        // Start charging after takeoff; the charge was planned
+                        // for the release height
+                        if ai_state.jump_shot_timer > JUMP_SHOT_CHARGE_DELAY {
                             if !input.throw_held && !input.throw_released {
                                 input.throw_held = true;
-                                ai_state.shot_charge_target = rand::thread_rng()
-                                    .gen_range(profile.charge_min..profile.charge_max);
-                                // No cap - AI uses full charge range like player
                             } else if input.throw_held {
                                 ai_state.shot_charge_target -= dt;
                                 if ai_state.shot_charge_target <= 0.0 {
@@ -958,13 +1243,22 @@ pub fn ai_decision_update(
 
                         // If opponent is elevated and we have a nav path, follow it
                         if height_diff > PLAYER_SIZE.y * 1.5 && nav_state.active {
-                            execute_nav_action(
+                            if let Some(outcome) = execute_nav_action(
                                 &mut input,
                                 &mut nav_state,
+                                &nav_graph,
                                 ai_pos,
                                 grounded.0,
                                 &time,
-                            );
+                            ) {
+                                report_edge_outcome(
+                                    &mut event_bus,
+                                    nav_edge_stats.as_deref_mut(),
+                                    &nav_graph,
+                                    player_id,
+                                    outcome,
+                                );
+                            }
                             nav_state.update_completion();
                         } else {
                             // Direct pursuit logic for reachable opponents
@@ -1036,7 +1330,7 @@ pub fn ai_decision_update(
                         let same_height_level = height_diff.abs() < PLAYER_SIZE.y * 1.5;
 
                         // Check if opponent is significantly elevated (needs climbing)
-                        let floor_y = ARENA_FLOOR_Y + PLAYER_SIZE.y;
+                        let floor_y = arena.floor_y + PLAYER_SIZE.y;
                         let ai_near_floor = ai_pos.y < floor_y + PLAYER_SIZE.y;
                         let opponent_highly_elevated = height_diff > NAV_MAX_JUMP_HEIGHT * 0.5;
 
@@ -1052,7 +1346,7 @@ pub fn ai_decision_update(
                         if ai_near_floor && opponent_highly_elevated {
                             // Opponent is high above and we're at ground level
                             // Move toward the ramp on the OPPONENT'S side to climb and intercept
-                            let arena_edge = ARENA_WIDTH / 2.0 - WALL_THICKNESS - 100.0;
+                            let arena_edge = arena.width / 2.0 - WALL_THICKNESS - 100.0;
                             let ramp_target_x = if opp_pos.x > 0.0 {
                                 arena_edge // Opponent on right side, use right ramp
                             } else {
@@ -1067,7 +1361,7 @@ pub fn ai_decision_update(
                             // Jump continuously when on the ramp area to climb steps
                             // (ramps are designed for climbing, ceiling check not needed)
                             let on_ramp_area =
-                                ai_pos.x.abs() > ARENA_WIDTH / 2.0 - WALL_THICKNESS - 300.0;
+                                ai_pos.x.abs() > arena.width / 2.0 - WALL_THICKNESS - 300.0;
                             if on_ramp_area && grounded.0 {
                                 input.jump_buffer_timer = JUMP_BUFFER_TIME;
                                 input.jump_held = true;
@@ -1220,9 +1514,56 @@ pub fn ai_decision_update(
             }
         }
 
+        // Beaten on a carry-in: get under our own basket and jump to cover the
+        // mouth - a defender on the rim when the carrier arrives forces a fumble
+        let carry_in_coming = opponent_has_ball
+            && opponent_pos.is_some_and(|opp_pos| carry_in_threat(opp_pos, ai_pos, own_basket_pos));
+        if carry_in_coming {
+            let block_spot = rim_block_spot(own_basket_pos);
+            let dx = block_spot.x - ai_pos.x;
+            input.move_x = if dx.abs() > profile.position_tolerance {
+                dx.signum()
+            } else {
+                0.0
+            };
+            let below_rim = ai_pos.y < block_spot.y;
+            if below_rim && dx.abs() < BASKET_SIZE.x && grounded.0 {
+                input.jump_buffer_timer = JUMP_BUFFER_TIME;
+                input.jump_held = true;
+            }
+            if below_rim && !grounded.0 {
+                input.jump_held = true;
+            }
+        }
+
+        // Opponent inbounding after our goal: drop back and set up the defense
+        // instead of pressing the inbounder on their own baseline
+        if opponent_has_ball && inbounding.is_some_and(|t| t != *team) {
+            let dx = inbound_setup_x(own_basket_pos.x) - ai_pos.x;
+            input.move_x = if dx.abs() > profile.position_tolerance {
+                dx.signum()
+            } else {
+                0.0
+            };
+            input.pickup_pressed = false;
+        }
+
+        // Don't camp in our own basket's mouth: after a while on the rim with no
+        // carry-in coming, step back out before it counts as rim camping. The
+        // timer only clears once we're well away, so we don't drift straight back.
+        if covers_rim(ai_pos, PLAYER_SIZE, own_basket_pos, BASKET_SIZE) && holding.is_none() {
+            ai_state.rim_time += dt;
+        } else if (ai_pos.x - own_basket_pos.x).abs() > RIM_CAMPING_AI_CLEAR_DISTANCE {
+            ai_state.rim_time = 0.0;
+        }
+        if ai_state.rim_time >= RIM_CAMPING_AI_LEAVE_SECS && !carry_in_coming {
+            input.move_x = if own_basket_pos.x > 0.0 { -1.0 } else { 1.0 };
+            input.jump_held = false;
+        }
+
         // Always allow pickup when near a free ball (respecting button cooldown)
         let distance_to_ball = ai_pos.distance(ball_pos);
-        if distance_to_ball < BALL_PICKUP_RADIUS
+        if distance_to_ball < ball_config.pickup_radius
             && matches!(ball_state, BallState::Free)
             && ai_state.button_press_cooldown <= 0.0
         {
@@ -1296,10 +1637,6 @@ pub fn ai_decision_update(
         input.jump_buffer_timer = (input.jump_buffer_timer - dt).max(0.0);
 
         // Emit ControllerInput event for auditability
-        let player_id = match team {
-            Team::Left => PlayerId::L,
-            Team::Right => PlayerId::R,
-        };
         event_bus.emit(GameEvent::ControllerInput {
             player: player_id,
             source: ControllerSource::Ai,
@@ -1313,20 +1650,23 @@ pub fn ai_decision_update(
     }
 }
 
-/// Execute the current navigation action
+/// Execute the current navigation action.
+/// Returns (from, to, outcome) when an edge traversal finishes or times out.
 fn execute_nav_action(
     input: &mut InputState,
     nav_state: &mut AiNavState,
+    nav_graph: &NavGraph,
     ai_pos: Vec2,
     grounded: bool,
     time: &Time,
-) {
+) -> Option<(usize, usize, EdgeOutcome)> {
     // Use minimum dt for headless mode compatibility
     let dt = time.delta_secs().max(1.0 / 60.0);
 
-    let Some(action) = nav_state.current_action().cloned() else {
-        return;
-    };
+    let action = nav_state.current_action().cloned()?;
+    let action_index = nav_state.path_index;
+    let edge = nav_state.current_edge();
+    nav_state.action_timer += dt;
 
     match action {
         NavAction::WalkTo { x } => {
@@ -1418,7 +1758,61 @@ fn execute_nav_action(
                 nav_state.jump_timer += dt;
             }
         }
+
+        NavAction::EnterPortal { x, land } => {
+            // Teleport happens on contact; done once we're standing near the exit landing
+            let near_land = (ai_pos.x - land.x).abs() <= PORTAL_SIZE.x
+                && (ai_pos.y - PLAYER_SIZE.y / 2.0 - land.y).abs() <= PLAYER_SIZE.y / 2.0;
+            if near_land && grounded {
+                nav_state.advance();
+            } else if !near_land {
+                input.move_x = (x - ai_pos.x).signum();
+            }
+        }
+
+        NavAction::LaunchAt { x } => {
+            if !nav_state.action_started {
+                // Walk onto the pad until it launches us
+                if grounded {
+                    input.move_x = (x - ai_pos.x).signum();
+                } else {
+                    nav_state.action_started = true;
+                    nav_state.jump_timer = 0.0;
+                }
+            } else {
+                nav_state.jump_timer += dt;
+
+                // Steer toward the landing point while airborne
+                if let Some(NavAction::WalkTo { x: land_x }) =
+                    nav_state.current_path.get(nav_state.path_index + 1)
+                {
+                    input.move_x = (land_x - ai_pos.x).signum();
+                }
+
+                if grounded && nav_state.jump_timer > 0.1 {
+                    nav_state.advance();
+                }
+            }
+        }
+    }
+
+    let (from, to) = edge?;
+    if nav_state.path_index != action_index {
+        // Traversal finished - did we land where the edge goes?
+        let landed = nav_graph.find_node_at(ai_pos, NAV_POSITION_TOLERANCE);
+        let outcome = if landed == Some(to) {
+            EdgeOutcome::Reached
+        } else {
+            EdgeOutcome::Fell
+        };
+        return Some((from, to, outcome));
+    }
+    if nav_state.action_timer > NAV_EDGE_TIMEOUT {
+        // Stuck on this edge - drop the path so it gets replanned
+        nav_state.clear();
+        return Some((from, to, EdgeOutcome::TimedOut));
     }
+    None
 }
 
 #[cfg(test)]
@@ -0,0 +1,292 @@
+//! Team defense - who guards whom in 2v2, and when to switch
+//!
+//! Without matchups both AI defenders went after the ball carrier and left the
+//! other attacker alone. `TeamBlackboard` holds each side's matchups (defender
+//! -> attacker) where both of that side's AIs read them: the one on the carrier
+//! plays the ball as before, the other denies its man (stays between him and
+//! the basket). Matchups change on three rules:
+//!
+//! - **Screen**: the attackers come within AI_SCREEN_DISTANCE of each other and
+//!   each defender is now closer to the other one - switch instead of fighting
+//!   through the screen
+//! - **Cross**: the attackers trade sides of the floor and switching shortens
+//!   the total distance to our men
+//! - **Help**: the carrier gets AI_HELP_BEATEN_DISTANCE closer to our basket
+//!   than its defender while the other defender is still between him and the
+//!   basket - the helper picks up the ball, the beaten defender rotates to the
+//!   helper's man
+//!
+//! After a change the matchups hold for AI_SWITCH_COOLDOWN so the pair doesn't
+//! flip back and forth. Every change is logged as a `DefenseAssignment` event
+//! (reason `match`, `screen`, `cross` or `help`) for replays and analysis.
+
+use bevy::prelude::*;
+
+use crate::constants::*;
+use crate::events::{EventBus, GameEvent, intern};
+use crate::player::{HoldingBall, Player, Team};
+use crate::scoring::defending_team;
+use crate::world::Basket;
+
+/// Why a side's matchups changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwitchReason {
+    /// Fresh matchups (match start, respawn): nearest pairing
+    Match,
+    Screen,
+    Cross,
+    Help,
+}
+
+impl SwitchReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Match => "match",
+            Self::Screen => "screen",
+            Self::Cross => "cross",
+            Self::Help => "help",
+        }
+    }
+}
+
+/// A player as the blackboard sees it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CourtPlayer {
+    pub entity: Entity,
+    pub pos: Vec2,
+    pub has_ball: bool,
+}
+
+/// One side's defensive matchups
+#[derive(Debug, Clone, Default)]
+pub struct SideDefense {
+    /// (defender, attacker) pairs
+    pub marks: Vec<(Entity, Entity)>,
+    /// Whether the first attacker was left of the second last update
+    attacker_order: Option<bool>,
+    /// Seconds before matchups may change again
+    cooldown: f32,
+}
+
+impl SideDefense {
+    /// Attacker guarded by `defender`
+    pub fn mark(&self, defender: Entity) -> Option<Entity> {
+        self.marks
+            .iter()
+            .find(|(d, _)| *d == defender)
+            .map(|(_, a)| *a)
+    }
+
+    /// Update the matchups from this frame's positions. Only two-on-two is
+    /// tracked (anything else clears them). `attackers` must come in a stable
+    /// order. Returns the reason when the matchups changed.
+    pub fn update(
+        &mut self,
+        defenders: &[CourtPlayer],
+        attackers: &[CourtPlayer],
+        own_basket: Vec2,
+        dt: f32,
+    ) -> Option<SwitchReason> {
+        let ([d0, d1], [a0, a1]) = (defenders, attackers) else {
+            self.marks.clear();
+            self.attacker_order = None;
+            return None;
+        };
+        self.cooldown = (self.cooldown - dt).max(0.0);
+        let order = a0.pos.x < a1.pos.x;
+        let crossed = self
+            .attacker_order
+            .replace(order)
+            .is_some_and(|last| last != order);
+
+        // Ordered pairs, or None when the matchups don't fit these players
+        let find = |players: &[CourtPlayer], entity: Entity| {
+            players.iter().copied().find(|p| p.entity == entity)
+        };
+        let pairs = match self.marks.as_slice() {
+            [(da, aa), (db, ab)] if aa != ab => find(defenders, *da)
+                .zip(find(attackers, *aa))
+                .zip(find(defenders, *db).zip(find(attackers, *ab))),
+            _ => None,
+        };
+        let Some(((da, aa), (db, ab))) = pairs else {
+            let straight = d0.pos.distance(a0.pos) + d1.pos.distance(a1.pos);
+            let across = d0.pos.distance(a1.pos) + d1.pos.distance(a0.pos);
+            self.marks = if straight <= across {
+                vec![(d0.entity, a0.entity), (d1.entity, a1.entity)]
+            } else {
+                vec![(d0.entity, a1.entity), (d1.entity, a0.entity)]
+            };
+            self.cooldown = AI_SWITCH_COOLDOWN;
+            return Some(SwitchReason::Match);
+        };
+        if self.cooldown > 0.0 {
+            return None;
+        }
+
+        let to_basket = |p: &CourtPlayer| (p.pos.x - own_basket.x).abs();
+        let beaten = |on_ball: &CourtPlayer, carrier: &CourtPlayer, helper: &CourtPlayer| {
+            carrier.has_ball
+                && to_basket(carrier) + AI_HELP_BEATEN_DISTANCE < to_basket(on_ball)
+                && to_basket(helper) < to_basket(carrier)
+        };
+        let current = da.pos.distance(aa.pos) + db.pos.distance(ab.pos);
+        let swapped = da.pos.distance(ab.pos) + db.pos.distance(aa.pos);
+        let reason = if beaten(&da, &aa, &db) || beaten(&db, &ab, &da) {
+            SwitchReason::Help
+        } else if aa.pos.distance(ab.pos) < AI_SCREEN_DISTANCE
+            && da.pos.distance(ab.pos) < da.pos.distance(aa.pos)
+            && db.pos.distance(aa.pos) < db.pos.distance(ab.pos)
+        {
+            SwitchReason::Screen
+        } else if crossed && swapped < current {
+            SwitchReason::Cross
+        } else {
+            return None;
+        };
+        self.marks = vec![(da.entity, ab.entity), (db.entity, aa.entity)];
+        self.cooldown = AI_SWITCH_COOLDOWN;
+        Some(reason)
+    }
+}
+
+/// State shared between teammates (2v2): each side's defensive matchups
+#[derive(Resource, Debug, Clone, Default)]
+pub struct TeamBlackboard {
+    sides: [SideDefense; 2],
+}
+
+impl TeamBlackboard {
+    pub fn side(&self, team: Team) -> &SideDefense {
+        &self.sides[Self::index(team)]
+    }
+
+    pub fn side_mut(&mut self, team: Team) -> &mut SideDefense {
+        &mut self.sides[Self::index(team)]
+    }
+
+    /// Attacker `defender` (on `team`) is guarding
+    pub fn mark(&self, team: Team, defender: Entity) -> Option<Entity> {
+        self.side(team).mark(defender)
+    }
+
+    fn index(team: Team) -> usize {
+        match team {
+            Team::Left => 0,
+            Team::Right => 1,
+        }
+    }
+}
+
+/// Refresh both sides' matchups and log the changes.
+/// Runs in Update before ai_navigation_update.
+pub fn update_team_blackboard(
+    time: Res<Time>,
+    blackboard: Option<ResMut<TeamBlackboard>>,
+    mut event_bus: ResMut<EventBus>,
+    players: Query<(Entity, &Transform, &Team, Option<&HoldingBall>), With<Player>>,
+    baskets: Query<(&Transform, &Basket)>,
+) {
+    let Some(mut blackboard) = blackboard else {
+        return;
+    };
+    let dt = time.delta_secs().max(1.0 / 60.0);
+    let mut court: Vec<(Team, CourtPlayer)> = players
+        .iter()
+        .map(|(entity, transform, team, holding)| {
+            let player = CourtPlayer {
+                entity,
+                pos: transform.translation.truncate(),
+                has_ball: holding.is_some(),
+            };
+            (*team, player)
+        })
+        .collect();
+    court.sort_by_key(|(_, player)| player.entity);
+
+    for (basket_transform, basket) in &baskets {
+        let team = defending_team(*basket);
+        let side = |on: bool| -> Vec<CourtPlayer> {
+            court
+                .iter()
+                .filter(|(t, _)| (*t == team) == on)
+                .map(|(_, player)| *player)
+                .collect()
+        };
+        let own_basket = basket_transform.translation.truncate();
+        if let Some(reason) =
+            blackboard
+                .side_mut(team)
+                .update(&side(true), &side(false), own_basket, dt)
+        {
+            event_bus.emit(GameEvent::DefenseAssignment {
+                player: team.player_id(),
+                reason: intern(reason.as_str()),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(index: u32, x: f32, has_ball: bool) -> CourtPlayer {
+        CourtPlayer {
+            entity: Entity::from_raw_u32(index).unwrap(),
+            pos: Vec2::new(x, 0.0),
+            has_ball,
+        }
+    }
+
+    #[test]
+    fn test_matchups_switch_and_help() {
+        // We defend the basket at x=600; they attack from the left
+        let basket = Vec2::new(600.0, 200.0);
+        let dt = AI_SWITCH_COOLDOWN;
+        let mut side = SideDefense::default();
+        let mut defenders = [player(1, 0.0, false), player(2, 300.0, false)];
+        let mut attackers = [player(3, -100.0, true), player(4, 200.0, false)];
+
+        // Nearest pairing to start
+        assert_eq!(
+            side.update(&defenders, &attackers, basket, dt),
+            Some(SwitchReason::Match)
+        );
+        assert_eq!(side.mark(defenders[0].entity), Some(attackers[0].entity));
+        assert_eq!(side.update(&defenders, &attackers, basket, dt), None);
+
+        // Off-ball attacker screens for the carrier: switch
+        attackers[0].pos.x = 150.0;
+        defenders[0].pos.x = 230.0;
+        defenders[1].pos.x = 100.0;
+        assert_eq!(
+            side.update(&defenders, &attackers, basket, dt),
+            Some(SwitchReason::Screen)
+        );
+        assert_eq!(side.mark(defenders[1].entity), Some(attackers[0].entity));
+        // Held for the cooldown even though they're still tangled up
+        attackers[0].pos.x = 240.0;
+        assert_eq!(side.update(&defenders, &attackers, basket, 0.1), None);
+
+        // Spread back out; the carrier blows past its defender, the other helps
+        let mut side = SideDefense::default();
+        defenders = [player(1, 0.0, false), player(2, 450.0, false)];
+        attackers = [player(3, -50.0, true), player(4, 350.0, false)];
+        side.update(&defenders, &attackers, basket, dt);
+        attackers[0].pos.x = 200.0;
+        assert_eq!(
+            side.update(&defenders, &attackers, basket, dt),
+            Some(SwitchReason::Help)
+        );
+        assert_eq!(side.mark(defenders[1].entity), Some(attackers[0].entity));
+        assert_eq!(side.mark(defenders[0].entity), Some(attackers[1].entity));
+
+        // 1v1: no matchups
+        assert_eq!(
+            side.update(&defenders[..1], &attackers[..1], basket, dt),
+            None
+        );
+        assert!(side.marks.is_empty());
+    }
+}
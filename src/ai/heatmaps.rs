@@ -1,57 +1,130 @@
 //! Heatmap loading and sampling for AI decision making
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::constants::{
-    ARENA_HEIGHT, ARENA_WIDTH, HEATMAP_CELL_SIZE, HEATMAP_GRID_HEIGHT, HEATMAP_GRID_WIDTH,
-};
-use crate::levels::LevelDatabase;
+use crate::constants::HEATMAP_CELL_SIZE;
+use crate::levels::{ArenaDims, LevelData, LevelDatabase};
 use crate::scoring::CurrentLevel;
 use crate::world::Basket;
 
-const HEATMAP_DIR: &str = "showcase/heatmaps";
+pub const HEATMAP_DIR: &str = "showcase/heatmaps";
+
+/// Level content hashes recorded by the heatmap tool when it last generated each level
+pub const LEVEL_HASH_FILE: &str = "config/level_hashes.json";
+
+/// Every heatmap the AI loads per level: (kind, side)
+pub const HEATMAP_KINDS: [(&str, Option<&str>); 10] = [
+    ("score", Some("left")),
+    ("score", Some("right")),
+    ("line_of_sight", Some("left")),
+    ("line_of_sight", Some("right")),
+    ("speed", None),
+    ("reachability", None),
+    ("landing_safety", None),
+    ("path_cost", None),
+    ("elevation", None),
+    ("escape_routes", None),
+];
+
+/// On-disk form of `LEVEL_HASH_FILE` (level id -> `LevelData::content_hash`)
+#[derive(Serialize, Deserialize, Default)]
+pub struct LevelHashCache {
+    pub levels: HashMap<String, String>,
+}
+
+impl LevelHashCache {
+    /// Load the cache, or an empty one if missing or unreadable
+    pub fn load() -> Self {
+        match fs::read_to_string(crate::config_dir::resolve_for_write(LEVEL_HASH_FILE)) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_default(),
+            Err(_) => LevelHashCache::default(),
+        }
+    }
+}
 
+/// Heatmap grid covering a level's arena (cell 0,0 is the top-left corner)
 #[derive(Clone)]
 pub struct HeatmapGrid {
     values: Vec<f32>,
+    arena: ArenaDims,
+    width: u32,
+    height: u32,
 }
 
 impl HeatmapGrid {
-    fn new() -> Self {
-        Self {
-            values: vec![0.0; (HEATMAP_GRID_WIDTH * HEATMAP_GRID_HEIGHT) as usize],
-        }
+    fn new(arena: ArenaDims) -> Self {
+        Self::new_with_value(arena, 0.0)
     }
 
     /// Create a new grid with all cells set to the given value.
-    fn new_with_value(value: f32) -> Self {
+    fn new_with_value(arena: ArenaDims, value: f32) -> Self {
+        let width = (arena.width as u32) / HEATMAP_CELL_SIZE;
+        let height = (arena.height as u32) / HEATMAP_CELL_SIZE;
         Self {
-            values: vec![value; (HEATMAP_GRID_WIDTH * HEATMAP_GRID_HEIGHT) as usize],
+            values: vec![value; (width * height) as usize],
+            arena,
+            width,
+            height,
         }
     }
 
-    fn index(cx: u32, cy: u32) -> usize {
-        (cy * HEATMAP_GRID_WIDTH + cx) as usize
+    fn index(&self, cx: u32, cy: u32) -> usize {
+        (cy * self.width + cx) as usize
     }
 
     fn set(&mut self, cx: u32, cy: u32, value: f32) {
-        let idx = Self::index(cx, cy);
+        let idx = self.index(cx, cy);
         self.values[idx] = value;
     }
 
     fn get(&self, cx: u32, cy: u32) -> f32 {
-        let idx = Self::index(cx, cy);
+        let idx = self.index(cx, cy);
         self.values[idx]
     }
 
+    fn world_to_cell(&self, x: f32, y: f32) -> Option<(u32, u32)> {
+        let cx = ((x + self.arena.width / 2.0) / HEATMAP_CELL_SIZE as f32).floor() as i32;
+        let cy = ((self.arena.ceiling_y() - y) / HEATMAP_CELL_SIZE as f32).floor() as i32;
+        if cx < 0 || cy < 0 {
+            return None;
+        }
+        let cx = cx as u32;
+        let cy = cy as u32;
+        if cx >= self.width || cy >= self.height {
+            return None;
+        }
+        Some((cx, cy))
+    }
+
+    /// True if any cell has a non-zero value (false for missing/skipped heatmaps)
+    pub fn has_data(&self) -> bool {
+        self.values.iter().any(|&v| v > 0.0)
+    }
+
     pub fn sample_world(&self, pos: Vec2) -> f32 {
-        world_to_cell(pos.x, pos.y)
+        self.world_to_cell(pos.x, pos.y)
             .map(|(cx, cy)| self.get(cx, cy))
             .unwrap_or(0.0)
     }
+
+    /// World-space center and value of every cell (for drawing the grid)
+    pub fn cells(&self) -> impl Iterator<Item = (Vec2, f32)> + '_ {
+        let cell = HEATMAP_CELL_SIZE as f32;
+        (0..self.height).flat_map(move |cy| {
+            (0..self.width).map(move |cx| {
+                let center = Vec2::new(
+                    (cx as f32 + 0.5) * cell - self.arena.width / 2.0,
+                    self.arena.ceiling_y() - (cy as f32 + 0.5) * cell,
+                );
+                (center, self.get(cx, cy))
+            })
+        })
+    }
 }
 
 #[derive(Resource, Clone)]
@@ -73,21 +146,21 @@ impl Default for HeatmapBundle {
     fn default() -> Self {
         Self {
             built_for_level_id: String::new(),
-            score_left: HeatmapGrid::new(),
-            score_right: HeatmapGrid::new(),
-            line_of_sight_left: HeatmapGrid::new(),
-            line_of_sight_right: HeatmapGrid::new(),
-            speed: HeatmapGrid::new(),
-            reachability: HeatmapGrid::new(),
-            landing_safety: HeatmapGrid::new(),
-            path_cost: HeatmapGrid::new(),
-            elevation: HeatmapGrid::new(),
-            escape_routes: HeatmapGrid::new(),
+            score_left: HeatmapGrid::new(ArenaDims::default()),
+            score_right: HeatmapGrid::new(ArenaDims::default()),
+            line_of_sight_left: HeatmapGrid::new(ArenaDims::default()),
+            line_of_sight_right: HeatmapGrid::new(ArenaDims::default()),
+            speed: HeatmapGrid::new(ArenaDims::default()),
+            reachability: HeatmapGrid::new(ArenaDims::default()),
+            landing_safety: HeatmapGrid::new(ArenaDims::default()),
+            path_cost: HeatmapGrid::new(ArenaDims::default()),
+            elevation: HeatmapGrid::new(ArenaDims::default()),
+            escape_routes: HeatmapGrid::new(ArenaDims::default()),
         }
     }
 }
 
-fn skip_reachability_heatmaps() -> bool {
+pub fn skip_reachability_heatmaps() -> bool {
     match env::var("BALLGAME_SKIP_REACHABILITY_HEATMAPS") {
         Ok(val) => {
             let val = val.to_lowercase();
@@ -141,37 +214,19 @@ pub fn load_heatmaps_on_level_change(
     };
 
     let safe_name = sanitize_level_name(level.name.as_str());
+    let arena = level.arena();
+    let load = |kind: &str, side: Option<&str>| {
+        load_heatmap_grid(
+            arena,
+            &resolve_heatmap_path(kind, &safe_name, level.id.as_str(), side),
+        )
+    };
 
-    let score_left = load_heatmap_grid(&resolve_heatmap_path(
-        "score",
-        &safe_name,
-        level.id.as_str(),
-        Some("left"),
-    ));
-    let score_right = load_heatmap_grid(&resolve_heatmap_path(
-        "score",
-        &safe_name,
-        level.id.as_str(),
-        Some("right"),
-    ));
-    let line_of_sight_left = load_heatmap_grid(&resolve_heatmap_path(
-        "line_of_sight",
-        &safe_name,
-        level.id.as_str(),
-        Some("left"),
-    ));
-    let line_of_sight_right = load_heatmap_grid(&resolve_heatmap_path(
-        "line_of_sight",
-        &safe_name,
-        level.id.as_str(),
-        Some("right"),
-    ));
-    let speed = load_heatmap_grid(&resolve_heatmap_path(
-        "speed",
-        &safe_name,
-        level.id.as_str(),
-        None,
-    ));
+    let score_left = load("score", Some("left"));
+    let score_right = load("score", Some("right"));
+    let line_of_sight_left = load("line_of_sight", Some("left"));
+    let line_of_sight_right = load("line_of_sight", Some("right"));
+    let speed = load("speed", None);
     let skip_reachability = skip_reachability_heatmaps();
     if skip_reachability {
         warn!(
@@ -179,48 +234,23 @@ pub fn load_heatmaps_on_level_change(
         );
     }
     let reachability = if skip_reachability {
-        HeatmapGrid::new()
+        HeatmapGrid::new(arena)
     } else {
         // Strict mode: panic if reachability data is missing
         // Set BALLGAME_SKIP_REACHABILITY_HEATMAPS=1 to bypass during development
-        load_heatmap_grid(&resolve_heatmap_path(
-            "reachability",
-            &safe_name,
-            level.id.as_str(),
-            None,
-        ))
+        load("reachability", None)
     };
-    let landing_safety = load_heatmap_grid(&resolve_heatmap_path(
-        "landing_safety",
-        &safe_name,
-        level.id.as_str(),
-        None,
-    ));
+    let landing_safety = load("landing_safety", None);
     let path_cost = if skip_reachability {
-        HeatmapGrid::new()
+        HeatmapGrid::new(arena)
     } else {
-        load_heatmap_grid(&resolve_heatmap_path(
-            "path_cost",
-            &safe_name,
-            level.id.as_str(),
-            None,
-        ))
+        load("path_cost", None)
     };
-    let elevation = load_heatmap_grid(&resolve_heatmap_path(
-        "elevation",
-        &safe_name,
-        level.id.as_str(),
-        None,
-    ));
+    let elevation = load("elevation", None);
     let escape_routes = if skip_reachability {
-        HeatmapGrid::new()
+        HeatmapGrid::new(arena)
     } else {
-        load_heatmap_grid(&resolve_heatmap_path(
-            "escape_routes",
-            &safe_name,
-            level.id.as_str(),
-            None,
-        ))
+        load("escape_routes", None)
     };
 
     *heatmaps = HeatmapBundle {
@@ -238,15 +268,31 @@ pub fn load_heatmaps_on_level_change(
     };
 }
 
+/// Load one heatmap for a level without the AI's strict checks: a missing file
+/// is an error, missing cells read as 0 (for viewers like the replay underlay)
+pub fn try_load_level_heatmap(
+    level: &LevelData,
+    kind: &str,
+    side: Option<&str>,
+) -> Result<HeatmapGrid, String> {
+    let safe_name = sanitize_level_name(level.name.as_str());
+    let path = find_heatmap_path(kind, &safe_name, level.id.as_str(), side)?;
+    Ok(load_heatmap_grid_with_default(level.arena(), &path, Some(0.0)))
+}
+
 /// Load a heatmap grid from a file, or return a default grid with neutral values if file is missing.
-fn load_heatmap_grid(path: &Path) -> HeatmapGrid {
-    load_heatmap_grid_with_default(path, None)
+fn load_heatmap_grid(arena: ArenaDims, path: &Path) -> HeatmapGrid {
+    load_heatmap_grid_with_default(arena, path, None)
 }
 
 /// Load a heatmap grid with a fallback default value if the file is missing.
 /// If `default_value` is Some, returns a grid filled with that value on file error.
 /// If `default_value` is None, panics on file error (strict mode).
-fn load_heatmap_grid_with_default(path: &Path, default_value: Option<f32>) -> HeatmapGrid {
+fn load_heatmap_grid_with_default(
+    arena: ArenaDims,
+    path: &Path,
+    default_value: Option<f32>,
+) -> HeatmapGrid {
     let data = match fs::read_to_string(path) {
         Ok(data) => data,
         Err(err) => {
@@ -257,7 +303,7 @@ fn load_heatmap_grid_with_default(path: &Path, default_value: Option<f32>) -> He
                     err,
                     default
                 );
-                return HeatmapGrid::new_with_value(default);
+                return HeatmapGrid::new_with_value(arena, default);
             } else {
                 panic!("Heatmaps: failed to read {}: {}", path.display(), err);
             }
@@ -278,8 +324,8 @@ fn load_heatmap_grid_with_default(path: &Path, default_value: Option<f32>) -> He
         first_line = "";
     }
 
-    let mut grid = HeatmapGrid::new();
-    let mut filled = vec![false; (HEATMAP_GRID_WIDTH * HEATMAP_GRID_HEIGHT) as usize];
+    let mut grid = HeatmapGrid::new(arena);
+    let mut filled = vec![false; grid.values.len()];
 
     for line in lines {
         let line = line.trim();
@@ -304,10 +350,10 @@ fn load_heatmap_grid_with_default(path: &Path, default_value: Option<f32>) -> He
         };
 
         value = (value * value_scale).clamp(0.0, 1.0);
-        let Some((cx, cy)) = world_to_cell(x, y) else {
+        let Some((cx, cy)) = grid.world_to_cell(x, y) else {
             continue;
         };
-        let idx = HeatmapGrid::index(cx, cy);
+        let idx = grid.index(cx, cy);
         filled[idx] = true;
         grid.set(cx, cy, value);
     }
@@ -345,19 +391,31 @@ fn resolve_heatmap_path(
     level_id: &str,
     side: Option<&str>,
 ) -> PathBuf {
+    find_heatmap_path(label, safe_name, level_id, side)
+        .unwrap_or_else(|err| panic!("Heatmaps: {}", err))
+}
+
+/// Locate a heatmap file: the exact `heatmap_{kind}_{name}_{id}[_{side}].txt`, or a
+/// unique file matching kind and level name (level ids can change between generations)
+pub fn find_heatmap_path(
+    label: &str,
+    safe_name: &str,
+    level_id: &str,
+    side: Option<&str>,
+) -> Result<PathBuf, String> {
     let base = match side {
         Some(side) => format!("heatmap_{}_{}_{}_{}", label, safe_name, level_id, side),
         None => format!("heatmap_{}_{}_{}", label, safe_name, level_id),
     };
     let direct = Path::new(HEATMAP_DIR).join(format!("{}.txt", base));
     if direct.exists() {
-        return direct;
+        return Ok(direct);
     }
 
     let prefix = format!("heatmap_{}_{}", label, safe_name);
     let mut matches = Vec::new();
     let Ok(entries) = fs::read_dir(HEATMAP_DIR) else {
-        panic!("Heatmaps: missing directory {}", HEATMAP_DIR);
+        return Err(format!("missing directory {}", HEATMAP_DIR));
     };
 
     for entry in entries.flatten() {
@@ -379,19 +437,19 @@ fn resolve_heatmap_path(
     }
 
     match matches.len() {
-        1 => matches.remove(0),
-        0 => panic!(
-            "Heatmaps: missing {} heatmap for level '{}' (expected {}, fallback by name failed)",
+        1 => Ok(matches.remove(0)),
+        0 => Err(format!(
+            "missing {} heatmap for level '{}' (expected {}, fallback by name failed)",
             label, safe_name, base
-        ),
-        _ => panic!(
-            "Heatmaps: multiple {} heatmaps matched for level '{}' ({:?})",
+        )),
+        _ => Err(format!(
+            "multiple {} heatmaps matched for level '{}' ({:?})",
             label, safe_name, matches
-        ),
+        )),
     }
 }
 
-fn sanitize_level_name(name: &str) -> String {
+pub fn sanitize_level_name(name: &str) -> String {
     let mut out = String::new();
     let mut last_was_underscore = false;
 
@@ -408,23 +466,10 @@ fn sanitize_level_name(name: &str) -> String {
     out.trim_matches('_').to_string()
 }
 
-fn world_to_cell(x: f32, y: f32) -> Option<(u32, u32)> {
-    let cx = ((x + ARENA_WIDTH / 2.0) / HEATMAP_CELL_SIZE as f32).floor() as i32;
-    let cy = ((ARENA_HEIGHT / 2.0 - y) / HEATMAP_CELL_SIZE as f32).floor() as i32;
-    if cx < 0 || cy < 0 {
-        return None;
-    }
-    let cx = cx as u32;
-    let cy = cy as u32;
-    if cx >= HEATMAP_GRID_WIDTH || cy >= HEATMAP_GRID_HEIGHT {
-        return None;
-    }
-    Some((cx, cy))
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constants::{ARENA_HEIGHT, ARENA_WIDTH, HEATMAP_GRID_HEIGHT, HEATMAP_GRID_WIDTH};
     use std::time::{SystemTime, UNIX_EPOCH};
 
     #[test]
@@ -446,7 +491,7 @@ mod tests {
         let path = std::env::temp_dir().join(format!("heatmap_test_{}.txt", nanos));
         fs::write(&path, data).expect("write test heatmap");
 
-        let grid = load_heatmap_grid(&path);
+        let grid = load_heatmap_grid(ArenaDims::default(), &path);
         let sample_pos = Vec2::new(
             -ARENA_WIDTH / 2.0 + HEATMAP_CELL_SIZE as f32 * 0.5,
             ARENA_HEIGHT / 2.0 - HEATMAP_CELL_SIZE as f32 * 0.5,
@@ -456,4 +501,24 @@ mod tests {
 
         let _ = fs::remove_file(&path);
     }
+
+    #[test]
+    fn grid_follows_level_arena_dims() {
+        let arena = ArenaDims {
+            width: 2000.0,
+            height: 1000.0,
+            floor_y: -400.0,
+        };
+        let mut grid = HeatmapGrid::new(arena);
+        assert_eq!(grid.width, 2000 / HEATMAP_CELL_SIZE);
+        assert_eq!(grid.height, 1000 / HEATMAP_CELL_SIZE);
+
+        // Top-left cell sits at the arena's left edge and ceiling (floor_y + height)
+        grid.set(0, 0, 0.75);
+        let pos = Vec2::new(-990.0, 590.0);
+        assert!((grid.sample_world(pos) - 0.75).abs() < 0.001);
+
+        // Above the ceiling is outside the grid
+        assert_eq!(grid.world_to_cell(-990.0, 610.0), None);
+    }
 }
@@ -0,0 +1,250 @@
+//! Mistake injection - believable slips for lower-difficulty AIs
+//!
+//! Decisions stay the same at every difficulty; execution gets sloppier. After
+//! `ai_decision_update` writes an AI's InputState, this layer may hold part of
+//! it back: a jump press comes late, a shot is held past its charge target
+//! (overcharged), or a pickup press hesitates. Physics only ever sees the
+//! perturbed InputState, so nothing downstream is faked. How often each slip
+//! happens comes from the profile's `difficulty` (1.0 = never). Every slip is
+//! logged as an `AiMistake` event.
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::constants::*;
+use crate::events::{EventBus, GameEvent, PlayerId, intern};
+use crate::player::{HumanControlled, Player, Team};
+
+use super::{AiProfileDatabase, AiState, InputState};
+
+/// Kinds of execution slip
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MistakeKind {
+    /// Jump press held back, then pressed late
+    LateJump,
+    /// Shot release held back past the charge target
+    Overcharge,
+    /// Pickup presses dropped for a moment
+    PickupHesitation,
+}
+
+impl MistakeKind {
+    /// Kind string used in `AiMistake` events
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MistakeKind::LateJump => "late_jump",
+            MistakeKind::Overcharge => "overcharge",
+            MistakeKind::PickupHesitation => "hesitation",
+        }
+    }
+
+    /// Longest the held-back input waits (seconds)
+    fn max_secs(self) -> f32 {
+        match self {
+            MistakeKind::LateJump => AI_MISTAKE_LATE_JUMP_SECS,
+            MistakeKind::Overcharge => AI_MISTAKE_OVERCHARGE_SECS,
+            MistakeKind::PickupHesitation => AI_MISTAKE_HESITATION_SECS,
+        }
+    }
+}
+
+/// Per-input slip chances for one difficulty
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MistakeModel {
+    pub late_jump: f32,
+    pub overcharge: f32,
+    pub hesitation: f32,
+}
+
+impl MistakeModel {
+    /// Chances scale linearly from the base rates at 0.0 down to none at 1.0
+    pub fn from_difficulty(difficulty: f32) -> Self {
+        let sloppiness = 1.0 - difficulty.clamp(0.0, 1.0);
+        Self {
+            late_jump: AI_MISTAKE_LATE_JUMP_CHANCE * sloppiness,
+            overcharge: AI_MISTAKE_OVERCHARGE_CHANCE * sloppiness,
+            hesitation: AI_MISTAKE_HESITATION_CHANCE * sloppiness,
+        }
+    }
+
+    pub fn is_flawless(&self) -> bool {
+        self.late_jump <= 0.0 && self.overcharge <= 0.0 && self.hesitation <= 0.0
+    }
+
+    pub fn chance(&self, kind: MistakeKind) -> f32 {
+        match kind {
+            MistakeKind::LateJump => self.late_jump,
+            MistakeKind::Overcharge => self.overcharge,
+            MistakeKind::PickupHesitation => self.hesitation,
+        }
+    }
+}
+
+/// Slip in progress and the cooldown before the next one
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MistakeState {
+    /// Active slip and seconds left on it
+    pub active: Option<(MistakeKind, f32)>,
+    pub cooldown: f32,
+}
+
+/// Hold back this frame's input for a slip. Returns false once there is
+/// nothing left to hold back (the AI gave up the shot).
+fn hold_back(kind: MistakeKind, input: &mut InputState) -> bool {
+    match kind {
+        MistakeKind::LateJump => {
+            input.jump_buffer_timer = 0.0;
+            input.jump_held = false;
+        }
+        MistakeKind::Overcharge => {
+            if input.throw_released {
+                input.throw_released = false;
+                input.throw_held = true;
+            } else if !input.throw_held {
+                return false;
+            }
+        }
+        MistakeKind::PickupHesitation => input.pickup_pressed = false,
+    }
+    true
+}
+
+/// Perturb one AI's freshly decided input. `roll` yields uniform 0..1 samples.
+/// Returns a newly started slip and its length (seconds) for logging.
+pub fn perturb_input(
+    model: &MistakeModel,
+    state: &mut MistakeState,
+    input: &mut InputState,
+    dt: f32,
+    mut roll: impl FnMut() -> f32,
+) -> Option<(MistakeKind, f32)> {
+    state.cooldown = (state.cooldown - dt).max(0.0);
+
+    if let Some((kind, remaining)) = state.active {
+        let remaining = remaining - dt;
+        if remaining > 0.0 && hold_back(kind, input) {
+            state.active = Some((kind, remaining));
+            return None;
+        }
+        // Slip over: a late jump gets pressed now, a held shot releases on
+        // the decision system's next frame
+        if kind == MistakeKind::LateJump {
+            input.jump_buffer_timer = JUMP_BUFFER_TIME;
+            input.jump_held = true;
+        }
+        state.active = None;
+        state.cooldown = AI_MISTAKE_COOLDOWN_SECS;
+        return None;
+    }
+
+    if state.cooldown > 0.0 || model.is_flawless() {
+        return None;
+    }
+    // Only fresh presses can slip (the decision system sets the full buffer on press)
+    let kind = if input.jump_buffer_timer >= JUMP_BUFFER_TIME {
+        MistakeKind::LateJump
+    } else if input.throw_released {
+        MistakeKind::Overcharge
+    } else if input.pickup_pressed {
+        MistakeKind::PickupHesitation
+    } else {
+        return None;
+    };
+    if roll() >= model.chance(kind) {
+        return None;
+    }
+
+    let secs = kind.max_secs() * (0.5 + 0.5 * roll());
+    hold_back(kind, input);
+    state.active = Some((kind, secs));
+    Some((kind, secs))
+}
+
+/// Apply difficulty-based mistakes to AI input.
+/// Runs in Update right after ai_decision_update.
+pub fn ai_mistake_update(
+    time: Res<Time>,
+    profile_db: Res<AiProfileDatabase>,
+    mut event_bus: ResMut<EventBus>,
+    mut ai_query: Query<
+        (&Team, &mut AiState, &mut InputState),
+        (
+            With<Player>,
+            Without<HumanControlled>,
+            Without<super::PolicyControlled>,
+        ),
+    >,
+) {
+    // Same minimum dt as ai_decision_update (headless delta can be tiny)
+    let dt = time.delta_secs().max(1.0 / 60.0);
+    let mut rng = rand::thread_rng();
+
+    for (team, mut ai_state, mut input) in &mut ai_query {
+        let difficulty = profile_db
+            .get_by_id(&ai_state.profile_id)
+            .unwrap_or_else(|| profile_db.default_profile())
+            .difficulty;
+        let model = MistakeModel::from_difficulty(difficulty);
+
+        let mut state = ai_state.mistakes;
+        let slip = perturb_input(&model, &mut state, &mut input, dt, || rng.r#gen::<f32>());
+        ai_state.mistakes = state;
+
+        if let Some((kind, secs)) = slip {
+            let player = match team {
+                Team::Left => PlayerId::L,
+                Team::Right => PlayerId::R,
+            };
+            event_bus.emit(GameEvent::AiMistake {
+                player,
+                kind: intern(kind.as_str()),
+                delay_ms: (secs * 1000.0) as u32,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_late_jump_is_held_then_pressed() {
+        let model = MistakeModel::from_difficulty(0.0);
+        assert!(MistakeModel::from_difficulty(1.0).is_flawless());
+        assert!(model.late_jump > model.hesitation);
+
+        let mut state = MistakeState::default();
+        let mut input = InputState {
+            jump_buffer_timer: JUMP_BUFFER_TIME,
+            jump_held: true,
+            ..default()
+        };
+        // Roll 0.0 always slips, at the shortest delay
+        let slip = perturb_input(&model, &mut state, &mut input, 0.02, || 0.0);
+        assert_eq!(slip.map(|(kind, _)| kind), Some(MistakeKind::LateJump));
+        assert_eq!(input.jump_buffer_timer, 0.0);
+        assert!(!input.jump_held);
+
+        // Held back until the delay runs out, then pressed
+        let frames = (AI_MISTAKE_LATE_JUMP_SECS * 0.5 / 0.02).ceil() as usize;
+        for _ in 1..frames {
+            let mut quiet = InputState::default();
+            assert!(perturb_input(&model, &mut state, &mut quiet, 0.02, || 0.0).is_none());
+            assert_eq!(quiet.jump_buffer_timer, 0.0);
+        }
+        let mut input = InputState::default();
+        perturb_input(&model, &mut state, &mut input, 0.02, || 0.0);
+        assert_eq!(input.jump_buffer_timer, JUMP_BUFFER_TIME);
+        assert!(state.active.is_none());
+        assert!(state.cooldown > 0.0);
+
+        // Cooldown: the next release goes through untouched
+        let mut input = InputState {
+            throw_released: true,
+            ..default()
+        };
+        assert!(perturb_input(&model, &mut state, &mut input, 0.02, || 0.0).is_none());
+        assert!(input.throw_released);
+    }
+}
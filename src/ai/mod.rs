@@ -1,31 +1,54 @@
 //! AI module - AI decision making and input generation
 
 pub mod capabilities;
+pub mod contested_quality;
 pub mod decision;
+pub mod defense;
 pub mod heatmaps;
+pub mod mistakes;
+pub mod nav_metrics;
 pub mod navigation;
+pub mod off_ball;
 pub mod pathfinding;
+pub mod policy;
+pub mod profile_lint;
 mod profiles;
+pub mod risk;
+pub mod shot_model;
 pub mod shot_quality;
 pub mod world_model;
 
-pub use capabilities::AiCapabilities;
+pub use capabilities::{AiCapabilities, JUMP_SHOT_CHARGE_DELAY, JumpShotPlan};
+pub use contested_quality::{
+    CONTESTED_QUALITY_DIR, ContestedQuality, ContestedQualityTable, ContestedRate, DefenderPattern,
+    DefenderPlacement,
+};
 pub use decision::*;
+pub use defense::{SwitchReason, TeamBlackboard, update_team_blackboard};
 pub use heatmaps::{HeatmapBundle, load_heatmaps_on_level_change};
+pub use mistakes::{MistakeKind, MistakeModel, MistakeState, ai_mistake_update};
+pub use nav_metrics::{
+    EdgeCounts, EdgeOutcome, NavEdgeStats, report_edge_outcome, sync_nav_edge_penalties,
+};
 pub use navigation::{
     AiNavState, EdgeType, LevelGeometry, NavAction, NavEdge, NavGraph, NavNode, PlatformSource,
     mark_nav_dirty_on_level_change, rebuild_nav_graph,
 };
+pub use off_ball::{OffBallMove, OffBallSpot, choose_off_ball_spot};
 pub use pathfinding::{PathResult, find_path, find_path_to_shoot};
+pub use policy::{PolicyAction, PolicyControlled, ai_policy_update};
+pub use profile_lint::{ProfileIssue, TYPICAL_BASKET_HEIGHTS, lint_profile, lint_profiles};
 pub use profiles::*;
+pub use risk::{GameSituation, MatchClock, RiskMode};
+pub use shot_model::{SHOT_MODELS_DIR, ShotQualityModel};
 pub use shot_quality::{SHOT_QUALITY_ACCEPTABLE, SHOT_QUALITY_GOOD, evaluate_shot_quality};
 pub use world_model::{PlatformBounds, extract_platform_data, extract_platforms_from_nav};
 
 use bevy::prelude::*;
 
 use crate::events::{EventBus, GameEvent, PlayerId};
-use crate::input::PlayerInput;
-use crate::player::{HumanControlTarget, HumanControlled, Player, Team};
+use crate::input::{PlayerInput, SecondPlayerInput};
+use crate::player::{HumanControlTarget, HumanControlled, Player, SecondHuman, Team, Teammate};
 
 /// Per-entity input buffer used by physics systems.
 /// All players have this component - human input is copied here, AI writes directly.
@@ -36,6 +59,10 @@ pub struct InputState {
     pub jump_buffer_timer: f32,
     pub jump_held: bool,
     pub pickup_pressed: bool,
+    /// Pickup button held down (humans only; lets a running player scoop a rolling ball)
+    pub pickup_held: bool,
+    /// Pass to a teammate (2v2); consumed by `pass_ball`
+    pub pass_pressed: bool,
     pub throw_held: bool,
     pub throw_released: bool,
 }
@@ -53,6 +80,8 @@ pub struct AiState {
     pub jump_shot_active: bool,
     /// Timer for jump shot (tracks jump phase)
     pub jump_shot_timer: f32,
+    /// Seconds to hold jump in the current jump shot (see `JumpShotPlan`)
+    pub jump_shot_hold: f32,
     /// Last position for stuck detection
     pub last_position: Option<bevy::prelude::Vec2>,
     /// Timer for how long AI has been stuck (not moving while trying to)
@@ -77,6 +106,14 @@ pub struct AiState {
     pub stuck_reverse_timer: f32,
     /// The reversed direction to use when stuck_reverse_timer > 0
     pub stuck_reverse_direction: f32,
+    /// Risk mode last logged (score/clock aware, see `risk`)
+    pub risk_mode: RiskMode,
+    /// Injected execution mistake in progress (see `mistakes`)
+    pub mistakes: MistakeState,
+    /// Seconds on our own rim (cleared once well away from it, see `RimCamping`)
+    pub rim_time: f32,
+    /// Off-ball spot while a teammate has the ball (2v2, see `off_ball`)
+    pub off_ball: Option<OffBallSpot>,
 }
 
 /// Goals the AI can pursue
@@ -97,6 +134,24 @@ pub enum AiGoal {
     InterceptDefense,
     /// Close-range: stay on opponent, attempt steals
     PressureDefense,
+    /// Teammate has the ball (2v2): move to a shooting spot for a pass
+    GetOpen,
+}
+
+impl AiGoal {
+    /// Goal name as logged in `AG` events and goal-time stats (matches `Debug`)
+    pub fn name(&self) -> &'static str {
+        match self {
+            AiGoal::Idle => "Idle",
+            AiGoal::ChaseBall => "ChaseBall",
+            AiGoal::AttackWithBall => "AttackWithBall",
+            AiGoal::ChargeShot => "ChargeShot",
+            AiGoal::AttemptSteal => "AttemptSteal",
+            AiGoal::InterceptDefense => "InterceptDefense",
+            AiGoal::PressureDefense => "PressureDefense",
+            AiGoal::GetOpen => "GetOpen",
+        }
+    }
 }
 
 /// Copy human PlayerInput into the human-controlled player's InputState.
@@ -105,15 +160,34 @@ pub enum AiGoal {
 /// Runs early in Update, after capture_input.
 pub fn copy_human_input(
     mut human_input: ResMut<PlayerInput>,
-    mut human_query: Query<&mut InputState, (With<Player>, With<HumanControlled>)>,
+    mut human_query: Query<
+        &mut InputState,
+        (With<Player>, With<HumanControlled>, Without<SecondHuman>),
+    >,
 ) {
     let Ok(mut input_state) = human_query.single_mut() else {
         return;
     };
+    move_buffered_input(&mut human_input, &mut input_state);
+}
 
+/// Copy the second keyboard slot into the `SecondHuman` player's InputState (local versus)
+pub fn copy_second_human_input(
+    mut second_input: ResMut<SecondPlayerInput>,
+    mut second_query: Query<&mut InputState, (With<Player>, With<SecondHuman>)>,
+) {
+    let Ok(mut input_state) = second_query.single_mut() else {
+        return;
+    };
+    move_buffered_input(&mut second_input.0, &mut input_state);
+}
+
+/// Move one human's buffered input into a player's InputState
+pub fn move_buffered_input(human_input: &mut PlayerInput, input_state: &mut InputState) {
     // Continuous inputs (overwrite each frame)
     input_state.move_x = human_input.move_x;
     input_state.jump_held = human_input.jump_held;
+    input_state.pickup_held = human_input.pickup_held;
     input_state.throw_held = human_input.throw_held;
 
     // Jump buffer timer: copy from PlayerInput to InputState
@@ -131,15 +205,20 @@ pub fn copy_human_input(
         input_state.throw_released = true;
         human_input.throw_released = false;
     }
+    if human_input.pass_pressed {
+        input_state.pass_pressed = true;
+        human_input.pass_pressed = false;
+    }
 }
 
 /// Swap which player the human controls (Q key / L bumper).
 /// Cycles through: Left player → Right player → Observer (both AI) → Left player
+/// (2v2 teammates always stay AI).
 /// Emits ControlSwap event to EventBus for auditability.
 pub fn swap_control(
     mut commands: Commands,
     mut input: ResMut<PlayerInput>,
-    players: Query<(Entity, &Team), With<Player>>,
+    players: Query<(Entity, &Team), (With<Player>, Without<Teammate>)>,
     human_query: Query<(Entity, &Team), (With<Player>, With<HumanControlled>)>,
     mut input_states: Query<&mut InputState>,
     mut human_target: ResMut<HumanControlTarget>,
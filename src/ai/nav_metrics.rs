@@ -0,0 +1,195 @@
+//! Navigation edge metrics - how often each nav graph edge actually works
+//!
+//! Every jump, drop, portal or launch the AI executes is one attempt at a graph
+//! edge. It succeeds when the AI lands on the edge's target node, fails when it
+//! lands somewhere else, and times out when it takes longer than
+//! NAV_EDGE_TIMEOUT. Each attempt is logged as a `NavEdge` event (aggregated per
+//! level by `analyze --nav-edges`) and counted in `NavEdgeStats`. With
+//! `--nav-edge-penalty`, edges that keep failing get more expensive for
+//! pathfinding so the AI routes around them.
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use crate::constants::*;
+use crate::events::{EventBus, GameEvent, PlayerId, intern};
+
+use super::navigation::NavGraph;
+
+/// How one edge traversal ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeOutcome {
+    /// Landed on the target node
+    Reached,
+    /// Landed on a different node (missed the jump, fell through)
+    Fell,
+    /// Still traversing after NAV_EDGE_TIMEOUT
+    TimedOut,
+}
+
+impl EdgeOutcome {
+    /// Code used in events
+    pub fn code(self) -> &'static str {
+        match self {
+            EdgeOutcome::Reached => "reached",
+            EdgeOutcome::Fell => "fell",
+            EdgeOutcome::TimedOut => "timeout",
+        }
+    }
+
+    pub fn is_success(self) -> bool {
+        self == EdgeOutcome::Reached
+    }
+}
+
+/// Attempt counts for one edge
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EdgeCounts {
+    pub attempts: u32,
+    pub failures: u32,
+}
+
+impl EdgeCounts {
+    pub fn record(&mut self, success: bool) {
+        self.attempts += 1;
+        self.failures += (!success) as u32;
+    }
+
+    pub fn failure_rate(&self) -> f32 {
+        if self.attempts == 0 {
+            0.0
+        } else {
+            self.failures as f32 / self.attempts as f32
+        }
+    }
+
+    /// Pathfinding cost multiplier: 1.0 until the edge has enough attempts and
+    /// fails more than NAV_EDGE_FAIL_RATE, rising to NAV_EDGE_MAX_PENALTY at 100%
+    pub fn penalty(&self) -> f32 {
+        let rate = self.failure_rate();
+        if self.attempts < NAV_EDGE_MIN_ATTEMPTS || rate <= NAV_EDGE_FAIL_RATE {
+            return 1.0;
+        }
+        let excess = (rate - NAV_EDGE_FAIL_RATE) / (1.0 - NAV_EDGE_FAIL_RATE);
+        1.0 + (NAV_EDGE_MAX_PENALTY - 1.0) * excess
+    }
+}
+
+/// Per-edge outcomes for the current nav graph (cleared when it's rebuilt)
+#[derive(Resource, Debug, Default)]
+pub struct NavEdgeStats {
+    /// Feed failure penalties back into pathfinding costs
+    pub penalize: bool,
+    /// NavGraph revision the counts belong to
+    pub revision: u32,
+    /// Counts keyed by (from, to) node index
+    pub edges: HashMap<(usize, usize), EdgeCounts>,
+}
+
+impl NavEdgeStats {
+    pub fn new(penalize: bool) -> Self {
+        Self {
+            penalize,
+            ..default()
+        }
+    }
+
+    pub fn record(&mut self, from: usize, to: usize, outcome: EdgeOutcome) {
+        self.edges
+            .entry((from, to))
+            .or_default()
+            .record(outcome.is_success());
+    }
+
+    /// Cost multipliers for edges that currently earn a penalty
+    pub fn penalties(&self) -> HashMap<(usize, usize), f32> {
+        self.edges
+            .iter()
+            .map(|(edge, counts)| (*edge, counts.penalty()))
+            .filter(|(_, penalty)| *penalty > 1.0)
+            .collect()
+    }
+}
+
+/// Log an edge attempt and count it
+pub fn report_edge_outcome(
+    event_bus: &mut EventBus,
+    stats: Option<&mut NavEdgeStats>,
+    nav_graph: &NavGraph,
+    player: PlayerId,
+    (from, to, outcome): (usize, usize, EdgeOutcome),
+) {
+    let edge_type = nav_graph
+        .edges
+        .get(from)
+        .and_then(|edges| edges.iter().find(|e| e.to_node == to))
+        .map_or("unknown", |e| e.edge_type.name());
+    event_bus.emit(GameEvent::NavEdge {
+        player,
+        from,
+        to,
+        edge_type: intern(edge_type),
+        outcome: intern(outcome.code()),
+    });
+    if let Some(stats) = stats {
+        stats.record(from, to, outcome);
+    }
+}
+
+/// Drop counts from an old nav graph and, with penalties on, push the current
+/// penalties into the graph's cost model. Chain after `rebuild_nav_graph`.
+pub fn sync_nav_edge_penalties(
+    stats: Option<ResMut<NavEdgeStats>>,
+    mut nav_graph: ResMut<NavGraph>,
+) {
+    let Some(mut stats) = stats else {
+        return;
+    };
+    if stats.revision != nav_graph.revision {
+        stats.revision = nav_graph.revision;
+        stats.edges.clear();
+        return;
+    }
+    if !stats.penalize || !stats.is_changed() {
+        return;
+    }
+    let penalties = stats.penalties();
+    if nav_graph.edge_penalties != penalties {
+        nav_graph.edge_penalties = penalties;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chronic_failures_are_penalized() {
+        let mut stats = NavEdgeStats::new(true);
+        // Too few attempts to judge
+        for _ in 0..NAV_EDGE_MIN_ATTEMPTS - 1 {
+            stats.record(0, 1, EdgeOutcome::Fell);
+        }
+        assert!(stats.penalties().is_empty());
+
+        stats.record(0, 1, EdgeOutcome::TimedOut);
+        for _ in 0..NAV_EDGE_MIN_ATTEMPTS {
+            stats.record(1, 0, EdgeOutcome::Reached);
+        }
+        let penalties = stats.penalties();
+        assert_eq!(penalties.len(), 1);
+        assert_eq!(penalties[&(0, 1)], NAV_EDGE_MAX_PENALTY);
+
+        // Failing half the time or less is left alone
+        let half = EdgeCounts {
+            attempts: 10,
+            failures: 5,
+        };
+        assert_eq!(half.penalty(), 1.0);
+        let mostly = EdgeCounts {
+            attempts: 10,
+            failures: 8,
+        };
+        assert!(mostly.penalty() > 1.0 && mostly.penalty() < NAV_EDGE_MAX_PENALTY);
+    }
+}
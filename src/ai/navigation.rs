@@ -4,12 +4,16 @@
 //! with edges representing possible jumps/drops between them.
 
 use bevy::prelude::*;
+use std::collections::HashMap;
 
 use crate::ai::capabilities::AiCapabilities;
 use crate::ai::heatmaps::HeatmapBundle;
 use crate::ai::shot_quality::evaluate_shot_quality;
 use crate::constants::*;
-use crate::levels::{LevelDatabase, LevelData, PlatformDef};
+use crate::levels::{
+    ArenaDims, LevelData, LevelDatabase, PlatformDef, bounce_pad_apex, bounce_pad_xs, portal_ends,
+};
+use crate::overtime::Overtime;
 use crate::scoring::CurrentLevel;
 use crate::world::{BasketRim, CornerRamp, LevelPlatform, Platform};
 
@@ -38,6 +42,7 @@ pub struct LevelGeometry {
     pub basket_push_in: f32,
     pub step_count: usize,
     pub platform_count: usize, // center + mirrored platforms
+    pub arena: ArenaDims,
 }
 
 /// A node in the navigation graph representing a walkable surface
@@ -94,6 +99,23 @@ pub enum EdgeType {
     Jump,
     /// Drop down to a lower platform
     Drop,
+    /// Walk into a portal and exit at its linked end
+    Teleport,
+    /// Step onto a bounce pad and get launched to a higher platform
+    Launch,
+}
+
+impl EdgeType {
+    /// Lowercase name used in events and reports
+    pub fn name(self) -> &'static str {
+        match self {
+            EdgeType::Walk => "walk",
+            EdgeType::Jump => "jump",
+            EdgeType::Drop => "drop",
+            EdgeType::Teleport => "teleport",
+            EdgeType::Launch => "launch",
+        }
+    }
 }
 
 /// Classification of a platform's role for AI decision-making
@@ -144,9 +166,24 @@ pub struct NavGraph {
     pub level_max_shot_quality: f32,
     /// Level geometry from config (for AI reasoning)
     pub level_geometry: LevelGeometry,
+    /// Bumped on every rebuild (node indices are only valid within one revision)
+    pub revision: u32,
+    /// Cost multipliers for edges that keep failing, keyed by (from, to)
+    /// (see `NavEdgeStats`; empty unless --nav-edge-penalty is on)
+    pub edge_penalties: HashMap<(usize, usize), f32>,
 }
 
 impl NavGraph {
+    /// Pathfinding cost of an edge, including any failure penalty
+    pub fn edge_cost(&self, from: usize, edge: &NavEdge) -> f32 {
+        edge.cost
+            * self
+                .edge_penalties
+                .get(&(from, edge.to_node))
+                .copied()
+                .unwrap_or(1.0)
+    }
+
     /// Find which node a position is standing on (if any)
     pub fn find_node_at(&self, pos: Vec2, tolerance: f32) -> Option<usize> {
         // Check if position is on any platform
@@ -447,6 +484,10 @@ pub enum NavAction {
     DropFrom { x: f32 },
     /// Walk off edge in a direction to fall
     WalkOffEdge { direction: f32 },
+    /// Walk into the portal at `x`; complete once standing near `land` (exit landing point)
+    EnterPortal { x: f32, land: Vec2 },
+    /// Walk onto the bounce pad at `x` and ride the launch
+    LaunchAt { x: f32 },
 }
 
 /// Component tracking AI navigation state
@@ -454,6 +495,9 @@ pub enum NavAction {
 pub struct AiNavState {
     /// Current path being followed (sequence of actions)
     pub current_path: Vec<NavAction>,
+    /// Graph edge (from, to) each action traverses, parallel to `current_path`
+    /// (None for walks up to a jump/drop point)
+    pub path_edges: Vec<Option<(usize, usize)>>,
     /// Index of current action in path
     pub path_index: usize,
     /// Whether the current action has been started
@@ -464,6 +508,8 @@ pub struct AiNavState {
     pub nav_target: Option<Vec2>,
     /// Whether navigation is active
     pub active: bool,
+    /// Seconds spent on the current action
+    pub action_timer: f32,
 }
 
 impl AiNavState {
@@ -477,6 +523,12 @@ impl AiNavState {
         self.path_index += 1;
         self.action_started = false;
         self.jump_timer = 0.0;
+        self.action_timer = 0.0;
+    }
+
+    /// Graph edge the current action traverses (if any)
+    pub fn current_edge(&self) -> Option<(usize, usize)> {
+        self.path_edges.get(self.path_index).copied().flatten()
     }
 
     /// Check if path is complete
@@ -495,19 +547,28 @@ impl AiNavState {
     /// Clear the current path
     pub fn clear(&mut self) {
         self.current_path.clear();
+        self.path_edges.clear();
         self.path_index = 0;
         self.action_started = false;
         self.jump_timer = 0.0;
+        self.action_timer = 0.0;
         self.nav_target = None;
         self.active = false;
     }
 
-    /// Set a new path
-    pub fn set_path(&mut self, path: Vec<NavAction>, target: Vec2) {
+    /// Set a new path (`edges` tags each action with the graph edge it traverses)
+    pub fn set_path(
+        &mut self,
+        path: Vec<NavAction>,
+        edges: Vec<Option<(usize, usize)>>,
+        target: Vec2,
+    ) {
         self.current_path = path;
+        self.path_edges = edges;
         self.path_index = 0;
         self.action_started = false;
         self.jump_timer = 0.0;
+        self.action_timer = 0.0;
         self.nav_target = Some(target);
         self.active = true;
     }
@@ -519,6 +580,7 @@ pub fn rebuild_nav_graph(
     current_level: Res<CurrentLevel>,
     level_db: Res<LevelDatabase>,
     heatmaps: Res<HeatmapBundle>,
+    overtime: Option<Res<Overtime>>,
     platform_query: Query<
         (Entity, &Transform, &Sprite, Option<&CornerRamp>),
         (With<Platform>, Without<BasketRim>),
@@ -542,13 +604,15 @@ pub fn rebuild_nav_graph(
 
     // Check if level platforms have spawned yet
     // Levels with platforms should have them spawned; if none found, wait for next frame
+    // (overtime may legitimately have despawned them all)
+    let overtime = overtime.filter(|o| o.active);
     let level_platform_count = level_platform_query.iter().count();
     // Check if current level has platforms defined
     let level_has_platforms = level_db
         .get_by_id(&current_level.0)
         .map(|l| !l.platforms.is_empty())
         .unwrap_or(false);
-    if level_has_platforms && level_platform_count == 0 {
+    if level_has_platforms && level_platform_count == 0 && overtime.is_none() {
         // Level platforms haven't spawned yet - keep graph dirty and wait
         nav_graph.dirty = true;
         return;
@@ -569,13 +633,21 @@ pub fn rebuild_nav_graph(
             .map(|l| l.basket_push_in)
             .unwrap_or(BASKET_PUSH_IN),
         step_count: level_config.map(|l| l.step_count).unwrap_or(0),
-        platform_count: level_config.map(|l| l.platforms.len()).unwrap_or(0),
+        platform_count: level_config
+            .map(|l| l.platforms.iter().filter(|p| p.is_solid()).count())
+            .unwrap_or(0),
+        arena: level_config.map(|l| l.arena()).unwrap_or_default(),
     };
+    if let Some(overtime) = &overtime {
+        // Overtime walls have moved in - floor node and edges use the narrowed court
+        nav_graph.level_geometry.arena = overtime.narrow(nav_graph.level_geometry.arena);
+    }
+    let arena = nav_graph.level_geometry.arena;
 
     // Create floor node
-    let floor_left = -ARENA_WIDTH / 2.0 + WALL_THICKNESS;
-    let floor_right = ARENA_WIDTH / 2.0 - WALL_THICKNESS;
-    let floor_y = ARENA_FLOOR_Y + 20.0; // Floor surface is 20 units above ARENA_FLOOR_Y
+    let floor_left = -arena.wall_inner();
+    let floor_right = arena.wall_inner();
+    let floor_y = arena.floor_top(); // Floor surface is 20 units above the floor sprite center
 
     nav_graph.nodes.push(NavNode {
         id: 0,
@@ -605,7 +677,7 @@ pub fn rebuild_nav_graph(
         if !is_level_platform && !is_ramp {
             // Check if this is the floor (already added)
             let pos = transform.translation;
-            if (pos.y - ARENA_FLOOR_Y).abs() < 5.0 {
+            if (pos.y - arena.floor_y).abs() < 5.0 {
                 continue; // Skip floor, already added
             }
             // Skip walls (very tall sprites)
@@ -656,14 +728,39 @@ pub fn rebuild_nav_graph(
 
     // Pre-compute shot qualities and reachability for all nodes
     // Basket positions are at ±BASKET_PUSH_IN from arena edges
-    let basket_x_offset = ARENA_WIDTH / 2.0 - WALL_THICKNESS - BASKET_PUSH_IN;
-    let basket_y = ARENA_FLOOR_Y + BASKET_SIZE.y / 2.0 + 200.0; // Approximate basket center height
+    let basket_x_offset = arena.wall_inner() - BASKET_PUSH_IN;
+    let basket_y = arena.floor_y + BASKET_SIZE.y / 2.0 + 200.0; // Approximate basket center height
     let left_basket = Vec2::new(-basket_x_offset, basket_y);
     let right_basket = Vec2::new(basket_x_offset, basket_y);
 
+    // Moving baskets: average over positions sampled across one cycle so cached
+    // qualities reflect the whole path instead of a single static point
+    let basket_paths: Option<(Vec<Vec2>, Vec<Vec2>)> =
+        level_config.filter(|l| l.basket_motion.is_some()).map(|l| {
+            const SAMPLES: usize = 8;
+            let period = l.basket_motion.map_or(0.0, |m| m.period);
+            let sample = |left: bool| -> Vec<Vec2> {
+                (0..SAMPLES)
+                    .map(|i| l.basket_position(left, period * i as f32 / SAMPLES as f32))
+                    .collect()
+            };
+            (sample(true), sample(false))
+        });
+    let path_quality = |center: Vec2, path: &[Vec2]| {
+        path.iter()
+            .map(|&pos| evaluate_shot_quality(center, pos, 0.0))
+            .sum::<f32>()
+            / path.len() as f32
+    };
+
     for node in &mut nav_graph.nodes {
-        node.shot_quality_left = evaluate_shot_quality(node.center, left_basket);
-        node.shot_quality_right = evaluate_shot_quality(node.center, right_basket);
+        if let Some((left_path, right_path)) = &basket_paths {
+            node.shot_quality_left = path_quality(node.center, left_path);
+            node.shot_quality_right = path_quality(node.center, right_path);
+        } else {
+            node.shot_quality_left = evaluate_shot_quality(node.center, left_basket, 0.0);
+            node.shot_quality_right = evaluate_shot_quality(node.center, right_basket, 0.0);
+        }
         node.platform_role = classify_platform_role(node);
         // Sample reachability from heatmap at node center
         node.reachability = heatmaps.reachability_at(node.center);
@@ -702,8 +799,15 @@ pub fn rebuild_nav_graph(
         }
     }
 
+    // Portals and bounce pads add edges that plain jumps/drops can't
+    if let Some(level) = level_config {
+        add_feature_edges(&mut nav_graph, level);
+    }
+
     nav_graph.dirty = false;
     nav_graph.built_for_level_id = current_level.0.clone();
+    nav_graph.revision = nav_graph.revision.wrapping_add(1);
+    nav_graph.edge_penalties.clear();
 
     info!(
         "Nav graph built: {} nodes, {} total edges",
@@ -728,6 +832,8 @@ pub fn rebuild_nav_graph(
                     EdgeType::Walk => "W",
                     EdgeType::Jump => "J",
                     EdgeType::Drop => "D",
+                    EdgeType::Teleport => "T",
+                    EdgeType::Launch => "L",
                 };
                 format!("{}->{}({})", node.id, e.to_node, edge_type)
             })
@@ -975,13 +1081,113 @@ fn calculate_edge(from: &NavNode, to: &NavNode, all_nodes: &[NavNode]) -> Option
     }
 }
 
+/// Highest node whose surface is at or below `pos` and spans its X (where you land from `pos`)
+fn surface_below(nodes: &[NavNode], pos: Vec2) -> Option<usize> {
+    nodes
+        .iter()
+        .filter(|n| n.contains_x(pos.x) && n.top_y <= pos.y + COLLISION_EPSILON)
+        .max_by(|a, b| a.top_y.total_cmp(&b.top_y))
+        .map(|n| n.id)
+}
+
+/// Add Teleport and Launch edges for the level's portals and bounce pads
+fn add_feature_edges(nav_graph: &mut NavGraph, level: &LevelData) {
+    let floor_y = level.floor_y;
+
+    for def in &level.platforms {
+        match def {
+            PlatformDef::Portal { x1, y1, x2, y2 } => {
+                let (a, b) = portal_ends(*x1, *y1, *x2, *y2, floor_y);
+                for (entry, exit) in [(a, b), (b, a)] {
+                    // Entry must be walkable: player center on the surface lands inside the portal
+                    let Some(from) = surface_below(&nav_graph.nodes, entry) else {
+                        continue;
+                    };
+                    let standing_y = nav_graph.nodes[from].top_y + PLAYER_SIZE.y / 2.0;
+                    if (standing_y - entry.y).abs() >= PORTAL_SIZE.y / 2.0 {
+                        continue;
+                    }
+                    let Some(to) = surface_below(&nav_graph.nodes, exit) else {
+                        continue;
+                    };
+                    if to == from {
+                        continue;
+                    }
+                    let fall = (exit.y - nav_graph.nodes[to].top_y).max(0.0);
+                    nav_graph.edges[from].push(NavEdge {
+                        to_node: to,
+                        edge_type: EdgeType::Teleport,
+                        cost: NAV_POSITION_TOLERANCE + fall * 0.3,
+                        jump_from_x: entry.x,
+                        land_on_x: nav_graph.nodes[to].clamp_x(exit.x),
+                        jump_hold_duration: 0.0,
+                    });
+                }
+            }
+            PlatformDef::BouncePad { x, y, launch, .. } => {
+                let apex = bounce_pad_apex(*launch);
+                for pad_x in bounce_pad_xs(*x) {
+                    let pad = Vec2::new(pad_x, floor_y + y + BOUNCE_PAD_HEIGHT);
+                    let Some(from) = surface_below(&nav_graph.nodes, pad) else {
+                        continue;
+                    };
+                    let from_top = nav_graph.nodes[from].top_y;
+                    for to in 0..nav_graph.nodes.len() {
+                        let target = &nav_graph.nodes[to];
+                        let height_diff = target.top_y - from_top;
+                        // Only worth a launch if plain jumping can't already do it
+                        if height_diff <= NAV_MAX_JUMP_HEIGHT || height_diff > apex {
+                            continue;
+                        }
+                        // Horizontal reach while falling back down to the target height
+                        let v = *launch;
+                        let g = GRAVITY_RISE;
+                        let time_to_height = (v + (v * v - 2.0 * g * height_diff).sqrt()) / g;
+                        let gap = (target.clamp_x(pad_x) - pad_x).abs();
+                        if gap > MOVE_SPEED * time_to_height {
+                            continue;
+                        }
+                        // A platform directly above the pad would block the launch
+                        let blocked = nav_graph.nodes.iter().any(|n| {
+                            n.id != to
+                                && !n.is_floor
+                                && n.contains_x(pad_x)
+                                && n.top_y > from_top + PLAYER_SIZE.y
+                                && n.top_y < target.top_y
+                        });
+                        if blocked {
+                            continue;
+                        }
+                        let edge_margin = PLAYER_SIZE.x / 2.0 + NAV_JUMP_TOLERANCE;
+                        let land_on_x = if target.right_x - target.left_x <= edge_margin * 2.0 {
+                            target.center.x
+                        } else {
+                            pad_x.clamp(target.left_x + edge_margin, target.right_x - edge_margin)
+                        };
+                        nav_graph.edges[from].push(NavEdge {
+                            to_node: to,
+                            edge_type: EdgeType::Launch,
+                            cost: height_diff * 0.3 + gap * 0.5,
+                            jump_from_x: pad_x,
+                            land_on_x,
+                            jump_hold_duration: 0.0,
+                        });
+                    }
+                }
+            }
+            PlatformDef::Mirror { .. } | PlatformDef::Center { .. } => {}
+        }
+    }
+}
+
 /// Match a spawned platform entity to its config definition.
 /// Uses position matching since entities don't store their config source directly.
 fn match_platform_to_config(pos: Vec3, level_config: Option<&LevelData>) -> PlatformSource {
+    let floor_y = level_config.map(|l| l.floor_y).unwrap_or(ARENA_FLOOR_Y);
     let Some(config) = level_config else {
         // Fallback when no config available
         return PlatformSource::Center {
-            y: pos.y - ARENA_FLOOR_Y,
+            y: pos.y - floor_y,
             width: 100.0,
         };
     };
@@ -989,15 +1195,15 @@ fn match_platform_to_config(pos: Vec3, level_config: Option<&LevelData>) -> Plat
     for platform_def in &config.platforms {
         match platform_def {
             PlatformDef::Center { y, width } => {
-                // Center platforms spawn at x=0, y=ARENA_FLOOR_Y + y
-                let config_y = ARENA_FLOOR_Y + y;
+                // Center platforms spawn at x=0, y=floor_y + y
+                let config_y = floor_y + y;
                 if pos.x.abs() < 1.0 && (pos.y - config_y).abs() < 5.0 {
                     return PlatformSource::Center { y: *y, width: *width };
                 }
             }
             PlatformDef::Mirror { x, y, width } => {
-                // Mirror platforms spawn at -x and +x, y=ARENA_FLOOR_Y + y
-                let config_y = ARENA_FLOOR_Y + y;
+                // Mirror platforms spawn at -x and +x, y=floor_y + y
+                let config_y = floor_y + y;
                 if (pos.y - config_y).abs() < 5.0 {
                     // Check left side
                     if (pos.x - (-x)).abs() < 5.0 {
@@ -1019,12 +1225,14 @@ fn match_platform_to_config(pos: Vec3, level_config: Option<&LevelData>) -> Plat
                     }
                 }
             }
+            // Features aren't solid, so never spawn as platform entities
+            PlatformDef::Portal { .. } | PlatformDef::BouncePad { .. } => {}
         }
     }
 
     // No match found - shouldn't happen if config is correct, use fallback
     PlatformSource::Center {
-        y: pos.y - ARENA_FLOOR_Y,
+        y: pos.y - floor_y,
         width: 100.0,
     }
 }
@@ -0,0 +1,171 @@
+//! Off-ball offense - where a 2v2 AI goes while its teammate has the ball
+//!
+//! Heading for the best shooting spot (what the carrier does) put both AIs on
+//! the same platform, so passing never bought a better look. Instead every
+//! standing spot is scored by its shot quality (heatmap-weighted, like the pass
+//! check) minus a spacing penalty for being near the carrier, and the best one
+//! wins. Three moves fall out of the score:
+//!
+//! - **Cut**: a spot near the basket that no defender covers and that we reach
+//!   first gets a bonus - the lane is open, go take it
+//! - **Spot up**: otherwise the best look that clears the profile's
+//!   `min_shot_quality`
+//! - **Space**: no good look anywhere, so at least get away from the carrier
+//!
+//! The current spot gets a small bonus so the AI doesn't flip between two
+//! similar spots every frame.
+
+use bevy::prelude::*;
+
+use crate::constants::*;
+
+use super::NavGraph;
+use super::navigation::PlatformRole;
+
+/// Which off-ball move a spot is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OffBallMove {
+    Cut,
+    SpotUp,
+    Space,
+}
+
+/// Where an off-ball AI is heading and why
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OffBallSpot {
+    pub pos: Vec2,
+    pub kind: OffBallMove,
+    pub score: f32,
+}
+
+/// What the spot choice needs to know about the court
+#[derive(Debug, Clone)]
+pub struct OffBallContext<'a> {
+    pub ai_pos: Vec2,
+    /// Teammate with the ball (or the ball itself while a pass is in the air)
+    pub carrier_pos: Vec2,
+    pub basket_pos: Vec2,
+    pub defenders: &'a [Vec2],
+    pub min_quality: f32,
+    /// Spot chosen last frame (kept unless something clearly better shows up)
+    pub current: Option<Vec2>,
+}
+
+/// 0 at AI_SPACING_DISTANCE or more from the carrier, 1 on top of them
+pub fn spacing_penalty(pos: Vec2, carrier_pos: Vec2) -> f32 {
+    (1.0 - pos.distance(carrier_pos) / AI_SPACING_DISTANCE).max(0.0)
+}
+
+/// Standing spots to consider: platform centers, plus points along the floor
+/// (the floor is one node, its center alone is a poor sample)
+pub fn off_ball_candidates(nav_graph: &NavGraph) -> Vec<Vec2> {
+    let mut candidates = Vec::new();
+    for node in &nav_graph.nodes {
+        if node.platform_role == PlatformRole::DeadZone {
+            continue;
+        }
+        if node.is_floor {
+            let mut x = node.left_x + AI_OFF_BALL_FLOOR_STEP / 2.0;
+            while x < node.right_x {
+                candidates.push(Vec2::new(x, node.center.y));
+                x += AI_OFF_BALL_FLOOR_STEP;
+            }
+        } else {
+            candidates.push(node.center);
+        }
+    }
+    candidates
+}
+
+/// Pick the best off-ball spot. `quality` rates a shot from a position.
+pub fn choose_off_ball_spot(
+    ctx: &OffBallContext,
+    candidates: &[Vec2],
+    quality: impl Fn(Vec2) -> f32,
+) -> Option<OffBallSpot> {
+    candidates
+        .iter()
+        .map(|&pos| {
+            let look = quality(pos);
+            let covered = ctx
+                .defenders
+                .iter()
+                .any(|d| d.distance(pos) < AI_OFF_BALL_COVER_DISTANCE);
+            let lane_open = (pos.x - ctx.basket_pos.x).abs() < AI_CUT_RANGE
+                && !covered
+                && ctx
+                    .defenders
+                    .iter()
+                    .all(|d| d.distance(pos) > ctx.ai_pos.distance(pos));
+
+            let mut score = look - AI_SPACING_WEIGHT * spacing_penalty(pos, ctx.carrier_pos);
+            if covered {
+                score -= AI_OFF_BALL_COVERED_PENALTY;
+            }
+            if lane_open {
+                score += AI_CUT_BONUS;
+            }
+            if ctx
+                .current
+                .is_some_and(|current| current.distance(pos) < NAV_POSITION_TOLERANCE)
+            {
+                score += AI_OFF_BALL_STICKINESS;
+            }
+
+            let kind = if lane_open {
+                OffBallMove::Cut
+            } else if look >= ctx.min_quality {
+                OffBallMove::SpotUp
+            } else {
+                OffBallMove::Space
+            };
+            OffBallSpot { pos, kind, score }
+        })
+        .max_by(|a, b| a.score.total_cmp(&b.score))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_off_ball_spot() {
+        let basket = Vec2::new(600.0, 200.0);
+        // Better looks closer to the basket
+        let quality = |pos: Vec2| 1.0 - (pos.x - basket.x).abs() / 1200.0;
+        let candidates = [
+            Vec2::new(100.0, 0.0),
+            Vec2::new(300.0, 0.0),
+            Vec2::new(500.0, 0.0),
+        ];
+        let covering_basket = [Vec2::new(520.0, 0.0)];
+        let on_carrier = [Vec2::new(300.0, 0.0)];
+        let mut ctx = OffBallContext {
+            ai_pos: Vec2::new(450.0, 0.0),
+            carrier_pos: Vec2::new(320.0, 0.0),
+            basket_pos: basket,
+            defenders: &covering_basket,
+            min_quality: 0.5,
+            current: None,
+        };
+
+        // Basket is covered and the carrier stands on the best open spot: spread out
+        let spot = choose_off_ball_spot(&ctx, &candidates, quality).unwrap();
+        assert_eq!(spot.pos, candidates[0]);
+        assert_eq!(spot.kind, OffBallMove::SpotUp);
+
+        // Defender drifts out to the carrier: the lane opens, cut
+        ctx.defenders = &on_carrier;
+        let spot = choose_off_ball_spot(&ctx, &candidates, quality).unwrap();
+        assert_eq!(spot.pos, candidates[2]);
+        assert_eq!(spot.kind, OffBallMove::Cut);
+
+        // Nothing clears the bar: just keep away from the carrier
+        ctx.min_quality = 0.95;
+        ctx.defenders = &covering_basket;
+        let spot = choose_off_ball_spot(&ctx, &candidates, quality).unwrap();
+        assert_eq!(spot.pos, candidates[0]);
+        assert_eq!(spot.kind, OffBallMove::Space);
+        assert!(spacing_penalty(candidates[1], ctx.carrier_pos) > 0.9);
+    }
+}
@@ -58,6 +58,8 @@ impl Ord for SearchNode {
 pub struct PathResult {
     /// Sequence of actions to reach goal
     pub actions: Vec<NavAction>,
+    /// Graph edge (from, to) each action traverses, parallel to `actions`
+    pub edges: Vec<Option<(usize, usize)>>,
     /// Total path cost
     pub total_cost: f32,
     /// Final node reached
@@ -78,6 +80,7 @@ pub fn find_path(nav_graph: &NavGraph, start_pos: Vec2, target_pos: Vec2) -> Opt
     if start_node == goal_node {
         return Some(PathResult {
             actions: Vec::new(),
+            edges: Vec::new(),
             total_cost: 0.0,
             goal_node,
         });
@@ -125,7 +128,7 @@ pub fn find_path(nav_graph: &NavGraph, start_pos: Vec2, target_pos: Vec2) -> Opt
 
         // Explore neighbors
         for edge in &nav_graph.edges[current.node_index] {
-            let tentative_g = current.g_cost + edge.cost;
+            let tentative_g = current.g_cost + nav_graph.edge_cost(current.node_index, edge);
 
             if tentative_g < g_scores[edge.to_node] {
                 g_scores[edge.to_node] = tentative_g;
@@ -199,6 +202,7 @@ fn reconstruct_path(
     total_cost: f32,
 ) -> PathResult {
     let mut actions = Vec::new();
+    let mut edges = Vec::new();
     let mut current = goal_node;
 
     // Build path in reverse
@@ -216,7 +220,7 @@ fn reconstruct_path(
     let mut current_x = start_pos.x;
 
     for i in 0..path_nodes.len() - 1 {
-        let _from_node = path_nodes[i];
+        let from_node = path_nodes[i];
         let to_node = path_nodes[i + 1];
 
         if let Some((_, edge_type, jump_from_x, land_on_x, hold_duration)) = came_from[to_node] {
@@ -224,6 +228,9 @@ fn reconstruct_path(
             if (current_x - jump_from_x).abs() > NAV_POSITION_TOLERANCE {
                 actions.push(NavAction::WalkTo { x: jump_from_x });
             }
+            // Everything pushed from here on is the traversal itself, except the
+            // in-air steering walk after a launch (the launch lands on its own)
+            let traversal_start = actions.len();
 
             // Perform the transition
             match edge_type {
@@ -244,7 +251,23 @@ fn reconstruct_path(
                     let direction = if land_on_x > jump_from_x { 1.0 } else { -1.0 };
                     actions.push(NavAction::WalkOffEdge { direction });
                 }
+                EdgeType::Teleport => {
+                    // Walk into the portal; the exit drops us onto the target node
+                    let land = Vec2::new(land_on_x, nav_graph.nodes[to_node].top_y);
+                    actions.push(NavAction::EnterPortal {
+                        x: jump_from_x,
+                        land,
+                    });
+                }
+                EdgeType::Launch => {
+                    // Step onto the pad, then steer toward the landing point in the air
+                    actions.push(NavAction::LaunchAt { x: jump_from_x });
+                    actions.push(NavAction::WalkTo { x: land_on_x });
+                }
             }
+            edges.resize(traversal_start, None);
+            edges.push(Some((from_node, to_node)));
+            edges.resize(actions.len(), None);
 
             current_x = land_on_x;
         }
@@ -265,9 +288,11 @@ fn reconstruct_path(
     if (current_x - target_x).abs() > NAV_POSITION_TOLERANCE {
         actions.push(NavAction::WalkTo { x: target_x });
     }
+    edges.resize(actions.len(), None);
 
     PathResult {
         actions,
+        edges,
         total_cost,
         goal_node,
     }
@@ -339,6 +364,8 @@ mod tests {
             rebuild_delay: 0,
             level_max_shot_quality: 0.5, // Test value
             level_geometry: crate::ai::navigation::LevelGeometry::default(),
+            revision: 0,
+            edge_penalties: Default::default(),
         }
     }
 
@@ -349,4 +376,19 @@ mod tests {
         assert!(result.is_some());
         // Should have minimal or no actions (same platform)
     }
+
+    #[test]
+    fn test_find_path_tags_traversal_edges() {
+        let graph = create_test_graph();
+        let result = find_path(&graph, Vec2::new(-300.0, -430.0), Vec2::new(0.0, -230.0)).unwrap();
+        assert_eq!(result.edges.len(), result.actions.len());
+        // Walk to the jump point is untagged; the jump itself is the 0 -> 1 edge
+        let jump = result
+            .actions
+            .iter()
+            .position(|a| matches!(a, NavAction::JumpAt { .. }))
+            .unwrap();
+        assert_eq!(result.edges[jump], Some((0, 1)));
+        assert_eq!(result.edges.iter().flatten().count(), 1);
+    }
 }
@@ -0,0 +1,460 @@
+//! Learned policies - AI players driven by an ONNX model instead of the scripted AI
+//!
+//! A profile with `controller: policy:<path>` hands its players to a model:
+//! each tick `ai_policy_update` builds the observation vector (the same flat
+//! layout `simulation::gym` trains on), runs the model and turns its output
+//! into `InputState`. Players carry `PolicyControlled` while that's the case,
+//! which keeps `ai_decision_update` and `ai_mistake_update` off them; the rest
+//! of the profile is unused. Inference needs the `onnx` feature
+//! (`cargo build --features onnx`); without it, or if the model fails to load,
+//! the player falls back to the scripted AI with a warning.
+//!
+//! Observation layout (x mirrored for the right side, so a policy can play
+//! either side): score (own, opponent), time left (0-1), the ball (x, y, vx,
+//! vy, held by own side, held by opponent), then per player: x, y, vx, vy,
+//! grounded, holding, charge (0-1), facing. The controlled player comes first,
+//! then teammates, then opponents, in spawn order. Positions are divided by the
+//! arena half-width (y measured from the floor), velocities by
+//! `POLICY_VELOCITY_SCALE`. The model outputs `[move_x, jump, pickup, throw,
+//! pass]`, see `PolicyAction`.
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use super::{AiProfileDatabase, AiState, InputState, MatchClock};
+use crate::ball::{Ball, BallState};
+use crate::constants::*;
+use crate::levels::ArenaDims;
+use crate::player::{Facing, Grounded, HoldingBall, HumanControlled, Player, Team, Velocity};
+use crate::scoring::Score;
+use crate::shooting::ChargingShot;
+use crate::tuning::PhysicsTweaks;
+
+/// `controller:` prefix for a model-driven profile
+pub const POLICY_CONTROLLER_PREFIX: &str = "policy:";
+/// Values per action: move_x, jump, pickup, throw, pass
+pub const POLICY_ACTION_LEN: usize = 5;
+/// Leading observation values: score, time and ball
+pub const POLICY_OBS_HEADER: usize = 9;
+/// Observation values per player
+pub const POLICY_OBS_PER_PLAYER: usize = 8;
+/// Velocities are divided by this (roughly a hard shot's speed)
+pub const POLICY_VELOCITY_SCALE: f32 = 1000.0;
+
+/// One player's input for a tick, as a policy outputs it
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PolicyAction {
+    /// -1.0 (left) to 1.0 (right)
+    pub move_x: f32,
+    pub jump: bool,
+    pub pickup: bool,
+    /// Held to charge, released to shoot
+    pub throw: bool,
+    pub pass: bool,
+}
+
+impl PolicyAction {
+    /// From a flat `[move_x, jump, pickup, throw, pass]` vector; buttons are
+    /// pressed above 0.5, missing values are unpressed
+    pub fn from_slice(values: &[f32]) -> Self {
+        let button = |i: usize| values.get(i).is_some_and(|v| *v > 0.5);
+        Self {
+            move_x: values.first().copied().unwrap_or(0.0).clamp(-1.0, 1.0),
+            jump: button(1),
+            pickup: button(2),
+            throw: button(3),
+            pass: button(4),
+        }
+    }
+
+    /// Write into a player's input for one tick (`dt` ages the jump buffer).
+    /// `mirror` is -1.0 for a right-side player, since policies see the court
+    /// from the left.
+    pub fn apply(&self, input: &mut InputState, dt: f32, mirror: f32) {
+        input.move_x = self.move_x * mirror;
+        if self.jump && !input.jump_held {
+            input.jump_buffer_timer = JUMP_BUFFER_TIME;
+        } else {
+            input.jump_buffer_timer = (input.jump_buffer_timer - dt).max(0.0);
+        }
+        input.jump_held = self.jump;
+        input.pickup_pressed = self.pickup;
+        // Letting go of throw is what shoots
+        if input.throw_held && !self.throw {
+            input.throw_released = true;
+        }
+        input.throw_held = self.throw;
+        input.pass_pressed = self.pass;
+    }
+}
+
+/// -1.0 for the right side (observations and actions are mirrored), else 1.0
+pub fn side_mirror(team: Team) -> f32 {
+    match team {
+        Team::Left => 1.0,
+        Team::Right => -1.0,
+    }
+}
+
+/// One player as a policy sees it
+#[derive(Debug, Clone, Copy)]
+pub struct PolicyPlayer {
+    pub entity: Entity,
+    pub team: Team,
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub grounded: bool,
+    pub holding: bool,
+    /// Shot charge, 0-1
+    pub charge: f32,
+    pub facing: f32,
+}
+
+/// Player components a `PolicyPlayer` is read from
+pub type PolicyPlayerData<'a> = (
+    Entity,
+    &'a Team,
+    &'a Transform,
+    &'a Velocity,
+    &'a Grounded,
+    &'a Facing,
+    &'a ChargingShot,
+    Option<&'a HoldingBall>,
+);
+
+impl PolicyPlayer {
+    /// `charge_time` is the full-charge time (`PhysicsTweaks::shot_charge_time`)
+    pub fn from_data(data: PolicyPlayerData, charge_time: f32) -> Self {
+        let (entity, team, transform, velocity, grounded, facing, charging, holding) = data;
+        Self {
+            entity,
+            team: *team,
+            position: transform.translation.truncate(),
+            velocity: velocity.0,
+            grounded: grounded.0,
+            holding: holding.is_some(),
+            charge: (charging.charge_time / charge_time.max(0.01)).min(1.0),
+            facing: facing.0,
+        }
+    }
+}
+
+/// The ball as a policy sees it
+#[derive(Debug, Clone, Copy)]
+pub struct PolicyBall {
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub holder: Option<Entity>,
+}
+
+impl PolicyBall {
+    pub fn from_data((transform, velocity, state): (&Transform, &Velocity, &BallState)) -> Self {
+        Self {
+            position: transform.translation.truncate(),
+            velocity: velocity.0,
+            holder: match state {
+                BallState::Held(holder) => Some(*holder),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Flat observation vector (layout in the module docs)
+pub type Observation = Vec<f32>;
+
+/// What's true for every player on one side this tick
+#[derive(Debug, Clone, Copy)]
+pub struct ObservationFrame<'a> {
+    /// (left, right)
+    pub score: (u32, u32),
+    /// Fraction of regulation left (1.0 when untimed)
+    pub time_left: f32,
+    pub arena: ArenaDims,
+    pub ball: Option<PolicyBall>,
+    pub players: &'a [PolicyPlayer],
+}
+
+impl ObservationFrame<'_> {
+    /// The observation for `team`, with `me` (if any) ahead of its teammates
+    pub fn observe(&self, team: Team, me: Option<Entity>) -> Observation {
+        let mirror = side_mirror(team);
+        let half_width = self.arena.width / 2.0;
+        let floor = self.arena.floor_top();
+        let scale = |position: Vec2, velocity: Vec2| {
+            [
+                position.x * mirror / half_width,
+                (position.y - floor) / half_width,
+                velocity.x * mirror / POLICY_VELOCITY_SCALE,
+                velocity.y / POLICY_VELOCITY_SCALE,
+            ]
+        };
+
+        let mut players: Vec<&PolicyPlayer> = self.players.iter().collect();
+        players.sort_by_key(|p| (Some(p.entity) != me, p.team != team, p.entity));
+
+        let (own, opponent) = match team {
+            Team::Left => self.score,
+            Team::Right => (self.score.1, self.score.0),
+        };
+        let mut obs = Vec::with_capacity(POLICY_OBS_HEADER + players.len() * POLICY_OBS_PER_PLAYER);
+        obs.extend([own as f32, opponent as f32, self.time_left]);
+        match self.ball {
+            Some(ball) => {
+                let holder_team = ball
+                    .holder
+                    .and_then(|holder| self.players.iter().find(|p| p.entity == holder))
+                    .map(|p| p.team);
+                obs.extend(scale(ball.position, ball.velocity));
+                obs.push((holder_team == Some(team)) as u8 as f32);
+                obs.push((holder_team == Some(team.opponent())) as u8 as f32);
+            }
+            None => obs.extend([0.0; 6]),
+        }
+        for player in players {
+            obs.extend(scale(player.position, player.velocity));
+            obs.extend([
+                player.grounded as u8 as f32,
+                player.holding as u8 as f32,
+                player.charge,
+                player.facing.signum() * mirror,
+            ]);
+        }
+        obs
+    }
+}
+
+/// A loaded model (`onnx` feature)
+#[cfg(feature = "onnx")]
+pub struct PolicyModel {
+    plan: tract_onnx::prelude::TypedRunnableModel<tract_onnx::prelude::TypedModel>,
+}
+
+#[cfg(feature = "onnx")]
+impl PolicyModel {
+    /// Load and optimize a model taking a `[1, obs_len]` f32 input
+    pub fn load(path: &str, obs_len: usize) -> Result<Self, String> {
+        use tract_onnx::prelude::*;
+
+        let plan = tract_onnx::onnx()
+            .model_for_path(crate::config_dir::resolve(path))
+            .and_then(|model| model.with_input_fact(0, f32::fact([1, obs_len]).into()))
+            .and_then(|model| model.into_optimized())
+            .and_then(|model| model.into_runnable())
+            .map_err(|e| format!("{}: {}", path, e))?;
+        Ok(Self { plan })
+    }
+
+    /// Run one observation, returning the first output flattened
+    pub fn run(&self, obs: &[f32]) -> Result<Vec<f32>, String> {
+        use tract_onnx::prelude::*;
+
+        let input = Tensor::from_shape(&[1, obs.len()], obs).map_err(|e| e.to_string())?;
+        let outputs = self
+            .plan
+            .run(tvec!(input.into()))
+            .map_err(|e| e.to_string())?;
+        let output = outputs
+            .first()
+            .ok_or("model has no outputs")?
+            .to_array_view::<f32>()
+            .map_err(|e| e.to_string())?;
+        Ok(output.iter().copied().collect())
+    }
+}
+
+/// Stand-in without the `onnx` feature: loading always fails
+#[cfg(not(feature = "onnx"))]
+pub struct PolicyModel;
+
+#[cfg(not(feature = "onnx"))]
+impl PolicyModel {
+    pub fn load(path: &str, _obs_len: usize) -> Result<Self, String> {
+        Err(format!(
+            "{}: built without ONNX support (cargo build --features onnx)",
+            path
+        ))
+    }
+
+    pub fn run(&self, _obs: &[f32]) -> Result<Vec<f32>, String> {
+        Err("built without ONNX support".to_string())
+    }
+}
+
+/// Model path for a `controller:` value, if it names a policy
+pub fn policy_path(controller: Option<&str>) -> Option<&str> {
+    controller?
+        .strip_prefix(POLICY_CONTROLLER_PREFIX)
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+}
+
+/// Marks a player driven by the model at `path` (the scripted AI skips it)
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct PolicyControlled(pub String);
+
+/// Models by (path, observation length); failed loads are kept so they warn once
+#[derive(Default)]
+pub struct PolicyModels {
+    models: HashMap<(String, usize), Result<PolicyModel, String>>,
+}
+
+impl PolicyModels {
+    pub fn get_or_load(&mut self, path: &str, obs_len: usize) -> Option<&PolicyModel> {
+        self.models
+            .entry((path.to_string(), obs_len))
+            .or_insert_with(|| {
+                let model = PolicyModel::load(path, obs_len);
+                match &model {
+                    Ok(_) => info!("Loaded AI policy {}", path),
+                    Err(e) => warn!("AI policy unavailable, using the scripted AI: {}", e),
+                }
+                model
+            })
+            .as_ref()
+            .ok()
+    }
+}
+
+/// Hand players whose profile names a policy to their model, and drive them.
+/// Runs in Update after ai_mistake_update.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn ai_policy_update(
+    mut commands: Commands,
+    time: Res<Time>,
+    profile_db: Res<AiProfileDatabase>,
+    score: Res<Score>,
+    tweaks: Res<PhysicsTweaks>,
+    clock: Option<Res<MatchClock>>,
+    arena: Option<Res<ArenaDims>>,
+    mut models: Local<PolicyModels>,
+    mut ai_query: Query<
+        (
+            Entity,
+            &Team,
+            &AiState,
+            &mut InputState,
+            Option<&PolicyControlled>,
+        ),
+        (With<Player>, Without<HumanControlled>),
+    >,
+    players: Query<PolicyPlayerData, With<Player>>,
+    balls: Query<(&Transform, &Velocity, &BallState), With<Ball>>,
+) {
+    let snapshot: Vec<PolicyPlayer> = players
+        .iter()
+        .map(|data| PolicyPlayer::from_data(data, tweaks.shot_charge_time))
+        .collect();
+    let frame = ObservationFrame {
+        score: (score.left, score.right),
+        time_left: clock
+            .as_ref()
+            .and_then(|clock| Some(clock.remaining()? / clock.time_limit?.max(1.0)))
+            .unwrap_or(1.0),
+        arena: arena.as_deref().copied().unwrap_or_default(),
+        ball: balls.iter().next().map(PolicyBall::from_data),
+        players: &snapshot,
+    };
+    let dt = time.delta_secs().max(1.0 / 60.0);
+    let obs_len = POLICY_OBS_HEADER + snapshot.len() * POLICY_OBS_PER_PLAYER;
+
+    for (entity, team, ai_state, mut input, controlled) in &mut ai_query {
+        let controller = profile_db
+            .get_by_id(&ai_state.profile_id)
+            .and_then(|profile| profile.controller.as_deref());
+        let model = policy_path(controller)
+            .and_then(|path| Some((path, models.get_or_load(path, obs_len)?)));
+
+        // Keep the marker in step with the profile (hot reload can change it)
+        match (model.as_ref().map(|(path, _)| *path), controlled) {
+            (Some(path), Some(current)) if current.0 == path => {}
+            (Some(path), _) => {
+                commands
+                    .entity(entity)
+                    .insert(PolicyControlled(path.to_string()));
+            }
+            (None, Some(_)) => {
+                commands.entity(entity).remove::<PolicyControlled>();
+            }
+            (None, None) => {}
+        }
+        let Some((path, model)) = model else {
+            continue;
+        };
+
+        let obs = frame.observe(*team, Some(entity));
+        match model.run(&obs) {
+            Ok(output) => {
+                PolicyAction::from_slice(&output).apply(&mut input, dt, side_mirror(*team))
+            }
+            Err(e) => warn!("AI policy {} failed: {}", path, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(index: u32, team: Team, x: f32, holding: bool) -> PolicyPlayer {
+        PolicyPlayer {
+            entity: Entity::from_raw_u32(index).unwrap(),
+            team,
+            position: Vec2::new(x, ARENA_FLOOR_Y + 20.0),
+            velocity: Vec2::new(100.0, 0.0),
+            grounded: true,
+            holding,
+            charge: 0.0,
+            facing: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_observation_is_mirrored_per_side() {
+        let players = [
+            player(1, Team::Left, -400.0, true),
+            player(2, Team::Right, 400.0, false),
+        ];
+        let frame = ObservationFrame {
+            score: (2, 1),
+            time_left: 0.5,
+            arena: ArenaDims::default(),
+            ball: Some(PolicyBall {
+                position: Vec2::new(-400.0, ARENA_FLOOR_Y + 20.0),
+                velocity: Vec2::ZERO,
+                holder: Some(players[0].entity),
+            }),
+            players: &players,
+        };
+        let left = frame.observe(Team::Left, Some(players[0].entity));
+        let right = frame.observe(Team::Right, Some(players[1].entity));
+        assert_eq!(left.len(), POLICY_OBS_HEADER + 2 * POLICY_OBS_PER_PLAYER);
+
+        // Each side sees its own score first, its own ball, and itself on the left
+        assert_eq!(&left[..3], &[2.0, 1.0, 0.5]);
+        assert_eq!(&right[..3], &[1.0, 2.0, 0.5]);
+        assert_eq!(&left[7..9], &[1.0, 0.0]);
+        assert_eq!(&right[7..9], &[0.0, 1.0]);
+        let me = POLICY_OBS_HEADER;
+        assert_eq!(left[me], right[me]);
+        assert!(left[me] < 0.0);
+        assert_eq!(left[me + 1], 0.0);
+        // The same court velocity reads as opposite directions once mirrored
+        assert_eq!(left[me + 2], -right[me + 2]);
+
+        // A "move right" from the right side's policy moves it left on the court
+        let mut input = InputState::default();
+        PolicyAction::from_slice(&[1.0, 1.0]).apply(
+            &mut input,
+            1.0 / 60.0,
+            side_mirror(Team::Right),
+        );
+        assert_eq!(input.move_x, -1.0);
+        assert_eq!(input.jump_buffer_timer, JUMP_BUFFER_TIME);
+        assert_eq!(
+            policy_path(Some("policy: models/rush.onnx")),
+            Some("models/rush.onnx")
+        );
+        assert_eq!(policy_path(Some("scripted")), None);
+    }
+}
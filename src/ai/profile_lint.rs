@@ -0,0 +1,222 @@
+//! Profile lint - flags AI profile values the game can't act on
+//!
+//! Profiles are hand-edited and the parser accepts any number, so a typo can
+//! produce an AI that never steals or never shoots without any error. The lint
+//! checks values against the physics: steal ranges tighter than two players
+//! touching, inverted charge windows, and shoot ranges no shot can cover for
+//! the basket heights in play. Runs on every profile load (warnings at startup)
+//! and from `ballgame profiles check`.
+
+use std::fmt;
+
+use crate::calculate_shot_trajectory;
+use crate::constants::*;
+
+use super::AiProfile;
+
+/// Basket heights to check shoot_range against when no level list is available
+/// (spans the shipped levels, 300-780)
+pub const TYPICAL_BASKET_HEIGHTS: [f32; 3] = [300.0, 500.0, 700.0];
+
+/// Largest speed multiplier `throw_ball` applies on top of the trajectory's
+/// required speed (distance multiplier at long range)
+const MAX_DISTANCE_MULTIPLIER: f32 = 1.05;
+
+/// One problem with one profile value
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfileIssue {
+    pub profile: String,
+    pub field: &'static str,
+    pub message: String,
+}
+
+impl fmt::Display for ProfileIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} - {}", self.profile, self.field, self.message)
+    }
+}
+
+/// Closest two player centers get when standing side by side
+pub fn contact_range() -> f32 {
+    PLAYER_SIZE.x
+}
+
+/// Ball speed a grounded shot needs to reach a basket `distance` away and
+/// `basket_height` above the floor
+pub fn shot_speed_needed(distance: f32, basket_height: f32, gravity: f32) -> f32 {
+    let rise = basket_height - PLAYER_SIZE.y / 2.0;
+    calculate_shot_trajectory(0.0, 0.0, distance, rise, gravity, 0.0, None)
+        .map(|traj| traj.required_speed * MAX_DISTANCE_MULTIPLIER)
+        .unwrap_or(f32::INFINITY)
+}
+
+/// Longest shot from anywhere on the court: far wall to the basket
+pub fn court_reach() -> f32 {
+    ARENA_WIDTH - 2.0 * WALL_THICKNESS - BASKET_PUSH_IN - PLAYER_SIZE.x / 2.0
+}
+
+/// Longest grounded shot that `max_speed` can land in a basket `basket_height`
+/// above the floor (never more than the court allows)
+pub fn max_shot_range(basket_height: f32, max_speed: f32, gravity: f32) -> f32 {
+    let reachable =
+        |distance: f32| shot_speed_needed(distance, basket_height, gravity) <= max_speed;
+    if reachable(court_reach()) {
+        return court_reach();
+    }
+    // Needed speed grows with distance, so bisect for the crossover
+    let (mut lo, mut hi) = (0.0, court_reach());
+    for _ in 0..24 {
+        let mid = (lo + hi) / 2.0;
+        if reachable(mid) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+/// Check one profile against the game physics
+pub fn lint_profile(profile: &AiProfile, basket_heights: &[f32]) -> Vec<ProfileIssue> {
+    let mut issues = Vec::new();
+    let mut flag = |field: &'static str, message: String| {
+        issues.push(ProfileIssue {
+            profile: profile.name.clone(),
+            field,
+            message,
+        });
+    };
+
+    if profile.steal_range < contact_range() {
+        flag(
+            "steal_range",
+            format!(
+                "{:.0} is below player contact range ({:.0}) - never reached",
+                profile.steal_range,
+                contact_range()
+            ),
+        );
+    }
+
+    if profile.charge_min > profile.charge_max {
+        flag(
+            "charge_min",
+            format!(
+                "{:.2}s is above charge_max ({:.2}s)",
+                profile.charge_min, profile.charge_max
+            ),
+        );
+    }
+
+    // A derived range replaces the hand-set one, so only check the latter when used
+    let court_reach = court_reach();
+    let fixed_range = profile.shoot_range_skill <= 0.0;
+    if fixed_range && profile.shoot_range > court_reach {
+        flag(
+            "shoot_range",
+            format!(
+                "{:.0} is longer than the court allows ({:.0})",
+                profile.shoot_range, court_reach
+            ),
+        );
+    }
+    let out_of_reach: Vec<String> = basket_heights
+        .iter()
+        .filter(|_| fixed_range)
+        .filter(|height| {
+            shot_speed_needed(profile.shoot_range, **height, BALL_GRAVITY) > SHOT_HARD_CAP
+        })
+        .map(|height| format!("{:.0}", height))
+        .collect();
+    if !out_of_reach.is_empty() {
+        flag(
+            "shoot_range",
+            format!(
+                "{:.0} needs more than max shot speed ({:.0}) at basket height {}",
+                profile.shoot_range,
+                SHOT_HARD_CAP,
+                out_of_reach.join(", ")
+            ),
+        );
+    }
+
+    for (field, value) in [
+        ("aggression", profile.aggression),
+        ("defensive_iq", profile.defensive_iq),
+        ("min_shot_quality", profile.min_shot_quality),
+        ("trailing_risk", profile.trailing_risk),
+        ("leading_caution", profile.leading_caution),
+        ("difficulty", profile.difficulty),
+        ("shoot_range_skill", profile.shoot_range_skill),
+    ] {
+        if !(0.0..=1.0).contains(&value) {
+            flag(field, format!("{:.2} is outside 0.0-1.0", value));
+        }
+    }
+
+    issues
+}
+
+/// Check every profile
+pub fn lint_profiles(profiles: &[AiProfile], basket_heights: &[f32]) -> Vec<ProfileIssue> {
+    profiles
+        .iter()
+        .flat_map(|profile| lint_profile(profile, basket_heights))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lint_flags_impossible_values() {
+        let fine = AiProfile::default();
+        assert!(lint_profile(&fine, &TYPICAL_BASKET_HEIGHTS).is_empty());
+
+        let broken = AiProfile {
+            name: "Broken".to_string(),
+            steal_range: 10.0,
+            charge_min: 1.5,
+            charge_max: 0.8,
+            shoot_range: 5000.0,
+            aggression: 1.5,
+            ..Default::default()
+        };
+        let issues = lint_profile(&broken, &TYPICAL_BASKET_HEIGHTS);
+        let fields: Vec<_> = issues.iter().map(|i| i.field).collect();
+        assert!(fields.contains(&"steal_range"));
+        assert!(fields.contains(&"charge_min"));
+        assert!(fields.contains(&"aggression"));
+        // Both too long for the court and too far for max shot speed
+        assert_eq!(fields.iter().filter(|f| **f == "shoot_range").count(), 2);
+        assert!(issues[0].to_string().starts_with("Broken: "));
+
+        // Higher baskets need faster shots
+        assert!(
+            shot_speed_needed(600.0, 500.0, BALL_GRAVITY)
+                > shot_speed_needed(600.0, 300.0, BALL_GRAVITY)
+        );
+
+        // Derived ranges shrink for higher baskets, slower shots and heavier balls
+        let (speed, gravity) = (1200.0, BALL_GRAVITY);
+        let high = max_shot_range(700.0, speed, gravity);
+        assert!(high > 0.0 && high < court_reach());
+        assert!(high < max_shot_range(300.0, speed, gravity));
+        assert!(max_shot_range(700.0, speed * 0.9, gravity) < high);
+        assert!(max_shot_range(700.0, speed, gravity * 1.2) < high);
+        assert!(shot_speed_needed(high, 700.0, gravity) <= speed);
+        assert_eq!(max_shot_range(300.0, SHOT_HARD_CAP, gravity), court_reach());
+        let derived = AiProfile {
+            shoot_range: 5000.0,
+            shoot_range_skill: 0.5,
+            ..Default::default()
+        };
+        assert!(lint_profile(&derived, &TYPICAL_BASKET_HEIGHTS).is_empty());
+        assert!((derived.shoot_range_for(700.0, speed, gravity) - high * 0.5).abs() < 1e-3);
+        assert_eq!(
+            fine.shoot_range_for(700.0, speed, gravity),
+            fine.shoot_range
+        );
+    }
+}
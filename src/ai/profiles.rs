@@ -8,12 +8,14 @@ use std::collections::hash_map::DefaultHasher;
 use std::fs;
 use std::hash::{Hash, Hasher};
 
+use super::profile_lint::max_shot_range;
+
 /// Path to AI profiles file
 pub const AI_PROFILES_FILE: &str = "config/ai_profiles.txt";
 
 /// Generate a deterministic 16-char hex UUID from a name.
 /// Used for backward compatibility when config files lack explicit IDs.
-fn generate_uuid_from_name(name: &str) -> String {
+pub fn generate_uuid_from_name(name: &str) -> String {
     let mut hasher = DefaultHasher::new();
     name.hash(&mut hasher);
     let hash = hasher.finish();
@@ -31,6 +33,9 @@ pub struct AiProfile {
     pub position_tolerance: f32,
     /// Distance from basket at which AI will shoot (pixels)
     pub shoot_range: f32,
+    /// Derive shoot range from physics instead (0.0 = use `shoot_range`)
+    /// Fraction (0.0-1.0) of the longest shot the ball can make on the current level
+    pub shoot_range_skill: f32,
     /// Minimum charge time before releasing shot (seconds)
     pub charge_min: f32,
     /// Maximum charge time for shots (seconds)
@@ -62,6 +67,17 @@ pub struct AiProfile {
     /// Minimum utility required to seek better position (0.05-0.20)
     /// Higher = shoots more quickly from current position
     pub seek_threshold: f32,
+    /// How much AI gambles when trailing late (0.0-1.0)
+    /// Higher = lower shot standards, harder steal pressure as the clock runs out
+    pub trailing_risk: f32,
+    /// How much AI protects a late lead (0.0-1.0)
+    /// Higher = pickier shots and longer possessions to run the clock
+    pub leading_caution: f32,
+    /// How cleanly AI executes its decisions (0.0-1.0)
+    /// 1.0 = never slips, lower = more late jumps, overcharged shots and pickup hesitation
+    pub difficulty: f32,
+    /// Who drives the player: None = the scripted AI, `policy:<path>` = an ONNX model
+    pub controller: Option<String>,
 }
 
 impl Default for AiProfile {
@@ -71,6 +87,7 @@ impl Default for AiProfile {
             name: "Balanced".to_string(),
             position_tolerance: 30.0,
             shoot_range: 400.0,
+            shoot_range_skill: 0.0, // Hand-set shoot_range
             charge_min: 0.5,
             charge_max: 1.2,
             steal_range: 80.0,
@@ -83,7 +100,55 @@ impl Default for AiProfile {
             button_presses_per_sec: 12.0, // ~12 presses/sec (typical human mashing)
             position_patience: 1.0,   // Moderate willingness to seek better positions
             seek_threshold: 0.10,     // Moderate threshold for seeking
+            trailing_risk: 0.5,       // Moderate late-game gambling
+            leading_caution: 0.5,     // Moderate late-game clock management
+            difficulty: 1.0,          // No injected mistakes
+            controller: None,
+        }
+    }
+}
+
+impl AiProfile {
+    /// Shoot range against a basket `basket_height` above the floor: `shoot_range`,
+    /// or with `shoot_range_skill` set, that fraction of the longest shot
+    /// `max_speed` and `gravity` allow
+    pub fn shoot_range_for(&self, basket_height: f32, max_speed: f32, gravity: f32) -> f32 {
+        if self.shoot_range_skill <= 0.0 {
+            return self.shoot_range;
         }
+        max_shot_range(basket_height, max_speed, gravity) * self.shoot_range_skill.min(1.0)
+    }
+
+    /// This profile as an `ai_profiles.txt` block (every field, ends with a newline)
+    pub fn to_config(&self) -> String {
+        let fields = [
+            ("position_tolerance", self.position_tolerance),
+            ("shoot_range", self.shoot_range),
+            ("shoot_range_skill", self.shoot_range_skill),
+            ("charge_min", self.charge_min),
+            ("charge_max", self.charge_max),
+            ("steal_range", self.steal_range),
+            ("defense_offset", self.defense_offset),
+            ("min_shot_quality", self.min_shot_quality),
+            ("pressure_distance", self.pressure_distance),
+            ("aggression", self.aggression),
+            ("defensive_iq", self.defensive_iq),
+            ("steal_reaction_time", self.steal_reaction_time),
+            ("button_presses_per_sec", self.button_presses_per_sec),
+            ("position_patience", self.position_patience),
+            ("seek_threshold", self.seek_threshold),
+            ("trailing_risk", self.trailing_risk),
+            ("leading_caution", self.leading_caution),
+            ("difficulty", self.difficulty),
+        ];
+        let mut out = format!("profile: {}\nid: {}\n", self.name, self.id);
+        for (key, value) in fields {
+            out.push_str(&format!("{}: {}\n", key, value));
+        }
+        if let Some(controller) = &self.controller {
+            out.push_str(&format!("controller: {}\n", controller));
+        }
+        out
     }
 }
 
@@ -102,6 +167,7 @@ impl Default for AiProfileDatabase {
 impl AiProfileDatabase {
     /// Load profiles from file, or return default if file doesn't exist
     pub fn load_from_file(path: &str) -> Self {
+        let path = &crate::config_dir::resolve(path);
         let content = match fs::read_to_string(path) {
             Ok(c) => c,
             Err(e) => {
@@ -121,6 +187,9 @@ impl AiProfileDatabase {
         }
 
         info!("Loaded {} AI profiles from {}", profiles.len(), path);
+        for issue in super::lint_profiles(&profiles, &super::TYPICAL_BASKET_HEIGHTS) {
+            warn!("AI profile lint: {} (run `ballgame profiles check`)", issue);
+        }
         Self { profiles }
     }
 
@@ -167,6 +236,37 @@ impl AiProfileDatabase {
             .iter()
             .find(|p| p.name.eq_ignore_ascii_case(name))
     }
+
+    /// `profile_id` to use after reloading from `old`: the same ID if it's still
+    /// here, else the profile with the old profile's name (its ID was edited),
+    /// else the default profile (it was removed)
+    pub fn resolve_reloaded(&self, old: &AiProfileDatabase, profile_id: &str) -> String {
+        if self.get_by_id(profile_id).is_some() {
+            return profile_id.to_string();
+        }
+        let renamed = old
+            .get_by_id(profile_id)
+            .and_then(|profile| self.get_by_name(&profile.name));
+        match renamed {
+            Some(profile) => profile.id.clone(),
+            None => {
+                warn!(
+                    "AI profile {} is gone after reload; using {}",
+                    profile_id,
+                    self.default_profile().name
+                );
+                self.default_profile().id.clone()
+            }
+        }
+    }
+
+    /// Insert a profile, replacing any existing profile with the same name
+    pub fn upsert(&mut self, profile: AiProfile) {
+        match self.index_of(&profile.name) {
+            Some(idx) => self.profiles[idx] = profile,
+            None => self.profiles.push(profile),
+        }
+    }
 }
 
 /// Parse profiles from file content
@@ -220,6 +320,11 @@ fn parse_profiles(content: &str) -> Vec<AiProfile> {
                         profile.shoot_range = v;
                     }
                 }
+                "shoot_range_skill" => {
+                    if let Ok(v) = value.parse() {
+                        profile.shoot_range_skill = v;
+                    }
+                }
                 "charge_min" => {
                     if let Ok(v) = value.parse() {
                         profile.charge_min = v;
@@ -280,6 +385,24 @@ fn parse_profiles(content: &str) -> Vec<AiProfile> {
                         profile.seek_threshold = v;
                     }
                 }
+                "trailing_risk" => {
+                    if let Ok(v) = value.parse() {
+                        profile.trailing_risk = v;
+                    }
+                }
+                "leading_caution" => {
+                    if let Ok(v) = value.parse() {
+                        profile.leading_caution = v;
+                    }
+                }
+                "difficulty" => {
+                    if let Ok(v) = value.parse() {
+                        profile.difficulty = v;
+                    }
+                }
+                "controller" => {
+                    profile.controller = (!value.is_empty()).then(|| value.to_string());
+                }
                 _ => {}
             }
         }
@@ -292,3 +415,27 @@ fn parse_profiles(content: &str) -> Vec<AiProfile> {
 
     profiles
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_ids_resolve_after_reload() {
+        let old = AiProfileDatabase {
+            profiles: parse_profiles(
+                "profile: Balanced\nid: aaaa\nprofile: Rush\nid: bbbb\nprofile: Gone\nid: cccc\n",
+            ),
+        };
+        let new = AiProfileDatabase {
+            profiles: parse_profiles(
+                "profile: Balanced\nid: aaaa\ncharge_min: 0.2\nprofile: Rush\nid: dddd\n",
+            ),
+        };
+        // Kept, ID edited (matched by name), removed (falls back to the default)
+        assert_eq!(new.resolve_reloaded(&old, "aaaa"), "aaaa");
+        assert_eq!(new.resolve_reloaded(&old, "bbbb"), "dddd");
+        assert_eq!(new.resolve_reloaded(&old, "cccc"), "aaaa");
+        assert_eq!(new.get_by_id("aaaa").unwrap().charge_min, 0.2);
+    }
+}
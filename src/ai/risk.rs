@@ -0,0 +1,197 @@
+//! Score/clock-aware risk - AIs gamble when trailing late and protect a lead
+//!
+//! In the last AI_RISK_LATE_SECS of a timed match the AI reads the score:
+//! trailing, it takes lower-quality shots, presses harder and reaches further
+//! for steals; leading, it waits for better shots and holds the ball longer to
+//! run the clock. How far each profile leans is `trailing_risk` and
+//! `leading_caution`, and both effects grow as the clock runs down. Untimed
+//! games (no `MatchClock`) always play neutral. Mode changes are logged as
+//! `AiRisk` events.
+
+use bevy::prelude::*;
+
+use crate::constants::*;
+use crate::player::Team;
+use crate::scoring::Score;
+
+use super::AiProfile;
+
+/// Ball hold time before a neutral AI forces a shot (seconds)
+pub const BASE_MAX_HOLD_TIME: f32 = 2.0;
+
+/// Match clock the AI plans against (inserted by modes with a time limit)
+#[derive(Resource, Debug, Clone, Copy, Default)]
+pub struct MatchClock {
+    /// Seconds of match time played
+    pub elapsed: f32,
+    /// Regulation length (None = untimed)
+    pub time_limit: Option<f32>,
+}
+
+impl MatchClock {
+    pub fn new(time_limit: Option<f32>) -> Self {
+        Self {
+            elapsed: 0.0,
+            time_limit,
+        }
+    }
+
+    /// Seconds of regulation left (None when untimed)
+    pub fn remaining(&self) -> Option<f32> {
+        self.time_limit.map(|limit| (limit - self.elapsed).max(0.0))
+    }
+}
+
+/// How the AI is weighing risk right now
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RiskMode {
+    #[default]
+    Neutral,
+    /// Behind late: take chances
+    Trailing,
+    /// Ahead late: protect the lead
+    Leading,
+}
+
+impl RiskMode {
+    /// Mode string used in `AiRisk` events
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RiskMode::Neutral => "neutral",
+            RiskMode::Trailing => "trailing",
+            RiskMode::Leading => "leading",
+        }
+    }
+}
+
+/// Score and clock as seen by one AI
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct GameSituation {
+    pub mode: RiskMode,
+    /// Own score minus opponent's
+    pub diff: i32,
+    /// 0.0 at the start of the late window, 1.0 at the buzzer
+    pub urgency: f32,
+    /// Seconds left on our possession (shot-clock rule; None when off or not our ball)
+    pub shot_clock: Option<f32>,
+}
+
+impl GameSituation {
+    pub fn evaluate(team: Team, score: &Score, clock: Option<&MatchClock>) -> Self {
+        let diff = match team {
+            Team::Left => score.left as i32 - score.right as i32,
+            Team::Right => score.right as i32 - score.left as i32,
+        };
+        let Some(remaining) = clock.and_then(MatchClock::remaining) else {
+            return Self { diff, ..default() };
+        };
+        if remaining > AI_RISK_LATE_SECS || diff == 0 {
+            return Self { diff, ..default() };
+        }
+        Self {
+            mode: if diff < 0 {
+                RiskMode::Trailing
+            } else {
+                RiskMode::Leading
+            },
+            diff,
+            urgency: 1.0 - remaining / AI_RISK_LATE_SECS,
+            shot_clock: None,
+        }
+    }
+
+    /// Profile with this situation's risk applied (None when neutral)
+    pub fn adjust_profile(&self, profile: &AiProfile) -> Option<AiProfile> {
+        let mut adjusted = profile.clone();
+        match self.mode {
+            RiskMode::Neutral => return None,
+            RiskMode::Trailing => {
+                let k = profile.trailing_risk * self.urgency;
+                adjusted.min_shot_quality *= 1.0 - AI_RISK_TRAILING_QUALITY_DROP * k;
+                adjusted.aggression =
+                    (adjusted.aggression + AI_RISK_TRAILING_AGGRESSION * k).min(1.0);
+                adjusted.steal_range *= 1.0 + AI_RISK_TRAILING_STEAL_RANGE * k;
+            }
+            RiskMode::Leading => {
+                let k = profile.leading_caution * self.urgency;
+                adjusted.min_shot_quality +=
+                    (1.0 - adjusted.min_shot_quality) * AI_RISK_LEADING_QUALITY_RAISE * k;
+                adjusted.position_patience *= 1.0 + k;
+            }
+        }
+        Some(adjusted)
+    }
+
+    /// Seconds the AI holds the ball before forcing a shot (leading AIs run clock)
+    pub fn max_hold_time(&self, profile: &AiProfile) -> f32 {
+        match self.mode {
+            RiskMode::Leading => {
+                BASE_MAX_HOLD_TIME
+                    * (1.0 + AI_RISK_LEADING_HOLD_STRETCH * profile.leading_caution * self.urgency)
+            }
+            _ => BASE_MAX_HOLD_TIME,
+        }
+    }
+
+    /// The shot clock is about to run out: shoot now or lose the ball.
+    /// `charge_time` is how long the shot takes to charge.
+    pub fn beat_shot_clock(&self, charge_time: f32) -> bool {
+        self.shot_clock
+            .is_some_and(|remaining| remaining < charge_time + AI_SHOT_CLOCK_MARGIN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_risk_follows_score_and_clock() {
+        let profile = AiProfile::default();
+        let score = Score { left: 1, right: 3 };
+
+        // Untimed or early: neutral
+        assert_eq!(
+            GameSituation::evaluate(Team::Left, &score, None).mode,
+            RiskMode::Neutral
+        );
+        let mut clock = MatchClock::new(Some(60.0));
+        assert_eq!(
+            GameSituation::evaluate(Team::Left, &score, Some(&clock)).mode,
+            RiskMode::Neutral
+        );
+
+        // Late: trailing side gambles, leading side gets careful
+        clock.elapsed = 60.0 - AI_RISK_LATE_SECS / 4.0;
+        let trailing = GameSituation::evaluate(Team::Left, &score, Some(&clock));
+        assert_eq!(trailing.mode, RiskMode::Trailing);
+        assert_eq!(trailing.diff, -2);
+        assert!((trailing.urgency - 0.75).abs() < 1e-5);
+        let gamble = trailing.adjust_profile(&profile).unwrap();
+        assert!(gamble.min_shot_quality < profile.min_shot_quality);
+        assert!(gamble.aggression > profile.aggression);
+        assert!(gamble.steal_range > profile.steal_range);
+
+        let leading = GameSituation::evaluate(Team::Right, &score, Some(&clock));
+        assert_eq!(leading.mode, RiskMode::Leading);
+        let careful = leading.adjust_profile(&profile).unwrap();
+        assert!(careful.min_shot_quality > profile.min_shot_quality);
+        assert!(leading.max_hold_time(&profile) > BASE_MAX_HOLD_TIME);
+
+        // A profile with no appetite for risk plays the same either way
+        let flat = AiProfile {
+            trailing_risk: 0.0,
+            ..default()
+        };
+        let unchanged = trailing.adjust_profile(&flat).unwrap();
+        assert_eq!(unchanged.min_shot_quality, flat.min_shot_quality);
+
+        // Shot clock: shoot once it gets within charge time
+        let mut situation = GameSituation::evaluate(Team::Left, &score, None);
+        assert!(!situation.beat_shot_clock(0.8));
+        situation.shot_clock = Some(5.0);
+        assert!(!situation.beat_shot_clock(0.8));
+        situation.shot_clock = Some(1.0);
+        assert!(situation.beat_shot_clock(0.8));
+    }
+}
@@ -0,0 +1,210 @@
+//! Versioned shot-quality models
+//!
+//! `evaluate_shot_quality` is a hand-tuned heuristic score, not a probability. A
+//! shot model maps that score to a make probability for one level, fitted from
+//! match data and stored as `config/shot_models/<level>/<version>.json` with
+//! metadata, so models can be compared, kept and rolled back. With no stored
+//! model, the heuristic score itself is the prediction (version "heuristic").
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::heatmaps::sanitize_level_name;
+
+/// Root directory for stored shot models
+pub const SHOT_MODELS_DIR: &str = "config/shot_models";
+
+/// Version name of the unfitted heuristic (identity) model
+pub const HEURISTIC_MODEL_VERSION: &str = "heuristic";
+
+/// Quality bins used when fitting and when measuring calibration
+pub const SHOT_MODEL_BINS: usize = 10;
+
+/// Where a model came from and what it was fitted on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShotModelMeta {
+    pub version: String,
+    pub level: String,
+    /// Creation time (YYYYMMDD_HHMMSS)
+    pub created: String,
+    /// Data the model was fitted on (e.g. a DB path)
+    pub source: String,
+    /// Shots used to fit the model
+    pub samples: usize,
+}
+
+/// Observed make rate for shots whose heuristic quality falls in [quality_min, quality_max)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationBin {
+    pub quality_min: f32,
+    pub quality_max: f32,
+    pub make_prob: f32,
+    pub samples: usize,
+}
+
+/// Heuristic quality -> make probability for one level
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShotQualityModel {
+    pub meta: ShotModelMeta,
+    /// Empty for the heuristic model
+    pub bins: Vec<CalibrationBin>,
+}
+
+/// Bin index for a quality value in [0, 1]
+pub fn quality_bin(quality: f32) -> usize {
+    ((quality.clamp(0.0, 1.0) * SHOT_MODEL_BINS as f32) as usize).min(SHOT_MODEL_BINS - 1)
+}
+
+impl ShotQualityModel {
+    /// The unfitted model: predicted make probability = heuristic quality
+    pub fn heuristic(level: &str) -> Self {
+        Self {
+            meta: ShotModelMeta {
+                version: HEURISTIC_MODEL_VERSION.to_string(),
+                level: level.to_string(),
+                created: String::new(),
+                source: "evaluate_shot_quality".to_string(),
+                samples: 0,
+            },
+            bins: Vec::new(),
+        }
+    }
+
+    /// Fit per-bin make rates from (heuristic quality, made) samples.
+    /// Bins with no shots are left out; predictions fall back to the nearest fitted bin.
+    pub fn fit(level: &str, version: &str, source: &str, samples: &[(f32, bool)]) -> Self {
+        let mut counts = [(0usize, 0usize); SHOT_MODEL_BINS];
+        for &(quality, made) in samples {
+            let bin = &mut counts[quality_bin(quality)];
+            bin.0 += 1;
+            if made {
+                bin.1 += 1;
+            }
+        }
+        let bins = counts
+            .iter()
+            .enumerate()
+            .filter(|(_, (shots, _))| *shots > 0)
+            .map(|(i, &(shots, made))| CalibrationBin {
+                quality_min: i as f32 / SHOT_MODEL_BINS as f32,
+                quality_max: (i + 1) as f32 / SHOT_MODEL_BINS as f32,
+                make_prob: made as f32 / shots as f32,
+                samples: shots,
+            })
+            .collect();
+        Self {
+            meta: ShotModelMeta {
+                version: version.to_string(),
+                level: level.to_string(),
+                created: chrono::Local::now().format("%Y%m%d_%H%M%S").to_string(),
+                source: source.to_string(),
+                samples: samples.len(),
+            },
+            bins,
+        }
+    }
+
+    /// Predicted make probability for a heuristic quality value
+    pub fn predict(&self, quality: f32) -> f32 {
+        if self.bins.is_empty() {
+            return quality.clamp(0.0, 1.0);
+        }
+        let center = |bin: &CalibrationBin| (bin.quality_min + bin.quality_max) / 2.0;
+        self.bins
+            .iter()
+            .find(|bin| quality >= bin.quality_min && quality < bin.quality_max)
+            .or_else(|| {
+                self.bins.iter().min_by(|a, b| {
+                    (center(a) - quality)
+                        .abs()
+                        .total_cmp(&(center(b) - quality).abs())
+                })
+            })
+            .map(|bin| bin.make_prob)
+            .unwrap_or(quality)
+    }
+
+    /// Directory holding all versions for a level
+    pub fn level_dir(models_dir: &Path, level: &str) -> PathBuf {
+        models_dir.join(sanitize_level_name(level))
+    }
+
+    /// Write as `<models_dir>/<level>/<version>.json`
+    pub fn save(&self, models_dir: &Path) -> Result<PathBuf, String> {
+        let dir = Self::level_dir(models_dir, &self.meta.level);
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        let path = dir.join(format!("{}.json", self.meta.version));
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize shot model: {}", e))?;
+        fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        Ok(path)
+    }
+
+    /// Load one stored version for a level
+    pub fn load(models_dir: &Path, level: &str, version: &str) -> Result<Self, String> {
+        let path = Self::level_dir(models_dir, level).join(format!("{}.json", version));
+        let contents = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+    }
+
+    /// Stored versions for a level, oldest first (empty if none)
+    pub fn versions(models_dir: &Path, level: &str) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(Self::level_dir(models_dir, level)) else {
+            return Vec::new();
+        };
+        let mut versions: Vec<String> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                if path.extension()? != "json" {
+                    return None;
+                }
+                path.file_stem()?.to_str().map(str::to_string)
+            })
+            .collect();
+        // "v10" sorts after "v9"
+        versions.sort_by_key(|v| {
+            let digits: String = v.chars().filter(|c| c.is_ascii_digit()).collect();
+            (digits.parse::<u64>().unwrap_or(0), v.clone())
+        });
+        versions
+    }
+
+    /// Newest stored version for a level, or the heuristic model if none is stored
+    pub fn load_latest(models_dir: &Path, level: &str) -> Self {
+        Self::versions(models_dir, level)
+            .last()
+            .and_then(|version| match Self::load(models_dir, level, version) {
+                Ok(model) => Some(model),
+                Err(e) => {
+                    eprintln!("Warning: {}", e);
+                    None
+                }
+            })
+            .unwrap_or_else(|| Self::heuristic(level))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_predicts_observed_make_rate() {
+        let samples = [(0.15, false), (0.15, false), (0.12, true), (0.81, true)];
+        let model = ShotQualityModel::fit("Arena", "v1", "test.db", &samples);
+        assert_eq!(model.meta.samples, 4);
+        assert_eq!(model.bins.len(), 2);
+        assert!((model.predict(0.1) - 1.0 / 3.0).abs() < 1e-6);
+        assert_eq!(model.predict(0.85), 1.0);
+        // Empty bin falls back to the nearest fitted one
+        assert_eq!(model.predict(0.7), 1.0);
+
+        let heuristic = ShotQualityModel::heuristic("Arena");
+        assert_eq!(heuristic.predict(0.42), 0.42);
+    }
+}
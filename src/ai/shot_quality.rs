@@ -16,7 +16,11 @@ use crate::constants::ARENA_FLOOR_Y;
 /// - Below basket (floor shots): 30-50% success (quality 0.35-0.5)
 /// - Behind basket: 20-40% success (quality 0.2-0.4)
 /// - Directly under basket: 15-30% success (quality 0.15-0.3)
-pub fn evaluate_shot_quality(shooter_pos: Vec2, basket_pos: Vec2) -> f32 {
+///
+/// `unsteady` (0-1) is the share of the charge spent moving or airborne. It costs
+/// up to SHOT_QUALITY_UNSTEADY_PENALTY, so the AI sets its feet before shooting.
+/// Pass 0.0 to rate a spot rather than a shot in progress.
+pub fn evaluate_shot_quality(shooter_pos: Vec2, basket_pos: Vec2, unsteady: f32) -> f32 {
     // Calculate relative position
     let dx = shooter_pos.x - basket_pos.x;
     let dy = shooter_pos.y - basket_pos.y;
@@ -93,6 +97,9 @@ pub fn evaluate_shot_quality(shooter_pos: Vec2, basket_pos: Vec2) -> f32 {
         quality -= floor_penalty;
     }
 
+    // === Charging on the move ===
+    quality -= unsteady.clamp(0.0, 1.0) * SHOT_QUALITY_UNSTEADY_PENALTY;
+
     // Clamp to valid range
     quality.clamp(0.1, 1.0)
 }
@@ -103,6 +110,9 @@ pub const SHOT_QUALITY_GOOD: f32 = 0.55;
 pub const SHOT_QUALITY_ACCEPTABLE: f32 = 0.40;
 pub const SHOT_QUALITY_DESPERATE: f32 = 0.25;
 
+/// Quality lost by a shot charged entirely on the move
+pub const SHOT_QUALITY_UNSTEADY_PENALTY: f32 = 0.1;
+
 /// Reference max quality (achievable with good platform elevation)
 /// Used for scaling min_shot_quality on flat levels
 pub const SHOT_QUALITY_REFERENCE_MAX: f32 = 0.85;
@@ -114,7 +124,7 @@ pub fn calculate_level_max_quality(node_positions: &[Vec2], basket_positions: &[
 
     for &node_pos in node_positions {
         for &basket_pos in basket_positions {
-            let quality = evaluate_shot_quality(node_pos, basket_pos);
+            let quality = evaluate_shot_quality(node_pos, basket_pos, 0.0);
             if quality > max_quality {
                 max_quality = quality;
             }
@@ -158,7 +168,7 @@ mod tests {
         // Basket at left side, shooter above and in front
         let basket = Vec2::new(-600.0, 0.0);
         let shooter = Vec2::new(-400.0, 150.0); // Above and to the right (in front)
-        let quality = evaluate_shot_quality(shooter, basket);
+        let quality = evaluate_shot_quality(shooter, basket, 0.0);
         assert!(
             quality >= SHOT_QUALITY_GOOD,
             "Above+front should be good: {}",
@@ -172,7 +182,7 @@ mod tests {
         // This is the most common shot scenario
         let basket = Vec2::new(-644.0, 210.0); // Left basket, elevated
         let shooter = Vec2::new(-200.0, -418.0); // Player on floor, center-ish
-        let quality = evaluate_shot_quality(shooter, basket);
+        let quality = evaluate_shot_quality(shooter, basket, 0.0);
         assert!(
             quality >= 0.35,
             "Floor shot should be at least 0.35 quality (got {})",
@@ -190,7 +200,7 @@ mod tests {
         // Shooting from directly below basket
         let basket = Vec2::new(-600.0, 200.0);
         let shooter = Vec2::new(-600.0, -100.0); // Directly below
-        let quality = evaluate_shot_quality(shooter, basket);
+        let quality = evaluate_shot_quality(shooter, basket, 0.0);
         assert!(
             quality < SHOT_QUALITY_ACCEPTABLE,
             "Directly under should be low: {}",
@@ -203,7 +213,7 @@ mod tests {
         // Behind left basket (further left)
         let basket = Vec2::new(-600.0, 0.0);
         let shooter = Vec2::new(-750.0, 0.0); // Behind the basket
-        let quality = evaluate_shot_quality(shooter, basket);
+        let quality = evaluate_shot_quality(shooter, basket, 0.0);
         assert!(
             quality < SHOT_QUALITY_GOOD,
             "Behind basket should be penalized: {}",
@@ -216,7 +226,7 @@ mod tests {
         // Optimal position: slightly above basket, good horizontal distance
         let basket = Vec2::new(-600.0, 200.0);
         let shooter = Vec2::new(-300.0, 350.0); // Above basket, in front
-        let quality = evaluate_shot_quality(shooter, basket);
+        let quality = evaluate_shot_quality(shooter, basket, 0.0);
         assert!(
             quality >= SHOT_QUALITY_EXCELLENT,
             "Optimal position should be excellent: {}",
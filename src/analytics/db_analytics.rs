@@ -2,7 +2,9 @@
 //!
 //! Provides profile analysis, comparison, and aggregation from SQLite database.
 
-use crate::simulation::{MatchFilter, ProfileStats, SimDatabase};
+use std::collections::HashMap;
+
+use crate::simulation::{MatchFilter, MatchSummary, ProfileStats, SimDatabase};
 
 /// Extended profile analysis from database
 #[derive(Debug, Clone)]
@@ -19,6 +21,10 @@ pub struct ProfileAnalysis {
     pub goal_differential: f64,
     /// Average match duration
     pub avg_duration: f64,
+    /// Rim-camping stays per match (see `RimCamping`)
+    pub rim_camping_per_match: f64,
+    /// Camping stays called as defensive three-seconds violations (total)
+    pub camping_violations: u32,
 }
 
 impl ProfileAnalysis {
@@ -35,6 +41,7 @@ impl ProfileAnalysis {
              Goal Diff:   {:+.2}\n\
              Shot Acc:    {:.1}%\n\
              Steal Rate:  {:.1}%\n\
+             Rim Camping: {:.2}/match ({} violations)\n\
              Avg Duration:{:.1}s\n",
             self.stats.profile,
             self.stats.matches,
@@ -48,6 +55,8 @@ impl ProfileAnalysis {
             self.goal_differential,
             self.shot_accuracy * 100.0,
             self.steal_success_rate * 100.0,
+            self.rim_camping_per_match,
+            self.camping_violations,
             self.avg_duration,
         )
     }
@@ -129,6 +138,13 @@ impl ProfileComparison {
         }
         output.push('\n');
 
+        // Rim Camping
+        output.push_str(&format!("{:<15}", "Rim Camping"));
+        for p in &self.profiles {
+            output.push_str(&format!("{:>12.2}", p.rim_camping_per_match));
+        }
+        output.push('\n');
+
         output
     }
 
@@ -213,6 +229,15 @@ pub fn analyze_profile(db: &SimDatabase, profile: &str) -> Result<ProfileAnalysi
     let shot_accuracy = 0.0; // Would need shots_attempted, shots_made
     let steal_success_rate = 0.0; // Would need steals_attempted, steals_successful
 
+    let (rim_camping, camping_violations) = db
+        .get_profile_rim_camping(profile)
+        .map_err(|e| format!("Database error: {}", e))?;
+    let rim_camping_per_match = if stats.matches == 0 {
+        0.0
+    } else {
+        rim_camping as f64 / stats.matches as f64
+    };
+
     Ok(ProfileAnalysis {
         stats,
         shot_accuracy,
@@ -220,6 +245,8 @@ pub fn analyze_profile(db: &SimDatabase, profile: &str) -> Result<ProfileAnalysi
         goals_per_match,
         goal_differential,
         avg_duration,
+        rim_camping_per_match,
+        camping_violations,
     })
 }
 
@@ -327,6 +354,39 @@ pub fn summarize_all_profiles(db: &SimDatabase) -> Result<Vec<ProfileAnalysis>,
     Ok(analyses)
 }
 
+/// Matches won by the profile with the lower overall win rate in the database
+/// (ties and even matchups are never upsets)
+pub fn underdog_wins(
+    db: &SimDatabase,
+    matches: &[MatchSummary],
+) -> Result<Vec<MatchSummary>, String> {
+    let mut win_rates: HashMap<String, f64> = HashMap::new();
+    let mut win_rate = |profile: &str| -> Result<f64, String> {
+        if let Some(rate) = win_rates.get(profile) {
+            return Ok(*rate);
+        }
+        let rate = db
+            .get_profile_stats(profile)
+            .map_err(|e| format!("Database error: {}", e))?
+            .win_rate();
+        win_rates.insert(profile.to_string(), rate);
+        Ok(rate)
+    };
+
+    let mut upsets = Vec::new();
+    for m in matches {
+        let (winner, loser) = match m.winner.as_str() {
+            "left" => (&m.left_profile, &m.right_profile),
+            "right" => (&m.right_profile, &m.left_profile),
+            _ => continue,
+        };
+        if win_rate(winner)? < win_rate(loser)? {
+            upsets.push(m.clone());
+        }
+    }
+    Ok(upsets)
+}
+
 /// Format a leaderboard of all profiles
 pub fn format_leaderboard(analyses: &[ProfileAnalysis]) -> String {
     let mut output = String::new();
@@ -377,6 +437,7 @@ mod tests {
                 left_stats: PlayerStats::default(),
                 right_stats: PlayerStats::default(),
                 seed: i as u64,
+                degenerate: None,
                 events: Vec::new(),
             };
             db.insert_match(&session_id, &result).unwrap();
@@ -416,4 +477,31 @@ mod tests {
         // Aggressive should be first (higher win rate)
         assert_eq!(analyses[0].stats.profile, "Aggressive");
     }
+
+    #[test]
+    fn test_underdog_wins() {
+        let db = create_test_db();
+        let session_id = db.create_session("upset", None).unwrap();
+        let upset = MatchResult {
+            level: 3,
+            level_name: "Test Level".to_string(),
+            left_profile: "Aggressive".to_string(),
+            right_profile: "Defensive".to_string(),
+            duration: 45.0,
+            score_left: 1,
+            score_right: 2,
+            winner: "right".to_string(),
+            left_stats: PlayerStats::default(),
+            right_stats: PlayerStats::default(),
+            seed: 99,
+            degenerate: None,
+            events: Vec::new(),
+        };
+        db.insert_match(&session_id, &upset).unwrap();
+
+        let matches = db.query_matches(&MatchFilter::default()).unwrap();
+        let upsets = underdog_wins(&db, &matches).unwrap();
+        assert_eq!(upsets.len(), 1);
+        assert_eq!(upsets[0].winner, "right");
+    }
 }
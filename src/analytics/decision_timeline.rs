@@ -0,0 +1,465 @@
+//! AI decision timelines - each AI's goal over time as a band chart.
+//!
+//! Reads goal-change (`AG`) and score (`G`) events per match and renders one row
+//! per match: the left AI's goals as a colored band above the right AI's, on a
+//! time axis shared by every match, with a tick at each goal in the scorer's
+//! color. Oscillating AIs show up as striped bands and stalled AIs as long
+//! solid stretches. The markdown report carries the legend, per-match switch
+//! rates and the matches that look pathological.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use image::{Rgb, RgbImage};
+use rusqlite::Connection;
+
+use crate::events::{EventStr, GameEvent, PlayerId, parse_event};
+
+type AnyResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+const SQL_MATCHES: &str = "SELECT id, left_profile, right_profile, score_left, score_right, \
+     duration_secs FROM matches ORDER BY id";
+const SQL_TIMELINE_EVENTS: &str = "SELECT match_id, time_ms, data FROM events \
+     WHERE event_type IN ('AG', 'G') AND match_id IS NOT NULL \
+     ORDER BY match_id, id";
+
+/// A goal held shorter than this between two stretches of the same goal is a flip-flop
+const FLIP_FLOP_MS: u32 = 1000;
+/// Goal switches per minute above which a side is flagged as oscillating
+const OSCILLATION_PER_MIN: f64 = 40.0;
+/// A single goal held longer than this is flagged as a stall
+const STALL_MS: u32 = 20_000;
+/// Matches drawn in the chart (the table still lists every match)
+const MAX_CHART_MATCHES: usize = 60;
+
+const CHART_WIDTH: u32 = 1200;
+const BAND_HEIGHT: u32 = 10;
+const BAND_GAP: u32 = 2;
+const ROW_GAP: u32 = 8;
+/// How far score ticks reach past the bands
+const TICK_OVERHANG: u32 = 3;
+
+const BACKGROUND: Rgb<u8> = Rgb([245, 245, 245]);
+const LEFT_SCORE_COLOR: Rgb<u8> = Rgb([200, 30, 30]);
+const RIGHT_SCORE_COLOR: Rgb<u8> = Rgb([30, 60, 200]);
+const UNKNOWN_GOAL_COLOR: Rgb<u8> = Rgb([90, 90, 90]);
+
+/// Band color per AI goal name (as logged by the emitter)
+const GOAL_COLORS: [(&str, Rgb<u8>); 8] = [
+    ("Idle", Rgb([170, 170, 170])),
+    ("ChaseBall", Rgb([240, 200, 60])),
+    ("AttackWithBall", Rgb([60, 170, 80])),
+    ("ChargeShot", Rgb([240, 130, 40])),
+    ("AttemptSteal", Rgb([150, 80, 200])),
+    ("InterceptDefense", Rgb([40, 170, 170])),
+    ("PressureDefense", Rgb([230, 110, 170])),
+    ("GetOpen", Rgb([120, 200, 240])),
+];
+
+fn goal_color(goal: &str) -> Rgb<u8> {
+    GOAL_COLORS
+        .iter()
+        .find(|(name, _)| *name == goal)
+        .map_or(UNKNOWN_GOAL_COLOR, |(_, color)| *color)
+}
+
+/// One stretch of time an AI spent on a single goal
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoalSpan {
+    pub goal: EventStr,
+    pub start_ms: u32,
+    pub end_ms: u32,
+}
+
+impl GoalSpan {
+    pub fn duration_ms(&self) -> u32 {
+        self.end_ms.saturating_sub(self.start_ms)
+    }
+}
+
+/// Goal timeline for one match
+#[derive(Debug, Clone)]
+pub struct MatchTimeline {
+    pub match_id: i64,
+    pub left_profile: String,
+    pub right_profile: String,
+    pub score: (u32, u32),
+    pub duration_ms: u32,
+    /// Goal spans per side [left, right]
+    pub spans: [Vec<GoalSpan>; 2],
+    /// (time_ms, scorer) for each goal
+    pub scores: Vec<(u32, PlayerId)>,
+}
+
+fn side_index(player: PlayerId) -> usize {
+    match player {
+        PlayerId::L => 0,
+        PlayerId::R => 1,
+    }
+}
+
+impl MatchTimeline {
+    /// Build spans from a match's events (ordered by time). Repeated events for the
+    /// same goal extend the current span; the last span runs to the end of the match.
+    pub fn from_events(
+        match_id: i64,
+        profiles: (String, String),
+        score: (u32, u32),
+        duration_ms: u32,
+        events: &[(u32, GameEvent)],
+    ) -> Self {
+        let mut spans: [Vec<GoalSpan>; 2] = [Vec::new(), Vec::new()];
+        let mut scores = Vec::new();
+        let mut end_ms = duration_ms;
+        for (time_ms, event) in events {
+            end_ms = end_ms.max(*time_ms);
+            match event {
+                GameEvent::AiGoal { player, goal } => {
+                    let side = &mut spans[side_index(*player)];
+                    if side.last().is_some_and(|span| span.goal == *goal) {
+                        continue;
+                    }
+                    if let Some(last) = side.last_mut() {
+                        last.end_ms = *time_ms;
+                    }
+                    side.push(GoalSpan {
+                        goal: goal.clone(),
+                        start_ms: *time_ms,
+                        end_ms: *time_ms,
+                    });
+                }
+                GameEvent::Goal { player, .. } => scores.push((*time_ms, *player)),
+                _ => {}
+            }
+        }
+        for side in &mut spans {
+            if let Some(last) = side.last_mut() {
+                last.end_ms = end_ms;
+            }
+        }
+        Self {
+            match_id,
+            left_profile: profiles.0,
+            right_profile: profiles.1,
+            score,
+            duration_ms: end_ms,
+            spans,
+            scores,
+        }
+    }
+
+    /// Goal changes per minute of match time
+    pub fn switches_per_min(&self, side: usize) -> f64 {
+        let switches = self.spans[side].len().saturating_sub(1);
+        let minutes = self.duration_ms as f64 / 60_000.0;
+        if minutes <= 0.0 {
+            0.0
+        } else {
+            switches as f64 / minutes
+        }
+    }
+
+    /// A -> B -> A patterns where B lasted under FLIP_FLOP_MS
+    pub fn flip_flops(&self, side: usize) -> usize {
+        self.spans[side]
+            .windows(3)
+            .filter(|w| w[0].goal == w[2].goal && w[1].duration_ms() < FLIP_FLOP_MS)
+            .count()
+    }
+
+    pub fn longest_span(&self, side: usize) -> Option<&GoalSpan> {
+        self.spans[side]
+            .iter()
+            .max_by_key(|span| span.duration_ms())
+    }
+
+    /// Why a side looks pathological (empty when it doesn't)
+    pub fn flags(&self, side: usize) -> Vec<String> {
+        let mut flags = Vec::new();
+        let rate = self.switches_per_min(side);
+        if rate > OSCILLATION_PER_MIN {
+            flags.push(format!(
+                "oscillating ({:.0} switches/min, {} flip-flops)",
+                rate,
+                self.flip_flops(side)
+            ));
+        }
+        if let Some(span) = self.longest_span(side)
+            && span.duration_ms() > STALL_MS
+        {
+            flags.push(format!(
+                "stalled in {} for {:.1}s from {:.1}s",
+                span.goal,
+                span.duration_ms() as f64 / 1000.0,
+                span.start_ms as f64 / 1000.0
+            ));
+        }
+        flags
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DecisionTimelineReport {
+    pub db_path: String,
+    pub timelines: Vec<MatchTimeline>,
+    /// Band chart written alongside the report
+    pub chart_path: String,
+}
+
+impl DecisionTimelineReport {
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        out.push_str("# AI Decision Timeline Report\n\n");
+        out.push_str(&format!("DB: `{}`\n\n", self.db_path));
+        // The chart is written next to the report, so link it by file name
+        let chart_name = Path::new(&self.chart_path)
+            .file_name()
+            .map_or(self.chart_path.clone(), |n| n.to_string_lossy().to_string());
+        out.push_str(&format!("![Decision timelines]({})\n\n", chart_name));
+        out.push_str(
+            "Each chart row is one match (table order): left AI's goals on top, right AI's \
+             below, time shared across matches. Ticks mark goals (red = left scored, blue = \
+             right scored).\n\n",
+        );
+        if self.timelines.len() > MAX_CHART_MATCHES {
+            out.push_str(&format!(
+                "Chart shows the first {} of {} matches.\n\n",
+                MAX_CHART_MATCHES,
+                self.timelines.len()
+            ));
+        }
+
+        out.push_str("## Legend\n");
+        for (goal, color) in GOAL_COLORS {
+            out.push_str(&format!(
+                "- {}: #{:02x}{:02x}{:02x}\n",
+                goal, color[0], color[1], color[2]
+            ));
+        }
+        out.push('\n');
+
+        out.push_str("## Matches\n");
+        out.push_str(
+            "| Row | Match | Left | Right | Score | L switches/min | L longest | R switches/min | R longest |\n",
+        );
+        out.push_str("|---|---|---|---|---|---|---|---|---|\n");
+        let longest = |timeline: &MatchTimeline, side: usize| {
+            timeline.longest_span(side).map_or("-".to_string(), |span| {
+                format!("{} {:.1}s", span.goal, span.duration_ms() as f64 / 1000.0)
+            })
+        };
+        for (row, timeline) in self.timelines.iter().enumerate() {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {}-{} | {:.1} | {} | {:.1} | {} |\n",
+                row + 1,
+                timeline.match_id,
+                timeline.left_profile,
+                timeline.right_profile,
+                timeline.score.0,
+                timeline.score.1,
+                timeline.switches_per_min(0),
+                longest(timeline, 0),
+                timeline.switches_per_min(1),
+                longest(timeline, 1),
+            ));
+        }
+        out.push('\n');
+
+        out.push_str("## Flagged\n");
+        let mut any = false;
+        for timeline in &self.timelines {
+            for (side, profile) in [(0, &timeline.left_profile), (1, &timeline.right_profile)] {
+                for flag in timeline.flags(side) {
+                    any = true;
+                    out.push_str(&format!(
+                        "- Match {} {} ({}): {}\n",
+                        timeline.match_id,
+                        if side == 0 { "left" } else { "right" },
+                        profile,
+                        flag
+                    ));
+                }
+            }
+        }
+        if !any {
+            out.push_str("- None\n");
+        }
+        out
+    }
+}
+
+/// Draw the band chart: one row per match, left band over right band
+pub fn render_timeline_chart(timelines: &[MatchTimeline], path: &Path) -> AnyResult<()> {
+    let rows = timelines.len().clamp(1, MAX_CHART_MATCHES) as u32;
+    let row_height = BAND_HEIGHT * 2 + BAND_GAP + ROW_GAP;
+    let mut img = RgbImage::from_pixel(CHART_WIDTH, rows * row_height + ROW_GAP, BACKGROUND);
+
+    let max_ms = timelines
+        .iter()
+        .map(|t| t.duration_ms)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let x_of = |ms: u32| ((ms as u64 * (CHART_WIDTH - 1) as u64) / max_ms as u64) as u32;
+
+    for (row, timeline) in timelines.iter().take(MAX_CHART_MATCHES).enumerate() {
+        let top = ROW_GAP + row as u32 * row_height;
+        for (side, spans) in timeline.spans.iter().enumerate() {
+            let band_top = top + side as u32 * (BAND_HEIGHT + BAND_GAP);
+            for span in spans {
+                let color = goal_color(&span.goal);
+                for x in x_of(span.start_ms)..=x_of(span.end_ms) {
+                    for y in band_top..band_top + BAND_HEIGHT {
+                        img.put_pixel(x, y, color);
+                    }
+                }
+            }
+        }
+        for (time_ms, scorer) in &timeline.scores {
+            let color = match scorer {
+                PlayerId::L => LEFT_SCORE_COLOR,
+                PlayerId::R => RIGHT_SCORE_COLOR,
+            };
+            let x = x_of(*time_ms);
+            let y_start = top.saturating_sub(TICK_OVERHANG);
+            let y_end = top + BAND_HEIGHT * 2 + BAND_GAP + TICK_OVERHANG;
+            for x in x.saturating_sub(1)..=x {
+                for y in y_start..y_end {
+                    img.put_pixel(x, y, color);
+                }
+            }
+        }
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).ok();
+    }
+    img.save(path)?;
+    Ok(())
+}
+
+/// Build timelines for every match in the DB and render the chart to `chart_path`
+pub fn run_decision_timeline(
+    db_path: &Path,
+    chart_path: &Path,
+) -> AnyResult<DecisionTimelineReport> {
+    let conn = Connection::open(db_path)?;
+
+    let mut matches = BTreeMap::<i64, ((String, String), (u32, u32), u32)>::new();
+    let mut stmt = conn.prepare(SQL_MATCHES)?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            (
+                (row.get::<_, String>(1)?, row.get::<_, String>(2)?),
+                (row.get::<_, u32>(3)?, row.get::<_, u32>(4)?),
+                (row.get::<_, f64>(5)? * 1000.0) as u32,
+            ),
+        ))
+    })?;
+    for row in rows {
+        let (id, info) = row?;
+        matches.insert(id, info);
+    }
+
+    let mut events = BTreeMap::<i64, Vec<(u32, GameEvent)>>::new();
+    let mut stmt = conn.prepare(SQL_TIMELINE_EVENTS)?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, u32>(1)?,
+            row.get::<_, String>(2)?,
+        ))
+    })?;
+    for row in rows {
+        let (match_id, time_ms, data) = row?;
+        if let Some((_, event)) = parse_event(&data) {
+            events.entry(match_id).or_default().push((time_ms, event));
+        }
+    }
+
+    let timelines: Vec<MatchTimeline> = matches
+        .into_iter()
+        .filter_map(|(id, (profiles, score, duration_ms))| {
+            // Nothing to draw without goal-change events (e.g. the ai category wasn't logged)
+            let events = events.remove(&id)?;
+            events
+                .iter()
+                .any(|(_, e)| matches!(e, GameEvent::AiGoal { .. }))
+                .then(|| MatchTimeline::from_events(id, profiles, score, duration_ms, &events))
+        })
+        .collect();
+
+    render_timeline_chart(&timelines, chart_path)?;
+    Ok(DecisionTimelineReport {
+        db_path: db_path.display().to_string(),
+        timelines,
+        chart_path: chart_path.display().to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ai_goal(player: PlayerId, goal: &str) -> GameEvent {
+        GameEvent::AiGoal {
+            player,
+            goal: crate::events::intern(goal),
+        }
+    }
+
+    #[test]
+    fn test_spans_switches_and_flags() {
+        let mut events = vec![
+            (0, ai_goal(PlayerId::L, "ChaseBall")),
+            (0, ai_goal(PlayerId::R, "ChaseBall")),
+            (500, ai_goal(PlayerId::R, "ChaseBall")),
+            (1000, ai_goal(PlayerId::L, "AttackWithBall")),
+            (1200, ai_goal(PlayerId::L, "ChaseBall")),
+            (1400, ai_goal(PlayerId::L, "AttackWithBall")),
+            (
+                5000,
+                GameEvent::Goal {
+                    player: PlayerId::L,
+                    score_left: 1,
+                    score_right: 0,
+                },
+            ),
+        ];
+        // Left keeps flip-flopping; right never changes its mind
+        for i in 0..40 {
+            let goal = if i % 2 == 0 {
+                "ChaseBall"
+            } else {
+                "AttackWithBall"
+            };
+            events.push((6000 + i * 100, ai_goal(PlayerId::L, goal)));
+        }
+        let timeline = MatchTimeline::from_events(
+            7,
+            ("A".to_string(), "B".to_string()),
+            (1, 0),
+            30_000,
+            &events,
+        );
+
+        // Repeated right goal events merge into one span lasting the whole match
+        assert_eq!(timeline.spans[1].len(), 1);
+        assert_eq!(timeline.spans[1][0].duration_ms(), 30_000);
+        assert_eq!(timeline.scores, vec![(5000, PlayerId::L)]);
+        assert_eq!(timeline.spans[0][0].end_ms, 1000);
+        assert!(timeline.flip_flops(0) > 30);
+        assert!(timeline.flags(0)[0].starts_with("oscillating"));
+        assert!(timeline.flags(1)[0].starts_with("stalled in ChaseBall"));
+
+        let markdown = DecisionTimelineReport {
+            db_path: "test.db".to_string(),
+            timelines: vec![timeline],
+            chart_path: "chart.png".to_string(),
+        }
+        .to_markdown();
+        assert!(markdown.contains("| 1 | 7 | A | B | 1-0 |"));
+        assert!(markdown.contains("Match 7 right (B): stalled"));
+    }
+}
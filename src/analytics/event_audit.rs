@@ -5,6 +5,9 @@ use std::path::Path;
 
 use rusqlite::{Connection, Result, params};
 
+use crate::events::{GameEvent, PlayerId, parse_event};
+use crate::scoring::handicap::scaled_points;
+
 const EVENT_CODES: &[&str] = &[
     "PU", "DR", "SS", "SR", "SA", "S+", "S-", "SO", "AG", "NS", "NC",
 ];
@@ -34,6 +37,8 @@ const SQL_POSSESSION_SUM: &str =
     "SELECT match_id, SUM(possession_time) FROM player_stats GROUP BY match_id";
 const SQL_SHOT_START: &str = "SELECT match_id, time_ms, data FROM events WHERE event_type = 'SS'";
 const SQL_SHOT_RELEASE: &str = "SELECT match_id, time_ms, data FROM events WHERE event_type = 'SR'";
+const SQL_CHARGE_CALIBRATION: &str =
+    "SELECT match_id, time_ms, data FROM events WHERE event_type = 'CC'";
 
 #[derive(Debug, Clone)]
 pub struct StatSummary {
@@ -65,6 +70,8 @@ pub struct DbAudit {
     pub per_match: HashMap<String, StatSummary>,
     pub shot_charge: ShotChargeSummary,
     pub charge_durations: ShotChargeSummary,
+    /// Release charge minus ideal charge (from CC events)
+    pub charge_delta: ShotChargeSummary,
     pub low_shot_levels: Vec<(String, i64)>,
     pub possession_sum: StatSummary,
     pub steal_success_rate: Option<f64>,
@@ -249,6 +256,20 @@ fn audit_db(path: &Path) -> Result<DbAudit> {
     }
     let charge_durations = summarize_opt(&durations);
 
+    // CC data: T|CC|player|ideal|delta
+    let mut deltas = Vec::new();
+    let mut stmt = conn.prepare(SQL_CHARGE_CALIBRATION)?;
+    let rows = stmt.query_map([], |row| row.get::<_, String>(2))?;
+    for row in rows {
+        if let Some(delta) = parse_event_parts(&row?)
+            .get(4)
+            .and_then(|v| v.parse::<f64>().ok())
+        {
+            deltas.push(delta);
+        }
+    }
+    let charge_delta = summarize_opt(&deltas);
+
     let mut low_shot_levels = Vec::new();
     let mut stmt = conn.prepare(SQL_LOW_SHOT_LEVELS)?;
     let rows = stmt.query_map([], |row| {
@@ -290,6 +311,7 @@ fn audit_db(path: &Path) -> Result<DbAudit> {
         per_match,
         shot_charge,
         charge_durations,
+        charge_delta,
         low_shot_levels,
         possession_sum,
         steal_success_rate,
@@ -338,6 +360,8 @@ impl AuditReport {
         out.push_str(SQL_SHOT_START);
         out.push_str("\n");
         out.push_str(SQL_SHOT_RELEASE);
+        out.push_str("\n");
+        out.push_str(SQL_CHARGE_CALIBRATION);
         out.push_str("\n```\n\n");
 
         out.push_str("## Summary\n");
@@ -436,6 +460,24 @@ impl AuditReport {
             self.current.charge_durations.p90
         ));
 
+        out.push_str("## Charge Calibration (release - ideal, from CC events)\n");
+        out.push_str(&format!(
+            "- Base: count {} avg {:?} med {:?} p10 {:?} p90 {:?}\n",
+            self.base.charge_delta.count,
+            self.base.charge_delta.avg,
+            self.base.charge_delta.med,
+            self.base.charge_delta.p10,
+            self.base.charge_delta.p90
+        ));
+        out.push_str(&format!(
+            "- Current: count {} avg {:?} med {:?} p10 {:?} p90 {:?}\n\n",
+            self.current.charge_delta.count,
+            self.current.charge_delta.avg,
+            self.current.charge_delta.med,
+            self.current.charge_delta.p10,
+            self.current.charge_delta.p90
+        ));
+
         out.push_str("## Low-shot Levels (<= 5 shots/match)\n");
         out.push_str("- Base:\n");
         for (lvl, count) in &self.base.low_shot_levels {
@@ -466,3 +508,345 @@ impl AuditReport {
         out
     }
 }
+
+const SQL_EVENTS_IN_ORDER: &str =
+    "SELECT match_id, time_ms, data FROM events WHERE match_id IS NOT NULL ORDER BY match_id, id";
+
+/// Most violations listed individually in the markdown report
+const MAX_LISTED_VIOLATIONS: usize = 200;
+
+/// One broken invariant, with enough context to find it in the log
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvariantViolation {
+    pub match_id: i64,
+    pub time_ms: u32,
+    pub rule: &'static str,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct InvariantReport {
+    pub db_path: String,
+    pub matches_checked: usize,
+    pub events_checked: usize,
+    pub violations: Vec<InvariantViolation>,
+}
+
+/// Check one match's events (in logged order) against the logging invariants:
+/// - `score_monotonic`: scores never go down, and each goal adds exactly one point
+///   to the scorer's side (ResetScores resets the baseline; Handicap events add the
+///   head start and scale the point value)
+/// - `possession`: releases (drop/shot) come from the current holder, steals take
+///   the ball from the other player
+/// - `double_holder`: nobody picks up the ball while the other player holds it
+/// - `final_score`: the MatchEnd score matches the logged goals
+pub fn check_match_invariants(
+    match_id: i64,
+    events: &[(u32, GameEvent)],
+) -> Vec<InvariantViolation> {
+    let mut violations = Vec::new();
+    let mut violation = |time_ms: u32, rule: &'static str, detail: String| {
+        violations.push(InvariantViolation {
+            match_id,
+            time_ms,
+            rule,
+            detail,
+        });
+    };
+
+    let mut score = (0u32, 0u32);
+    let mut holder: Option<PlayerId> = None;
+    // Points per goal [left, right] (changed by Handicap events)
+    let mut goal_points = [1u32, 1u32];
+
+    for (time_ms, event) in events {
+        let time_ms = *time_ms;
+        match event {
+            GameEvent::Goal {
+                player,
+                score_left,
+                score_right,
+            } => {
+                let expected = match player {
+                    PlayerId::L => (score.0 + goal_points[0], score.1),
+                    PlayerId::R => (score.0, score.1 + goal_points[1]),
+                };
+                if *score_left < score.0 || *score_right < score.1 {
+                    violation(
+                        time_ms,
+                        "score_monotonic",
+                        format!(
+                            "score went from {}-{} to {}-{}",
+                            score.0, score.1, score_left, score_right
+                        ),
+                    );
+                } else if (*score_left, *score_right) != expected {
+                    violation(
+                        time_ms,
+                        "score_monotonic",
+                        format!(
+                            "goal by {} moved score {}-{} to {}-{} (expected {}-{})",
+                            player,
+                            score.0,
+                            score.1,
+                            score_left,
+                            score_right,
+                            expected.0,
+                            expected.1
+                        ),
+                    );
+                }
+                score = (*score_left, *score_right);
+                // The ball resets after every goal
+                holder = None;
+            }
+            GameEvent::ResetScores => {
+                score = (0, 0);
+                holder = None;
+            }
+            GameEvent::Handicap {
+                player,
+                head_start,
+                shot_value,
+                ..
+            } => {
+                let points = scaled_points(1, *shot_value);
+                match player {
+                    PlayerId::L => {
+                        score.0 = *head_start;
+                        goal_points[0] = points;
+                    }
+                    PlayerId::R => {
+                        score.1 = *head_start;
+                        goal_points[1] = points;
+                    }
+                }
+            }
+            GameEvent::MatchStart { .. }
+            | GameEvent::ResetBall
+            | GameEvent::LevelChange { .. }
+            | GameEvent::BallStuck { .. } => {
+                holder = None;
+            }
+            GameEvent::Pickup { player } => {
+                match holder {
+                    Some(current) if current != *player => violation(
+                        time_ms,
+                        "double_holder",
+                        format!("{} picked up the ball while {} holds it", player, current),
+                    ),
+                    _ => {}
+                }
+                holder = Some(*player);
+            }
+            GameEvent::StealSuccess { attacker } => {
+                let victim = match attacker {
+                    PlayerId::L => PlayerId::R,
+                    PlayerId::R => PlayerId::L,
+                };
+                if holder != Some(victim) {
+                    violation(
+                        time_ms,
+                        "possession",
+                        format!(
+                            "steal by {} but holder is {}",
+                            attacker,
+                            holder_label(holder)
+                        ),
+                    );
+                }
+                holder = Some(*attacker);
+            }
+            GameEvent::Drop { player } | GameEvent::ShotRelease { player, .. } => {
+                if holder != Some(*player) {
+                    violation(
+                        time_ms,
+                        "possession",
+                        format!(
+                            "{} by {} but holder is {}",
+                            event.type_code(),
+                            player,
+                            holder_label(holder)
+                        ),
+                    );
+                }
+                holder = None;
+            }
+            GameEvent::MatchEnd {
+                score_left,
+                score_right,
+                ..
+            } => {
+                if (*score_left, *score_right) != score {
+                    violation(
+                        time_ms,
+                        "final_score",
+                        format!(
+                            "match ended {}-{} but goals add up to {}-{}",
+                            score_left, score_right, score.0, score.1
+                        ),
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    violations
+}
+
+fn holder_label(holder: Option<PlayerId>) -> String {
+    holder.map_or_else(|| "nobody".to_string(), |p| p.to_string())
+}
+
+/// Run the invariant checks over every match in a DB
+pub fn run_invariant_audit(path: &Path) -> Result<InvariantReport> {
+    let conn = Connection::open(path)?;
+    let mut stmt = conn.prepare(SQL_EVENTS_IN_ORDER)?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, u32>(1)?,
+            row.get::<_, String>(2)?,
+        ))
+    })?;
+
+    let mut report = InvariantReport {
+        db_path: path.display().to_string(),
+        matches_checked: 0,
+        events_checked: 0,
+        violations: Vec::new(),
+    };
+    let mut current: Option<i64> = None;
+    let mut events: Vec<(u32, GameEvent)> = Vec::new();
+    for row in rows {
+        let (match_id, time_ms, data) = row?;
+        if current != Some(match_id) {
+            if let Some(prev) = current {
+                report
+                    .violations
+                    .extend(check_match_invariants(prev, &events));
+                report.matches_checked += 1;
+            }
+            current = Some(match_id);
+            events.clear();
+        }
+        // Use the DB timestamp; the serialized one wraps at 100s
+        if let Some((_, event)) = parse_event(&data) {
+            events.push((time_ms, event));
+            report.events_checked += 1;
+        }
+    }
+    if let Some(prev) = current {
+        report
+            .violations
+            .extend(check_match_invariants(prev, &events));
+        report.matches_checked += 1;
+    }
+
+    Ok(report)
+}
+
+impl InvariantReport {
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        out.push_str("# Event Invariant Report\n\n");
+        out.push_str(&format!("DB: `{}`\n\n", self.db_path));
+        out.push_str(&format!(
+            "Checked {} events across {} matches: {} violation(s)\n\n",
+            self.events_checked,
+            self.matches_checked,
+            self.violations.len()
+        ));
+
+        if self.violations.is_empty() {
+            return out;
+        }
+
+        let mut by_rule: HashMap<&str, usize> = HashMap::new();
+        for v in &self.violations {
+            *by_rule.entry(v.rule).or_default() += 1;
+        }
+        let mut by_rule: Vec<_> = by_rule.into_iter().collect();
+        by_rule.sort();
+        out.push_str("## By Rule\n");
+        for (rule, count) in by_rule {
+            out.push_str(&format!("- {}: {}\n", rule, count));
+        }
+        out.push_str("\n");
+
+        out.push_str("## Violations\n");
+        out.push_str("| Match | Time (ms) | Rule | Detail |\n");
+        out.push_str("|---|---|---|---|\n");
+        for v in self.violations.iter().take(MAX_LISTED_VIOLATIONS) {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                v.match_id, v.time_ms, v.rule, v.detail
+            ));
+        }
+        if self.violations.len() > MAX_LISTED_VIOLATIONS {
+            out.push_str(&format!(
+                "\n...and {} more\n",
+                self.violations.len() - MAX_LISTED_VIOLATIONS
+            ));
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pickup(player: PlayerId) -> GameEvent {
+        GameEvent::Pickup { player }
+    }
+
+    fn goal(player: PlayerId, score_left: u32, score_right: u32) -> GameEvent {
+        GameEvent::Goal {
+            player,
+            score_left,
+            score_right,
+        }
+    }
+
+    #[test]
+    fn test_invariants_flag_double_holder_and_score_jump() {
+        let events = vec![
+            (100, pickup(PlayerId::L)),
+            (
+                200,
+                GameEvent::StealSuccess {
+                    attacker: PlayerId::R,
+                },
+            ),
+            (200, pickup(PlayerId::R)),
+            (
+                300,
+                GameEvent::ShotRelease {
+                    player: PlayerId::R,
+                    charge: 0.5,
+                    angle: 60.0,
+                    power: 500.0,
+                },
+            ),
+            (400, goal(PlayerId::R, 0, 1)),
+        ];
+        assert!(check_match_invariants(1, &events).is_empty());
+
+        let bad = vec![
+            (100, pickup(PlayerId::L)),
+            (150, pickup(PlayerId::R)),
+            (400, goal(PlayerId::L, 2, 0)),
+        ];
+        let rules: Vec<_> = check_match_invariants(7, &bad)
+            .iter()
+            .map(|v| (v.match_id, v.time_ms, v.rule))
+            .collect();
+        assert_eq!(
+            rules,
+            vec![(7, 150, "double_holder"), (7, 400, "score_monotonic")]
+        );
+    }
+}
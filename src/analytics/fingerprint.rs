@@ -0,0 +1,528 @@
+//! Per-profile behavioral fingerprints for a simulation DB.
+//!
+//! Reduces each AI profile to a few behavior features (how far out it shoots, how
+//! quickly it shoots after picking up, how often it goes for steals, how many
+//! platform heights it uses) and groups profiles whose fingerprints are nearly
+//! identical, to spot redundant or degenerate profiles.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use rusqlite::{Connection, Result};
+
+use crate::constants::LEVELS_FILE;
+use crate::events::{GameEvent, PlayerId, parse_event};
+use crate::helpers::basket_x_from_offset;
+use crate::levels::LevelDatabase;
+
+const SQL_MATCHES: &str = "SELECT id, level_name, left_profile, right_profile FROM matches";
+const SQL_FINGERPRINT_EVENTS: &str = "SELECT match_id, time_ms, data FROM events \
+     WHERE event_type IN ('PU', 'SS', 'SA', 'T') AND match_id IS NOT NULL \
+     ORDER BY match_id, id";
+
+/// Height band (px) used to bucket grounded positions into platforms
+const PLATFORM_BAND_HEIGHT: f32 = 50.0;
+/// Vertical speed below which a tick counts as standing on something
+const GROUNDED_VEL_Y: f32 = 1.0;
+/// Profiles closer than this (in z-score units) land in the same cluster
+const CLUSTER_DISTANCE: f64 = 0.5;
+
+/// Behavior features for one profile (None when the profile never did the thing)
+#[derive(Debug, Clone)]
+pub struct ProfileFingerprint {
+    pub profile: String,
+    pub matches: usize,
+    pub possessions: usize,
+    pub shots: usize,
+    /// Average distance from shot start to the target basket
+    pub avg_shot_distance: Option<f64>,
+    /// Average seconds from pickup to the first shot of that possession
+    pub time_to_first_shot: Option<f64>,
+    /// Steal attempts per opponent possession
+    pub steal_attempts_per_possession: Option<f64>,
+    /// Shannon entropy (bits) of grounded height bands
+    pub platform_entropy: Option<f64>,
+}
+
+impl ProfileFingerprint {
+    fn features(&self) -> [Option<f64>; 4] {
+        [
+            self.avg_shot_distance,
+            self.time_to_first_shot,
+            self.steal_attempts_per_possession,
+            self.platform_entropy,
+        ]
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FingerprintReport {
+    pub db_path: String,
+    pub fingerprints: Vec<ProfileFingerprint>,
+    /// Groups of 2+ profiles with near-identical fingerprints
+    pub clusters: Vec<Vec<String>>,
+    /// Closest profile pairs by fingerprint distance (ascending)
+    pub nearest_pairs: Vec<(String, String, f64)>,
+    /// (profile, reason) for profiles that never shoot, steal or leave the floor
+    pub degenerate: Vec<(String, String)>,
+}
+
+/// Raw per-profile counts, accumulated match by match
+#[derive(Debug, Default)]
+struct ProfileAccumulator {
+    matches: usize,
+    possessions: usize,
+    opponent_possessions: usize,
+    steal_attempts: usize,
+    shot_distances: Vec<f64>,
+    first_shot_delays: Vec<f64>,
+    height_bands: HashMap<i32, usize>,
+}
+
+impl ProfileAccumulator {
+    fn finish(self, profile: String) -> ProfileFingerprint {
+        let mean = |values: &[f64]| {
+            (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+        };
+        let grounded: usize = self.height_bands.values().sum();
+        let platform_entropy = (grounded > 0).then(|| {
+            self.height_bands
+                .values()
+                .map(|&count| {
+                    let p = count as f64 / grounded as f64;
+                    -p * p.log2()
+                })
+                .sum::<f64>()
+        });
+        ProfileFingerprint {
+            profile,
+            matches: self.matches,
+            possessions: self.possessions,
+            shots: self.shot_distances.len(),
+            avg_shot_distance: mean(&self.shot_distances),
+            time_to_first_shot: mean(&self.first_shot_delays),
+            steal_attempts_per_possession: (self.opponent_possessions > 0)
+                .then(|| self.steal_attempts as f64 / self.opponent_possessions as f64),
+            platform_entropy,
+        }
+    }
+}
+
+fn side_index(player: PlayerId) -> usize {
+    match player {
+        PlayerId::L => 0,
+        PlayerId::R => 1,
+    }
+}
+
+/// Fold one match's events into the two profiles' accumulators.
+/// `targets` is the basket each side shoots at: [left player's, right player's].
+fn observe_match(
+    mut sides: [&mut ProfileAccumulator; 2],
+    targets: Option<[(f64, f64); 2]>,
+    events: &[(u32, GameEvent)],
+) {
+    for side in sides.iter_mut() {
+        side.matches += 1;
+    }
+
+    // Pickup time of the current possession, until its first shot
+    let mut possession_start: [Option<u32>; 2] = [None, None];
+
+    for (time_ms, event) in events {
+        match event {
+            GameEvent::Pickup { player } => {
+                let idx = side_index(*player);
+                sides[idx].possessions += 1;
+                sides[1 - idx].opponent_possessions += 1;
+                possession_start = [None, None];
+                possession_start[idx] = Some(*time_ms);
+            }
+            GameEvent::ShotStart { player, pos, .. } => {
+                let idx = side_index(*player);
+                if let Some(start) = possession_start[idx].take() {
+                    sides[idx]
+                        .first_shot_delays
+                        .push(time_ms.saturating_sub(start) as f64 / 1000.0);
+                }
+                if let Some(targets) = targets {
+                    let (tx, ty) = targets[idx];
+                    let dx = pos.0 as f64 - tx;
+                    let dy = pos.1 as f64 - ty;
+                    sides[idx].shot_distances.push((dx * dx + dy * dy).sqrt());
+                }
+            }
+            GameEvent::StealAttempt { attacker } => {
+                sides[side_index(*attacker)].steal_attempts += 1;
+            }
+            GameEvent::Tick {
+                left_pos,
+                left_vel,
+                right_pos,
+                right_vel,
+                ..
+            } => {
+                for (idx, (pos, vel)) in [(left_pos, left_vel), (right_pos, right_vel)]
+                    .into_iter()
+                    .enumerate()
+                {
+                    if vel.1.abs() < GROUNDED_VEL_Y {
+                        let band = (pos.1 / PLATFORM_BAND_HEIGHT).floor() as i32;
+                        *sides[idx].height_bands.entry(band).or_default() += 1;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Z-score each feature across profiles (missing values sit at the mean)
+fn normalized_features(fingerprints: &[ProfileFingerprint]) -> Vec<[f64; 4]> {
+    let mut normalized = vec![[0.0; 4]; fingerprints.len()];
+    for feature in 0..4 {
+        let values: Vec<f64> = fingerprints
+            .iter()
+            .filter_map(|fp| fp.features()[feature])
+            .collect();
+        if values.len() < 2 {
+            continue;
+        }
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        let std =
+            (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64).sqrt();
+        if std <= f64::EPSILON {
+            continue;
+        }
+        for (row, fp) in normalized.iter_mut().zip(fingerprints) {
+            if let Some(value) = fp.features()[feature] {
+                row[feature] = (value - mean) / std;
+            }
+        }
+    }
+    normalized
+}
+
+/// Single-linkage clusters (size 2+) and all pairwise distances, closest first
+fn cluster_profiles(
+    fingerprints: &[ProfileFingerprint],
+) -> (Vec<Vec<String>>, Vec<(String, String, f64)>) {
+    let features = normalized_features(fingerprints);
+    let mut pairs = Vec::new();
+    for i in 0..fingerprints.len() {
+        for j in (i + 1)..fingerprints.len() {
+            let distance = features[i]
+                .iter()
+                .zip(&features[j])
+                .map(|(a, b)| (a - b).powi(2))
+                .sum::<f64>()
+                .sqrt();
+            pairs.push((i, j, distance));
+        }
+    }
+    pairs.sort_by(|a, b| a.2.total_cmp(&b.2));
+
+    // Union-find over pairs under the threshold
+    let mut parent: Vec<usize> = (0..fingerprints.len()).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+    for &(i, j, distance) in &pairs {
+        if distance < CLUSTER_DISTANCE {
+            let (ri, rj) = (root(&mut parent, i), root(&mut parent, j));
+            parent[ri.max(rj)] = ri.min(rj);
+        }
+    }
+    let mut groups: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+    for i in 0..fingerprints.len() {
+        let r = root(&mut parent, i);
+        groups
+            .entry(r)
+            .or_default()
+            .push(fingerprints[i].profile.clone());
+    }
+    let clusters = groups.into_values().filter(|g| g.len() > 1).collect();
+
+    let pairs = pairs
+        .into_iter()
+        .map(|(i, j, d)| {
+            (
+                fingerprints[i].profile.clone(),
+                fingerprints[j].profile.clone(),
+                d,
+            )
+        })
+        .collect();
+    (clusters, pairs)
+}
+
+fn degenerate_reasons(fp: &ProfileFingerprint) -> Vec<String> {
+    let mut reasons = Vec::new();
+    if fp.possessions > 0 && fp.shots == 0 {
+        reasons.push(format!("never shot in {} possessions", fp.possessions));
+    }
+    if fp.steal_attempts_per_possession == Some(0.0) {
+        reasons.push("never attempted a steal".to_string());
+    }
+    if fp.platform_entropy == Some(0.0) {
+        reasons.push("never left its starting height".to_string());
+    }
+    reasons
+}
+
+fn build_report(
+    db_path: String,
+    accumulators: BTreeMap<String, ProfileAccumulator>,
+) -> FingerprintReport {
+    let fingerprints: Vec<ProfileFingerprint> = accumulators
+        .into_iter()
+        .map(|(profile, acc)| acc.finish(profile))
+        .collect();
+    let (clusters, nearest_pairs) = cluster_profiles(&fingerprints);
+    let degenerate = fingerprints
+        .iter()
+        .flat_map(|fp| {
+            degenerate_reasons(fp)
+                .into_iter()
+                .map(|reason| (fp.profile.clone(), reason))
+        })
+        .collect();
+    FingerprintReport {
+        db_path,
+        fingerprints,
+        clusters,
+        nearest_pairs,
+        degenerate,
+    }
+}
+
+pub fn run_fingerprint_analysis(db_path: &Path) -> Result<FingerprintReport> {
+    let conn = Connection::open(db_path)?;
+
+    let level_db = LevelDatabase::load_from_file(LEVELS_FILE);
+    let mut level_baskets = HashMap::<String, [(f64, f64); 2]>::new();
+    for level in level_db.all() {
+        let (left_x, right_x) = basket_x_from_offset(level.basket_push_in);
+        let basket_y = (level.floor_y + level.basket_height) as f64;
+        // Left player shoots at the right basket and vice versa
+        level_baskets.insert(
+            level.name.clone(),
+            [(right_x as f64, basket_y), (left_x as f64, basket_y)],
+        );
+    }
+
+    let mut matches = HashMap::<i64, (String, String, String)>::new();
+    let mut stmt = conn.prepare(SQL_MATCHES)?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            (
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+            ),
+        ))
+    })?;
+    for row in rows {
+        let (id, info) = row?;
+        matches.insert(id, info);
+    }
+
+    let mut accumulators = BTreeMap::<String, ProfileAccumulator>::new();
+    let mut observe = |match_id: i64, events: &[(u32, GameEvent)]| {
+        let Some((level_name, left, right)) = matches.get(&match_id) else {
+            return;
+        };
+        let targets = level_baskets.get(level_name).copied();
+        if left == right {
+            // Mirror match: both sides feed the same fingerprint
+            let mut other = ProfileAccumulator::default();
+            let acc = accumulators.entry(left.clone()).or_default();
+            observe_match([&mut *acc, &mut other], targets, events);
+            merge(acc, other);
+        } else {
+            let mut left_acc = accumulators.remove(left).unwrap_or_default();
+            let right_acc = accumulators.entry(right.clone()).or_default();
+            observe_match([&mut left_acc, right_acc], targets, events);
+            accumulators.insert(left.clone(), left_acc);
+        }
+    };
+
+    let mut stmt = conn.prepare(SQL_FINGERPRINT_EVENTS)?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, u32>(1)?,
+            row.get::<_, String>(2)?,
+        ))
+    })?;
+    let mut current: Option<i64> = None;
+    let mut events: Vec<(u32, GameEvent)> = Vec::new();
+    for row in rows {
+        let (match_id, time_ms, data) = row?;
+        if current != Some(match_id) {
+            if let Some(prev) = current {
+                observe(prev, &events);
+            }
+            current = Some(match_id);
+            events.clear();
+        }
+        if let Some((_, event)) = parse_event(&data) {
+            events.push((time_ms, event));
+        }
+    }
+    if let Some(prev) = current {
+        observe(prev, &events);
+    }
+
+    Ok(build_report(db_path.display().to_string(), accumulators))
+}
+
+fn merge(into: &mut ProfileAccumulator, other: ProfileAccumulator) {
+    into.matches += other.matches;
+    into.possessions += other.possessions;
+    into.opponent_possessions += other.opponent_possessions;
+    into.steal_attempts += other.steal_attempts;
+    into.shot_distances.extend(other.shot_distances);
+    into.first_shot_delays.extend(other.first_shot_delays);
+    for (band, count) in other.height_bands {
+        *into.height_bands.entry(band).or_default() += count;
+    }
+}
+
+fn fmt_opt(value: Option<f64>, precision: usize) -> String {
+    value.map_or_else(|| "-".to_string(), |v| format!("{:.*}", precision, v))
+}
+
+impl FingerprintReport {
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        out.push_str("# Profile Fingerprint Report\n\n");
+        out.push_str(&format!("DB: `{}`\n\n", self.db_path));
+
+        out.push_str("## Fingerprints\n");
+        out.push_str(
+            "| Profile | Matches | Possessions | Shots | Shot dist | Pickup->shot (s) | Steals/poss | Platform entropy |\n",
+        );
+        out.push_str("|---|---|---|---|---|---|---|---|\n");
+        for fp in &self.fingerprints {
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} | {} | {} | {} |\n",
+                fp.profile,
+                fp.matches,
+                fp.possessions,
+                fp.shots,
+                fmt_opt(fp.avg_shot_distance, 0),
+                fmt_opt(fp.time_to_first_shot, 2),
+                fmt_opt(fp.steal_attempts_per_possession, 2),
+                fmt_opt(fp.platform_entropy, 2),
+            ));
+        }
+        out.push_str("\n");
+
+        out.push_str(&format!(
+            "## Similar Profiles (z-distance < {})\n",
+            CLUSTER_DISTANCE
+        ));
+        if self.clusters.is_empty() {
+            out.push_str("- None\n");
+        }
+        for cluster in &self.clusters {
+            out.push_str(&format!("- {}\n", cluster.join(", ")));
+        }
+        out.push_str("\n");
+
+        out.push_str("## Nearest Pairs\n");
+        for (a, b, distance) in self.nearest_pairs.iter().take(10) {
+            out.push_str(&format!("- {} / {}: {:.3}\n", a, b, distance));
+        }
+        out.push_str("\n");
+
+        out.push_str("## Degenerate Behavior\n");
+        if self.degenerate.is_empty() {
+            out.push_str("- None\n");
+        }
+        for (profile, reason) in &self.degenerate {
+            out.push_str(&format!("- {}: {}\n", profile, reason));
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shooter_events(shot_x: f32) -> Vec<(u32, GameEvent)> {
+        vec![
+            (
+                0,
+                GameEvent::Pickup {
+                    player: PlayerId::L,
+                },
+            ),
+            (
+                500,
+                GameEvent::ShotStart {
+                    player: PlayerId::L,
+                    pos: (shot_x, 0.0),
+                    quality: 0.5,
+                },
+            ),
+            (
+                600,
+                GameEvent::StealAttempt {
+                    attacker: PlayerId::R,
+                },
+            ),
+            (
+                700,
+                GameEvent::Pickup {
+                    player: PlayerId::R,
+                },
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_fingerprints_cluster_identical_profiles() {
+        let targets = Some([(100.0, 0.0), (-100.0, 0.0)]);
+        let mut accumulators = BTreeMap::<String, ProfileAccumulator>::new();
+        for (left, right, shot_x) in [
+            ("A", "Camper", 0.0),
+            ("B", "Camper", 0.0),
+            ("C", "Camper", 90.0),
+        ] {
+            let mut left_acc = ProfileAccumulator::default();
+            let mut right_acc = accumulators.remove(right).unwrap_or_default();
+            observe_match(
+                [&mut left_acc, &mut right_acc],
+                targets,
+                &shooter_events(shot_x),
+            );
+            accumulators.insert(left.to_string(), left_acc);
+            accumulators.insert(right.to_string(), right_acc);
+        }
+
+        let report = build_report("test.db".to_string(), accumulators);
+        let a = &report.fingerprints[0];
+        assert_eq!(a.profile, "A");
+        assert_eq!(a.avg_shot_distance, Some(100.0));
+        assert_eq!(a.time_to_first_shot, Some(0.5));
+        assert_eq!(a.steal_attempts_per_possession, Some(0.0));
+
+        assert_eq!(
+            report.clusters,
+            vec![vec!["A".to_string(), "B".to_string()]]
+        );
+        assert!(report.degenerate.contains(&(
+            "Camper".to_string(),
+            "never shot in 3 possessions".to_string()
+        )));
+    }
+}
@@ -5,7 +5,7 @@ use std::path::Path;
 
 use rusqlite::{Connection, Result};
 
-use crate::constants::{ARENA_FLOOR_Y, LEVELS_FILE};
+use crate::constants::LEVELS_FILE;
 use crate::helpers::basket_x_from_offset;
 use crate::levels::LevelDatabase;
 
@@ -322,7 +322,7 @@ pub fn run_focused_analysis(db_path: &Path) -> Result<FocusedReport> {
     let mut level_baskets = HashMap::<String, (f64, f64, f64)>::new();
     for level in level_db.all() {
         let (left_x, right_x) = basket_x_from_offset(level.basket_push_in);
-        let basket_y = ARENA_FLOOR_Y + level.basket_height;
+        let basket_y = level.floor_y + level.basket_height;
         level_baskets.insert(
             level.name.clone(),
             (left_x as f64, right_x as f64, basket_y as f64),
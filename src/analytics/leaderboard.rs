@@ -12,6 +12,8 @@ pub struct ProfileRanking {
     pub shot_accuracy: f32,
     pub steals_per_match: f32,
     pub goal_differential: f32,
+    /// Rim-camping stays per match
+    pub camping_per_match: f32,
     pub matches_played: u32,
 }
 
@@ -35,6 +37,7 @@ impl Leaderboard {
                 shot_accuracy: p.shot_accuracy(),
                 steals_per_match: p.steals_per_match(),
                 goal_differential: p.goal_differential(),
+                camping_per_match: p.rim_camping_per_match(),
                 matches_played: p.matches_played,
             })
             .collect();
@@ -78,12 +81,16 @@ impl Leaderboard {
     pub fn format_table(&self) -> String {
         let mut output = String::new();
         output.push_str("\nPROFILE LEADERBOARD:\n");
-        output.push_str("  Rank  Profile         Win%   Goals/Match  Accuracy  Steals  +/-\n");
-        output.push_str("  ────────────────────────────────────────────────────────────────\n");
+        output.push_str(
+            "  Rank  Profile         Win%   Goals/Match  Accuracy  Steals  +/-    Camp\n",
+        );
+        output.push_str(
+            "  ───────────────────────────────────────────────────────────────────────\n",
+        );
 
         for r in &self.rankings {
             output.push_str(&format!(
-                "  {:>2}.   {:<14}  {:>5.1}%      {:>5.1}     {:>5.1}%    {:>4.1}  {:>+5.1}\n",
+                "  {:>2}.   {:<14}  {:>5.1}%      {:>5.1}     {:>5.1}%    {:>4.1}  {:>+5.1}  {:>4.1}\n",
                 r.rank,
                 &r.profile[..r.profile.len().min(14)],
                 r.win_rate * 100.0,
@@ -91,6 +98,7 @@ impl Leaderboard {
                 r.shot_accuracy * 100.0,
                 r.steals_per_match,
                 r.goal_differential,
+                r.camping_per_match,
             ));
         }
 
@@ -0,0 +1,299 @@
+//! Per-level difficulty and fairness ratings from simulated matches.
+//!
+//! For each level: average score margin, left/right win bias, BallStuck events
+//! per match and the average time until the first shot release. Those give the
+//! level a difficulty (`LevelRating::new`) and flag levels that favor one side.
+//! The ratings are written to `config/level_ratings.json` for the level select,
+//! and the report suggests a playlist of fair levels that alternates difficulty.
+//! Side bias assumes profiles were assigned to both sides evenly (tournaments
+//! and random pairings do this).
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use rusqlite::{Connection, Result};
+
+use crate::levels::{LevelDatabase, LevelDifficulty, LevelRating, LevelRatings};
+
+const SQL_MATCHES: &str = "SELECT id, level_name, score_left, score_right, winner FROM matches";
+const SQL_STUCK_COUNTS: &str = "SELECT match_id, COUNT(*) FROM events \
+     WHERE event_type = 'BS' AND match_id IS NOT NULL GROUP BY match_id";
+const SQL_FIRST_SHOTS: &str = "SELECT match_id, MIN(time_ms) FROM events \
+     WHERE event_type = 'SR' AND match_id IS NOT NULL GROUP BY match_id";
+
+/// What one match contributes to its level's rating
+#[derive(Debug, Clone, Default)]
+pub struct RatedMatch {
+    pub level: String,
+    pub score_left: u32,
+    pub score_right: u32,
+    /// "left", "right" or "tie"
+    pub winner: String,
+    pub stuck: u32,
+    /// Game-clock time of the first shot release (None = nobody shot)
+    pub first_shot_ms: Option<i64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LevelRatingReport {
+    pub db_path: String,
+    /// (level name, rating), sorted by name
+    pub levels: Vec<(String, LevelRating)>,
+}
+
+/// Rate each level from its matches
+pub fn rate_levels(matches: &[RatedMatch]) -> Vec<(String, LevelRating)> {
+    let mut by_level = BTreeMap::<&str, Vec<&RatedMatch>>::new();
+    for m in matches {
+        by_level.entry(m.level.as_str()).or_default().push(m);
+    }
+
+    by_level
+        .into_iter()
+        .map(|(level, matches)| {
+            let count = matches.len() as f32;
+            let avg_margin = matches
+                .iter()
+                .map(|m| m.score_left.abs_diff(m.score_right) as f32)
+                .sum::<f32>()
+                / count;
+            let left_wins = matches.iter().filter(|m| m.winner == "left").count();
+            let right_wins = matches.iter().filter(|m| m.winner == "right").count();
+            let left_win_bias = match left_wins + right_wins {
+                0 => 0.0,
+                decided => left_wins as f32 / decided as f32 - 0.5,
+            };
+            let stuck_per_match = matches.iter().map(|m| m.stuck).sum::<u32>() as f32 / count;
+            let first_shots: Vec<f32> = matches
+                .iter()
+                .filter_map(|m| m.first_shot_ms)
+                .map(|ms| ms as f32 / 1000.0)
+                .collect();
+            let avg_first_shot_secs = (!first_shots.is_empty())
+                .then(|| first_shots.iter().sum::<f32>() / first_shots.len() as f32);
+            (
+                level.to_string(),
+                LevelRating::new(
+                    matches.len() as u32,
+                    avg_margin,
+                    left_win_bias,
+                    stuck_per_match,
+                    avg_first_shot_secs,
+                ),
+            )
+        })
+        .collect()
+}
+
+pub fn run_level_ratings(db_path: &Path) -> Result<LevelRatingReport> {
+    let conn = Connection::open(db_path)?;
+
+    let mut stuck = HashMap::<i64, u32>::new();
+    let mut stmt = conn.prepare(SQL_STUCK_COUNTS)?;
+    let rows = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, u32>(1)?)))?;
+    for row in rows {
+        let (match_id, count) = row?;
+        stuck.insert(match_id, count);
+    }
+
+    let mut first_shots = HashMap::<i64, i64>::new();
+    let mut stmt = conn.prepare(SQL_FIRST_SHOTS)?;
+    let rows = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)))?;
+    for row in rows {
+        let (match_id, time_ms) = row?;
+        first_shots.insert(match_id, time_ms);
+    }
+
+    let mut matches = Vec::new();
+    let mut stmt = conn.prepare(SQL_MATCHES)?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            RatedMatch {
+                level: row.get(1)?,
+                score_left: row.get(2)?,
+                score_right: row.get(3)?,
+                winner: row.get(4)?,
+                ..Default::default()
+            },
+        ))
+    })?;
+    for row in rows {
+        let (id, mut rated) = row?;
+        rated.stuck = stuck.get(&id).copied().unwrap_or(0);
+        rated.first_shot_ms = first_shots.get(&id).copied();
+        matches.push(rated);
+    }
+
+    Ok(LevelRatingReport {
+        db_path: db_path.display().to_string(),
+        levels: rate_levels(&matches),
+    })
+}
+
+impl LevelRatingReport {
+    /// Merge these ratings into `ratings` by level id.
+    /// Returns the names of levels not found in `level_db` (not stored).
+    pub fn apply(&self, level_db: &LevelDatabase, ratings: &mut LevelRatings) -> Vec<String> {
+        let mut unknown = Vec::new();
+        for (name, rating) in &self.levels {
+            match level_db.get_by_name(name) {
+                Some(level) => {
+                    ratings.levels.insert(level.id.clone(), rating.clone());
+                }
+                None => unknown.push(name.clone()),
+            }
+        }
+        unknown
+    }
+
+    /// Levels without a side bias, cycling easy -> medium -> hard so difficulty
+    /// alternates; closest games first within each difficulty
+    pub fn balanced_playlist(&self) -> Vec<&str> {
+        let mut buckets: Vec<Vec<&(String, LevelRating)>> = [
+            LevelDifficulty::Easy,
+            LevelDifficulty::Medium,
+            LevelDifficulty::Hard,
+        ]
+        .iter()
+        .map(|difficulty| {
+            let mut levels: Vec<_> = self
+                .levels
+                .iter()
+                .filter(|(_, r)| r.difficulty == *difficulty && !r.is_biased())
+                .collect();
+            // Popped from the back
+            levels.sort_by(|a, b| b.1.avg_margin.total_cmp(&a.1.avg_margin));
+            levels
+        })
+        .collect();
+
+        let mut playlist = Vec::new();
+        while buckets.iter().any(|b| !b.is_empty()) {
+            for bucket in &mut buckets {
+                if let Some((name, _)) = bucket.pop() {
+                    playlist.push(name.as_str());
+                }
+            }
+        }
+        playlist
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        out.push_str("# Level Ratings\n\n");
+        out.push_str(&format!("DB: `{}`\n\n", self.db_path));
+        if self.levels.is_empty() {
+            out.push_str("No matches recorded (run a simulation with --db).\n");
+            return out;
+        }
+
+        out.push_str("## Levels\n");
+        out.push_str(
+            "| Level | Matches | Avg margin | Left win bias | Stuck/match | First shot | Difficulty |\n",
+        );
+        out.push_str("|---|---|---|---|---|---|---|\n");
+        for (name, rating) in &self.levels {
+            let first_shot = rating
+                .avg_first_shot_secs
+                .map_or("-".to_string(), |secs| format!("{:.1}s", secs));
+            out.push_str(&format!(
+                "| {} | {} | {:.1} | {:+.0}%{} | {:.2} | {} | {} |\n",
+                name,
+                rating.matches,
+                rating.avg_margin,
+                rating.left_win_bias * 100.0,
+                if rating.is_biased() { " (biased)" } else { "" },
+                rating.stuck_per_match,
+                first_shot,
+                rating.difficulty.name()
+            ));
+        }
+        out.push('\n');
+
+        out.push_str("## Suggested playlist\n");
+        out.push_str("Levels without a side bias, alternating difficulty:\n\n");
+        for (i, name) in self.balanced_playlist().iter().enumerate() {
+            out.push_str(&format!("{}. {}\n", i + 1, name));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rated(level: &str, left: u32, right: u32, stuck: u32, first_shot_ms: i64) -> RatedMatch {
+        RatedMatch {
+            level: level.to_string(),
+            score_left: left,
+            score_right: right,
+            winner: match left.cmp(&right) {
+                std::cmp::Ordering::Greater => "left",
+                std::cmp::Ordering::Less => "right",
+                std::cmp::Ordering::Equal => "tie",
+            }
+            .to_string(),
+            stuck,
+            first_shot_ms: Some(first_shot_ms),
+        }
+    }
+
+    #[test]
+    fn test_rate_levels_margin_bias_and_difficulty() {
+        let mut matches = Vec::new();
+        // Open: quick shots, even sides
+        for i in 0..10 {
+            matches.push(if i % 2 == 0 {
+                rated("Open", 5, 3, 0, 3000)
+            } else {
+                rated("Open", 2, 5, 0, 4000)
+            });
+        }
+        // Maze: slow to shoot, ball keeps getting stuck, left wins 8 of 10
+        for i in 0..10 {
+            matches.push(if i < 8 {
+                rated("Maze", 5, 1, 2, 15000)
+            } else {
+                rated("Maze", 1, 5, 1, 13000)
+            });
+        }
+        // Middle: medium, too few matches to trust its bias
+        matches.push(rated("Middle", 5, 0, 0, 8000));
+
+        let report = LevelRatingReport {
+            db_path: "test.db".into(),
+            levels: rate_levels(&matches),
+        };
+        let names: Vec<&str> = report.levels.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, ["Maze", "Middle", "Open"]);
+
+        let maze = &report.levels[0].1;
+        assert_eq!(maze.matches, 10);
+        assert!((maze.avg_margin - 4.0).abs() < 1e-6);
+        assert!((maze.left_win_bias - 0.3).abs() < 1e-6);
+        assert!((maze.stuck_per_match - 1.8).abs() < 1e-6);
+        assert_eq!(maze.difficulty, LevelDifficulty::Hard);
+        assert!(maze.is_biased());
+        assert_eq!(maze.tag(), "hard, L+30%");
+
+        let middle = &report.levels[1].1;
+        assert_eq!(middle.difficulty, LevelDifficulty::Medium);
+        assert!(!middle.is_biased());
+
+        let open = &report.levels[2].1;
+        assert!((open.avg_margin - 2.5).abs() < 1e-6);
+        assert!((open.avg_first_shot_secs.unwrap() - 3.5).abs() < 1e-6);
+        assert_eq!(open.difficulty, LevelDifficulty::Easy);
+        assert_eq!(open.tag(), "easy");
+
+        // Biased Maze is left out; easy before medium
+        assert_eq!(report.balanced_playlist(), ["Open", "Middle"]);
+        assert!(
+            report
+                .to_markdown()
+                .contains("| Maze | 10 | 4.0 | +30% (biased) |")
+        );
+    }
+}
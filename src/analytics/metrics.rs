@@ -34,6 +34,10 @@ pub struct ProfileMetrics {
     pub pickups: u32,
     /// Total match time (seconds)
     pub total_match_time: f32,
+    /// Rim-camping stays (defender parked on own rim, see `RimCamping`)
+    pub rim_camping: u32,
+    /// Rim-camping stays called as defensive three-seconds violations
+    pub camping_violations: u32,
 }
 
 impl ProfileMetrics {
@@ -98,6 +102,15 @@ impl ProfileMetrics {
         }
     }
 
+    /// Rim-camping stays per match
+    pub fn rim_camping_per_match(&self) -> f32 {
+        if self.matches_played == 0 {
+            0.0
+        } else {
+            self.rim_camping as f32 / self.matches_played as f32
+        }
+    }
+
     /// Goal differential per match
     pub fn goal_differential(&self) -> f32 {
         if self.matches_played == 0 {
@@ -134,6 +147,11 @@ impl ProfileMetrics {
 
         // Pickups
         self.pickups += m.pickups_for(PlayerId::L) as u32;
+
+        // Rim camping
+        let (stays, violations) = m.rim_camping_for(PlayerId::L);
+        self.rim_camping += stays as u32;
+        self.camping_violations += violations as u32;
     }
 
     /// Add stats from a match where this profile was the right player
@@ -162,6 +180,11 @@ impl ProfileMetrics {
 
         // Pickups
         self.pickups += m.pickups_for(PlayerId::R) as u32;
+
+        // Rim camping
+        let (stays, violations) = m.rim_camping_for(PlayerId::R);
+        self.rim_camping += stays as u32;
+        self.camping_violations += violations as u32;
     }
 }
 
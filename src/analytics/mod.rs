@@ -4,30 +4,59 @@
 //! generating leaderboards, and suggesting parameter changes.
 
 pub mod db_analytics;
+mod decision_timeline;
 mod defaults;
 mod event_audit;
+mod fingerprint;
 mod focused_analysis;
 mod leaderboard;
+mod level_ratings;
 mod metrics;
+mod nav_edges;
 pub mod parser;
 mod requests;
+mod sample_traces;
+mod shot_model_eval;
 pub mod suggestions;
 mod targets;
 mod training_debug;
+pub mod upload;
 
 pub use db_analytics::{
     DetailedProfileStats, ProfileAnalysis, ProfileComparison, analyze_profile, compare_profiles,
-    format_leaderboard, summarize_all_profiles,
+    format_leaderboard, summarize_all_profiles, underdog_wins,
+};
+pub use decision_timeline::{
+    DecisionTimelineReport, GoalSpan, MatchTimeline, render_timeline_chart, run_decision_timeline,
 };
 pub use defaults::{format_update_report, get_current_defaults, update_default_profiles};
-pub use event_audit::run_event_audit;
+pub use event_audit::{
+    InvariantReport, InvariantViolation, check_match_invariants, run_event_audit,
+    run_invariant_audit,
+};
+pub use fingerprint::{FingerprintReport, ProfileFingerprint, run_fingerprint_analysis};
 pub use focused_analysis::run_focused_analysis;
 pub use leaderboard::{Leaderboard, ProfileRanking};
+pub use level_ratings::{LevelRatingReport, RatedMatch, rate_levels, run_level_ratings};
 pub use metrics::{AggregateMetrics, ProfileMetrics};
+pub use nav_edges::{
+    EdgeSummary, LevelNavSummary, NavEdgeReport, run_nav_edge_report, summarize_nav_edges,
+};
 pub use parser::{ParsedMatch, parse_all_matches_from_db, parse_match_from_db};
 pub use requests::{
     AnalysisQuery, AnalysisRequest, AnalysisRequestFile, AnalysisRunReport, run_request,
 };
+pub use sample_traces::{
+    PlayerTrace, SampleTraceReport, TraceMarker, TraceSample, render_trace_chart, run_sample_traces,
+};
+pub use shot_model_eval::{
+    ShotModelEvalReport, collect_shot_samples, fit_shot_models, run_shot_model_evaluation,
+};
 pub use suggestions::{ParameterSuggestion, format_suggestions, generate_suggestions};
-pub use targets::{TargetDelta, TargetStatus, TuningTargets, default_targets, load_targets};
+pub use targets::{
+    TUNING_TARGETS_FILE, TargetDelta, TargetStatus, TuningTargets, default_targets, load_targets,
+};
 pub use training_debug::{TrainingDebugReport, run_training_debug_analysis};
+pub use upload::{
+    DEFAULT_UPLOAD_QUEUE, UploadConfig, UploadReport, upload_match_results, upload_session_summary,
+};
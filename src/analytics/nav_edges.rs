@@ -0,0 +1,256 @@
+//! Per-level nav edge success rates from logged `NavEdge` events.
+//!
+//! Each AI jump, drop, portal or launch is logged with its outcome (reached,
+//! fell, timeout). Grouping them by level and edge shows which connections in a
+//! level's nav graph the AI can't actually pull off. Node indices come from the
+//! graph build, which is deterministic per level (overtime rebuilds excepted).
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use rusqlite::{Connection, Result};
+
+use crate::constants::{NAV_EDGE_FAIL_RATE, NAV_EDGE_MIN_ATTEMPTS};
+use crate::events::{GameEvent, parse_event};
+
+const SQL_MATCH_LEVELS: &str = "SELECT id, level_name FROM matches";
+const SQL_NAV_EDGE_EVENTS: &str = "SELECT match_id, time_ms, data FROM events \
+     WHERE event_type = 'NE' AND match_id IS NOT NULL ORDER BY match_id, id";
+
+/// Outcome counts for one edge on one level
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EdgeSummary {
+    pub from: usize,
+    pub to: usize,
+    pub edge_type: String,
+    pub reached: u32,
+    pub fell: u32,
+    pub timed_out: u32,
+}
+
+impl EdgeSummary {
+    pub fn attempts(&self) -> u32 {
+        self.reached + self.fell + self.timed_out
+    }
+
+    pub fn success_rate(&self) -> f64 {
+        match self.attempts() {
+            0 => 0.0,
+            n => self.reached as f64 / n as f64,
+        }
+    }
+
+    /// Enough attempts and failing more often than the penalty threshold
+    pub fn is_chronic(&self) -> bool {
+        self.attempts() >= NAV_EDGE_MIN_ATTEMPTS
+            && 1.0 - self.success_rate() > NAV_EDGE_FAIL_RATE as f64
+    }
+}
+
+/// All edges seen on one level
+#[derive(Debug, Clone, Default)]
+pub struct LevelNavSummary {
+    pub level: String,
+    /// Sorted by success rate, worst first
+    pub edges: Vec<EdgeSummary>,
+}
+
+impl LevelNavSummary {
+    pub fn attempts(&self) -> u32 {
+        self.edges.iter().map(EdgeSummary::attempts).sum()
+    }
+
+    pub fn success_rate(&self) -> f64 {
+        let reached: u32 = self.edges.iter().map(|e| e.reached).sum();
+        match self.attempts() {
+            0 => 0.0,
+            n => reached as f64 / n as f64,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NavEdgeReport {
+    pub db_path: String,
+    pub levels: Vec<LevelNavSummary>,
+}
+
+/// Group (level, event) pairs into per-level edge summaries
+pub fn summarize_nav_edges<'a>(
+    events: impl IntoIterator<Item = (&'a str, &'a GameEvent)>,
+) -> Vec<LevelNavSummary> {
+    let mut by_level = BTreeMap::<&str, HashMap<(usize, usize), EdgeSummary>>::new();
+    for (level, event) in events {
+        let GameEvent::NavEdge {
+            from,
+            to,
+            edge_type,
+            outcome,
+            ..
+        } = event
+        else {
+            continue;
+        };
+        let summary = by_level
+            .entry(level)
+            .or_default()
+            .entry((*from, *to))
+            .or_insert_with(|| EdgeSummary {
+                from: *from,
+                to: *to,
+                edge_type: edge_type.to_string(),
+                ..Default::default()
+            });
+        match &**outcome {
+            "reached" => summary.reached += 1,
+            "fell" => summary.fell += 1,
+            _ => summary.timed_out += 1,
+        }
+    }
+
+    by_level
+        .into_iter()
+        .map(|(level, edges)| {
+            let mut edges: Vec<_> = edges.into_values().collect();
+            edges.sort_by(|a, b| {
+                a.success_rate()
+                    .total_cmp(&b.success_rate())
+                    .then(b.attempts().cmp(&a.attempts()))
+            });
+            LevelNavSummary {
+                level: level.to_string(),
+                edges,
+            }
+        })
+        .collect()
+}
+
+pub fn run_nav_edge_report(db_path: &Path) -> Result<NavEdgeReport> {
+    let conn = Connection::open(db_path)?;
+
+    let mut levels = HashMap::<i64, String>::new();
+    let mut stmt = conn.prepare(SQL_MATCH_LEVELS)?;
+    let rows = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get(1)?)))?;
+    for row in rows {
+        let (id, level) = row?;
+        levels.insert(id, level);
+    }
+
+    let mut events = Vec::new();
+    let mut stmt = conn.prepare(SQL_NAV_EDGE_EVENTS)?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, i64>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, String>(2)?,
+        ))
+    })?;
+    for row in rows {
+        let (match_id, _time_ms, data) = row?;
+        let (Some(level), Some((_, event))) = (levels.get(&match_id), parse_event(&data)) else {
+            continue;
+        };
+        events.push((level.as_str(), event));
+    }
+
+    Ok(NavEdgeReport {
+        db_path: db_path.display().to_string(),
+        levels: summarize_nav_edges(events.iter().map(|(level, event)| (*level, event))),
+    })
+}
+
+impl NavEdgeReport {
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        out.push_str("# Nav Edge Report\n\n");
+        out.push_str(&format!("DB: `{}`\n\n", self.db_path));
+        if self.levels.is_empty() {
+            out.push_str("No NavEdge events logged (record a simulation with --db).\n");
+            return out;
+        }
+
+        out.push_str("## Levels\n");
+        out.push_str("| Level | Edges | Attempts | Success | Chronic failures |\n");
+        out.push_str("|---|---|---|---|---|\n");
+        for level in &self.levels {
+            out.push_str(&format!(
+                "| {} | {} | {} | {:.0}% | {} |\n",
+                level.level,
+                level.edges.len(),
+                level.attempts(),
+                level.success_rate() * 100.0,
+                level.edges.iter().filter(|e| e.is_chronic()).count()
+            ));
+        }
+        out.push('\n');
+
+        for level in &self.levels {
+            out.push_str(&format!("## {}\n", level.level));
+            out.push_str("| Edge | Type | Attempts | Reached | Fell | Timeout | Success |\n");
+            out.push_str("|---|---|---|---|---|---|---|\n");
+            for edge in &level.edges {
+                out.push_str(&format!(
+                    "| {} -> {}{} | {} | {} | {} | {} | {} | {:.0}% |\n",
+                    edge.from,
+                    edge.to,
+                    if edge.is_chronic() { " (chronic)" } else { "" },
+                    edge.edge_type,
+                    edge.attempts(),
+                    edge.reached,
+                    edge.fell,
+                    edge.timed_out,
+                    edge.success_rate() * 100.0
+                ));
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{PlayerId, intern};
+
+    fn edge(from: usize, to: usize, outcome: &str) -> GameEvent {
+        GameEvent::NavEdge {
+            player: PlayerId::L,
+            from,
+            to,
+            edge_type: intern("jump"),
+            outcome: intern(outcome),
+        }
+    }
+
+    #[test]
+    fn test_summarize_groups_by_level_and_edge() {
+        let mut events = Vec::new();
+        for outcome in ["reached", "fell", "fell", "timeout", "fell"] {
+            events.push(("Skyway", edge(0, 3, outcome)));
+        }
+        events.push(("Skyway", edge(3, 0, "reached")));
+        events.push(("Arena", edge(0, 3, "reached")));
+        events.push((
+            "Arena",
+            GameEvent::NavComplete {
+                player: PlayerId::R,
+            },
+        ));
+
+        let levels = summarize_nav_edges(events.iter().map(|(l, e)| (*l, e)));
+        assert_eq!(levels.len(), 2);
+        assert_eq!(levels[0].level, "Arena");
+        assert_eq!(levels[0].attempts(), 1);
+
+        let skyway = &levels[1];
+        // Worst edge first
+        let worst = &skyway.edges[0];
+        assert_eq!((worst.from, worst.to), (0, 3));
+        assert_eq!((worst.reached, worst.fell, worst.timed_out), (1, 3, 1));
+        assert!(worst.is_chronic());
+        assert!(!skyway.edges[1].is_chronic());
+        // 2 of 6 attempts reached
+        assert!((skyway.success_rate() - 2.0 / 6.0).abs() < 1e-9);
+    }
+}
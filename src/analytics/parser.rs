@@ -45,6 +45,8 @@ pub struct ParsedMatch {
     pub steal_successes: Vec<(f32, PlayerId)>,
     /// Steal failures: (time, attacker)
     pub steal_failures: Vec<(f32, PlayerId)>,
+    /// Rim-camping stays: (time, defender, violation)
+    pub rim_camping: Vec<(f32, PlayerId, bool)>,
 }
 
 impl ParsedMatch {
@@ -111,6 +113,12 @@ impl ParsedMatch {
     pub fn pickups_for(&self, player: PlayerId) -> usize {
         self.pickups.iter().filter(|(_, p)| *p == player).count()
     }
+
+    /// Count rim-camping stays and violations for a player
+    pub fn rim_camping_for(&self, player: PlayerId) -> (usize, usize) {
+        let stays = self.rim_camping.iter().filter(|(_, p, _)| *p == player);
+        (stays.clone().count(), stays.filter(|(_, _, v)| *v).count())
+    }
 }
 
 /// Parse a single match from SQLite by match ID.
@@ -144,6 +152,7 @@ pub fn parse_match_from_db(db: &SimDatabase, match_id: i64) -> Option<ParsedMatc
     let mut steal_attempts = Vec::new();
     let mut steal_successes = Vec::new();
     let mut steal_failures = Vec::new();
+    let mut rim_camping = Vec::new();
 
     let events = db.get_events(match_id).ok()?;
     for event in events {
@@ -169,6 +178,9 @@ pub fn parse_match_from_db(db: &SimDatabase, match_id: i64) -> Option<ParsedMatc
             GameEvent::StealAttempt { attacker } => steal_attempts.push((time_secs, attacker)),
             GameEvent::StealSuccess { attacker } => steal_successes.push((time_secs, attacker)),
             GameEvent::StealFail { attacker } => steal_failures.push((time_secs, attacker)),
+            GameEvent::RimCamping { player, violation } => {
+                rim_camping.push((time_secs, player, violation))
+            }
             _ => {}
         }
     }
@@ -191,6 +203,7 @@ pub fn parse_match_from_db(db: &SimDatabase, match_id: i64) -> Option<ParsedMatc
         steal_attempts,
         steal_successes,
         steal_failures,
+        rim_camping,
     })
 }
 
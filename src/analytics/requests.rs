@@ -6,6 +6,8 @@ use std::path::Path;
 use rusqlite::{Connection, Result};
 use serde::{Deserialize, Serialize};
 
+use crate::db_maintenance::latest_training_db;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalysisRequestFile {
     pub requests: Vec<AnalysisRequest>,
@@ -93,7 +95,7 @@ pub fn run_request(
     let db_path = db_override
         .and_then(|p| p.to_str().map(|s| s.to_string()))
         .or_else(|| request.db_path.clone())
-        .unwrap_or_else(|| "db/training.db".to_string());
+        .unwrap_or_else(|| latest_training_db().display().to_string());
 
     let conn = Connection::open(Path::new(&db_path))?;
 
@@ -0,0 +1,541 @@
+//! Debug sample traces - per-player physics over time for one match.
+//!
+//! Reads the `debug_events` rows written from the `DebugSampleBuffer` (every
+//! `DEBUG_TICK_MS` while debug logging is on) and draws one panel per player:
+//! horizontal velocity, vertical velocity and stick input as line traces, with
+//! grounded / jumping / jump-held strips under them. Jumps, landings, pickups,
+//! shot releases, steals and goals are drawn as vertical markers on the same
+//! time axis, so a missed jump or a landing that never registers lines up with
+//! the samples around it. The PNG is wrapped in an HTML page with the legend,
+//! per-player summaries and the marker list.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use image::{Rgb, RgbImage};
+use rusqlite::Connection;
+
+use crate::events::{GameEvent, PlayerId, parse_event};
+
+type AnyResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+const SQL_SAMPLES: &str = "SELECT time_ms, player, vel_x, vel_y, input_move_x, input_jump, \
+     grounded, is_jumping FROM debug_events WHERE match_id = ?1 ORDER BY time_ms, id";
+const SQL_MARKER_EVENTS: &str = "SELECT time_ms, data FROM events \
+     WHERE match_id = ?1 AND event_type IN ('G', 'J', 'LD', 'PU', 'SR', 'S+') ORDER BY id";
+
+const CHART_WIDTH: u32 = 1400;
+const TRACK_HEIGHT: u32 = 60;
+const STRIP_HEIGHT: u32 = 6;
+const TRACK_GAP: u32 = 4;
+const PANEL_GAP: u32 = 16;
+
+const BACKGROUND: Rgb<u8> = Rgb([245, 245, 245]);
+const TRACK_BACKGROUND: Rgb<u8> = Rgb([255, 255, 255]);
+const ZERO_LINE: Rgb<u8> = Rgb([200, 200, 200]);
+
+/// Line traces, top to bottom within a panel
+const TRACKS: [(&str, Rgb<u8>); 3] = [
+    ("vel_x", Rgb([30, 90, 200])),
+    ("vel_y", Rgb([200, 60, 40])),
+    ("input_move_x", Rgb([60, 150, 60])),
+];
+/// On/off strips under the traces
+const STRIPS: [(&str, Rgb<u8>); 3] = [
+    ("grounded", Rgb([110, 80, 50])),
+    ("is_jumping", Rgb([240, 150, 30])),
+    ("input_jump", Rgb([140, 70, 190])),
+];
+/// Marker color per event code
+const MARKERS: [(&str, &str, Rgb<u8>); 6] = [
+    ("J", "Jump", Rgb([240, 150, 30])),
+    ("LD", "Land", Rgb([110, 80, 50])),
+    ("PU", "Pickup", Rgb([40, 170, 170])),
+    ("SR", "Shot release", Rgb([220, 40, 140])),
+    ("S+", "Steal", Rgb([150, 80, 200])),
+    ("G", "Goal", Rgb([20, 20, 20])),
+];
+
+fn marker_color(code: &str) -> Rgb<u8> {
+    MARKERS
+        .iter()
+        .find(|(c, ..)| *c == code)
+        .map_or(ZERO_LINE, |(.., color)| *color)
+}
+
+/// One debug sample, trimmed to what the chart draws
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TraceSample {
+    pub time_ms: u32,
+    pub vel_x: f32,
+    pub vel_y: f32,
+    pub input_move_x: f32,
+    pub input_jump: bool,
+    pub grounded: bool,
+    pub is_jumping: bool,
+}
+
+/// An event drawn as a vertical line; goals (no player) span every panel
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceMarker {
+    pub time_ms: u32,
+    pub code: &'static str,
+    pub player: Option<PlayerId>,
+}
+
+impl TraceMarker {
+    /// Marker for the events the chart shows (None for everything else)
+    pub fn from_event(time_ms: u32, event: &GameEvent) -> Option<Self> {
+        let player = match event {
+            GameEvent::Jump { player }
+            | GameEvent::Land { player }
+            | GameEvent::Pickup { player }
+            | GameEvent::ShotRelease { player, .. } => Some(*player),
+            GameEvent::StealSuccess { attacker } => Some(*attacker),
+            GameEvent::Goal { .. } => None,
+            _ => return None,
+        };
+        Some(Self {
+            time_ms,
+            code: event.type_code(),
+            player,
+        })
+    }
+}
+
+/// One player's samples for the match
+#[derive(Debug, Clone)]
+pub struct PlayerTrace {
+    pub player: PlayerId,
+    pub samples: Vec<TraceSample>,
+}
+
+impl PlayerTrace {
+    pub fn max_speed_x(&self) -> f32 {
+        self.samples
+            .iter()
+            .map(|s| s.vel_x.abs())
+            .fold(0.0, f32::max)
+    }
+
+    /// (fastest fall, fastest rise)
+    pub fn vel_y_range(&self) -> (f32, f32) {
+        self.samples
+            .iter()
+            .fold((0.0, 0.0), |(lo, hi), s| (s.vel_y.min(lo), s.vel_y.max(hi)))
+    }
+
+    /// Share of samples spent off the ground
+    pub fn airborne_share(&self) -> f32 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        let airborne = self.samples.iter().filter(|s| !s.grounded).count();
+        airborne as f32 / self.samples.len() as f32
+    }
+
+    /// Jump presses made on the ground that still hadn't left it a sample
+    /// later - the first thing to look at when jumps feel eaten
+    pub fn stuck_jumps(&self) -> usize {
+        self.samples
+            .windows(3)
+            .filter(|w| {
+                !w[0].input_jump
+                    && w[1].input_jump
+                    && w[1].grounded
+                    && w[2].grounded
+                    && !w[2].is_jumping
+            })
+            .count()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SampleTraceReport {
+    pub db_path: String,
+    pub match_id: i64,
+    pub traces: Vec<PlayerTrace>,
+    pub markers: Vec<TraceMarker>,
+    /// Chart written alongside the page
+    pub chart_path: String,
+}
+
+fn hex(color: Rgb<u8>) -> String {
+    format!("#{:02x}{:02x}{:02x}", color[0], color[1], color[2])
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+impl SampleTraceReport {
+    /// (first, last) sample time across every player
+    pub fn time_span(&self) -> (u32, u32) {
+        let times = self
+            .traces
+            .iter()
+            .flat_map(|t| &t.samples)
+            .map(|s| s.time_ms);
+        let first = times.clone().min().unwrap_or(0);
+        (first, times.max().unwrap_or(first))
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut out = String::new();
+        out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        out.push_str(&format!(
+            "<title>Debug sample traces - match {}</title>\n",
+            self.match_id
+        ));
+        out.push_str(
+            "<style>body { font-family: sans-serif; margin: 24px; } \
+             table { border-collapse: collapse; } \
+             td, th { border: 1px solid #ccc; padding: 2px 8px; text-align: right; }</style>\n",
+        );
+        out.push_str("</head>\n<body>\n");
+        out.push_str(&format!(
+            "<h1>Debug sample traces: match {}</h1>\n",
+            self.match_id
+        ));
+        let (first, last) = self.time_span();
+        out.push_str(&format!(
+            "<p>DB: <code>{}</code> - {:.2}s to {:.2}s</p>\n",
+            escape_html(&self.db_path),
+            first as f64 / 1000.0,
+            last as f64 / 1000.0
+        ));
+        // The chart is written next to the page, so link it by file name
+        let chart_name = Path::new(&self.chart_path)
+            .file_name()
+            .map_or(self.chart_path.clone(), |n| n.to_string_lossy().to_string());
+        out.push_str(&format!(
+            "<img src=\"{}\" alt=\"Sample traces\">\n",
+            escape_html(&chart_name)
+        ));
+        let panels: Vec<String> = self.traces.iter().map(|t| t.player.to_string()).collect();
+        out.push_str(&format!(
+            "<p>One panel per player ({}). Traces top to bottom: vel_x, vel_y, input_move_x, \
+             each centered on zero (velocities share one scale across panels). Strips below: \
+             grounded, is_jumping, input_jump. Vertical lines mark the player's events; goals \
+             cross every panel.</p>\n",
+            panels.join(", ")
+        ));
+
+        out.push_str("<h2>Legend</h2>\n<ul>\n");
+        for (name, color) in TRACKS.iter().chain(STRIPS.iter()) {
+            out.push_str(&format!(
+                "<li><span style=\"color:{}\">&#9632;</span> {}</li>\n",
+                hex(*color),
+                name
+            ));
+        }
+        for (code, name, color) in MARKERS {
+            out.push_str(&format!(
+                "<li><span style=\"color:{}\">|</span> {} ({})</li>\n",
+                hex(color),
+                name,
+                code
+            ));
+        }
+        out.push_str("</ul>\n");
+
+        out.push_str("<h2>Players</h2>\n<table>\n");
+        out.push_str(
+            "<tr><th>Player</th><th>Samples</th><th>Airborne</th><th>Max |vel_x|</th>\
+             <th>vel_y range</th><th>Jumps</th><th>Landings</th><th>Stuck jumps</th></tr>\n",
+        );
+        for trace in &self.traces {
+            let count = |code: &str| {
+                self.markers
+                    .iter()
+                    .filter(|m| m.code == code && m.player == Some(trace.player))
+                    .count()
+            };
+            let (fall, rise) = trace.vel_y_range();
+            out.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{:.0}%</td><td>{:.0}</td><td>{:.0} to {:.0}</td>\
+                 <td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                trace.player,
+                trace.samples.len(),
+                trace.airborne_share() * 100.0,
+                trace.max_speed_x(),
+                fall,
+                rise,
+                count("J"),
+                count("LD"),
+                trace.stuck_jumps()
+            ));
+        }
+        out.push_str("</table>\n");
+
+        out.push_str("<h2>Markers</h2>\n<table>\n");
+        out.push_str("<tr><th>Time (s)</th><th>Event</th><th>Player</th></tr>\n");
+        for marker in &self.markers {
+            out.push_str(&format!(
+                "<tr><td>{:.2}</td><td>{}</td><td>{}</td></tr>\n",
+                marker.time_ms as f64 / 1000.0,
+                marker.code,
+                marker.player.map_or("-".to_string(), |p| p.to_string())
+            ));
+        }
+        out.push_str("</table>\n</body>\n</html>\n");
+        out
+    }
+}
+
+fn draw_segment(img: &mut RgbImage, from: (u32, u32), to: (u32, u32), color: Rgb<u8>) {
+    let (dx, dy) = (to.0 as f32 - from.0 as f32, to.1 as f32 - from.1 as f32);
+    let steps = dx.abs().max(dy.abs()).max(1.0) as u32;
+    for step in 0..=steps {
+        let t = step as f32 / steps as f32;
+        let x = (from.0 as f32 + dx * t).round() as u32;
+        let y = (from.1 as f32 + dy * t).round() as u32;
+        if x < img.width() && y < img.height() {
+            img.put_pixel(x, y, color);
+        }
+    }
+}
+
+fn fill_rect(img: &mut RgbImage, x: std::ops::RangeInclusive<u32>, y: (u32, u32), color: Rgb<u8>) {
+    for x in x {
+        for y in y.0..y.1 {
+            img.put_pixel(x, y, color);
+        }
+    }
+}
+
+/// Draw one panel per trace (see the module docs for the layout)
+pub fn render_trace_chart(
+    traces: &[PlayerTrace],
+    markers: &[TraceMarker],
+    span: (u32, u32),
+    path: &Path,
+) -> AnyResult<()> {
+    let panel_height =
+        TRACKS.len() as u32 * (TRACK_HEIGHT + TRACK_GAP) + STRIPS.len() as u32 * (STRIP_HEIGHT + 1);
+    let panels = traces.len().max(1) as u32;
+    let mut img = RgbImage::from_pixel(
+        CHART_WIDTH,
+        PANEL_GAP + panels * (panel_height + PANEL_GAP),
+        BACKGROUND,
+    );
+
+    let (first, last) = span;
+    let length = (last.saturating_sub(first)).max(1) as u64;
+    let x_of = |ms: u32| {
+        ((ms.clamp(first, last) - first) as u64 * (CHART_WIDTH - 1) as u64 / length) as u32
+    };
+    // Velocities share one scale across panels so the players compare directly
+    let all = || traces.iter().flat_map(|t| &t.samples);
+    let scales = [
+        all().map(|s| s.vel_x.abs()).fold(1.0, f32::max),
+        all().map(|s| s.vel_y.abs()).fold(1.0, f32::max),
+        1.0,
+    ];
+    let values = |s: &TraceSample| [s.vel_x, s.vel_y, s.input_move_x];
+    let flags = |s: &TraceSample| [s.grounded, s.is_jumping, s.input_jump];
+
+    for (panel, trace) in traces.iter().enumerate() {
+        let top = PANEL_GAP + panel as u32 * (panel_height + PANEL_GAP);
+        let track_top = |track: usize| top + track as u32 * (TRACK_HEIGHT + TRACK_GAP);
+        let strip_top = |strip: usize| track_top(TRACKS.len()) + strip as u32 * (STRIP_HEIGHT + 1);
+        let y_of = |track: usize, value: f32| {
+            let unit = (value / scales[track]).clamp(-1.0, 1.0);
+            track_top(track) + ((1.0 - unit) * 0.5 * (TRACK_HEIGHT - 1) as f32).round() as u32
+        };
+
+        for track in 0..TRACKS.len() {
+            let band = (track_top(track), track_top(track) + TRACK_HEIGHT);
+            fill_rect(&mut img, 0..=CHART_WIDTH - 1, band, TRACK_BACKGROUND);
+            let zero = y_of(track, 0.0);
+            fill_rect(&mut img, 0..=CHART_WIDTH - 1, (zero, zero + 1), ZERO_LINE);
+        }
+
+        for marker in markers {
+            if marker.player.is_some_and(|p| p != trace.player) {
+                continue;
+            }
+            let x = x_of(marker.time_ms);
+            fill_rect(
+                &mut img,
+                x..=x,
+                (top, top + panel_height),
+                marker_color(marker.code),
+            );
+        }
+
+        for pair in trace.samples.windows(2) {
+            let (a, b) = (&pair[0], &pair[1]);
+            let (xa, xb) = (x_of(a.time_ms), x_of(b.time_ms));
+            for (track, (_, color)) in TRACKS.iter().enumerate() {
+                let (va, vb) = (values(a)[track], values(b)[track]);
+                draw_segment(
+                    &mut img,
+                    (xa, y_of(track, va)),
+                    (xb, y_of(track, vb)),
+                    *color,
+                );
+            }
+            for (strip, (_, color)) in STRIPS.iter().enumerate() {
+                if flags(a)[strip] {
+                    let band = (strip_top(strip), strip_top(strip) + STRIP_HEIGHT);
+                    fill_rect(&mut img, xa..=xb, band, *color);
+                }
+            }
+        }
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).ok();
+    }
+    img.save(path)?;
+    Ok(())
+}
+
+fn parse_player(value: &str) -> Option<PlayerId> {
+    match value {
+        "L" => Some(PlayerId::L),
+        "R" => Some(PlayerId::R),
+        _ => None,
+    }
+}
+
+/// Load one match's debug samples and event markers and render the chart to `chart_path`
+pub fn run_sample_traces(
+    db_path: &Path,
+    match_id: i64,
+    chart_path: &Path,
+) -> AnyResult<SampleTraceReport> {
+    let conn = Connection::open(db_path)?;
+
+    let mut by_player = BTreeMap::<String, Vec<TraceSample>>::new();
+    let mut stmt = conn.prepare(SQL_SAMPLES)?;
+    let rows = stmt.query_map([match_id], |row| {
+        Ok((
+            row.get::<_, String>(1)?,
+            TraceSample {
+                time_ms: row.get(0)?,
+                vel_x: row.get::<_, f64>(2)? as f32,
+                vel_y: row.get::<_, f64>(3)? as f32,
+                input_move_x: row.get::<_, f64>(4)? as f32,
+                input_jump: row.get::<_, i64>(5)? != 0,
+                grounded: row.get::<_, i64>(6)? != 0,
+                is_jumping: row.get::<_, i64>(7)? != 0,
+            },
+        ))
+    })?;
+    for row in rows {
+        let (player, sample) = row?;
+        by_player.entry(player).or_default().push(sample);
+    }
+    let traces: Vec<PlayerTrace> = by_player
+        .into_iter()
+        .filter_map(|(player, samples)| {
+            parse_player(&player).map(|player| PlayerTrace { player, samples })
+        })
+        .collect();
+    if traces.is_empty() {
+        return Err(format!(
+            "no debug samples for match {} (was debug logging on?)",
+            match_id
+        )
+        .into());
+    }
+
+    let mut markers = Vec::new();
+    let mut stmt = conn.prepare(SQL_MARKER_EVENTS)?;
+    let rows = stmt.query_map([match_id], |row| {
+        Ok((row.get::<_, u32>(0)?, row.get::<_, String>(1)?))
+    })?;
+    for row in rows {
+        let (time_ms, data) = row?;
+        if let Some((_, event)) = parse_event(&data) {
+            markers.extend(TraceMarker::from_event(time_ms, &event));
+        }
+    }
+
+    let mut report = SampleTraceReport {
+        db_path: db_path.display().to_string(),
+        match_id,
+        traces,
+        markers,
+        chart_path: chart_path.display().to_string(),
+    };
+    // Events outside the sampled stretch have nothing to line up with
+    let (first, last) = report.time_span();
+    report
+        .markers
+        .retain(|m| (first..=last).contains(&m.time_ms));
+    render_trace_chart(&report.traces, &report.markers, (first, last), chart_path)?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(time_ms: u32, input_jump: bool, grounded: bool) -> TraceSample {
+        TraceSample {
+            time_ms,
+            vel_x: 0.0,
+            vel_y: if grounded { 0.0 } else { 400.0 },
+            input_move_x: 1.0,
+            input_jump,
+            grounded,
+            is_jumping: !grounded,
+        }
+    }
+
+    #[test]
+    fn test_trace_summary_and_markers() {
+        // One jump that takes off, one press that never leaves the ground
+        let trace = PlayerTrace {
+            player: PlayerId::L,
+            samples: vec![
+                sample(0, false, true),
+                sample(50, true, true),
+                sample(100, true, false),
+                sample(150, false, true),
+                sample(200, true, true),
+                sample(250, true, true),
+            ],
+        };
+        assert_eq!(trace.stuck_jumps(), 1);
+        assert!((trace.airborne_share() - 1.0 / 6.0).abs() < 1e-6);
+        assert_eq!(trace.vel_y_range(), (0.0, 400.0));
+
+        let jump = TraceMarker::from_event(
+            60,
+            &GameEvent::Jump {
+                player: PlayerId::L,
+            },
+        );
+        assert_eq!(
+            jump.as_ref().map(|m| (m.code, m.player)),
+            Some(("J", Some(PlayerId::L)))
+        );
+        let goal = GameEvent::Goal {
+            player: PlayerId::R,
+            score_left: 0,
+            score_right: 1,
+        };
+        assert_eq!(TraceMarker::from_event(240, &goal).unwrap().player, None);
+        assert!(TraceMarker::from_event(0, &GameEvent::ResetScores).is_none());
+
+        let html = SampleTraceReport {
+            db_path: "a<b>.db".to_string(),
+            match_id: 3,
+            traces: vec![trace],
+            markers: vec![jump.unwrap()],
+            chart_path: "out/traces.png".to_string(),
+        }
+        .to_html();
+        assert!(html.contains("<img src=\"traces.png\""));
+        assert!(html.contains("a&lt;b&gt;.db"));
+        assert!(html.contains("<td>L</td><td>6</td><td>17%</td>"));
+        assert!(html.contains("<tr><td>0.06</td><td>J</td><td>L</td></tr>"));
+    }
+}
@@ -0,0 +1,299 @@
+//! Shot-quality model evaluation against match outcomes.
+//!
+//! Pairs each logged shot's heuristic quality (from its ShotStart event) with
+//! whether it scored, then reports per level how well each stored shot model's
+//! predicted make probability matches reality (calibration error and Brier score).
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use rusqlite::{Connection, Result};
+
+use crate::ai::shot_model::{SHOT_MODEL_BINS, ShotQualityModel, quality_bin};
+use crate::events::{GameEvent, PlayerId, parse_event};
+
+const SQL_MATCH_LEVELS: &str = "SELECT id, level_name FROM matches";
+const SQL_SHOT_EVENTS: &str = "SELECT match_id, data FROM events \
+     WHERE event_type IN ('SS', 'SR', 'PU', 'G') AND match_id IS NOT NULL \
+     ORDER BY match_id, id";
+
+/// How well one model predicts one level's shots
+#[derive(Debug, Clone)]
+pub struct ModelScore {
+    pub version: String,
+    /// Expected calibration error: shot-weighted |predicted - actual| over quality bins
+    pub calibration_error: f64,
+    /// Mean squared error of the predicted probability
+    pub brier: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct LevelEvaluation {
+    pub level: String,
+    pub shots: usize,
+    pub make_rate: f64,
+    pub scores: Vec<ModelScore>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ShotModelEvalReport {
+    pub db_path: String,
+    pub models_dir: String,
+    pub levels: Vec<LevelEvaluation>,
+}
+
+/// Resolve each match's shots: (heuristic quality, made) grouped by level name.
+/// A released shot is made if the shooter's goal comes before the next pickup or shot.
+pub fn collect_shot_samples(db_path: &Path) -> Result<BTreeMap<String, Vec<(f32, bool)>>> {
+    let conn = Connection::open(db_path)?;
+
+    let mut match_levels = BTreeMap::<i64, String>::new();
+    let mut stmt = conn.prepare(SQL_MATCH_LEVELS)?;
+    let rows = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get(1)?)))?;
+    for row in rows {
+        let (id, level) = row?;
+        match_levels.insert(id, level);
+    }
+
+    let mut samples = BTreeMap::<String, Vec<(f32, bool)>>::new();
+    let mut stmt = conn.prepare(SQL_SHOT_EVENTS)?;
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+    })?;
+
+    let mut current: Option<i64> = None;
+    let mut events = Vec::new();
+    let mut flush = |match_id: i64, events: &[GameEvent]| {
+        if let Some(level) = match_levels.get(&match_id) {
+            samples
+                .entry(level.clone())
+                .or_default()
+                .extend(resolve_shots(events));
+        }
+    };
+    for row in rows {
+        let (match_id, data) = row?;
+        if current != Some(match_id) {
+            if let Some(prev) = current {
+                flush(prev, &events);
+            }
+            current = Some(match_id);
+            events.clear();
+        }
+        if let Some((_, event)) = parse_event(&data) {
+            events.push(event);
+        }
+    }
+    if let Some(prev) = current {
+        flush(prev, &events);
+    }
+
+    Ok(samples)
+}
+
+/// Turn one match's SS/SR/PU/G events into (quality, made) shots
+fn resolve_shots(events: &[GameEvent]) -> Vec<(f32, bool)> {
+    let mut shots = Vec::new();
+    // Quality of the shot each side is charging
+    let mut charging: [Option<f32>; 2] = [None, None];
+    // Released shot waiting for an outcome
+    let mut in_flight: Option<(PlayerId, f32)> = None;
+    let side = |player: PlayerId| match player {
+        PlayerId::L => 0,
+        PlayerId::R => 1,
+    };
+
+    for event in events {
+        match event {
+            GameEvent::ShotStart {
+                player, quality, ..
+            } => {
+                charging[side(*player)] = Some(*quality);
+            }
+            GameEvent::ShotRelease { player, .. } => {
+                if let Some((_, quality)) = in_flight.take() {
+                    shots.push((quality, false));
+                }
+                in_flight = charging[side(*player)].take().map(|q| (*player, q));
+            }
+            GameEvent::Pickup { .. } => {
+                if let Some((_, quality)) = in_flight.take() {
+                    shots.push((quality, false));
+                }
+                charging = [None, None];
+            }
+            GameEvent::Goal { player, .. } => {
+                if let Some((shooter, quality)) = in_flight.take() {
+                    shots.push((quality, shooter == *player));
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Some((_, quality)) = in_flight {
+        shots.push((quality, false));
+    }
+    shots
+}
+
+/// Calibration error and Brier score of a model over (quality, made) samples
+pub fn score_model(model: &ShotQualityModel, samples: &[(f32, bool)]) -> ModelScore {
+    let mut bins = [(0usize, 0.0f64, 0.0f64); SHOT_MODEL_BINS];
+    let mut brier = 0.0;
+    for &(quality, made) in samples {
+        let predicted = model.predict(quality) as f64;
+        let actual = if made { 1.0 } else { 0.0 };
+        brier += (predicted - actual).powi(2);
+        let bin = &mut bins[quality_bin(quality)];
+        bin.0 += 1;
+        bin.1 += predicted;
+        bin.2 += actual;
+    }
+    let total = samples.len().max(1) as f64;
+    let calibration_error = bins
+        .iter()
+        .filter(|(count, _, _)| *count > 0)
+        .map(|&(_, predicted, actual)| (predicted - actual).abs() / total)
+        .sum();
+    ModelScore {
+        version: model.meta.version.clone(),
+        calibration_error,
+        brier: brier / total,
+    }
+}
+
+/// Score the heuristic model and every stored version on each level's shots
+pub fn run_shot_model_evaluation(db_path: &Path, models_dir: &Path) -> Result<ShotModelEvalReport> {
+    let samples = collect_shot_samples(db_path)?;
+    let levels = samples
+        .into_iter()
+        .filter(|(_, shots)| !shots.is_empty())
+        .map(|(level, shots)| {
+            let mut models = vec![ShotQualityModel::heuristic(&level)];
+            for version in ShotQualityModel::versions(models_dir, &level) {
+                match ShotQualityModel::load(models_dir, &level, &version) {
+                    Ok(model) => models.push(model),
+                    Err(e) => eprintln!("Warning: {}", e),
+                }
+            }
+            let made = shots.iter().filter(|(_, made)| *made).count();
+            LevelEvaluation {
+                make_rate: made as f64 / shots.len() as f64,
+                scores: models.iter().map(|m| score_model(m, &shots)).collect(),
+                shots: shots.len(),
+                level,
+            }
+        })
+        .collect();
+
+    Ok(ShotModelEvalReport {
+        db_path: db_path.display().to_string(),
+        models_dir: models_dir.display().to_string(),
+        levels,
+    })
+}
+
+/// Fit and save a new model version for every level with logged shots
+pub fn fit_shot_models(
+    db_path: &Path,
+    models_dir: &Path,
+    version: &str,
+) -> std::result::Result<Vec<PathBuf>, String> {
+    let samples = collect_shot_samples(db_path).map_err(|e| e.to_string())?;
+    let source = db_path.display().to_string();
+    samples
+        .iter()
+        .filter(|(_, shots)| !shots.is_empty())
+        .map(|(level, shots)| {
+            ShotQualityModel::fit(level, version, &source, shots).save(models_dir)
+        })
+        .collect()
+}
+
+impl ShotModelEvalReport {
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        out.push_str("# Shot Model Evaluation\n\n");
+        out.push_str(&format!("DB: `{}`\n\n", self.db_path));
+        out.push_str(&format!("Models: `{}`\n\n", self.models_dir));
+        out.push_str(
+            "Calibration error is the shot-weighted gap between predicted and actual make rate \
+             across quality bins (lower is better).\n\n",
+        );
+
+        out.push_str("| Level | Shots | Make rate | Model | Calibration error | Brier |\n");
+        out.push_str("|---|---|---|---|---|---|\n");
+        for level in &self.levels {
+            let best = level
+                .scores
+                .iter()
+                .min_by(|a, b| a.calibration_error.total_cmp(&b.calibration_error))
+                .map(|s| s.version.as_str());
+            for score in &level.scores {
+                let marker = if Some(score.version.as_str()) == best {
+                    " *"
+                } else {
+                    ""
+                };
+                out.push_str(&format!(
+                    "| {} | {} | {:.3} | {}{} | {:.4} | {:.4} |\n",
+                    level.level,
+                    level.shots,
+                    level.make_rate,
+                    score.version,
+                    marker,
+                    score.calibration_error,
+                    score.brier
+                ));
+            }
+        }
+        out.push_str("\n`*` = best calibrated model for the level\n");
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_shots_and_calibration() {
+        let shot = |player, quality| {
+            [
+                GameEvent::ShotStart {
+                    player,
+                    pos: (0.0, 0.0),
+                    quality,
+                },
+                GameEvent::ShotRelease {
+                    player,
+                    charge: 0.5,
+                    angle: 60.0,
+                    power: 500.0,
+                },
+            ]
+        };
+        let mut events = Vec::new();
+        events.extend(shot(PlayerId::L, 0.8));
+        events.push(GameEvent::Goal {
+            player: PlayerId::L,
+            score_left: 1,
+            score_right: 0,
+        });
+        events.extend(shot(PlayerId::R, 0.3));
+        events.push(GameEvent::Pickup {
+            player: PlayerId::L,
+        });
+
+        let samples = resolve_shots(&events);
+        assert_eq!(samples, vec![(0.8, true), (0.3, false)]);
+
+        // Heuristic predicts 0.8 and 0.3: off by 0.2 and 0.3 in their bins
+        let score = score_model(&ShotQualityModel::heuristic("Arena"), &samples);
+        assert!((score.calibration_error - 0.25).abs() < 1e-6);
+
+        let fitted = ShotQualityModel::fit("Arena", "v1", "test.db", &samples);
+        assert!(score_model(&fitted, &samples).calibration_error < 1e-6);
+    }
+}
@@ -5,6 +5,9 @@ use std::path::Path;
 
 use super::metrics::AggregateMetrics;
 
+/// Default targets file (`analyze --targets`, shot test)
+pub const TUNING_TARGETS_FILE: &str = "assets/tuning_targets.toml";
+
 /// Status of a target comparison
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TargetStatus {
@@ -98,6 +101,8 @@ pub struct TuningTargets {
     pub match_duration: Option<Target>,
     pub turnovers_per_match: Option<Target>,
     pub missed_shots_per_match: Option<Target>,
+    /// Make rate (%) of contested shots in the shot test (not in match metrics)
+    pub contested_make_rate: Option<Target>,
 }
 
 impl TuningTargets {
@@ -215,6 +220,7 @@ fn parse_targets_toml(content: &str) -> Option<TuningTargets> {
                     }
                     "turnovers_per_match" => targets.turnovers_per_match = Some(target),
                     "missed_shots_per_match" => targets.missed_shots_per_match = Some(target),
+                    "contested_make_rate" => targets.contested_make_rate = Some(target),
                     _ => {}
                 }
             }
@@ -280,5 +286,10 @@ pub fn default_targets() -> TuningTargets {
             target: 20.0,
             tolerance: 5.0,
         }),
+        contested_make_rate: Some(Target {
+            name: "contested make rate".to_string(),
+            target: 35.0,
+            tolerance: 10.0,
+        }),
     }
 }
@@ -0,0 +1,304 @@
+//! Result upload - posts match results and session summaries to a shared leaderboard
+//!
+//! `--upload http://host[:port]/path` (simulate and train) posts every simulated
+//! `MatchResult` and each training `SessionSummary` as one JSON body, e.g.
+//! `{"kind": "match_result", "data": {...}}`, once the run finishes. A failed
+//! post is retried with exponential backoff. Bodies that still fail go to an
+//! offline queue file (one body per line, `--upload-queue FILE`, default
+//! `db/upload_queue.jsonl`) and are sent ahead of new results the next time.
+
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fs;
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::events::parse_http_url;
+use crate::simulation::MatchResult;
+use crate::training::SessionSummary;
+
+/// Offline queue used when `--upload-queue` isn't given
+pub const DEFAULT_UPLOAD_QUEUE: &str = "db/upload_queue.jsonl";
+
+/// Where results go and how hard to try
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UploadConfig {
+    /// `http://` endpoint every body is posted to
+    pub url: String,
+    /// Bodies that couldn't be sent yet, one per line
+    pub queue_file: String,
+    /// Tries per body before it's queued
+    pub attempts: u32,
+    /// Delay before the first retry (doubles on each further retry)
+    pub backoff_ms: u64,
+}
+
+impl UploadConfig {
+    /// Parse `--upload <url>` and `--upload-queue <file>`.
+    /// Returns None unless a valid URL is given.
+    pub fn from_args(args: &[String]) -> Option<Self> {
+        let value = |flag: &str| {
+            args.iter()
+                .position(|a| a == flag)
+                .and_then(|i| args.get(i + 1))
+        };
+        let url = value("--upload")?;
+        if let Err(e) = parse_http_url(url) {
+            eprintln!("Warning: --upload: {}", e);
+            return None;
+        }
+        Some(Self {
+            url: url.clone(),
+            queue_file: value("--upload-queue")
+                .cloned()
+                .unwrap_or_else(|| DEFAULT_UPLOAD_QUEUE.to_string()),
+            attempts: 3,
+            backoff_ms: 500,
+        })
+    }
+
+    /// Delay before retry number `retry` (1-based)
+    pub fn retry_delay(&self, retry: u32) -> Duration {
+        Duration::from_millis(self.backoff_ms << retry.saturating_sub(1).min(16))
+    }
+}
+
+/// What an upload run did
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UploadReport {
+    pub sent: usize,
+    /// Left in the offline queue for next time
+    pub queued: usize,
+}
+
+/// JSON body for one upload
+pub fn upload_body<T: Serialize>(kind: &str, data: &T) -> Option<String> {
+    let data = serde_json::to_value(data).ok()?;
+    Some(json!({ "kind": kind, "data": data }).to_string())
+}
+
+/// Post each simulated match (events aren't part of the JSON)
+pub fn upload_match_results(config: &UploadConfig, results: &[MatchResult]) -> UploadReport {
+    let bodies = results
+        .iter()
+        .filter_map(|result| upload_body("match_result", result))
+        .collect();
+    upload(config, bodies)
+}
+
+/// Post a training session's summary
+pub fn upload_session_summary(config: &UploadConfig, summary: &SessionSummary) -> UploadReport {
+    let bodies = upload_body("session_summary", summary)
+        .into_iter()
+        .collect();
+    upload(config, bodies)
+}
+
+/// Send the queued bodies, then `bodies`, in order. The first body that fails
+/// every attempt stops the run; it and everything after it stay queued, so the
+/// server always sees results in the order they were played.
+pub fn upload(config: &UploadConfig, bodies: Vec<String>) -> UploadReport {
+    let mut pending = read_queue(&config.queue_file);
+    let backlog = pending.len();
+    pending.extend(bodies);
+
+    let mut sent = 0;
+    match parse_http_url(&config.url) {
+        Ok((host, port, path)) => {
+            for body in &pending {
+                if let Err(e) = post_with_retry(config, &host, port, &path, body) {
+                    eprintln!("Warning: upload to {} failed: {}", config.url, e);
+                    break;
+                }
+                sent += 1;
+            }
+        }
+        Err(e) => eprintln!("Warning: upload: {}", e),
+    }
+
+    let queued = pending.len() - sent;
+    if let Err(e) = write_queue(&config.queue_file, &pending[sent..]) {
+        eprintln!("Warning: failed to write {}: {}", config.queue_file, e);
+    }
+    if sent > 0 || queued > 0 {
+        println!(
+            "Upload: {} sent ({} from the offline queue), {} queued in {}",
+            sent,
+            sent.min(backlog),
+            queued,
+            config.queue_file
+        );
+    }
+    UploadReport { sent, queued }
+}
+
+fn post_with_retry(
+    config: &UploadConfig,
+    host: &str,
+    port: u16,
+    path: &str,
+    body: &str,
+) -> Result<(), String> {
+    let mut result = post_json(host, port, path, body);
+    for retry in 1..config.attempts.max(1) {
+        if result.is_ok() {
+            break;
+        }
+        std::thread::sleep(config.retry_delay(retry));
+        result = post_json(host, port, path, body);
+    }
+    result
+}
+
+/// POST `body` and require a 2xx status
+fn post_json(host: &str, port: u16, path: &str, body: &str) -> Result<(), String> {
+    let addr = (host, port)
+        .to_socket_addrs()
+        .map_err(|e| e.to_string())?
+        .next()
+        .ok_or_else(|| format!("no address for {}", host))?;
+    let timeout = Duration::from_secs(5);
+    let mut stream = TcpStream::connect_timeout(&addr, timeout).map_err(|e| e.to_string())?;
+    let _ = stream.set_write_timeout(Some(timeout));
+    let _ = stream.set_read_timeout(Some(timeout));
+    let request = format!(
+        "POST {} HTTP/1.1\r\nHost: {}:{}\r\nContent-Type: application/json\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        path,
+        host,
+        port,
+        body.len(),
+        body
+    );
+    stream
+        .write_all(request.as_bytes())
+        .map_err(|e| e.to_string())?;
+
+    let mut response = [0u8; 64];
+    let read = stream.read(&mut response).map_err(|e| e.to_string())?;
+    let status_line = String::from_utf8_lossy(&response[..read]);
+    let status = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse::<u16>().ok())
+        .ok_or_else(|| "no HTTP status in response".to_string())?;
+    if (200..300).contains(&status) {
+        Ok(())
+    } else {
+        Err(format!("HTTP {}", status))
+    }
+}
+
+fn read_queue(path: &str) -> Vec<String> {
+    fs::read_to_string(path)
+        .map(|contents| {
+            contents
+                .lines()
+                .filter(|l| !l.trim().is_empty())
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Rewrite the queue with `bodies` (removing it when nothing is left)
+fn write_queue(path: &str, bodies: &[String]) -> std::io::Result<()> {
+    if bodies.is_empty() {
+        if Path::new(path).exists() {
+            fs::remove_file(path)?;
+        }
+        return Ok(());
+    }
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut contents = bodies.join("\n");
+    contents.push('\n');
+    fs::write(path, contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    /// Accept `count` posts, answering each with 200, and return their bodies
+    fn serve(listener: TcpListener, count: usize) -> std::thread::JoinHandle<Vec<String>> {
+        std::thread::spawn(move || {
+            let mut bodies = Vec::new();
+            for stream in listener.incoming().take(count) {
+                let mut stream = stream.unwrap();
+                let mut request = Vec::new();
+                let mut chunk = [0u8; 1024];
+                loop {
+                    let read = stream.read(&mut chunk).unwrap();
+                    request.extend_from_slice(&chunk[..read]);
+                    let text = String::from_utf8_lossy(&request).to_string();
+                    if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                        let length: usize = head
+                            .lines()
+                            .find_map(|l| l.strip_prefix("Content-Length: "))
+                            .and_then(|v| v.trim().parse().ok())
+                            .unwrap_or(0);
+                        if read == 0 || body.len() >= length {
+                            bodies.push(body.to_string());
+                            break;
+                        }
+                    }
+                }
+                stream.write_all(b"HTTP/1.1 200 OK\r\n\r\n").unwrap();
+            }
+            bodies
+        })
+    }
+
+    #[test]
+    fn test_failed_uploads_queue_and_resend_in_order() {
+        let queue = std::env::temp_dir().join(format!("upload_queue_test_{}", std::process::id()));
+        let _ = fs::remove_file(&queue);
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let args: Vec<String> = [
+            "simulate",
+            "--upload",
+            &format!("http://127.0.0.1:{}/results", port),
+            "--upload-queue",
+            queue.to_str().unwrap(),
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let config = UploadConfig {
+            attempts: 2,
+            backoff_ms: 1,
+            ..UploadConfig::from_args(&args).unwrap()
+        };
+        assert_eq!(config.retry_delay(1), Duration::from_millis(1));
+        assert_eq!(config.retry_delay(3), Duration::from_millis(4));
+
+        // Endpoint down: both bodies land in the offline queue
+        drop(listener);
+        let report = upload(&config, vec!["{\"n\":1}".into(), "{\"n\":2}".into()]);
+        assert_eq!(report, UploadReport { sent: 0, queued: 2 });
+        assert_eq!(read_queue(queue.to_str().unwrap()).len(), 2);
+
+        // Back up: the queue goes first, then the new body, and the queue file is gone
+        let server = serve(TcpListener::bind(("127.0.0.1", port)).unwrap(), 3);
+        let report = upload(&config, vec!["{\"n\":3}".into()]);
+        assert_eq!(report, UploadReport { sent: 3, queued: 0 });
+        assert_eq!(
+            server.join().unwrap(),
+            ["{\"n\":1}", "{\"n\":2}", "{\"n\":3}"]
+        );
+        assert!(!queue.exists());
+
+        let body: serde_json::Value =
+            serde_json::from_str(&upload_body("match_result", &json!({"seed": 7})).unwrap())
+                .unwrap();
+        assert_eq!(body["kind"], "match_result");
+        assert_eq!(body["data"]["seed"], 7);
+    }
+}
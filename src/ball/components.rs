@@ -3,8 +3,11 @@
 use bevy::prelude::*;
 use std::collections::HashMap;
 
+use super::style_physics::BallPhysics;
+
 /// Marker for ball entities
 #[derive(Component)]
+#[require(BallStuckTimer, BallPhysics)]
 pub struct Ball;
 
 /// Ball style name - stored as a string to be fully dynamic
@@ -27,6 +30,27 @@ pub struct StyleTextures {
     pub textures: Vec<Handle<Image>>,
 }
 
+/// Asset path of a ball texture. `size` selects a reduced-resolution set from
+/// `sizes:` in ball_options.txt (None = the full-size set).
+pub fn ball_texture_path(style: &str, palette: usize, size: Option<u32>) -> String {
+    match size {
+        Some(size) => format!("textures/balls/{}/ball_{}_{}.png", size, style, palette),
+        None => format!("textures/balls/ball_{}_{}.png", style, palette),
+    }
+}
+
+/// Reduced texture sizes listed by `sizes:` in ball_options.txt (ascending)
+pub fn ball_texture_sizes(options: &str) -> Vec<u32> {
+    let mut sizes: Vec<u32> = options
+        .lines()
+        .filter_map(|l| l.trim().strip_prefix("sizes:"))
+        .flat_map(|v| v.split_whitespace().filter_map(|s| s.parse().ok()))
+        .collect();
+    sizes.sort_unstable();
+    sizes.dedup();
+    sizes
+}
+
 /// Holds handles to all ball textures for all styles and palettes
 /// Dynamically loaded based on ball_options.txt
 #[derive(Resource, Clone, Default)]
@@ -35,9 +59,57 @@ pub struct BallTextures {
     pub styles: HashMap<String, StyleTextures>,
     /// Ordered list of style names (for cycling through styles)
     pub style_order: Vec<String>,
+    /// Reduced sizes on disk (ascending), picked by `select_ball_texture_size`
+    pub sizes: Vec<u32>,
+    /// Set currently loaded into `styles` (None = full size)
+    pub active_size: Option<u32>,
 }
 
 impl BallTextures {
+    /// Load every style × palette at one size
+    pub fn load(
+        asset_server: &AssetServer,
+        style_names: &[String],
+        num_palettes: usize,
+        sizes: Vec<u32>,
+        size: Option<u32>,
+    ) -> Self {
+        let mut textures = Self {
+            styles: HashMap::new(),
+            style_order: style_names.to_vec(),
+            sizes,
+            active_size: None,
+        };
+        textures.load_size(asset_server, size, num_palettes);
+        textures
+    }
+
+    /// Swap every style to the textures of `size` (handles are cached by the asset server)
+    pub fn load_size(
+        &mut self,
+        asset_server: &AssetServer,
+        size: Option<u32>,
+        num_palettes: usize,
+    ) {
+        for style in &self.style_order {
+            let textures = (0..num_palettes)
+                .map(|i| asset_server.load(ball_texture_path(style, i, size)))
+                .collect();
+            self.styles
+                .insert(style.clone(), StyleTextures { textures });
+        }
+        self.active_size = size;
+    }
+
+    /// Texture set for a ball drawn `screen_px` pixels tall: the smallest reduced
+    /// size that still covers it, or the full-size set when none does
+    pub fn pick_size(&self, screen_px: f32) -> Option<u32> {
+        self.sizes
+            .iter()
+            .copied()
+            .find(|&size| size as f32 >= screen_px)
+    }
+
     /// Get textures for a specific style by name
     pub fn get(&self, style: &str) -> Option<&StyleTextures> {
         self.styles.get(style)
@@ -108,6 +180,11 @@ pub enum BallState {
         shooter: Entity,
         power: f32,
     }, // Who shot it and how hard
+    /// Lobbed to a teammate (2v2); anyone but the passer can catch it
+    Passed {
+        passer: Entity,
+        target: Entity,
+    },
 }
 
 /// Track overlap for collision effects
@@ -130,6 +207,46 @@ pub struct BallRolling(pub bool);
 #[derive(Component, Default)]
 pub struct BallShotGrace(pub f32);
 
+/// On a player who just scooped up a rolling ball (see `can_scoop`): the ball eases
+/// from where it was picked up into their hands, and they can't be stolen from
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Scooping {
+    /// Ball position relative to the player when the scoop started
+    pub start_offset: Vec2,
+    pub duration: f32,
+    pub remaining: f32,
+}
+
+impl Scooping {
+    pub fn new(start_offset: Vec2, duration: f32) -> Self {
+        Self {
+            start_offset,
+            duration,
+            remaining: duration,
+        }
+    }
+
+    pub fn active(&self) -> bool {
+        self.remaining > 0.0
+    }
+
+    /// 0 when the scoop starts, 1 once the ball is in hand (eased out)
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
+        let t = (1.0 - self.remaining / self.duration).clamp(0.0, 1.0);
+        t * (2.0 - t)
+    }
+}
+
+/// Seconds the ball has been wedged out of reach or out of bounds (see `recover_stuck_ball`)
+#[derive(Component, Default)]
+pub struct BallStuckTimer {
+    pub wedged: f32,
+    pub out_of_bounds: f32,
+}
+
 /// Tracks ball's angular velocity (radians per second)
 #[derive(Component, Default)]
 pub struct BallSpin(pub f32);
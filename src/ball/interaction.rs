@@ -5,13 +5,17 @@ use rand::Rng;
 
 use crate::ai::{InputState, decision::defender_in_shot_path};
 use crate::ball::components::*;
+use crate::config::{BallConfig, StealConfig};
 use crate::constants::*;
+use crate::events::{EventBus, GameEvent, PlayerId, intern};
 use crate::player::{Facing, HoldingBall, Player, Team, Velocity};
+use crate::scoring::PossessionArrow;
 use crate::shooting::ChargingShot;
-use crate::steal::{StealContest, StealCooldown, StealTracker};
+use crate::steal::{LooseBall, StealContest, StealCooldown, StealDenial, StealTracker};
 
 /// Handle ball-player collision physics
 pub fn ball_player_collision(
+    ball_config: Res<BallConfig>,
     mut ball_query: Query<
         (
             &Transform,
@@ -51,9 +55,10 @@ pub fn ball_player_collision(
 
         let mut is_overlapping = false;
 
-        // Get shooter entity if ball is in flight
+        // Get shooter entity if ball is in flight (or the passer of a pass)
         let shooter_entity = match ball_state {
             BallState::InFlight { shooter, .. } => Some(*shooter),
+            BallState::Passed { passer, .. } => Some(*passer),
             _ => None,
         };
 
@@ -98,15 +103,17 @@ pub fn ball_player_collision(
 
                     // Both slow down when passing through each other
                     // Ball has higher Y friction (gravity effect) than X
-                    ball_velocity.0.x *= BALL_PLAYER_DRAG_X;
-                    ball_velocity.0.y *= BALL_PLAYER_DRAG_Y;
-                    player_velocity.0 *= BALL_PLAYER_DRAG_X;
+                    ball_velocity.0.x *= ball_config.player_drag_x;
+                    ball_velocity.0.y *= ball_config.player_drag_y;
+                    player_velocity.0 *= ball_config.player_drag_x;
 
                     // If ball is slow/stationary and player is moving, kick the ball
-                    if ball_speed < BALL_KICK_THRESHOLD && player_speed > BALL_KICK_THRESHOLD {
+                    if ball_speed < ball_config.kick_threshold
+                        && player_speed > ball_config.kick_threshold
+                    {
                         let kick_dir = if player_velocity.0.x > 0.0 { 1.0 } else { -1.0 };
-                        ball_velocity.0.x += kick_dir * BALL_KICK_STRENGTH;
-                        ball_velocity.0.y += BALL_KICK_STRENGTH * 0.3; // Small upward nudge
+                        ball_velocity.0.x += kick_dir * ball_config.kick_strength;
+                        ball_velocity.0.y += ball_config.kick_strength * 0.3; // Small upward nudge
                         rolling.0 = false; // Ball is kicked into the air
                     }
                 }
@@ -117,30 +124,129 @@ pub fn ball_player_collision(
     }
 }
 
-/// Make ball follow holder
+/// Make ball follow holder (easing it into the hands during a scoop)
 pub fn ball_follow_holder(
+    time: Res<Time>,
     mut ball_query: Query<(&mut Transform, &BallState), With<Ball>>,
-    player_query: Query<(&Transform, &Facing), (With<Player>, Without<Ball>)>,
+    mut player_query: Query<
+        (&Transform, &Facing, Option<&mut Scooping>),
+        (With<Player>, Without<Ball>),
+    >,
 ) {
     for (mut ball_transform, state) in &mut ball_query {
         if let BallState::Held(holder_entity) = state {
-            if let Ok((player_transform, facing)) = player_query.get(*holder_entity) {
+            if let Ok((player_transform, facing, scooping)) = player_query.get_mut(*holder_entity) {
                 // Position ball inside player, on facing side, at middle height
-                ball_transform.translation.x =
-                    player_transform.translation.x + facing.0 * (PLAYER_SIZE.x / 4.0);
-                ball_transform.translation.y = player_transform.translation.y; // Center height
+                let mut offset = Vec2::new(facing.0 * (PLAYER_SIZE.x / 4.0), 0.0);
+                if let Some(mut scooping) = scooping.filter(|s| s.active()) {
+                    scooping.remaining = (scooping.remaining - time.delta_secs()).max(0.0);
+                    offset = scooping.start_offset.lerp(offset, scooping.progress());
+                }
+                ball_transform.translation.x = player_transform.translation.x + offset.x;
+                ball_transform.translation.y = player_transform.translation.y + offset.y;
             }
         }
     }
 }
 
+/// Whether a player holding pickup scoops up a free ball without pressing: the
+/// ball has to be rolling slower than `scoop_max_ball_speed`, the player running
+/// at `scoop_min_run_speed` or faster, and the ball within pickup reach.
+pub fn can_scoop(
+    config: &BallConfig,
+    player_pos: Vec2,
+    player_velocity: Vec2,
+    ball_pos: Vec2,
+    ball_velocity: Vec2,
+    rolling: bool,
+) -> bool {
+    rolling
+        && ball_velocity.length() < config.scoop_max_ball_speed
+        && player_velocity.x.abs() >= config.scoop_min_run_speed
+        && player_pos.distance(ball_pos) < config.pickup_radius
+}
+
+/// A player's claim on a free ball in a pickup contest
+#[derive(Debug, Clone, Copy)]
+pub struct PickupClaim {
+    pub entity: Entity,
+    pub team: Team,
+    pub pos: Vec2,
+    pub velocity: Vec2,
+    pub facing: f32,
+}
+
+/// Pickup pressed while a rival was also in reach of the ball. Waits a few ticks
+/// for the rival's press before the ball is granted.
+#[derive(Debug, Clone, Copy)]
+pub struct PendingPickup {
+    pub player: Entity,
+    pub ball: Entity,
+    pub ticks_left: u32,
+}
+
+/// Decide a pickup contest: closer to the ball wins, then moving toward it faster,
+/// then facing it. Exact ties (mirrored positions) go to the left team.
+pub fn pickup_contest_winner<'a>(
+    ball_pos: Vec2,
+    a: &'a PickupClaim,
+    b: &'a PickupClaim,
+) -> &'a PickupClaim {
+    let distance = |c: &PickupClaim| c.pos.distance(ball_pos);
+    let approach = |c: &PickupClaim| c.velocity.dot((ball_pos - c.pos).normalize_or_zero());
+    let faces_ball = |c: &PickupClaim| c.facing * (ball_pos.x - c.pos.x) > 0.0;
+
+    let distance_diff = distance(a) - distance(b);
+    if distance_diff.abs() > PICKUP_CONTEST_DISTANCE_MARGIN {
+        return if distance_diff < 0.0 { a } else { b };
+    }
+    let approach_diff = approach(a) - approach(b);
+    if approach_diff.abs() > PICKUP_CONTEST_SPEED_MARGIN {
+        return if approach_diff > 0.0 { a } else { b };
+    }
+    if faces_ball(a) != faces_ball(b) {
+        return if faces_ball(a) { a } else { b };
+    }
+    if b.team == Team::Left { b } else { a }
+}
+
+/// Show denial feedback for a steal press and log why it was denied (`SD`).
+fn deny_steal(
+    steal_contest: &mut StealContest,
+    event_bus: &mut EventBus,
+    entity: Entity,
+    team: Team,
+    reason: StealDenial,
+) {
+    steal_contest.deny(entity, reason);
+    event_bus.emit(GameEvent::StealDenied {
+        attacker: match team {
+            Team::Left => PlayerId::L,
+            Team::Right => PlayerId::R,
+        },
+        reason: intern(reason.as_str()),
+    });
+}
+
 /// Handle ball pickup and instant steal attempts.
 /// All players read from their InputState component.
 /// Uses graduated steal difficulty: teams with more steals have reduced success chance.
+/// Pickup presses from both players within `PICKUP_CONTEST_WINDOW_TICKS` are settled
+/// by `pickup_contest_winner` instead of query order, and logged as `ContestedPickup`.
+/// With the possession arrow rule on, the arrow decides contests between teams instead.
+/// With the loose-ball rule on, a successful steal knocks the ball free instead of
+/// handing it to the attacker.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn pickup_ball(
     mut commands: Commands,
+    ball_config: Res<BallConfig>,
+    steal: Res<StealConfig>,
     mut steal_contest: ResMut<StealContest>,
     mut steal_tracker: ResMut<StealTracker>,
+    mut event_bus: ResMut<EventBus>,
+    mut pending: Local<Option<PendingPickup>>,
+    mut arrow: Option<ResMut<PossessionArrow>>,
+    mut loose_ball: Option<ResMut<LooseBall>>,
     mut non_holding_players: Query<
         (
             Entity,
@@ -149,6 +255,8 @@ pub fn pickup_ball(
             &mut ChargingShot,
             &mut InputState,
             &mut StealCooldown,
+            &Velocity,
+            &Facing,
         ),
         (With<Player>, Without<HoldingBall>),
     >,
@@ -160,54 +268,212 @@ pub fn pickup_ball(
             &ChargingShot,
             &mut Velocity,
             &mut StealCooldown,
+            Option<&Scooping>,
+            &Team,
         ),
         With<Player>,
     >,
-    mut ball_query: Query<(Entity, &Transform, &mut BallState), With<Ball>>,
+    mut ball_query: Query<
+        (
+            Entity,
+            &Transform,
+            &mut BallState,
+            &mut Velocity,
+            &mut BallRolling,
+            &mut BallShotGrace,
+        ),
+        (With<Ball>, Without<Player>),
+    >,
 ) {
-    // Check each non-holding player for pickup/steal attempts
-    for (player_entity, player_transform, team, mut charging, mut input, mut cooldown) in
-        &mut non_holding_players
-    {
-        if !input.pickup_pressed {
+    // First, free-ball pickups. Everyone pressing this tick (plus a claim waiting
+    // on a rival) is collected before anyone gets the ball.
+    let claims: Vec<PickupClaim> = non_holding_players
+        .iter()
+        .filter(|(entity, _, _, _, input, ..)| {
+            input.pickup_pressed || pending.is_some_and(|p| p.player == *entity)
+        })
+        .map(
+            |(entity, transform, team, _, _, _, velocity, facing)| PickupClaim {
+                entity,
+                team: *team,
+                pos: transform.translation.truncate(),
+                velocity: velocity.0,
+                facing: facing.0,
+            },
+        )
+        .collect();
+
+    let mut next_pending = None;
+    let mut granted = None;
+    let mut consumed = Vec::new();
+    for (ball_entity, ball_transform, ball_state, ..) in &ball_query {
+        if *ball_state != BallState::Free {
             continue;
         }
+        let ball_pos = ball_transform.translation.truncate();
+        let in_reach = |pos: Vec2| pos.distance(ball_pos) < ball_config.pickup_radius;
+        let reaching: Vec<&PickupClaim> = claims.iter().filter(|c| in_reach(c.pos)).collect();
+
+        match reaching.as_slice() {
+            [] => continue,
+            [a, b, ..] => {
+                let player_id = |team: Team| match team {
+                    Team::Left => PlayerId::L,
+                    Team::Right => PlayerId::R,
+                };
+                let by_arrow = arrow
+                    .as_deref_mut()
+                    .filter(|_| a.team != b.team)
+                    .and_then(PossessionArrow::award);
+                let winner = match by_arrow {
+                    Some(team) => {
+                        info!("PICKUP CONTEST: possession arrow awards {:?}", team);
+                        event_bus.emit(GameEvent::PossessionAwarded {
+                            player: player_id(team),
+                            reason: intern("pickup_contest"),
+                        });
+                        if a.team == team { a } else { b }
+                    }
+                    None => pickup_contest_winner(ball_pos, a, b),
+                };
+                let loser = if winner.entity == a.entity { b } else { a };
+                info!(
+                    "PICKUP CONTEST: {:?} beats {:?} ({:.1}px vs {:.1}px from ball)",
+                    winner.team,
+                    loser.team,
+                    winner.pos.distance(ball_pos),
+                    loser.pos.distance(ball_pos)
+                );
+                event_bus.emit(GameEvent::ContestedPickup {
+                    winner: player_id(winner.team),
+                    loser: player_id(loser.team),
+                });
+                consumed.push(loser.entity);
+                granted = Some((winner.entity, ball_entity));
+            }
+            [claim] => {
+                consumed.push(claim.entity);
+                let rival_in_reach = non_holding_players.iter().any(|(entity, transform, ..)| {
+                    entity != claim.entity && in_reach(transform.translation.truncate())
+                });
+                let ticks_left = match *pending {
+                    Some(p) if p.player == claim.entity && p.ball == ball_entity => p.ticks_left,
+                    _ => PICKUP_CONTEST_WINDOW_TICKS,
+                };
+                if rival_in_reach && ticks_left > 0 {
+                    // Give the rival a few ticks to press too
+                    next_pending = Some(PendingPickup {
+                        player: claim.entity,
+                        ball: ball_entity,
+                        ticks_left: ticks_left - 1,
+                    });
+                    continue;
+                }
+                granted = Some((claim.entity, ball_entity));
+            }
+        }
+        break;
+    }
 
-        // Consume the input
-        input.pickup_pressed = false;
-
-        let player_pos = player_transform.translation.truncate();
-
-        // First, try to pick up a free ball
-        let mut picked_up = false;
-        for (ball_entity, ball_transform, mut ball_state) in &mut ball_query {
-            if *ball_state != BallState::Free {
+    // Nobody pressed: a player running over a slow roller with pickup held
+    // scoops it up
+    let mut scoop = None;
+    if granted.is_none() && next_pending.is_none() {
+        'scoop: for (player_entity, transform, team, _, input, _, velocity, _) in
+            &non_holding_players
+        {
+            if !input.pickup_held {
                 continue;
             }
+            let player_pos = transform.translation.truncate();
+            for (ball_entity, ball_transform, ball_state, ball_velocity, rolling, _) in &ball_query
+            {
+                let ball_pos = ball_transform.translation.truncate();
+                if *ball_state == BallState::Free
+                    && can_scoop(
+                        &ball_config,
+                        player_pos,
+                        velocity.0,
+                        ball_pos,
+                        ball_velocity.0,
+                        rolling.0,
+                    )
+                {
+                    granted = Some((player_entity, ball_entity));
+                    scoop = Some((*team, ball_pos - player_pos, ball_velocity.0.length()));
+                    break 'scoop;
+                }
+            }
+        }
+    }
+    *pending = next_pending;
 
-            let distance = player_pos.distance(ball_transform.translation.truncate());
-
-            if distance < BALL_PICKUP_RADIUS {
-                *ball_state = BallState::Held(player_entity);
+    for entity in consumed {
+        if let Ok((_, _, _, _, mut input, ..)) = non_holding_players.get_mut(entity) {
+            input.pickup_pressed = false;
+        }
+    }
+    if let Some((player_entity, ball_entity)) = granted {
+        if let Ok((_, _, mut ball_state, ..)) = ball_query.get_mut(ball_entity) {
+            *ball_state = BallState::Held(player_entity);
+        }
+        if let Some(loose_ball) = loose_ball.as_deref_mut() {
+            loose_ball.end_scramble();
+        }
+        commands
+            .entity(player_entity)
+            .insert(HoldingBall(ball_entity));
+        match scoop {
+            Some((team, start_offset, ball_speed)) => {
+                info!("SCOOP: {:?} scoops a roller at {:.0}px/s", team, ball_speed);
                 commands
                     .entity(player_entity)
-                    .insert(HoldingBall(ball_entity));
-                // Reset charge so it starts fresh (even if throw button is held)
-                charging.charge_time = 0.0;
-                picked_up = true;
-                break;
+                    .insert(Scooping::new(start_offset, ball_config.scoop_window));
+                event_bus.emit(GameEvent::Scoop {
+                    player: match team {
+                        Team::Left => PlayerId::L,
+                        Team::Right => PlayerId::R,
+                    },
+                    ball_speed,
+                });
+            }
+            None => {
+                commands.entity(player_entity).remove::<Scooping>();
             }
         }
+        if let Ok((_, _, _, mut charging, mut input, ..)) =
+            non_holding_players.get_mut(player_entity)
+        {
+            // Reset charge so it starts fresh (even if throw button is held)
+            charging.reset();
+            input.pickup_pressed = false;
+        }
+        return; // Done - picked up ball
+    }
 
-        if picked_up {
-            return; // Done - picked up ball
+    // Then steal attempts for anyone still pressing
+    for (player_entity, player_transform, team, _, mut input, mut cooldown, _, _) in
+        &mut non_holding_players
+    {
+        if !input.pickup_pressed {
+            continue;
         }
 
+        // Consume the input
+        input.pickup_pressed = false;
+
+        let player_pos = player_transform.translation.truncate();
+
         // Skip steal attempts if on cooldown, but give visual feedback
         if cooldown.0 > 0.0 {
             // Show "blocked by cooldown" feedback so player knows their press was eaten
-            steal_contest.cooldown_blocked_timer = 0.15; // Brief flash
-            steal_contest.cooldown_blocked_entity = Some(player_entity);
+            deny_steal(
+                &mut steal_contest,
+                &mut event_bus,
+                player_entity,
+                *team,
+                StealDenial::OnCooldown,
+            );
             continue;
         }
 
@@ -230,26 +496,37 @@ pub fn pickup_ball(
             defender_charging,
             mut defender_velocity,
             mut defender_cooldown,
+            defender_scooping,
+            defender_team,
         ) in &mut holding_players
         {
+            // No stealing from a teammate (2v2)
+            if defender_team == team {
+                continue;
+            }
             let distance = player_pos.distance(defender_transform.translation.truncate());
             nearest_defender_distance = nearest_defender_distance.min(distance);
 
-            if distance < STEAL_RANGE {
+            if distance < steal.range {
                 // Record the attempt BEFORE rolling for success
                 steal_tracker.record_attempt(*team);
 
                 // Instant steal attempt - calculate success chance
-                let mut success_chance = STEAL_SUCCESS_CHANCE;
+                let mut success_chance = steal.success_chance;
 
                 // Bonus if defender is charging a shot
                 if defender_charging.charge_time > 0.0 {
-                    success_chance += STEAL_CHARGING_BONUS;
+                    success_chance += steal.charging_bonus;
                 }
 
                 // Apply graduated difficulty modifier (rubber-banding)
                 success_chance = (success_chance * steal_modifier).clamp(0.0, 1.0);
 
+                // Can't strip the ball mid-scoop
+                if defender_scooping.is_some_and(Scooping::active) {
+                    success_chance = 0.0;
+                }
+
                 // Roll for success
                 let mut rng = rand::thread_rng();
                 let roll: f32 = rng.gen_range(0.0..1.0);
@@ -266,14 +543,40 @@ pub fn pickup_ball(
                 );
 
                 if roll < success_chance {
-                    // Steal succeeded! Transfer ball
+                    // Steal succeeded! Transfer ball (or knock it loose)
                     let ball_entity = holding.0;
-                    if let Ok((_, _, mut ball_state)) = ball_query.get_mut(ball_entity) {
-                        *ball_state = BallState::Held(player_entity);
+                    if let Ok((_, _, mut ball_state, mut ball_velocity, mut rolling, mut grace)) =
+                        ball_query.get_mut(ball_entity)
+                    {
                         commands.entity(defender_entity).remove::<HoldingBall>();
-                        commands
-                            .entity(player_entity)
-                            .insert(HoldingBall(ball_entity));
+                        match loose_ball.as_deref_mut().filter(|l| l.enabled) {
+                            Some(loose_ball) => {
+                                // Pop the ball free - both players scramble for it
+                                let pop = LooseBall::pop_velocity(&mut rng);
+                                *ball_state = BallState::Free;
+                                ball_velocity.0 = pop;
+                                rolling.0 = false;
+                                grace.0 = LOOSE_BALL_GRACE;
+                                loose_ball.start_scramble();
+                                info!(
+                                    "LOOSE BALL: {:?} knocks it free (pop {:.0}, {:.0})",
+                                    team, pop.x, pop.y
+                                );
+                                event_bus.emit(GameEvent::LooseBall {
+                                    attacker: match team {
+                                        Team::Left => PlayerId::L,
+                                        Team::Right => PlayerId::R,
+                                    },
+                                    pop: (pop.x, pop.y),
+                                });
+                            }
+                            None => {
+                                *ball_state = BallState::Held(player_entity);
+                                commands
+                                    .entity(player_entity)
+                                    .insert(HoldingBall(ball_entity));
+                            }
+                        }
 
                         // Record the success for tracking
                         steal_tracker.record_success(*team);
@@ -285,25 +588,35 @@ pub fn pickup_ball(
                         } else {
                             -1.0
                         };
-                        defender_velocity.0.x += pushback_dir * STEAL_PUSHBACK_STRENGTH;
-                        defender_velocity.0.y += STEAL_PUSHBACK_STRENGTH * 0.3; // Small upward nudge
+                        defender_velocity.0.x += pushback_dir * steal.pushback_strength;
+                        defender_velocity.0.y += steal.pushback_strength * 0.3; // Small upward nudge
 
                         // Apply no-stealback cooldown to victim
-                        defender_cooldown.0 = STEAL_VICTIM_COOLDOWN;
+                        defender_cooldown.0 = steal.victim_cooldown;
 
                         // Short cooldown after successful steal
-                        cooldown.0 = STEAL_COOLDOWN;
+                        cooldown.0 = steal.cooldown;
                     }
                 } else {
-                    // Steal failed - set fail flash
-                    steal_contest.last_attempt_failed = true;
-                    steal_contest.fail_flash_timer = STEAL_FAIL_FLASH_DURATION;
-                    steal_contest.fail_flash_entity = Some(player_entity);
+                    // Steal failed - a zero chance means the holder was untouchable,
+                    // otherwise the attacker just lost the roll
+                    let reason = if success_chance <= 0.0 {
+                        StealDenial::HolderInvulnerable
+                    } else {
+                        StealDenial::ContestLost
+                    };
+                    deny_steal(
+                        &mut steal_contest,
+                        &mut event_bus,
+                        player_entity,
+                        *team,
+                        reason,
+                    );
 
                     steal_tracker.log_state("FAIL");
 
                     // Longer cooldown after failed steal (penalty for spam)
-                    cooldown.0 = STEAL_FAIL_COOLDOWN;
+                    cooldown.0 = steal.fail_cooldown;
                 }
 
                 return;
@@ -312,15 +625,77 @@ pub fn pickup_ball(
 
         // Check for "near miss" - defender nearby but outside steal range
         // This gives feedback to players who pressed steal too early
-        if nearest_defender_distance < STEAL_NEAR_MISS_RANGE {
-            steal_contest.out_of_range_timer = STEAL_OUT_OF_RANGE_FLASH_DURATION;
-            steal_contest.out_of_range_entity = Some(player_entity);
+        if nearest_defender_distance < steal.near_miss_range {
+            deny_steal(
+                &mut steal_contest,
+                &mut event_bus,
+                player_entity,
+                *team,
+                StealDenial::OutOfRange,
+            );
             // Short cooldown to prevent spam, but less punishing than actual failed steal
-            cooldown.0 = STEAL_OUT_OF_RANGE_COOLDOWN;
+            cooldown.0 = steal.out_of_range_cooldown;
             info!(
                 "STEAL OUT OF RANGE: {:?} at {:.1}px (need <{:.1}px)",
-                team, nearest_defender_distance, STEAL_RANGE
+                team, nearest_defender_distance, steal.range
             );
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claim(team: Team, x: f32, velocity_x: f32, facing: f32) -> PickupClaim {
+        PickupClaim {
+            entity: Entity::PLACEHOLDER,
+            team,
+            pos: Vec2::new(x, 0.0),
+            velocity: Vec2::new(velocity_x, 0.0),
+            facing,
+        }
+    }
+
+    #[test]
+    fn test_pickup_contest_winner() {
+        let ball = Vec2::ZERO;
+
+        // Closer wins
+        let left = claim(Team::Left, -40.0, 0.0, 1.0);
+        let right = claim(Team::Right, 20.0, 0.0, -1.0);
+        assert_eq!(pickup_contest_winner(ball, &left, &right).team, Team::Right);
+
+        // Same distance: moving toward the ball wins
+        let left = claim(Team::Left, -30.0, 0.0, 1.0);
+        let right = claim(Team::Right, 30.0, -200.0, -1.0);
+        assert_eq!(pickup_contest_winner(ball, &left, &right).team, Team::Right);
+
+        // Same distance and speed: facing the ball wins
+        let right = claim(Team::Right, 30.0, 0.0, 1.0);
+        assert_eq!(pickup_contest_winner(ball, &left, &right).team, Team::Left);
+
+        // Mirrored: left, whichever order the claims come in
+        let right = claim(Team::Right, 30.0, 0.0, -1.0);
+        assert_eq!(pickup_contest_winner(ball, &left, &right).team, Team::Left);
+        assert_eq!(pickup_contest_winner(ball, &right, &left).team, Team::Left);
+    }
+
+    #[test]
+    fn test_can_scoop() {
+        let config = BallConfig::default();
+        let (player, ball) = (Vec2::ZERO, Vec2::new(20.0, -10.0));
+        let run = Vec2::new(config.scoop_min_run_speed + 10.0, 0.0);
+        let roll = Vec2::new(config.scoop_max_ball_speed * 0.5, 0.0);
+        assert!(can_scoop(&config, player, run, ball, roll, true));
+        assert!(can_scoop(&config, player, -run, ball, -roll, true));
+
+        // Ball in the air, rolling too fast, player walking, or out of reach
+        assert!(!can_scoop(&config, player, run, ball, roll, false));
+        let fast = Vec2::new(config.scoop_max_ball_speed + 1.0, 0.0);
+        assert!(!can_scoop(&config, player, run, ball, fast, true));
+        assert!(!can_scoop(&config, player, run * 0.5, ball, roll, true));
+        let far = Vec2::new(config.pickup_radius + 1.0, 0.0);
+        assert!(!can_scoop(&config, player, run, far, roll, true));
+    }
+}
@@ -3,10 +3,18 @@
 mod components;
 mod interaction;
 mod physics;
+mod rails;
+mod recovery;
+mod style_physics;
+mod textures;
 
 pub use components::*;
 pub use interaction::*;
 pub use physics::*;
+pub use rails::*;
+pub use recovery::*;
+pub use style_physics::*;
+pub use textures::*;
 
 // Re-export Velocity from player since it's shared
 pub use crate::player::Velocity;
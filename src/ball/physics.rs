@@ -3,6 +3,7 @@
 use bevy::prelude::*;
 
 use crate::ball::components::*;
+use crate::config::BallConfig;
 use crate::constants::*;
 use crate::helpers::{ReflectAxis, apply_bounce_deflection};
 use crate::player::Velocity;
@@ -23,31 +24,40 @@ pub fn apply_velocity(mut query: Query<(&mut Transform, &Velocity)>, time: Res<T
 /// Apply gravity and friction to ball
 pub fn ball_gravity(
     tweaks: Res<PhysicsTweaks>,
-    mut query: Query<(&mut Velocity, &BallState, &BallRolling, &mut BallShotGrace), With<Ball>>,
+    mut query: Query<
+        (
+            &mut Velocity,
+            &BallState,
+            &BallRolling,
+            &mut BallShotGrace,
+            &BallPhysics,
+        ),
+        With<Ball>,
+    >,
     time: Res<Time>,
 ) {
     // Use minimum dt for headless mode compatibility
     let dt = time.delta_secs().max(1.0 / 60.0);
 
-    for (mut velocity, state, rolling, mut grace) in &mut query {
+    for (mut velocity, state, rolling, mut grace, physics) in &mut query {
         // Decrement grace timer
         if grace.0 > 0.0 {
             grace.0 = (grace.0 - dt).max(0.0);
         }
 
         match state {
-            BallState::Free | BallState::InFlight { .. } => {
+            BallState::Free | BallState::InFlight { .. } | BallState::Passed { .. } => {
                 if rolling.0 {
                     // Rolling on ground - no gravity, apply rolling friction (skip if grace active)
                     velocity.0.y = 0.0;
                     if grace.0 <= 0.0 {
-                        velocity.0.x *= tweaks.ball_roll_friction.powf(dt);
+                        velocity.0.x *= physics.retention(tweaks.ball_roll_friction, dt);
                     }
                 } else {
                     // In air - apply gravity, apply air friction only if no grace
                     velocity.0.y -= tweaks.ball_gravity * dt;
                     if grace.0 <= 0.0 {
-                        velocity.0.x *= tweaks.ball_air_friction.powf(dt);
+                        velocity.0.x *= physics.retention(tweaks.ball_air_friction, dt);
                     }
                 }
             }
@@ -62,6 +72,7 @@ pub fn ball_gravity(
 /// Handle ball collisions with platforms
 pub fn ball_collisions(
     tweaks: Res<PhysicsTweaks>,
+    ball_config: Res<BallConfig>,
     mut ball_query: Query<
         (
             &mut Transform,
@@ -69,6 +80,7 @@ pub fn ball_collisions(
             &BallState,
             &Sprite,
             &mut BallRolling,
+            &BallPhysics,
         ),
         With<Ball>,
     >,
@@ -84,15 +96,17 @@ pub fn ball_collisions(
 ) {
     let mut rng = rand::thread_rng();
 
-    for (mut ball_transform, mut ball_velocity, state, ball_sprite, mut rolling) in &mut ball_query
+    for (mut ball_transform, mut ball_velocity, state, ball_sprite, mut rolling, physics) in
+        &mut ball_query
     {
+        let bounce = physics.restitution(tweaks.ball_bounce);
         // Skip collision for held balls
         if matches!(state, BallState::Held(_)) {
             continue;
         }
 
         // Check if ball is in flight (thrown) - rims only collide with thrown/free balls
-        let is_thrown_or_free = matches!(state, BallState::Free | BallState::InFlight { .. });
+        let is_thrown_or_free = !matches!(state, BallState::Held(_));
 
         let ball_size = ball_sprite.custom_size.unwrap_or(BALL_SIZE);
         let ball_half = ball_size / 2.0;
@@ -158,14 +172,15 @@ pub fn ball_collisions(
                             rolling.0 = false;
                         } else {
                             // Normal floor bounce
-                            ball_velocity.0.x *= BALL_GROUND_FRICTION;
+                            ball_velocity.0.x *=
+                                physics.retention(ball_config.ground_friction, 1.0);
 
-                            let post_bounce_vel = ball_velocity.0.y.abs() * tweaks.ball_bounce;
+                            let post_bounce_vel = ball_velocity.0.y.abs() * bounce;
                             let max_bounce_height =
                                 (post_bounce_vel * post_bounce_vel) / (2.0 * tweaks.ball_gravity);
 
-                            if max_bounce_height > ball_size.y * BALL_BOUNCE_HEIGHT_MULT {
-                                ball_velocity.0.y = -ball_velocity.0.y * tweaks.ball_bounce;
+                            if max_bounce_height > ball_size.y * ball_config.bounce_height_mult {
+                                ball_velocity.0.y = -ball_velocity.0.y * bounce;
                                 rolling.0 = false;
                             } else {
                                 ball_velocity.0.y = 0.0;
@@ -196,7 +211,7 @@ pub fn ball_collisions(
                                 &mut rng,
                             );
                         } else {
-                            ball_velocity.0.y = -ball_velocity.0.y * tweaks.ball_bounce;
+                            ball_velocity.0.y = -ball_velocity.0.y * bounce;
                         }
                     }
                 }
@@ -226,7 +241,7 @@ pub fn ball_collisions(
                         &mut rng,
                     );
                 } else {
-                    ball_velocity.0.x = -ball_velocity.0.x * tweaks.ball_bounce;
+                    ball_velocity.0.x = -ball_velocity.0.x * bounce;
                 }
             }
         }
@@ -238,13 +253,19 @@ pub fn ball_collisions(
     }
 }
 
-/// Update ball state (InFlight -> Free when slow)
-pub fn ball_state_update(mut ball_query: Query<(&Velocity, &mut BallState), With<Ball>>) {
+/// Update ball state (InFlight/Passed -> Free when slow)
+pub fn ball_state_update(
+    ball_config: Res<BallConfig>,
+    mut ball_query: Query<(&Velocity, &mut BallState), With<Ball>>,
+) {
     for (velocity, mut state) in &mut ball_query {
-        // InFlight balls become Free when they slow down enough
-        if matches!(*state, BallState::InFlight { .. }) {
+        // Shots and uncaught passes become Free when they slow down enough
+        if matches!(
+            *state,
+            BallState::InFlight { .. } | BallState::Passed { .. }
+        ) {
             let speed = velocity.0.length();
-            if speed < BALL_FREE_SPEED {
+            if speed < ball_config.free_speed {
                 *state = BallState::Free;
             }
         }
@@ -254,6 +275,7 @@ pub fn ball_state_update(mut ball_query: Query<(&Velocity, &mut BallState), With
 /// Update ball spin/rotation based on velocity
 pub fn ball_spin(
     time: Res<Time>,
+    ball_config: Res<BallConfig>,
     mut query: Query<
         (
             &mut Transform,
@@ -284,8 +306,8 @@ pub fn ball_spin(
             spin.0 = -velocity.0.x / (BALL_SIZE.x / 2.0);
         } else {
             // In flight: spin based on velocity with decay
-            spin.0 = -velocity.0.x * BALL_SPIN_FACTOR;
-            spin.0 *= BALL_SPIN_DECAY.powf(dt);
+            spin.0 = -velocity.0.x * ball_config.spin_factor;
+            spin.0 *= ball_config.spin_decay.powf(dt);
         }
 
         // Apply rotation
@@ -0,0 +1,136 @@
+//! Ball safety rails - invisible ceiling and side rails above the arena
+//!
+//! On open levels a hard shot can sail far above the view and sit out of play
+//! until `recover_stuck_ball` resets it. Rails bounce it back instead: a ceiling
+//! `height` above the visible arena and side rails continuing the walls up to it,
+//! keeping `bounce` of the velocity. Both are set per level (`rails:` in the level
+//! file). Every hit is logged as `BallRailHit`, so levels where shots keep
+//! hitting the rails show up in the event logs.
+
+use bevy::prelude::*;
+
+use crate::ball::{Ball, BallState, Velocity};
+use crate::constants::BALL_SIZE;
+use crate::events::{EventBus, GameEvent, intern};
+use crate::levels::{ArenaDims, BallRails, LevelDatabase};
+use crate::scoring::CurrentLevel;
+
+/// Keep a ball (center `pos`, half-size `half`) inside the rails. Returns the rail
+/// it bounced off ("ceiling", "left" or "right"), if any. Side rails only apply
+/// above the visible arena, where the walls are out of view.
+pub fn bounce_off_rails(
+    rails: &BallRails,
+    arena: &ArenaDims,
+    pos: &mut Vec2,
+    velocity: &mut Vec2,
+    half: Vec2,
+) -> Option<&'static str> {
+    let ceiling = arena.ceiling_y() + rails.height;
+    if pos.y + half.y > ceiling && velocity.y > 0.0 {
+        pos.y = ceiling - half.y;
+        velocity.y = -velocity.y * rails.bounce;
+        return Some("ceiling");
+    }
+
+    let side = arena.wall_inner() - half.x;
+    if pos.y > arena.ceiling_y() && pos.x.abs() > side && pos.x * velocity.x > 0.0 {
+        let rail = if pos.x < 0.0 { "left" } else { "right" };
+        pos.x = side.copysign(pos.x);
+        velocity.x = -velocity.x * rails.bounce;
+        return Some(rail);
+    }
+    None
+}
+
+/// Bounce loose and in-flight balls off the current level's rails
+pub fn ball_rails(
+    current_level: Res<CurrentLevel>,
+    level_db: Res<LevelDatabase>,
+    mut event_bus: ResMut<EventBus>,
+    mut balls: Query<(&mut Transform, &mut Velocity, &BallState, &Sprite), With<Ball>>,
+) {
+    let Some(level) = level_db.get_by_id(&current_level.0) else {
+        return;
+    };
+    let Some(rails) = level.ball_rails else {
+        return;
+    };
+    let arena = level.arena();
+
+    for (mut transform, mut velocity, state, sprite) in &mut balls {
+        if matches!(state, BallState::Held(_)) {
+            continue;
+        }
+        let half = sprite.custom_size.unwrap_or(BALL_SIZE) / 2.0;
+        let mut pos = transform.translation.truncate();
+        let Some(rail) = bounce_off_rails(&rails, &arena, &mut pos, &mut velocity.0, half) else {
+            continue;
+        };
+        transform.translation.x = pos.x;
+        transform.translation.y = pos.y;
+        event_bus.emit(GameEvent::BallRailHit {
+            pos: (pos.x, pos.y),
+            rail: intern(rail),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rails_bounce_ball_back_into_play() {
+        let arena = ArenaDims::default();
+        let rails = BallRails {
+            height: 300.0,
+            bounce: 0.5,
+        };
+        let half = BALL_SIZE / 2.0;
+
+        // Rising through the ceiling rail: pushed back under it, falling at half speed
+        let mut pos = Vec2::new(0.0, arena.ceiling_y() + 310.0);
+        let mut vel = Vec2::new(100.0, 800.0);
+        assert_eq!(
+            bounce_off_rails(&rails, &arena, &mut pos, &mut vel, half),
+            Some("ceiling")
+        );
+        assert_eq!(pos.y, arena.ceiling_y() + 300.0 - half.y);
+        assert_eq!(vel, Vec2::new(100.0, -400.0));
+        // Already falling away: no second hit
+        assert_eq!(
+            bounce_off_rails(&rails, &arena, &mut pos, &mut vel, half),
+            None
+        );
+
+        // Past the right wall above the view
+        let mut pos = Vec2::new(arena.wall_inner() + 5.0, arena.ceiling_y() + 100.0);
+        let mut vel = Vec2::new(600.0, 0.0);
+        assert_eq!(
+            bounce_off_rails(&rails, &arena, &mut pos, &mut vel, half),
+            Some("right")
+        );
+        assert_eq!(pos.x, arena.wall_inner() - half.x);
+        assert_eq!(vel.x, -300.0);
+
+        // Inside the visible arena the real walls handle it
+        let mut pos = Vec2::new(-arena.wall_inner() - 5.0, arena.floor_top() + 100.0);
+        let mut vel = Vec2::new(-600.0, 0.0);
+        assert_eq!(
+            bounce_off_rails(&rails, &arena, &mut pos, &mut vel, half),
+            None
+        );
+
+        // Per-level settings
+        let db = LevelDatabase::parse(
+            "level: Open\nrails: 400 0.8\nlevel: Closed\nrails: off\nlevel: Plain\n",
+        );
+        let open = db.get_by_name("Open").unwrap().ball_rails.unwrap();
+        assert_eq!((open.height, open.bounce), (400.0, 0.8));
+        assert_eq!(db.get_by_name("Closed").unwrap().ball_rails, None);
+        assert_eq!(
+            db.get_by_name("Plain").unwrap().ball_rails,
+            Some(BallRails::default())
+        );
+    }
+}
@@ -0,0 +1,167 @@
+//! Stuck ball recovery - resets balls wedged out of reach or lost out of bounds
+//!
+//! Incidents are emitted as `BallStuck` events so problem spots in level design
+//! show up in the event logs. With the possession arrow rule on, the reset ball
+//! goes straight to the team the arrow points at.
+
+use bevy::prelude::*;
+
+use crate::ai::HeatmapBundle;
+use crate::ball::{Ball, BallState, BallStuckTimer, Velocity};
+use crate::config::ArenaConfig;
+use crate::constants::*;
+use crate::events::{EventBus, GameEvent, PlayerId, intern};
+use crate::levels::{ArenaDims, LevelDatabase};
+use crate::player::{HoldingBall, Player, Team};
+use crate::scoring::{CurrentLevel, PossessionArrow};
+use crate::shooting::ChargingShot;
+
+/// True if the ball is past the walls, under the floor, or far above the arena
+pub fn ball_out_of_bounds(pos: Vec2, arena: &ArenaDims, config: &ArenaConfig) -> bool {
+    pos.x.abs() > arena.wall_inner() + config.out_of_bounds_margin
+        || pos.y < arena.floor_y - config.out_of_bounds_margin
+        || pos.y > arena.ceiling_y() + config.max_height_above_ceiling
+}
+
+/// Reset a ball that sat out of reach (per the reachability heatmap) or stayed out
+/// of bounds for too long. Wedge detection is skipped when no reachability data is loaded.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn recover_stuck_ball(
+    mut commands: Commands,
+    time: Res<Time>,
+    arena_config: Res<ArenaConfig>,
+    current_level: Res<CurrentLevel>,
+    level_db: Res<LevelDatabase>,
+    heatmaps: Option<Res<HeatmapBundle>>,
+    mut event_bus: ResMut<EventBus>,
+    mut arrow: Option<ResMut<PossessionArrow>>,
+    mut players: Query<(Entity, &Team, &mut ChargingShot), (With<Player>, Without<HoldingBall>)>,
+    mut balls: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut Velocity,
+            &mut BallState,
+            &mut BallStuckTimer,
+        ),
+        With<Ball>,
+    >,
+) {
+    // Use minimum dt for headless mode compatibility
+    let dt = time.delta_secs().max(1.0 / 60.0);
+    let arena = level_db
+        .get_by_id(&current_level.0)
+        .map(|l| l.arena())
+        .unwrap_or_default();
+    let reachability = heatmaps
+        .as_deref()
+        .filter(|h| h.built_for_level_id == current_level.0 && h.reachability.has_data());
+
+    for (ball_entity, mut transform, mut velocity, mut state, mut timer) in &mut balls {
+        if matches!(*state, BallState::Held(_)) {
+            *timer = BallStuckTimer::default();
+            continue;
+        }
+
+        let pos = transform.translation.truncate();
+
+        if ball_out_of_bounds(pos, &arena, &arena_config) {
+            timer.out_of_bounds += dt;
+        } else {
+            timer.out_of_bounds = 0.0;
+        }
+
+        // Sample at the ball and half a player height up - a player standing next
+        // to a resting ball has their center above it
+        let wedged = velocity.0.length() < arena_config.stuck_speed
+            && reachability.is_some_and(|h| {
+                let reach = h
+                    .reachability_at(pos)
+                    .max(h.reachability_at(pos + Vec2::new(0.0, PLAYER_SIZE.y / 2.0)));
+                reach < arena_config.unreachable_threshold
+            });
+        if wedged {
+            timer.wedged += dt;
+        } else {
+            timer.wedged = 0.0;
+        }
+
+        let reason = if timer.out_of_bounds >= arena_config.out_of_bounds_time {
+            "out_of_bounds"
+        } else if timer.wedged >= arena_config.stuck_time {
+            "wedged"
+        } else {
+            continue;
+        };
+
+        warn!(
+            "Ball {} at ({:.0}, {:.0}) on level {} - resetting",
+            reason, pos.x, pos.y, current_level.0
+        );
+        event_bus.emit(GameEvent::BallStuck {
+            pos: (pos.x, pos.y),
+            reason: intern(reason),
+        });
+
+        transform.translation = level_db.ball_spawn(&current_level.0);
+        velocity.0 = Vec2::ZERO;
+        *state = BallState::Free;
+        *timer = BallStuckTimer::default();
+
+        let Some(team) = arrow.as_deref_mut().and_then(PossessionArrow::award) else {
+            continue;
+        };
+        let Some((player_entity, _, mut charging)) =
+            players.iter_mut().find(|(_, t, _)| **t == team)
+        else {
+            continue;
+        };
+        info!("Possession arrow awards the reset ball to {:?}", team);
+        event_bus.emit(GameEvent::PossessionAwarded {
+            player: match team {
+                Team::Left => PlayerId::L,
+                Team::Right => PlayerId::R,
+            },
+            reason: intern("stuck_ball"),
+        });
+        // Same as a pickup: ball_follow_holder moves it to the player next tick
+        *state = BallState::Held(player_entity);
+        commands
+            .entity(player_entity)
+            .insert(HoldingBall(ball_entity));
+        charging.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_out_of_bounds_margins() {
+        let arena = ArenaDims::default();
+        let config = ArenaConfig::default();
+        let floor = arena.floor_top();
+        assert!(!ball_out_of_bounds(Vec2::new(0.0, floor), &arena, &config));
+        assert!(!ball_out_of_bounds(
+            Vec2::new(0.0, arena.ceiling_y() + 100.0),
+            &arena,
+            &config
+        ));
+        assert!(ball_out_of_bounds(
+            Vec2::new(arena.wall_inner() + BALL_OUT_OF_BOUNDS_MARGIN + 1.0, floor),
+            &arena,
+            &config
+        ));
+        assert!(ball_out_of_bounds(
+            Vec2::new(0.0, arena.floor_y - BALL_OUT_OF_BOUNDS_MARGIN - 1.0),
+            &arena,
+            &config
+        ));
+        assert!(ball_out_of_bounds(
+            Vec2::new(0.0, arena.ceiling_y() + BALL_MAX_HEIGHT_ABOVE_CEILING + 1.0),
+            &arena,
+            &config
+        ));
+    }
+}
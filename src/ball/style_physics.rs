@@ -0,0 +1,147 @@
+//! Ball style physics - per-style bounce, friction and size modifiers
+//!
+//! A style block in `config/ball_options.txt` may add `bounce:`, `friction:`
+//! and `scale:` multipliers on top of the global tuning, turning a style into a
+//! mutator: a beach ball (big, bouncy, slows fast in the air) or a medicine
+//! ball (small bounce, dies on the floor). `scale:` rather than `size:`, since
+//! `size:` is the texture size for the generator. Styles without any of the
+//! keys play exactly like the tuning says. `apply_ball_style_physics` copies a
+//! style's modifiers onto the ball whenever its `BallStyle` is set, so spawning
+//! and cycling styles both pick them up.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::ball::{Ball, BallStyle};
+use crate::constants::*;
+
+/// A style's physics modifiers (multipliers, 1.0 = tuning as-is)
+#[derive(Component, Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BallPhysics {
+    /// Bounce restitution multiplier
+    pub bounce: f32,
+    /// Drag multiplier: 2.0 loses speed twice as fast while rolling, in the air and on floor bounces
+    pub friction: f32,
+    /// Ball size multiplier (sprite and collision box)
+    pub scale: f32,
+}
+
+impl Default for BallPhysics {
+    fn default() -> Self {
+        Self {
+            bounce: 1.0,
+            friction: 1.0,
+            scale: 1.0,
+        }
+    }
+}
+
+impl BallPhysics {
+    /// Restitution for a bounce. A style can't push it past BALL_STYLE_MAX_BOUNCE
+    /// (the ball must settle), but never lowers a higher tuned value either.
+    pub fn restitution(&self, base: f32) -> f32 {
+        (base * self.bounce).min(base.max(BALL_STYLE_MAX_BOUNCE))
+    }
+
+    /// Velocity retained: `base` is the fraction kept per unit (second or bounce)
+    pub fn retention(&self, base: f32, units: f32) -> f32 {
+        base.powf(units * self.friction)
+    }
+
+    pub fn size(&self) -> Vec2 {
+        BALL_SIZE * self.scale
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Physics modifiers per ball style, from ball_options.txt
+#[derive(Resource, Debug, Clone, Default)]
+pub struct BallStylePhysics {
+    pub styles: HashMap<String, BallPhysics>,
+}
+
+impl BallStylePhysics {
+    /// Parse `bounce:`, `friction:` and `scale:` lines inside style blocks
+    pub fn parse(options: &str) -> Self {
+        let mut styles = HashMap::new();
+        let mut current: Option<(String, BallPhysics)> = None;
+        for line in options.lines() {
+            let line = line.trim();
+            if let Some(name) = line.strip_prefix("style:") {
+                if let Some((name, physics)) = current.take() {
+                    styles.insert(name, physics);
+                }
+                current = Some((name.trim().to_string(), BallPhysics::default()));
+                continue;
+            }
+            let Some((_, physics)) = current.as_mut() else {
+                continue;
+            };
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let Ok(value) = value.trim().parse::<f32>() else {
+                continue;
+            };
+            match key.trim() {
+                "bounce" => physics.bounce = value.max(0.0),
+                "friction" => physics.friction = value.max(0.0),
+                "scale" => physics.scale = value.clamp(BALL_STYLE_MIN_SCALE, BALL_STYLE_MAX_SCALE),
+                _ => {}
+            }
+        }
+        if let Some((name, physics)) = current {
+            styles.insert(name, physics);
+        }
+        styles.retain(|_, physics| !physics.is_default());
+        Self { styles }
+    }
+
+    pub fn get(&self, style: &str) -> BallPhysics {
+        self.styles.get(style).copied().unwrap_or_default()
+    }
+}
+
+/// Give the ball its style's modifiers when spawned or restyled
+pub fn apply_ball_style_physics(
+    style_physics: Res<BallStylePhysics>,
+    mut balls: Query<(&BallStyle, &mut BallPhysics, &mut Sprite), (With<Ball>, Changed<BallStyle>)>,
+) {
+    for (style, mut physics, mut sprite) in &mut balls {
+        *physics = style_physics.get(style.name());
+        sprite.custom_size = Some(physics.size());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_style_physics() {
+        let options = "size: 128\nborder: 10\n\
+            style: s01\npattern: half\n\
+            style: beach\npattern: stripes\nbounce: 1.3\nfriction: 3\nscale: 1.6\n\
+            style: medicine\nbounce: 0.4\nfriction: 2.5\nscale: 9\n";
+        let physics = BallStylePhysics::parse(options);
+        // Plain styles aren't stored; the global `size:` isn't a style's scale
+        assert_eq!(physics.styles.len(), 2);
+        assert!(physics.get("s01").is_default());
+
+        let beach = physics.get("beach");
+        assert_eq!((beach.bounce, beach.friction, beach.scale), (1.3, 3.0, 1.6));
+        // Never bounces back harder than it came in
+        assert_eq!(beach.restitution(0.9), BALL_STYLE_MAX_BOUNCE);
+        // Loses speed three times as fast (in log terms)
+        assert!((beach.retention(0.5, 1.0) - 0.125).abs() < 1e-6);
+
+        let medicine = physics.get("medicine");
+        assert_eq!(medicine.scale, BALL_STYLE_MAX_SCALE);
+        assert!((medicine.restitution(0.5) - 0.2).abs() < 1e-6);
+        assert_eq!(medicine.size(), BALL_SIZE * BALL_STYLE_MAX_SCALE);
+    }
+}
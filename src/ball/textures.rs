@@ -0,0 +1,85 @@
+//! Ball texture resolution selection
+//!
+//! The camera fits the arena height to the window, so a ball's on-screen size
+//! changes with the window (V viewport cycling) and the level's arena height.
+//! Sampling a full-size texture down to a couple dozen pixels shimmers, so the
+//! smallest pre-generated size that still covers the ball is swapped in.
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::ball::components::*;
+use crate::constants::BALL_SIZE;
+use crate::levels::ArenaDims;
+use crate::palettes::PaletteDatabase;
+
+/// On-screen height in pixels of a ball for a window of `window_height`
+pub fn ball_screen_px(window_height: f32, arena: &ArenaDims) -> f32 {
+    BALL_SIZE.y * window_height / arena.height
+}
+
+/// Swap ball textures to the resolution that matches the current window and arena
+pub fn select_ball_texture_size(
+    asset_server: Res<AssetServer>,
+    arena: Res<ArenaDims>,
+    palette_db: Res<PaletteDatabase>,
+    current_palette: Res<CurrentPalette>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut ball_textures: ResMut<BallTextures>,
+    mut balls: Query<(&BallStyle, &mut Sprite)>,
+) {
+    if ball_textures.sizes.is_empty() {
+        return;
+    }
+    let Ok(window) = windows.single() else {
+        return;
+    };
+    let size = ball_textures.pick_size(ball_screen_px(window.height(), &arena));
+    if size == ball_textures.active_size {
+        return;
+    }
+
+    ball_textures.load_size(&asset_server, size, palette_db.len());
+    info!(
+        "Ball textures: {}",
+        size.map_or("full size".to_string(), |s| format!("{}px", s))
+    );
+    for (style, mut sprite) in &mut balls {
+        if let Some(texture) = ball_textures
+            .get(style.name())
+            .and_then(|textures| textures.textures.get(current_palette.0))
+        {
+            sprite.image = texture.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_smallest_size_covering_the_ball() {
+        let textures = BallTextures {
+            sizes: ball_texture_sizes("size: 128\nsizes: 64 32\n"),
+            ..default()
+        };
+        assert_eq!(textures.sizes, vec![32, 64]);
+
+        let arena = ArenaDims::default();
+        // 900px window on the default arena: ball drawn at its world size
+        let native = ball_screen_px(arena.height, &arena);
+        assert_eq!(textures.pick_size(native), Some(32));
+        assert_eq!(textures.pick_size(native * 2.0), Some(64));
+        assert_eq!(textures.pick_size(100.0), None);
+
+        assert_eq!(
+            ball_texture_path("s01", 3, Some(32)),
+            "textures/balls/32/ball_s01_3.png"
+        );
+        assert_eq!(
+            ball_texture_path("s01", 3, None),
+            "textures/balls/ball_s01_3.png"
+        );
+    }
+}
@@ -12,13 +12,19 @@ use std::path::PathBuf;
 
 use rusqlite::Connection;
 
+use ballgame::ai::SHOT_MODELS_DIR;
 use ballgame::analytics::{
     AggregateMetrics, AnalysisQuery, AnalysisRequest, AnalysisRequestFile, Leaderboard,
-    ParameterSuggestion, TrainingDebugReport, TuningTargets, default_targets, format_suggestions,
-    format_update_report, generate_suggestions, load_targets, parse_all_matches_from_db,
-    run_event_audit, run_focused_analysis, run_request, run_training_debug_analysis,
+    ParameterSuggestion, TrainingDebugReport, TuningTargets, default_targets, fit_shot_models,
+    format_suggestions, format_update_report, generate_suggestions, load_targets,
+    parse_all_matches_from_db, run_decision_timeline, run_event_audit, run_fingerprint_analysis,
+    run_focused_analysis, run_invariant_audit, run_level_ratings, run_nav_edge_report, run_request,
+    run_sample_traces, run_shot_model_evaluation, run_training_debug_analysis,
     update_default_profiles,
 };
+use ballgame::constants::LEVELS_FILE;
+use ballgame::db_maintenance::latest_training_db;
+use ballgame::levels::{LEVEL_RATINGS_FILE, LevelDatabase, LevelRatings};
 
 fn main() {
     let config = AnalyzeConfig::from_args();
@@ -187,6 +193,225 @@ fn main() {
         return;
     }
 
+    // Event invariant checks (single DB); exits non-zero on violations
+    if let Some(db_path) = &config.event_invariants {
+        let report = run_invariant_audit(db_path).unwrap_or_else(|e| {
+            eprintln!("Failed to run invariant checks: {}", e);
+            std::process::exit(1);
+        });
+
+        let output_path = config
+            .audit_output
+            .clone()
+            .unwrap_or_else(default_invariants_output_path);
+        if let Some(parent) = output_path.parent() {
+            std::fs::create_dir_all(parent).ok();
+        }
+        if let Err(e) = std::fs::write(&output_path, report.to_markdown()) {
+            eprintln!("Failed to write invariant report: {}", e);
+            std::process::exit(1);
+        }
+        println!(
+            "Checked {} events across {} matches: {} violation(s)",
+            report.events_checked,
+            report.matches_checked,
+            report.violations.len()
+        );
+        for v in report.violations.iter().take(10) {
+            println!(
+                "  match {} @ {}ms [{}] {}",
+                v.match_id, v.time_ms, v.rule, v.detail
+            );
+        }
+        println!("Invariant report written to {}", output_path.display());
+        if !report.violations.is_empty() {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Behavioral fingerprints per profile (single DB)
+    if let Some(db_path) = &config.fingerprint_db {
+        let report = run_fingerprint_analysis(db_path)
+            .unwrap_or_else(|e| {
+                eprintln!("Failed to run fingerprint analysis: {}", e);
+                std::process::exit(1);
+            })
+            .to_markdown();
+        let output_path = config
+            .fingerprint_output
+            .clone()
+            .unwrap_or_else(default_fingerprint_output_path);
+        if let Some(parent) = output_path.parent() {
+            std::fs::create_dir_all(parent).ok();
+        }
+        if let Err(e) = std::fs::write(&output_path, &report) {
+            eprintln!("Failed to write fingerprint report: {}", e);
+            std::process::exit(1);
+        }
+        println!("Fingerprint report written to {}", output_path.display());
+        return;
+    }
+
+    // Nav edge success rates per level (single DB)
+    if let Some(db_path) = &config.nav_edges_db {
+        let report = run_nav_edge_report(db_path)
+            .unwrap_or_else(|e| {
+                eprintln!("Failed to run nav edge report: {}", e);
+                std::process::exit(1);
+            })
+            .to_markdown();
+        let output_path = config
+            .nav_edges_output
+            .clone()
+            .unwrap_or_else(default_nav_edges_output_path);
+        if let Some(parent) = output_path.parent() {
+            std::fs::create_dir_all(parent).ok();
+        }
+        if let Err(e) = std::fs::write(&output_path, &report) {
+            eprintln!("Failed to write nav edge report: {}", e);
+            std::process::exit(1);
+        }
+        println!("Nav edge report written to {}", output_path.display());
+        return;
+    }
+
+    // Level difficulty/fairness ratings (single DB), stored for the level select
+    if let Some(db_path) = &config.level_ratings_db {
+        let report = run_level_ratings(db_path).unwrap_or_else(|e| {
+            eprintln!("Failed to run level ratings: {}", e);
+            std::process::exit(1);
+        });
+        let level_db = LevelDatabase::load_from_file(LEVELS_FILE);
+        let mut ratings = LevelRatings::load();
+        for name in report.apply(&level_db, &mut ratings) {
+            eprintln!(
+                "Warning: level '{}' isn't in {}, not rated",
+                name, LEVELS_FILE
+            );
+        }
+        if let Err(e) = ratings.save() {
+            eprintln!("Failed to write {}: {}", LEVEL_RATINGS_FILE, e);
+            std::process::exit(1);
+        }
+        println!(
+            "Rated {} levels in {}",
+            report.levels.len(),
+            LEVEL_RATINGS_FILE
+        );
+        let output_path = config
+            .level_ratings_output
+            .clone()
+            .unwrap_or_else(default_level_ratings_output_path);
+        if let Some(parent) = output_path.parent() {
+            std::fs::create_dir_all(parent).ok();
+        }
+        if let Err(e) = std::fs::write(&output_path, report.to_markdown()) {
+            eprintln!("Failed to write level ratings report: {}", e);
+            std::process::exit(1);
+        }
+        println!("Level ratings report written to {}", output_path.display());
+        return;
+    }
+
+    // AI goal band chart per match (single DB)
+    if let Some(db_path) = &config.timeline_db {
+        let output_path = config
+            .timeline_output
+            .clone()
+            .unwrap_or_else(default_timeline_output_path);
+        if let Some(parent) = output_path.parent() {
+            std::fs::create_dir_all(parent).ok();
+        }
+        let chart_path = output_path.with_extension("png");
+        let report = run_decision_timeline(db_path, &chart_path)
+            .unwrap_or_else(|e| {
+                eprintln!("Failed to build decision timelines: {}", e);
+                std::process::exit(1);
+            })
+            .to_markdown();
+        if let Err(e) = std::fs::write(&output_path, &report) {
+            eprintln!("Failed to write decision timeline report: {}", e);
+            std::process::exit(1);
+        }
+        println!(
+            "Decision timeline report written to {} (chart: {})",
+            output_path.display(),
+            chart_path.display()
+        );
+        return;
+    }
+
+    // Per-player physics traces for one match (single DB)
+    if let Some((db_path, match_id)) = &config.sample_traces {
+        let output_path = config
+            .traces_output
+            .clone()
+            .unwrap_or_else(|| default_traces_output_path(*match_id));
+        if let Some(parent) = output_path.parent() {
+            std::fs::create_dir_all(parent).ok();
+        }
+        let chart_path = output_path.with_extension("png");
+        let page = run_sample_traces(db_path, *match_id, &chart_path)
+            .unwrap_or_else(|e| {
+                eprintln!("Failed to build sample traces: {}", e);
+                std::process::exit(1);
+            })
+            .to_html();
+        if let Err(e) = std::fs::write(&output_path, &page) {
+            eprintln!("Failed to write sample trace page: {}", e);
+            std::process::exit(1);
+        }
+        println!(
+            "Sample traces written to {} (chart: {})",
+            output_path.display(),
+            chart_path.display()
+        );
+        return;
+    }
+
+    // Shot-quality models: fit a new version, or evaluate stored versions
+    let models_dir = config
+        .shot_models_dir
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(SHOT_MODELS_DIR));
+    if let Some((db_path, version)) = &config.fit_shot_models {
+        match fit_shot_models(db_path, &models_dir, version) {
+            Ok(paths) => {
+                for path in &paths {
+                    println!("Wrote {}", path.display());
+                }
+                println!("Fitted {} shot model(s) as {}", paths.len(), version);
+            }
+            Err(e) => {
+                eprintln!("Failed to fit shot models: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+    if let Some(db_path) = &config.shot_model_eval {
+        let report = run_shot_model_evaluation(db_path, &models_dir)
+            .unwrap_or_else(|e| {
+                eprintln!("Failed to evaluate shot models: {}", e);
+                std::process::exit(1);
+            })
+            .to_markdown();
+        let output_path = config
+            .shot_model_output
+            .clone()
+            .unwrap_or_else(default_shot_model_output_path);
+        if let Some(parent) = output_path.parent() {
+            std::fs::create_dir_all(parent).ok();
+        }
+        if let Err(e) = std::fs::write(&output_path, &report) {
+            eprintln!("Failed to write shot model report: {}", e);
+            std::process::exit(1);
+        }
+        println!("Shot model evaluation written to {}", output_path.display());
+        return;
+    }
+
     // Focused analysis (single DB)
     if let Some(db_path) = &config.focused_db {
         let report = run_focused_analysis(db_path)
@@ -293,7 +518,22 @@ struct AnalyzeConfig {
     targets_file: Option<PathBuf>,
     output_file: Option<PathBuf>,
     event_audit: Option<(PathBuf, PathBuf)>,
+    event_invariants: Option<PathBuf>,
     audit_output: Option<PathBuf>,
+    fingerprint_db: Option<PathBuf>,
+    fingerprint_output: Option<PathBuf>,
+    nav_edges_db: Option<PathBuf>,
+    nav_edges_output: Option<PathBuf>,
+    level_ratings_db: Option<PathBuf>,
+    level_ratings_output: Option<PathBuf>,
+    timeline_db: Option<PathBuf>,
+    timeline_output: Option<PathBuf>,
+    sample_traces: Option<(PathBuf, i64)>,
+    traces_output: Option<PathBuf>,
+    fit_shot_models: Option<(PathBuf, String)>,
+    shot_model_eval: Option<PathBuf>,
+    shot_model_output: Option<PathBuf>,
+    shot_models_dir: Option<PathBuf>,
     focused_db: Option<PathBuf>,
     focused_output: Option<PathBuf>,
     training_db: Option<PathBuf>,
@@ -315,11 +555,26 @@ struct AnalyzeConfig {
 impl Default for AnalyzeConfig {
     fn default() -> Self {
         Self {
-            db_path: PathBuf::from("db/training.db"),
+            db_path: latest_training_db(),
             targets_file: None,
             output_file: None,
             event_audit: None,
+            event_invariants: None,
             audit_output: None,
+            fingerprint_db: None,
+            fingerprint_output: None,
+            nav_edges_db: None,
+            nav_edges_output: None,
+            level_ratings_db: None,
+            level_ratings_output: None,
+            timeline_db: None,
+            timeline_output: None,
+            sample_traces: None,
+            traces_output: None,
+            fit_shot_models: None,
+            shot_model_eval: None,
+            shot_model_output: None,
+            shot_models_dir: None,
             focused_db: None,
             focused_output: None,
             training_db: None,
@@ -367,12 +622,111 @@ impl AnalyzeConfig {
                         i += 2;
                     }
                 }
+                "--event-invariants" => {
+                    if i + 1 < args.len() {
+                        config.event_invariants = Some(PathBuf::from(&args[i + 1]));
+                        i += 1;
+                    }
+                }
                 "--audit-output" => {
                     if i + 1 < args.len() {
                         config.audit_output = Some(PathBuf::from(&args[i + 1]));
                         i += 1;
                     }
                 }
+                "--fingerprints" => {
+                    if i + 1 < args.len() {
+                        config.fingerprint_db = Some(PathBuf::from(&args[i + 1]));
+                        i += 1;
+                    }
+                }
+                "--fingerprint-output" => {
+                    if i + 1 < args.len() {
+                        config.fingerprint_output = Some(PathBuf::from(&args[i + 1]));
+                        i += 1;
+                    }
+                }
+                "--nav-edges" => {
+                    if i + 1 < args.len() {
+                        config.nav_edges_db = Some(PathBuf::from(&args[i + 1]));
+                        i += 1;
+                    }
+                }
+                "--nav-edges-output" => {
+                    if i + 1 < args.len() {
+                        config.nav_edges_output = Some(PathBuf::from(&args[i + 1]));
+                        i += 1;
+                    }
+                }
+                "--level-ratings" => {
+                    if i + 1 < args.len() {
+                        config.level_ratings_db = Some(PathBuf::from(&args[i + 1]));
+                        i += 1;
+                    }
+                }
+                "--level-ratings-output" => {
+                    if i + 1 < args.len() {
+                        config.level_ratings_output = Some(PathBuf::from(&args[i + 1]));
+                        i += 1;
+                    }
+                }
+                "--decision-timeline" => {
+                    if i + 1 < args.len() {
+                        config.timeline_db = Some(PathBuf::from(&args[i + 1]));
+                        i += 1;
+                    }
+                }
+                "--timeline-output" => {
+                    if i + 1 < args.len() {
+                        config.timeline_output = Some(PathBuf::from(&args[i + 1]));
+                        i += 1;
+                    }
+                }
+                "--sample-traces" => {
+                    if i + 2 < args.len() {
+                        match args[i + 2].parse::<i64>() {
+                            Ok(match_id) => {
+                                config.sample_traces = Some((PathBuf::from(&args[i + 1]), match_id))
+                            }
+                            Err(_) => eprintln!(
+                                "Warning: --sample-traces: invalid match id '{}'",
+                                args[i + 2]
+                            ),
+                        }
+                        i += 2;
+                    }
+                }
+                "--traces-output" => {
+                    if i + 1 < args.len() {
+                        config.traces_output = Some(PathBuf::from(&args[i + 1]));
+                        i += 1;
+                    }
+                }
+                "--fit-shot-models" => {
+                    if i + 2 < args.len() {
+                        config.fit_shot_models =
+                            Some((PathBuf::from(&args[i + 1]), args[i + 2].clone()));
+                        i += 2;
+                    }
+                }
+                "--shot-model-eval" => {
+                    if i + 1 < args.len() {
+                        config.shot_model_eval = Some(PathBuf::from(&args[i + 1]));
+                        i += 1;
+                    }
+                }
+                "--shot-model-output" => {
+                    if i + 1 < args.len() {
+                        config.shot_model_output = Some(PathBuf::from(&args[i + 1]));
+                        i += 1;
+                    }
+                }
+                "--shot-models-dir" => {
+                    if i + 1 < args.len() {
+                        config.shot_models_dir = Some(PathBuf::from(&args[i + 1]));
+                        i += 1;
+                    }
+                }
                 "--focused" => {
                     if i + 1 < args.len() {
                         config.focused_db = Some(PathBuf::from(&args[i + 1]));
@@ -487,7 +841,22 @@ OPTIONS:
     --targets <FILE>    Load tuning targets from TOML file
     --output, -o <FILE> Write full report to file
     --event-audit <BASE_DB> <CURRENT_DB>  Compare two DBs via event audit queries
-    --audit-output <FILE> Write event audit report to file (default: notes/analysis_runs/...)
+    --event-invariants <DB>  Check logged events for invariant violations (exit 1 if any)
+    --audit-output <FILE> Write event audit/invariant report to file (default: notes/analysis_runs/...)
+    --fingerprints <DB>  Per-profile behavior fingerprints and similar-profile clusters
+    --fingerprint-output <FILE> Write fingerprint report to file (default: notes/analysis_runs/...)
+    --nav-edges <DB>     Per-level success rate of each AI nav edge (jumps, drops, portals)
+    --nav-edges-output <FILE> Write nav edge report to file (default: notes/analysis_runs/...)
+    --level-ratings <DB> Rate each level's difficulty and side bias, save to config/level_ratings.json
+    --level-ratings-output <FILE> Write level ratings report to file (default: notes/analysis_runs/...)
+    --decision-timeline <DB>  Band chart of each AI's goal over time per match, flags oscillation/stalls
+    --timeline-output <FILE> Write timeline report to file; chart goes next to it as .png
+    --sample-traces <DB> <MATCH_ID>  Plot velocity, grounded and input traces from debug samples
+    --traces-output <FILE> Write trace page (.html) to file; chart goes next to it as .png
+    --fit-shot-models <DB> <VERSION>  Fit per-level shot-quality models and save them as VERSION
+    --shot-model-eval <DB>  Compare stored shot models' predictions with actual makes
+    --shot-model-output <FILE> Write shot model evaluation to file (default: notes/analysis_runs/...)
+    --shot-models-dir <DIR> Shot model directory (default: config/shot_models)
     --focused <DB>       Run focused analysis on a single DB
     --focused-output <FILE> Write focused report to file (default: notes/analysis_runs/...)
     --training-db <DB>   Run training debug analysis on a training DB
@@ -518,6 +887,28 @@ EXAMPLES:
     # Event audit: compare baseline vs current tournament DBs
     cargo run --bin analyze -- --event-audit db/baseline.db db/current.db
 
+    # Data-quality gate: score/possession invariants over every logged match
+    cargo run --bin analyze -- --event-invariants db/current.db
+
+    # Find redundant or degenerate profiles
+    cargo run --bin analyze -- --fingerprints db/tournament.db
+
+    # Spot AIs that flip-flop between goals or get stuck in one
+    cargo run --bin analyze -- --decision-timeline db/tournament.db
+
+    # Physics debugging: velocity/grounded/input over time with event markers
+    cargo run --bin analyze -- --sample-traces db/training_YYYYMMDD_HHMMSS.db 3
+
+    # Find jumps and drops the AI keeps missing on each level
+    cargo run --bin analyze -- --nav-edges db/tournament.db
+
+    # Rate level difficulty/fairness for the level select
+    cargo run --bin analyze -- --level-ratings db/tournament.db
+
+    # Fit shot-quality models on one DB, check calibration on another
+    cargo run --bin analyze -- --fit-shot-models db/train.db v2
+    cargo run --bin analyze -- --shot-model-eval db/holdout.db
+
     # Focused analysis: deep dive on a single DB
     cargo run --bin analyze -- --focused db/current.db
 
@@ -563,6 +954,53 @@ fn default_audit_output_path() -> PathBuf {
     PathBuf::from(format!("notes/analysis_runs/event_audit_{}.md", timestamp))
 }
 
+fn default_invariants_output_path() -> PathBuf {
+    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+    PathBuf::from(format!(
+        "notes/analysis_runs/event_invariants_{}.md",
+        timestamp
+    ))
+}
+
+fn default_fingerprint_output_path() -> PathBuf {
+    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+    PathBuf::from(format!("notes/analysis_runs/fingerprints_{}.md", timestamp))
+}
+
+fn default_nav_edges_output_path() -> PathBuf {
+    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+    PathBuf::from(format!("notes/analysis_runs/nav_edges_{}.md", timestamp))
+}
+
+fn default_level_ratings_output_path() -> PathBuf {
+    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+    PathBuf::from(format!(
+        "notes/analysis_runs/level_ratings_{}.md",
+        timestamp
+    ))
+}
+
+fn default_timeline_output_path() -> PathBuf {
+    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+    PathBuf::from(format!(
+        "notes/analysis_runs/decision_timeline_{}.md",
+        timestamp
+    ))
+}
+
+fn default_traces_output_path(match_id: i64) -> PathBuf {
+    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+    PathBuf::from(format!(
+        "notes/analysis_runs/sample_traces_{}_{}.html",
+        match_id, timestamp
+    ))
+}
+
+fn default_shot_model_output_path() -> PathBuf {
+    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+    PathBuf::from(format!("notes/analysis_runs/shot_models_{}.md", timestamp))
+}
+
 fn default_focused_output_path() -> PathBuf {
     let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
     PathBuf::from(format!("notes/analysis_runs/focused_{}.md", timestamp))
@@ -609,9 +1047,7 @@ fn training_session_count(db_path: &PathBuf) -> Option<usize> {
 fn infer_training_session_dir(db_path: &PathBuf) -> Option<PathBuf> {
     let mut resolved = db_path.clone();
     if db_path.file_name().and_then(|n| n.to_str()) == Some("training.db") {
-        if let Ok(target) = std::fs::read_link(db_path) {
-            resolved = target;
-        }
+        resolved = std::fs::read_link(db_path).unwrap_or_else(|_| latest_training_db());
     }
     let file_name = resolved.file_stem()?.to_string_lossy();
     let timestamp = file_name.strip_prefix("training_")?;
@@ -13,6 +13,7 @@ use std::path::{Path, PathBuf};
 
 use rusqlite::params;
 
+use ballgame::db_maintenance::latest_training_db;
 use ballgame::events::{GameEvent, PlayerId, parse_event};
 use ballgame::simulation::SimDatabase;
 
@@ -112,7 +113,7 @@ fn parse_match_from_db(db: &SimDatabase, match_id: i64) -> Vec<Drive> {
                 ..
             } => {
                 level = lvl;
-                level_name = name;
+                level_name = name.to_string();
             }
             GameEvent::Pickup { player } => {
                 let player = player_char(player);
@@ -287,7 +288,7 @@ fn main() {
         std::process::exit(1);
     }
 
-    let mut db_path = PathBuf::from("db/training.db");
+    let mut db_path = latest_training_db();
     let mut session_id: Option<String> = None;
     let mut match_id: Option<i64> = None;
     let mut output_dir: Option<PathBuf> = None;
@@ -0,0 +1,107 @@
+//! Scenario Extractor
+//!
+//! Turns a moment from a logged match into a scenario test, so a bug seen in a
+//! replay becomes a reproducible `test-scenarios` case.
+//!
+//! Usage:
+//!   cargo run --bin extract-scenario -- --match <MATCH_ID> --from <TICK> --to <TICK>
+//!   cargo run --bin extract-scenario -- --db db/sim.db --match 42 --from 1200 --to 1500 --output tests/scenarios/regressions/
+
+use std::fs;
+use std::path::PathBuf;
+
+use ballgame::db_maintenance::latest_training_db;
+use ballgame::replay::load_replay_from_db;
+use ballgame::testing::extract::to_toml;
+use ballgame::testing::{EXTRACTED_SCENARIOS_DIR, extract_scenario};
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+
+    let mut db_path = latest_training_db();
+    let mut match_id: Option<i64> = None;
+    let mut from_tick: Option<u64> = None;
+    let mut to_tick: Option<u64> = None;
+    let mut output_dir = PathBuf::from(EXTRACTED_SCENARIOS_DIR);
+
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--db" if i + 1 < args.len() => {
+                db_path = PathBuf::from(&args[i + 1]);
+                i += 2;
+            }
+            "--match" if i + 1 < args.len() => {
+                match_id = args[i + 1].parse().ok();
+                i += 2;
+            }
+            "--from" if i + 1 < args.len() => {
+                from_tick = args[i + 1].parse().ok();
+                i += 2;
+            }
+            "--to" if i + 1 < args.len() => {
+                to_tick = args[i + 1].parse().ok();
+                i += 2;
+            }
+            "--output" if i + 1 < args.len() => {
+                output_dir = PathBuf::from(&args[i + 1]);
+                i += 2;
+            }
+            _ => {
+                i += 1;
+            }
+        }
+    }
+
+    let (Some(match_id), Some(from_tick), Some(to_tick)) = (match_id, from_tick, to_tick) else {
+        eprintln!(
+            "Usage: {} --match <id> --from <tick> --to <tick> [--db <path>] [--output <dir>]",
+            args[0]
+        );
+        eprintln!();
+        eprintln!("Extracts a tick range of a logged match as a scenario test (TOML).");
+        eprintln!("Tick numbers are the `frame` field of logged Tick events.");
+        std::process::exit(1);
+    };
+
+    let replay = match load_replay_from_db(&db_path, match_id) {
+        Ok(replay) => replay,
+        Err(e) => {
+            eprintln!(
+                "Failed to load match {} from {}: {}",
+                match_id,
+                db_path.display(),
+                e
+            );
+            std::process::exit(1);
+        }
+    };
+
+    let test = match extract_scenario(&replay, match_id, from_tick, to_tick) {
+        Ok(test) => test,
+        Err(e) => {
+            eprintln!("Extraction failed: {}", e);
+            std::process::exit(1);
+        }
+    };
+    if test.input.is_empty() {
+        eprintln!("Warning: no logged inputs in this range, players will stand still");
+    }
+
+    fs::create_dir_all(&output_dir).expect("Failed to create output directory");
+    let path = output_dir.join(format!("{}.toml", test.name));
+    fs::write(&path, to_toml(&test)).expect("Failed to write scenario");
+
+    println!("Wrote {}", path.display());
+    println!(
+        "  {} input frames, {} expected events",
+        test.input.len(),
+        test.expect.sequence.len()
+    );
+    if let Ok(rel) = path.strip_prefix("tests/scenarios") {
+        println!(
+            "Run it: cargo run --bin test-scenarios -- {}",
+            rel.with_extension("").display()
+        );
+    }
+}
@@ -8,6 +8,7 @@
 //!   cargo run --bin generate levels     # Generate level showcase grid
 //!   cargo run --bin generate gif wedge  # Generate wedge rotation GIF
 //!   cargo run --bin generate gif baseball  # Generate baseball rotation GIF
+//!   cargo run --bin generate all        # Everything in config/generate_manifest.txt
 //!   cargo run --bin generate --help     # Show help
 
 use ballgame::generate;
@@ -55,6 +56,27 @@ fn main() {
                 }
             }
         }
+        "all" => {
+            let refresh = args.iter().any(|a| a == "--refresh");
+            let manifest_path = args
+                .iter()
+                .position(|a| a == "--manifest")
+                .and_then(|i| args.get(i + 1))
+                .map(|s| s.as_str())
+                .unwrap_or(generate::MANIFEST_FILE);
+            let manifest = match generate::Manifest::load(manifest_path) {
+                Ok(manifest) => manifest,
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            };
+            let failed = generate::run_all(&manifest, refresh);
+            if !failed.is_empty() {
+                eprintln!("Failed outputs: {}", failed.join(", "));
+                std::process::exit(1);
+            }
+        }
         "--help" | "-h" | "help" => {
             print_help();
         }
@@ -89,12 +111,17 @@ COMMANDS:
     gif baseball   Generate baseball rotation GIF
                    Output: assets/baseball_frames/ + baseball.gif
 
+    all         Generate every output in config/generate_manifest.txt,
+                skipping outputs whose inputs haven't changed
+                Options: --refresh (rebuild everything), --manifest <file>
+
     help        Show this help message
 
 EXAMPLES:
     cargo run --bin generate ball
     cargo run --bin generate showcase
     cargo run --bin generate gif wedge
+    cargo run --bin generate all --refresh
 "#
     );
 }
@@ -33,24 +33,24 @@
 //! Full bundles write showcase/heatmaps/heatmap_full_<level>_<uuid>.png.
 //! Skips debug/regression levels and training protocol levels unless --level is specified.
 
+use ballgame::ai::heatmaps::{LEVEL_HASH_FILE, LevelHashCache};
+use ballgame::config_dir;
 use ballgame::training::TrainingProtocol;
 use ballgame::tuning::{load_gameplay_tuning_from_file, GameplayTuning, GAMEPLAY_TUNING_FILE};
 use ballgame::{
-    ARENA_FLOOR_Y, ARENA_HEIGHT, ARENA_WIDTH, BALL_BOUNCE, BALL_GRAVITY, CORNER_STEP_THICKNESS,
-    LevelDatabase, PLAYER_SIZE, RIM_THICKNESS, SHOT_DISTANCE_VARIANCE, SHOT_MIN_VARIANCE,
-    WALL_THICKNESS, basket_x_from_offset, calculate_shot_trajectory,
+    ArenaDims, BALL_BOUNCE, BALL_GRAVITY, CORNER_STEP_THICKNESS, LevelDatabase, PLAYER_SIZE,
+    RIM_THICKNESS, SHOT_DISTANCE_VARIANCE, SHOT_MIN_VARIANCE, WALL_THICKNESS, bounce_pad_xs,
+    calculate_shot_trajectory, portal_ends,
 };
 use bevy::prelude::Vec2;
 use image::{Rgb, RgbImage};
 use rand::Rng;
 use rayon::prelude::*;
-use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
 use std::fmt::Write as FmtWrite;
 use std::fs;
-use std::hash::{Hash, Hasher};
 use std::io::Write;
-use std::sync::{Mutex, OnceLock};
+use std::sync::{Mutex, OnceLock, RwLock};
 
 // Basket dimensions (matching ballgame constants)
 const BASKET_SIZE_X: f32 = 60.0;
@@ -59,8 +59,31 @@ const BALL_RADIUS: f32 = 12.0;
 
 // Grid settings
 const CELL_SIZE: u32 = 20; // pixels per cell
-const GRID_WIDTH: u32 = (ARENA_WIDTH as u32) / CELL_SIZE; // 80 cells
-const GRID_HEIGHT: u32 = (ARENA_HEIGHT as u32) / CELL_SIZE; // 45 cells
+
+// Arena of the level currently being generated (levels are processed one at a time)
+static ACTIVE_ARENA: OnceLock<RwLock<ArenaDims>> = OnceLock::new();
+
+fn arena() -> ArenaDims {
+    *ACTIVE_ARENA
+        .get_or_init(|| RwLock::new(ArenaDims::default()))
+        .read()
+        .unwrap()
+}
+
+fn set_active_arena(dims: ArenaDims) {
+    *ACTIVE_ARENA
+        .get_or_init(|| RwLock::new(ArenaDims::default()))
+        .write()
+        .unwrap() = dims;
+}
+
+fn grid_width() -> u32 {
+    (arena().width as u32) / CELL_SIZE // 80 cells on the default arena
+}
+
+fn grid_height() -> u32 {
+    (arena().height as u32) / CELL_SIZE // 45 cells on the default arena
+}
 
 // Speed range for color mapping
 const SPEED_MIN: f32 = 300.0; // Green
@@ -72,7 +95,6 @@ const MONTE_CARLO_DEFAULT: u32 = 50;
 const MONTE_CARLO_ACCURATE: u32 = 100;
 
 const LEVELS_FILE: &str = "config/levels.txt";
-const LEVEL_HASH_FILE: &str = "config/level_hashes.json";
 const OUTPUT_DIR: &str = "showcase/heatmaps";
 const HEATMAP_STATS_FILE: &str = "showcase/heatmaps/heatmap_stats.txt";
 
@@ -318,7 +340,7 @@ fn simulate_ball_flight(
         }
 
         // Ball fell below floor - miss
-        if y < ARENA_FLOOR_Y - 50.0 {
+        if y < arena().floor_y - 50.0 {
             return false;
         }
     }
@@ -354,6 +376,7 @@ fn simulate_scoring(
         basket_y,
         BALL_GRAVITY,
         SHOT_DISTANCE_VARIANCE,
+        None,
     ) else {
         return 0.0;
     };
@@ -403,6 +426,7 @@ fn simulate_scoring(
 // =============================================================================
 
 fn main() {
+    config_dir::init_from_args(&std::env::args().collect::<Vec<_>>());
     let config = parse_args();
     fs::create_dir_all(OUTPUT_DIR).expect("Failed to create heatmap output directory");
     if config.refresh {
@@ -441,8 +465,8 @@ fn main() {
             println!(
                 "Generating {} heatmap: {}x{} cells ({} pixels)",
                 heatmap_kind_label(kind),
-                GRID_WIDTH,
-                GRID_HEIGHT,
+                grid_width(),
+                grid_height(),
                 CELL_SIZE
             );
 run_single_kind(kind, &eligible_levels, &physics, config.trial_count);
@@ -450,7 +474,9 @@ run_single_kind(kind, &eligible_levels, &physics, config.trial_count);
         HeatmapMode::Full => {
             println!(
                 "Generating full heatmap bundle: {}x{} cells ({} pixels)",
-                GRID_WIDTH, GRID_HEIGHT, CELL_SIZE
+                grid_width(),
+                grid_height(),
+                CELL_SIZE
             );
 run_full_bundle(&eligible_levels, &physics, config.trial_count);
             if config.check && config.level_filter.is_empty() {
@@ -507,11 +533,6 @@ struct LevelChangeSet {
     removed_ids: Vec<String>,
 }
 
-#[derive(Serialize, Deserialize, Default)]
-struct LevelHashCache {
-    levels: HashMap<String, String>,
-}
-
 #[derive(Clone)]
 struct HeatmapGrid {
     values: Vec<f32>,
@@ -520,12 +541,12 @@ struct HeatmapGrid {
 impl HeatmapGrid {
     fn new() -> Self {
         Self {
-            values: vec![0.0; (GRID_WIDTH * GRID_HEIGHT) as usize],
+            values: vec![0.0; (grid_width() * grid_height()) as usize],
         }
     }
 
     fn index(cx: u32, cy: u32) -> usize {
-        (cy * GRID_WIDTH + cx) as usize
+        (cy * grid_width() + cx) as usize
     }
 
     fn get(&self, cx: u32, cy: u32) -> f32 {
@@ -601,8 +622,9 @@ fn run_single_kind(kind: HeatmapKind, levels: &[&ballgame::LevelData], physics:
     let mut generated_overlays = Vec::new();
 
     for level in levels {
-        let basket_y = ARENA_FLOOR_Y + level.basket_height;
-        let (left_x, right_x) = basket_x_from_offset(level.basket_push_in);
+        set_active_arena(level.arena());
+        let basket_y = arena().floor_y + level.basket_height;
+        let (left_x, right_x) = arena().basket_x_from_offset(level.basket_push_in);
         let platform_rects = build_platform_rects(level);
         let overlay = LevelOverlayContext {
             platform_rects: &platform_rects,
@@ -719,8 +741,9 @@ fn run_full_bundle(levels: &[&ballgame::LevelData], physics: &PhysicsConfig, tri
     let mut per_kind_overlays: HashMap<HeatmapKind, Vec<String>> = HashMap::new();
 
     for level in levels {
-        let basket_y = ARENA_FLOOR_Y + level.basket_height;
-        let (left_x, right_x) = basket_x_from_offset(level.basket_push_in);
+        set_active_arena(level.arena());
+        let basket_y = arena().floor_y + level.basket_height;
+        let (left_x, right_x) = arena().basket_x_from_offset(level.basket_push_in);
         let platform_rects = build_platform_rects(level);
         let reachability = compute_reachability(&platform_rects, physics);
         let overlay = LevelOverlayContext {
@@ -946,7 +969,8 @@ fn generate_heatmap_for_kind(
                 }
                 owned_reachability.as_ref().expect("reachability cache")
             };
-            let cost = compute_path_cost(reachability);
+            let links = build_feature_links(level, physics);
+            let cost = compute_path_cost(reachability, &links);
             generate_value_heatmap(level, "path_cost", &cost, 1.0, None, overlay)
         }
         HeatmapKind::LineOfSight => {
@@ -978,23 +1002,25 @@ fn build_platform_rects(level: &ballgame::LevelData) -> Vec<PlatformRect> {
     for platform in &level.platforms {
         match platform {
             ballgame::PlatformDef::Mirror { x, y, width } => {
-                let world_y = ARENA_FLOOR_Y + *y;
+                let world_y = arena().floor_y + *y;
                 rects.push(rect_from_center(-x, world_y, *width, 20.0));
                 rects.push(rect_from_center(*x, world_y, *width, 20.0));
             }
             ballgame::PlatformDef::Center { y, width } => {
-                let world_y = ARENA_FLOOR_Y + *y;
+                let world_y = arena().floor_y + *y;
                 rects.push(rect_from_center(0.0, world_y, *width, 20.0));
             }
+            // Not solid - handled as traversal links in path cost
+            ballgame::PlatformDef::Portal { .. } | ballgame::PlatformDef::BouncePad { .. } => {}
         }
     }
 
     if level.step_count > 0 {
-        let left_wall_inner = -ARENA_WIDTH / 2.0 + WALL_THICKNESS;
-        let right_wall_inner = ARENA_WIDTH / 2.0 - WALL_THICKNESS;
+        let left_wall_inner = -arena().width / 2.0 + WALL_THICKNESS;
+        let right_wall_inner = arena().width / 2.0 - WALL_THICKNESS;
         let step_height = level.corner_height / level.step_count as f32;
         let step_width = level.corner_width / level.step_count as f32;
-        let floor_top = ARENA_FLOOR_Y + 20.0;
+        let floor_top = arena().floor_y + 20.0;
 
         for i in 0..level.step_count {
             let step_num = (level.step_count - 1 - i) as f32;
@@ -1064,8 +1090,8 @@ fn generate_value_heatmap(
     let image_path = format!("{}/{}.png", OUTPUT_DIR, base_name);
     let data_path = format!("{}/{}.txt", OUTPUT_DIR, base_name);
 
-    let img_width = GRID_WIDTH * CELL_SIZE;
-    let img_height = GRID_HEIGHT * CELL_SIZE;
+    let img_width = grid_width() * CELL_SIZE;
+    let img_height = grid_height() * CELL_SIZE;
     let mut img = RgbImage::new(img_width, img_height);
 
     let bg_color = Rgb([230, 230, 230]);
@@ -1074,9 +1100,9 @@ fn generate_value_heatmap(
     }
 
     let mut data = String::from("x,y,value\n");
-    let mut values = Vec::with_capacity((GRID_WIDTH * GRID_HEIGHT) as usize);
-    for cy in 0..GRID_HEIGHT {
-        for cx in 0..GRID_WIDTH {
+    let mut values = Vec::with_capacity((grid_width() * grid_height()) as usize);
+    for cy in 0..grid_height() {
+        for cx in 0..grid_width() {
             let value = grid.get(cx, cy).clamp(0.0, 1.0);
             let color = score_to_color(value);
             fill_cell(&mut img, cx, cy, color);
@@ -1112,16 +1138,16 @@ fn generate_value_heatmap(
 
 fn compute_reachability(platform_rects: &[PlatformRect], physics: &PhysicsConfig) -> HeatmapGrid {
     let mut grid = HeatmapGrid::new();
-    let mut counts = vec![0u32; (GRID_WIDTH * GRID_HEIGHT) as usize];
+    let mut counts = vec![0u32; (grid_width() * grid_height()) as usize];
     let mut rng = rand::thread_rng();
 
-    let start_y = ARENA_FLOOR_Y + PLAYER_SIZE.y / 2.0;
+    let start_y = arena().floor_y + PLAYER_SIZE.y / 2.0;
     let per_start = REACHABILITY_SAMPLES_PER_START as f32;
 
-    for cx in 0..GRID_WIDTH {
+    for cx in 0..grid_width() {
         let (start_x, _) = cell_world_coords(cx, 0);
         for _ in 0..REACHABILITY_SAMPLES_PER_START {
-            let mut visited = vec![false; (GRID_WIDTH * GRID_HEIGHT) as usize];
+            let mut visited = vec![false; (grid_width() * grid_height()) as usize];
             simulate_jump(
                 start_x,
                 start_y,
@@ -1141,8 +1167,8 @@ fn compute_reachability(platform_rects: &[PlatformRect], physics: &PhysicsConfig
         }
     }
 
-    for cy in 0..GRID_HEIGHT {
-        for cx in 0..GRID_WIDTH {
+    for cy in 0..grid_height() {
+        for cx in 0..grid_width() {
             let idx = HeatmapGrid::index(cx, cy);
             let value = (counts[idx] as f32 / per_start).clamp(0.0, 1.0);
             grid.set(cx, cy, value);
@@ -1156,13 +1182,13 @@ fn compute_landing_safety(platform_rects: &[PlatformRect]) -> HeatmapGrid {
     let mut grid = HeatmapGrid::new();
     let mut surfaces = platform_rects.to_vec();
 
-    let floor_left = -ARENA_WIDTH / 2.0 + WALL_THICKNESS;
-    let floor_right = ARENA_WIDTH / 2.0 - WALL_THICKNESS;
+    let floor_left = -arena().width / 2.0 + WALL_THICKNESS;
+    let floor_right = arena().width / 2.0 - WALL_THICKNESS;
     let floor_rect = PlatformRect {
         left: floor_left,
         right: floor_right,
-        top: ARENA_FLOOR_Y,
-        bottom: ARENA_FLOOR_Y - 1.0,
+        top: arena().floor_y,
+        bottom: arena().floor_y - 1.0,
     };
     surfaces.push(floor_rect);
 
@@ -1170,8 +1196,8 @@ fn compute_landing_safety(platform_rects: &[PlatformRect]) -> HeatmapGrid {
     let min_margin = PLAYER_SIZE.x;
     let max_margin = PLAYER_SIZE.x * 3.0;
 
-    for cy in 0..GRID_HEIGHT {
-        for cx in 0..GRID_WIDTH {
+    for cy in 0..grid_height() {
+        for cx in 0..grid_width() {
             let (world_x, world_y) = cell_world_coords(cx, cy);
             let mut best: f32 = 0.0;
 
@@ -1198,22 +1224,57 @@ fn compute_landing_safety(platform_rects: &[PlatformRect]) -> HeatmapGrid {
     grid
 }
 
-fn compute_path_cost(reachability: &HeatmapGrid) -> HeatmapGrid {
+/// Cell-to-cell shortcuts from portals and bounce pads (one BFS step each)
+fn build_feature_links(level: &ballgame::LevelData, physics: &PhysicsConfig) -> Vec<(Vec2, Vec2)> {
+    let mut links = Vec::new();
+    let floor_y = arena().floor_y;
+
+    for platform in &level.platforms {
+        match platform {
+            ballgame::PlatformDef::Portal { x1, y1, x2, y2 } => {
+                let (a, b) = portal_ends(*x1, *y1, *x2, *y2, floor_y);
+                links.push((a, b));
+                links.push((b, a));
+            }
+            ballgame::PlatformDef::BouncePad { x, y, launch, .. } => {
+                let apex = launch * launch / (2.0 * physics.gravity_rise);
+                for pad_x in bounce_pad_xs(*x) {
+                    // Player center while standing on the pad, then every cell up the launch column
+                    let start = Vec2::new(pad_x, floor_y + y + PLAYER_SIZE.y / 2.0);
+                    let mut rise = CELL_SIZE as f32;
+                    while rise <= apex {
+                        links.push((start, start + Vec2::new(0.0, rise)));
+                        rise += CELL_SIZE as f32;
+                    }
+                }
+            }
+            ballgame::PlatformDef::Mirror { .. } | ballgame::PlatformDef::Center { .. } => {}
+        }
+    }
+
+    links
+}
+
+fn compute_path_cost(reachability: &HeatmapGrid, links: &[(Vec2, Vec2)]) -> HeatmapGrid {
     let mut grid = HeatmapGrid::new();
-    let mut dist = vec![u32::MAX; (GRID_WIDTH * GRID_HEIGHT) as usize];
+    let mut dist = vec![u32::MAX; (grid_width() * grid_height()) as usize];
     let mut queue = VecDeque::new();
 
-    for cx in 0..GRID_WIDTH {
-        let (_, world_y) = cell_world_coords(cx, GRID_HEIGHT - 1);
-        let passable = reachability.get(cx, GRID_HEIGHT - 1) >= REACHABILITY_PASSABLE_THRESHOLD;
-        if passable && world_y <= ARENA_FLOOR_Y + PLAYER_SIZE.y {
-            let idx = HeatmapGrid::index(cx, GRID_HEIGHT - 1);
+    for cx in 0..grid_width() {
+        let (_, world_y) = cell_world_coords(cx, grid_height() - 1);
+        let passable = reachability.get(cx, grid_height() - 1) >= REACHABILITY_PASSABLE_THRESHOLD;
+        if passable && world_y <= arena().floor_y + PLAYER_SIZE.y {
+            let idx = HeatmapGrid::index(cx, grid_height() - 1);
             dist[idx] = 0;
-            queue.push_back((cx, GRID_HEIGHT - 1));
+            queue.push_back((cx, grid_height() - 1));
         }
     }
 
     let neighbors = [(-1i32, 0i32), (1, 0), (0, -1), (0, 1)];
+    let cell_links: Vec<((u32, u32), (u32, u32))> = links
+        .iter()
+        .filter_map(|(from, to)| Some((world_to_cell(from.x, from.y)?, world_to_cell(to.x, to.y)?)))
+        .collect();
 
     while let Some((cx, cy)) = queue.pop_front() {
         let base_idx = HeatmapGrid::index(cx, cy);
@@ -1227,7 +1288,7 @@ fn compute_path_cost(reachability: &HeatmapGrid) -> HeatmapGrid {
             }
             let nx = nx as u32;
             let ny = ny as u32;
-            if nx >= GRID_WIDTH || ny >= GRID_HEIGHT {
+            if nx >= grid_width() || ny >= grid_height() {
                 continue;
             }
             if reachability.get(nx, ny) < REACHABILITY_PASSABLE_THRESHOLD {
@@ -1239,6 +1300,15 @@ fn compute_path_cost(reachability: &HeatmapGrid) -> HeatmapGrid {
                 queue.push_back((nx, ny));
             }
         }
+
+        // Feature links skip the passability check (portal exits/launch columns need no jumps)
+        for (_, to) in cell_links.iter().filter(|(from, _)| *from == (cx, cy)) {
+            let nidx = HeatmapGrid::index(to.0, to.1);
+            if dist[nidx] == u32::MAX {
+                dist[nidx] = base_dist + 1;
+                queue.push_back(*to);
+            }
+        }
     }
 
     let max_dist = dist
@@ -1247,8 +1317,8 @@ fn compute_path_cost(reachability: &HeatmapGrid) -> HeatmapGrid {
         .filter(|d| *d != u32::MAX)
         .max()
         .unwrap_or(0) as f32;
-    for cy in 0..GRID_HEIGHT {
-        for cx in 0..GRID_WIDTH {
+    for cy in 0..grid_height() {
+        for cx in 0..grid_width() {
             let idx = HeatmapGrid::index(cx, cy);
             let value = if dist[idx] == u32::MAX || max_dist <= 0.0 {
                 0.0
@@ -1269,8 +1339,8 @@ fn compute_line_of_sight(
 ) -> HeatmapGrid {
     let mut grid = HeatmapGrid::new();
 
-    for cy in 0..GRID_HEIGHT {
-        for cx in 0..GRID_WIDTH {
+    for cy in 0..grid_height() {
+        for cx in 0..grid_width() {
             let (world_x, world_y) = cell_world_coords(cx, cy);
             let clear = !platform_rects
                 .iter()
@@ -1285,8 +1355,8 @@ fn compute_line_of_sight(
 fn compute_elevation(basket_y: f32) -> HeatmapGrid {
     let mut grid = HeatmapGrid::new();
 
-    for cy in 0..GRID_HEIGHT {
-        for cx in 0..GRID_WIDTH {
+    for cy in 0..grid_height() {
+        for cx in 0..grid_width() {
             let (_, world_y) = cell_world_coords(cx, cy);
             let dy = world_y - basket_y;
             let value = ((dy / ELEVATION_RANGE) + 1.0).clamp(0.0, 2.0) / 2.0;
@@ -1310,8 +1380,8 @@ fn compute_escape_routes(reachability: &HeatmapGrid) -> HeatmapGrid {
         (1, 1),
     ];
 
-    for cy in 0..GRID_HEIGHT {
-        for cx in 0..GRID_WIDTH {
+    for cy in 0..grid_height() {
+        for cx in 0..grid_width() {
             if reachability.get(cx, cy) < REACHABILITY_PASSABLE_THRESHOLD {
                 continue;
             }
@@ -1324,7 +1394,7 @@ fn compute_escape_routes(reachability: &HeatmapGrid) -> HeatmapGrid {
                 }
                 let nx = nx as u32;
                 let ny = ny as u32;
-                if nx >= GRID_WIDTH || ny >= GRID_HEIGHT {
+                if nx >= grid_width() || ny >= grid_height() {
                     continue;
                 }
                 if reachability.get(nx, ny) >= REACHABILITY_PASSABLE_THRESHOLD {
@@ -1369,12 +1439,12 @@ fn simulate_jump(
             next_input += REACHABILITY_INPUT_INTERVAL;
         }
 
-        let accel = if y - half_h <= ARENA_FLOOR_Y + 0.5 {
+        let accel = if y - half_h <= arena().floor_y + 0.5 {
             physics.ground_accel
         } else {
             physics.air_accel
         };
-        let decel = if y - half_h <= ARENA_FLOOR_Y + 0.5 {
+        let decel = if y - half_h <= arena().floor_y + 0.5 {
             physics.ground_decel
         } else {
             physics.air_decel
@@ -1398,8 +1468,8 @@ fn simulate_jump(
         x += vx * REACHABILITY_DT;
         y += vy * REACHABILITY_DT;
 
-        let min_x = -ARENA_WIDTH / 2.0 + WALL_THICKNESS + half_w;
-        let max_x = ARENA_WIDTH / 2.0 - WALL_THICKNESS - half_w;
+        let min_x = -arena().width / 2.0 + WALL_THICKNESS + half_w;
+        let max_x = arena().width / 2.0 - WALL_THICKNESS - half_w;
         if x < min_x {
             x = min_x;
             vx = 0.0;
@@ -1409,8 +1479,8 @@ fn simulate_jump(
         }
 
         let mut grounded = false;
-        if y - half_h <= ARENA_FLOOR_Y {
-            y = ARENA_FLOOR_Y + half_h;
+        if y - half_h <= arena().floor_y {
+            y = arena().floor_y + half_h;
             vy = 0.0;
             grounded = true;
         } else if vy <= 0.0 {
@@ -1448,14 +1518,14 @@ fn approach(current: f32, target: f32, max_delta: f32) -> f32 {
 }
 
 fn world_to_cell(x: f32, y: f32) -> Option<(u32, u32)> {
-    let cx = ((x + ARENA_WIDTH / 2.0) / CELL_SIZE as f32).floor() as i32;
-    let cy = ((ARENA_HEIGHT / 2.0 - y) / CELL_SIZE as f32).floor() as i32;
+    let cx = ((x + arena().width / 2.0) / CELL_SIZE as f32).floor() as i32;
+    let cy = ((arena().ceiling_y() - y) / CELL_SIZE as f32).floor() as i32;
     if cx < 0 || cy < 0 {
         return None;
     }
     let cx = cx as u32;
     let cy = cy as u32;
-    if cx >= GRID_WIDTH || cy >= GRID_HEIGHT {
+    if cx >= grid_width() || cy >= grid_height() {
         return None;
     }
     Some((cx, cy))
@@ -1504,69 +1574,24 @@ fn segments_intersect(
 fn compute_level_hashes(level_db: &LevelDatabase) -> HashMap<String, String> {
     let mut hashes = HashMap::new();
     for level in level_db.all() {
-        hashes.insert(level.id.clone(), hash_level(level));
+        hashes.insert(level.id.clone(), level.content_hash());
     }
     hashes
 }
 
-fn hash_level(level: &ballgame::LevelData) -> String {
-    use std::collections::hash_map::DefaultHasher;
-    let mut hasher = DefaultHasher::new();
-
-    level.id.hash(&mut hasher);
-    level.name.hash(&mut hasher);
-    hash_f32(&mut hasher, level.basket_height);
-    hash_f32(&mut hasher, level.basket_push_in);
-    level.step_count.hash(&mut hasher);
-    hash_f32(&mut hasher, level.corner_height);
-    hash_f32(&mut hasher, level.corner_width);
-    hash_f32(&mut hasher, level.step_push_in);
-    level.debug.hash(&mut hasher);
-    level.regression.hash(&mut hasher);
-
-    for platform in &level.platforms {
-        match platform {
-            ballgame::PlatformDef::Mirror { x, y, width } => {
-                "mirror".hash(&mut hasher);
-                hash_f32(&mut hasher, *x);
-                hash_f32(&mut hasher, *y);
-                hash_f32(&mut hasher, *width);
-            }
-            ballgame::PlatformDef::Center { y, width } => {
-                "center".hash(&mut hasher);
-                hash_f32(&mut hasher, *y);
-                hash_f32(&mut hasher, *width);
-            }
-        }
-    }
-
-    format!("{:016x}", hasher.finish())
-}
-
-fn hash_f32<H: Hasher>(hasher: &mut H, value: f32) {
-    value.to_bits().hash(hasher);
-}
-
-fn load_level_hash_cache() -> LevelHashCache {
-    match fs::read_to_string(LEVEL_HASH_FILE) {
-        Ok(contents) => serde_json::from_str(&contents).unwrap_or_default(),
-        Err(_) => LevelHashCache::default(),
-    }
-}
-
 fn save_level_hashes(current: &HashMap<String, String>) {
     let cache = LevelHashCache {
         levels: current.clone(),
     };
     if let Ok(json) = serde_json::to_string_pretty(&cache) {
-        if let Err(err) = fs::write(LEVEL_HASH_FILE, json) {
+        if let Err(err) = fs::write(config_dir::resolve_for_write(LEVEL_HASH_FILE), json) {
             println!("Failed to write level hash cache: {}", err);
         }
     }
 }
 
 fn compare_level_hashes(current: &HashMap<String, String>) -> LevelChangeSet {
-    let cache = load_level_hash_cache();
+    let cache = LevelHashCache::load();
     let mut changes = LevelChangeSet::default();
 
     for (id, hash) in current {
@@ -1647,7 +1672,7 @@ fn clear_heatmap_outputs() {
         }
     }
 
-    let _ = fs::remove_file(LEVEL_HASH_FILE);
+    let _ = fs::remove_file(config_dir::resolve_for_write(LEVEL_HASH_FILE));
     let _ = fs::remove_file(HEATMAP_STATS_FILE);
     println!("Cleared prior heatmap outputs and hash cache.");
 }
@@ -1746,8 +1771,8 @@ fn draw_line(img: &mut RgbImage, x0: i32, y0: i32, x1: i32, y1: i32, color: Rgb<
 /// Draw a marker for the basket position
 fn draw_basket_marker(img: &mut RgbImage, basket_x: f32, basket_y: f32) {
     // Convert world coords to image coords
-    let img_x = ((basket_x + ARENA_WIDTH / 2.0) / CELL_SIZE as f32 * CELL_SIZE as f32) as u32;
-    let img_y = ((ARENA_HEIGHT / 2.0 - basket_y) / CELL_SIZE as f32 * CELL_SIZE as f32) as u32;
+    let img_x = ((basket_x + arena().width / 2.0) / CELL_SIZE as f32 * CELL_SIZE as f32) as u32;
+    let img_y = ((arena().ceiling_y() - basket_y) / CELL_SIZE as f32 * CELL_SIZE as f32) as u32;
 
     let basket_color = Rgb([200, 50, 50]); // Red
 
@@ -1769,8 +1794,8 @@ fn draw_basket_marker(img: &mut RgbImage, basket_x: f32, basket_y: f32) {
 
 /// Draw the floor line
 fn draw_floor_line(img: &mut RgbImage) {
-    // Convert ARENA_FLOOR_Y to image Y
-    let floor_img_y = ((ARENA_HEIGHT / 2.0 - ARENA_FLOOR_Y) / 1.0) as u32;
+    // Convert floor Y to image Y
+    let floor_img_y = ((arena().ceiling_y() - arena().floor_y) / 1.0) as u32;
 
     if floor_img_y < img.height() {
         let floor_color = Rgb([50, 50, 50]); // Dark gray
@@ -1783,8 +1808,8 @@ fn draw_floor_line(img: &mut RgbImage) {
 fn cell_world_coords(cx: u32, cy: u32) -> (f32, f32) {
     // Convert cell to world coordinates (center of cell)
     // Image Y is top-down, world Y is bottom-up
-    let world_x = (cx as f32 + 0.5) * CELL_SIZE as f32 - ARENA_WIDTH / 2.0;
-    let world_y = ARENA_HEIGHT / 2.0 - (cy as f32 + 0.5) * CELL_SIZE as f32;
+    let world_x = (cx as f32 + 0.5) * CELL_SIZE as f32 - arena().width / 2.0;
+    let world_y = arena().ceiling_y() - (cy as f32 + 0.5) * CELL_SIZE as f32;
     (world_x, world_y)
 }
 
@@ -1802,11 +1827,14 @@ fn generate_speed_heatmap(
 
     println!(
         "Generating speed heatmap for {} ({}): {}x{} cells",
-        level_name, level_id, GRID_WIDTH, GRID_HEIGHT
+        level_name,
+        level_id,
+        grid_width(),
+        grid_height()
     );
 
-    let img_width = GRID_WIDTH * CELL_SIZE;
-    let img_height = GRID_HEIGHT * CELL_SIZE;
+    let img_width = grid_width() * CELL_SIZE;
+    let img_height = grid_height() * CELL_SIZE;
     let mut img = RgbImage::new(img_width, img_height);
 
     let bg_color = Rgb([230, 230, 230]);
@@ -1814,12 +1842,12 @@ fn generate_speed_heatmap(
         *pixel = bg_color;
     }
 
-    let total_cells = GRID_WIDTH * GRID_HEIGHT;
+    let total_cells = grid_width() * grid_height();
     let mut processed = 0;
     let mut data = String::from("x,y,value\n");
 
-    for cy in 0..GRID_HEIGHT {
-        for cx in 0..GRID_WIDTH {
+    for cy in 0..grid_height() {
+        for cx in 0..grid_width() {
             let (world_x, world_y) = cell_world_coords(cx, cy);
 
             if let Some(traj) = calculate_shot_trajectory(
@@ -1829,6 +1857,7 @@ fn generate_speed_heatmap(
                 basket_y,
                 BALL_GRAVITY,
                 SHOT_DISTANCE_VARIANCE,
+                None,
             ) {
                 let speed = traj.required_speed.clamp(SPEED_MIN, SPEED_MAX);
                 let t = (speed - SPEED_MIN) / (SPEED_MAX - SPEED_MIN);
@@ -1882,15 +1911,20 @@ fn generate_score_heatmap(
 
     println!(
         "Generating score heatmap for {} ({}, {}): {}x{} cells, {} trials per cell",
-        level_name, level_id, side, GRID_WIDTH, GRID_HEIGHT, trial_count
+        level_name,
+        level_id,
+        side,
+        grid_width(),
+        grid_height(),
+        trial_count
     );
 
     // Pre-compute rim geometry once (instead of 360K times)
     let rims = build_rim_geometry(basket_x, basket_y);
 
     // Create image (multiply by cell size for actual pixels)
-    let img_width = GRID_WIDTH * CELL_SIZE;
-    let img_height = GRID_HEIGHT * CELL_SIZE;
+    let img_width = grid_width() * CELL_SIZE;
+    let img_height = grid_height() * CELL_SIZE;
     let mut img = RgbImage::new(img_width, img_height);
 
     // Background color (light gray, like the game)
@@ -1899,11 +1933,11 @@ fn generate_score_heatmap(
         *pixel = bg_color;
     }
 
-    let total_cells = GRID_WIDTH * GRID_HEIGHT;
+    let total_cells = grid_width() * grid_height();
 
     // Build list of all cells for parallel processing
-    let cells: Vec<(u32, u32)> = (0..GRID_HEIGHT)
-        .flat_map(|cy| (0..GRID_WIDTH).map(move |cx| (cx, cy)))
+    let cells: Vec<(u32, u32)> = (0..grid_height())
+        .flat_map(|cy| (0..grid_width()).map(move |cx| (cx, cy)))
         .collect();
 
     // Parallel Monte Carlo simulation for all cells
@@ -1934,8 +1968,8 @@ fn generate_score_heatmap(
 
     let (p_low, p_high) =
         percentile_bounds(&mut values, SCORE_PERCENTILE_LOW, SCORE_PERCENTILE_HIGH);
-    for cy in 0..GRID_HEIGHT {
-        for cx in 0..GRID_WIDTH {
+    for cy in 0..grid_height() {
+        for cx in 0..grid_width() {
             let score_pct = grid.get(cx, cy);
             let color = if score_pct < SCORE_MASK_THRESHOLD {
                 Rgb([80, 80, 80])
@@ -2208,10 +2242,10 @@ fn overlay_path(label: &str, level_name: &str, level_id: &str, side: Option<&str
 fn draw_platform_overlays(img: &mut RgbImage, rects: &[PlatformRect]) {
     let color = Rgb([240, 240, 240]);
     for rect in rects {
-        let left = (rect.left + ARENA_WIDTH / 2.0).round() as i32;
-        let right = (rect.right + ARENA_WIDTH / 2.0).round() as i32;
-        let top = (ARENA_HEIGHT / 2.0 - rect.top).round() as i32;
-        let bottom = (ARENA_HEIGHT / 2.0 - rect.bottom).round() as i32;
+        let left = (rect.left + arena().width / 2.0).round() as i32;
+        let right = (rect.right + arena().width / 2.0).round() as i32;
+        let top = (arena().ceiling_y() - rect.top).round() as i32;
+        let bottom = (arena().ceiling_y() - rect.bottom).round() as i32;
 
         draw_rect_outline(img, left, top, right, bottom, color);
     }
@@ -2241,8 +2275,8 @@ fn draw_rect_outline(
 }
 
 fn draw_basket_marker_color(img: &mut RgbImage, basket_x: f32, basket_y: f32, color: Rgb<u8>) {
-    let img_x = ((basket_x + ARENA_WIDTH / 2.0) / CELL_SIZE as f32 * CELL_SIZE as f32) as u32;
-    let img_y = ((ARENA_HEIGHT / 2.0 - basket_y) / CELL_SIZE as f32 * CELL_SIZE as f32) as u32;
+    let img_x = ((basket_x + arena().width / 2.0) / CELL_SIZE as f32 * CELL_SIZE as f32) as u32;
+    let img_y = ((arena().ceiling_y() - basket_y) / CELL_SIZE as f32 * CELL_SIZE as f32) as u32;
 
     let size = CELL_SIZE as i32;
     for i in -size..=size {
@@ -21,9 +21,11 @@ use ballgame::ball::{
     Ball, BallState, CurrentPalette, apply_velocity, ball_collisions, ball_follow_holder,
     ball_gravity, ball_player_collision, ball_spin, ball_state_update, pickup_ball,
 };
+use ballgame::config::GameplayConfig;
 use ballgame::constants::*;
 use ballgame::debug_logging::DebugLogConfig;
-use ballgame::levels::LevelDatabase;
+use ballgame::events::EventBus;
+use ballgame::levels::{LevelDatabase, apply_wind_forces, level_feature_traversal, move_baskets};
 use ballgame::palettes::PaletteDatabase;
 use ballgame::player::{HoldingBall, Player, Team, apply_gravity, apply_input, check_collisions};
 use ballgame::scoring::{CurrentLevel, Score, check_scoring};
@@ -85,7 +87,9 @@ fn run_ghost_trial(
     app.init_resource::<NavGraph>();
     app.init_resource::<PhysicsTweaks>();
     let _ = tuning::apply_global_tuning(&mut app.world_mut().resource_mut::<PhysicsTweaks>());
+    app.add_plugins(GameplayConfig::load("ghost"));
     app.init_resource::<LastShotInfo>();
+    app.insert_resource(EventBus::new());
     app.insert_resource(CurrentPalette(0));
     app.init_resource::<PaletteDatabase>();
 
@@ -155,9 +159,12 @@ fn run_ghost_trial(
             apply_gravity,
             ball_gravity,
             ball_spin,
+            apply_wind_forces,
+            move_baskets,
             apply_velocity,
             check_collisions,
             ball_collisions,
+            level_feature_traversal,
             ball_state_update,
             ball_player_collision,
             ball_follow_holder,
@@ -8,9 +8,11 @@
 //!   cargo run --bin simulate -- --tournament 10
 //!   cargo run --bin simulate -- --level-sweep 5 --left Sniper
 
+use ballgame::config_dir;
 use ballgame::simulation::{SimConfig, run_simulation};
 
 fn main() {
+    config_dir::init_from_args(&std::env::args().collect::<Vec<_>>());
     let config = SimConfig::from_args();
     run_simulation(config);
 }
@@ -0,0 +1,292 @@
+//! Smoke Test - boot each major mode headlessly for a few seconds
+//!
+//! A fast integration gate for refactors. Checks that the config files and ball
+//! textures the game loads are on disk, then runs each mode without a window:
+//!   versus     - full match plugins, AI vs AI, overtime and possession arrow on
+//!   training   - left seat with null input (no AI, no device) against an AI
+//!   replay     - playback of the bundled fixture DB (tests/fixtures/smoke_replay.sql)
+//!   simulation - one simulated match through the simulation runner
+//!
+//! Exits nonzero if an asset is missing or any mode panics.
+//!
+//! Usage:
+//!   cargo run --bin smoke
+//!   cargo run --bin smoke -- --secs 5
+//!   cargo run --bin smoke -- --only replay
+
+use std::env;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use bevy::app::ScheduleRunnerPlugin;
+use bevy::prelude::*;
+
+use ballgame::ai::{AI_PROFILES_FILE, AiProfileDatabase, AiState};
+use ballgame::constants::LEVELS_FILE;
+use ballgame::generate::integrity;
+use ballgame::levels::LevelDatabase;
+use ballgame::palettes::PALETTES_FILE;
+use ballgame::player::{Player, Team};
+use ballgame::replay::{ReplayData, ReplayState, replay_playback, replay_setup};
+use ballgame::scoring::{BallResetMode, CurrentLevel};
+use ballgame::simulation::{SimConfig, SimDatabase, run_match};
+use ballgame::{BallgamePlugins, EventLog, MatchRules, MatchStatus, config_dir};
+
+/// Ball style list (style names pick the texture files)
+const BALL_OPTIONS_FILE: &str = "config/ball_options.txt";
+
+/// Replay fixture loaded into an in-memory database
+const REPLAY_FIXTURE: &str = "tests/fixtures/smoke_replay.sql";
+
+/// Modes in run order
+const MODES: [&str; 4] = ["versus", "training", "replay", "simulation"];
+
+/// Advance a headless app by `secs` of game time at 60 Hz (same stepping as the simulation runner)
+fn step(app: &mut App, secs: f32) {
+    let dt = Duration::from_secs_f32(1.0 / 60.0);
+    for _ in 0..(secs * 60.0) as u32 {
+        app.world_mut()
+            .resource_mut::<Time<Virtual>>()
+            .advance_by(dt);
+        app.world_mut().resource_mut::<Time<Real>>().advance_by(dt);
+        app.world_mut().resource_mut::<Time<Fixed>>().advance_by(dt);
+        app.world_mut().run_schedule(Update);
+        app.world_mut().run_schedule(FixedUpdate);
+    }
+}
+
+/// Headless app running the embedded match plugins
+fn match_app() -> App {
+    let mut app = App::new();
+    app.add_plugins(
+        MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(Duration::from_secs_f32(
+            1.0 / 60.0,
+        ))),
+    );
+    app.add_plugins(
+        BallgamePlugins::new()
+            .headless(true)
+            .event_log(EventLog::Discard)
+            .rules(MatchRules {
+                overtime: true,
+                possession_arrow: true,
+                momentum: true,
+                loose_ball: true,
+                rim_camping_violation: true,
+                shot_clock: Some(8.0),
+                out_of_bounds: true,
+                ball_reset: BallResetMode::Inbound,
+                ..default()
+            }),
+    );
+    app.finish();
+    app.cleanup();
+    app.update(); // Startup
+    app
+}
+
+/// Every config file and ball texture the game loads at startup that isn't on disk
+fn missing_assets() -> Vec<String> {
+    let mut missing: Vec<String> = [
+        LEVELS_FILE,
+        AI_PROFILES_FILE,
+        PALETTES_FILE,
+        BALL_OPTIONS_FILE,
+    ]
+    .iter()
+    .map(|path| config_dir::resolve(path))
+    .filter(|path| !Path::new(path).exists())
+    .collect();
+    if !Path::new(REPLAY_FIXTURE).exists() {
+        missing.push(REPLAY_FIXTURE.to_string());
+    }
+
+    // Ball textures: one per style per palette (see setup in main.rs)
+    let styles = fs::read_to_string(config_dir::resolve(BALL_OPTIONS_FILE)).unwrap_or_default();
+    let palettes = fs::read_to_string(config_dir::resolve(PALETTES_FILE)).unwrap_or_default();
+    missing.extend(
+        integrity::expected_ball_textures(&styles, &palettes)
+            .into_iter()
+            .filter(|path| !Path::new(path).exists()),
+    );
+    missing
+}
+
+/// AI vs AI through the same plugins an embedded match uses
+fn smoke_versus(secs: f32) -> Result<String, String> {
+    let mut app = match_app();
+    step(&mut app, secs);
+    let status = app.world().resource::<MatchStatus>();
+    if status.elapsed <= 0.0 {
+        return Err("match clock never advanced".to_string());
+    }
+    Ok(format!("{:.1}s played", status.elapsed))
+}
+
+/// Left seat with null input: no AI and no device writes its InputState
+fn smoke_training(secs: f32) -> Result<String, String> {
+    let mut app = match_app();
+    let world = app.world_mut();
+    let left = world
+        .query_filtered::<(Entity, &Team), With<Player>>()
+        .iter(world)
+        .find(|(_, team)| **team == Team::Left)
+        .map(|(entity, _)| entity)
+        .ok_or("no left player spawned")?;
+    world.entity_mut(left).remove::<AiState>();
+
+    step(&mut app, secs);
+    let status = app.world().resource::<MatchStatus>();
+    Ok(format!(
+        "{:.1}s played with an idle left seat",
+        status.elapsed
+    ))
+}
+
+/// Load the fixture into an in-memory DB and play it back
+fn smoke_replay(secs: f32) -> Result<String, String> {
+    let sql = fs::read_to_string(REPLAY_FIXTURE)
+        .map_err(|e| format!("read {}: {}", REPLAY_FIXTURE, e))?;
+    let db = SimDatabase::open_in_memory().map_err(|e| e.to_string())?;
+    db.conn()
+        .execute_batch(&sql)
+        .map_err(|e| format!("load {}: {}", REPLAY_FIXTURE, e))?;
+    let replay_data: ReplayData = db.load_replay_data(1)?;
+    if replay_data.ticks.is_empty() {
+        return Err("fixture has no ticks".to_string());
+    }
+    let ticks = replay_data.ticks.len();
+
+    let mut app = App::new();
+    app.add_plugins(
+        MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(Duration::from_secs_f32(
+            1.0 / 60.0,
+        ))),
+    );
+    app.add_plugins(bevy::transform::TransformPlugin);
+    app.insert_resource(Time::<Fixed>::from_duration(Duration::from_secs_f32(
+        1.0 / 60.0,
+    )));
+    app.insert_resource(LevelDatabase::load_from_file(LEVELS_FILE));
+    app.insert_resource(CurrentLevel(String::new())); // Set by replay_setup
+    app.insert_resource(replay_data);
+    app.init_resource::<ReplayState>();
+    app.add_systems(Startup, replay_setup);
+    app.add_systems(Update, replay_playback);
+    app.finish();
+    app.cleanup();
+    app.update(); // Startup
+
+    step(&mut app, secs);
+    let state = app.world().resource::<ReplayState>();
+    if state.current_time_ms == 0 {
+        return Err("playback never advanced".to_string());
+    }
+    Ok(format!(
+        "{} ticks, reached {}ms",
+        ticks, state.current_time_ms
+    ))
+}
+
+/// One short match through the simulation runner
+fn smoke_simulation(secs: f32) -> Result<String, String> {
+    let config = SimConfig {
+        duration_limit: secs,
+        quiet: true,
+        ..default()
+    };
+    let level_db = LevelDatabase::load_from_file(LEVELS_FILE);
+    let profile_db = AiProfileDatabase::load_from_file(AI_PROFILES_FILE);
+    let result = run_match(&config, 1, &level_db, &profile_db);
+    if result.duration <= 0.0 {
+        return Err("match ended immediately".to_string());
+    }
+    Ok(format!(
+        "{} {}-{} on {}",
+        result.winner, result.score_left, result.score_right, result.level_name
+    ))
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    config_dir::init_from_args(&args);
+
+    let secs = args
+        .iter()
+        .position(|a| a == "--secs")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|s| s.parse::<f32>().ok())
+        .unwrap_or(3.0);
+    let only = args
+        .iter()
+        .position(|a| a == "--only")
+        .and_then(|i| args.get(i + 1))
+        .cloned();
+    if let Some(mode) = &only
+        && !MODES.contains(&mode.as_str())
+    {
+        eprintln!("Unknown mode '{}' (expected one of {:?})", mode, MODES);
+        std::process::exit(2);
+    }
+
+    println!("Smoke Test");
+    println!("==========\n");
+
+    let mut failed = 0;
+
+    let missing = missing_assets();
+    if missing.is_empty() {
+        println!("  PASS  assets");
+    } else {
+        println!("  FAIL  assets: {} missing", missing.len());
+        for path in missing.iter().take(10) {
+            println!("          {}", path);
+        }
+        if missing.len() > 10 {
+            println!("          ... and {} more", missing.len() - 10);
+        }
+        failed += 1;
+    }
+
+    // Panics are reported per mode instead of aborting the run
+    panic::set_hook(Box::new(|_| {}));
+    for mode in MODES {
+        if only.as_deref().is_some_and(|o| o != mode) {
+            continue;
+        }
+        let start = Instant::now();
+        let outcome = panic::catch_unwind(AssertUnwindSafe(|| match mode {
+            "versus" => smoke_versus(secs),
+            "training" => smoke_training(secs),
+            "replay" => smoke_replay(secs),
+            _ => smoke_simulation(secs),
+        }));
+        let elapsed = start.elapsed().as_secs_f32();
+        match outcome {
+            Ok(Ok(detail)) => println!("  PASS  {} ({}, {:.2}s)", mode, detail, elapsed),
+            Ok(Err(e)) => {
+                println!("  FAIL  {}: {}", mode, e);
+                failed += 1;
+            }
+            Err(payload) => {
+                let message = payload
+                    .downcast_ref::<String>()
+                    .cloned()
+                    .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+                    .unwrap_or_else(|| "unknown panic".to_string());
+                println!("  FAIL  {}: panicked: {}", mode, message);
+                failed += 1;
+            }
+        }
+    }
+    let _ = panic::take_hook();
+
+    println!();
+    if failed > 0 {
+        println!("{} check(s) failed", failed);
+        std::process::exit(1);
+    }
+    println!("All checks passed");
+}
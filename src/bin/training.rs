@@ -7,35 +7,42 @@
 //!   cargo run --bin training
 //!   cargo run --bin training -- --iterations 5 --profile Aggressive
 
+use ballgame::analytics::{UploadConfig, upload_session_summary};
+use ballgame::config::{BallConfig, GameplayConfig, ShotConfig, StealConfig};
+use ballgame::config_dir;
+use ballgame::db_maintenance;
 use ballgame::debug_logging::DebugLogConfig;
 use ballgame::events::{
-    BasketSnapshot, DebugSampleBuffer, EmitterConfig, EventEmitterState, SqliteEventLogger,
-    emit_game_events, flush_debug_samples_to_sqlite, push_debug_samples, snapshot_ball,
-    snapshot_player, tick_frame_from_time,
+    BasketSnapshot, DebugSampleBuffer, EmitterConfig, EventEmitterState, GameClock, SnapshotPool,
+    SqliteEventLogger, WebhookBridge, WebhookConfig, emit_game_events,
+    flush_debug_samples_to_sqlite, intern, push_debug_samples, snapshot_ball, snapshot_player,
+    tick_frame_from_time,
 };
 use ballgame::simulation::SimDatabase;
 use ballgame::training::{
-    LevelSelector, ReachabilityCollector, TrainingMode, TrainingPhase, TrainingProtocol,
-    TrainingSettings, TrainingState, analyze_pursuit_session_from_db, analyze_session_from_db,
+    DrillTelemetry, LevelSelector, ReachabilityCollector, SessionSummary, TrainingMode,
+    TrainingPhase, TrainingProtocol, TrainingSettings, TrainingState,
+    analyze_pursuit_session_from_db, analyze_session_from_db, dummy_contest_shots,
     ensure_session_dir, format_pursuit_analysis_markdown, generate_analysis_request,
-    print_session_summary, write_analysis_files, write_session_summary,
+    move_practice_dummies, print_session_summary, run_ball_feeders, spawn_practice_props,
+    write_analysis_files, write_session_summary,
 };
-use ballgame::ui::spawn_steal_indicators;
+use ballgame::ui::{spawn_charge_feedback_markers, spawn_steal_indicators};
 use ballgame::{
     AiCapabilities, AiGoal, AiNavState, AiProfileDatabase, AiState, Ball, BallPlayerContact,
     BallPulse, BallRolling, BallShotGrace, BallSpin, BallState, BallStyle, BallTextures,
     ChargeGaugeBackground, ChargeGaugeFill, ChargingShot, CoyoteTimer, CurrentLevel,
     CurrentPalette, DebugSettings, EventBuffer, EventBus, Facing, GameConfig, GameEvent, Grounded,
-    HoldingBall, HumanControlTarget, HumanControlled, InputState, JumpState, LastShotInfo,
-    LevelChangeTracker, LevelDatabase, MatchCountdown, NavGraph, PALETTES_FILE, PaletteDatabase,
-    PhysicsTweaks, Player, PlayerId, PlayerInput, Score, SnapshotConfig, StealContest,
-    StealCooldown, StealTracker, StyleTextures, TargetBasket, Team, TweakPanelState, Velocity, ai,
-    ball, constants::*, countdown, emit_level_change_events, helpers::*, input, levels, player,
-    scoring, shooting, spawn_countdown_text, steal, tuning, update_event_bus_time, world,
+    HoldingBall, HumanControlTarget, HumanControlled, InputBindings, InputState, JumpState,
+    LastShotInfo, LevelChangeTracker, LevelDatabase, MatchCountdown, NavGraph, PALETTES_FILE,
+    PaletteDatabase, PhysicsTweaks, Player, PlayerId, PlayerInput, Score, SnapshotConfig,
+    StealContest, StealCooldown, StealTracker, TargetBasket, Team, TeamSize, Teammate,
+    TweakPanelState, Velocity, ai, ball, constants::*, countdown, emit_level_change_events,
+    helpers::*, input, levels, momentum, overtime, player, scoring, shooting, spawn_countdown_text,
+    steal, tuning, update_event_bus_time, world,
 };
 use bevy::{camera::ScalingMode, prelude::*};
 use rand::seq::SliceRandom;
-use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 use world::{Basket, BasketRim, Collider, Platform};
@@ -121,17 +128,8 @@ fn create_sqlite_logger() -> (SqliteEventLogger, String) {
     let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
     let db_path_buf = format!("db/training_{}.db", timestamp);
     let db_path = std::path::Path::new(&db_path_buf);
-    let latest_path = std::path::Path::new("db/training.db");
-    if let Err(e) = std::fs::remove_file(latest_path) {
-        if e.kind() != std::io::ErrorKind::NotFound {
-            warn!("Failed to remove existing training.db symlink: {}", e);
-        }
-    }
-    let link_target = std::env::current_dir()
-        .map(|cwd| cwd.join(&db_path_buf))
-        .unwrap_or_else(|_| db_path.to_path_buf());
-    if let Err(e) = std::os::unix::fs::symlink(&link_target, latest_path) {
-        warn!("Failed to update training.db symlink: {}", e);
+    if let Err(e) = db_maintenance::update_latest_training_db(db_path) {
+        warn!("Failed to record latest training database: {}", e);
     }
     match SqliteEventLogger::new(db_path, "training") {
         Ok(logger) => {
@@ -177,6 +175,7 @@ fn append_offline_db_path(db_path: &str) {
 }
 
 fn main() {
+    config_dir::init_from_args(&std::env::args().collect::<Vec<_>>());
     let settings = TrainingSettings::from_args();
     let allowed_levels = load_allowed_levels(&settings);
 
@@ -221,6 +220,7 @@ fn main() {
     println!("    E or West: Pickup/Steal");
     println!("    F or RB: Throw (hold to charge)");
     println!("    P or Start: Pause/Resume");
+    println!("    I: Input viewer");
     println!("    Escape: Quit training session");
     println!();
     match settings.mode {
@@ -366,10 +366,24 @@ fn main() {
     let debug_config = DebugLogConfig::load_with_args(&args);
     debug_config.apply_env();
 
-    let (sqlite_logger, db_path_buf) = create_sqlite_logger();
-    if settings.offline_levels_file.is_some() {
-        append_offline_db_path(&db_path_buf);
-    }
+    let sqlite_logger = if settings.incognito {
+        println!("Incognito: nothing from this session is written to db/");
+        SqliteEventLogger::disabled()
+    } else {
+        let (sqlite_logger, db_path_buf) = create_sqlite_logger();
+        if settings.offline_levels_file.is_some() {
+            append_offline_db_path(&db_path_buf);
+        }
+        sqlite_logger
+    };
+    let sqlite_logger = sqlite_logger.with_event_categories(debug_config.event_categories.clone());
+    let webhook = WebhookConfig::from_args(&args).map(WebhookBridge::start);
+    // Incognito sessions stay on this machine
+    let upload = UploadConfig::from_args(&args).filter(|_| !settings.incognito);
+    let team_size = TeamSize::from_args(&args);
+    let db_gc = settings.db_gc;
+    let ai_labels = settings.ai_labels;
+    let input_viewer = settings.input_viewer;
 
     App::new()
         .add_plugins(
@@ -390,14 +404,28 @@ fn main() {
         .insert_resource(ClearColor(initial_bg))
         .insert_resource(palette_db)
         .insert_resource(level_db)
+        .insert_resource(scoring::PossessionArrow::new(settings.possession_arrow))
+        .insert_resource(settings.handicap)
+        .insert_resource(settings.teams.clone())
+        .insert_resource(momentum::Momentum::new(settings.momentum))
+        .insert_resource(steal::LooseBall::new(settings.loose_ball))
+        .insert_resource(scoring::RimCamping::new(settings.rim_camping_violation))
+        .insert_resource(scoring::ShotClock::new(settings.shot_clock_secs))
+        .insert_resource(scoring::OutOfBoundsRule::new(settings.out_of_bounds))
+        .insert_resource(scoring::BallReset::new(settings.ball_reset))
+        .insert_resource(ai::MatchClock::new(settings.time_limit_secs))
+        .insert_resource(ai::NavEdgeStats::new(settings.nav_edge_penalty))
+        .init_resource::<ai::TeamBlackboard>()
         .insert_resource(settings)
         .insert_resource(AllowedTrainingLevels(allowed_levels))
         .insert_resource(training_state)
         .init_resource::<PlayerInput>()
+        .init_resource::<InputBindings>()
         .init_resource::<TweakPanelState>()
         .init_resource::<DebugSettings>()
         .init_resource::<StealContest>()
         .init_resource::<StealTracker>()
+        .init_resource::<DrillTelemetry>()
         .init_resource::<Score>()
         .insert_resource(CurrentLevel(String::new())) // Will be set from training state
         .insert_resource(CurrentPalette(0))
@@ -406,17 +434,36 @@ fn main() {
             let _ = tuning::apply_global_tuning(&mut tweaks);
             tweaks
         })
+        .add_plugins(GameplayConfig::load("train"))
         .init_resource::<LastShotInfo>()
+        .insert_resource({
+            let mut assists = ballgame::ui::Assists::from_settings(&ballgame::InitSettings::load());
+            assists.ai_goal_labels |= ai_labels;
+            assists
+        })
         .init_resource::<AiProfileDatabase>()
+        .init_resource::<ballgame::ConfigWatcher>()
         .init_resource::<NavGraph>()
         .init_resource::<AiCapabilities>()
+        .insert_resource(ai::ContestedQuality::load_dir(std::path::Path::new(
+            ai::CONTESTED_QUALITY_DIR,
+        )))
         .init_resource::<ai::HeatmapBundle>()
+        .insert_resource(ballgame::ui::InputViewer::new(input_viewer))
+        .init_resource::<levels::ArenaDims>()
+        .init_resource::<overtime::Overtime>()
         .insert_resource(SnapshotConfig::default())
-        .init_resource::<TrainingEventBuffer>()
+        .insert_resource(TrainingEventBuffer::new(
+            debug_config.tick_interval_ms,
+            webhook,
+            upload,
+        ))
         .init_resource::<MatchCountdown>()
         // Event bus resources
         .insert_resource(EventBus::new())
+        .init_resource::<GameClock>()
         .insert_resource(HumanControlTarget(Some(PlayerId::L))) // Left player is human
+        .insert_resource(team_size)
         .init_resource::<LevelChangeTracker>()
         .insert_resource(debug_config)
         .init_resource::<DebugSampleBuffer>()
@@ -425,7 +472,7 @@ fn main() {
         // Startup systems
         .add_systems(Startup, training_setup)
         // Event bus time update (runs every frame for timestamping)
-        .add_systems(Update, update_event_bus_time)
+        .add_systems(Update, update_event_bus_time.after(sync_match_clock))
         .add_systems(Update, flush_debug_samples_to_sqlite)
         // Input systems chain - paused when game is paused
         .add_systems(
@@ -436,8 +483,12 @@ fn main() {
                 ai::mark_nav_dirty_on_level_change,
                 ai::load_heatmaps_on_level_change,
                 ai::rebuild_nav_graph,
+                ai::sync_nav_edge_penalties,
+                ai::update_team_blackboard,
                 ai::ai_navigation_update,
                 ai::ai_decision_update,
+                ai::ai_mistake_update,
+                ai::ai_policy_update,
             )
                 .chain()
                 .run_if(not_paused),
@@ -448,16 +499,36 @@ fn main() {
         // Note: steal_cooldown_update is only in FixedUpdate (not here) to avoid double-ticking
         // Level change event emission
         .add_systems(Update, emit_level_change_events)
+        // AI profiles hot-reload (edit ai_profiles.txt mid-session)
+        .add_systems(Update, ballgame::config_watcher::check_ai_profile_changes)
+        .add_systems(Update, levels::apply_arena_dims_on_level_change)
+        .add_systems(Update, ballgame::teams::apply_team_colors)
         .add_systems(
             Update,
             (
                 ballgame::ui::animate_pickable_ball,
+                ball::apply_ball_style_physics,
                 ballgame::ui::update_charge_gauge,
+                ballgame::ui::update_charge_feedback,
                 ballgame::ui::update_steal_indicators,
+                ballgame::ui::spawn_ai_goal_labels,
+                ballgame::ui::toggle_ai_goal_labels,
+                ballgame::ui::update_ai_goal_labels,
+                ballgame::ui::toggle_input_viewer,
+                ballgame::ui::update_input_viewer,
+                ballgame::ui::shake_camera_on_steal_denial,
+                levels::animate_wind_particles,
+            ),
+        )
+        // Countdown and overtime banners, hot-streak auras
+        .add_systems(
+            Update,
+            (
+                countdown::update_countdown,
+                overtime::update_overtime_banner,
+                momentum::update_momentum_aura,
             ),
         )
-        // Countdown system
-        .add_systems(Update, countdown::update_countdown)
         // Training-specific systems
         .add_systems(
             Update,
@@ -465,6 +536,9 @@ fn main() {
                 check_advance_level,
                 emit_training_events,
                 training_state_machine,
+                sync_match_clock.after(training_state_machine),
+                // Resolve the deciding shot before the result is recorded
+                update_drill_telemetry.before(training_state_machine),
                 update_training_hud,
                 flush_training_events_to_sqlite,
                 check_escape_quit,
@@ -479,26 +553,66 @@ fn main() {
                 player::apply_gravity,
                 ball::ball_gravity,
                 ball::ball_spin,
+                levels::apply_wind_forces,
+                (overtime::shrink_court, levels::move_baskets).chain(),
                 ball::apply_velocity,
                 player::check_collisions,
-                ball::ball_collisions,
+                (ball::ball_collisions, ball::ball_rails).chain(),
+                levels::level_feature_traversal,
                 ball::ball_state_update,
                 ball::ball_player_collision,
                 ball::ball_follow_holder,
-                ball::pickup_ball,
+                (ball::pickup_ball, shooting::catch_pass).chain(),
                 steal::steal_cooldown_update,
                 shooting::update_shot_charge,
-                shooting::throw_ball,
-                scoring::check_scoring,
+                (shooting::throw_ball, shooting::pass_ball).chain(),
+                (
+                    scoring::check_scoring,
+                    scoring::shot_clock_update,
+                    scoring::check_out_of_bounds,
+                    scoring::reset_possession_arrow,
+                    momentum::update_momentum,
+                    scoring::apply_handicap,
+                    ball::recover_stuck_ball,
+                )
+                    .chain(),
                 give_ball_to_human,
-                collect_training_debug_samples,
-                collect_reachability_positions,
+                (
+                    collect_training_debug_samples,
+                    collect_reachability_positions,
+                ),
             )
                 .chain()
                 .run_if(countdown::not_in_countdown)
                 .run_if(not_paused),
         )
+        // Input viewer samples exactly what apply_input is about to read
+        .add_systems(
+            FixedUpdate,
+            ballgame::ui::record_input_history
+                .before(player::apply_input)
+                .run_if(countdown::not_in_countdown)
+                .run_if(not_paused),
+        )
+        // Practice props (dummies contest shots after the ball moves)
+        .add_systems(
+            FixedUpdate,
+            (move_practice_dummies, dummy_contest_shots, run_ball_feeders)
+                .chain()
+                .after(ball::ball_player_collision)
+                .run_if(countdown::not_in_countdown)
+                .run_if(not_paused),
+        )
         .run();
+
+    // This session's database stays as-is (training.db points at it)
+    if db_gc {
+        let report = db_maintenance::run_gc(&db_maintenance::GcOptions::default());
+        for error in &report.errors {
+            eprintln!("db gc: {}", error);
+        }
+        println!("{}", report.summary());
+    }
 }
 
 /// Run condition: game is not paused
@@ -524,7 +638,7 @@ fn collect_training_debug_samples(
             Option<&AiNavState>,
             Option<&HumanControlled>,
         ),
-        With<Player>,
+        (With<Player>, Without<Teammate>),
     >,
 ) {
     if !debug_config.enabled || training_state.phase != TrainingPhase::Playing {
@@ -563,7 +677,7 @@ fn collect_reachability_positions(
 fn give_ball_to_human(
     mut commands: Commands,
     mut balls: Query<(Entity, &mut Transform, &mut BallState), With<Ball>>,
-    players: Query<(Entity, &Transform, &Team), (With<Player>, Without<Ball>)>,
+    players: Query<(Entity, &Transform, &Team), (With<Player>, Without<Ball>, Without<Teammate>)>,
     training_settings: Res<TrainingSettings>,
 ) {
     if !training_settings.drive_mode {
@@ -599,25 +713,47 @@ pub struct TrainingEventBuffer {
     pub emitter_state: EventEmitterState,
     /// Track elapsed time
     pub elapsed: f32,
+    /// Posts selected events to `--webhook` as they're flushed
+    pub webhook: Option<WebhookBridge>,
+    /// Posts the session summary to `--upload` when the session ends
+    pub upload: Option<UploadConfig>,
 }
 
-impl Default for TrainingEventBuffer {
-    fn default() -> Self {
+impl TrainingEventBuffer {
+    fn new(
+        tick_interval_ms: u32,
+        webhook: Option<WebhookBridge>,
+        upload: Option<UploadConfig>,
+    ) -> Self {
         Self {
             buffer: EventBuffer::default(),
             emitter_state: EventEmitterState::with_config(EmitterConfig {
                 // Training only tracks right player (AI opponent)
                 track_both_ai_goals: false,
+                tick_interval_ms,
             }),
             elapsed: 0.0,
+            webhook,
+            upload,
         }
     }
+
+    /// Start over for a new game (keeps the emitter config)
+    fn reset(&mut self) {
+        self.buffer = EventBuffer::default();
+        self.emitter_state.reset();
+        self.elapsed = 0.0;
+    }
 }
 
 /// HUD text marker
 #[derive(Component)]
 pub struct TrainingHudText;
 
+/// Drill telemetry panel marker
+#[derive(Component)]
+pub struct DrillPanelText;
+
 /// Setup the training game world
 fn training_setup(
     mut commands: Commands,
@@ -630,6 +766,10 @@ fn training_setup(
     mut current_level: ResMut<CurrentLevel>,
     mut event_buffer: ResMut<TrainingEventBuffer>,
     sqlite_logger: Res<SqliteEventLogger>,
+    ball_config: Res<BallConfig>,
+    shot_config: Res<ShotConfig>,
+    steal_config: Res<StealConfig>,
+    team_size: Res<TeamSize>,
 ) {
     // Set current level from training state (convert level number to level ID)
     let level_id = level_db
@@ -680,10 +820,11 @@ fn training_setup(
         .unwrap_or_else(|| profile_db.default_profile().id.clone());
 
     // Left player - HUMAN controlled
+    let left_spawn = level_db.player_spawn(&level_id, true);
     let left_player = commands
         .spawn((
             Sprite::from_color(initial_palette.left, PLAYER_SIZE),
-            Transform::from_translation(PLAYER_SPAWN_LEFT),
+            Transform::from_translation(left_spawn),
             Player,
             Velocity::default(),
             Grounded(false),
@@ -719,7 +860,7 @@ fn training_setup(
     let right_spawn = if training_settings.protocol.is_solo_mode() {
         Vec3::new(ARENA_WIDTH + 500.0, 0.0, 0.0) // Off-screen right
     } else {
-        PLAYER_SPAWN_RIGHT
+        level_db.player_spawn(&level_id, false)
     };
 
     let right_player = commands
@@ -801,27 +942,42 @@ fn training_setup(
         .id();
     commands.entity(right_player).add_child(right_gauge_fill);
 
+    // Post-release charge markers
+    spawn_charge_feedback_markers(&mut commands, left_player, gauge_x);
+    spawn_charge_feedback_markers(&mut commands, right_player, right_gauge_x);
+
     // Steal indicators
     spawn_steal_indicators(&mut commands, left_player, 1.0);
     spawn_steal_indicators(&mut commands, right_player, -1.0);
 
-    // Load ball textures
-    let style_names = load_ball_style_names();
-    let num_palettes = palette_db.len();
-    let mut styles_map = HashMap::new();
-    for style_name in &style_names {
-        let textures = StyleTextures {
-            textures: (0..num_palettes)
-                .map(|i| asset_server.load(format!("textures/balls/ball_{}_{}.png", style_name, i)))
-                .collect(),
-        };
-        styles_map.insert(style_name.clone(), textures);
+    // 2v2: an AI teammate on each side (not in solo drills)
+    if team_size.has_teammates() && !training_settings.protocol.is_solo_mode() {
+        player::spawn_teammate(
+            &mut commands,
+            Team::Left,
+            left_spawn,
+            initial_palette.left,
+            profile_db.default_profile().id.clone(),
+            AiGoal::ChaseBall,
+        );
+        player::spawn_teammate(
+            &mut commands,
+            Team::Right,
+            right_spawn,
+            initial_palette.right,
+            ai_profile_id.clone(),
+            ai_initial_goal,
+        );
     }
 
-    let ball_textures = BallTextures {
-        styles: styles_map,
-        style_order: style_names.clone(),
-    };
+    // Load ball textures
+    let style_names = load_ball_style_names();
+    let style_physics =
+        ball::BallStylePhysics::parse(&fs::read_to_string(BALL_OPTIONS_FILE).unwrap_or_default());
+    commands.insert_resource(style_physics.clone());
+    // Full-size set only: training runs at a fixed window size
+    let ball_textures =
+        BallTextures::load(&asset_server, &style_names, palette_db.len(), Vec::new(), None);
     commands.insert_resource(ball_textures.clone());
 
     // Spawn ball - use settings or random
@@ -837,11 +993,11 @@ fn training_setup(
     if let Some(textures) = ball_textures.get(&ball_style_name) {
         let (ball_spawn_pos, ball_state) = if training_settings.drive_mode {
             (
-                Vec3::new(PLAYER_SPAWN_LEFT.x, PLAYER_SPAWN_LEFT.y, BALL_SPAWN.z),
+                Vec3::new(left_spawn.x, left_spawn.y, BALL_SPAWN.z),
                 BallState::Held(left_player),
             )
         } else {
-            (BALL_SPAWN, BallState::Free)
+            (level_db.ball_spawn(&level_id), BallState::Free)
         };
 
         let ball_entity = commands
@@ -872,27 +1028,12 @@ fn training_setup(
         }
     }
 
-    // Arena floor
-    commands.spawn((
-        Sprite::from_color(
-            initial_palette.platforms,
-            Vec2::new(ARENA_WIDTH - WALL_THICKNESS * 2.0, 40.0),
-        ),
-        Transform::from_xyz(0.0, ARENA_FLOOR_Y, 0.0),
-        Platform,
-    ));
+    // Dummy defenders and ball feeders from the settings file
+    spawn_practice_props(&mut commands, &training_settings.props);
 
-    // Walls
-    commands.spawn((
-        Sprite::from_color(initial_palette.platforms, Vec2::new(WALL_THICKNESS, 5000.0)),
-        Transform::from_xyz(-ARENA_WIDTH / 2.0 + WALL_THICKNESS / 2.0, 2000.0, 0.0),
-        Platform,
-    ));
-    commands.spawn((
-        Sprite::from_color(initial_palette.platforms, Vec2::new(WALL_THICKNESS, 5000.0)),
-        Transform::from_xyz(ARENA_WIDTH / 2.0 - WALL_THICKNESS / 2.0, 2000.0, 0.0),
-        Platform,
-    ));
+    // Arena floor and walls (resized per level by apply_arena_dims_on_level_change)
+    world::spawn_floor(&mut commands, initial_palette.platforms);
+    world::spawn_walls(&mut commands, initial_palette.platforms);
 
     // Level platforms
     levels::spawn_level_platforms(
@@ -904,13 +1045,14 @@ fn training_setup(
 
     // Baskets
     let initial_level = level_db.get_by_id(&level_id);
+    let initial_arena = initial_level.map(|l| l.arena()).unwrap_or_default();
     let basket_y = initial_level
-        .map(|l| ARENA_FLOOR_Y + l.basket_height)
+        .map(|l| l.floor_y + l.basket_height)
         .unwrap_or(ARENA_FLOOR_Y + 400.0);
     let basket_push_in = initial_level
         .map(|l| l.basket_push_in)
         .unwrap_or(BASKET_PUSH_IN);
-    let (left_basket_x, right_basket_x) = basket_x_from_offset(basket_push_in);
+    let (left_basket_x, right_basket_x) = initial_arena.basket_x_from_offset(basket_push_in);
 
     let rim_outer_height = BASKET_SIZE.y * 0.5;
     let rim_inner_height = BASKET_SIZE.y * 0.1;
@@ -1007,6 +1149,7 @@ fn training_setup(
         .unwrap_or(STEP_PUSH_IN);
     levels::spawn_corner_ramps(
         &mut commands,
+        &initial_arena,
         initial_step_count,
         initial_corner_height,
         initial_corner_width,
@@ -1032,8 +1175,23 @@ fn training_setup(
         TrainingHudText,
     ));
 
+    // Drill telemetry panel (steal/shot streaks and success rates)
+    commands.spawn((
+        Text2d::new(""),
+        TextFont {
+            font_size: 14.0,
+            ..default()
+        },
+        TextLayout::new_with_justify(Justify::Center),
+        TextColor(TEXT_SECONDARY),
+        Transform::from_xyz(0.0, ARENA_HEIGHT / 2.0 - 62.0, 1.0),
+        DrillPanelText,
+    ));
+
     // Countdown text (3-2-1 before match starts)
     spawn_countdown_text(&mut commands);
+    overtime::spawn_overtime_banner(&mut commands);
+    ballgame::ui::spawn_input_viewer(&mut commands);
 
     // Initialize event buffer for this game
     let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S").to_string();
@@ -1049,17 +1207,20 @@ fn training_setup(
         seed,
     );
     training_state.current_match_id = match_id;
-    training_state.sqlite_session_id = Some(sqlite_logger.session_id().to_string());
+    if sqlite_logger.is_enabled() {
+        training_state.sqlite_session_id = Some(sqlite_logger.session_id().to_string());
+    }
 
     // Log match start
     event_buffer.buffer.log(
         0.0,
         GameEvent::MatchStart {
             level: training_state.current_level,
-            level_name: training_state.current_level_name.clone(),
-            left_profile: "Player".to_string(),
-            right_profile: training_state.ai_profile.clone(),
+            level_name: intern(&training_state.current_level_name),
+            left_profile: intern("Player"),
+            right_profile: intern(&training_state.ai_profile),
             seed,
+            teams: training_settings.teams.tags(),
         },
     );
 
@@ -1076,28 +1237,47 @@ fn training_setup(
             ball_gravity: BALL_GRAVITY,
             ball_bounce: BALL_BOUNCE,
             ball_air_friction: BALL_AIR_FRICTION,
-            ball_ground_friction: BALL_GROUND_FRICTION,
+            ball_ground_friction: ball_config.ground_friction,
             shot_max_power: SHOT_MAX_POWER,
-            shot_max_speed: SHOT_MAX_SPEED,
+            shot_max_speed: shot_config.max_speed,
             shot_charge_time: SHOT_CHARGE_TIME,
             shot_max_variance: SHOT_MAX_VARIANCE,
             shot_min_variance: SHOT_MIN_VARIANCE,
-            steal_range: STEAL_RANGE,
-            steal_success_chance: STEAL_SUCCESS_CHANCE,
-            steal_cooldown: STEAL_COOLDOWN,
+            steal_range: steal_config.range,
+            steal_success_chance: steal_config.success_chance,
+            steal_cooldown: steal_config.cooldown,
+            steal_fail_cooldown: steal_config.fail_cooldown,
+            steal_victim_cooldown: steal_config.victim_cooldown,
+            steal_charging_bonus: steal_config.charging_bonus,
             preset_movement: None,
             preset_ball: None,
             preset_shooting: None,
             preset_composite: None,
+            preset_steal: None,
+            ball_style: Some(ball_style_name.clone()),
+            ball_physics: Some(style_physics.get(&ball_style_name))
+                .filter(|physics| !physics.is_default()),
         }),
     );
 }
 
+/// Mirror the game clock into `MatchClock` for the AI's late-game risk model
+/// and into `GameClock` for event bus timestamps
+fn sync_match_clock(
+    training_state: Res<TrainingState>,
+    mut clock: ResMut<ai::MatchClock>,
+    mut game_clock: ResMut<GameClock>,
+) {
+    clock.elapsed = training_state.game_elapsed;
+    game_clock.elapsed = training_state.game_elapsed;
+}
+
 /// Training state machine - handles game flow
 fn training_state_machine(
     mut training_state: ResMut<TrainingState>,
     mut score: ResMut<Score>,
     mut steal_tracker: ResMut<StealTracker>,
+    mut drill_telemetry: ResMut<DrillTelemetry>,
     mut event_buffer: ResMut<TrainingEventBuffer>,
     mut countdown: ResMut<MatchCountdown>,
     training_settings: Res<TrainingSettings>,
@@ -1108,6 +1288,7 @@ fn training_state_machine(
     level_db: Res<LevelDatabase>,
     mut current_level: ResMut<CurrentLevel>,
     sqlite_logger: Res<SqliteEventLogger>,
+    mut overtime: ResMut<overtime::Overtime>,
 ) {
     match training_state.phase {
         TrainingPhase::WaitingToStart => {
@@ -1127,7 +1308,10 @@ fn training_state_machine(
         }
 
         TrainingPhase::Playing => {
-            training_state.update_elapsed();
+            // The game clock stands still through the post-goal countdown
+            if !countdown.active {
+                training_state.update_elapsed(time.delta_secs());
+            }
             event_buffer.elapsed = training_state.game_elapsed;
 
             // Reachability: no win condition - player decides when to advance via LB
@@ -1144,7 +1328,26 @@ fn training_state_machine(
                 .map(|limit| training_state.game_elapsed >= limit)
                 .unwrap_or(false);
 
-            if score_reached || time_expired {
+            // Tied at the time limit: play sudden death instead of ending
+            if time_expired
+                && training_settings.overtime
+                && !overtime.active
+                && score.left == score.right
+            {
+                overtime.start(&score);
+                println!(
+                    "Tied {}-{} at {:.1}s - SUDDEN DEATH OVERTIME!",
+                    score.left, score.right, training_state.game_elapsed
+                );
+                return;
+            }
+            let overtime_decided = overtime.is_decided(&score);
+            let time_expired = time_expired && !overtime.active;
+
+            if score_reached || time_expired || overtime_decided {
+                // Walls move back out while the result is shown
+                overtime.end();
+
                 // Log match end
                 event_buffer.buffer.log(
                     training_state.game_elapsed,
@@ -1163,9 +1366,24 @@ fn training_state_machine(
 
                 // Record result
                 training_state.record_result(score.left, score.right, match_id);
+                if let Some(result) = training_state.game_results.last_mut() {
+                    result.drill = Some(drill_telemetry.game_stats());
+                }
+                if overtime_decided && score.left > score.right {
+                    // Sudden-death goal wins regardless of win_score
+                    if let Some(result) = training_state.game_results.last_mut() {
+                        result.winner = ballgame::training::Winner::Human;
+                    }
+                }
 
                 // Determine outcome message
-                let outcome = if time_expired && !score_reached {
+                let outcome = if overtime_decided {
+                    if score.left > score.right {
+                        "You win in overtime!".to_string()
+                    } else {
+                        "AI wins in overtime!".to_string()
+                    }
+                } else if time_expired && !score_reached {
                     format!("Time expired ({:.1}s)", training_state.game_elapsed)
                 } else if score.left >= training_state.win_score {
                     "You win!".to_string()
@@ -1236,16 +1454,17 @@ fn training_state_machine(
 
                     training_state.next_game();
 
-                    // Reset score and steal tracker for new game
+                    // Reset score, steal tracker and drill telemetry for new game
                     score.left = 0;
                     score.right = 0;
                     steal_tracker.reset();
+                    drill_telemetry.reset();
 
                     // Start countdown for new game
                     countdown.start();
 
                     // Reset event buffer for new game
-                    *event_buffer = TrainingEventBuffer::default();
+                    event_buffer.reset();
                     let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S").to_string();
                     event_buffer.buffer.start_session(&timestamp);
 
@@ -1264,10 +1483,11 @@ fn training_state_machine(
                         0.0,
                         GameEvent::MatchStart {
                             level: training_state.current_level,
-                            level_name: training_state.current_level_name.clone(),
-                            left_profile: "Player".to_string(),
-                            right_profile: training_state.ai_profile.clone(),
+                            level_name: intern(&training_state.current_level_name),
+                            left_profile: intern("Player"),
+                            right_profile: intern(&training_state.ai_profile),
                             seed,
+                            teams: training_settings.teams.tags(),
                         },
                     );
 
@@ -1288,9 +1508,7 @@ fn training_state_machine(
 
         TrainingPhase::SessionComplete => {
             // Write summary and exit
-            if let Err(e) = write_session_summary(&training_state) {
-                eprintln!("Failed to write session summary: {}", e);
-            }
+            finish_session_summary(&training_state, &event_buffer);
             print_session_summary(&training_state);
 
             // Run standard analysis (same for all protocols)
@@ -1299,7 +1517,7 @@ fn training_state_machine(
                 .sqlite_session_id
                 .as_deref()
                 .and_then(|session_id| {
-                    SimDatabase::open(std::path::Path::new("db/training.db"))
+                    SimDatabase::open(&db_maintenance::latest_training_db())
                         .ok()
                         .and_then(|db| {
                             analyze_session_from_db(&db, session_id, training_state.protocol)
@@ -1323,7 +1541,7 @@ fn training_state_machine(
                             .sqlite_session_id
                             .as_deref()
                             .and_then(|session_id| {
-                                SimDatabase::open(std::path::Path::new("db/training.db"))
+                                SimDatabase::open(&db_maintenance::latest_training_db())
                                     .ok()
                                     .and_then(|db| analyze_pursuit_session_from_db(&db, session_id))
                             });
@@ -1396,6 +1614,7 @@ fn training_state_machine(
 fn update_training_hud(
     training_state: Res<TrainingState>,
     score: Res<Score>,
+    arrow: Res<scoring::PossessionArrow>,
     mut hud_query: Query<&mut Text2d, With<TrainingHudText>>,
 ) {
     for mut text in &mut hud_query {
@@ -1419,25 +1638,96 @@ fn update_training_hud(
         // Standard training mode HUD
         let phase_indicator = match training_state.phase {
             TrainingPhase::WaitingToStart => " [Pick up the ball to start]",
+            TrainingPhase::Paused if training_state.current_match_id.is_some() => {
+                " [PAUSED - Start: resume, N: don't log this match]"
+            }
             TrainingPhase::Paused => " [PAUSED - Press Start to resume]",
             TrainingPhase::GameEnded => " [Game Over - Press Start to retry]",
             TrainingPhase::SessionComplete => " [Session Complete]",
             _ => "",
         };
+        let logged_indicator = match training_state.phase {
+            TrainingPhase::Playing | TrainingPhase::Paused
+                if training_state.current_match_id.is_none() =>
+            {
+                " | Not logged"
+            }
+            _ => "",
+        };
+        let arrow_indicator = match (arrow.enabled, arrow.next) {
+            (false, _) => "",
+            (true, Team::Left) => " | Arrow: You",
+            (true, Team::Right) => " | Arrow: AI",
+        };
 
         text.0 = format!(
-            "Game {}/{} | {} | You {} - {} {}{}",
+            "Game {}/{} | {} | You {} - {} {}{}{}{}",
             training_state.game_number,
             training_state.games_total,
             training_state.current_level_name,
             score.left,
             score.right,
             training_state.ai_profile,
+            arrow_indicator,
+            logged_indicator,
             phase_indicator
         );
     }
 }
 
+/// Track the human's steal and shot attempts and refresh the drill panel
+fn update_drill_telemetry(
+    training_state: Res<TrainingState>,
+    steal_tracker: Res<StealTracker>,
+    score: Res<Score>,
+    shot_info: Res<LastShotInfo>,
+    mut telemetry: ResMut<DrillTelemetry>,
+    balls: Query<&BallState, With<Ball>>,
+    players: Query<&Team, With<Player>>,
+    mut panel_query: Query<&mut Text2d, With<DrillPanelText>>,
+    mut last_score: Local<(u32, u32)>,
+    mut ball_was_held: Local<bool>,
+) {
+    let ball_state = balls.iter().next();
+    let ball_held = matches!(ball_state, Some(BallState::Held(_)));
+
+    if training_state.phase == TrainingPhase::Playing {
+        telemetry.observe_steals(&steal_tracker);
+
+        // Order matters: a release, then its goal, then the next pickup
+        if shot_info.is_changed()
+            && !shot_info.is_added()
+            && let Some(BallState::InFlight { shooter, .. }) = ball_state
+            && players.get(*shooter).is_ok_and(|team| *team == Team::Left)
+        {
+            telemetry.shot_released();
+        }
+        if score.left > last_score.0 {
+            telemetry.goal_scored(true);
+        }
+        if score.right > last_score.1 {
+            telemetry.goal_scored(false);
+        }
+        if ball_held && !*ball_was_held {
+            telemetry.ball_picked_up();
+        }
+    }
+    *last_score = (score.left, score.right);
+    *ball_was_held = ball_held;
+
+    // Exploration protocols have no drills to report
+    let text = if training_state.protocol.iterates_all_levels() {
+        String::new()
+    } else {
+        telemetry.panel_text()
+    };
+    for mut panel in &mut panel_query {
+        if panel.0 != text {
+            panel.0 = text.clone();
+        }
+    }
+}
+
 /// Emit game events during training
 ///
 /// This is a thin wrapper around the shared `emit_game_events` function.
@@ -1460,11 +1750,12 @@ fn emit_training_events(
             Option<&HoldingBall>,
             &InputState,
         ),
-        With<Player>,
+        (With<Player>, Without<Teammate>),
     >,
     baskets: Query<(&Transform, &Basket)>,
     balls: Query<(&Transform, &Velocity, &BallState), With<Ball>>,
     mut event_bus: ResMut<EventBus>,
+    mut pool: Local<SnapshotPool>,
 ) {
     if training_state.phase != TrainingPhase::Playing {
         return;
@@ -1480,11 +1771,27 @@ fn emit_training_events(
 
     let time = training_state.game_elapsed;
 
-    // Convert query results to snapshots
-    let player_snapshots: Vec<_> = players
-        .iter()
-        .map(
-            |(
+    // Convert query results to snapshots (reusing last frame's vectors)
+    let SnapshotPool {
+        players: ref mut player_snapshots,
+        baskets: ref mut basket_snapshots,
+    } = *pool;
+    player_snapshots.clear();
+    basket_snapshots.clear();
+    player_snapshots.extend(players.iter().map(
+        |(
+            entity,
+            team,
+            transform,
+            velocity,
+            target,
+            charging,
+            ai_state,
+            steal_cooldown,
+            holding,
+            input_state,
+        )| {
+            snapshot_player(
                 entity,
                 team,
                 transform,
@@ -1495,30 +1802,13 @@ fn emit_training_events(
                 steal_cooldown,
                 holding,
                 input_state,
-            )| {
-                snapshot_player(
-                    entity,
-                    team,
-                    transform,
-                    velocity,
-                    target,
-                    charging,
-                    ai_state,
-                    steal_cooldown,
-                    holding,
-                    input_state,
-                )
-            },
-        )
-        .collect();
-
-    let basket_snapshots: Vec<_> = baskets
-        .iter()
-        .map(|(transform, basket)| BasketSnapshot {
-            basket: *basket,
-            position: (transform.translation.x, transform.translation.y),
-        })
-        .collect();
+            )
+        },
+    ));
+    basket_snapshots.extend(baskets.iter().map(|(transform, basket)| BasketSnapshot {
+        basket: *basket,
+        position: (transform.translation.x, transform.translation.y),
+    }));
 
     let ball_snapshot = balls
         .iter()
@@ -1539,8 +1829,8 @@ fn emit_training_events(
         time,
         &score,
         &steal_contest,
-        &player_snapshots,
-        &basket_snapshots,
+        player_snapshots,
+        basket_snapshots,
         ball_snapshot.as_ref(),
         Some(&shot_info),
     );
@@ -1555,6 +1845,9 @@ fn flush_training_events_buffer(
         return;
     }
 
+    if let Some(webhook) = &event_buffer.webhook {
+        webhook.forward(&events);
+    }
     sqlite_logger.log_events(&events);
 }
 
@@ -1767,9 +2060,7 @@ fn check_escape_quit(
 
         // Still write summary with completed games
         if !training_state.game_results.is_empty() {
-            if let Err(e) = write_session_summary(&training_state) {
-                eprintln!("Failed to write session summary: {}", e);
-            }
+            finish_session_summary(&training_state, &event_buffer);
             print_session_summary(&training_state);
         }
 
@@ -1777,6 +2068,16 @@ fn check_escape_quit(
     }
 }
 
+/// Write the session summary, and post it when `--upload` is set
+fn finish_session_summary(training_state: &TrainingState, event_buffer: &TrainingEventBuffer) {
+    if let Err(e) = write_session_summary(training_state) {
+        eprintln!("Failed to write session summary: {}", e);
+    }
+    if let Some(upload) = &event_buffer.upload {
+        upload_session_summary(upload, &SessionSummary::from_state(training_state));
+    }
+}
+
 /// Check for Start button to pause/unpause or restart
 fn check_pause_restart(
     mut commands: Commands,
@@ -1784,14 +2085,15 @@ fn check_pause_restart(
     gamepads: Query<&Gamepad>,
     mut training_state: ResMut<TrainingState>,
     mut score: ResMut<Score>,
-    mut steal_tracker: ResMut<StealTracker>,
+    // Grouped to stay within the system parameter limit
+    (mut steal_tracker, mut drill_telemetry): (ResMut<StealTracker>, ResMut<DrillTelemetry>),
     mut event_buffer: ResMut<TrainingEventBuffer>,
     mut countdown: ResMut<MatchCountdown>,
     level_db: Res<LevelDatabase>,
     settings: Res<TrainingSettings>,
     allowed_levels: Res<AllowedTrainingLevels>,
     mut current_level: ResMut<CurrentLevel>,
-    mut players: Query<(Entity, &mut Transform, &Team), With<Player>>,
+    mut players: Query<(Entity, &mut Transform, &Team, Has<Teammate>), With<Player>>,
     mut balls: Query<
         (Entity, &mut Transform, &mut BallState, &mut Velocity),
         (With<Ball>, Without<Player>),
@@ -1804,6 +2106,17 @@ fn check_pause_restart(
             .iter()
             .any(|gp| gp.just_pressed(GamepadButton::Start));
 
+    // N while paused: don't log this match (drops what's already in SQLite;
+    // in-memory events and the HUD are unaffected)
+    if training_state.phase == TrainingPhase::Paused
+        && keyboard.just_pressed(KeyCode::KeyN)
+        && training_state.current_match_id.is_some()
+    {
+        sqlite_logger.discard_current_match();
+        training_state.current_match_id = None;
+        println!("[NOT LOGGED] This match won't be saved to the database");
+    }
+
     if !start_pressed {
         return;
     }
@@ -1811,7 +2124,11 @@ fn check_pause_restart(
     // Toggle pause during Playing
     if training_state.phase == TrainingPhase::Playing {
         training_state.phase = TrainingPhase::Paused;
-        println!("\n[PAUSED] Press Start to resume");
+        if training_state.current_match_id.is_some() {
+            println!("\n[PAUSED] Press Start to resume, N to not log this match");
+        } else {
+            println!("\n[PAUSED] Press Start to resume");
+        }
         return;
     }
 
@@ -1864,22 +2181,20 @@ fn check_pause_restart(
         }
     }
 
-    // Reset score and steal tracker
+    // Reset score, steal tracker and drill telemetry
     score.left = 0;
     score.right = 0;
     steal_tracker.reset();
+    drill_telemetry.reset();
 
-    // Reset players to spawn positions and find human player (left team)
+    // Reset players to the level's spawn points and find human player (left team)
+    let left_spawn = level_db.player_spawn(&current_level.0, true);
     let mut left_player_entity = None;
-    for (entity, mut player_transform, team) in &mut players {
-        match team {
-            Team::Left => {
-                player_transform.translation = PLAYER_SPAWN_LEFT;
-                left_player_entity = Some(entity);
-            }
-            Team::Right => {
-                player_transform.translation = PLAYER_SPAWN_RIGHT;
-            }
+    for (entity, mut player_transform, team, teammate) in &mut players {
+        let spawn = level_db.player_spawn(&current_level.0, *team == Team::Left);
+        player_transform.translation = player::spawn_for_slot(spawn, *team, teammate);
+        if *team == Team::Left && !teammate {
+            left_player_entity = Some(entity);
         }
         commands.entity(entity).remove::<HoldingBall>();
     }
@@ -1888,8 +2203,8 @@ fn check_pause_restart(
     for (ball_entity, mut ball_transform, mut ball_state, mut velocity) in &mut balls {
         if settings.drive_mode {
             if let Some(left_player) = left_player_entity {
-                ball_transform.translation.x = PLAYER_SPAWN_LEFT.x;
-                ball_transform.translation.y = PLAYER_SPAWN_LEFT.y;
+                ball_transform.translation.x = left_spawn.x;
+                ball_transform.translation.y = left_spawn.y;
                 *ball_state = BallState::Held(left_player);
                 velocity.0 = Vec2::ZERO;
                 commands
@@ -1897,7 +2212,7 @@ fn check_pause_restart(
                     .insert(HoldingBall(ball_entity));
             }
         } else {
-            ball_transform.translation = BALL_SPAWN;
+            ball_transform.translation = level_db.ball_spawn(&current_level.0);
             *ball_state = BallState::Free;
             velocity.0 = Vec2::ZERO;
         }
@@ -1913,7 +2228,7 @@ fn check_pause_restart(
     countdown.start();
 
     // Reset event buffer for new game
-    *event_buffer = TrainingEventBuffer::default();
+    event_buffer.reset();
     let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S").to_string();
     event_buffer.buffer.start_session(&timestamp);
 
@@ -1932,10 +2247,11 @@ fn check_pause_restart(
         0.0,
         GameEvent::MatchStart {
             level: training_state.current_level,
-            level_name: training_state.current_level_name.clone(),
-            left_profile: "Player".to_string(),
-            right_profile: training_state.ai_profile.clone(),
+            level_name: intern(&training_state.current_level_name),
+            left_profile: intern("Player"),
+            right_profile: intern(&training_state.ai_profile),
             seed,
+            teams: settings.teams.tags(),
         },
     );
 
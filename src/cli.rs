@@ -0,0 +1,1124 @@
+//! `ballgame` command line - one binary with a subcommand per tool
+//!
+//! Option types shared across subcommands (level, match, debug) are defined once here.
+//! Each subcommand converts its parsed options back into the flag list its tool already
+//! understands, so the per-tool parsers stay the single source of truth for defaults.
+//! `play` is the default: `ballgame --level 3` still launches the game.
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+use std::process::Command as Process;
+
+use crate::ai::{AI_PROFILES_FILE, AiProfileDatabase, lint_profiles};
+use crate::config_dir;
+use crate::constants::LEVELS_FILE;
+use crate::db_maintenance::{DB_DEBUG_RETENTION_DAYS, DB_DIR, GcOptions, run_gc};
+use crate::generate;
+use crate::levels::LevelDatabase;
+use crate::simulation::parallel::init_parallel;
+use crate::simulation::{SeedBank, SeedBankEntry, SimConfig, SimDatabase, run_simulation};
+use crate::training::{EvolveConfig, append_evolved_profiles, run_evolution};
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "ballgame",
+    about = "Ballgame - 2v2 ball sport game and tooling"
+)]
+pub struct Cli {
+    /// Config directory to use instead of config/ (missing files fall back to config/)
+    #[arg(long, global = true, value_name = "DIR")]
+    pub config_dir: Option<PathBuf>,
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Play the game (default when no subcommand is given)
+    Play(PlayArgs),
+    /// Training mode against AI (runs the `training` binary)
+    Train(TrainArgs),
+    /// Headless AI vs AI simulation
+    Simulate(SimulateArgs),
+    /// Replay a recorded match from the training database
+    Replay(ReplayArgs),
+    /// Generate heatmaps (runs the `heatmap` binary)
+    Heatmap(HeatmapArgs),
+    /// Analysis reports from the simulation/training databases (runs the `analyze` binary)
+    Report(PassthroughArgs),
+    /// Generate assets
+    Generate {
+        #[command(subcommand)]
+        target: GenerateTarget,
+    },
+    /// Curated seeds that reproduce interesting matches (config/seed_bank.json)
+    Seeds {
+        #[command(subcommand)]
+        action: SeedsAction,
+    },
+    /// AI profile tools (config/ai_profiles.txt)
+    Profiles {
+        #[command(subcommand)]
+        action: ProfilesAction,
+    },
+    /// Database housekeeping (db/)
+    Db {
+        #[command(subcommand)]
+        action: DbAction,
+    },
+}
+
+/// Level selection (name, ID, or 1-based number)
+#[derive(Args, Debug, Default)]
+pub struct LevelArgs {
+    #[arg(long)]
+    pub level: Option<String>,
+}
+
+impl LevelArgs {
+    fn append_to(&self, args: &mut Vec<String>) {
+        if let Some(level) = &self.level {
+            args.extend(["--level".to_string(), level.clone()]);
+        }
+    }
+
+    /// Level as a 1-based number, looking names and IDs up in the level file
+    pub fn resolve_number(&self, level_db: &LevelDatabase) -> Option<u32> {
+        let level = self.level.as_deref()?;
+        if let Ok(number) = level.parse::<u32>() {
+            return Some(number);
+        }
+        level_db
+            .all()
+            .iter()
+            .position(|l| l.id == level || l.name.eq_ignore_ascii_case(level))
+            .map(|i| i as u32 + 1)
+    }
+}
+
+/// Options shared by modes that play full matches
+#[derive(Args, Debug, Default)]
+pub struct MatchArgs {
+    /// RNG seed for reproducible matches
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// Sudden-death overtime when tied at the time limit
+    #[arg(long)]
+    pub overtime: bool,
+    /// Award stuck balls and simultaneous pickups by alternating possession arrow
+    #[arg(long)]
+    pub possession_arrow: bool,
+    /// Two goals in a row without conceding give a brief hot-streak buff
+    #[arg(long)]
+    pub momentum: bool,
+    /// Successful steals knock the ball loose instead of taking it
+    #[arg(long)]
+    pub loose_ball: bool,
+    /// Defensive three seconds: camping on your own rim voids its carry-in block
+    #[arg(long)]
+    pub rim_camping_violation: bool,
+    /// Holding the ball SECS without shooting pops it loose (passes don't reset it)
+    #[arg(long, value_name = "SECS")]
+    pub shot_clock: Option<f32>,
+    /// A ball leaving the court is a turnover: the other side throws in
+    #[arg(long)]
+    pub out_of_bounds: bool,
+    /// Where play restarts after a goal: center, inbound or random
+    #[arg(long, value_name = "MODE")]
+    pub ball_reset: Option<String>,
+    /// AI routes around nav edges it keeps failing
+    #[arg(long)]
+    pub nav_edge_penalty: bool,
+}
+
+impl MatchArgs {
+    fn append_to(&self, args: &mut Vec<String>) {
+        if let Some(seed) = self.seed {
+            args.extend(["--seed".to_string(), seed.to_string()]);
+        }
+        if self.overtime {
+            args.push("--overtime".to_string());
+        }
+        if self.possession_arrow {
+            args.push("--possession-arrow".to_string());
+        }
+        if self.momentum {
+            args.push("--momentum".to_string());
+        }
+        if self.loose_ball {
+            args.push("--loose-ball".to_string());
+        }
+        if self.rim_camping_violation {
+            args.push("--rim-camping-violation".to_string());
+        }
+        if let Some(secs) = self.shot_clock {
+            args.extend(["--shot-clock".to_string(), secs.to_string()]);
+        }
+        if self.out_of_bounds {
+            args.push("--out-of-bounds".to_string());
+        }
+        if let Some(mode) = &self.ball_reset {
+            args.extend(["--ball-reset".to_string(), mode.clone()]);
+        }
+        if self.nav_edge_penalty {
+            args.push("--nav-edge-penalty".to_string());
+        }
+    }
+}
+
+/// Per-side handicaps (versus and training): `head=N,shot=X,cooldown=X`
+#[derive(Args, Debug, Default)]
+pub struct HandicapArgs {
+    /// Left side handicap, e.g. head=2,shot=1.5,cooldown=0.8
+    #[arg(long, value_name = "SPEC")]
+    pub handicap_left: Option<String>,
+    /// Right side handicap (same format)
+    #[arg(long, value_name = "SPEC")]
+    pub handicap_right: Option<String>,
+}
+
+impl HandicapArgs {
+    fn append_to(&self, args: &mut Vec<String>) {
+        for (flag, spec) in [
+            ("--handicap-left", &self.handicap_left),
+            ("--handicap-right", &self.handicap_right),
+        ] {
+            if let Some(spec) = spec {
+                args.extend([flag.to_string(), spec.clone()]);
+            }
+        }
+    }
+}
+
+/// Team names and colors (versus and training): `name=Reds,color=#d03030`
+#[derive(Args, Debug, Default)]
+pub struct TeamArgs {
+    /// Left team, e.g. name=Reds,color=#d03030
+    #[arg(long, value_name = "SPEC")]
+    pub team_left: Option<String>,
+    /// Right team (same format)
+    #[arg(long, value_name = "SPEC")]
+    pub team_right: Option<String>,
+}
+
+impl TeamArgs {
+    fn append_to(&self, args: &mut Vec<String>) {
+        for (flag, spec) in [
+            ("--team-left", &self.team_left),
+            ("--team-right", &self.team_right),
+        ] {
+            if let Some(spec) = spec {
+                args.extend([flag.to_string(), spec.clone()]);
+            }
+        }
+    }
+}
+
+/// Debug logging controls shared by all game modes
+#[derive(Args, Debug, Default)]
+pub struct DebugArgs {
+    /// Enable debug sample logging
+    #[arg(long)]
+    pub debug_log: bool,
+    /// Milliseconds between logged tick/input events
+    #[arg(long, value_name = "MS")]
+    pub log_tick_ms: Option<u32>,
+    /// Event categories to persist, comma-separated or "all"
+    #[arg(long, value_name = "LIST")]
+    pub log_categories: Option<String>,
+    /// Record debug samples for both, left, right or none
+    #[arg(long, value_name = "WHO")]
+    pub debug_samples: Option<String>,
+}
+
+impl DebugArgs {
+    fn append_to(&self, args: &mut Vec<String>) {
+        if self.debug_log {
+            args.push("--debug-log".to_string());
+        }
+        if let Some(ms) = self.log_tick_ms {
+            args.extend(["--log-tick-ms".to_string(), ms.to_string()]);
+        }
+        if let Some(categories) = &self.log_categories {
+            args.extend(["--log-categories".to_string(), categories.clone()]);
+        }
+        if let Some(players) = &self.debug_samples {
+            args.extend(["--debug-samples".to_string(), players.clone()]);
+        }
+    }
+}
+
+/// Periodic JSON snapshots for external dashboards (needs the `spectate` feature)
+#[derive(Args, Debug, Default)]
+pub struct SpectateArgs {
+    /// Rewrite this file with the latest game snapshot
+    #[arg(long, value_name = "FILE")]
+    pub spectate_file: Option<PathBuf>,
+    /// Serve the latest game snapshot at http://127.0.0.1:PORT/
+    #[arg(long, value_name = "PORT")]
+    pub spectate_port: Option<u16>,
+    /// Seconds between snapshots
+    #[arg(long, value_name = "SECS")]
+    pub spectate_interval: Option<f32>,
+}
+
+impl SpectateArgs {
+    fn append_to(&self, args: &mut Vec<String>) {
+        if let Some(file) = &self.spectate_file {
+            args.extend(["--spectate-file".to_string(), file.display().to_string()]);
+        }
+        if let Some(port) = self.spectate_port {
+            args.extend(["--spectate-port".to_string(), port.to_string()]);
+        }
+        if let Some(interval) = self.spectate_interval {
+            args.extend(["--spectate-interval".to_string(), interval.to_string()]);
+        }
+    }
+}
+
+/// Tool-specific flags forwarded untouched
+#[derive(Args, Debug, Default)]
+pub struct PassthroughArgs {
+    #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+    pub rest: Vec<String>,
+}
+
+#[derive(Args, Debug, Default)]
+pub struct PlayArgs {
+    #[command(flatten)]
+    pub level: LevelArgs,
+    #[command(flatten)]
+    pub debug: DebugArgs,
+    #[command(flatten)]
+    pub handicap: HandicapArgs,
+    #[command(flatten)]
+    pub teams: TeamArgs,
+    #[command(flatten)]
+    pub spectate: SpectateArgs,
+    /// Window size override
+    #[arg(long, num_args = 2, value_names = ["WIDTH", "HEIGHT"])]
+    pub viewport: Option<Vec<f32>>,
+    /// Palette index
+    #[arg(long)]
+    pub palette: Option<usize>,
+    /// Keep the countdown frozen
+    #[arg(long)]
+    pub freeze_countdown: bool,
+    /// Two humans on one keyboard (WASD vs arrows + numpad)
+    #[arg(long)]
+    pub local_versus: bool,
+    /// Keyboard and each gamepad drive their own player (replaces --local-versus)
+    #[arg(long)]
+    pub split_input: bool,
+    /// Award stuck balls and simultaneous pickups by alternating possession arrow
+    #[arg(long)]
+    pub possession_arrow: bool,
+    /// Two goals in a row without conceding give a brief hot-streak buff
+    #[arg(long)]
+    pub momentum: bool,
+    /// Successful steals knock the ball loose instead of taking it
+    #[arg(long)]
+    pub loose_ball: bool,
+    /// Defensive three seconds: camping on your own rim voids its carry-in block
+    #[arg(long)]
+    pub rim_camping_violation: bool,
+    /// Holding the ball SECS without shooting pops it loose (passes don't reset it)
+    #[arg(long, value_name = "SECS")]
+    pub shot_clock: Option<f32>,
+    /// A ball leaving the court is a turnover: the other side throws in
+    #[arg(long)]
+    pub out_of_bounds: bool,
+    /// Where play restarts after a goal: center, inbound or random
+    #[arg(long, value_name = "MODE")]
+    pub ball_reset: Option<String>,
+    /// AI routes around nav edges it keeps failing
+    #[arg(long)]
+    pub nav_edge_penalty: bool,
+    /// End each match at N points; R / Start plays an instant rematch
+    #[arg(long, value_name = "N")]
+    pub first_to: Option<u32>,
+    /// Best-of-N series of first-to matches, resumed after a quit
+    #[arg(long, value_name = "N")]
+    pub series: Option<u32>,
+    /// Two players per side: each side gets an AI teammate (C passes)
+    #[arg(long = "2v2")]
+    pub two_v_two: bool,
+    /// Slow-motion replay after each goal (jump skips)
+    #[arg(long)]
+    pub instant_replay: bool,
+    /// Draw ability cooldown clusters in the top corners instead of above players
+    #[arg(long)]
+    pub ability_hud_corners: bool,
+    /// Show each AI's current goal and nav step above its head (L toggles)
+    #[arg(long)]
+    pub ai_labels: bool,
+    /// Rebuild missing or stale ball textures and heatmaps at startup without asking
+    #[arg(long)]
+    pub regenerate_assets: bool,
+    /// Skip the startup check of generated assets
+    #[arg(long)]
+    pub skip_asset_check: bool,
+    /// Run the first-run setup wizard again
+    #[arg(long)]
+    pub setup: bool,
+    /// Length of F6 GIF captures in seconds
+    #[arg(long)]
+    pub gif_secs: Option<f32>,
+    /// Capture a startup snapshot and exit (used by scripts/screenshot.sh)
+    #[arg(long)]
+    pub screenshot_and_quit: bool,
+}
+
+impl PlayArgs {
+    /// Flag list understood by the game's own argument handling
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec!["ballgame".to_string()];
+        self.level.append_to(&mut args);
+        self.debug.append_to(&mut args);
+        self.handicap.append_to(&mut args);
+        self.teams.append_to(&mut args);
+        self.spectate.append_to(&mut args);
+        if let Some(viewport) = &self.viewport {
+            args.push("--viewport".to_string());
+            args.extend(viewport.iter().map(|v| v.to_string()));
+        }
+        if let Some(palette) = self.palette {
+            args.extend(["--palette".to_string(), palette.to_string()]);
+        }
+        if let Some(secs) = self.gif_secs {
+            args.extend(["--gif-secs".to_string(), secs.to_string()]);
+        }
+        if let Some(mode) = &self.ball_reset {
+            args.extend(["--ball-reset".to_string(), mode.clone()]);
+        }
+        if let Some(secs) = self.shot_clock {
+            args.extend(["--shot-clock".to_string(), secs.to_string()]);
+        }
+        if let Some(points) = self.first_to {
+            args.extend(["--first-to".to_string(), points.to_string()]);
+        }
+        if let Some(games) = self.series {
+            args.extend(["--series".to_string(), games.to_string()]);
+        }
+        for (set, flag) in [
+            (self.freeze_countdown, "--freeze-countdown"),
+            (self.local_versus, "--local-versus"),
+            (self.split_input, "--split-input"),
+            (self.possession_arrow, "--possession-arrow"),
+            (self.momentum, "--momentum"),
+            (self.loose_ball, "--loose-ball"),
+            (self.rim_camping_violation, "--rim-camping-violation"),
+            (self.out_of_bounds, "--out-of-bounds"),
+            (self.nav_edge_penalty, "--nav-edge-penalty"),
+            (self.two_v_two, "--2v2"),
+            (self.instant_replay, "--instant-replay"),
+            (self.ability_hud_corners, "--ability-hud-corners"),
+            (self.ai_labels, "--ai-labels"),
+            (self.regenerate_assets, "--regenerate-assets"),
+            (self.skip_asset_check, "--skip-asset-check"),
+            (self.setup, "--setup"),
+            (self.screenshot_and_quit, "--screenshot-and-quit"),
+        ] {
+            if set {
+                args.push(flag.to_string());
+            }
+        }
+        args
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct TrainArgs {
+    #[command(flatten)]
+    pub level: LevelArgs,
+    #[command(flatten)]
+    pub matches: MatchArgs,
+    #[command(flatten)]
+    pub debug: DebugArgs,
+    #[command(flatten)]
+    pub handicap: HandicapArgs,
+    #[command(flatten)]
+    pub teams: TeamArgs,
+    /// Other training flags (see `ballgame train -- --help`)
+    #[command(flatten)]
+    pub rest: PassthroughArgs,
+}
+
+impl TrainArgs {
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec!["training".to_string()];
+        self.level.append_to(&mut args);
+        self.matches.append_to(&mut args);
+        self.debug.append_to(&mut args);
+        self.handicap.append_to(&mut args);
+        self.teams.append_to(&mut args);
+        args.extend(self.rest.rest.iter().cloned());
+        args
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct SimulateArgs {
+    #[command(flatten)]
+    pub level: LevelArgs,
+    #[command(flatten)]
+    pub matches: MatchArgs,
+    #[command(flatten)]
+    pub debug: DebugArgs,
+    #[command(flatten)]
+    pub spectate: SpectateArgs,
+    /// Left player AI profile
+    #[arg(long)]
+    pub left: Option<String>,
+    /// Right player AI profile
+    #[arg(long)]
+    pub right: Option<String>,
+    /// SQLite database for results
+    #[arg(long)]
+    pub db: Option<PathBuf>,
+    /// Serve Prometheus metrics at http://127.0.0.1:PORT/metrics
+    #[arg(long, value_name = "PORT")]
+    pub metrics_port: Option<u16>,
+    /// Other simulation flags (see `ballgame simulate -- --help`)
+    #[command(flatten)]
+    pub rest: PassthroughArgs,
+}
+
+impl SimulateArgs {
+    /// Flag list for `SimConfig::from_arg_list`; level names are resolved to numbers
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec!["simulate".to_string()];
+        if self.level.level.is_some() {
+            let level_db = LevelDatabase::load_from_file(LEVELS_FILE);
+            match self.level.resolve_number(&level_db) {
+                Some(number) => args.extend(["--level".to_string(), number.to_string()]),
+                None => eprintln!(
+                    "Warning: unknown level '{}', using defaults",
+                    self.level.level.as_deref().unwrap_or_default()
+                ),
+            }
+        }
+        self.matches.append_to(&mut args);
+        self.debug.append_to(&mut args);
+        self.spectate.append_to(&mut args);
+        for (flag, value) in [("--left", &self.left), ("--right", &self.right)] {
+            if let Some(value) = value {
+                args.extend([flag.to_string(), value.clone()]);
+            }
+        }
+        if let Some(db) = &self.db {
+            args.extend(["--db".to_string(), db.display().to_string()]);
+        }
+        if let Some(port) = self.metrics_port {
+            args.extend(["--metrics-port".to_string(), port.to_string()]);
+        }
+        args.extend(self.rest.rest.iter().cloned());
+        args
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct ReplayArgs {
+    /// Match ID in the training database
+    #[arg(required_unless_present_any = ["file", "session"])]
+    pub match_id: Option<i64>,
+    /// Play every match of a session back-to-back (session ID or "latest")
+    #[arg(long, conflicts_with_all = ["match_id", "file"])]
+    pub session: Option<String>,
+    /// Only queue some of the session's matches (underdog = upsets only)
+    #[arg(long, requires = "session")]
+    pub filter: Option<String>,
+    /// Clip file saved with F5 (instead of a match ID)
+    #[arg(long, conflicts_with = "match_id")]
+    pub file: Option<PathBuf>,
+    /// Database holding the match (default: the latest training database)
+    #[arg(long, conflicts_with = "file")]
+    pub db: Option<PathBuf>,
+    /// Exit this many seconds after playback ends
+    #[arg(long)]
+    pub timeout: Option<f32>,
+    /// Emphasize one player: dim the other, show this one's trail and markers (left/right)
+    #[arg(long)]
+    pub focus: Option<String>,
+    /// Only show timeline markers of one type (goal/steal/pickup/ai_goal)
+    #[arg(long)]
+    pub markers: Option<String>,
+}
+
+impl ReplayArgs {
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec!["ballgame".to_string()];
+        if let Some(match_id) = self.match_id {
+            args.extend(["--replay-db".to_string(), match_id.to_string()]);
+        }
+        if let Some(session) = &self.session {
+            args.extend(["--replay-session".to_string(), session.clone()]);
+        }
+        if let Some(filter) = &self.filter {
+            args.extend(["--replay-filter".to_string(), filter.clone()]);
+        }
+        if let Some(db) = &self.db {
+            args.extend(["--replay-db-path".to_string(), db.display().to_string()]);
+        }
+        if let Some(file) = &self.file {
+            args.extend(["--replay-file".to_string(), file.display().to_string()]);
+        }
+        if let Some(timeout) = self.timeout {
+            args.extend(["--replay-timeout".to_string(), timeout.to_string()]);
+        }
+        if let Some(focus) = &self.focus {
+            args.extend(["--replay-focus".to_string(), focus.clone()]);
+        }
+        if let Some(markers) = &self.markers {
+            args.extend(["--replay-markers".to_string(), markers.clone()]);
+        }
+        args
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct HeatmapArgs {
+    #[command(flatten)]
+    pub level: LevelArgs,
+    /// Other heatmap flags and kinds (e.g. `score`, `--full --check`)
+    #[command(flatten)]
+    pub rest: PassthroughArgs,
+}
+
+impl HeatmapArgs {
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec!["heatmap".to_string()];
+        self.level.append_to(&mut args);
+        args.extend(self.rest.rest.iter().cloned());
+        args
+    }
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum GenerateTarget {
+    /// Ball textures for all styles x palettes
+    Ball,
+    /// Ball styles showcase image
+    Showcase,
+    /// Level showcase grid (requires level_screenshots/)
+    Levels,
+    /// Ball rotation GIF
+    Gif {
+        #[arg(value_enum)]
+        kind: GifKind,
+    },
+    /// Every output in the manifest, skipping ones whose inputs haven't changed
+    All {
+        /// Rebuild everything regardless of recorded hashes
+        #[arg(long)]
+        refresh: bool,
+        /// Manifest file
+        #[arg(long, default_value = generate::MANIFEST_FILE)]
+        manifest: String,
+    },
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum GifKind {
+    Wedge,
+    Baseball,
+}
+
+impl GenerateTarget {
+    pub fn run(self) {
+        match self {
+            GenerateTarget::Ball => generate::ball::run(),
+            GenerateTarget::Showcase => generate::showcase::run(),
+            GenerateTarget::Levels => generate::levels::run(),
+            GenerateTarget::Gif {
+                kind: GifKind::Wedge,
+            } => generate::gif_wedge::run(),
+            GenerateTarget::Gif {
+                kind: GifKind::Baseball,
+            } => generate::gif_baseball::run(),
+            GenerateTarget::All { refresh, manifest } => {
+                let manifest = generate::Manifest::load(&manifest).unwrap_or_else(|e| {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                });
+                let failed = generate::run_all(&manifest, refresh);
+                if !failed.is_empty() {
+                    eprintln!("Failed outputs: {}", failed.join(", "));
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+}
+
+/// Database `seeds run --watch` simulates into before opening the replay viewer
+const SEED_BANK_DB: &str = "db/seed_bank.db";
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum SeedsAction {
+    /// List seed bank entries
+    List,
+    /// Replay an entry headlessly (attended with --watch)
+    Run {
+        /// Entry name
+        name: String,
+        /// Open the simulated match in the replay viewer afterwards
+        #[arg(long)]
+        watch: bool,
+        /// Store the match in this simulation database (--watch default: db/seed_bank.db)
+        #[arg(long)]
+        db: Option<PathBuf>,
+    },
+    /// Add an entry from a simulation database match (e.g. one flagged degenerate)
+    Add {
+        /// Simulation database
+        #[arg(long)]
+        db: PathBuf,
+        /// Match ID in that database
+        #[arg(long = "match", value_name = "ID")]
+        match_id: i64,
+        /// Entry name
+        #[arg(long)]
+        name: String,
+        /// What the match shows
+        #[arg(long, default_value = "")]
+        note: String,
+    },
+}
+
+impl SeedsAction {
+    /// Run the action. Returns game arguments when a replay should be opened.
+    pub fn run(self) -> Option<Vec<String>> {
+        let mut bank = SeedBank::load().unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        });
+        match self {
+            SeedsAction::List => {
+                for entry in &bank.entries {
+                    println!(
+                        "{:<24} seed {:<20} level {:<3} {} vs {}{}",
+                        entry.name,
+                        entry.seed,
+                        entry.level,
+                        entry.left_profile,
+                        entry.right_profile,
+                        entry
+                            .reason
+                            .as_deref()
+                            .map(|r| format!(" [{}]", r))
+                            .unwrap_or_default()
+                    );
+                    if !entry.note.is_empty() {
+                        println!("    {}", entry.note);
+                    }
+                }
+                if bank.entries.is_empty() {
+                    println!("Seed bank is empty (add one with `ballgame seeds add`)");
+                }
+                None
+            }
+            SeedsAction::Run { name, watch, db } => {
+                let Some(entry) = bank.get(&name) else {
+                    eprintln!(
+                        "Error: no seed bank entry '{}' (see `ballgame seeds list`)",
+                        name
+                    );
+                    std::process::exit(1);
+                };
+                if !entry.note.is_empty() {
+                    println!("{}: {}", entry.name, entry.note);
+                }
+                let mut config = entry.sim_config();
+                let db = db.or_else(|| watch.then(|| PathBuf::from(SEED_BANK_DB)));
+                if let Some(db) = &db {
+                    if let Some(parent) = db.parent() {
+                        std::fs::create_dir_all(parent).ok();
+                    }
+                    config.db_path = Some(db.display().to_string());
+                }
+                run_simulation(config);
+
+                let db = db.filter(|_| watch)?;
+                let match_id = SimDatabase::open(&db)
+                    .ok()
+                    .and_then(|sim_db| sim_db.get_latest_session_matches().ok())
+                    .and_then(|matches| matches.last().map(|m| m.id));
+                let Some(match_id) = match_id else {
+                    eprintln!("Error: simulated match not found in {}", db.display());
+                    std::process::exit(1);
+                };
+                Some(vec![
+                    "ballgame".to_string(),
+                    "--replay-db".to_string(),
+                    match_id.to_string(),
+                    "--replay-db-path".to_string(),
+                    db.display().to_string(),
+                ])
+            }
+            SeedsAction::Add {
+                db,
+                match_id,
+                name,
+                note,
+            } => {
+                let result = SimDatabase::open(&db)
+                    .map_err(|e| format!("{}: {}", db.display(), e))
+                    .and_then(|sim_db| SeedBankEntry::from_match(&sim_db, match_id, &name))
+                    .and_then(|entry| bank.add(SeedBankEntry { note, ..entry }))
+                    .and_then(|_| bank.save());
+                if let Err(e) = result {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+                println!("Added '{}' to the seed bank", name);
+                None
+            }
+        }
+    }
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ProfilesAction {
+    /// Lint profiles for values the AI can't act on (exits 1 on issues)
+    Check {
+        /// Profiles file
+        #[arg(long, default_value = AI_PROFILES_FILE)]
+        file: String,
+    },
+    /// Breed new profiles with a genetic algorithm over headless matches
+    /// (winners are appended to the profiles file)
+    Evolve {
+        /// Profiles file to read and append to
+        #[arg(long, default_value = AI_PROFILES_FILE)]
+        file: String,
+        #[arg(long, default_value_t = 8)]
+        generations: u32,
+        /// Candidates per generation
+        #[arg(long, default_value_t = 12)]
+        population: usize,
+        /// Matches each candidate plays per generation
+        #[arg(long, default_value_t = 4)]
+        matches: u32,
+        /// Best candidates carried over unchanged
+        #[arg(long, default_value_t = 2)]
+        elite: usize,
+        /// Chance each parameter mutates (0-1)
+        #[arg(long, default_value_t = 0.3)]
+        mutation_rate: f32,
+        #[arg(long, default_value_t = 42)]
+        seed: u64,
+        /// Seed profiles, comma-separated (default: every profile)
+        #[arg(long, value_delimiter = ',')]
+        from: Vec<String>,
+        /// Opponent pool, comma-separated (default: the seed profiles)
+        #[arg(long, value_delimiter = ',')]
+        opponents: Vec<String>,
+        /// Evolved profiles written back (best first)
+        #[arg(long, default_value_t = 3)]
+        keep: usize,
+        /// Simulation threads (0 = all cores)
+        #[arg(long, default_value_t = 0)]
+        threads: usize,
+        /// Print the results without writing the profiles file
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+impl ProfilesAction {
+    pub fn run(self) {
+        match self {
+            ProfilesAction::Check { file } => {
+                let profile_db = AiProfileDatabase::load_from_file(&file);
+                let level_db = LevelDatabase::load_from_file(LEVELS_FILE);
+                // Check shoot ranges against every basket height actually in play
+                let mut heights: Vec<f32> = level_db
+                    .all()
+                    .iter()
+                    .filter(|level| !level.debug && !level.regression)
+                    .map(|level| level.basket_height)
+                    .collect();
+                heights.sort_by(f32::total_cmp);
+                heights.dedup();
+                if heights.is_empty() {
+                    heights = crate::ai::TYPICAL_BASKET_HEIGHTS.to_vec();
+                }
+
+                let issues = lint_profiles(profile_db.profiles(), &heights);
+                for issue in &issues {
+                    println!("{}", issue);
+                }
+                println!(
+                    "{} profiles checked against {} basket heights: {} issues",
+                    profile_db.len(),
+                    heights.len(),
+                    issues.len()
+                );
+                if !issues.is_empty() {
+                    std::process::exit(1);
+                }
+            }
+            ProfilesAction::Evolve {
+                file,
+                generations,
+                population,
+                matches,
+                elite,
+                mutation_rate,
+                seed,
+                from,
+                opponents,
+                keep,
+                threads,
+                dry_run,
+            } => {
+                let profile_db = AiProfileDatabase::load_from_file(&file);
+                let level_db = LevelDatabase::load_from_file(LEVELS_FILE);
+                let config = EvolveConfig {
+                    generations,
+                    population,
+                    matches,
+                    elite,
+                    mutation_rate,
+                    seed,
+                    parents: from,
+                    opponents,
+                    tag: format!("evo{}", chrono::Local::now().format("%m%d_%H%M")),
+                    ..Default::default()
+                };
+                let base_config = SimConfig {
+                    quiet: true,
+                    ..Default::default()
+                };
+                init_parallel(threads);
+                let ranked = run_evolution(config.clone(), &base_config, &level_db, &profile_db)
+                    .unwrap_or_else(|e| {
+                        eprintln!("Evolve: {}", e);
+                        std::process::exit(1);
+                    });
+                let best = &ranked[..keep.min(ranked.len())];
+                for candidate in best {
+                    print!("\n{}", candidate.lineage_comment());
+                    print!("{}", candidate.profile.to_config());
+                }
+                if dry_run {
+                    return;
+                }
+                if let Err(e) = append_evolved_profiles(&file, best, &config) {
+                    eprintln!("Evolve: {}", e);
+                    std::process::exit(1);
+                }
+                println!("\nAppended {} evolved profiles to {}", best.len(), file);
+            }
+        }
+    }
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum DbAction {
+    /// Prune old debug samples and compress finished training databases
+    Gc {
+        /// Database directory
+        #[arg(long, default_value = DB_DIR)]
+        dir: PathBuf,
+        /// Keep debug samples from the last N days
+        #[arg(long, value_name = "N", default_value_t = DB_DEBUG_RETENTION_DAYS)]
+        debug_days: u32,
+        /// Prune only; leave finished databases uncompressed
+        #[arg(long)]
+        no_compress: bool,
+        /// Show what would be pruned and compressed without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+impl DbAction {
+    pub fn run(self) {
+        match self {
+            DbAction::Gc {
+                dir,
+                debug_days,
+                no_compress,
+                dry_run,
+            } => {
+                let report = run_gc(&GcOptions {
+                    dir,
+                    debug_days,
+                    compress: !no_compress,
+                    dry_run,
+                });
+                for path in &report.compressed {
+                    let verb = if dry_run {
+                        "would compress"
+                    } else {
+                        "compressed"
+                    };
+                    println!("  {} {}", verb, path.display());
+                }
+                for error in &report.errors {
+                    eprintln!("Error: {}", error);
+                }
+                println!("{}", report.summary());
+                if !report.errors.is_empty() {
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+}
+
+/// Parse the process arguments and select the config dir. Bare flags (or no arguments) mean `play`.
+pub fn parse() -> Cli {
+    let args: Vec<String> = std::env::args().collect();
+    config_dir::init_from_args(&args);
+    Cli::parse_from(with_default_command(args))
+}
+
+/// Insert `play` when the first argument is not a subcommand (keeps old invocations working)
+fn with_default_command(mut args: Vec<String>) -> Vec<String> {
+    let wants_play = match args.get(1).map(String::as_str) {
+        None => true,
+        Some("-h" | "--help" | "-V" | "--version") => false,
+        Some(first) => first.starts_with('-'),
+    };
+    if wants_play {
+        args.insert(1.min(args.len()), "play".to_string());
+    }
+    args
+}
+
+/// Run a sibling binary (built alongside `ballgame`) with the given flag list.
+/// Exits with the tool's status code.
+pub fn run_tool(args: &[String]) -> ! {
+    match run_tool_status(args) {
+        Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Run a sibling binary and wait for it, returning its exit status
+pub fn run_tool_status(args: &[String]) -> Result<std::process::ExitStatus, String> {
+    let (name, rest) = args
+        .split_first()
+        .expect("tool args start with the binary name");
+    let exe = std::env::current_exe()
+        .map(|path| path.with_file_name(format!("{}{}", name, std::env::consts::EXE_SUFFIX)));
+    let exe = match exe {
+        Ok(exe) if exe.exists() => exe,
+        _ => {
+            return Err(format!(
+                "'{}' binary not found next to ballgame - build it with `cargo build --bin {}`",
+                name, name
+            ));
+        }
+    };
+    // The config dir travels by environment (clap has already consumed --config-dir)
+    let mut command = Process::new(&exe);
+    command.args(rest);
+    if let Some(dir) = config_dir::active() {
+        command.env(config_dir::CONFIG_DIR_ENV, dir);
+    }
+    command
+        .status()
+        .map_err(|e| format!("failed to run {}: {}", exe.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_args(args: &[&str]) -> Cli {
+        Cli::parse_from(with_default_command(
+            args.iter().map(|s| s.to_string()).collect(),
+        ))
+    }
+
+    #[test]
+    fn test_bare_flags_mean_play() {
+        let cli = parse_args(&["ballgame", "--level", "Arena", "--freeze-countdown"]);
+        let Command::Play(play) = cli.command else {
+            panic!("expected play");
+        };
+        assert_eq!(
+            play.to_args(),
+            ["ballgame", "--level", "Arena", "--freeze-countdown"]
+        );
+        assert!(matches!(
+            parse_args(&["ballgame"]).command,
+            Command::Play(_)
+        ));
+    }
+
+    #[test]
+    fn test_shared_options_forwarded_to_tools() {
+        let cli = parse_args(&[
+            "ballgame",
+            "train",
+            "--level",
+            "2",
+            "--seed",
+            "7",
+            "--overtime",
+            "--protocol",
+            "pursuit",
+        ]);
+        let Command::Train(train) = cli.command else {
+            panic!("expected train");
+        };
+        assert_eq!(
+            train.to_args(),
+            [
+                "training",
+                "--level",
+                "2",
+                "--seed",
+                "7",
+                "--overtime",
+                "--protocol",
+                "pursuit"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_seeds_add_and_replay_db() {
+        let cli = parse_args(&[
+            "ballgame",
+            "seeds",
+            "add",
+            "--db",
+            "db/sim.db",
+            "--match",
+            "12",
+            "--name",
+            "stuck",
+        ]);
+        let Command::Seeds {
+            action: SeedsAction::Add { match_id, name, .. },
+        } = cli.command
+        else {
+            panic!("expected seeds add");
+        };
+        assert_eq!((match_id, name.as_str()), (12, "stuck"));
+
+        let cli = parse_args(&["ballgame", "replay", "3", "--db", "db/seed_bank.db"]);
+        let Command::Replay(replay) = cli.command else {
+            panic!("expected replay");
+        };
+        assert_eq!(
+            replay.to_args(),
+            [
+                "ballgame",
+                "--replay-db",
+                "3",
+                "--replay-db-path",
+                "db/seed_bank.db"
+            ]
+        );
+    }
+}
@@ -0,0 +1,305 @@
+//! Typed gameplay config - grouped, serde-loadable versions of `constants`
+//!
+//! Each group (movement, ball, shot, steal, arena) is its own resource, so
+//! systems read `Res<StealConfig>` instead of bare `STEAL_*` constants and a
+//! mode can run with different numbers without a rebuild. The constants stay
+//! the defaults: every field is `#[serde(default)]`, so a config file only
+//! lists what it changes.
+//!
+//! Each app adds `GameplayConfig::load(mode)` as a plugin at startup.
+//! `config/gameplay_config.json` (resolved through `config_dir`) holds the base
+//! overrides, plus an optional `modes` table keyed by mode name ("play",
+//! "simulate", "train", "embed", "test", "ghost") whose entries are layered on
+//! top for that mode only:
+//!
+//! ```json
+//! {
+//!   "steal": { "success_chance": 0.3 },
+//!   "modes": { "simulate": { "steal": { "cooldown": 0.2 } } }
+//! }
+//! ```
+//!
+//! Values the tweak panel already edits live (gravity, move speed, shot power
+//! and variance, ...) stay in `PhysicsTweaks`.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::constants::*;
+
+/// Path to the typed gameplay config (optional; missing = all defaults)
+pub const GAMEPLAY_CONFIG_FILE: &str = "config/gameplay_config.json";
+
+/// Player movement feel
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MovementConfig {
+    /// Seconds after leaving ground you can still jump
+    pub coyote_time: f32,
+    /// Seconds before landing that a jump press is remembered
+    pub jump_buffer_time: f32,
+    /// Velocity multiplier when releasing jump early
+    pub jump_cut_multiplier: f32,
+    /// Analog stick deadzone
+    pub stick_deadzone: f32,
+}
+
+impl Default for MovementConfig {
+    fn default() -> Self {
+        Self {
+            coyote_time: COYOTE_TIME,
+            jump_buffer_time: JUMP_BUFFER_TIME,
+            jump_cut_multiplier: JUMP_CUT_MULTIPLIER,
+            stick_deadzone: STICK_DEADZONE,
+        }
+    }
+}
+
+/// Ball handling, bounce and contact
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BallConfig {
+    /// How close a player must be to pick up the ball
+    pub pickup_radius: f32,
+    /// Ball becomes Free below this speed
+    pub free_speed: f32,
+    /// Horizontal velocity retained per floor bounce
+    pub ground_friction: f32,
+    /// Ball must bounce this × its height to keep bouncing, else rolls
+    pub bounce_height_mult: f32,
+    /// Spin rate per unit velocity (airborne)
+    pub spin_factor: f32,
+    /// Spin retained per second (airborne)
+    pub spin_decay: f32,
+    /// Horizontal velocity multiplier when the ball hits a player
+    pub player_drag_x: f32,
+    /// Vertical velocity multiplier when the ball hits a player
+    pub player_drag_y: f32,
+    /// Velocity a player imparts to a stationary ball
+    pub kick_strength: f32,
+    /// Ball slower than this counts as stationary
+    pub kick_threshold: f32,
+    /// Rolling balls slower than this are scooped by a running player holding pickup
+    pub scoop_max_ball_speed: f32,
+    /// Horizontal speed a player needs to scoop
+    pub scoop_min_run_speed: f32,
+    /// Length of the scoop animation; the scooper can't be stolen from meanwhile
+    pub scoop_window: f32,
+}
+
+impl Default for BallConfig {
+    fn default() -> Self {
+        Self {
+            pickup_radius: BALL_PICKUP_RADIUS,
+            free_speed: BALL_FREE_SPEED,
+            ground_friction: BALL_GROUND_FRICTION,
+            bounce_height_mult: BALL_BOUNCE_HEIGHT_MULT,
+            spin_factor: BALL_SPIN_FACTOR,
+            spin_decay: BALL_SPIN_DECAY,
+            player_drag_x: BALL_PLAYER_DRAG_X,
+            player_drag_y: BALL_PLAYER_DRAG_Y,
+            kick_strength: BALL_KICK_STRENGTH,
+            kick_threshold: BALL_KICK_THRESHOLD,
+            scoop_max_ball_speed: BALL_SCOOP_MAX_SPEED,
+            scoop_min_run_speed: BALL_SCOOP_MIN_RUN_SPEED,
+            scoop_window: BALL_SCOOP_WINDOW,
+        }
+    }
+}
+
+/// Shot limits
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShotConfig {
+    /// Maximum total ball speed of a shot
+    pub max_speed: f32,
+    /// Absolute maximum shot speed (alerts if reached)
+    pub hard_cap: f32,
+    /// Default shot angle in degrees
+    pub default_angle: f32,
+    /// Post-shot grace period (no friction/player drag)
+    pub grace_period: f32,
+    /// Charging on the ground below this horizontal speed counts as set
+    pub steady_speed: f32,
+    /// Additional variance for a charge spent entirely moving or airborne
+    pub unsteady_variance: f32,
+    /// Fraction of shot speed lost for a charge spent entirely moving or airborne
+    pub unsteady_power_loss: f32,
+}
+
+impl Default for ShotConfig {
+    fn default() -> Self {
+        Self {
+            max_speed: SHOT_MAX_SPEED,
+            hard_cap: SHOT_HARD_CAP,
+            default_angle: SHOT_DEFAULT_ANGLE,
+            grace_period: SHOT_GRACE_PERIOD,
+            steady_speed: SHOT_CHARGE_STEADY_SPEED,
+            unsteady_variance: SHOT_CHARGE_UNSTEADY_VARIANCE,
+            unsteady_power_loss: SHOT_CHARGE_UNSTEADY_POWER_LOSS,
+        }
+    }
+}
+
+/// Steal attempts
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StealConfig {
+    /// How close to initiate a steal
+    pub range: f32,
+    /// Range for "out of range" feedback
+    pub near_miss_range: f32,
+    /// Base success chance
+    pub success_chance: f32,
+    /// Added when the holder is charging a shot
+    pub charging_bonus: f32,
+    /// Knockback velocity on a successful steal
+    pub pushback_strength: f32,
+    /// Attacker cooldown after a successful steal
+    pub cooldown: f32,
+    /// Attacker cooldown after a failed steal
+    pub fail_cooldown: f32,
+    /// Short cooldown when out of range
+    pub out_of_range_cooldown: f32,
+    /// Seconds before the victim can steal back
+    pub victim_cooldown: f32,
+}
+
+impl Default for StealConfig {
+    fn default() -> Self {
+        Self {
+            range: STEAL_RANGE,
+            near_miss_range: STEAL_NEAR_MISS_RANGE,
+            success_chance: STEAL_SUCCESS_CHANCE,
+            charging_bonus: STEAL_CHARGING_BONUS,
+            pushback_strength: STEAL_PUSHBACK_STRENGTH,
+            cooldown: STEAL_COOLDOWN,
+            fail_cooldown: STEAL_FAIL_COOLDOWN,
+            out_of_range_cooldown: STEAL_OUT_OF_RANGE_COOLDOWN,
+            victim_cooldown: STEAL_VICTIM_COOLDOWN,
+        }
+    }
+}
+
+/// How the arena treats a ball that leaves play (dimensions come from the level)
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ArenaConfig {
+    /// Ball slower than this counts as resting
+    pub stuck_speed: f32,
+    /// Seconds resting out of reach before a reset
+    pub stuck_time: f32,
+    /// Reachability below this = out of reach
+    pub unreachable_threshold: f32,
+    /// Seconds out of bounds before a reset
+    pub out_of_bounds_time: f32,
+    /// Slack past walls/floor before out of bounds
+    pub out_of_bounds_margin: f32,
+    /// Higher than this above the ceiling = lost
+    pub max_height_above_ceiling: f32,
+}
+
+impl Default for ArenaConfig {
+    fn default() -> Self {
+        Self {
+            stuck_speed: BALL_STUCK_SPEED,
+            stuck_time: BALL_STUCK_TIME,
+            unreachable_threshold: BALL_UNREACHABLE_THRESHOLD,
+            out_of_bounds_time: BALL_OUT_OF_BOUNDS_TIME,
+            out_of_bounds_margin: BALL_OUT_OF_BOUNDS_MARGIN,
+            max_height_above_ceiling: BALL_MAX_HEIGHT_ABOVE_CEILING,
+        }
+    }
+}
+
+/// All config groups, as stored in `gameplay_config.json`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameplayConfig {
+    pub movement: MovementConfig,
+    pub ball: BallConfig,
+    pub shot: ShotConfig,
+    pub steal: StealConfig,
+    pub arena: ArenaConfig,
+}
+
+impl GameplayConfig {
+    /// Parse a config file's JSON with `mode`'s overrides layered on top
+    pub fn from_json(json: &str, mode: &str) -> Result<Self, String> {
+        let mut value: Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        let overrides = value
+            .as_object_mut()
+            .and_then(|root| root.remove("modes"))
+            .and_then(|mut modes| modes.get_mut(mode).map(Value::take));
+        if let Some(overrides) = overrides {
+            merge_json(&mut value, overrides);
+        }
+        serde_json::from_value(value).map_err(|e| e.to_string())
+    }
+
+    /// Load GAMEPLAY_CONFIG_FILE for `mode` (defaults when missing or invalid)
+    pub fn load(mode: &str) -> Self {
+        let path = crate::config_dir::resolve(GAMEPLAY_CONFIG_FILE);
+        let Ok(json) = std::fs::read_to_string(&path) else {
+            return Self::default();
+        };
+        Self::from_json(&json, mode).unwrap_or_else(|e| {
+            warn!("Failed to parse {}: {}; using defaults", path, e);
+            Self::default()
+        })
+    }
+}
+
+/// Adding the config as a plugin inserts each group as its own resource
+impl Plugin for GameplayConfig {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.movement.clone());
+        app.insert_resource(self.ball.clone());
+        app.insert_resource(self.shot.clone());
+        app.insert_resource(self.steal.clone());
+        app.insert_resource(self.arena.clone());
+    }
+}
+
+/// Overlay `overrides` onto `base`, recursing into objects
+fn merge_json(base: &mut Value, overrides: Value) {
+    match (base, overrides) {
+        (Value::Object(base), Value::Object(overrides)) => {
+            for (key, value) in overrides {
+                merge_json(base.entry(key).or_insert(Value::Null), value);
+            }
+        }
+        (base, overrides) => *base = overrides,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mode_overrides_layer_on_base() {
+        let json = r#"{
+            "steal": { "success_chance": 0.3, "range": 70.0 },
+            "modes": {
+                "simulate": { "steal": { "range": 80.0 }, "movement": { "coyote_time": 0.2 } }
+            }
+        }"#;
+
+        let play = GameplayConfig::from_json(json, "play").unwrap();
+        assert_eq!(play.steal.success_chance, 0.3);
+        assert_eq!(play.steal.range, 70.0);
+        // Unlisted fields and groups keep the constants
+        assert_eq!(play.steal.cooldown, STEAL_COOLDOWN);
+        assert_eq!(play.ball, BallConfig::default());
+
+        let simulate = GameplayConfig::from_json(json, "simulate").unwrap();
+        assert_eq!(simulate.steal.success_chance, 0.3);
+        assert_eq!(simulate.steal.range, 80.0);
+        assert_eq!(simulate.movement.coyote_time, 0.2);
+        assert_eq!(simulate.movement.jump_buffer_time, JUMP_BUFFER_TIME);
+
+        assert!(GameplayConfig::from_json("{ \"steal\": 3 }", "play").is_err());
+    }
+}
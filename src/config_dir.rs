@@ -0,0 +1,115 @@
+//! Config directories - side-by-side configurations ("tournament", "experimental", ...)
+//!
+//! A config dir holds any subset of the files in `config/` (levels.txt, palettes.txt,
+//! ai_profiles.txt, game_presets.txt, gameplay_tuning.json). Files it contains replace
+//! the defaults; anything missing falls back to `config/`.
+//!
+//! Selected with `--config-dir <dir>` or the `BALLGAME_CONFIG_DIR` environment variable.
+//! The loaders call `resolve` on their `config/...` paths, so every binary picks it up.
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Default config directory (prefix of all built-in config paths)
+pub const DEFAULT_CONFIG_DIR: &str = "config";
+
+/// Environment variable selecting the config dir (also passed to child tools)
+pub const CONFIG_DIR_ENV: &str = "BALLGAME_CONFIG_DIR";
+
+static ACTIVE_CONFIG_DIR: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+/// Select the config dir from `--config-dir <dir>` (or `BALLGAME_CONFIG_DIR`).
+/// Call once at startup, before any config is loaded.
+pub fn init_from_args(args: &[String]) {
+    let dir = args
+        .iter()
+        .position(|a| a == "--config-dir")
+        .and_then(|i| args.get(i + 1))
+        .map(PathBuf::from)
+        .or_else(dir_from_env);
+    if ACTIVE_CONFIG_DIR.set(validate(dir)).is_err() {
+        eprintln!("Warning: config dir already selected, ignoring --config-dir");
+    }
+}
+
+/// The selected config dir, if any (None = plain `config/`)
+pub fn active() -> Option<&'static Path> {
+    ACTIVE_CONFIG_DIR
+        .get_or_init(|| validate(dir_from_env()))
+        .as_deref()
+}
+
+/// Map a built-in `config/...` path into the selected config dir.
+/// Returns the original path when no dir is selected or the dir lacks that file.
+pub fn resolve(path: &str) -> String {
+    resolve_in(active(), path)
+}
+
+/// Like `resolve`, but always points into the selected config dir (for files tools write)
+pub fn resolve_for_write(path: &str) -> String {
+    match (active(), Path::new(path).strip_prefix(DEFAULT_CONFIG_DIR)) {
+        (Some(dir), Ok(file)) => dir.join(file).to_string_lossy().into_owned(),
+        _ => path.to_string(),
+    }
+}
+
+fn resolve_in(dir: Option<&Path>, path: &str) -> String {
+    let (Some(dir), Ok(file)) = (dir, Path::new(path).strip_prefix(DEFAULT_CONFIG_DIR)) else {
+        return path.to_string();
+    };
+    let candidate = dir.join(file);
+    if candidate.exists() {
+        candidate.to_string_lossy().into_owned()
+    } else {
+        path.to_string()
+    }
+}
+
+fn dir_from_env() -> Option<PathBuf> {
+    std::env::var_os(CONFIG_DIR_ENV).map(PathBuf::from)
+}
+
+fn validate(dir: Option<PathBuf>) -> Option<PathBuf> {
+    let dir = dir?;
+    if dir.is_dir() {
+        Some(dir)
+    } else {
+        eprintln!(
+            "Warning: config dir {} not found, using {}/",
+            dir.display(),
+            DEFAULT_CONFIG_DIR
+        );
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_prefers_config_dir_then_falls_back() {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!("config_dir_test_{}", nanos));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("levels.txt"), "").unwrap();
+
+        let levels = resolve_in(Some(&dir), "config/levels.txt");
+        assert_eq!(Path::new(&levels), dir.join("levels.txt"));
+        // Missing files and non-config paths are untouched
+        assert_eq!(
+            resolve_in(Some(&dir), "config/palettes.txt"),
+            "config/palettes.txt"
+        );
+        assert_eq!(
+            resolve_in(Some(&dir), "tests/levels.txt"),
+            "tests/levels.txt"
+        );
+        assert_eq!(resolve_in(None, "config/levels.txt"), "config/levels.txt");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -2,12 +2,16 @@
 //!
 //! Polls config files every 10 seconds and reloads when modified.
 //! Replaces F2 manual hot-reload.
+//!
+//! AI profiles reload in place: AI players keep their profile across the
+//! reload (re-resolved by name if its ID was edited), so tuning a profile takes
+//! effect mid-match. Training only watches the profiles (`check_ai_profile_changes`).
 
 use bevy::prelude::*;
 use std::fs;
 use std::time::SystemTime;
 
-use crate::ai::{AI_PROFILES_FILE, AiProfileDatabase};
+use crate::ai::{AI_PROFILES_FILE, AiProfileDatabase, AiState};
 use crate::ball::CurrentPalette;
 use crate::constants::LEVELS_FILE;
 use crate::levels::{LevelDatabase, reload_level_geometry};
@@ -52,8 +56,11 @@ impl Default for ConfigWatcher {
 }
 
 /// Get file modification time, or None if file doesn't exist
+/// (watches the selected config dir's copy when it has one)
 fn get_mtime(path: &str) -> Option<SystemTime> {
-    fs::metadata(path).ok().and_then(|m| m.modified().ok())
+    fs::metadata(crate::config_dir::resolve(path))
+        .ok()
+        .and_then(|m| m.modified().ok())
 }
 
 /// Check for config file changes and reload as needed.
@@ -73,6 +80,7 @@ pub fn check_config_changes(
     level_platforms: Query<Entity, With<LevelPlatform>>,
     corner_ramps: Query<Entity, With<CornerRamp>>,
     mut baskets: Query<(&mut Transform, &Basket)>,
+    mut ai_states: Query<&mut AiState>,
 ) {
     watcher.timer += time.delta_secs();
 
@@ -174,8 +182,7 @@ pub fn check_config_changes(
 
     // Reload AI profiles if changed
     if ai_profiles_changed {
-        *profile_db = AiProfileDatabase::load_from_file(AI_PROFILES_FILE);
-        info!("Auto-reloaded AI profiles from {}", AI_PROFILES_FILE);
+        reload_ai_profiles(&mut profile_db, &mut ai_states);
     }
 
     // Reload game presets if changed
@@ -200,3 +207,43 @@ pub fn check_config_changes(
         }
     }
 }
+
+/// Reload AI profiles when ai_profiles.txt changes, for apps that don't run
+/// `check_config_changes` (training). Runs every 10 seconds.
+pub fn check_ai_profile_changes(
+    time: Res<Time>,
+    mut watcher: ResMut<ConfigWatcher>,
+    mut profile_db: ResMut<AiProfileDatabase>,
+    mut ai_states: Query<&mut AiState>,
+) {
+    watcher.timer += time.delta_secs();
+    if watcher.timer < CHECK_INTERVAL {
+        return;
+    }
+    watcher.timer = 0.0;
+
+    let new_ai_profiles_mtime = get_mtime(AI_PROFILES_FILE);
+    if new_ai_profiles_mtime != watcher.ai_profiles_mtime {
+        watcher.ai_profiles_mtime = new_ai_profiles_mtime;
+        reload_ai_profiles(&mut profile_db, &mut ai_states);
+    }
+}
+
+/// Swap in the reloaded profiles and point every AI at its profile in the new set
+fn reload_ai_profiles(profile_db: &mut AiProfileDatabase, ai_states: &mut Query<&mut AiState>) {
+    let old = std::mem::replace(
+        profile_db,
+        AiProfileDatabase::load_from_file(AI_PROFILES_FILE),
+    );
+    for mut ai_state in ai_states.iter_mut() {
+        let resolved = profile_db.resolve_reloaded(&old, &ai_state.profile_id);
+        if resolved != ai_state.profile_id {
+            ai_state.profile_id = resolved;
+        }
+    }
+    info!(
+        "Auto-reloaded {} AI profiles from {}",
+        profile_db.len(),
+        AI_PROFILES_FILE
+    );
+}
@@ -28,6 +28,7 @@ pub const PLAYER_SIZE: Vec2 = Vec2::new(32.0, 64.0);
 pub const BALL_SIZE: Vec2 = Vec2::new(26.0, 26.0); // 10% larger than original 24x24
 pub const CHARGE_GAUGE_WIDTH: f32 = 8.0;
 pub const CHARGE_GAUGE_HEIGHT: f32 = PLAYER_SIZE.y; // Same height as player
+pub const CHARGE_FEEDBACK_DURATION: f32 = 1.2; // Seconds release/ideal markers stay on the gauge
 
 // =============================================================================
 // PHYSICS CONSTANTS
@@ -43,6 +44,8 @@ pub const GROUND_DECEL: f32 = 1800.0; // Ground deceleration - slight slide when
 pub const AIR_ACCEL: f32 = 1500.0; // Air acceleration - committed but adjustable jumps
 pub const AIR_DECEL: f32 = 900.0; // Air deceleration - momentum preserved in air
 pub const COLLISION_EPSILON: f32 = 0.5; // Skin width for collision detection
+pub const DEPENETRATION_TOLERANCE: f32 = 2.0; // Overlap (both axes) beyond which a player counts as embedded
+pub const DEPENETRATION_MAX_STEPS: usize = 4; // Platforms a player can be pushed out of per frame
 
 // =============================================================================
 // GAME FEEL CONSTANTS
@@ -62,8 +65,17 @@ pub const BALL_AIR_FRICTION: f32 = 0.95; // Horizontal velocity retained after 1
 pub const BALL_GROUND_FRICTION: f32 = 0.6; // Horizontal velocity retained per bounce
 pub const BALL_ROLL_FRICTION: f32 = 0.6; // Horizontal velocity retained after 1 second while rolling
 pub const BALL_BOUNCE_HEIGHT_MULT: f32 = 1.0; // Ball must bounce this × its height to keep bouncing, else rolls
+pub const BALL_STYLE_MAX_BOUNCE: f32 = 0.95; // Cap on restitution after a style's `bounce:` multiplier
+pub const BALL_STYLE_MIN_SCALE: f32 = 0.5; // Smallest `scale:` a ball style may set
+pub const BALL_STYLE_MAX_SCALE: f32 = 2.0; // Largest `scale:` a ball style may set
 pub const BALL_PICKUP_RADIUS: f32 = 50.0; // How close player must be to pick up ball
+pub const PICKUP_CONTEST_WINDOW_TICKS: u32 = 3; // Presses this many ticks apart still contest a free ball
+pub const PICKUP_CONTEST_DISTANCE_MARGIN: f32 = 4.0; // Closer by more than this wins a pickup contest
+pub const PICKUP_CONTEST_SPEED_MARGIN: f32 = 20.0; // Then approaching faster by more than this wins
 pub const BALL_FREE_SPEED: f32 = 200.0; // Ball becomes Free when speed drops below this (2x pickup radius speed)
+pub const BALL_SCOOP_MAX_SPEED: f32 = 150.0; // Rolling balls slower than this can be scooped (pickup held, no press)
+pub const BALL_SCOOP_MIN_RUN_SPEED: f32 = 120.0; // Player must be running at least this fast to scoop
+pub const BALL_SCOOP_WINDOW: f32 = 0.25; // Scoop animation length (seconds), steals on the scooper are off meanwhile
 
 // =============================================================================
 // BALL SPIN/ROTATION
@@ -86,10 +98,35 @@ pub const SHOT_MAX_VARIANCE: f32 = 0.35; // Variance at zero charge (35%)
 pub const SHOT_MIN_VARIANCE: f32 = 0.02; // Variance at full charge (2%)
 pub const SHOT_AIR_VARIANCE_PENALTY: f32 = 0.10; // Additional variance when airborne (10%)
 pub const SHOT_MOVE_VARIANCE_PENALTY: f32 = 0.10; // Additional variance at full horizontal speed (10%)
+pub const SHOT_CHARGE_STEADY_SPEED: f32 = 50.0; // Charging on the ground below this horizontal speed counts as set
+pub const SHOT_CHARGE_UNSTEADY_VARIANCE: f32 = 0.08; // Additional variance for a charge spent entirely moving/airborne (8%)
+pub const SHOT_CHARGE_UNSTEADY_POWER_LOSS: f32 = 0.04; // Shot speed lost for a charge spent entirely moving/airborne (4%)
 pub const SHOT_QUICK_THRESHOLD: f32 = 0.4; // Charge below this (400ms) = half power shot
 pub const SHOT_DEFAULT_ANGLE: f32 = 60.0; // Default shot angle in degrees
 pub const SHOT_GRACE_PERIOD: f32 = 0.1; // Post-shot grace period (no friction/player drag)
 
+// =============================================================================
+// PASSING (2v2)
+// =============================================================================
+
+pub const PASS_SPEED: f32 = 600.0; // Pass distance / this = flight time (before clamping)
+pub const PASS_MIN_FLIGHT_TIME: f32 = 0.3; // Short passes still get a little arc
+pub const PASS_MAX_FLIGHT_TIME: f32 = 0.9; // Long passes are lobbed no higher than this
+pub const TEAMMATE_SPAWN_OFFSET: f32 = 150.0; // Teammates spawn this far behind the regular player
+pub const AI_PASS_MIN_HOLD_TIME: f32 = 0.4; // AI holds the ball at least this long before passing
+pub const AI_PASS_QUALITY_MARGIN: f32 = 0.15; // AI passes when the teammate's look beats its own by this much
+pub const AI_SPACING_DISTANCE: f32 = 250.0; // Off-ball AI is penalized for standing closer than this to the carrier
+pub const AI_SPACING_WEIGHT: f32 = 0.6; // Spot score lost standing on top of the carrier (shot quality units)
+pub const AI_CUT_RANGE: f32 = 200.0; // Spots this close (horizontally) to the basket count as a cut
+pub const AI_CUT_BONUS: f32 = 0.25; // Spot score bonus for an open cut
+pub const AI_OFF_BALL_COVER_DISTANCE: f32 = 120.0; // A defender this close covers an off-ball spot
+pub const AI_OFF_BALL_COVERED_PENALTY: f32 = 0.3; // Spot score lost when a defender covers it
+pub const AI_OFF_BALL_FLOOR_STEP: f32 = 80.0; // Spacing of candidate spots along the floor
+pub const AI_OFF_BALL_STICKINESS: f32 = 0.1; // Bonus for the current spot (prevents flip-flopping)
+pub const AI_SCREEN_DISTANCE: f32 = 80.0; // Attackers this close together set a screen (defenders switch)
+pub const AI_HELP_BEATEN_DISTANCE: f32 = 60.0; // Carrier this much closer to our basket than its defender has beaten them
+pub const AI_SWITCH_COOLDOWN: f32 = 1.0; // Seconds after a switch before matchups may change again
+
 // =============================================================================
 // BALL-PLAYER COLLISION
 // =============================================================================
@@ -99,6 +136,28 @@ pub const BALL_PLAYER_DRAG_Y: f32 = 0.4; // Vertical velocity multiplier (higher
 pub const BALL_KICK_STRENGTH: f32 = 100.0; // How much velocity player imparts to stationary ball
 pub const BALL_KICK_THRESHOLD: f32 = 30.0; // Ball speed below this counts as "stationary"
 
+// =============================================================================
+// STUCK BALL RECOVERY
+// =============================================================================
+
+pub const BALL_STUCK_SPEED: f32 = 5.0; // Ball slower than this counts as resting
+pub const BALL_STUCK_TIME: f32 = 3.0; // Seconds resting out of reach before reset
+pub const BALL_UNREACHABLE_THRESHOLD: f32 = 0.02; // Reachability below this = out of reach
+pub const BALL_OUT_OF_BOUNDS_TIME: f32 = 1.5; // Seconds out of bounds before reset
+pub const BALL_OUT_OF_BOUNDS_MARGIN: f32 = 40.0; // Slack past walls/floor before out of bounds
+pub const BALL_MAX_HEIGHT_ABOVE_CEILING: f32 = 900.0; // Higher than this above view = lost
+pub const BALL_RAIL_HEIGHT: f32 = 600.0; // Invisible ceiling rail height above the view
+pub const BALL_RAIL_BOUNCE: f32 = 0.5; // Velocity kept bouncing off a rail
+
+// =============================================================================
+// POST-GOAL BALL RESET
+// =============================================================================
+
+pub const BALL_RESET_RANDOM_SPREAD: f32 = 300.0; // Random reset lands within +/- this of center
+pub const INBOUND_BASELINE_OFFSET: f32 = 120.0; // Inbounder starts this far in from their basket
+pub const INBOUND_SETUP_TIME: f32 = 1.5; // Seconds the scorer drops back before pressing
+pub const INBOUND_SETUP_DEPTH: f32 = 0.5; // Scorer sets up this fraction of the way to own basket
+
 // =============================================================================
 // STEAL SYSTEM
 // =============================================================================
@@ -115,6 +174,12 @@ pub const STEAL_VICTIM_COOLDOWN: f32 = 1.0; // Seconds before victim can steal b
 pub const STEAL_INDICATOR_SIZE: f32 = 16.0; // Size of cooldown/fail indicators
 pub const STEAL_FAIL_FLASH_DURATION: f32 = 0.15; // Duration of fail flash
 pub const STEAL_OUT_OF_RANGE_FLASH_DURATION: f32 = 0.2; // Duration of out-of-range feedback
+pub const LOOSE_BALL_POP_X: f32 = 220.0; // Max horizontal pop speed when a steal knocks the ball loose (random sign)
+pub const LOOSE_BALL_POP_Y_MIN: f32 = 180.0; // Min upward pop speed
+pub const LOOSE_BALL_POP_Y_MAX: f32 = 320.0; // Max upward pop speed
+pub const LOOSE_BALL_GRACE: f32 = 0.15; // Seconds the popped ball ignores player drag (clears both bodies)
+pub const LOOSE_BALL_SCRAMBLE_TIME: f32 = 2.0; // Seconds AIs treat a loose ball as a scramble
+pub const LOOSE_BALL_PRESS_RATE_SCALE: f32 = 2.0; // AI pickup mash rate multiplier during a scramble
 
 // =============================================================================
 // ARENA DIMENSIONS
@@ -144,6 +209,11 @@ pub const BASKET_SIZE: Vec2 = Vec2::new(60.0, 80.0);
 pub const RIM_THICKNESS: f32 = 10.0;
 pub const WALL_THICKNESS: f32 = 20.0; // Walls are 20 wide
 pub const BASKET_PUSH_IN: f32 = 156.0; // Default distance from wall inner edge to basket center
+pub const CARRY_IN_BLOCK_MARGIN: f32 = 12.0; // Padding around the basket that counts as covering its mouth
+pub const CARRY_IN_BLOCK_WINDOW_FRAMES: u32 = 8; // Defender on the rim this recently blocks a carry-in (fumble)
+pub const CARRY_IN_CONTEST_WINDOW_FRAMES: u32 = 30; // Defender on the rim this recently still counts as a contest
+pub const CARRY_IN_FUMBLE_SPEED: f32 = 260.0; // Speed the ball is knocked out of the basket on a block
+pub const CARRY_IN_THREAT_RANGE: f32 = 250.0; // Carrier this close to the basket (and past the defender) is a threat
 
 // =============================================================================
 // CORNER STEPS
@@ -159,6 +229,82 @@ pub const STEP_DEFLECT_ANGLE_MAX: f32 = 35.0; // Max random deflection angle in
 pub const RIM_BOUNCE_RETENTION: f32 = 0.85; // Rims: between normal (0.7) and steps (0.92)
 pub const RIM_DEFLECT_ANGLE_MAX: f32 = 20.0; // Rims: less chaotic than steps (35°)
 
+// =============================================================================
+// LEVEL FEATURES (portals, bounce pads, wind zones)
+// =============================================================================
+
+pub const PORTAL_SIZE: Vec2 = Vec2::new(40.0, 80.0); // Trigger area (entity center must be inside)
+pub const PORTAL_COOLDOWN: f32 = 0.4; // Seconds before the same entity can teleport again
+pub const BOUNCE_PAD_HEIGHT: f32 = 8.0; // Visual/trigger thickness of a bounce pad
+pub const BOUNCE_PAD_TRIGGER_MARGIN: f32 = 6.0; // Vertical slack when checking feet-on-pad
+pub const WIND_PLAYER_FACTOR: f32 = 0.5; // Fraction of zone force applied to airborne players
+pub const WIND_PARTICLE_DENSITY: f32 = 1.0 / 4000.0; // Particles per square pixel of zone area
+pub const WIND_PARTICLE_SPEED_SCALE: f32 = 0.25; // Particle drift speed per unit of zone force
+
+// =============================================================================
+// SUDDEN DEATH OVERTIME
+// =============================================================================
+
+pub const OVERTIME_SHRINK_RATE: f32 = 12.0; // Pixels per second each wall moves inward
+pub const OVERTIME_MAX_INSET: f32 = 350.0; // Walls stop here (court is 700 narrower)
+pub const OVERTIME_NAV_REBUILD_STEP: f32 = 40.0; // Rebuild nav graph every N pixels of shrink
+
+// =============================================================================
+// MOMENTUM (HOT STREAKS)
+// =============================================================================
+
+pub const MOMENTUM_STREAK: u32 = 2; // Consecutive goals without conceding to heat up
+pub const MOMENTUM_DURATION: f32 = 12.0; // Seconds a hot streak lasts (renewed by each goal)
+pub const MOMENTUM_MOVE_BONUS: f32 = 0.08; // Extra move speed while hot (fraction)
+pub const MOMENTUM_CHARGE_BONUS: f32 = 0.15; // Extra charge rate while hot (fraction)
+pub const MOMENTUM_AI_AGGRESSION: f32 = 0.2; // Added to AI aggression against a hot opponent
+pub const MOMENTUM_AI_PRESSURE: f32 = 0.75; // AI pressure distance scale against a hot opponent
+pub const MOMENTUM_AURA_PADDING: f32 = 14.0; // Aura extends this far past the player sprite
+
+// =============================================================================
+// RIM CAMPING (DEFENSIVE THREE SECONDS)
+// =============================================================================
+
+pub const RIM_CAMPING_SECS: f32 = 3.0; // Unbroken seconds on own rim that count as camping
+pub const RIM_CAMPING_AI_LEAVE_SECS: f32 = 2.0; // AI steps off its own rim after this long (no carry-in threat)
+pub const RIM_CAMPING_AI_CLEAR_DISTANCE: f32 = 120.0; // How far out from the basket the AI steps
+
+// =============================================================================
+// SHOT CLOCK (POSSESSION TIMER)
+// =============================================================================
+
+pub const SHOT_CLOCK_REGRAB_SECS: f32 = 2.0; // Clock left when the violating side picks the ball straight back up
+pub const SHOT_CLOCK_HUD_SECS: f32 = 10.0; // HUD shows the countdown once this little is left
+pub const AI_SHOT_CLOCK_MARGIN: f32 = 0.5; // AI holder starts its shot when the clock is within charge time + this
+
+// =============================================================================
+// OUT OF BOUNDS (THROW-INS)
+// =============================================================================
+
+pub const COURT_BASELINE_DEPTH: f32 = 60.0; // Strip along each wall's inner edge that is out of bounds
+
+// =============================================================================
+// VERSUS SERIES
+// =============================================================================
+
+pub const VERSUS_DEFAULT_FIRST_TO: u32 = 5; // Points that win a series match when --first-to isn't given
+
+// =============================================================================
+// INSTANT REPLAY
+// =============================================================================
+
+pub const INSTANT_REPLAY_SECS: f32 = 3.0; // Real time each goal replay plays for
+pub const INSTANT_REPLAY_SPEED: f32 = 0.5; // Slow motion playback rate
+pub const INSTANT_REPLAY_BUFFER_TICKS: usize = 90; // 3s at 0.5x = last 1.5s of 60 Hz ticks
+
+// =============================================================================
+// ROLLING CLIP RECORDER
+// =============================================================================
+
+pub const CLIP_RECORDER_SECS: u32 = 30; // F5 saves this much of the live game
+pub const CLIP_RECORDER_TICK_MS: u32 = 50; // Tick sample interval (20 Hz, same as logged matches)
+pub const CLIPS_DIR: &str = "clips"; // Where saved clips go
+
 // =============================================================================
 // SPAWN POSITIONS
 // =============================================================================
@@ -205,6 +351,29 @@ pub const NAV_JUMP_TOLERANCE: f32 = 8.0;
 pub const NAV_PATH_RECALC_DISTANCE: f32 = 100.0;
 /// Minimum distance to walk before executing jump (ensures momentum)
 pub const NAV_JUMP_APPROACH_DISTANCE: f32 = 20.0;
+/// Seconds one edge traversal (jump, drop, portal, launch) may take before it counts as timed out
+pub const NAV_EDGE_TIMEOUT: f32 = 4.0;
+/// Attempts before an edge's failure rate is trusted
+pub const NAV_EDGE_MIN_ATTEMPTS: u32 = 5;
+/// Failure rate above which an edge is penalized (with --nav-edge-penalty)
+pub const NAV_EDGE_FAIL_RATE: f32 = 0.5;
+/// Cost multiplier for an edge that always fails (scales up from 1.0 at NAV_EDGE_FAIL_RATE)
+pub const NAV_EDGE_MAX_PENALTY: f32 = 4.0;
+
+// =============================================================================
+// LEVEL RATINGS (analyze --level-ratings)
+// =============================================================================
+
+/// Matches on a level before its side bias is trusted
+pub const LEVEL_RATING_MIN_MATCHES: u32 = 10;
+/// Left-win share away from 50% that marks a level as favoring one side
+pub const LEVEL_RATING_BIAS_THRESHOLD: f32 = 0.1;
+/// Mean seconds to the first shot at or below which a level rates easy
+pub const LEVEL_RATING_EASY_FIRST_SHOT_SECS: f32 = 6.0;
+/// Mean seconds to the first shot at or above which a level rates hard
+pub const LEVEL_RATING_HARD_FIRST_SHOT_SECS: f32 = 12.0;
+/// BallStuck events per match at or above which a level rates hard
+pub const LEVEL_RATING_HARD_STUCK_PER_MATCH: f32 = 1.0;
 /// Minimum reachability value for AI to consider a shooting position
 /// Areas with reachability below this threshold are skipped (likely unreachable/problematic)
 pub const MIN_REACHABILITY_FOR_SHOT: f32 = 0.1;
@@ -219,6 +388,42 @@ pub const DEFENSE_GRACE_REDUCTION: f32 = 0.3;
 /// Maximum shot variance penalty from defender proximity
 pub const DEFENSE_SHOT_VARIANCE_MAX: f32 = 0.20;
 
+// =============================================================================
+// AI RISK (score/clock aware)
+// =============================================================================
+
+/// Seconds left in regulation when AIs start weighing the score
+pub const AI_RISK_LATE_SECS: f32 = 20.0;
+/// Fraction of min_shot_quality a fully trailing AI drops (at trailing_risk 1.0, buzzer)
+pub const AI_RISK_TRAILING_QUALITY_DROP: f32 = 0.6;
+/// Aggression a fully trailing AI adds
+pub const AI_RISK_TRAILING_AGGRESSION: f32 = 0.4;
+/// Steal range stretch for a fully trailing AI (0.3 = 30% further)
+pub const AI_RISK_TRAILING_STEAL_RANGE: f32 = 0.3;
+/// Share of the gap to a perfect min_shot_quality a fully leading AI closes
+pub const AI_RISK_LEADING_QUALITY_RAISE: f32 = 0.4;
+/// Ball hold time stretch for a fully leading AI (1.5 = holds 2.5x as long)
+pub const AI_RISK_LEADING_HOLD_STRETCH: f32 = 1.5;
+
+// =============================================================================
+// AI MISTAKES (lower difficulties)
+// =============================================================================
+
+/// Chance a jump press comes late, at difficulty 0.0 (scales to 0 at 1.0)
+pub const AI_MISTAKE_LATE_JUMP_CHANCE: f32 = 0.3;
+/// Chance a shot is held past its charge target, at difficulty 0.0
+pub const AI_MISTAKE_OVERCHARGE_CHANCE: f32 = 0.35;
+/// Chance a pickup press hesitates, at difficulty 0.0 (rolled per press - AI mashes)
+pub const AI_MISTAKE_HESITATION_CHANCE: f32 = 0.1;
+/// Longest late-jump delay (seconds)
+pub const AI_MISTAKE_LATE_JUMP_SECS: f32 = 0.15;
+/// Longest extra charge hold (seconds)
+pub const AI_MISTAKE_OVERCHARGE_SECS: f32 = 0.4;
+/// Longest pickup hesitation (seconds)
+pub const AI_MISTAKE_HESITATION_SECS: f32 = 0.35;
+/// Clean play after a mistake before the next can happen (seconds)
+pub const AI_MISTAKE_COOLDOWN_SECS: f32 = 1.0;
+
 // =============================================================================
 // DEFAULT AI PROFILES
 // =============================================================================
@@ -0,0 +1,368 @@
+//! Database housekeeping - compress finished sessions, prune debug samples
+//!
+//! Every training run writes `db/training_<timestamp>.db` and records it in
+//! `db/training.latest` (a one-line text file, readable on every platform).
+//! `db/training.db` is also symlinked to it where symlinks are allowed; tools
+//! defaulting to the latest session go through `latest_training_db`, so they
+//! work without the symlink (Windows without developer mode).
+//!
+//! `ballgame db gc` (also run when a training session closes) keeps the `db/`
+//! directory from growing without bound:
+//! - `debug_events` rows older than `--debug-days` are deleted from every
+//!   database in the directory (and the file is vacuumed)
+//! - finished training databases (all but the newest and the latest-session
+//!   one, untouched for `DB_GC_IDLE_SECS`) are gzipped to `.db.gz`
+//!
+//! Databases listed in `offline_training/db_list.txt` are pruned but never
+//! compressed, since offline analysis opens them by path. `gunzip` restores a
+//! compressed database.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use rusqlite::{Connection, params};
+
+/// Directory holding the training and simulation databases
+pub const DB_DIR: &str = "db";
+
+/// Symlink to the newest training database (not created where symlinks aren't allowed)
+pub const LATEST_TRAINING_DB: &str = "db/training.db";
+
+/// Text file naming the newest training database
+pub const LATEST_TRAINING_POINTER: &str = "db/training.latest";
+
+/// Days of debug samples kept by default
+pub const DB_DEBUG_RETENTION_DAYS: u32 = 14;
+
+/// A database modified more recently than this is assumed to be in use
+pub const DB_GC_IDLE_SECS: u64 = 600;
+
+/// Databases offline analysis reads by path (never compressed)
+const OFFLINE_DB_LIST: &str = "offline_training/db_list.txt";
+
+/// Record `target` as the newest training database: write the pointer file,
+/// then symlink `training.db` to it if the platform allows. No hard-link or
+/// copy fallback - SQLite's WAL file is keyed by path, so a second name for a
+/// live database can corrupt it.
+pub fn update_latest_training_db(target: &Path) -> Result<(), String> {
+    fs::write(LATEST_TRAINING_POINTER, format!("{}\n", target.display()))
+        .map_err(|e| format!("Failed to write {}: {}", LATEST_TRAINING_POINTER, e))?;
+
+    let link = Path::new(LATEST_TRAINING_DB);
+    if let Err(e) = fs::remove_file(link)
+        && e.kind() != io::ErrorKind::NotFound
+    {
+        return Err(format!("Failed to remove {}: {}", link.display(), e));
+    }
+    let absolute = std::env::current_dir()
+        .map(|cwd| cwd.join(target))
+        .unwrap_or_else(|_| target.to_path_buf());
+    // The pointer file is enough, so a refused symlink isn't an error
+    let _ = symlink_file(&absolute, link);
+    Ok(())
+}
+
+/// Newest training database: the pointer file's target if it still exists,
+/// otherwise `db/training.db`
+pub fn latest_training_db() -> PathBuf {
+    latest_from_pointer(Path::new(LATEST_TRAINING_POINTER))
+}
+
+fn latest_from_pointer(pointer: &Path) -> PathBuf {
+    fs::read_to_string(pointer)
+        .ok()
+        .map(|contents| PathBuf::from(contents.trim()))
+        .filter(|path| !path.as_os_str().is_empty() && path.exists())
+        .unwrap_or_else(|| PathBuf::from(LATEST_TRAINING_DB))
+}
+
+#[cfg(unix)]
+fn symlink_file(target: &Path, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn symlink_file(target: &Path, link: &Path) -> io::Result<()> {
+    std::os::windows::fs::symlink_file(target, link)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn symlink_file(_target: &Path, _link: &Path) -> io::Result<()> {
+    Err(io::ErrorKind::Unsupported.into())
+}
+
+/// What `run_gc` does
+#[derive(Debug, Clone)]
+pub struct GcOptions {
+    pub dir: PathBuf,
+    /// Debug samples older than this many days are deleted
+    pub debug_days: u32,
+    /// Gzip finished training databases
+    pub compress: bool,
+    /// Report what would change without touching anything
+    pub dry_run: bool,
+}
+
+impl Default for GcOptions {
+    fn default() -> Self {
+        Self {
+            dir: PathBuf::from(DB_DIR),
+            debug_days: DB_DEBUG_RETENTION_DAYS,
+            compress: true,
+            dry_run: false,
+        }
+    }
+}
+
+/// What `run_gc` did (or would do, on a dry run)
+#[derive(Debug, Default)]
+pub struct GcReport {
+    pub pruned_rows: usize,
+    pub compressed: Vec<PathBuf>,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    pub errors: Vec<String>,
+}
+
+impl GcReport {
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "db gc: {} debug samples pruned, {} databases compressed",
+            self.pruned_rows,
+            self.compressed.len()
+        );
+        if self.bytes_before > 0 {
+            summary.push_str(&format!(
+                " ({:.1} MB -> {:.1} MB)",
+                self.bytes_before as f64 / 1e6,
+                self.bytes_after as f64 / 1e6
+            ));
+        }
+        if !self.errors.is_empty() {
+            summary.push_str(&format!(", {} errors", self.errors.len()));
+        }
+        summary
+    }
+}
+
+/// Training databases that are done being written: every `training_*.db`
+/// except the newest (names sort by timestamp) and the one `latest` names
+pub fn finished_training_dbs(names: &[String], latest: Option<&str>) -> Vec<String> {
+    let mut sessions: Vec<&String> = names
+        .iter()
+        .filter(|n| n.starts_with("training_") && n.ends_with(".db"))
+        .collect();
+    sessions.sort();
+    sessions.pop();
+    sessions
+        .into_iter()
+        .filter(|n| Some(n.as_str()) != latest)
+        .cloned()
+        .collect()
+}
+
+/// Delete debug samples older than `days`. Returns the rows removed (or, on a
+/// dry run, the rows that would be).
+pub fn prune_debug_samples(path: &Path, days: u32, dry_run: bool) -> Result<usize, String> {
+    let conn = Connection::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let has_table: i64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'debug_events'",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+    if has_table == 0 {
+        return Ok(0);
+    }
+
+    let cutoff = format!("-{} days", days);
+    let rows = if dry_run {
+        conn.query_row(
+            "SELECT COUNT(*) FROM debug_events WHERE created_at < datetime('now', ?1)",
+            params![cutoff],
+            |row| row.get::<_, i64>(0),
+        )
+        .map(|n| n as usize)
+    } else {
+        conn.execute(
+            "DELETE FROM debug_events WHERE created_at < datetime('now', ?1)",
+            params![cutoff],
+        )
+    }
+    .map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    if rows > 0 && !dry_run {
+        conn.execute_batch("VACUUM")
+            .map_err(|e| format!("{}: vacuum failed: {}", path.display(), e))?;
+    }
+    Ok(rows)
+}
+
+/// Gzip `path` to `<path>.gz` and remove the original
+pub fn compress_db(path: &Path) -> Result<PathBuf, String> {
+    let gz_path = PathBuf::from(format!("{}.gz", path.display()));
+    let write = || -> io::Result<()> {
+        let mut input = fs::File::open(path)?;
+        let mut encoder = GzEncoder::new(fs::File::create(&gz_path)?, Compression::default());
+        io::copy(&mut input, &mut encoder)?;
+        encoder.finish()?;
+        Ok(())
+    };
+    if let Err(e) = write() {
+        let _ = fs::remove_file(&gz_path);
+        return Err(format!("Failed to compress {}: {}", path.display(), e));
+    }
+    fs::remove_file(path).map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+    Ok(gz_path)
+}
+
+/// File names listed in the offline training DB list
+fn offline_db_names() -> HashSet<String> {
+    fs::read_to_string(OFFLINE_DB_LIST)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .filter_map(|path| Path::new(path).file_name())
+        .map(|name| name.to_string_lossy().into_owned())
+        .collect()
+}
+
+/// A database with a journal next to it wasn't closed cleanly (or is open)
+fn has_journal(path: &Path) -> bool {
+    ["-journal", "-wal"]
+        .iter()
+        .any(|suffix| PathBuf::from(format!("{}{}", path.display(), suffix)).exists())
+}
+
+fn is_idle(path: &Path) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age >= Duration::from_secs(DB_GC_IDLE_SECS))
+}
+
+/// Prune debug samples everywhere in `options.dir`, then compress finished
+/// training databases
+pub fn run_gc(options: &GcOptions) -> GcReport {
+    let mut report = GcReport::default();
+    let entries = match fs::read_dir(&options.dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            report
+                .errors
+                .push(format!("{}: {}", options.dir.display(), e));
+            return report;
+        }
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_file()))
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|name| name.ends_with(".db"))
+        .collect();
+    names.sort();
+
+    for name in &names {
+        match prune_debug_samples(&options.dir.join(name), options.debug_days, options.dry_run) {
+            Ok(rows) => report.pruned_rows += rows,
+            Err(e) => report.errors.push(e),
+        }
+    }
+    if !options.compress {
+        return report;
+    }
+
+    let latest = latest_training_db()
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned());
+    let offline = offline_db_names();
+    for name in finished_training_dbs(&names, latest.as_deref()) {
+        let path = options.dir.join(&name);
+        if offline.contains(&name) || has_journal(&path) || !is_idle(&path) {
+            continue;
+        }
+        let before = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        if options.dry_run {
+            report.bytes_before += before;
+            report.compressed.push(path);
+            continue;
+        }
+        match compress_db(&path) {
+            Ok(gz_path) => {
+                report.bytes_before += before;
+                report.bytes_after += fs::metadata(&gz_path).map(|m| m.len()).unwrap_or(0);
+                report.compressed.push(gz_path);
+            }
+            Err(e) => report.errors.push(e),
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_finished_dbs_prune_and_compress() {
+        let names: Vec<String> = [
+            "training_20250101_090000.db",
+            "training_20250102_090000.db",
+            "training_20250103_090000.db",
+            "training.db",
+            "sim.db",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        // Newest is always kept; so is whatever training.db points at
+        assert_eq!(
+            finished_training_dbs(&names, Some("training_20250101_090000.db")),
+            vec!["training_20250102_090000.db".to_string()]
+        );
+
+        let dir = std::env::temp_dir().join(format!("ballgame_db_gc_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("training_20250101_090000.db");
+        let _ = fs::remove_file(&path);
+        {
+            let conn = Connection::open(&path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE debug_events (id INTEGER PRIMARY KEY, created_at TEXT);
+                 INSERT INTO debug_events (created_at) VALUES ('2000-01-01 00:00:00');
+                 INSERT INTO debug_events (created_at) VALUES (datetime('now'));",
+            )
+            .unwrap();
+        }
+        assert_eq!(prune_debug_samples(&path, 14, true).unwrap(), 1);
+        assert_eq!(prune_debug_samples(&path, 14, false).unwrap(), 1);
+        assert_eq!(prune_debug_samples(&path, 14, false).unwrap(), 0);
+
+        // The pointer file names the latest database while it exists
+        let pointer = dir.join("training.latest");
+        fs::write(&pointer, format!("{}\n", path.display())).unwrap();
+        assert_eq!(latest_from_pointer(&pointer), path);
+
+        let original = fs::read(&path).unwrap();
+        let gz_path = compress_db(&path).unwrap();
+        assert!(!path.exists());
+        assert_eq!(
+            latest_from_pointer(&pointer),
+            PathBuf::from(LATEST_TRAINING_DB)
+        );
+        let mut restored = Vec::new();
+        flate2::read::GzDecoder::new(fs::File::open(&gz_path).unwrap())
+            .read_to_end(&mut restored)
+            .unwrap();
+        assert_eq!(restored, original);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
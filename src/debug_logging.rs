@@ -1,16 +1,66 @@
 //! Debug logging configuration shared across binaries.
+//!
+//! Besides the on/off switches, long sessions can keep their databases small by
+//! lowering the tick sample rate, persisting only some event categories, and
+//! recording debug samples for one player only. Each setting can be set in
+//! `config/debug_logging.json` or overridden on the command line:
+//! `--log-tick-ms <MS>`, `--log-categories <LIST>`, `--debug-samples <WHO>`.
 
 use bevy::prelude::Resource;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
 
+use crate::events::{EventCategory, GameEvent, PlayerId};
+
 pub const DEBUG_LOG_SETTINGS_FILE: &str = "config/debug_logging.json";
 
+/// Default interval between Tick/Input events
+pub const DEFAULT_TICK_INTERVAL_MS: u32 = 50;
+
+/// Which players debug samples are recorded for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DebugSamplePlayers {
+    #[default]
+    Both,
+    Left,
+    Right,
+    None,
+}
+
+impl DebugSamplePlayers {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "both" => Some(Self::Both),
+            "left" => Some(Self::Left),
+            "right" => Some(Self::Right),
+            "none" => Some(Self::None),
+            _ => None,
+        }
+    }
+
+    pub fn includes(&self, player: PlayerId) -> bool {
+        match self {
+            Self::Both => true,
+            Self::Left => player == PlayerId::L,
+            Self::Right => player == PlayerId::R,
+            Self::None => false,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Resource)]
+#[serde(default)]
 pub struct DebugLogConfig {
     pub enabled: bool,
     pub skip_reachability_heatmaps: bool,
+    /// Interval between Tick and per-player Input events
+    pub tick_interval_ms: u32,
+    /// Event categories written to the database (match and scoring are always kept)
+    pub event_categories: Vec<EventCategory>,
+    /// Players debug samples are recorded for
+    pub debug_sample_players: DebugSamplePlayers,
 }
 
 impl Default for DebugLogConfig {
@@ -18,6 +68,9 @@ impl Default for DebugLogConfig {
         Self {
             enabled: false,
             skip_reachability_heatmaps: false,
+            tick_interval_ms: DEFAULT_TICK_INTERVAL_MS,
+            event_categories: EventCategory::ALL.to_vec(),
+            debug_sample_players: DebugSamplePlayers::Both,
         }
     }
 }
@@ -43,9 +96,60 @@ impl DebugLogConfig {
         if Self::enabled_from_args(args) {
             config.enabled = true;
         }
+        let value = |flag: &str| {
+            args.iter()
+                .position(|arg| arg == flag)
+                .and_then(|i| args.get(i + 1))
+                .map(String::as_str)
+        };
+        if let Err(e) = config.apply_overrides(
+            value("--log-tick-ms"),
+            value("--log-categories"),
+            value("--debug-samples"),
+        ) {
+            eprintln!("Warning: {}", e);
+        }
         config
     }
 
+    /// Apply command-line overrides; invalid values leave the setting unchanged
+    pub fn apply_overrides(
+        &mut self,
+        tick_ms: Option<&str>,
+        categories: Option<&str>,
+        sample_players: Option<&str>,
+    ) -> Result<(), String> {
+        let mut errors = Vec::new();
+        if let Some(value) = tick_ms {
+            match value.parse::<u32>() {
+                Ok(ms) if ms > 0 => self.tick_interval_ms = ms,
+                _ => errors.push(format!("invalid --log-tick-ms '{}'", value)),
+            }
+        }
+        if let Some(value) = categories {
+            match EventCategory::parse_list(value) {
+                Ok(list) => self.event_categories = list,
+                Err(e) => errors.push(format!("--log-categories: {}", e)),
+            }
+        }
+        if let Some(value) = sample_players {
+            match DebugSamplePlayers::from_str(value) {
+                Some(players) => self.debug_sample_players = players,
+                None => errors.push(format!("invalid --debug-samples '{}'", value)),
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+
+    /// Whether an event is written to the database
+    pub fn persists(&self, event: &GameEvent) -> bool {
+        event.persisted_with(&self.event_categories)
+    }
+
     pub fn apply_env(&self) {
         if self.skip_reachability_heatmaps {
             unsafe {
@@ -54,3 +158,48 @@ impl DebugLogConfig {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overrides_and_category_filter() {
+        // Older config files only have the two switches
+        let mut config: DebugLogConfig =
+            serde_json::from_str(r#"{"enabled": true, "skip_reachability_heatmaps": false}"#)
+                .unwrap();
+        assert_eq!(config.tick_interval_ms, DEFAULT_TICK_INTERVAL_MS);
+        assert!(config.persists(&GameEvent::ResetBall));
+
+        config
+            .apply_overrides(Some("200"), Some("shot, steal"), Some("left"))
+            .unwrap();
+        assert_eq!(config.tick_interval_ms, 200);
+        assert_eq!(
+            config.event_categories,
+            vec![EventCategory::Shot, EventCategory::Steal]
+        );
+        assert!(config.debug_sample_players.includes(PlayerId::L));
+        assert!(!config.debug_sample_players.includes(PlayerId::R));
+
+        assert!(!config.persists(&GameEvent::Jump {
+            player: PlayerId::L
+        }));
+        // Goals are kept regardless so points still split
+        let goal = GameEvent::Goal {
+            player: PlayerId::R,
+            score_left: 0,
+            score_right: 1,
+        };
+        assert!(config.persists(&goal));
+
+        assert!(
+            config
+                .apply_overrides(Some("0"), Some("shots"), Some("all"))
+                .is_err()
+        );
+        assert_eq!(config.tick_interval_ms, 200);
+        assert_eq!(EventCategory::parse_list("all").unwrap().len(), 9);
+    }
+}
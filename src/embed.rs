@@ -0,0 +1,630 @@
+//! Embedding API - run a ballgame match inside another Bevy app
+//!
+//! `BallgamePlugins` bundles everything a match needs (resources, world setup, AI,
+//! physics, match rules, event logging) so launchers and tournament frontends can
+//! host a match without going through the binaries' `main` functions.
+//!
+//! ```ignore
+//! App::new()
+//!     .add_plugins(DefaultPlugins)
+//!     .add_plugins(
+//!         BallgamePlugins::new()
+//!             .level("Arena")
+//!             .profiles("v2_Balanced", "v3_Rush_Smart")
+//!             .rules(MatchRules { score_limit: 5, ..default() })
+//!             .event_log(EventLog::Sqlite("db/embedded.db".into())),
+//!     )
+//!     .run();
+//! ```
+//!
+//! The host supplies the base plugins (`DefaultPlugins`, or `MinimalPlugins` when headless)
+//! and reads `MatchStatus` to find out when the match is over.
+
+use bevy::app::PluginGroupBuilder;
+use bevy::prelude::*;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::ai::{
+    AiCapabilities, AiProfileDatabase, HeatmapBundle, MatchClock, NavGraph, ai_decision_update,
+    ai_mistake_update, ai_navigation_update, ai_policy_update, load_heatmaps_on_level_change,
+    mark_nav_dirty_on_level_change, rebuild_nav_graph,
+};
+use crate::ball::{
+    CurrentPalette, apply_velocity, ball_collisions, ball_follow_holder, ball_gravity,
+    ball_player_collision, ball_rails, ball_spin, ball_state_update, pickup_ball,
+    recover_stuck_ball,
+};
+use crate::config::GameplayConfig;
+use crate::constants::*;
+use crate::debug_logging::DebugLogConfig;
+use crate::events::{
+    EventBus, GameClock, GameEvent, SqliteEventLogger, flush_events_to_sqlite, intern,
+    update_event_bus_time,
+};
+use crate::levels::{LevelDatabase, apply_wind_forces, level_feature_traversal, move_baskets};
+use crate::momentum::{Momentum, update_momentum, update_momentum_aura};
+use crate::overtime::{Overtime, shrink_court, spawn_overtime_banner, update_overtime_banner};
+use crate::palettes::PaletteDatabase;
+use crate::player::{Player, Team, apply_gravity, apply_input, check_collisions};
+use crate::schedule::{AiSet, LoggingSet, MatchSetsPlugin, PhysicsSet, ScoringSet};
+use crate::scoring::{
+    BallReset, BallResetMode, CurrentLevel, Handicap, OutOfBoundsRule, PossessionArrow, RimCamping,
+    Score, ShotClock, apply_handicap, check_out_of_bounds, check_scoring, reset_possession_arrow,
+    shot_clock_update,
+};
+use crate::shooting::{LastShotInfo, throw_ball, update_shot_charge};
+use crate::simulation::{SimConfig, SimControl, setup::sim_setup};
+use crate::steal::{LooseBall, StealContest, StealTracker, steal_cooldown_update};
+use crate::teams::{TeamIdentity, apply_team_colors};
+use crate::tuning::{self, PhysicsTweaks};
+use crate::world::{Basket, Platform};
+
+/// Where the embedded match loads its levels from
+#[derive(Clone)]
+pub enum LevelSource {
+    /// Level file on disk (same format as `config/levels.txt`)
+    File(PathBuf),
+    /// Level file contents (e.g. from `include_str!`)
+    Text(String),
+    /// Already-loaded database
+    Database(LevelDatabase),
+}
+
+impl LevelSource {
+    fn load(&self) -> LevelDatabase {
+        match self {
+            LevelSource::File(path) => LevelDatabase::load_from_file(&path.to_string_lossy()),
+            LevelSource::Text(content) => LevelDatabase::parse(content),
+            LevelSource::Database(db) => db.clone(),
+        }
+    }
+}
+
+/// Where game events go
+#[derive(Clone)]
+pub enum EventLog {
+    /// Leave events on the `EventBus` for the host to export
+    Host,
+    /// Write events to a SQLite database (same schema as the binaries)
+    Sqlite(PathBuf),
+    /// Drop events every frame
+    Discard,
+}
+
+/// Match end conditions
+#[derive(Resource, Clone, Debug)]
+pub struct MatchRules {
+    /// Regulation length in seconds
+    pub time_limit: f32,
+    /// First to this many points wins (0 = no score limit)
+    pub score_limit: u32,
+    /// Play sudden-death overtime when tied at the time limit
+    pub overtime: bool,
+    /// Award dead balls (stuck-ball resets, simultaneous pickups) by alternating arrow
+    pub possession_arrow: bool,
+    /// Per-side head start, shot value and cooldown handicaps
+    pub handicap: Handicap,
+    /// Hot streaks: consecutive goals briefly speed up the scoring team
+    pub momentum: bool,
+    /// Successful steals knock the ball loose for both players to scramble after
+    pub loose_ball: bool,
+    /// Defensive three seconds: camping on your own rim voids its carry-in block
+    pub rim_camping_violation: bool,
+    /// Shot clock: seconds a side may keep the ball without shooting (None = off)
+    pub shot_clock: Option<f32>,
+    /// A ball leaving the court is a turnover; the other side throws in
+    pub out_of_bounds: bool,
+    /// Where play restarts after a goal
+    pub ball_reset: BallResetMode,
+}
+
+impl Default for MatchRules {
+    fn default() -> Self {
+        Self {
+            time_limit: 60.0,
+            score_limit: 0,
+            overtime: false,
+            possession_arrow: false,
+            handicap: Handicap::default(),
+            momentum: false,
+            loose_ball: false,
+            rim_camping_violation: false,
+            shot_clock: None,
+            out_of_bounds: false,
+            ball_reset: BallResetMode::Center,
+        }
+    }
+}
+
+/// Progress of the embedded match (read by the host)
+#[derive(Resource, Default, Debug)]
+pub struct MatchStatus {
+    /// Seconds of match time played
+    pub elapsed: f32,
+    /// Set once an end condition is reached; gameplay stops
+    pub finished: bool,
+}
+
+/// Run condition: the embedded match is still being played
+pub fn match_in_progress(status: Res<MatchStatus>) -> bool {
+    !status.finished
+}
+
+/// Builder options shared by the plugins in the group
+#[derive(Clone)]
+struct EmbedOptions {
+    headless: bool,
+    event_log: EventLog,
+    rules: MatchRules,
+    level_source: LevelSource,
+    level: Option<String>,
+    left_profile: String,
+    right_profile: String,
+    teams: TeamIdentity,
+}
+
+/// Plugin group for hosting a ballgame match in another app
+pub struct BallgamePlugins {
+    options: EmbedOptions,
+}
+
+impl Default for BallgamePlugins {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BallgamePlugins {
+    /// AI vs AI on the first level of `config/levels.txt`, default rules, events left on the bus
+    pub fn new() -> Self {
+        let sim_defaults = SimConfig::default();
+        Self {
+            options: EmbedOptions {
+                headless: false,
+                event_log: EventLog::Host,
+                rules: MatchRules::default(),
+                level_source: LevelSource::File(LEVELS_FILE.into()),
+                level: None,
+                left_profile: sim_defaults.left_profile,
+                right_profile: sim_defaults.right_profile,
+                teams: TeamIdentity::default(),
+            },
+        }
+    }
+
+    /// Skip visual-only systems (team colors, overtime banner)
+    pub fn headless(mut self, headless: bool) -> Self {
+        self.options.headless = headless;
+        self
+    }
+
+    /// Set where game events are logged
+    pub fn event_log(mut self, event_log: EventLog) -> Self {
+        self.options.event_log = event_log;
+        self
+    }
+
+    /// Set the match end conditions
+    pub fn rules(mut self, rules: MatchRules) -> Self {
+        self.options.rules = rules;
+        self
+    }
+
+    /// Set where levels are loaded from
+    pub fn level_source(mut self, source: LevelSource) -> Self {
+        self.options.level_source = source;
+        self
+    }
+
+    /// Play on this level (name or ID; defaults to the first level)
+    pub fn level(mut self, level: &str) -> Self {
+        self.options.level = Some(level.to_string());
+        self
+    }
+
+    /// AI profile names for the left and right players
+    pub fn profiles(mut self, left: &str, right: &str) -> Self {
+        self.options.left_profile = left.to_string();
+        self.options.right_profile = right.to_string();
+        self
+    }
+
+    /// Team names and colors (recorded in MatchStart; colors need the visuals plugin)
+    pub fn teams(mut self, teams: TeamIdentity) -> Self {
+        self.options.teams = teams;
+        self
+    }
+}
+
+impl PluginGroup for BallgamePlugins {
+    fn build(self) -> PluginGroupBuilder {
+        let group = PluginGroupBuilder::start::<Self>()
+            .add(BallgameMatchPlugin {
+                options: self.options.clone(),
+            })
+            .add(BallgameEventLogPlugin {
+                event_log: self.options.event_log.clone(),
+            });
+        if self.options.headless {
+            group
+        } else {
+            group.add(BallgameVisualsPlugin)
+        }
+    }
+}
+
+/// Resources, world setup, AI and physics for one match
+pub struct BallgameMatchPlugin {
+    options: EmbedOptions,
+}
+
+impl Plugin for BallgameMatchPlugin {
+    fn build(&self, app: &mut App) {
+        let options = &self.options;
+        let level_db = options.level_source.load();
+        let requested = options
+            .level
+            .as_deref()
+            .and_then(|l| level_db.get_by_id(l).or_else(|| level_db.get_by_name(l)));
+        if let (Some(level), None) = (&options.level, requested) {
+            warn!(
+                "Embedded match: level '{}' not found, using first level",
+                level
+            );
+        }
+        let level_id = requested
+            .or_else(|| level_db.all().first())
+            .map(|l| l.id.clone())
+            .unwrap_or_default();
+
+        // Colliders read GlobalTransform
+        if !app.is_plugin_added::<bevy::transform::TransformPlugin>() {
+            app.add_plugins(bevy::transform::TransformPlugin);
+        }
+        // Physics is tuned for 60 Hz
+        app.insert_resource(Time::<Fixed>::from_duration(Duration::from_secs_f32(
+            1.0 / 60.0,
+        )));
+
+        // Game resources
+        app.insert_resource(level_db);
+        app.init_resource::<AiProfileDatabase>();
+        app.init_resource::<Score>();
+        app.insert_resource(CurrentLevel(level_id));
+        app.init_resource::<StealContest>();
+        app.init_resource::<StealTracker>();
+        app.init_resource::<NavGraph>();
+        app.init_resource::<AiCapabilities>();
+        app.init_resource::<HeatmapBundle>();
+        app.init_resource::<PhysicsTweaks>();
+        app.add_plugins(GameplayConfig::load("embed"));
+        app.init_resource::<LastShotInfo>();
+        app.insert_resource(CurrentPalette(0));
+        app.init_resource::<PaletteDatabase>();
+        app.init_resource::<DebugLogConfig>();
+        app.init_resource::<Overtime>();
+        app.insert_resource(PossessionArrow::new(options.rules.possession_arrow));
+        app.insert_resource(options.rules.handicap);
+        app.insert_resource(options.teams.clone());
+        app.insert_resource(Momentum::new(options.rules.momentum));
+        app.insert_resource(LooseBall::new(options.rules.loose_ball));
+        app.insert_resource(RimCamping::new(options.rules.rim_camping_violation));
+        app.insert_resource(ShotClock::new(options.rules.shot_clock));
+        app.insert_resource(OutOfBoundsRule::new(options.rules.out_of_bounds));
+        app.insert_resource(BallReset::new(options.rules.ball_reset));
+        if !app.world().contains_resource::<EventBus>() {
+            app.insert_resource(EventBus::new());
+        }
+        app.init_resource::<GameClock>();
+
+        // sim_setup spawns players from the SimControl profile names
+        app.insert_resource(SimControl {
+            config: SimConfig {
+                left_profile: options.left_profile.clone(),
+                right_profile: options.right_profile.clone(),
+                duration_limit: options.rules.time_limit,
+                score_limit: options.rules.score_limit,
+                overtime: options.rules.overtime,
+                possession_arrow: options.rules.possession_arrow,
+                momentum: options.rules.momentum,
+                loose_ball: options.rules.loose_ball,
+                rim_camping_violation: options.rules.rim_camping_violation,
+                shot_clock: options.rules.shot_clock,
+                out_of_bounds: options.rules.out_of_bounds,
+                ball_reset: options.rules.ball_reset,
+                quiet: true,
+                ..default()
+            },
+            should_exit: false,
+            current_seed: 0,
+        });
+        app.insert_resource(options.rules.clone());
+        app.init_resource::<MatchStatus>();
+        app.insert_resource(MatchClock::new(Some(options.rules.time_limit)));
+
+        app.add_systems(
+            Startup,
+            (
+                tuning::load_global_tuning_system,
+                sim_setup,
+                emit_match_start,
+            )
+                .chain(),
+        );
+        // Mark nav graph dirty after first frame so GlobalTransform is populated
+        app.add_systems(PostStartup, |mut nav_graph: ResMut<NavGraph>| {
+            nav_graph.dirty = true;
+        });
+
+        // Host systems added to these sets stop with the match too
+        if !app.is_plugin_added::<MatchSetsPlugin>() {
+            app.add_plugins(MatchSetsPlugin);
+        }
+        app.configure_sets(Update, AiSet.run_if(match_in_progress));
+        app.configure_sets(
+            FixedUpdate,
+            (PhysicsSet, ScoringSet).run_if(match_in_progress),
+        );
+
+        app.add_systems(
+            Update,
+            (
+                update_event_bus_time,
+                mark_nav_dirty_on_level_change,
+                load_heatmaps_on_level_change,
+                rebuild_nav_graph,
+                ai_navigation_update,
+                ai_decision_update,
+                ai_mistake_update,
+                ai_policy_update,
+            )
+                .chain()
+                .in_set(AiSet),
+        );
+
+        app.add_systems(
+            FixedUpdate,
+            (
+                apply_input,
+                apply_gravity,
+                ball_gravity,
+                ball_spin,
+                apply_wind_forces,
+                shrink_court,
+                move_baskets,
+                apply_velocity,
+                check_collisions,
+                ball_collisions,
+                ball_rails,
+                level_feature_traversal,
+                ball_state_update,
+                ball_player_collision,
+                ball_follow_holder,
+                pickup_ball,
+                steal_cooldown_update,
+                update_shot_charge,
+                throw_ball,
+            )
+                .chain()
+                .in_set(PhysicsSet),
+        );
+        app.add_systems(
+            FixedUpdate,
+            (
+                check_scoring,
+                shot_clock_update,
+                check_out_of_bounds,
+                reset_possession_arrow,
+                update_momentum,
+                apply_handicap,
+                recover_stuck_ball,
+                check_match_end,
+            )
+                .chain()
+                .in_set(ScoringSet),
+        );
+    }
+}
+
+/// Emit MatchStart (and open a match row when logging to SQLite)
+fn emit_match_start(
+    control: Res<SimControl>,
+    level_db: Res<LevelDatabase>,
+    current_level: Res<CurrentLevel>,
+    logger: Option<Res<SqliteEventLogger>>,
+    teams: Res<TeamIdentity>,
+    mut event_bus: ResMut<EventBus>,
+) {
+    let config = &control.config;
+    let level = level_db
+        .all()
+        .iter()
+        .position(|l| l.id == current_level.0)
+        .map_or(0, |i| i as u32 + 1);
+    let level_name = level_db
+        .get_by_id(&current_level.0)
+        .map(|l| l.name.clone())
+        .unwrap_or_default();
+
+    if let Some(logger) = logger {
+        logger.start_match(
+            level,
+            &level_name,
+            &config.left_profile,
+            &config.right_profile,
+            control.current_seed,
+        );
+    }
+    event_bus.emit(GameEvent::MatchStart {
+        level,
+        level_name: intern(&level_name),
+        left_profile: intern(&config.left_profile),
+        right_profile: intern(&config.right_profile),
+        seed: control.current_seed,
+        teams: teams.tags(),
+    });
+}
+
+/// Apply MatchRules: time limit (with optional sudden death) and score limit
+#[allow(clippy::too_many_arguments)]
+fn check_match_end(
+    time: Res<Time>,
+    rules: Res<MatchRules>,
+    score: Res<Score>,
+    logger: Option<Res<SqliteEventLogger>>,
+    mut overtime: ResMut<Overtime>,
+    mut status: ResMut<MatchStatus>,
+    mut clock: ResMut<MatchClock>,
+    mut game_clock: ResMut<GameClock>,
+    mut event_bus: ResMut<EventBus>,
+) {
+    // Use minimum dt for headless mode compatibility
+    status.elapsed += time.delta_secs().max(1.0 / 60.0);
+    clock.elapsed = status.elapsed;
+    game_clock.elapsed = status.elapsed;
+
+    let finished = if overtime.active {
+        // Sudden death: first goal wins; give up as a tie after another full regulation
+        overtime.is_decided(&score) || overtime.elapsed >= rules.time_limit
+    } else if status.elapsed >= rules.time_limit {
+        if rules.overtime && score.left == score.right {
+            overtime.start(&score);
+            false
+        } else {
+            true
+        }
+    } else {
+        rules.score_limit > 0
+            && (score.left >= rules.score_limit || score.right >= rules.score_limit)
+    };
+    if !finished {
+        return;
+    }
+
+    status.finished = true;
+    overtime.end();
+    info!(
+        "Embedded match over: {}-{} after {:.0}s",
+        score.left, score.right, status.elapsed
+    );
+    event_bus.emit(GameEvent::MatchEnd {
+        score_left: score.left,
+        score_right: score.right,
+        duration: status.elapsed,
+    });
+    if let Some(logger) = logger {
+        // Flush the final events before closing the match row
+        logger.log_events(&event_bus.export_events());
+        logger.end_match(score.left, score.right, status.elapsed);
+    }
+}
+
+/// Routes EventBus events to the chosen backend
+pub struct BallgameEventLogPlugin {
+    event_log: EventLog,
+}
+
+impl Plugin for BallgameEventLogPlugin {
+    fn build(&self, app: &mut App) {
+        match &self.event_log {
+            EventLog::Host => {}
+            EventLog::Sqlite(path) => {
+                match SqliteEventLogger::new(path, "embedded") {
+                    Ok(logger) => {
+                        app.insert_resource(logger);
+                    }
+                    Err(e) => {
+                        warn!(
+                            "Embedded match: failed to open {}: {}, events not logged",
+                            path.display(),
+                            e
+                        );
+                        app.insert_resource(SqliteEventLogger::disabled());
+                    }
+                }
+                app.add_systems(PostUpdate, flush_events_to_sqlite.in_set(LoggingSet));
+            }
+            EventLog::Discard => {
+                app.add_systems(
+                    PostUpdate,
+                    (|mut event_bus: ResMut<EventBus>| {
+                        let _ = event_bus.export_events();
+                    })
+                    .in_set(LoggingSet),
+                );
+            }
+        }
+    }
+}
+
+/// Team colors, the overtime banner and hot-streak auras (needs rendering)
+pub struct BallgameVisualsPlugin;
+
+impl Plugin for BallgameVisualsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Startup,
+            (tint_match_sprites, |mut commands: Commands| {
+                spawn_overtime_banner(&mut commands)
+            })
+                .after(sim_setup),
+        );
+        app.add_systems(
+            Update,
+            (
+                update_overtime_banner,
+                update_momentum_aura,
+                apply_team_colors,
+            ),
+        );
+    }
+}
+
+/// Color players, baskets and platforms from the first palette (sim_setup spawns them untinted)
+fn tint_match_sprites(
+    palette_db: Res<PaletteDatabase>,
+    mut players: Query<(&mut Sprite, &Team), (With<Player>, Without<Basket>)>,
+    mut baskets: Query<(&mut Sprite, &Basket), Without<Player>>,
+    mut platforms: Query<&mut Sprite, (With<Platform>, Without<Player>, Without<Basket>)>,
+) {
+    let Some(palette) = palette_db.get(0) else {
+        return;
+    };
+    for (mut sprite, team) in &mut players {
+        sprite.color = match team {
+            Team::Left => palette.left,
+            Team::Right => palette.right,
+        };
+    }
+    for (mut sprite, basket) in &mut baskets {
+        sprite.color = match basket {
+            Basket::Left => palette.left,
+            Basket::Right => palette.right,
+        };
+    }
+    for mut sprite in &mut platforms {
+        sprite.color = palette.platforms;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_headless_group_registers_match_resources() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins).add_plugins(
+            BallgamePlugins::new()
+                .headless(true)
+                .event_log(EventLog::Discard)
+                .rules(MatchRules {
+                    time_limit: 1.0,
+                    ..default()
+                }),
+        );
+        assert!(app.world().contains_resource::<MatchStatus>());
+        assert!(app.world().contains_resource::<SimControl>());
+    }
+}
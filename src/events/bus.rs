@@ -9,12 +9,13 @@
 
 use bevy::prelude::*;
 
+use super::clock::GameClock;
 use super::types::GameEvent;
 
 /// Timestamped event for the event bus
 #[derive(Debug, Clone)]
 pub struct BusEvent {
-    /// Time in milliseconds since match start
+    /// Game-clock time in milliseconds since match start
     pub time_ms: u32,
     /// The event data
     pub event: GameEvent,
@@ -32,7 +33,7 @@ pub struct EventBus {
     /// Events that have been consumed (for logging)
     processed: Vec<BusEvent>,
 
-    /// Current elapsed time in milliseconds (for timestamping)
+    /// Current game-clock time in milliseconds (for timestamping)
     elapsed_ms: u32,
 
     /// Whether the bus is enabled (for testing/simulation)
@@ -90,11 +91,11 @@ impl EventBus {
         &self.pending
     }
 
-    /// Drain pending events, moving them to processed
+    /// Drain pending events, moving them to processed.
+    /// The pending queue keeps its capacity, and an empty drain doesn't allocate.
     pub fn drain(&mut self) -> Vec<BusEvent> {
-        let events = std::mem::take(&mut self.pending);
-        self.processed.extend(events.clone());
-        events
+        self.processed.extend(self.pending.iter().cloned());
+        self.pending.drain(..).collect()
     }
 
     /// Get all processed events (for logging)
@@ -134,15 +135,18 @@ impl EventBus {
 
     /// Export pending events as (time_ms, GameEvent) tuples for EventBuffer
     pub fn export_events(&mut self) -> Vec<(u32, super::types::GameEvent)> {
-        let events = std::mem::take(&mut self.pending);
-        self.processed.extend(events.clone());
-        events.into_iter().map(|e| (e.time_ms, e.event)).collect()
+        self.processed.extend(self.pending.iter().cloned());
+        self.pending
+            .drain(..)
+            .map(|e| (e.time_ms, e.event))
+            .collect()
     }
 }
 
-/// System to update the event bus time each frame
-pub fn update_event_bus_time(mut bus: ResMut<EventBus>, time: Res<Time>) {
-    bus.update_time(time.elapsed_secs());
+/// System to update the event bus time each frame (from the game clock, so
+/// countdowns and pauses don't show up in timestamps)
+pub fn update_event_bus_time(mut bus: ResMut<EventBus>, clock: Res<GameClock>) {
+    bus.update_time(clock.elapsed);
 }
 
 /// Resource to track previous level for change detection
@@ -161,7 +165,7 @@ pub fn emit_level_change_events(
     let level_id = &current_level.0;
     if level_id != &tracker.prev_level_id && !tracker.prev_level_id.is_empty() {
         bus.emit(super::types::GameEvent::LevelChange {
-            level_id: level_id.clone(),
+            level_id: super::intern(level_id),
         });
     }
     tracker.prev_level_id = level_id.clone();
@@ -0,0 +1,141 @@
+//! Game clock - the canonical timeline for event timestamps
+//!
+//! `GameClock` only advances while the match is actually being played: not
+//! during the 3-2-1 countdown, a goal replay, the training pause, or while
+//! time dilation has gameplay frozen (it reads `Time<Virtual>`, so slow-mo
+//! slows it too). The EventBus, the clip recorder and SQLite logging all stamp
+//! events with it, so `time_ms` means the same thing in every mode.
+//!
+//! Matches logged before the game clock existed carry wall-clock time in some
+//! modes. The `matches.time_base` column says which clock a match used:
+//! `game` for new rows; the schema migration backfills older rows with `wall`
+//! (training and embedded sessions, whose timestamps included countdowns and
+//! pauses) or `game` (headless simulation, which always counted fixed ticks).
+
+use bevy::prelude::*;
+use rusqlite::Connection;
+
+/// Gameplay time since the match started (excludes countdowns and pauses)
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct GameClock {
+    /// Seconds of gameplay
+    pub elapsed: f32,
+}
+
+impl GameClock {
+    pub fn advance(&mut self, delta_secs: f32) {
+        self.elapsed += delta_secs;
+    }
+
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+    }
+
+    pub fn elapsed_ms(&self) -> u32 {
+        (self.elapsed * 1000.0) as u32
+    }
+}
+
+/// Advance the game clock by this frame's (virtual) delta.
+/// Callers gate it with the same run conditions as gameplay.
+pub fn tick_game_clock(mut clock: ResMut<GameClock>, time: Res<Time>) {
+    clock.advance(time.delta_secs());
+}
+
+/// Which clock a match's `time_ms` values were taken from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TimeBase {
+    /// Gameplay time (`GameClock`)
+    #[default]
+    Game,
+    /// Legacy wall-clock time: countdowns and pauses are inside the timeline
+    Wall,
+}
+
+impl TimeBase {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Game => "game",
+            Self::Wall => "wall",
+        }
+    }
+
+    /// Parse a `matches.time_base` value (unknown or missing means game time)
+    pub fn from_column(value: Option<&str>) -> Self {
+        match value {
+            Some("wall") => Self::Wall,
+            _ => Self::Game,
+        }
+    }
+}
+
+/// Add `matches.time_base` and backfill rows logged before it existed.
+/// Safe to call on every open: the backfill only runs when the column is new.
+pub fn migrate_time_base(conn: &Connection) {
+    if conn
+        .execute("ALTER TABLE matches ADD COLUMN time_base TEXT", [])
+        .is_err()
+    {
+        return;
+    }
+    let result = conn.execute(
+        "UPDATE matches SET time_base = CASE WHEN session_id IN \
+         (SELECT id FROM sessions WHERE session_type IN ('training', 'embedded')) \
+         THEN 'wall' ELSE 'game' END WHERE time_base IS NULL",
+        [],
+    );
+    if let Err(e) = result {
+        warn!("Failed to backfill matches.time_base: {}", e);
+    }
+}
+
+/// Time base a match was logged with (game time if the match is unknown)
+pub fn match_time_base(conn: &Connection, match_id: i64) -> TimeBase {
+    let value: Option<String> = conn
+        .query_row(
+            "SELECT time_base FROM matches WHERE id = ?1",
+            [match_id],
+            |row| row.get(0),
+        )
+        .ok()
+        .flatten();
+    TimeBase::from_column(value.as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migration_flags_legacy_wall_clock_matches() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE sessions (id TEXT PRIMARY KEY, session_type TEXT NOT NULL);
+             CREATE TABLE matches (id INTEGER PRIMARY KEY, session_id TEXT);
+             INSERT INTO sessions VALUES ('t', 'training'), ('s', 'simulation');
+             INSERT INTO matches VALUES (1, 't'), (2, 's');",
+        )
+        .unwrap();
+
+        migrate_time_base(&conn);
+        assert_eq!(match_time_base(&conn, 1), TimeBase::Wall);
+        assert_eq!(match_time_base(&conn, 2), TimeBase::Game);
+
+        // New rows say so themselves; a second migration leaves them alone
+        conn.execute(
+            "INSERT INTO matches (id, session_id, time_base) VALUES (3, 't', 'game')",
+            [],
+        )
+        .unwrap();
+        migrate_time_base(&conn);
+        assert_eq!(match_time_base(&conn, 3), TimeBase::Game);
+        assert_eq!(match_time_base(&conn, 99), TimeBase::Game);
+
+        let mut clock = GameClock::default();
+        clock.advance(1.25);
+        clock.advance(0.25);
+        assert_eq!(clock.elapsed_ms(), 1500);
+        clock.reset();
+        assert_eq!(clock.elapsed_ms(), 0);
+    }
+}
@@ -58,7 +58,7 @@ pub fn push_debug_samples(
             Option<&AiNavState>,
             Option<&crate::player::HumanControlled>,
         ),
-        With<Player>,
+        (With<Player>, Without<crate::player::Teammate>),
     >,
 ) {
     for (
@@ -5,7 +5,7 @@
 
 use bevy::prelude::*;
 
-use super::{EventBuffer, GameEvent, PlayerId};
+use super::{EventBuffer, GameEvent, PlayerId, intern};
 use crate::ai::evaluate_shot_quality;
 use crate::{
     AiState, BallState, Basket, ChargingShot, HoldingBall, InputState, LastShotInfo, Score,
@@ -17,12 +17,15 @@ use crate::{
 pub struct EmitterConfig {
     /// Track AI goals for both players (true) or just right player (false)
     pub track_both_ai_goals: bool,
+    /// Interval between Tick (and per-player Input) events
+    pub tick_interval_ms: u32,
 }
 
 impl Default for EmitterConfig {
     fn default() -> Self {
         Self {
             track_both_ai_goals: true,
+            tick_interval_ms: crate::debug_logging::DEFAULT_TICK_INTERVAL_MS,
         }
     }
 }
@@ -41,8 +44,8 @@ pub struct EventEmitterState {
     pub prev_ball_holder: Option<Entity>,
     /// Whether each player was charging last frame [left, right]
     pub prev_charging: [bool; 2],
-    /// Previous AI goal strings [left, right]
-    pub prev_ai_goals: [Option<String>; 2],
+    /// Previous AI goal names [left, right]
+    pub prev_ai_goals: [Option<&'static str>; 2],
     /// Previous steal cooldowns [left, right]
     pub prev_steal_cooldowns: [f32; 2],
     /// Time of last tick event
@@ -99,7 +102,7 @@ pub struct PlayerSnapshot {
     pub velocity: (f32, f32),
     pub charge_time: f32,
     pub target_basket: Basket,
-    pub ai_goal: String,
+    pub ai_goal: &'static str,
     pub steal_cooldown: f32,
     pub is_holding_ball: bool,
     /// Input state for replay/analysis
@@ -122,6 +125,22 @@ pub struct BasketSnapshot {
     pub position: (f32, f32),
 }
 
+/// Snapshot vectors kept between frames so per-frame emission reuses their
+/// capacity instead of allocating. Hold one in a `Local` and refill it each frame.
+#[derive(Default)]
+pub struct SnapshotPool {
+    pub players: Vec<PlayerSnapshot>,
+    pub baskets: Vec<BasketSnapshot>,
+}
+
+impl SnapshotPool {
+    /// Empty both vectors, keeping their capacity
+    pub fn clear(&mut self) {
+        self.players.clear();
+        self.baskets.clear();
+    }
+}
+
 /// Emit all game events by comparing current state to previous state
 ///
 /// This is the main entry point for event emission. Call this once per frame
@@ -165,7 +184,7 @@ fn emit_tick_events(
     players: &[PlayerSnapshot],
     ball: Option<&BallSnapshot>,
 ) {
-    if elapsed - state.last_tick_time < 0.05 {
+    if elapsed - state.last_tick_time < state.config.tick_interval_ms as f32 / 1000.0 {
         return;
     }
 
@@ -198,7 +217,8 @@ fn emit_tick_events(
             let state_char = match &b.state {
                 BallState::Free => 'F',
                 BallState::Held(_) => 'H',
-                BallState::InFlight { .. } => 'I',
+                // Passes log as in flight (tick format has no pass state)
+                BallState::InFlight { .. } | BallState::Passed { .. } => 'I',
             };
             (b.position, b.velocity, state_char)
         })
@@ -284,14 +304,13 @@ fn emit_ai_goal_events(
             continue;
         }
 
-        let goal_str = &player.ai_goal;
-        if state.prev_ai_goals[idx].as_ref() != Some(goal_str) {
-            state.prev_ai_goals[idx] = Some(goal_str.clone());
+        if state.prev_ai_goals[idx] != Some(player.ai_goal) {
+            state.prev_ai_goals[idx] = Some(player.ai_goal);
             buffer.log(
                 elapsed,
                 GameEvent::AiGoal {
                     player: player_id,
-                    goal: goal_str.clone(),
+                    goal: intern(player.ai_goal),
                 },
             );
         }
@@ -380,7 +399,7 @@ fn emit_possession_events(
                 .map(|b| Vec2::new(b.position.0, b.position.1));
             let quality = target_pos
                 .map(|pos| {
-                    evaluate_shot_quality(Vec2::new(player.position.0, player.position.1), pos)
+                    evaluate_shot_quality(Vec2::new(player.position.0, player.position.1), pos, 0.0)
                 })
                 .unwrap_or(0.0);
             buffer.log(
@@ -430,6 +449,16 @@ fn emit_ball_state_events(
                             power: *power,
                         },
                     );
+                    if let Some(info) = shot_info {
+                        buffer.log(
+                            elapsed,
+                            GameEvent::ChargeCalibration {
+                                player: pid,
+                                ideal: info.ideal_charge_pct,
+                                delta: info.charge_pct - info.ideal_charge_pct,
+                            },
+                        );
+                    }
                 }
                 state.prev_ball_holder = None;
             }
@@ -450,6 +479,10 @@ fn emit_ball_state_events(
                 state.prev_ball_holder = None;
             }
         }
+        BallState::Passed { .. } => {
+            // Logged as Pass/PassCaught by the pass systems, not as a drop
+            state.prev_ball_holder = None;
+        }
         BallState::Held(_) => {
             // Ball is held - already tracked in possession events
         }
@@ -478,7 +511,7 @@ pub fn snapshot_player(
         velocity: (velocity.0.x, velocity.0.y),
         charge_time: charging.charge_time,
         target_basket: target.0,
-        ai_goal: format!("{:?}", ai_state.current_goal),
+        ai_goal: ai_state.current_goal.name(),
         steal_cooldown: steal_cooldown.0,
         is_holding_ball: holding.is_some(),
         input_move_x: input_state.move_x,
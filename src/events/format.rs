@@ -8,6 +8,7 @@
 //! Examples:
 //! ```text
 //! T:00000|MS|1|Open Floor|Balanced|Balanced|12345678
+//! T:00000|MS|1|Open Floor|Player|Balanced|12345678|Reds|Blues|#d03030|#3050d0
 //! T:00150|PU|L
 //! T:00320|SS|L|-200.5,-418.2|0.47
 //! T:00850|SR|L|0.65|62.3|720.5
@@ -21,7 +22,8 @@
 //!          ^frame|left_pos|left_vel|right_pos|right_vel|ball_pos|ball_vel|state
 //! ```
 
-use super::types::{ControllerSource, GameConfig, GameEvent, PlayerId};
+use super::intern::intern;
+use super::types::{ControllerSource, GameConfig, GameEvent, PlayerId, TeamTags};
 
 /// Format a float with fixed precision (1 decimal)
 fn fmt_f1(v: f32) -> String {
@@ -55,11 +57,20 @@ pub fn serialize_event(time_ms: u32, event: &GameEvent) -> String {
             left_profile,
             right_profile,
             seed,
+            teams,
         } => {
-            format!(
+            let mut data = format!(
                 "{}|{}|{}|{}|{}",
                 level, level_name, left_profile, right_profile, seed
-            )
+            );
+            // Team fields only when set, so unnamed matches keep the short form
+            if !teams.is_empty() {
+                data.push_str(&format!(
+                    "|{}|{}|{}|{}",
+                    teams.left_name, teams.right_name, teams.left_color, teams.right_color
+                ));
+            }
+            data
         }
         GameEvent::MatchEnd {
             score_left,
@@ -75,8 +86,22 @@ pub fn serialize_event(time_ms: u32, event: &GameEvent) -> String {
         } => {
             format!("{}|{}|{}", player, score_left, score_right)
         }
+        GameEvent::RimContest { carrier, outcome } => format!("{}|{}", carrier, outcome),
+        GameEvent::RimCamping { player, violation } => {
+            format!("{}|{}", player, if *violation { 1 } else { 0 })
+        }
         GameEvent::Pickup { player } => player.to_string(),
         GameEvent::Drop { player } => player.to_string(),
+        GameEvent::ContestedPickup { winner, loser } => format!("{}|{}", winner, loser),
+        GameEvent::PossessionAwarded { player, reason } => format!("{}|{}", player, reason),
+        GameEvent::ShotClockViolation { player, limit } => format!("{}|{}", player, fmt_f1(*limit)),
+        GameEvent::OutOfBounds { player, pos } => format!("{}|{}", player, fmt_pos(*pos)),
+        GameEvent::Scoop { player, ball_speed } => format!("{}|{}", player, fmt_f1(*ball_speed)),
+        GameEvent::Pass { player, distance } => format!("{}|{}", player, fmt_f1(*distance)),
+        GameEvent::PassCaught {
+            player,
+            intercepted,
+        } => format!("{}|{}", player, if *intercepted { 1 } else { 0 }),
         GameEvent::ShotStart {
             player,
             pos,
@@ -92,19 +117,40 @@ pub fn serialize_event(time_ms: u32, event: &GameEvent) -> String {
         } => {
             format!("{}|{:.2}|{:.1}|{:.1}", player, charge, angle, power)
         }
+        GameEvent::ChargeCalibration {
+            player,
+            ideal,
+            delta,
+        } => format!("{}|{:.3}|{:.3}", player, ideal, delta),
         GameEvent::StealAttempt { attacker } => attacker.to_string(),
         GameEvent::StealSuccess { attacker } => attacker.to_string(),
         GameEvent::StealFail { attacker } => attacker.to_string(),
         GameEvent::StealOutOfRange { attacker } => attacker.to_string(),
+        GameEvent::StealDenied { attacker, reason } => format!("{}|{}", attacker, reason),
+        GameEvent::LooseBall { attacker, pop } => format!("{}|{}", attacker, fmt_pos(*pop)),
         GameEvent::Jump { player } => player.to_string(),
         GameEvent::Land { player } => player.to_string(),
         GameEvent::AiGoal { player, goal } => {
             format!("{}|{}", player, goal)
         }
+        GameEvent::AiRisk { player, mode, diff } => format!("{}|{}|{}", player, mode, diff),
+        GameEvent::DefenseAssignment { player, reason } => format!("{}|{}", player, reason),
+        GameEvent::AiMistake {
+            player,
+            kind,
+            delay_ms,
+        } => format!("{}|{}|{}", player, kind, delay_ms),
         GameEvent::NavStart { player, target } => {
             format!("{}|{}", player, fmt_pos(*target))
         }
         GameEvent::NavComplete { player } => player.to_string(),
+        GameEvent::NavEdge {
+            player,
+            from,
+            to,
+            edge_type,
+            outcome,
+        } => format!("{}|{}|{}|{}|{}", player, from, to, edge_type, outcome),
         GameEvent::Input {
             player,
             move_x,
@@ -189,7 +235,26 @@ pub fn serialize_event(time_ms: u32, event: &GameEvent) -> String {
         GameEvent::ResetAiState { player } => player.to_string(),
         GameEvent::ResetScores => String::new(),
         GameEvent::ResetBall => String::new(),
-        GameEvent::LevelChange { level_id } => level_id.clone(),
+        GameEvent::LevelChange { level_id } => level_id.to_string(),
+        GameEvent::BallStuck { pos, reason } => format!("{}|{}", fmt_pos(*pos), reason),
+        GameEvent::BallRailHit { pos, rail } => format!("{}|{}", fmt_pos(*pos), rail),
+        GameEvent::CoachAdjustment {
+            player,
+            param,
+            from,
+            to,
+            reason,
+        } => format!("{}|{}|{:.3}|{:.3}|{}", player, param, from, to, reason),
+        GameEvent::Handicap {
+            player,
+            head_start,
+            shot_value,
+            cooldown,
+        } => format!(
+            "{}|{}|{:.2}|{:.2}",
+            player, head_start, shot_value, cooldown
+        ),
+        GameEvent::Momentum { player, streak } => format!("{}|{}", player, streak),
     };
 
     format!("{}|{}|{}", ts, code, data)
@@ -222,10 +287,19 @@ pub fn parse_event(line: &str) -> Option<(u32, GameEvent)> {
         }
         "MS" if data.len() >= 5 => GameEvent::MatchStart {
             level: data[0].parse().ok()?,
-            level_name: data[1].to_string(),
-            left_profile: data[2].to_string(),
-            right_profile: data[3].to_string(),
+            level_name: intern(data[1]),
+            left_profile: intern(data[2]),
+            right_profile: intern(data[3]),
             seed: data[4].parse().ok()?,
+            teams: match data.get(5..9) {
+                Some(tags) => TeamTags {
+                    left_name: intern(tags[0]),
+                    right_name: intern(tags[1]),
+                    left_color: intern(tags[2]),
+                    right_color: intern(tags[3]),
+                },
+                None => TeamTags::default(),
+            },
         },
         "ME" if data.len() >= 3 => GameEvent::MatchEnd {
             score_left: data[0].parse().ok()?,
@@ -237,12 +311,48 @@ pub fn parse_event(line: &str) -> Option<(u32, GameEvent)> {
             score_left: data[1].parse().ok()?,
             score_right: data[2].parse().ok()?,
         },
+        "RH" if data.len() >= 2 => GameEvent::RimContest {
+            carrier: parse_player(data[0])?,
+            outcome: intern(data[1]),
+        },
+        "RC" if data.len() >= 2 => GameEvent::RimCamping {
+            player: parse_player(data[0])?,
+            violation: data[1] == "1",
+        },
         "PU" if !data.is_empty() => GameEvent::Pickup {
             player: parse_player(data[0])?,
         },
         "DR" if !data.is_empty() => GameEvent::Drop {
             player: parse_player(data[0])?,
         },
+        "CP" if data.len() >= 2 => GameEvent::ContestedPickup {
+            winner: parse_player(data[0])?,
+            loser: parse_player(data[1])?,
+        },
+        "PA" if data.len() >= 2 => GameEvent::PossessionAwarded {
+            player: parse_player(data[0])?,
+            reason: intern(data[1]),
+        },
+        "SV" if data.len() >= 2 => GameEvent::ShotClockViolation {
+            player: parse_player(data[0])?,
+            limit: data[1].parse().ok()?,
+        },
+        "OB" if data.len() >= 2 => GameEvent::OutOfBounds {
+            player: parse_player(data[0])?,
+            pos: parse_pos(data[1])?,
+        },
+        "SP" if data.len() >= 2 => GameEvent::Scoop {
+            player: parse_player(data[0])?,
+            ball_speed: data[1].parse().ok()?,
+        },
+        "PS" if data.len() >= 2 => GameEvent::Pass {
+            player: parse_player(data[0])?,
+            distance: data[1].parse().ok()?,
+        },
+        "PK" if data.len() >= 2 => GameEvent::PassCaught {
+            player: parse_player(data[0])?,
+            intercepted: data[1] == "1",
+        },
         "SS" if data.len() >= 3 => GameEvent::ShotStart {
             player: parse_player(data[0])?,
             pos: parse_pos(data[1])?,
@@ -254,6 +364,11 @@ pub fn parse_event(line: &str) -> Option<(u32, GameEvent)> {
             angle: data[2].parse().ok()?,
             power: data[3].parse().ok()?,
         },
+        "CC" if data.len() >= 3 => GameEvent::ChargeCalibration {
+            player: parse_player(data[0])?,
+            ideal: data[1].parse().ok()?,
+            delta: data[2].parse().ok()?,
+        },
         "SA" if !data.is_empty() => GameEvent::StealAttempt {
             attacker: parse_player(data[0])?,
         },
@@ -266,6 +381,14 @@ pub fn parse_event(line: &str) -> Option<(u32, GameEvent)> {
         "SO" if !data.is_empty() => GameEvent::StealOutOfRange {
             attacker: parse_player(data[0])?,
         },
+        "SD" if data.len() >= 2 => GameEvent::StealDenied {
+            attacker: parse_player(data[0])?,
+            reason: intern(data[1]),
+        },
+        "SL" if data.len() >= 2 => GameEvent::LooseBall {
+            attacker: parse_player(data[0])?,
+            pop: parse_pos(data[1])?,
+        },
         "J" if !data.is_empty() => GameEvent::Jump {
             player: parse_player(data[0])?,
         },
@@ -274,7 +397,21 @@ pub fn parse_event(line: &str) -> Option<(u32, GameEvent)> {
         },
         "AG" if data.len() >= 2 => GameEvent::AiGoal {
             player: parse_player(data[0])?,
-            goal: data[1].to_string(),
+            goal: intern(data[1]),
+        },
+        "DA" if data.len() >= 2 => GameEvent::DefenseAssignment {
+            player: parse_player(data[0])?,
+            reason: intern(data[1]),
+        },
+        "RM" if data.len() >= 3 => GameEvent::AiRisk {
+            player: parse_player(data[0])?,
+            mode: intern(data[1]),
+            diff: data[2].parse().ok()?,
+        },
+        "MK" if data.len() >= 3 => GameEvent::AiMistake {
+            player: parse_player(data[0])?,
+            kind: intern(data[1]),
+            delay_ms: data[2].parse().ok()?,
         },
         "NS" if data.len() >= 2 => GameEvent::NavStart {
             player: parse_player(data[0])?,
@@ -283,6 +420,13 @@ pub fn parse_event(line: &str) -> Option<(u32, GameEvent)> {
         "NC" if !data.is_empty() => GameEvent::NavComplete {
             player: parse_player(data[0])?,
         },
+        "NE" if data.len() >= 5 => GameEvent::NavEdge {
+            player: parse_player(data[0])?,
+            from: data[1].parse().ok()?,
+            to: data[2].parse().ok()?,
+            edge_type: intern(data[3]),
+            outcome: intern(data[4]),
+        },
         "I" if data.len() >= 3 => GameEvent::Input {
             player: parse_player(data[0])?,
             move_x: data[1].parse().ok()?,
@@ -328,7 +472,32 @@ pub fn parse_event(line: &str) -> Option<(u32, GameEvent)> {
         "RS" => GameEvent::ResetScores,
         "RB" => GameEvent::ResetBall,
         "LC" if !data.is_empty() => GameEvent::LevelChange {
-            level_id: data[0].to_string(),
+            level_id: intern(data[0]),
+        },
+        "BS" if data.len() >= 2 => GameEvent::BallStuck {
+            pos: parse_pos(data[0])?,
+            reason: intern(data[1]),
+        },
+        "BH" if data.len() >= 2 => GameEvent::BallRailHit {
+            pos: parse_pos(data[0])?,
+            rail: intern(data[1]),
+        },
+        "CA" if data.len() >= 5 => GameEvent::CoachAdjustment {
+            player: parse_player(data[0])?,
+            param: intern(data[1]),
+            from: data[2].parse().ok()?,
+            to: data[3].parse().ok()?,
+            reason: data[4].into(),
+        },
+        "HC" if data.len() >= 4 => GameEvent::Handicap {
+            player: parse_player(data[0])?,
+            head_start: data[1].parse().ok()?,
+            shot_value: data[2].parse().ok()?,
+            cooldown: data[3].parse().ok()?,
+        },
+        "MO" if data.len() >= 2 => GameEvent::Momentum {
+            player: parse_player(data[0])?,
+            streak: data[1].parse().ok()?,
         },
         _ => return None,
     };
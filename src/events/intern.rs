@@ -0,0 +1,44 @@
+//! Interned strings for event payloads
+//!
+//! Event text (AI goal names, reasons, level ids, profile names) comes from a
+//! small fixed vocabulary but used to be re-allocated as a fresh `String` every
+//! time an event was built or cloned into the bus's processed list. `EventStr`
+//! is a shared `Arc<str>`: cloning it is a refcount bump, and `intern` hands
+//! back the same allocation for text it has seen before.
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Shared, cheaply cloned event text
+pub type EventStr = Arc<str>;
+
+fn pool() -> &'static Mutex<HashSet<EventStr>> {
+    static POOL: OnceLock<Mutex<HashSet<EventStr>>> = OnceLock::new();
+    POOL.get_or_init(Default::default)
+}
+
+/// Shared copy of `text` (allocates only the first time a string is seen).
+/// Meant for the bounded vocabulary of event labels - don't intern free-form text.
+pub fn intern(text: &str) -> EventStr {
+    let mut pool = pool().lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(existing) = pool.get(text) {
+        return existing.clone();
+    }
+    let interned: EventStr = Arc::from(text);
+    pool.insert(interned.clone());
+    interned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern_shares_allocation() {
+        let a = intern("ChaseBall");
+        let b = intern(&String::from("ChaseBall"));
+        assert!(Arc::ptr_eq(&a, &b));
+        assert_eq!(&*a, "ChaseBall");
+        assert!(!Arc::ptr_eq(&a, &intern("AttackWithBall")));
+    }
+}
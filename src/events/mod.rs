@@ -17,23 +17,29 @@
 
 mod buffer;
 mod bus;
+mod clock;
 mod debug;
 mod emitter;
 mod format;
+mod intern;
 mod sqlite_logger;
 mod types;
+mod webhook;
 
 pub use buffer::EventBuffer;
 pub use bus::{
     BusEvent, EventBus, LevelChangeTracker, emit_level_change_events, update_event_bus_time,
 };
+pub use clock::{GameClock, TimeBase, match_time_base, migrate_time_base, tick_game_clock};
 pub use debug::{
     DEBUG_TICK_MS, DebugSample, DebugSampleBuffer, push_debug_samples, tick_frame_from_time,
 };
 pub use emitter::{
-    BallSnapshot, BasketSnapshot, EmitterConfig, EventEmitterState, PlayerSnapshot,
+    BallSnapshot, BasketSnapshot, EmitterConfig, EventEmitterState, PlayerSnapshot, SnapshotPool,
     emit_game_events, snapshot_ball, snapshot_player,
 };
 pub use format::{parse_event, serialize_event};
+pub use intern::{EventStr, intern};
 pub use sqlite_logger::{SqliteEventLogger, flush_debug_samples_to_sqlite, flush_events_to_sqlite};
-pub use types::{ControllerSource, GameConfig, GameEvent, PlayerId};
+pub use types::{ControllerSource, EventCategory, GameConfig, GameEvent, PlayerId, TeamTags};
+pub use webhook::{DEFAULT_WEBHOOK_EVENTS, WebhookBridge, WebhookConfig, parse_http_url};
@@ -8,9 +8,10 @@ use rusqlite::{Connection, params};
 use std::path::Path;
 use std::sync::Mutex;
 
+use super::clock::{TimeBase, migrate_time_base};
 use super::debug::{DEBUG_TICK_MS, DebugSample, DebugSampleBuffer};
 use super::format::serialize_event;
-use super::types::GameEvent;
+use super::types::{EventCategory, GameEvent};
 use crate::debug_logging::DebugLogConfig;
 
 /// Resource for logging events to SQLite
@@ -29,6 +30,8 @@ pub struct SqliteEventLogger {
     current_point_index: Mutex<u32>,
     /// Whether logging is enabled
     enabled: bool,
+    /// Event categories written (match and scoring are always kept)
+    categories: Vec<EventCategory>,
 }
 
 impl SqliteEventLogger {
@@ -60,9 +63,16 @@ impl SqliteEventLogger {
             current_point_id: Mutex::new(None),
             current_point_index: Mutex::new(0),
             enabled: true,
+            categories: EventCategory::ALL.to_vec(),
         })
     }
 
+    /// Only persist events in these categories (see `DebugLogConfig::event_categories`)
+    pub fn with_event_categories(mut self, categories: Vec<EventCategory>) -> Self {
+        self.categories = categories;
+        self
+    }
+
     /// Create a disabled logger (no-op, for testing)
     pub fn disabled() -> Self {
         // Use in-memory database that won't be accessed
@@ -74,6 +84,7 @@ impl SqliteEventLogger {
             current_point_id: Mutex::new(None),
             current_point_index: Mutex::new(0),
             enabled: false,
+            categories: Vec::new(),
         }
     }
 
@@ -104,8 +115,8 @@ impl SqliteEventLogger {
         let result = conn.execute(
             r#"INSERT INTO matches
                (session_id, display_name, seed, level, level_name, left_profile, right_profile,
-                score_left, score_right, duration_secs, winner)
-               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 0, 0, 0.0, '')"#,
+                score_left, score_right, duration_secs, winner, time_base)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 0, 0, 0.0, '', ?8)"#,
             params![
                 self.session_id,
                 display_name,
@@ -114,6 +125,7 @@ impl SqliteEventLogger {
                 level_name,
                 left_profile,
                 right_profile,
+                TimeBase::Game.as_str(),
             ],
         );
 
@@ -140,7 +152,7 @@ impl SqliteEventLogger {
 
     /// Log a single event
     pub fn log_event(&self, time_ms: u32, event: &GameEvent) {
-        if !self.enabled {
+        if !self.enabled || !event.persisted_with(&self.categories) {
             return;
         }
 
@@ -207,6 +219,9 @@ impl SqliteEventLogger {
         }
 
         for (time_ms, event) in events {
+            if !event.persisted_with(&self.categories) {
+                continue;
+            }
             let data = serialize_event(*time_ms, event);
             let event_type = event.type_code();
             let tick_frame = (*time_ms / DEBUG_TICK_MS) as i64;
@@ -353,6 +368,35 @@ impl SqliteEventLogger {
         }
     }
 
+    /// Drop everything written for the current match and stop logging it.
+    ///
+    /// Later `log_event`/`end_match` calls are no-ops until the next `start_match`.
+    /// Returns the discarded match ID.
+    pub fn discard_current_match(&self) -> Option<i64> {
+        let match_id = self.current_match_id.lock().ok()?.take()?;
+        if let Ok(mut guard) = self.current_point_id.lock() {
+            *guard = None;
+        }
+        if let Ok(mut guard) = self.current_point_index.lock() {
+            *guard = 0;
+        }
+
+        let conn = self.conn.lock().ok()?;
+        for table in ["events", "debug_events", "player_stats", "points"] {
+            if let Err(e) = conn.execute(
+                &format!("DELETE FROM {} WHERE match_id = ?1", table),
+                params![match_id],
+            ) {
+                warn!("Failed to discard {} for match {}: {}", table, match_id, e);
+            }
+        }
+        if let Err(e) = conn.execute("DELETE FROM matches WHERE id = ?1", params![match_id]) {
+            warn!("Failed to discard match {}: {}", match_id, e);
+        }
+        info!("Discarded match {} (not logged)", match_id);
+        Some(match_id)
+    }
+
     /// Get the current match ID (if a match is in progress)
     pub fn current_match_id(&self) -> Option<i64> {
         self.current_match_id.lock().ok().and_then(|g| *g)
@@ -438,7 +482,9 @@ fn init_schema(conn: &Connection) -> Result<(), rusqlite::Error> {
             nav_paths_failed INTEGER NOT NULL,
             avg_shot_x REAL NOT NULL DEFAULT 0.0,
             avg_shot_y REAL NOT NULL DEFAULT 0.0,
-            avg_shot_quality REAL NOT NULL DEFAULT 0.0
+            avg_shot_quality REAL NOT NULL DEFAULT 0.0,
+            rim_camping INTEGER NOT NULL DEFAULT 0,
+            camping_violations INTEGER NOT NULL DEFAULT 0
         );
 
         CREATE INDEX IF NOT EXISTS idx_matches_session ON matches(session_id);
@@ -500,6 +546,15 @@ fn init_schema(conn: &Connection) -> Result<(), rusqlite::Error> {
     let _ = conn.execute("ALTER TABLE matches ADD COLUMN display_name TEXT", []);
     let _ = conn.execute("ALTER TABLE events ADD COLUMN point_id INTEGER", []);
     let _ = conn.execute("ALTER TABLE events ADD COLUMN tick_frame INTEGER", []);
+    let _ = conn.execute(
+        "ALTER TABLE player_stats ADD COLUMN rim_camping INTEGER NOT NULL DEFAULT 0",
+        [],
+    );
+    let _ = conn.execute(
+        "ALTER TABLE player_stats ADD COLUMN camping_violations INTEGER NOT NULL DEFAULT 0",
+        [],
+    );
+    migrate_time_base(conn);
     Ok(())
 }
 
@@ -619,6 +674,10 @@ pub fn flush_debug_samples_to_sqlite(
         buffer.samples.clear();
         return;
     }
+    let players = config.debug_sample_players;
+    buffer
+        .samples
+        .retain(|sample| players.includes(sample.player));
     if !buffer.samples.is_empty() {
         logger.log_debug_samples(&buffer.samples);
         buffer.samples.clear();
@@ -641,6 +700,7 @@ mod tests {
             current_point_id: Mutex::new(None),
             current_point_index: Mutex::new(0),
             enabled: true,
+            categories: EventCategory::ALL.to_vec(),
         }
     }
 
@@ -719,6 +779,50 @@ mod tests {
         assert_eq!(count, 3);
     }
 
+    #[test]
+    fn test_discard_current_match() {
+        let logger = create_test_logger();
+        let kept = logger
+            .start_match(1, "Test Level", "Human", "AI", 1)
+            .unwrap();
+        logger.log_event(
+            100,
+            &GameEvent::Pickup {
+                player: PlayerId::L,
+            },
+        );
+        logger.end_match(1, 0, 10.0);
+
+        let discarded = logger
+            .start_match(1, "Test Level", "Human", "AI", 2)
+            .unwrap();
+        logger.log_event(
+            100,
+            &GameEvent::Pickup {
+                player: PlayerId::R,
+            },
+        );
+        assert_eq!(logger.discard_current_match(), Some(discarded));
+        assert!(logger.current_match_id().is_none());
+
+        // Nothing more lands for the discarded match
+        logger.log_event(200, &GameEvent::ResetScores);
+        logger.end_match(0, 1, 20.0);
+        assert_eq!(logger.discard_current_match(), None);
+
+        let conn = logger.conn.lock().unwrap();
+        let count = |sql: &str| -> i64 { conn.query_row(sql, [], |row| row.get(0)).unwrap() };
+        assert_eq!(count("SELECT COUNT(*) FROM matches"), 1);
+        assert_eq!(
+            count(&format!(
+                "SELECT COUNT(*) FROM events WHERE match_id = {}",
+                kept
+            )),
+            1
+        );
+        assert_eq!(count("SELECT COUNT(*) FROM events"), 1);
+    }
+
     #[test]
     fn test_disabled_logger() {
         let logger = SqliteEventLogger::disabled();
@@ -3,6 +3,9 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use super::intern::EventStr;
+use crate::ball::BallPhysics;
+
 /// Player identifier (Left or Right)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PlayerId {
@@ -66,11 +69,43 @@ pub struct GameConfig {
     pub steal_range: f32,
     pub steal_success_chance: f32,
     pub steal_cooldown: f32,
+    #[serde(default)]
+    pub steal_fail_cooldown: f32,
+    #[serde(default)]
+    pub steal_victim_cooldown: f32,
+    #[serde(default)]
+    pub steal_charging_bonus: f32,
     // Active presets (if using preset system)
     pub preset_movement: Option<String>,
     pub preset_ball: Option<String>,
     pub preset_shooting: Option<String>,
     pub preset_composite: Option<String>,
+    #[serde(default)]
+    pub preset_steal: Option<String>,
+    // Ball style and its physics modifiers (None = default physics)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ball_style: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ball_physics: Option<BallPhysics>,
+}
+
+/// Team names and colors recorded in MatchStart (empty = not set, show L/R)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TeamTags {
+    pub left_name: EventStr,
+    pub right_name: EventStr,
+    /// Hex colors, e.g. `#d03030`
+    pub left_color: EventStr,
+    pub right_color: EventStr,
+}
+
+impl TeamTags {
+    pub fn is_empty(&self) -> bool {
+        self.left_name.is_empty()
+            && self.right_name.is_empty()
+            && self.left_color.is_empty()
+            && self.right_color.is_empty()
+    }
 }
 
 /// All game events that can be logged
@@ -89,10 +124,13 @@ pub enum GameEvent {
     /// Match started
     MatchStart {
         level: u32,
-        level_name: String,
-        left_profile: String,
-        right_profile: String,
+        level_name: EventStr,
+        left_profile: EventStr,
+        right_profile: EventStr,
         seed: u64,
+        /// Team names and colors (empty when the match didn't set any)
+        #[serde(default)]
+        teams: TeamTags,
     },
     /// Match ended
     MatchEnd {
@@ -108,12 +146,33 @@ pub enum GameEvent {
         score_left: u32,
         score_right: u32,
     },
+    /// Carry-in contested by a defender on the rim: outcome is blocked (fumble) or scored
+    RimContest {
+        carrier: PlayerId,
+        outcome: EventStr,
+    },
+    /// Defender stayed on their own rim for RIM_CAMPING_SECS (violation when the rule is on)
+    RimCamping { player: PlayerId, violation: bool },
 
     // === Ball Events ===
     /// Ball picked up
     Pickup { player: PlayerId },
     /// Ball dropped/lost without shot
     Drop { player: PlayerId },
+    /// Both players went for a free ball within a few ticks; winner got it
+    ContestedPickup { winner: PlayerId, loser: PlayerId },
+    /// Dead ball awarded by the possession arrow (reason: "pickup_contest", "stuck_ball")
+    PossessionAwarded { player: PlayerId, reason: EventStr },
+    /// Shot clock ran out on the holder's side (shot-clock rule); the ball popped loose
+    ShotClockViolation { player: PlayerId, limit: f32 },
+    /// Ball left the court off this side (out-of-bounds rule); the other side throws in
+    OutOfBounds { player: PlayerId, pos: (f32, f32) },
+    /// Running player scooped up a slow-rolling ball with pickup held (followed by Pickup)
+    Scoop { player: PlayerId, ball_speed: f32 },
+    /// Holder lobbed the ball toward a teammate (2v2)
+    Pass { player: PlayerId, distance: f32 },
+    /// Pass caught; `intercepted` when the other side got it
+    PassCaught { player: PlayerId, intercepted: bool },
     /// Shot started (charge began)
     ShotStart {
         player: PlayerId,
@@ -127,6 +186,12 @@ pub enum GameEvent {
         angle: f32,
         power: f32,
     },
+    /// Release charge vs the ideal charge for the shot (delta = charge - ideal)
+    ChargeCalibration {
+        player: PlayerId,
+        ideal: f32,
+        delta: f32,
+    },
 
     // === Steal Events ===
     /// Steal attempted
@@ -137,6 +202,13 @@ pub enum GameEvent {
     StealFail { attacker: PlayerId },
     /// Steal attempted but out of range
     StealOutOfRange { attacker: PlayerId },
+    /// Steal press denied, with why (out_of_range, cooldown, invulnerable, contest_lost)
+    StealDenied {
+        attacker: PlayerId,
+        reason: EventStr,
+    },
+    /// Steal knocked the ball loose instead of taking it (loose-ball rule), with its pop velocity
+    LooseBall { attacker: PlayerId, pop: (f32, f32) },
 
     // === Movement Events ===
     /// Player jumped
@@ -146,7 +218,21 @@ pub enum GameEvent {
 
     // === AI State Events ===
     /// AI goal changed
-    AiGoal { player: PlayerId, goal: String },
+    AiGoal { player: PlayerId, goal: EventStr },
+    /// 2v2 defensive matchups changed on this side (reason: "match", "screen", "cross", "help")
+    DefenseAssignment { player: PlayerId, reason: EventStr },
+    /// AI risk mode changed (neutral, trailing, leading) with its score differential
+    AiRisk {
+        player: PlayerId,
+        mode: EventStr,
+        diff: i32,
+    },
+    /// AI slipped on an input (late jump, overcharge, pickup hesitation) for delay_ms
+    AiMistake {
+        player: PlayerId,
+        kind: EventStr,
+        delay_ms: u32,
+    },
     /// AI navigation path started
     NavStart {
         player: PlayerId,
@@ -154,6 +240,22 @@ pub enum GameEvent {
     },
     /// AI navigation completed
     NavComplete { player: PlayerId },
+    /// AI finished (or gave up on) one nav graph edge: outcome is reached, fell or timeout
+    NavEdge {
+        player: PlayerId,
+        from: usize,
+        to: usize,
+        edge_type: EventStr,
+        outcome: EventStr,
+    },
+    /// Halftime coach changed one of a player's profile parameters
+    CoachAdjustment {
+        player: PlayerId,
+        param: EventStr,
+        from: f32,
+        to: f32,
+        reason: EventStr,
+    },
 
     // === Input Events (for replay/analysis) ===
     /// Input state snapshot (periodic, every N frames)
@@ -204,7 +306,20 @@ pub enum GameEvent {
     /// Reset ball to spawn position
     ResetBall,
     /// Level changed
-    LevelChange { level_id: String },
+    LevelChange { level_id: EventStr },
+    /// Ball was stuck (wedged out of reach or out of bounds) and got reset
+    BallStuck { pos: (f32, f32), reason: EventStr },
+    /// Ball bounced off an invisible rail above the arena (rail = ceiling/left/right)
+    BallRailHit { pos: (f32, f32), rail: EventStr },
+    /// A side's handicap, logged at every 0-0 (head start already on the board)
+    Handicap {
+        player: PlayerId,
+        head_start: u32,
+        shot_value: f32,
+        cooldown: f32,
+    },
+    /// A side's scoring run heated up (streak = consecutive goals) or cooled off (streak 0)
+    Momentum { player: PlayerId, streak: u32 },
 }
 
 impl GameEvent {
@@ -216,19 +331,38 @@ impl GameEvent {
             GameEvent::MatchStart { .. } => "MS",
             GameEvent::MatchEnd { .. } => "ME",
             GameEvent::Goal { .. } => "G",
+            GameEvent::RimContest { .. } => "RH",
+            GameEvent::RimCamping { .. } => "RC",
             GameEvent::Pickup { .. } => "PU",
             GameEvent::Drop { .. } => "DR",
+            GameEvent::ContestedPickup { .. } => "CP",
+            GameEvent::PossessionAwarded { .. } => "PA",
+            GameEvent::ShotClockViolation { .. } => "SV",
+            GameEvent::OutOfBounds { .. } => "OB",
+            GameEvent::Scoop { .. } => "SP",
+            GameEvent::Pass { .. } => "PS",
+            GameEvent::PassCaught { .. } => "PK",
             GameEvent::ShotStart { .. } => "SS",
             GameEvent::ShotRelease { .. } => "SR",
+            GameEvent::ChargeCalibration { .. } => "CC",
             GameEvent::StealAttempt { .. } => "SA",
             GameEvent::StealSuccess { .. } => "S+",
             GameEvent::StealFail { .. } => "S-",
             GameEvent::StealOutOfRange { .. } => "SO",
+            GameEvent::StealDenied { .. } => "SD",
+            GameEvent::LooseBall { .. } => "SL",
             GameEvent::Jump { .. } => "J",
             GameEvent::Land { .. } => "LD",
             GameEvent::AiGoal { .. } => "AG",
+            GameEvent::AiRisk { .. } => "RM",
+            GameEvent::DefenseAssignment { .. } => "DA",
+            GameEvent::AiMistake { .. } => "MK",
             GameEvent::NavStart { .. } => "NS",
             GameEvent::NavComplete { .. } => "NC",
+            GameEvent::NavEdge { .. } => "NE",
+            GameEvent::CoachAdjustment { .. } => "CA",
+            GameEvent::Handicap { .. } => "HC",
+            GameEvent::Momentum { .. } => "MO",
             GameEvent::Input { .. } => "I",
             GameEvent::Tick { .. } => "T",
             GameEvent::ControllerInput { .. } => "CI",
@@ -237,6 +371,136 @@ impl GameEvent {
             GameEvent::ResetScores => "RS",
             GameEvent::ResetBall => "RB",
             GameEvent::LevelChange { .. } => "LC",
+            GameEvent::BallStuck { .. } => "BS",
+            GameEvent::BallRailHit { .. } => "BH",
+        }
+    }
+}
+
+/// Coarse event grouping for choosing what gets persisted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventCategory {
+    /// Session, match, level and reset bookkeeping
+    Match,
+    Scoring,
+    Possession,
+    Shot,
+    Steal,
+    Movement,
+    /// AI goals, navigation and coach adjustments
+    Ai,
+    /// Per-tick player inputs and controller changes
+    Input,
+    /// Periodic position/velocity samples
+    Tick,
+}
+
+impl EventCategory {
+    pub const ALL: [EventCategory; 9] = [
+        EventCategory::Match,
+        EventCategory::Scoring,
+        EventCategory::Possession,
+        EventCategory::Shot,
+        EventCategory::Steal,
+        EventCategory::Movement,
+        EventCategory::Ai,
+        EventCategory::Input,
+        EventCategory::Tick,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventCategory::Match => "match",
+            EventCategory::Scoring => "scoring",
+            EventCategory::Possession => "possession",
+            EventCategory::Shot => "shot",
+            EventCategory::Steal => "steal",
+            EventCategory::Movement => "movement",
+            EventCategory::Ai => "ai",
+            EventCategory::Input => "input",
+            EventCategory::Tick => "tick",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.as_str() == s)
+    }
+
+    /// Parse a comma-separated list (`all` = every category)
+    pub fn parse_list(value: &str) -> Result<Vec<Self>, String> {
+        if value.trim() == "all" {
+            return Ok(Self::ALL.to_vec());
         }
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| Self::from_str(s).ok_or_else(|| format!("unknown event category '{}'", s)))
+            .collect()
+    }
+
+    /// Match and scoring events are always kept: points are split on goals
+    pub fn always_persisted(&self) -> bool {
+        matches!(self, EventCategory::Match | EventCategory::Scoring)
+    }
+}
+
+impl GameEvent {
+    pub fn category(&self) -> EventCategory {
+        match self {
+            GameEvent::SessionStart { .. }
+            | GameEvent::Config(_)
+            | GameEvent::MatchStart { .. }
+            | GameEvent::MatchEnd { .. }
+            | GameEvent::ResetAiState { .. }
+            | GameEvent::ResetScores
+            | GameEvent::ResetBall
+            | GameEvent::LevelChange { .. }
+            | GameEvent::Handicap { .. } => EventCategory::Match,
+            GameEvent::Goal { .. }
+            | GameEvent::RimContest { .. }
+            | GameEvent::RimCamping { .. }
+            | GameEvent::Momentum { .. } => EventCategory::Scoring,
+            GameEvent::Pickup { .. }
+            | GameEvent::Drop { .. }
+            | GameEvent::ContestedPickup { .. }
+            | GameEvent::PossessionAwarded { .. }
+            | GameEvent::ShotClockViolation { .. }
+            | GameEvent::OutOfBounds { .. }
+            | GameEvent::Scoop { .. }
+            | GameEvent::Pass { .. }
+            | GameEvent::PassCaught { .. }
+            | GameEvent::BallStuck { .. }
+            | GameEvent::BallRailHit { .. } => EventCategory::Possession,
+            GameEvent::ShotStart { .. }
+            | GameEvent::ShotRelease { .. }
+            | GameEvent::ChargeCalibration { .. } => EventCategory::Shot,
+            GameEvent::StealAttempt { .. }
+            | GameEvent::StealSuccess { .. }
+            | GameEvent::StealFail { .. }
+            | GameEvent::StealOutOfRange { .. }
+            | GameEvent::StealDenied { .. }
+            | GameEvent::LooseBall { .. } => EventCategory::Steal,
+            GameEvent::Jump { .. } | GameEvent::Land { .. } => EventCategory::Movement,
+            GameEvent::AiGoal { .. }
+            | GameEvent::AiRisk { .. }
+            | GameEvent::DefenseAssignment { .. }
+            | GameEvent::AiMistake { .. }
+            | GameEvent::NavStart { .. }
+            | GameEvent::NavComplete { .. }
+            | GameEvent::NavEdge { .. }
+            | GameEvent::CoachAdjustment { .. } => EventCategory::Ai,
+            GameEvent::Input { .. }
+            | GameEvent::ControllerInput { .. }
+            | GameEvent::ControlSwap { .. } => EventCategory::Input,
+            GameEvent::Tick { .. } => EventCategory::Tick,
+        }
+    }
+
+    /// Whether this event is stored when only `categories` are persisted
+    pub fn persisted_with(&self, categories: &[EventCategory]) -> bool {
+        let category = self.category();
+        category.always_persisted() || categories.contains(&category)
     }
 }
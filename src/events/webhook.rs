@@ -0,0 +1,219 @@
+//! Webhook bridge - posts selected events as JSON to a local HTTP endpoint
+//!
+//! For external automation (stream overlays, tournament brackets) that wants to
+//! react to a match as it happens. Each selected event is sent as one
+//! `POST` with a body like
+//! `{"event": "Goal", "time_ms": 41230, "data": {"Goal": {...}}}`.
+//! Posting happens on a background thread, so a slow or missing endpoint never
+//! stalls the game; failed posts are dropped.
+
+use serde_json::{Value, json};
+use std::io::{Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Sender};
+use std::time::Duration;
+
+use super::types::GameEvent;
+
+/// Events posted when `--webhook-events` isn't given
+pub const DEFAULT_WEBHOOK_EVENTS: [&str; 3] = ["MatchStart", "Goal", "MatchEnd"];
+
+/// Where to post and which events to send
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebhookConfig {
+    pub host: String,
+    pub port: u16,
+    pub path: String,
+    /// Event names as in the event enum (e.g. `Goal`)
+    pub events: Vec<String>,
+}
+
+impl WebhookConfig {
+    /// Parse `--webhook <url>` and `--webhook-events <A,B,...>`.
+    /// Returns None unless a valid URL is given.
+    pub fn from_args(args: &[String]) -> Option<Self> {
+        let value = |flag: &str| {
+            args.iter()
+                .position(|a| a == flag)
+                .and_then(|i| args.get(i + 1))
+        };
+        let url = value("--webhook")?;
+        let (host, port, path) = match parse_http_url(url) {
+            Ok(parts) => parts,
+            Err(e) => {
+                eprintln!("Warning: --webhook: {}", e);
+                return None;
+            }
+        };
+        let events = match value("--webhook-events") {
+            Some(list) => list
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
+            None => DEFAULT_WEBHOOK_EVENTS.map(String::from).to_vec(),
+        };
+        Some(Self {
+            host,
+            port,
+            path,
+            events,
+        })
+    }
+
+    /// JSON body for `event`, or None if it isn't one of the selected events
+    pub fn payload(&self, time_ms: u32, event: &GameEvent) -> Option<String> {
+        let data = serde_json::to_value(event).ok()?;
+        let name = match &data {
+            Value::String(name) => name.clone(),
+            Value::Object(map) => map.keys().next()?.clone(),
+            _ => return None,
+        };
+        if !self.events.contains(&name) {
+            return None;
+        }
+        Some(json!({ "event": name, "time_ms": time_ms, "data": data }).to_string())
+    }
+}
+
+/// Split `http://host[:port][/path]` into its parts (port defaults to 80)
+pub fn parse_http_url(url: &str) -> Result<(String, u16, String), String> {
+    let rest = url
+        .strip_prefix("http://")
+        .ok_or_else(|| format!("only http:// URLs are supported (got '{}')", url))?;
+    let (authority, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, "/"),
+    };
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => (
+            host,
+            port.parse::<u16>()
+                .map_err(|_| format!("bad port '{}' in '{}'", port, url))?,
+        ),
+        None => (authority, 80),
+    };
+    if host.is_empty() {
+        return Err(format!("missing host in '{}'", url));
+    }
+    Ok((host.to_string(), port, path.to_string()))
+}
+
+/// Sends selected events to the configured endpoint from a background thread
+pub struct WebhookBridge {
+    config: WebhookConfig,
+    sender: Sender<String>,
+}
+
+impl WebhookBridge {
+    /// Start the posting thread
+    pub fn start(config: WebhookConfig) -> Self {
+        let (sender, receiver) = mpsc::channel::<String>();
+        let (host, port, path) = (config.host.clone(), config.port, config.path.clone());
+        eprintln!(
+            "Webhook: posting {} to http://{}:{}{}",
+            config.events.join(", "),
+            host,
+            port,
+            path
+        );
+        std::thread::spawn(move || {
+            let mut failing = false;
+            for body in receiver {
+                match post_json(&host, port, &path, &body) {
+                    Ok(()) => failing = false,
+                    Err(e) if !failing => {
+                        // Warn once per outage, not once per event
+                        eprintln!("Warning: webhook post to {}:{} failed: {}", host, port, e);
+                        failing = true;
+                    }
+                    Err(_) => {}
+                }
+            }
+        });
+        Self { config, sender }
+    }
+
+    /// Queue the selected events from `events` for posting
+    pub fn forward(&self, events: &[(u32, GameEvent)]) {
+        for (time_ms, event) in events {
+            if let Some(body) = self.config.payload(*time_ms, event) {
+                let _ = self.sender.send(body);
+            }
+        }
+    }
+}
+
+fn post_json(host: &str, port: u16, path: &str, body: &str) -> Result<(), String> {
+    let addr = (host, port)
+        .to_socket_addrs()
+        .map_err(|e| e.to_string())?
+        .next()
+        .ok_or_else(|| format!("no address for {}", host))?;
+    let timeout = Duration::from_secs(1);
+    let mut stream = TcpStream::connect_timeout(&addr, timeout).map_err(|e| e.to_string())?;
+    let _ = stream.set_write_timeout(Some(timeout));
+    let _ = stream.set_read_timeout(Some(timeout));
+    let request = format!(
+        "POST {} HTTP/1.1\r\nHost: {}:{}\r\nContent-Type: application/json\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        path,
+        host,
+        port,
+        body.len(),
+        body
+    );
+    stream
+        .write_all(request.as_bytes())
+        .map_err(|e| e.to_string())?;
+    // Wait for the status line so the endpoint isn't cut off mid-request
+    let mut response = [0u8; 64];
+    let _ = stream.read(&mut response);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::types::PlayerId;
+
+    #[test]
+    fn test_webhook_config_and_payload() {
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert!(WebhookConfig::from_args(&args(&["training"])).is_none());
+        assert!(WebhookConfig::from_args(&args(&["training", "--webhook", "https://x"])).is_none());
+
+        let config =
+            WebhookConfig::from_args(&args(&["training", "--webhook", "http://127.0.0.1:9000"]))
+                .unwrap();
+        assert_eq!(
+            (config.host.as_str(), config.port, config.path.as_str()),
+            ("127.0.0.1", 9000, "/")
+        );
+        assert_eq!(config.events, DEFAULT_WEBHOOK_EVENTS.map(String::from));
+
+        let goal = GameEvent::Goal {
+            player: PlayerId::L,
+            score_left: 2,
+            score_right: 1,
+        };
+        let body: Value = serde_json::from_str(&config.payload(1500, &goal).unwrap()).unwrap();
+        assert_eq!(body["event"], "Goal");
+        assert_eq!(body["time_ms"], 1500);
+        assert_eq!(body["data"]["Goal"]["score_left"], 2);
+        assert!(config.payload(0, &GameEvent::ResetScores).is_none());
+
+        let only_resets = WebhookConfig::from_args(&args(&[
+            "training",
+            "--webhook",
+            "http://localhost/hooks/ballgame",
+            "--webhook-events",
+            "ResetScores",
+        ]))
+        .unwrap();
+        assert_eq!(only_resets.port, 80);
+        assert_eq!(only_resets.path, "/hooks/ballgame");
+        assert!(only_resets.payload(0, &GameEvent::ResetScores).is_some());
+        assert!(only_resets.payload(0, &goal).is_none());
+    }
+}
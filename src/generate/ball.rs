@@ -3,17 +3,20 @@
 //! Generates ball textures for all styles × all color palettes.
 //! Styles and palettes are read from config files (single source of truth).
 
-use image::{Rgba, RgbaImage};
+use image::{Rgba, RgbaImage, imageops};
 use std::collections::HashMap;
 use std::f32::consts::PI;
 use std::fs;
 
+use super::OutputOptions;
+use crate::ball::{ball_texture_path, ball_texture_sizes};
+
 // Colors
 const WHITE: [u8; 4] = [245, 245, 240, 255]; // Off-white (cream)
 const BLACK: [u8; 4] = [20, 20, 20, 255]; // Outline
 
-const PALETTES_FILE: &str = "config/palettes.txt";
-const OPTIONS_FILE: &str = "config/ball_options.txt";
+pub(super) const PALETTES_FILE: &str = "config/palettes.txt";
+pub(super) const OPTIONS_FILE: &str = "config/ball_options.txt";
 
 /// Color palette with left and right team colors (RGB 0-255)
 #[derive(Clone)]
@@ -100,34 +103,69 @@ struct StyleConfig {
 struct BallConfig {
     size: u32,
     border: f32,
+    /// Reduced copies written to assets/textures/balls/<size>/ (`sizes:` line)
+    mip_sizes: Vec<u32>,
     styles: Vec<StyleConfig>,
     palettes: Vec<Palette>,
 }
 
 pub fn run() {
+    run_with(&OutputOptions::default());
+}
+
+/// Generate ball textures. `size` overrides ball_options.txt and `palettes` limits
+/// which palette indices are rebuilt.
+pub fn run_with(options: &OutputOptions) {
     let palettes = load_palettes();
-    let config = load_config(palettes);
+    let mut config = load_config(palettes);
+    if let Some(size) = options.size {
+        config.size = size;
+    }
 
     println!("\nGenerating ball textures...");
     println!("  Size: {}px, Border: {}px", config.size, config.border);
+    println!("  Reduced sizes: {:?}", config.mip_sizes);
+    for size in &config.mip_sizes {
+        fs::create_dir_all(format!("assets/textures/balls/{}", size)).ok();
+    }
     println!("  Styles: {}", config.styles.len());
     println!("  Palettes: {}", config.palettes.len());
 
+    let mut generated = 0;
     for style in &config.styles {
         for (palette_idx, palette) in config.palettes.iter().enumerate() {
+            if options
+                .palettes
+                .as_ref()
+                .is_some_and(|only| !only.contains(&palette_idx))
+            {
+                continue;
+            }
             let filename = format!(
-                "assets/textures/balls/ball_{}_{}.png",
-                style.name, palette_idx
+                "assets/{}",
+                ball_texture_path(&style.name, palette_idx, None)
             );
-            generate_texture(&filename, &config, style, palette);
+            let img = render_texture(&config, style, palette);
+            img.save(&filename).expect("Failed to save ball texture");
+            // Downsampled copies so small on-screen balls don't shimmer
+            for &size in &config.mip_sizes {
+                let mip = imageops::resize(&img, size, size, imageops::FilterType::Lanczos3);
+                let path = format!(
+                    "assets/{}",
+                    ball_texture_path(&style.name, palette_idx, Some(size))
+                );
+                mip.save(&path).expect("Failed to save ball texture");
+            }
             println!("  Created: {} ({})", filename, palette.name);
+            generated += 1;
         }
     }
 
-    println!(
-        "\nGenerated {} ball textures.",
-        config.styles.len() * config.palettes.len()
-    );
+    println!("\nGenerated {} ball textures.", generated);
+    // Only a full set matches what the game loads
+    if options.palettes.is_none() {
+        super::integrity::record_ball_texture_hash();
+    }
 }
 
 fn load_config(palettes: Vec<Palette>) -> BallConfig {
@@ -184,12 +222,13 @@ fn parse_config(content: &str, palettes: Vec<Palette>) -> BallConfig {
     BallConfig {
         size: size.expect("Missing size"),
         border: border.expect("Missing border"),
+        mip_sizes: ball_texture_sizes(content),
         styles,
         palettes,
     }
 }
 
-fn generate_texture(path: &str, config: &BallConfig, style: &StyleConfig, palette: &Palette) {
+fn render_texture(config: &BallConfig, style: &StyleConfig, palette: &Palette) -> RgbaImage {
     let size = config.size;
     let center = size as f32 / 2.0;
     let radius = center - config.border;
@@ -243,7 +282,7 @@ fn generate_texture(path: &str, config: &BallConfig, style: &StyleConfig, palett
         }
     }
 
-    img.save(path).expect("Failed to save ball texture");
+    img
 }
 
 fn get_pixel_color(
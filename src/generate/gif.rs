@@ -0,0 +1,66 @@
+//! Animated GIF encoding shared by the GIF generators and live capture
+
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame, RgbaImage, imageops};
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::Path;
+
+/// Quantizer speed (1 = best quality, 30 = fastest)
+const ENCODE_SPEED: i32 = 10;
+
+/// Write frames as a looping GIF, `frame_ms` per frame
+pub fn encode_gif(frames: Vec<RgbaImage>, frame_ms: u32, path: &Path) -> Result<(), String> {
+    if frames.is_empty() {
+        return Err("no frames to encode".to_string());
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("create {}: {}", dir.display(), e))?;
+    }
+    let file = File::create(path).map_err(|e| format!("create {}: {}", path.display(), e))?;
+    let mut encoder = GifEncoder::new_with_speed(BufWriter::new(file), ENCODE_SPEED);
+    encoder
+        .set_repeat(Repeat::Infinite)
+        .map_err(|e| format!("encode {}: {}", path.display(), e))?;
+    let delay = Delay::from_numer_denom_ms(frame_ms, 1);
+    encoder
+        .encode_frames(
+            frames
+                .into_iter()
+                .map(|img| Frame::from_parts(img, 0, 0, delay)),
+        )
+        .map_err(|e| format!("encode {}: {}", path.display(), e))
+}
+
+/// Shrink an image to at most `max_width` pixels wide, keeping its aspect ratio
+pub fn downscale(img: &RgbaImage, max_width: u32) -> RgbaImage {
+    if img.width() <= max_width || max_width == 0 {
+        return img.clone();
+    }
+    let height = (img.height() as u64 * max_width as u64 / img.width() as u64).max(1) as u32;
+    imageops::resize(img, max_width, height, imageops::FilterType::Triangle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn downscale_keeps_aspect_and_gif_round_trips() {
+        let img = RgbaImage::new(1600, 900);
+        let small = downscale(&img, 480);
+        assert_eq!((small.width(), small.height()), (480, 270));
+        assert_eq!(downscale(&small, 640).dimensions(), (480, 270));
+
+        let path = std::env::temp_dir().join("ballgame_gif_test.gif");
+        let frames = vec![
+            RgbaImage::new(8, 8),
+            RgbaImage::from_pixel(8, 8, image::Rgba([255; 4])),
+        ];
+        encode_gif(frames, 66, &path).unwrap();
+        let decoded = image::open(&path).unwrap();
+        assert_eq!(decoded.width(), 8);
+        let _ = fs::remove_file(&path);
+        assert!(encode_gif(Vec::new(), 66, &path).is_err());
+    }
+}
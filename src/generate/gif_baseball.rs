@@ -5,9 +5,15 @@
 
 use image::{Rgba, RgbaImage};
 use std::f32::consts::PI;
+use std::path::Path;
+
+use super::OutputOptions;
+use super::gif::encode_gif;
 
 const SIZE: u32 = 256;
 const FRAMES: u32 = 120; // More frames for smoother dual-axis rotation
+const FRAME_MS: u32 = 33; // ~30 fps
+pub(super) const OUTPUT_PATH: &str = "assets/baseball_rotation.gif";
 
 const COLOR_A: [u8; 3] = [0, 255, 128];
 const COLOR_B: [u8; 3] = [255, 0, 128];
@@ -16,43 +22,43 @@ const BORDER_COLOR: [u8; 3] = [20, 20, 20];
 const BG_COLOR: [u8; 3] = [30, 30, 35];
 
 pub fn run() {
+    run_with(&OutputOptions::default());
+}
+
+/// Render frames and assemble the GIF. `size` is the frame size in pixels.
+pub fn run_with(options: &OutputOptions) {
+    let size = options.size.unwrap_or(SIZE);
+    let frames = options.frames.unwrap_or(FRAMES);
+    let output_path = options.path.as_deref().unwrap_or(OUTPUT_PATH);
     println!(
         "Generating baseball frames ({} frames at {}x{})...",
-        FRAMES, SIZE, SIZE
+        frames, size, size
     );
 
     std::fs::create_dir_all("assets/baseball_frames").ok();
 
-    for frame in 0..FRAMES {
-        let t = frame as f32 / FRAMES as f32;
+    let mut images = Vec::with_capacity(frames as usize);
+    for frame in 0..frames {
+        let t = frame as f32 / frames as f32;
         // Primary rotation around Y axis (full rotation)
         let y_angle = t * 2.0 * PI;
         // Secondary slow tilt on X axis (wobble to show top/bottom)
         let x_angle = (t * 2.0 * PI).sin() * 0.4; // ±0.4 radians tilt
 
-        let img = render_baseball(y_angle, x_angle);
+        let img = render_baseball(y_angle, x_angle, size);
         img.save(format!("assets/baseball_frames/frame_{:03}.png", frame))
             .unwrap();
-        print!("\r  Frame {}/{}", frame + 1, FRAMES);
+        images.push(img);
+        print!("\r  Frame {}/{}", frame + 1, frames);
     }
 
     println!("\n\nCreating GIF...");
-    let _ = std::process::Command::new("ffmpeg")
-        .args([
-            "-y",
-            "-framerate",
-            "30",
-            "-i",
-            "assets/baseball_frames/frame_%03d.png",
-            "-vf",
-            "split[s0][s1];[s0]palettegen=max_colors=128[p];[s1][p]paletteuse",
-            "-loop",
-            "0",
-            "assets/baseball_rotation.gif",
-        ])
-        .status();
-
-    println!("Done! assets/baseball_rotation.gif");
+    if let Err(e) = encode_gif(images, FRAME_MS, Path::new(output_path)) {
+        eprintln!("Error: {}", e);
+        return;
+    }
+
+    println!("Done! {}", output_path);
 }
 
 /// Baseball seam point at parameter t (0 to 4π for full curve)
@@ -166,19 +172,19 @@ fn spherical_angle(a: [f32; 3], b: [f32; 3], c: [f32; 3]) -> f32 {
     sin_sign * cos_angle.clamp(-1.0, 1.0).acos()
 }
 
-fn render_baseball(y_rotation: f32, x_rotation: f32) -> RgbaImage {
+fn render_baseball(y_rotation: f32, x_rotation: f32, size: u32) -> RgbaImage {
     let mut img = RgbaImage::from_pixel(
-        SIZE,
-        SIZE,
+        size,
+        size,
         Rgba([BG_COLOR[0], BG_COLOR[1], BG_COLOR[2], 255]),
     );
 
-    let center = SIZE as f32 / 2.0;
+    let center = size as f32 / 2.0;
     let radius = center - 20.0;
     let seam_width = 0.08; // Width of seam line on sphere surface
 
-    for py in 0..SIZE {
-        for px in 0..SIZE {
+    for py in 0..size {
+        for px in 0..size {
             let x = (px as f32 - center) / radius;
             let y = (py as f32 - center) / radius;
             let r2 = x * x + y * y;
@@ -221,8 +227,8 @@ fn render_baseball(y_rotation: f32, x_rotation: f32) -> RgbaImage {
     }
 
     // Draw border
-    for py in 0..SIZE {
-        for px in 0..SIZE {
+    for py in 0..size {
+        for px in 0..size {
             let x = (px as f32 - center) / radius;
             let y = (py as f32 - center) / radius;
             let r = (x * x + y * y).sqrt();
@@ -5,9 +5,15 @@
 
 use image::{Rgba, RgbaImage};
 use std::f32::consts::PI;
+use std::path::Path;
+
+use super::OutputOptions;
+use super::gif::encode_gif;
 
 const SIZE: u32 = 256;
 const FRAMES: u32 = 120;
+const FRAME_MS: u32 = 33; // ~30 fps
+pub(super) const OUTPUT_PATH: &str = "assets/wedge_rotation.gif";
 const SECTIONS: u32 = 6; // Number of wedge sections
 
 const COLOR_A: [u8; 3] = [0, 255, 128]; // Green
@@ -16,43 +22,43 @@ const BORDER_COLOR: [u8; 3] = [20, 20, 20];
 const BG_COLOR: [u8; 3] = [30, 30, 35];
 
 pub fn run() {
+    run_with(&OutputOptions::default());
+}
+
+/// Render frames and assemble the GIF. `size` is the frame size in pixels.
+pub fn run_with(options: &OutputOptions) {
+    let size = options.size.unwrap_or(SIZE);
+    let frames = options.frames.unwrap_or(FRAMES);
+    let output_path = options.path.as_deref().unwrap_or(OUTPUT_PATH);
     println!(
         "Generating wedge_{} frames ({} frames at {}x{})...",
-        SECTIONS, FRAMES, SIZE, SIZE
+        SECTIONS, frames, size, size
     );
 
     std::fs::create_dir_all("assets/wedge_frames").ok();
 
-    for frame in 0..FRAMES {
-        let t = frame as f32 / FRAMES as f32;
+    let mut images = Vec::with_capacity(frames as usize);
+    for frame in 0..frames {
+        let t = frame as f32 / frames as f32;
         // Primary rotation around Y axis (full rotation)
         let y_angle = t * 2.0 * PI;
         // Secondary slow tilt on X axis (wobble to show top/bottom)
         let x_angle = (t * 2.0 * PI).sin() * 0.4; // ±0.4 radians tilt
 
-        let img = render_wedge(y_angle, x_angle);
+        let img = render_wedge(y_angle, x_angle, size);
         img.save(format!("assets/wedge_frames/frame_{:03}.png", frame))
             .unwrap();
-        print!("\r  Frame {}/{}", frame + 1, FRAMES);
+        images.push(img);
+        print!("\r  Frame {}/{}", frame + 1, frames);
     }
 
     println!("\n\nCreating GIF...");
-    let _ = std::process::Command::new("ffmpeg")
-        .args([
-            "-y",
-            "-framerate",
-            "30",
-            "-i",
-            "assets/wedge_frames/frame_%03d.png",
-            "-vf",
-            "split[s0][s1];[s0]palettegen=max_colors=128[p];[s1][p]paletteuse",
-            "-loop",
-            "0",
-            "assets/wedge_rotation.gif",
-        ])
-        .status();
-
-    println!("Done! assets/wedge_rotation.gif");
+    if let Err(e) = encode_gif(images, FRAME_MS, Path::new(output_path)) {
+        eprintln!("Error: {}", e);
+        return;
+    }
+
+    println!("Done! {}", output_path);
 }
 
 /// Rotate point around Y axis
@@ -109,14 +115,14 @@ fn near_boundary(point: [f32; 3], threshold: f32) -> bool {
     within_section < threshold || within_section > (section_angle - threshold)
 }
 
-fn render_wedge(y_angle: f32, x_angle: f32) -> RgbaImage {
-    let mut img = RgbaImage::new(SIZE, SIZE);
-    let radius = (SIZE as f32 / 2.0) - 2.0;
-    let center = SIZE as f32 / 2.0;
+fn render_wedge(y_angle: f32, x_angle: f32, size: u32) -> RgbaImage {
+    let mut img = RgbaImage::new(size, size);
+    let radius = (size as f32 / 2.0) - 2.0;
+    let center = size as f32 / 2.0;
     let border = 8.0;
 
-    for y in 0..SIZE {
-        for x in 0..SIZE {
+    for y in 0..size {
+        for x in 0..size {
             let fx = x as f32 - center;
             let fy = y as f32 - center;
             let dist = (fx * fx + fy * fy).sqrt();
@@ -0,0 +1,267 @@
+//! Asset integrity check
+//!
+//! Verifies that the generated assets the game loads exist and were built from
+//! the current config:
+//! - Ball textures: one per style × palette. Staleness compares a hash of
+//!   `palettes.txt` + `ball_options.txt` with the one recorded by `generate ball`.
+//! - AI heatmaps: the full bundle for every level the AI plays. Staleness compares
+//!   `LevelData::content_hash` with the hashes recorded by the heatmap tool.
+//!
+//! Missing or stale assets can be rebuilt with `regenerate`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::Path;
+
+use super::ball::{OPTIONS_FILE, PALETTES_FILE};
+use crate::ai::heatmaps::{
+    HEATMAP_KINDS, LevelHashCache, find_heatmap_path, sanitize_level_name,
+    skip_reachability_heatmaps,
+};
+use crate::ball::{ball_texture_path, ball_texture_sizes};
+use crate::config_dir;
+use crate::levels::{LevelData, LevelDatabase};
+use crate::training::TrainingProtocol;
+
+/// Config hashes recorded when assets were last generated
+pub const ASSET_HASH_FILE: &str = "config/asset_hashes.json";
+
+#[derive(Serialize, Deserialize, Default)]
+pub(super) struct AssetHashCache {
+    pub(super) ball_textures: Option<String>,
+    /// Manifest output name -> input hash it was last built from (see `manifest::run_all`)
+    #[serde(default)]
+    pub(super) outputs: HashMap<String, String>,
+}
+
+impl AssetHashCache {
+    pub(super) fn load() -> Self {
+        match fs::read_to_string(config_dir::resolve_for_write(ASSET_HASH_FILE)) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_default(),
+            Err(_) => AssetHashCache::default(),
+        }
+    }
+
+    pub(super) fn save(&self) {
+        if let Ok(json) = serde_json::to_string_pretty(self)
+            && let Err(e) = fs::write(config_dir::resolve_for_write(ASSET_HASH_FILE), json)
+        {
+            eprintln!("Failed to write {}: {}", ASSET_HASH_FILE, e);
+        }
+    }
+}
+
+/// Hash of the config files ball textures are generated from (missing files hash as empty)
+pub fn ball_config_hash() -> String {
+    let mut hasher = DefaultHasher::new();
+    for path in [PALETTES_FILE, OPTIONS_FILE] {
+        fs::read_to_string(path)
+            .unwrap_or_default()
+            .hash(&mut hasher);
+    }
+    format!("{:016x}", hasher.finish())
+}
+
+/// Record the current ball config hash (called after textures are generated)
+pub fn record_ball_texture_hash() {
+    let mut cache = AssetHashCache::load();
+    cache.ball_textures = Some(ball_config_hash());
+    cache.save();
+}
+
+/// Texture paths the game expects for the given `ball_options.txt` and `palettes.txt`
+/// contents: the full-size set plus each reduced size
+pub fn expected_ball_textures(options: &str, palettes: &str) -> Vec<String> {
+    let num_palettes = palettes
+        .lines()
+        .filter(|l| l.trim().starts_with("palette:"))
+        .count();
+    let sizes: Vec<Option<u32>> = std::iter::once(None)
+        .chain(ball_texture_sizes(options).into_iter().map(Some))
+        .collect();
+    let styles: Vec<&str> = options
+        .lines()
+        .filter_map(|l| l.trim().strip_prefix("style:"))
+        .map(str::trim)
+        .collect();
+
+    let mut paths = Vec::new();
+    for size in sizes {
+        for style in &styles {
+            for i in 0..num_palettes {
+                paths.push(format!("assets/{}", ball_texture_path(style, i, size)));
+            }
+        }
+    }
+    paths
+}
+
+/// Levels the AI plays with heatmaps (same selection as the heatmap tool)
+fn heatmaps_required(level: &LevelData) -> bool {
+    if level.debug || level.regression {
+        return false;
+    }
+    ![TrainingProtocol::Pursuit, TrainingProtocol::Pursuit2]
+        .into_iter()
+        .filter_map(|protocol| protocol.fixed_level())
+        .any(|name| level.name.eq_ignore_ascii_case(name))
+}
+
+/// Result of an integrity check
+#[derive(Debug, Default)]
+pub struct AssetReport {
+    pub missing_textures: Vec<String>,
+    /// Textures exist but the ball config changed since they were generated
+    pub stale_textures: bool,
+    /// One entry per missing heatmap ("level: kind")
+    pub missing_heatmaps: Vec<String>,
+    /// Levels whose layout changed since their heatmaps were generated
+    pub stale_heatmap_levels: Vec<String>,
+}
+
+impl AssetReport {
+    pub fn is_clean(&self) -> bool {
+        !self.textures_need_regen() && !self.heatmaps_need_regen()
+    }
+
+    pub fn textures_need_regen(&self) -> bool {
+        !self.missing_textures.is_empty() || self.stale_textures
+    }
+
+    pub fn heatmaps_need_regen(&self) -> bool {
+        !self.missing_heatmaps.is_empty() || !self.stale_heatmap_levels.is_empty()
+    }
+
+    /// Print a short summary (first few entries of each list)
+    pub fn print(&self) {
+        fn list(label: &str, items: &[String]) {
+            if items.is_empty() {
+                return;
+            }
+            eprintln!("  {}: {}", label, items.len());
+            for item in items.iter().take(5) {
+                eprintln!("    {}", item);
+            }
+            if items.len() > 5 {
+                eprintln!("    ... and {} more", items.len() - 5);
+            }
+        }
+
+        eprintln!("Asset check: generated assets are missing or out of date");
+        list("Missing ball textures", &self.missing_textures);
+        if self.stale_textures {
+            eprintln!("  Ball textures predate the current palettes/ball options");
+        }
+        list("Missing heatmaps", &self.missing_heatmaps);
+        list("Levels with stale heatmaps", &self.stale_heatmap_levels);
+    }
+}
+
+/// Check ball textures and heatmaps against the current config
+pub fn check_assets(level_db: &LevelDatabase) -> AssetReport {
+    let mut report = AssetReport::default();
+
+    let options = fs::read_to_string(OPTIONS_FILE).unwrap_or_default();
+    let palettes = fs::read_to_string(PALETTES_FILE).unwrap_or_default();
+    report.missing_textures = expected_ball_textures(&options, &palettes)
+        .into_iter()
+        .filter(|path| !Path::new(path).exists())
+        .collect();
+
+    let mut hashes = AssetHashCache::load();
+    match &hashes.ball_textures {
+        Some(recorded) => report.stale_textures = *recorded != ball_config_hash(),
+        // No record yet: trust complete texture sets and start tracking from here
+        None if report.missing_textures.is_empty() => {
+            hashes.ball_textures = Some(ball_config_hash());
+            hashes.save();
+        }
+        None => {}
+    }
+
+    let level_hashes = LevelHashCache::load();
+    let skip_reachability = skip_reachability_heatmaps();
+    for level in level_db.all().iter().filter(|l| heatmaps_required(l)) {
+        let safe_name = sanitize_level_name(&level.name);
+        let mut missing = false;
+        for (kind, side) in HEATMAP_KINDS {
+            if skip_reachability && matches!(kind, "reachability" | "path_cost" | "escape_routes") {
+                continue;
+            }
+            if find_heatmap_path(kind, &safe_name, &level.id, side).is_err() {
+                missing = true;
+                report.missing_heatmaps.push(match side {
+                    Some(side) => format!("{}: {} ({})", level.name, kind, side),
+                    None => format!("{}: {}", level.name, kind),
+                });
+            }
+        }
+        if !missing && level_hashes.levels.get(&level.id) != Some(&level.content_hash()) {
+            report.stale_heatmap_levels.push(level.name.clone());
+        }
+    }
+
+    report
+}
+
+/// Ask on the terminal whether to regenerate (false when stdin isn't interactive)
+pub fn confirm_regenerate() -> bool {
+    let stdin = std::io::stdin();
+    if !stdin.is_terminal() {
+        return false;
+    }
+    eprint!("Regenerate now? [y/N] ");
+    let _ = std::io::stderr().flush();
+    let mut answer = String::new();
+    if stdin.lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Rebuild whatever the report flags: ball textures in-process, heatmaps via the
+/// `heatmap` tool (`--full --check` only rebuilds new or changed levels)
+pub fn regenerate(report: &AssetReport) -> Result<(), String> {
+    if report.textures_need_regen() {
+        fs::create_dir_all("assets/textures/balls")
+            .map_err(|e| format!("create assets/textures/balls: {}", e))?;
+        super::ball::run();
+    }
+    if report.heatmaps_need_regen() {
+        let args = ["heatmap", "--full", "--check"].map(String::from);
+        let status = crate::cli::run_tool_status(&args)?;
+        if !status.success() {
+            return Err(format!("heatmap tool exited with {}", status));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expected_textures_cover_every_style_and_palette() {
+        let options = "# styles\nstyle: s01\npattern: wedges\nstyle: s02\n";
+        let palettes = "palette: Classic\nleft: 1 0 0\nright: 0 0 1\n\npalette: Mono\n";
+        let paths = expected_ball_textures(options, palettes);
+        assert_eq!(paths.len(), 4);
+        assert!(paths.contains(&"assets/textures/balls/ball_s01_0.png".to_string()));
+        assert!(paths.contains(&"assets/textures/balls/ball_s02_1.png".to_string()));
+        let with_mips = expected_ball_textures(&format!("sizes: 32\n{}", options), palettes);
+        assert_eq!(with_mips.len(), 8);
+        assert!(with_mips.contains(&"assets/textures/balls/32/ball_s02_1.png".to_string()));
+
+        let report = AssetReport {
+            stale_heatmap_levels: vec!["Arena".to_string()],
+            ..Default::default()
+        };
+        assert!(!report.is_clean());
+        assert!(report.heatmaps_need_regen() && !report.textures_need_regen());
+    }
+}
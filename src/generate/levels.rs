@@ -8,6 +8,8 @@ use image::{Rgba, RgbaImage, imageops};
 use imageproc::drawing::draw_text_mut;
 use std::fs;
 
+use super::OutputOptions;
+
 // Layout parameters
 const COLS: u32 = 4;
 const SCALE: f32 = 0.25; // Scale down screenshots to 25%
@@ -17,9 +19,21 @@ const LABEL_HEIGHT: u32 = 40;
 // Embed system font
 const FONT_DATA: &[u8] = include_bytes!("/System/Library/Fonts/Helvetica.ttc");
 
+pub(super) const SCREENSHOT_DIR: &str = "level_screenshots";
+pub(super) const OUTPUT_PATH: &str = "showcase/level_showcase.png";
+
 pub fn run() {
-    let screenshot_dir = "level_screenshots";
-    let output_path = "showcase/level_showcase.png";
+    if let Err(e) = run_with(&OutputOptions::default()) {
+        eprintln!("{}", e);
+        eprintln!("Run ./scripts/generate_level_showcase.sh first to capture screenshots");
+        std::process::exit(1);
+    }
+}
+
+/// Build the grid. `size` is the width of each scaled screenshot (default 25%).
+pub fn run_with(options: &OutputOptions) -> Result<(), String> {
+    let screenshot_dir = SCREENSHOT_DIR;
+    let output_path = options.path.as_deref().unwrap_or(OUTPUT_PATH);
 
     // Find all level screenshots
     let mut screenshots: Vec<(String, String)> = Vec::new();
@@ -40,9 +54,7 @@ pub fn run() {
     screenshots.sort_by(|a, b| a.0.cmp(&b.0)); // Sort by path (which includes level number)
 
     if screenshots.is_empty() {
-        eprintln!("No screenshots found in {}/", screenshot_dir);
-        eprintln!("Run ./scripts/generate_level_showcase.sh first to capture screenshots");
-        std::process::exit(1);
+        return Err(format!("No screenshots found in {}/", screenshot_dir));
     }
 
     println!("Found {} level screenshots", screenshots.len());
@@ -52,8 +64,12 @@ pub fn run() {
     let orig_width = first_img.width();
     let orig_height = first_img.height();
 
-    let scaled_width = (orig_width as f32 * SCALE) as u32;
-    let scaled_height = (orig_height as f32 * SCALE) as u32;
+    let scale = options
+        .size
+        .map(|width| width as f32 / orig_width as f32)
+        .unwrap_or(SCALE);
+    let scaled_width = (orig_width as f32 * scale) as u32;
+    let scaled_height = (orig_height as f32 * scale) as u32;
 
     println!(
         "Original size: {}x{}, scaled to: {}x{}",
@@ -158,8 +174,11 @@ pub fn run() {
     }
 
     // Save output
-    showcase.save(output_path).expect("Failed to save showcase");
+    showcase
+        .save(output_path)
+        .map_err(|e| format!("Failed to save {}: {}", output_path, e))?;
     println!("\nShowcase saved to: {}", output_path);
+    Ok(())
 }
 
 fn extract_level_name(filename: &str) -> Option<String> {
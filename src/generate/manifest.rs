@@ -0,0 +1,295 @@
+//! Generate manifest - every generated output in one file
+//!
+//! `config/generate_manifest.txt` lists the outputs `generate all` builds, with
+//! optional sizes and palettes. Each output's inputs (manifest entry plus the
+//! config files or screenshots it reads) are hashed and stored in
+//! `config/asset_hashes.json`, like the heatmap tool's level hashes, so reruns only
+//! rebuild outputs whose inputs changed or whose files are missing.
+
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+use super::ball::{OPTIONS_FILE, PALETTES_FILE};
+use super::integrity::{AssetHashCache, ball_config_hash, expected_ball_textures};
+use super::{OutputOptions, ball, gif_baseball, gif_wedge, levels, showcase};
+
+/// Default manifest location
+pub const MANIFEST_FILE: &str = "config/generate_manifest.txt";
+
+/// Generator behind an output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OutputKind {
+    Ball,
+    Showcase,
+    Levels,
+    GifWedge,
+    GifBaseball,
+}
+
+impl OutputKind {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "ball" => Some(OutputKind::Ball),
+            "showcase" => Some(OutputKind::Showcase),
+            "levels" => Some(OutputKind::Levels),
+            "gif_wedge" => Some(OutputKind::GifWedge),
+            "gif_baseball" => Some(OutputKind::GifBaseball),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutputKind::Ball => "ball",
+            OutputKind::Showcase => "showcase",
+            OutputKind::Levels => "levels",
+            OutputKind::GifWedge => "gif_wedge",
+            OutputKind::GifBaseball => "gif_baseball",
+        }
+    }
+
+    /// Output file when the manifest doesn't set `path`
+    fn default_path(&self) -> Option<&'static str> {
+        match self {
+            OutputKind::Ball => None,
+            OutputKind::Showcase => Some(showcase::OUTPUT_PATH),
+            OutputKind::Levels => Some(levels::OUTPUT_PATH),
+            OutputKind::GifWedge => Some(gif_wedge::OUTPUT_PATH),
+            OutputKind::GifBaseball => Some(gif_baseball::OUTPUT_PATH),
+        }
+    }
+}
+
+/// One manifest entry
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputSpec {
+    pub name: String,
+    pub kind: OutputKind,
+    pub options: OutputOptions,
+}
+
+/// Parsed manifest, outputs in file order
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Manifest {
+    pub outputs: Vec<OutputSpec>,
+}
+
+impl Manifest {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("read {}: {}", path, e))?;
+        Self::parse(&content).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    /// Parse `output:` blocks. Keys: kind, path, size, palettes (indices or `all`), frames.
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let mut outputs: Vec<OutputSpec> = Vec::new();
+        // kind is required; tracked per output until the end of the file
+        let mut has_kind: Vec<bool> = Vec::new();
+
+        for (line_no, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let err = |msg: String| format!("line {}: {}", line_no + 1, msg);
+            let Some((key, value)) = line.split_once(':') else {
+                return Err(err(format!("expected `key: value`, got '{}'", line)));
+            };
+            let value = value.trim();
+
+            if key == "output" {
+                if outputs.iter().any(|o| o.name == value) {
+                    return Err(err(format!("duplicate output '{}'", value)));
+                }
+                outputs.push(OutputSpec {
+                    name: value.to_string(),
+                    kind: OutputKind::Ball,
+                    options: OutputOptions::default(),
+                });
+                has_kind.push(false);
+                continue;
+            }
+
+            let Some(current) = outputs.last_mut() else {
+                return Err(err(format!("'{}' before the first output:", key)));
+            };
+            let parse_u32 = |v: &str| {
+                v.parse::<u32>()
+                    .map_err(|_| err(format!("invalid {} '{}'", key, v)))
+            };
+            match key {
+                "kind" => {
+                    current.kind = OutputKind::from_str(value)
+                        .ok_or_else(|| err(format!("unknown kind '{}'", value)))?;
+                    *has_kind.last_mut().unwrap() = true;
+                }
+                "path" => current.options.path = Some(value.to_string()),
+                "size" => current.options.size = Some(parse_u32(value)?),
+                "frames" => current.options.frames = Some(parse_u32(value)?),
+                "palettes" if value == "all" => current.options.palettes = None,
+                "palettes" => {
+                    let indices = value
+                        .split_whitespace()
+                        .map(|v| v.parse::<usize>())
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(|_| err(format!("invalid palettes '{}'", value)))?;
+                    current.options.palettes = Some(indices);
+                }
+                _ => return Err(err(format!("unknown key '{}'", key))),
+            }
+        }
+
+        if let Some(i) = has_kind.iter().position(|seen| !seen) {
+            return Err(format!("output '{}' has no kind", outputs[i].name));
+        }
+        Ok(Manifest { outputs })
+    }
+}
+
+/// Hash of everything an output is built from
+fn input_hash(spec: &OutputSpec) -> String {
+    let mut hasher = DefaultHasher::new();
+    spec.kind.hash(&mut hasher);
+    format!("{:?}", spec.options).hash(&mut hasher);
+    match spec.kind {
+        // Showcase reads the generated textures, which follow the ball config
+        OutputKind::Ball | OutputKind::Showcase => ball_config_hash().hash(&mut hasher),
+        OutputKind::Levels => {
+            let mut shots: Vec<(String, u64)> = fs::read_dir(levels::SCREENSHOT_DIR)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .filter_map(|entry| {
+                    let len = entry.metadata().ok()?.len();
+                    Some((entry.file_name().to_string_lossy().to_string(), len))
+                })
+                .collect();
+            shots.sort();
+            shots.hash(&mut hasher);
+        }
+        OutputKind::GifWedge | OutputKind::GifBaseball => {}
+    }
+    format!("{:016x}", hasher.finish())
+}
+
+/// Whether the files an output produces are all on disk
+fn output_exists(spec: &OutputSpec) -> bool {
+    match spec.kind.default_path() {
+        Some(default) => Path::new(spec.options.path.as_deref().unwrap_or(default)).exists(),
+        None => {
+            let options = fs::read_to_string(OPTIONS_FILE).unwrap_or_default();
+            let palettes = fs::read_to_string(PALETTES_FILE).unwrap_or_default();
+            expected_ball_textures(&options, &palettes)
+                .iter()
+                .filter(|path| {
+                    let Some(only) = &spec.options.palettes else {
+                        return true;
+                    };
+                    // ball_<style>_<palette>.png
+                    path.trim_end_matches(".png")
+                        .rsplit('_')
+                        .next()
+                        .and_then(|idx| idx.parse::<usize>().ok())
+                        .is_some_and(|idx| only.contains(&idx))
+                })
+                .all(|path| Path::new(path).exists())
+        }
+    }
+}
+
+/// Build every output in the manifest. Outputs whose inputs match the recorded
+/// hash and whose files exist are skipped unless `refresh` is set.
+/// Returns the names of outputs that failed.
+pub fn run_all(manifest: &Manifest, refresh: bool) -> Vec<String> {
+    let mut hashes = AssetHashCache::load();
+    let mut failed = Vec::new();
+
+    for spec in &manifest.outputs {
+        let hash = input_hash(spec);
+        let up_to_date = hashes.outputs.get(&spec.name) == Some(&hash) && output_exists(spec);
+        if up_to_date && !refresh {
+            println!("=== {} ({}): up to date", spec.name, spec.kind.as_str());
+            continue;
+        }
+
+        println!("=== {} ({}) ===\n", spec.name, spec.kind.as_str());
+        let result = match spec.kind {
+            OutputKind::Ball => {
+                fs::create_dir_all("assets/textures/balls").ok();
+                ball::run_with(&spec.options);
+                Ok(())
+            }
+            OutputKind::Showcase => {
+                showcase::run_with(&spec.options);
+                Ok(())
+            }
+            OutputKind::Levels => levels::run_with(&spec.options),
+            OutputKind::GifWedge => {
+                gif_wedge::run_with(&spec.options);
+                Ok(())
+            }
+            OutputKind::GifBaseball => {
+                gif_baseball::run_with(&spec.options);
+                Ok(())
+            }
+        };
+        println!();
+
+        match result {
+            Ok(()) => {
+                hashes.outputs.insert(spec.name.clone(), hash);
+                // Save after each output so an interrupted run keeps its progress
+                hashes.save();
+            }
+            Err(e) => {
+                eprintln!("{}: {}", spec.name, e);
+                failed.push(spec.name.clone());
+            }
+        }
+    }
+
+    failed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_manifest_blocks() {
+        let manifest = Manifest::parse(
+            "# outputs\n\
+             output: textures\n\
+             kind: ball\n\
+             palettes: all\n\
+             \n\
+             output: showcase\n\
+             kind: showcase\n\
+             size: 96\n\
+             palettes: 0 2 8\n\
+             path: showcase/small.png\n\
+             \n\
+             output: wedge\n\
+             kind: gif_wedge\n\
+             frames: 60\n",
+        )
+        .unwrap();
+
+        assert_eq!(manifest.outputs.len(), 3);
+        assert_eq!(manifest.outputs[0].kind, OutputKind::Ball);
+        assert_eq!(manifest.outputs[0].options, OutputOptions::default());
+        let showcase = &manifest.outputs[1].options;
+        assert_eq!(showcase.size, Some(96));
+        assert_eq!(showcase.palettes, Some(vec![0, 2, 8]));
+        assert_eq!(showcase.path.as_deref(), Some("showcase/small.png"));
+        assert_eq!(manifest.outputs[2].kind, OutputKind::GifWedge);
+        assert_eq!(manifest.outputs[2].options.frames, Some(60));
+
+        assert!(Manifest::parse("kind: ball\n").is_err());
+        assert!(Manifest::parse("output: a\nkind: mesh\n").is_err());
+        assert!(Manifest::parse("output: a\noutput: a\n").is_err());
+        assert!(Manifest::parse("output: a\nsize: 64\n").is_err());
+    }
+}
@@ -4,10 +4,32 @@
 //! - Ball textures for all styles × palettes
 //! - Ball styles showcase image
 //! - Level showcase grid
-//! - Animated GIFs (wedge, baseball)
+//! - Animated GIFs (wedge, baseball); `gif` is the encoder they share with live capture
+//!
+//! `run_all` builds every output listed in the manifest (config/generate_manifest.txt),
+//! skipping outputs whose inputs haven't changed. `integrity` checks generated
+//! assets against the current config at startup.
 
 pub mod ball;
+pub mod gif;
 pub mod gif_baseball;
 pub mod gif_wedge;
+pub mod integrity;
 pub mod levels;
+pub mod manifest;
 pub mod showcase;
+
+pub use manifest::{MANIFEST_FILE, Manifest, run_all};
+
+/// Per-output overrides from the manifest (None = the generator's default)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OutputOptions {
+    /// Output file (ball textures always go to assets/textures/balls/)
+    pub path: Option<String>,
+    /// Size in pixels: texture size, showcase cell, screenshot width or GIF frame
+    pub size: Option<u32>,
+    /// Palette indices to include
+    pub palettes: Option<Vec<usize>>,
+    /// GIF frame count
+    pub frames: Option<u32>,
+}
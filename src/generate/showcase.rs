@@ -7,6 +7,8 @@ use image::{Rgba, RgbaImage};
 use imageproc::drawing::draw_text_mut;
 use std::fs;
 
+use super::OutputOptions;
+
 const BALL_SIZE: u32 = 128;
 const PADDING: u32 = 16;
 const COVERAGE_COL_WIDTH: u32 = 70; // Width for coverage percentage column
@@ -15,6 +17,8 @@ const COVERAGE_COL_WIDTH: u32 = 70; // Width for coverage percentage column
 // Aurora (0), Ocean Fire (2), Synthwave (8), Monochrome (9), Blood Moon (16)
 const SHOWCASE_PALETTES: [usize; 5] = [0, 2, 8, 9, 16];
 
+pub(super) const OUTPUT_PATH: &str = "showcase/ball_styles_showcase.png";
+
 // Embed a simple font (using system font path for macOS)
 const FONT_DATA: &[u8] = include_bytes!("/System/Library/Fonts/Helvetica.ttc");
 
@@ -86,6 +90,17 @@ fn calculate_coverage(ball: &RgbaImage) -> (f32, f32) {
 }
 
 pub fn run() {
+    run_with(&OutputOptions::default());
+}
+
+/// Generate the showcase. `size` is the ball cell size, `palettes` the columns.
+pub fn run_with(options: &OutputOptions) {
+    let ball_size = options.size.unwrap_or(BALL_SIZE);
+    let palettes = options
+        .palettes
+        .clone()
+        .unwrap_or_else(|| SHOWCASE_PALETTES.to_vec());
+
     // Load style names from ball_options.txt
     let style_names = load_style_names();
     let palette_names = load_palette_names();
@@ -93,18 +108,18 @@ pub fn run() {
     println!(
         "Creating showcase with {} styles x {} palettes",
         style_names.len(),
-        SHOWCASE_PALETTES.len()
+        palettes.len()
     );
 
     // Calculate image dimensions
-    let cols = SHOWCASE_PALETTES.len() as u32;
+    let cols = palettes.len() as u32;
     let rows = style_names.len() as u32;
 
     let style_label_width: u32 = 120; // Left column for style names
     let palette_label_height: u32 = 40; // Top row for palette names
 
-    let width = style_label_width + cols * (BALL_SIZE + PADDING) + PADDING + COVERAGE_COL_WIDTH;
-    let height = palette_label_height + rows * (BALL_SIZE + PADDING) + PADDING;
+    let width = style_label_width + cols * (ball_size + PADDING) + PADDING + COVERAGE_COL_WIDTH;
+    let height = palette_label_height + rows * (ball_size + PADDING) + PADDING;
 
     let mut showcase = RgbaImage::new(width, height);
 
@@ -120,12 +135,12 @@ pub fn run() {
     let text_color = Rgba([220u8, 220u8, 220u8, 255u8]);
 
     // Draw palette names in header
-    for (col, &palette_idx) in SHOWCASE_PALETTES.iter().enumerate() {
+    for (col, &palette_idx) in palettes.iter().enumerate() {
         let palette_name = palette_names
             .get(palette_idx)
             .map(|s| s.as_str())
             .unwrap_or("?");
-        let x = style_label_width + PADDING + (col as u32) * (BALL_SIZE + PADDING) + 10;
+        let x = style_label_width + PADDING + (col as u32) * (ball_size + PADDING) + 10;
         let y = 12;
         draw_text_mut(
             &mut showcase,
@@ -139,7 +154,7 @@ pub fn run() {
     }
 
     // Draw coverage header
-    let coverage_x = style_label_width + PADDING + (cols) * (BALL_SIZE + PADDING) + 5;
+    let coverage_x = style_label_width + PADDING + (cols) * (ball_size + PADDING) + 5;
     draw_text_mut(
         &mut showcase,
         text_color,
@@ -154,7 +169,7 @@ pub fn run() {
     for (row, style_name) in style_names.iter().enumerate() {
         // Draw style name label
         let label_y =
-            palette_label_height + PADDING + (row as u32) * (BALL_SIZE + PADDING) + BALL_SIZE / 2
+            palette_label_height + PADDING + (row as u32) * (ball_size + PADDING) + ball_size / 2
                 - 9;
         draw_text_mut(
             &mut showcase,
@@ -168,7 +183,7 @@ pub fn run() {
 
         let mut row_coverage: Option<(f32, f32)> = None;
 
-        for (col, &palette_idx) in SHOWCASE_PALETTES.iter().enumerate() {
+        for (col, &palette_idx) in palettes.iter().enumerate() {
             let filename = format!(
                 "assets/textures/balls/ball_{}_{}.png",
                 style_name, palette_idx
@@ -184,8 +199,8 @@ pub fn run() {
                     }
 
                     // Calculate position
-                    let x = style_label_width + PADDING + (col as u32) * (BALL_SIZE + PADDING);
-                    let y = palette_label_height + PADDING + (row as u32) * (BALL_SIZE + PADDING);
+                    let x = style_label_width + PADDING + (col as u32) * (ball_size + PADDING);
+                    let y = palette_label_height + PADDING + (row as u32) * (ball_size + PADDING);
 
                     // Copy ball texture to showcase with alpha blending
                     for (bx, by, pixel) in ball.enumerate_pixels() {
@@ -218,8 +233,8 @@ pub fn run() {
             let coverage_text = format!("{:.0}/{:.0}", left_pct, right_pct);
             let cov_y = palette_label_height
                 + PADDING
-                + (row as u32) * (BALL_SIZE + PADDING)
-                + BALL_SIZE / 2
+                + (row as u32) * (ball_size + PADDING)
+                + ball_size / 2
                 - 9;
             draw_text_mut(
                 &mut showcase,
@@ -248,12 +263,12 @@ pub fn run() {
     }
 
     // Save the showcase
-    let output_path = "showcase/ball_styles_showcase.png";
+    let output_path = options.path.as_deref().unwrap_or(OUTPUT_PATH);
     showcase.save(output_path).expect("Failed to save showcase");
 
     println!("\nShowcase saved to: {}", output_path);
     println!("\nStyles: {:?}", style_names);
-    let showcase_palette_names: Vec<_> = SHOWCASE_PALETTES
+    let showcase_palette_names: Vec<_> = palettes
         .iter()
         .map(|&i| palette_names.get(i).map(|s| s.as_str()).unwrap_or("?"))
         .collect();
@@ -0,0 +1,239 @@
+//! Per-device input - each keyboard/gamepad drives its own player (split-input co-op)
+//!
+//! `capture_input` merges the keyboard and every gamepad into one `PlayerInput`
+//! for the single human player. With `--split-input`, `InputDeviceAssignment`
+//! binds the keyboard and each connected gamepad to a player entity instead,
+//! and `capture_device_input` writes each device's input straight into that
+//! player's `InputState`. Devices take free players in order: left, right, then
+//! the 2v2 teammates. A player without a device stays AI, and unplugging a pad
+//! hands its player back to the AI.
+
+use bevy::prelude::*;
+
+use super::{InputBindings, KeyBindings, PlayerInput, capture_slot, emit_controller_input};
+use crate::ai::{InputState, move_buffered_input};
+use crate::config::MovementConfig;
+use crate::events::EventBus;
+use crate::player::{HumanControlTarget, HumanControlled, Player, Team, Teammate};
+use crate::ui::TweakPanelState;
+
+/// Something a human plays with
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputDevice {
+    /// The keyboard, using the primary slot's keys
+    Keyboard,
+    /// One gamepad, by its entity
+    Gamepad(Entity),
+}
+
+/// One device driving one player, with the device's buffered input
+pub struct DeviceBinding {
+    pub device: InputDevice,
+    pub player: Entity,
+    pub input: PlayerInput,
+}
+
+/// Which device drives which player (`--split-input`; disabled by default)
+#[derive(Resource, Default)]
+pub struct InputDeviceAssignment {
+    pub enabled: bool,
+    pub bindings: Vec<DeviceBinding>,
+}
+
+impl InputDeviceAssignment {
+    pub fn from_args(args: &[String]) -> Self {
+        Self {
+            enabled: args.iter().any(|a| a == "--split-input"),
+            bindings: Vec::new(),
+        }
+    }
+
+    pub fn player_for(&self, device: InputDevice) -> Option<Entity> {
+        self.bindings
+            .iter()
+            .find(|b| b.device == device)
+            .map(|b| b.player)
+    }
+
+    pub fn device_for(&self, player: Entity) -> Option<InputDevice> {
+        self.bindings
+            .iter()
+            .find(|b| b.player == player)
+            .map(|b| b.device)
+    }
+
+    /// Drop bindings whose device or player is gone, then bind each unbound
+    /// device (in order) to the first player in `slots` without one
+    pub fn sync(&mut self, devices: &[InputDevice], slots: &[Entity]) {
+        self.bindings
+            .retain(|b| devices.contains(&b.device) && slots.contains(&b.player));
+        for &device in devices {
+            if self.player_for(device).is_some() {
+                continue;
+            }
+            let Some(&player) = slots.iter().find(|p| self.device_for(**p).is_none()) else {
+                break;
+            };
+            self.bindings.push(DeviceBinding {
+                device,
+                player,
+                input: PlayerInput::default(),
+            });
+        }
+    }
+}
+
+/// Run condition: split-input co-op is on
+pub fn split_input_active(assignment: Option<Res<InputDeviceAssignment>>) -> bool {
+    assignment.is_some_and(|a| a.enabled)
+}
+
+/// Bind devices to players, keep `HumanControlled` in step with the bindings,
+/// and write each device's input into its player's InputState.
+/// Replaces capture_input/copy_human_input/swap_control when split input is on.
+#[allow(clippy::too_many_arguments)]
+pub fn capture_device_input(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<(Entity, &Gamepad)>,
+    key_bindings: Res<InputBindings>,
+    movement: Res<MovementConfig>,
+    mut assignment: ResMut<InputDeviceAssignment>,
+    panel_state: Res<TweakPanelState>,
+    time: Res<Time>,
+    mut event_bus: ResMut<EventBus>,
+    mut human_target: ResMut<HumanControlTarget>,
+    mut players: Query<
+        (
+            Entity,
+            &Team,
+            Has<Teammate>,
+            Has<HumanControlled>,
+            &mut InputState,
+        ),
+        With<Player>,
+    >,
+) {
+    // Keyboard first, then pads in connection order
+    let mut pads: Vec<Entity> = gamepads.iter().map(|(entity, _)| entity).collect();
+    pads.sort();
+    let devices: Vec<InputDevice> = std::iter::once(InputDevice::Keyboard)
+        .chain(pads.into_iter().map(InputDevice::Gamepad))
+        .collect();
+
+    // Regular players before teammates, left before right
+    let mut slots: Vec<(Entity, bool, Team)> = players
+        .iter()
+        .map(|(entity, team, teammate, ..)| (entity, teammate, *team))
+        .collect();
+    slots.sort_by_key(|&(_, teammate, team)| (teammate, team == Team::Right));
+    let slots: Vec<Entity> = slots.into_iter().map(|(entity, ..)| entity).collect();
+
+    assignment.sync(&devices, &slots);
+
+    for (entity, team, _, human, mut input_state) in &mut players {
+        match (assignment.device_for(entity), human) {
+            (Some(device), false) => {
+                commands.entity(entity).insert(HumanControlled);
+                *input_state = InputState::default();
+                info!("Split input: {:?} drives the {:?} player", device, team);
+            }
+            (None, true) => {
+                commands.entity(entity).remove::<HumanControlled>();
+                info!("Split input: {:?} player back to AI", team);
+            }
+            _ => {}
+        }
+    }
+    human_target.0 = assignment
+        .player_for(InputDevice::Keyboard)
+        .and_then(|entity| players.get(entity).ok())
+        .map(|(_, team, ..)| team.player_id());
+
+    // Don't capture game input when tweak panel is open (uses arrow keys)
+    if panel_state.panel_visible {
+        return;
+    }
+
+    // Keyboard uses the primary keys, pads use buttons only; nobody swaps
+    let keys = KeyBindings {
+        swap: Vec::new(),
+        gamepads: false,
+        ..key_bindings.primary.clone()
+    };
+    let no_keys = KeyBindings {
+        left: Vec::new(),
+        right: Vec::new(),
+        jump: Vec::new(),
+        pickup: Vec::new(),
+        throw: Vec::new(),
+        pass: Vec::new(),
+        swap: Vec::new(),
+        gamepads: true,
+    };
+    for binding in &mut assignment.bindings {
+        let Ok((_, team, _, _, mut input_state)) = players.get_mut(binding.player) else {
+            continue;
+        };
+        let edges = match binding.device {
+            InputDevice::Keyboard => capture_slot(
+                &keys,
+                &keyboard,
+                &[],
+                &movement,
+                &mut binding.input,
+                time.delta_secs(),
+            ),
+            InputDevice::Gamepad(pad) => {
+                let Ok((_, gamepad)) = gamepads.get(pad) else {
+                    continue;
+                };
+                capture_slot(
+                    &no_keys,
+                    &keyboard,
+                    &[gamepad],
+                    &movement,
+                    &mut binding.input,
+                    time.delta_secs(),
+                )
+            }
+        };
+        emit_controller_input(&mut event_bus, team.player_id(), &binding.input, edges);
+        move_buffered_input(&mut binding.input, &mut input_state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_devices_fill_free_slots_in_order() {
+        let mut world = World::new();
+        let [left, right, left_mate] = [(); 3].map(|_| world.spawn_empty().id());
+        let [pad_a, pad_b, pad_c] = [(); 3].map(|_| world.spawn_empty().id());
+        let slots = [left, right, left_mate];
+
+        let mut assignment = InputDeviceAssignment::default();
+        assignment.sync(&[InputDevice::Keyboard], &slots);
+        assert_eq!(assignment.player_for(InputDevice::Keyboard), Some(left));
+        assert_eq!(assignment.device_for(right), None);
+
+        // Pads join the next free players; with no player left, a pad stays unbound
+        let all = [
+            InputDevice::Keyboard,
+            InputDevice::Gamepad(pad_a),
+            InputDevice::Gamepad(pad_b),
+            InputDevice::Gamepad(pad_c),
+        ];
+        assignment.sync(&all, &slots);
+        assert_eq!(assignment.player_for(all[1]), Some(right));
+        assert_eq!(assignment.player_for(all[2]), Some(left_mate));
+        assert_eq!(assignment.player_for(all[3]), None);
+
+        // Unplugging a pad frees its player for the next pad
+        assignment.sync(&[all[0], all[2], all[3]], &slots);
+        assert_eq!(assignment.device_for(right), Some(all[3]));
+        assert_eq!(assignment.player_for(all[2]), Some(left_mate));
+    }
+}
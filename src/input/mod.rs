@@ -1,9 +1,13 @@
-//! Input module - PlayerInput resource and capture_input system
+//! Input module - PlayerInput resources, key bindings and capture systems
+
+mod devices;
+
+pub use devices::*;
 
 use bevy::prelude::*;
 
-use crate::constants::*;
-use crate::events::{ControllerSource, EventBus, GameEvent};
+use crate::config::MovementConfig;
+use crate::events::{ControllerSource, EventBus, GameEvent, PlayerId};
 use crate::player::HumanControlTarget;
 use crate::ui::TweakPanelState;
 
@@ -14,40 +18,162 @@ pub struct PlayerInput {
     pub jump_buffer_timer: f32, // Time remaining in jump buffer
     pub jump_held: bool,        // Is jump button currently held
     pub pickup_pressed: bool,   // West button - pick up ball
+    pub pickup_held: bool,      // West button held (scoops rolling balls)
+    pub pass_pressed: bool,     // North button - pass to a teammate (2v2)
     pub throw_held: bool,       // R shoulder - charging throw
     pub throw_released: bool,   // R shoulder released - execute throw
     pub swap_pressed: bool,     // L shoulder / Q key - swap which player you control
     pub advance_level_pressed: bool, // L shoulder / Q key - advance to next level (Reachability)
 }
 
-/// Runs in Update to capture input state before it's cleared.
-/// Also emits ControllerInput events to the EventBus for auditability.
-pub fn capture_input(
-    keyboard: Res<ButtonInput<KeyCode>>,
-    gamepads: Query<&Gamepad>,
-    mut input: ResMut<PlayerInput>,
-    panel_state: Res<TweakPanelState>,
-    time: Res<Time>,
-    mut event_bus: ResMut<EventBus>,
-    human_target: Res<HumanControlTarget>,
-) {
-    // Don't capture game input when tweak panel is open (uses arrow keys)
-    if panel_state.panel_visible {
-        return;
+/// Buffered input for the second keyboard player (local versus only)
+#[derive(Resource, Default)]
+pub struct SecondPlayerInput(pub PlayerInput);
+
+/// Keys (and optionally gamepads) that drive one input slot
+#[derive(Clone, Debug)]
+pub struct KeyBindings {
+    pub left: Vec<KeyCode>,
+    pub right: Vec<KeyCode>,
+    pub jump: Vec<KeyCode>,
+    pub pickup: Vec<KeyCode>,
+    pub throw: Vec<KeyCode>,
+    pub pass: Vec<KeyCode>,
+    /// Empty in local versus - control swapping makes no sense with two humans
+    pub swap: Vec<KeyCode>,
+    /// Whether connected gamepads also feed this slot
+    pub gamepads: bool,
+}
+
+impl KeyBindings {
+    /// Presets selectable for the primary slot (settings `key_bindings`), with descriptions
+    pub const PRESETS: [(&'static str, &'static str); 3] = [
+        ("solo", "WASD or arrows, E pick up, F throw, C pass, Q swap"),
+        ("wasd", "WASD, E pick up, F throw, C pass"),
+        (
+            "arrows",
+            "Arrows, Numpad 1 / . pick up, Numpad 0 / / throw, Numpad 2 / , pass",
+        ),
+    ];
+
+    /// Bindings for a preset name from PRESETS
+    pub fn from_preset(name: &str) -> Option<Self> {
+        match name {
+            "solo" => Some(Self::solo()),
+            "wasd" => Some(Self::wasd()),
+            "arrows" => Some(Self {
+                gamepads: true,
+                ..Self::arrows()
+            }),
+            _ => None,
+        }
     }
+
+    /// Single player: WASD and arrows both move, gamepads enabled
+    pub fn solo() -> Self {
+        Self {
+            left: vec![KeyCode::KeyA, KeyCode::ArrowLeft],
+            right: vec![KeyCode::KeyD, KeyCode::ArrowRight],
+            jump: vec![KeyCode::Space, KeyCode::KeyW, KeyCode::ArrowUp],
+            pickup: vec![KeyCode::KeyE],
+            throw: vec![KeyCode::KeyF],
+            pass: vec![KeyCode::KeyC],
+            swap: vec![KeyCode::KeyQ],
+            gamepads: true,
+        }
+    }
+
+    /// Left half of a shared keyboard: WASD + E/F (gamepads still work)
+    pub fn wasd() -> Self {
+        Self {
+            left: vec![KeyCode::KeyA],
+            right: vec![KeyCode::KeyD],
+            jump: vec![KeyCode::KeyW, KeyCode::Space],
+            pickup: vec![KeyCode::KeyE],
+            throw: vec![KeyCode::KeyF],
+            pass: vec![KeyCode::KeyC],
+            swap: Vec::new(),
+            gamepads: true,
+        }
+    }
+
+    /// Right half of a shared keyboard: arrows + numpad 1/0 (or . and / without a numpad)
+    pub fn arrows() -> Self {
+        Self {
+            left: vec![KeyCode::ArrowLeft],
+            right: vec![KeyCode::ArrowRight],
+            jump: vec![KeyCode::ArrowUp],
+            pickup: vec![KeyCode::Numpad1, KeyCode::Period],
+            throw: vec![KeyCode::Numpad0, KeyCode::Slash],
+            pass: vec![KeyCode::Numpad2, KeyCode::Comma],
+            swap: Vec::new(),
+            gamepads: false,
+        }
+    }
+}
+
+/// Active input slots. The primary slot drives the human-controlled player; the
+/// secondary slot exists only in local versus and always drives the right player.
+#[derive(Resource, Clone, Debug)]
+pub struct InputBindings {
+    pub primary: KeyBindings,
+    pub secondary: Option<KeyBindings>,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        Self {
+            primary: KeyBindings::solo(),
+            secondary: None,
+        }
+    }
+}
+
+impl InputBindings {
+    /// Two humans on one keyboard: WASD (left player) vs arrows + numpad (right player)
+    pub fn local_versus() -> Self {
+        Self {
+            primary: KeyBindings::wasd(),
+            secondary: Some(KeyBindings::arrows()),
+        }
+    }
+
+    pub fn is_local_versus(&self) -> bool {
+        self.secondary.is_some()
+    }
+}
+
+/// Buttons that changed this frame (reported in ControllerInput events)
+struct InputEdges {
+    jump_pressed: bool,
+    pickup_pressed: bool,
+    throw_released: bool,
+}
+
+/// Read one slot's keys and `pads` into its buffered input
+fn capture_slot(
+    bindings: &KeyBindings,
+    keyboard: &ButtonInput<KeyCode>,
+    pads: &[&Gamepad],
+    movement: &MovementConfig,
+    input: &mut PlayerInput,
+    dt: f32,
+) -> InputEdges {
+    let pads = || pads.iter();
+
     // Horizontal movement (continuous - overwrite each frame)
     let mut move_x = 0.0;
 
-    if keyboard.pressed(KeyCode::KeyA) || keyboard.pressed(KeyCode::ArrowLeft) {
+    if keyboard.any_pressed(bindings.left.iter().copied()) {
         move_x -= 1.0;
     }
-    if keyboard.pressed(KeyCode::KeyD) || keyboard.pressed(KeyCode::ArrowRight) {
+    if keyboard.any_pressed(bindings.right.iter().copied()) {
         move_x += 1.0;
     }
 
-    for gamepad in &gamepads {
+    for gamepad in pads() {
         if let Some(stick_x) = gamepad.get(GamepadAxis::LeftStickX) {
-            if stick_x.abs() > STICK_DEADZONE {
+            if stick_x.abs() > movement.stick_deadzone {
                 move_x += stick_x;
             }
         }
@@ -56,39 +182,38 @@ pub fn capture_input(
     input.move_x = move_x.clamp(-1.0, 1.0);
 
     // Jump button state
-    let jump_pressed = keyboard.just_pressed(KeyCode::Space)
-        || keyboard.just_pressed(KeyCode::KeyW)
-        || keyboard.just_pressed(KeyCode::ArrowUp)
-        || gamepads
-            .iter()
-            .any(|gp| gp.just_pressed(GamepadButton::South));
-
-    input.jump_held = keyboard.pressed(KeyCode::Space)
-        || keyboard.pressed(KeyCode::KeyW)
-        || keyboard.pressed(KeyCode::ArrowUp)
-        || gamepads.iter().any(|gp| gp.pressed(GamepadButton::South));
+    let jump_pressed = keyboard.any_just_pressed(bindings.jump.iter().copied())
+        || pads().any(|gp| gp.just_pressed(GamepadButton::South));
+
+    input.jump_held = keyboard.any_pressed(bindings.jump.iter().copied())
+        || pads().any(|gp| gp.pressed(GamepadButton::South));
 
     // Jump buffering - reset timer on press, count down otherwise
     if jump_pressed {
-        input.jump_buffer_timer = JUMP_BUFFER_TIME;
+        input.jump_buffer_timer = movement.jump_buffer_time;
     } else {
-        input.jump_buffer_timer = (input.jump_buffer_timer - time.delta_secs()).max(0.0);
+        input.jump_buffer_timer = (input.jump_buffer_timer - dt).max(0.0);
     }
 
     // Pickup (West button / E key) - accumulate until consumed
-    let pickup_just_pressed = keyboard.just_pressed(KeyCode::KeyE)
-        || gamepads
-            .iter()
-            .any(|gp| gp.just_pressed(GamepadButton::West));
+    let pickup_just_pressed = keyboard.any_just_pressed(bindings.pickup.iter().copied())
+        || pads().any(|gp| gp.just_pressed(GamepadButton::West));
     if pickup_just_pressed {
         input.pickup_pressed = true;
     }
+    input.pickup_held = keyboard.any_pressed(bindings.pickup.iter().copied())
+        || pads().any(|gp| gp.pressed(GamepadButton::West));
+
+    // Pass (North button / C key) - accumulate until consumed
+    if keyboard.any_just_pressed(bindings.pass.iter().copied())
+        || pads().any(|gp| gp.just_pressed(GamepadButton::North))
+    {
+        input.pass_pressed = true;
+    }
 
     // Throw (R shoulder / F key)
-    let throw_held_now = keyboard.pressed(KeyCode::KeyF)
-        || gamepads
-            .iter()
-            .any(|gp| gp.pressed(GamepadButton::RightTrigger));
+    let throw_held_now = keyboard.any_pressed(bindings.throw.iter().copied())
+        || pads().any(|gp| gp.pressed(GamepadButton::RightTrigger));
 
     // Accumulate throw_released until consumed (like jump buffering)
     let throw_just_released = input.throw_held && !throw_held_now;
@@ -99,27 +224,134 @@ pub fn capture_input(
 
     // Swap control (L shoulder / Q key) - accumulate until consumed
     // Also triggers advance_level for Reachability protocol
-    if keyboard.just_pressed(KeyCode::KeyQ)
-        || gamepads
-            .iter()
-            .any(|gp| gp.just_pressed(GamepadButton::LeftTrigger))
+    if keyboard.any_just_pressed(bindings.swap.iter().copied())
+        || (!bindings.swap.is_empty()
+            && pads().any(|gp| gp.just_pressed(GamepadButton::LeftTrigger)))
     {
         input.swap_pressed = true;
         input.advance_level_pressed = true;
     }
 
+    InputEdges {
+        jump_pressed,
+        pickup_pressed: pickup_just_pressed,
+        throw_released: throw_just_released,
+    }
+}
+
+fn emit_controller_input(
+    event_bus: &mut EventBus,
+    player: PlayerId,
+    input: &PlayerInput,
+    edges: InputEdges,
+) {
+    event_bus.emit(GameEvent::ControllerInput {
+        player,
+        source: ControllerSource::Human,
+        move_x: input.move_x,
+        jump: input.jump_held,
+        jump_pressed: edges.jump_pressed,
+        throw: input.throw_held,
+        throw_released: edges.throw_released,
+        pickup: edges.pickup_pressed,
+    });
+}
+
+/// Runs in Update to capture input state before it's cleared.
+/// Also emits ControllerInput events to the EventBus for auditability.
+#[allow(clippy::too_many_arguments)]
+pub fn capture_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    bindings: Res<InputBindings>,
+    movement: Res<MovementConfig>,
+    mut input: ResMut<PlayerInput>,
+    panel_state: Res<TweakPanelState>,
+    time: Res<Time>,
+    mut event_bus: ResMut<EventBus>,
+    human_target: Res<HumanControlTarget>,
+) {
+    // Don't capture game input when tweak panel is open (uses arrow keys)
+    if panel_state.panel_visible {
+        return;
+    }
+
+    let pads: Vec<&Gamepad> = gamepads
+        .iter()
+        .filter(|_| bindings.primary.gamepads)
+        .collect();
+    let edges = capture_slot(
+        &bindings.primary,
+        &keyboard,
+        &pads,
+        &movement,
+        &mut input,
+        time.delta_secs(),
+    );
+
     // Emit ControllerInput event to EventBus for auditability
     // Only emit if there's a human-controlled player
     if let Some(player) = human_target.0 {
-        event_bus.emit(GameEvent::ControllerInput {
-            player,
-            source: ControllerSource::Human,
-            move_x: input.move_x,
-            jump: input.jump_held,
-            jump_pressed,
-            throw: input.throw_held,
-            throw_released: throw_just_released,
-            pickup: pickup_just_pressed,
-        });
+        emit_controller_input(&mut event_bus, player, &input, edges);
+    }
+}
+
+/// Capture the second keyboard slot (local versus). Always drives the right player.
+pub fn capture_second_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    bindings: Res<InputBindings>,
+    movement: Res<MovementConfig>,
+    mut input: ResMut<SecondPlayerInput>,
+    panel_state: Res<TweakPanelState>,
+    time: Res<Time>,
+    mut event_bus: ResMut<EventBus>,
+) {
+    let Some(secondary) = &bindings.secondary else {
+        return;
+    };
+    if panel_state.panel_visible {
+        return;
+    }
+
+    let pads: Vec<&Gamepad> = gamepads.iter().filter(|_| secondary.gamepads).collect();
+    let edges = capture_slot(
+        secondary,
+        &keyboard,
+        &pads,
+        &movement,
+        &mut input.0,
+        time.delta_secs(),
+    );
+    emit_controller_input(&mut event_bus, PlayerId::R, &input.0, edges);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_versus_slots_share_no_keys() {
+        let bindings = InputBindings::local_versus();
+        let secondary = bindings.secondary.expect("local versus has a second slot");
+        let keys = |b: &KeyBindings| -> Vec<KeyCode> {
+            [
+                &b.left, &b.right, &b.jump, &b.pickup, &b.throw, &b.pass, &b.swap,
+            ]
+            .into_iter()
+            .flatten()
+            .copied()
+            .collect()
+        };
+        let primary_keys = keys(&bindings.primary);
+        for key in keys(&secondary) {
+            assert!(
+                !primary_keys.contains(&key),
+                "{:?} bound to both slots",
+                key
+            );
+        }
+        // Only one slot may read gamepads, or both players would move together
+        assert!(!(bindings.primary.gamepads && secondary.gamepads));
     }
 }
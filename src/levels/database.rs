@@ -5,6 +5,7 @@ use std::collections::hash_map::DefaultHasher;
 use std::fs;
 use std::hash::{Hash, Hasher};
 
+use super::ratings::{LevelRating, LevelRatings};
 use crate::constants::*;
 
 /// Generate a deterministic 16-char hex UUID from a name.
@@ -19,8 +20,182 @@ fn generate_uuid_from_name(name: &str) -> String {
 /// Platform definition in level data
 #[derive(Clone, Debug)]
 pub enum PlatformDef {
-    Mirror { x: f32, y: f32, width: f32 }, // Spawns at -x and +x
-    Center { y: f32, width: f32 },         // Spawns at x=0
+    /// Spawns at -x and +x
+    Mirror { x: f32, y: f32, width: f32 },
+    /// Spawns at x=0
+    Center { y: f32, width: f32 },
+    /// Linked portal pair at (x1, y1) and (x2, y2); entering either end exits the other
+    Portal { x1: f32, y1: f32, x2: f32, y2: f32 },
+    /// Non-solid launch strip at -x and +x (once if x=0); launches upward at `launch`
+    BouncePad {
+        x: f32,
+        y: f32,
+        width: f32,
+        launch: f32,
+    },
+}
+
+impl PlatformDef {
+    /// True for solid platforms (collidable, become nav graph nodes)
+    pub fn is_solid(&self) -> bool {
+        matches!(
+            self,
+            PlatformDef::Mirror { .. } | PlatformDef::Center { .. }
+        )
+    }
+}
+
+/// Rectangular force zone (updraft, crosswind) applying a constant acceleration inside it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WindZone {
+    pub x: f32,      // Center X
+    pub y: f32,      // Center Y relative to floor_y
+    pub width: f32,  // Zone width
+    pub height: f32, // Zone height
+    pub force: Vec2, // Acceleration (pixels/sec²) applied to anything inside
+}
+
+impl WindZone {
+    /// World-space (min, max) corners of this zone
+    pub fn bounds(&self, floor_y: f32) -> (Vec2, Vec2) {
+        let center = Vec2::new(self.x, floor_y + self.y);
+        let half = Vec2::new(self.width, self.height) / 2.0;
+        (center - half, center + half)
+    }
+
+    /// Check if a world-space position is inside this zone
+    pub fn contains(&self, pos: Vec2, floor_y: f32) -> bool {
+        let (min, max) = self.bounds(floor_y);
+        pos.x >= min.x && pos.x <= max.x && pos.y >= min.y && pos.y <= max.y
+    }
+}
+
+/// Oscillating basket path - both baskets slide sinusoidally around their home position
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BasketMotion {
+    pub dx: f32,     // Max horizontal offset, positive = toward center (mirrored per side)
+    pub dy: f32,     // Max vertical offset, positive = up
+    pub period: f32, // Seconds per full cycle
+}
+
+impl BasketMotion {
+    /// Offset from home at time `t` for the right basket (left basket mirrors X)
+    pub fn offset(&self, t: f32) -> Vec2 {
+        if self.period <= 0.0 {
+            return Vec2::ZERO;
+        }
+        let phase = std::f32::consts::TAU * t / self.period;
+        Vec2::new(-self.dx, self.dy) * phase.sin()
+    }
+}
+
+/// Invisible rails that keep the ball in play on open levels: a ceiling above the
+/// visible arena and side rails continuing the walls up to it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BallRails {
+    pub height: f32, // Ceiling rail height above the top of the visible arena
+    pub bounce: f32, // Velocity kept bouncing off a rail (0 = dead stop, 1 = no loss)
+}
+
+impl Default for BallRails {
+    fn default() -> Self {
+        Self {
+            height: BALL_RAIL_HEIGHT,
+            bounce: BALL_RAIL_BOUNCE,
+        }
+    }
+}
+
+/// Arena dimensions for a level (walls, floor, camera framing)
+///
+/// Levels default to the global `ARENA_*` constants; the active level's
+/// dimensions are kept in this resource so systems don't need a database lookup.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct ArenaDims {
+    /// Total width including walls
+    pub width: f32,
+    /// Visible height (camera viewport height)
+    pub height: f32,
+    /// Y of the floor sprite center (floor surface is 20 above this)
+    pub floor_y: f32,
+}
+
+impl Default for ArenaDims {
+    fn default() -> Self {
+        Self {
+            width: ARENA_WIDTH,
+            height: ARENA_HEIGHT,
+            floor_y: ARENA_FLOOR_Y,
+        }
+    }
+}
+
+impl ArenaDims {
+    /// X of the right wall's inner edge (left wall is the negation)
+    pub fn wall_inner(&self) -> f32 {
+        self.width / 2.0 - WALL_THICKNESS
+    }
+
+    /// Walkable floor surface Y
+    pub fn floor_top(&self) -> f32 {
+        self.floor_y + 20.0
+    }
+
+    /// Y at the top of the visible arena
+    pub fn ceiling_y(&self) -> f32 {
+        self.floor_y + self.height
+    }
+
+    /// Y at the vertical center of the visible arena (camera target)
+    pub fn center_y(&self) -> f32 {
+        self.floor_y + self.height / 2.0
+    }
+
+    /// Calculate basket X positions from wall offset
+    pub fn basket_x_from_offset(&self, offset: f32) -> (f32, f32) {
+        let wall_inner = self.wall_inner();
+        (-wall_inner + offset, wall_inner - offset)
+    }
+
+    /// Shift a default-arena spawn position (e.g. `PLAYER_SPAWN_LEFT`) onto this arena's floor
+    pub fn spawn_point(&self, base: Vec3) -> Vec3 {
+        Vec3::new(base.x, base.y + self.floor_y - ARENA_FLOOR_Y, base.z)
+    }
+}
+
+/// Parse an `x y` spawn override
+fn parse_spawn(params: &str) -> Option<Vec2> {
+    let parts: Vec<f32> = params
+        .split_whitespace()
+        .filter_map(|p| p.parse::<f32>().ok())
+        .collect();
+    (parts.len() >= 2).then(|| Vec2::new(parts[0], parts[1]))
+}
+
+/// Parse `off` or `<height> [bounce]` (unparseable values keep the defaults)
+fn parse_rails(params: &str) -> Option<BallRails> {
+    if params.trim() == "off" {
+        return None;
+    }
+    let parts: Vec<f32> = params
+        .split_whitespace()
+        .filter_map(|p| p.parse::<f32>().ok())
+        .collect();
+    let mut rails = BallRails::default();
+    if let Some(height) = parts.first() {
+        rails.height = height.max(0.0);
+    }
+    if let Some(bounce) = parts.get(1) {
+        rails.bounce = bounce.clamp(0.0, 1.0);
+    }
+    Some(rails)
+}
+
+/// True if two center/size boxes overlap
+fn boxes_overlap(a: Vec2, a_size: Vec2, b: Vec2, b_size: Vec2) -> bool {
+    let reach = (a_size + b_size) / 2.0;
+    let diff = (a - b).abs();
+    diff.x < reach.x && diff.y < reach.y
 }
 
 /// Single level definition
@@ -40,6 +215,222 @@ pub struct LevelData {
     pub heatmap_score_weight: f32, // Per-level multiplier for score heatmap influence
     pub heatmap_los_threshold: f32, // Line-of-sight threshold for shooting decisions
     pub heatmap_los_margin: f32, // Line-of-sight margin for shooting decisions
+    pub arena_width: f32,  // Total arena width including walls
+    pub arena_height: f32, // Visible arena height
+    pub floor_y: f32,      // Floor sprite center Y (platform heights are relative to this)
+    pub wind_zones: Vec<WindZone>, // Force zones (empty = no wind)
+    pub basket_motion: Option<BasketMotion>, // None = static baskets
+    pub spawn_left: Option<Vec2>, // Left player spawn override (y relative to floor_y)
+    pub spawn_right: Option<Vec2>, // Right player spawn override (y relative to floor_y)
+    pub spawn_ball: Option<Vec2>, // Ball spawn override (y relative to floor_y)
+    pub ball_rails: Option<BallRails>, // None = no rails (`rails: off`)
+    pub rating: Option<LevelRating>, // From LEVEL_RATINGS_FILE (None = never rated)
+}
+
+impl LevelData {
+    /// Arena dimensions for this level
+    pub fn arena(&self) -> ArenaDims {
+        ArenaDims {
+            width: self.arena_width,
+            height: self.arena_height,
+            floor_y: self.floor_y,
+        }
+    }
+
+    /// Resting basket center (left or right) before any motion is applied
+    pub fn basket_home(&self, left: bool) -> Vec2 {
+        let (left_x, right_x) = self.arena().basket_x_from_offset(self.basket_push_in);
+        let x = if left { left_x } else { right_x };
+        Vec2::new(x, self.floor_y + self.basket_height)
+    }
+
+    /// Basket center at time `t` (seconds on the fixed-timestep clock)
+    pub fn basket_position(&self, left: bool, t: f32) -> Vec2 {
+        let home = self.basket_home(left);
+        let Some(motion) = self.basket_motion else {
+            return home;
+        };
+        let offset = motion.offset(t);
+        let offset = if left {
+            Vec2::new(-offset.x, offset.y)
+        } else {
+            offset
+        };
+        home + offset
+    }
+
+    /// Player spawn for the left or right team (override, or the default spawn on this floor)
+    pub fn player_spawn(&self, left: bool) -> Vec3 {
+        let (spawn, default) = if left {
+            (self.spawn_left, PLAYER_SPAWN_LEFT)
+        } else {
+            (self.spawn_right, PLAYER_SPAWN_RIGHT)
+        };
+        match spawn {
+            Some(pos) => Vec3::new(pos.x, self.floor_y + pos.y, default.z),
+            None => self.arena().spawn_point(default),
+        }
+    }
+
+    /// Ball spawn (override, or the default spawn on this floor)
+    pub fn ball_spawn(&self) -> Vec3 {
+        match self.spawn_ball {
+            Some(pos) => Vec3::new(pos.x, self.floor_y + pos.y, BALL_SPAWN.z),
+            None => self.arena().spawn_point(BALL_SPAWN),
+        }
+    }
+
+    /// Solid boxes (center, size) a spawn has to stay clear of: platforms and corner ramps
+    fn spawn_blockers(&self) -> Vec<(Vec2, Vec2)> {
+        let mut blockers = Vec::new();
+        for platform in &self.platforms {
+            match platform {
+                PlatformDef::Mirror { x, y, width } => {
+                    let size = Vec2::new(*width, 20.0);
+                    blockers.push((Vec2::new(-x, self.floor_y + y), size));
+                    blockers.push((Vec2::new(*x, self.floor_y + y), size));
+                }
+                PlatformDef::Center { y, width } => {
+                    blockers.push((Vec2::new(0.0, self.floor_y + y), Vec2::new(*width, 20.0)));
+                }
+                _ => {}
+            }
+        }
+        if self.step_count > 0 {
+            // Whole staircase as one filled block per corner
+            let arena = self.arena();
+            let width = self.step_push_in + self.corner_width;
+            let size = Vec2::new(width, self.corner_height);
+            let y = arena.floor_top() + self.corner_height / 2.0;
+            let x = arena.wall_inner() - width / 2.0;
+            blockers.push((Vec2::new(-x, y), size));
+            blockers.push((Vec2::new(x, y), size));
+        }
+        blockers
+    }
+
+    /// Why a spawn box is unusable (None = clear of walls, floor and platforms)
+    fn spawn_conflict(&self, center: Vec2, size: Vec2) -> Option<String> {
+        let arena = self.arena();
+        let half = size / 2.0;
+        if center.x.abs() + half.x > arena.wall_inner() {
+            return Some("is inside a wall".to_string());
+        }
+        if center.y - half.y < arena.floor_top() {
+            return Some("is below the floor".to_string());
+        }
+        if center.y + half.y > arena.ceiling_y() {
+            return Some("is above the arena".to_string());
+        }
+        self.spawn_blockers()
+            .into_iter()
+            .find(|(blocker, blocker_size)| boxes_overlap(center, size, *blocker, *blocker_size))
+            .map(|(blocker, _)| {
+                format!(
+                    "overlaps geometry at ({:.0}, {:.0})",
+                    blocker.x,
+                    blocker.y - self.floor_y
+                )
+            })
+    }
+
+    /// Drop spawn overrides that overlap level geometry (the default spawn is used
+    /// instead). Returns one message per dropped override.
+    pub fn validate_spawns(&mut self) -> Vec<String> {
+        let left = self.spawn_conflict(self.player_spawn(true).truncate(), PLAYER_SIZE);
+        let right = self.spawn_conflict(self.player_spawn(false).truncate(), PLAYER_SIZE);
+        let ball = self.spawn_conflict(self.ball_spawn().truncate(), BALL_SIZE);
+
+        let mut issues = Vec::new();
+        for (field, spawn, conflict) in [
+            ("spawn_left", &mut self.spawn_left, left),
+            ("spawn_right", &mut self.spawn_right, right),
+            ("spawn_ball", &mut self.spawn_ball, ball),
+        ] {
+            if let (Some(_), Some(conflict)) = (*spawn, conflict) {
+                issues.push(format!("{} {}, using the default spawn", field, conflict));
+                *spawn = None;
+            }
+        }
+        issues
+    }
+
+    /// Total wind acceleration at a world-space position (overlapping zones add up)
+    pub fn wind_at(&self, pos: Vec2) -> Vec2 {
+        self.wind_zones
+            .iter()
+            .filter(|zone| zone.contains(pos, self.floor_y))
+            .map(|zone| zone.force)
+            .sum()
+    }
+
+    /// Average wind along the straight line between two points.
+    /// Returns None on windless levels so callers can skip compensation entirely.
+    pub fn average_wind(&self, from: Vec2, to: Vec2) -> Option<Vec2> {
+        const SAMPLES: usize = 16;
+        if self.wind_zones.is_empty() {
+            return None;
+        }
+        let total: Vec2 = (0..=SAMPLES)
+            .map(|i| self.wind_at(from.lerp(to, i as f32 / SAMPLES as f32)))
+            .sum();
+        Some(total / (SAMPLES + 1) as f32)
+    }
+
+    /// Stable hash of the fields that shape generated heatmaps (16-char hex).
+    /// Stored per level in `config/level_hashes.json` to detect stale heatmaps.
+    pub fn content_hash(&self) -> String {
+        fn hash_f32<H: Hasher>(hasher: &mut H, value: f32) {
+            value.to_bits().hash(hasher);
+        }
+
+        let mut hasher = DefaultHasher::new();
+        self.id.hash(&mut hasher);
+        self.name.hash(&mut hasher);
+        hash_f32(&mut hasher, self.basket_height);
+        hash_f32(&mut hasher, self.basket_push_in);
+        self.step_count.hash(&mut hasher);
+        hash_f32(&mut hasher, self.corner_height);
+        hash_f32(&mut hasher, self.corner_width);
+        hash_f32(&mut hasher, self.step_push_in);
+        self.debug.hash(&mut hasher);
+        self.regression.hash(&mut hasher);
+
+        for platform in &self.platforms {
+            match platform {
+                PlatformDef::Mirror { x, y, width } => {
+                    "mirror".hash(&mut hasher);
+                    hash_f32(&mut hasher, *x);
+                    hash_f32(&mut hasher, *y);
+                    hash_f32(&mut hasher, *width);
+                }
+                PlatformDef::Center { y, width } => {
+                    "center".hash(&mut hasher);
+                    hash_f32(&mut hasher, *y);
+                    hash_f32(&mut hasher, *width);
+                }
+                PlatformDef::Portal { x1, y1, x2, y2 } => {
+                    "portal".hash(&mut hasher);
+                    for value in [x1, y1, x2, y2] {
+                        hash_f32(&mut hasher, *value);
+                    }
+                }
+                PlatformDef::BouncePad {
+                    x,
+                    y,
+                    width,
+                    launch,
+                } => {
+                    "bounce_pad".hash(&mut hasher);
+                    for value in [x, y, width, launch] {
+                        hash_f32(&mut hasher, *value);
+                    }
+                }
+            }
+        }
+
+        format!("{:016x}", hasher.finish())
+    }
 }
 
 /// Database of all loaded levels
@@ -57,13 +448,23 @@ impl Default for LevelDatabase {
 impl LevelDatabase {
     /// Load levels from file, returns default hardcoded levels on error
     pub fn load_from_file(path: &str) -> Self {
-        match fs::read_to_string(path) {
+        let path = &crate::config_dir::resolve(path);
+        let db = match fs::read_to_string(path) {
             Ok(content) => Self::parse(&content),
             Err(e) => {
                 warn!("Failed to load levels from {}: {}, using defaults", path, e);
                 Self::default_levels()
             }
+        };
+        db.with_ratings(&LevelRatings::load())
+    }
+
+    /// Attach generated ratings to the levels they were measured on
+    pub fn with_ratings(mut self, ratings: &LevelRatings) -> Self {
+        for level in &mut self.levels {
+            level.rating = ratings.levels.get(&level.id).cloned();
         }
+        self
     }
 
     /// Parse level data from string
@@ -100,6 +501,16 @@ impl LevelDatabase {
                     heatmap_score_weight: 1.0,
                     heatmap_los_threshold: HEATMAP_LOS_THRESHOLD_DEFAULT,
                     heatmap_los_margin: HEATMAP_LOS_MARGIN_DEFAULT,
+                    arena_width: ARENA_WIDTH,
+                    arena_height: ARENA_HEIGHT,
+                    floor_y: ARENA_FLOOR_Y,
+                    wind_zones: Vec::new(),
+                    basket_motion: None,
+                    spawn_left: None,
+                    spawn_right: None,
+                    spawn_ball: None,
+                    ball_rails: Some(BallRails::default()),
+                    rating: None,
                 });
             } else if let Some(id_str) = line.strip_prefix("id:") {
                 if let Some(level) = &mut current_level {
@@ -133,6 +544,82 @@ impl LevelDatabase {
                         }
                     }
                 }
+            } else if let Some(params) = line.strip_prefix("portal:") {
+                if let Some(level) = &mut current_level {
+                    let parts: Vec<f32> = params
+                        .split_whitespace()
+                        .filter_map(|p| p.parse::<f32>().ok())
+                        .collect();
+                    if parts.len() >= 4 {
+                        level.platforms.push(PlatformDef::Portal {
+                            x1: parts[0],
+                            y1: parts[1],
+                            x2: parts[2],
+                            y2: parts[3],
+                        });
+                    }
+                }
+            } else if let Some(params) = line.strip_prefix("bounce_pad:") {
+                if let Some(level) = &mut current_level {
+                    let parts: Vec<f32> = params
+                        .split_whitespace()
+                        .filter_map(|p| p.parse::<f32>().ok())
+                        .collect();
+                    if parts.len() >= 4 {
+                        level.platforms.push(PlatformDef::BouncePad {
+                            x: parts[0],
+                            y: parts[1],
+                            width: parts[2],
+                            launch: parts[3],
+                        });
+                    }
+                }
+            } else if let Some(params) = line.strip_prefix("wind:") {
+                if let Some(level) = &mut current_level {
+                    let parts: Vec<f32> = params
+                        .split_whitespace()
+                        .filter_map(|p| p.parse::<f32>().ok())
+                        .collect();
+                    if parts.len() >= 6 {
+                        level.wind_zones.push(WindZone {
+                            x: parts[0],
+                            y: parts[1],
+                            width: parts[2],
+                            height: parts[3],
+                            force: Vec2::new(parts[4], parts[5]),
+                        });
+                    }
+                }
+            } else if let Some(params) = line.strip_prefix("basket_motion:") {
+                if let Some(level) = &mut current_level {
+                    let parts: Vec<f32> = params
+                        .split_whitespace()
+                        .filter_map(|p| p.parse::<f32>().ok())
+                        .collect();
+                    if parts.len() >= 3 {
+                        level.basket_motion = Some(BasketMotion {
+                            dx: parts[0],
+                            dy: parts[1],
+                            period: parts[2],
+                        });
+                    }
+                }
+            } else if let Some(params) = line.strip_prefix("spawn_left:") {
+                if let Some(level) = &mut current_level {
+                    level.spawn_left = parse_spawn(params);
+                }
+            } else if let Some(params) = line.strip_prefix("spawn_right:") {
+                if let Some(level) = &mut current_level {
+                    level.spawn_right = parse_spawn(params);
+                }
+            } else if let Some(params) = line.strip_prefix("spawn_ball:") {
+                if let Some(level) = &mut current_level {
+                    level.spawn_ball = parse_spawn(params);
+                }
+            } else if let Some(params) = line.strip_prefix("rails:") {
+                if let Some(level) = &mut current_level {
+                    level.ball_rails = parse_rails(params);
+                }
             } else if let Some(count_str) = line.strip_prefix("steps:") {
                 if let Some(level) = &mut current_level {
                     if let Ok(count) = count_str.trim().parse::<usize>() {
@@ -189,6 +676,28 @@ impl LevelDatabase {
                         level.heatmap_los_margin = value;
                     }
                 }
+            } else if let Some(width_str) = line.strip_prefix("arena_width:") {
+                if let Some(level) = &mut current_level {
+                    if let Ok(width) = width_str.trim().parse::<f32>() {
+                        level.arena_width = width;
+                    }
+                }
+            } else if let Some(height_str) = line.strip_prefix("arena_height:") {
+                if let Some(level) = &mut current_level {
+                    if let Ok(height) = height_str.trim().parse::<f32>() {
+                        // Keep the floor at the bottom edge unless floor_y is given explicitly
+                        if level.floor_y == -level.arena_height / 2.0 {
+                            level.floor_y = -height / 2.0;
+                        }
+                        level.arena_height = height;
+                    }
+                }
+            } else if let Some(floor_str) = line.strip_prefix("floor_y:") {
+                if let Some(level) = &mut current_level {
+                    if let Ok(floor_y) = floor_str.trim().parse::<f32>() {
+                        level.floor_y = floor_y;
+                    }
+                }
             }
         }
 
@@ -202,6 +711,12 @@ impl LevelDatabase {
             return Self::default_levels();
         }
 
+        for level in &mut levels {
+            for issue in level.validate_spawns() {
+                warn!("Level {}: {}", level.name, issue);
+            }
+        }
+
         info!("Loaded {} levels from file", levels.len());
         Self { levels }
     }
@@ -229,6 +744,16 @@ impl LevelDatabase {
                     heatmap_score_weight: 1.0,
                     heatmap_los_threshold: HEATMAP_LOS_THRESHOLD_DEFAULT,
                     heatmap_los_margin: HEATMAP_LOS_MARGIN_DEFAULT,
+                    arena_width: ARENA_WIDTH,
+                    arena_height: ARENA_HEIGHT,
+                    floor_y: ARENA_FLOOR_Y,
+                    wind_zones: Vec::new(),
+                    basket_motion: None,
+                    spawn_left: None,
+                    spawn_right: None,
+                    spawn_ball: None,
+                    ball_rails: Some(BallRails::default()),
+                    rating: None,
                 },
                 LevelData {
                     id: generate_uuid_from_name("Default"),
@@ -255,11 +780,38 @@ impl LevelDatabase {
                     heatmap_score_weight: 1.0,
                     heatmap_los_threshold: HEATMAP_LOS_THRESHOLD_DEFAULT,
                     heatmap_los_margin: HEATMAP_LOS_MARGIN_DEFAULT,
+                    arena_width: ARENA_WIDTH,
+                    arena_height: ARENA_HEIGHT,
+                    floor_y: ARENA_FLOOR_Y,
+                    wind_zones: Vec::new(),
+                    basket_motion: None,
+                    spawn_left: None,
+                    spawn_right: None,
+                    spawn_ball: None,
+                    ball_rails: Some(BallRails::default()),
+                    rating: None,
                 },
             ],
         }
     }
 
+    /// Player spawn on a level (by ID or name), default spawn if the level is unknown
+    pub fn player_spawn(&self, level: &str, left: bool) -> Vec3 {
+        match self.get_by_id(level).or_else(|| self.get_by_name(level)) {
+            Some(level) => level.player_spawn(left),
+            None if left => PLAYER_SPAWN_LEFT,
+            None => PLAYER_SPAWN_RIGHT,
+        }
+    }
+
+    /// Ball spawn on a level (by ID or name), default spawn if the level is unknown
+    pub fn ball_spawn(&self, level: &str) -> Vec3 {
+        self.get_by_id(level)
+            .or_else(|| self.get_by_name(level))
+            .map(|level| level.ball_spawn())
+            .unwrap_or(BALL_SPAWN)
+    }
+
     /// Get level by index
     pub fn get(&self, index: usize) -> Option<&LevelData> {
         self.levels.get(index)
@@ -299,3 +851,29 @@ impl LevelDatabase {
         self.levels.is_empty()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spawn_overrides_and_validation() {
+        let db = LevelDatabase::parse(
+            "level: Spawns\nsteps: 0\ncenter: 100 200\nspawn_left: -500 60\nspawn_right: 0 100\nspawn_ball: 9000 50\n",
+        );
+        let level = db.get_by_name("Spawns").unwrap();
+
+        // In open floor space: kept, y measured from floor_y
+        assert_eq!(level.spawn_left, Some(Vec2::new(-500.0, 60.0)));
+        assert_eq!(
+            db.player_spawn("Spawns", true),
+            Vec3::new(-500.0, ARENA_FLOOR_Y + 60.0, PLAYER_SPAWN_LEFT.z)
+        );
+        // Inside the center platform / outside the walls: dropped for the defaults
+        assert_eq!(level.spawn_right, None);
+        assert_eq!(level.spawn_ball, None);
+        assert_eq!(db.player_spawn("Spawns", false), PLAYER_SPAWN_RIGHT);
+        assert_eq!(db.ball_spawn("Spawns"), BALL_SPAWN);
+        assert_eq!(db.ball_spawn("Missing"), BALL_SPAWN);
+    }
+}
@@ -0,0 +1,342 @@
+//! Level features - portals, bounce pads, wind zones, and moving baskets
+//!
+//! Portals and bounce pads are defined alongside platforms in `PlatformDef` but are
+//! not solid: portals move entities to their linked end, bounce pads launch them
+//! upward. Wind zones are stored per level and push loose balls and airborne players.
+//! Basket motion slides both baskets along a per-level oscillating path.
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::ball::{Ball, BallState};
+use crate::constants::*;
+use crate::levels::database::{LevelData, LevelDatabase, PlatformDef};
+use crate::overtime::Overtime;
+use crate::player::{Grounded, Player, Velocity};
+use crate::scoring::CurrentLevel;
+use crate::world::{
+    Basket, BasketRim, BouncePad, LevelPlatform, PortalCooldown, Teleporter, WindParticle,
+    sync_rim_globals,
+};
+
+const PORTAL_COLOR: Color = Color::srgba(0.45, 0.3, 0.85, 0.6);
+const BOUNCE_PAD_COLOR: Color = Color::srgb(0.85, 0.55, 0.2);
+const WIND_PARTICLE_COLOR: Color = Color::srgba(0.9, 0.9, 1.0, 0.35);
+
+/// World-space centers of a portal pair's two ends
+pub fn portal_ends(x1: f32, y1: f32, x2: f32, y2: f32, floor_y: f32) -> (Vec2, Vec2) {
+    (Vec2::new(x1, floor_y + y1), Vec2::new(x2, floor_y + y2))
+}
+
+/// World-space X centers of a bounce pad definition (mirrored unless centered)
+pub fn bounce_pad_xs(x: f32) -> Vec<f32> {
+    if x.abs() < 1.0 {
+        vec![0.0]
+    } else {
+        vec![-x, x]
+    }
+}
+
+/// Peak height gained from a bounce pad launch
+pub fn bounce_pad_apex(launch: f32) -> f32 {
+    launch * launch / (2.0 * GRAVITY_RISE)
+}
+
+/// Spawn a portal pair or bounce pad. Solid platform defs are ignored (callers spawn those).
+pub fn spawn_level_feature(commands: &mut Commands, def: &PlatformDef, floor_y: f32) {
+    match def {
+        PlatformDef::Portal { x1, y1, x2, y2 } => {
+            let (a, b) = portal_ends(*x1, *y1, *x2, *y2, floor_y);
+            for (pos, exit) in [(a, b), (b, a)] {
+                commands.spawn((
+                    Sprite::from_color(PORTAL_COLOR, PORTAL_SIZE),
+                    Transform::from_xyz(pos.x, pos.y, -0.2),
+                    Teleporter { exit },
+                    LevelPlatform,
+                ));
+            }
+        }
+        PlatformDef::BouncePad {
+            x,
+            y,
+            width,
+            launch,
+        } => {
+            for pad_x in bounce_pad_xs(*x) {
+                commands.spawn((
+                    Sprite::from_color(BOUNCE_PAD_COLOR, Vec2::new(*width, BOUNCE_PAD_HEIGHT)),
+                    Transform::from_xyz(pad_x, floor_y + y, 0.1),
+                    BouncePad { launch: *launch },
+                    LevelPlatform,
+                ));
+            }
+        }
+        PlatformDef::Mirror { .. } | PlatformDef::Center { .. } => {}
+    }
+}
+
+/// Teleport players and loose balls through portals and launch them off bounce pads.
+/// Runs after collisions so grounded state reflects this frame's landing.
+#[allow(clippy::type_complexity)]
+pub fn level_feature_traversal(
+    mut commands: Commands,
+    time: Res<Time>,
+    portals: Query<(&Transform, &Teleporter), Without<Velocity>>,
+    pads: Query<(&Transform, &Sprite, &BouncePad), Without<Velocity>>,
+    mut movers: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut Velocity,
+            &Sprite,
+            Option<&mut Grounded>,
+            Option<&BallState>,
+            Option<&mut PortalCooldown>,
+        ),
+        Or<(With<Player>, With<Ball>)>,
+    >,
+) {
+    if portals.is_empty() && pads.is_empty() {
+        return;
+    }
+
+    // Use minimum dt for headless mode compatibility
+    let dt = time.delta_secs().max(1.0 / 60.0);
+    let portal_half = PORTAL_SIZE / 2.0;
+
+    for (entity, mut transform, mut velocity, sprite, grounded, ball_state, cooldown) in &mut movers
+    {
+        // Held balls follow their holder
+        if matches!(ball_state, Some(BallState::Held(_))) {
+            continue;
+        }
+
+        let pos = transform.translation.truncate();
+        let half = sprite.custom_size.unwrap_or(PLAYER_SIZE) / 2.0;
+
+        let in_portal = portals.iter().find(|(portal_transform, _)| {
+            let diff = (pos - portal_transform.translation.truncate()).abs();
+            diff.x < portal_half.x && diff.y < portal_half.y
+        });
+
+        match cooldown {
+            Some(mut cooldown) => {
+                cooldown.0 -= dt;
+                if cooldown.0 <= 0.0 && in_portal.is_none() {
+                    commands.entity(entity).remove::<PortalCooldown>();
+                }
+            }
+            None => {
+                if let Some((_, teleporter)) = in_portal {
+                    transform.translation.x = teleporter.exit.x;
+                    transform.translation.y = teleporter.exit.y;
+                    commands
+                        .entity(entity)
+                        .insert(PortalCooldown(PORTAL_COOLDOWN));
+                    continue;
+                }
+            }
+        }
+
+        // Bounce pads: feet within the pad strip while not already rising
+        if velocity.0.y > 0.0 {
+            continue;
+        }
+        let feet_y = pos.y - half.y;
+        for (pad_transform, pad_sprite, pad) in &pads {
+            let pad_half = pad_sprite
+                .custom_size
+                .unwrap_or(Vec2::new(100.0, BOUNCE_PAD_HEIGHT))
+                / 2.0;
+            let pad_pos = pad_transform.translation.truncate();
+            let overlaps_x = (pos.x - pad_pos.x).abs() < pad_half.x + half.x;
+            let on_pad = feet_y <= pad_pos.y + pad_half.y + BOUNCE_PAD_TRIGGER_MARGIN
+                && feet_y >= pad_pos.y - pad_half.y - BOUNCE_PAD_TRIGGER_MARGIN;
+            if overlaps_x && on_pad {
+                velocity.0.y = pad.launch;
+                if let Some(mut grounded) = grounded {
+                    grounded.0 = false;
+                }
+                break;
+            }
+        }
+    }
+}
+
+/// Spawn drifting particles so wind zones are visible (render-only, skipped in headless sims)
+pub fn spawn_wind_particles(commands: &mut Commands, level: &LevelData) {
+    let mut rng = rand::thread_rng();
+
+    for zone in &level.wind_zones {
+        let (min, max) = zone.bounds(level.floor_y);
+        let count = ((zone.width * zone.height * WIND_PARTICLE_DENSITY) as usize).max(1);
+        let velocity = zone.force * WIND_PARTICLE_SPEED_SCALE;
+
+        for _ in 0..count {
+            let x = rng.gen_range(min.x..=max.x);
+            let y = rng.gen_range(min.y..=max.y);
+            commands.spawn((
+                Sprite::from_color(WIND_PARTICLE_COLOR, Vec2::new(3.0, 3.0)),
+                Transform::from_xyz(x, y, -0.3),
+                WindParticle { min, max, velocity },
+                LevelPlatform,
+            ));
+        }
+    }
+}
+
+/// Drift wind particles with their zone's force, wrapping at the zone edges
+pub fn animate_wind_particles(
+    time: Res<Time>,
+    mut particles: Query<(&mut Transform, &WindParticle)>,
+) {
+    let dt = time.delta_secs();
+
+    for (mut transform, particle) in &mut particles {
+        let mut pos = transform.translation.truncate() + particle.velocity * dt;
+        let size = particle.max - particle.min;
+        if pos.x > particle.max.x {
+            pos.x -= size.x;
+        } else if pos.x < particle.min.x {
+            pos.x += size.x;
+        }
+        if pos.y > particle.max.y {
+            pos.y -= size.y;
+        } else if pos.y < particle.min.y {
+            pos.y += size.y;
+        }
+        transform.translation.x = pos.x;
+        transform.translation.y = pos.y;
+    }
+}
+
+/// Push loose balls (full force) and airborne players (reduced force) inside wind zones
+#[allow(clippy::type_complexity)]
+pub fn apply_wind_forces(
+    current_level: Res<CurrentLevel>,
+    level_db: Res<LevelDatabase>,
+    time: Res<Time>,
+    mut movers: Query<
+        (
+            &Transform,
+            &mut Velocity,
+            Option<&Grounded>,
+            Option<&BallState>,
+        ),
+        Or<(With<Player>, With<Ball>)>,
+    >,
+) {
+    let Some(level) = level_db.get_by_id(&current_level.0) else {
+        return;
+    };
+    if level.wind_zones.is_empty() {
+        return;
+    }
+
+    // Use minimum dt for headless mode compatibility
+    let dt = time.delta_secs().max(1.0 / 60.0);
+
+    for (transform, mut velocity, grounded, ball_state) in &mut movers {
+        // Held balls follow their holder
+        if matches!(ball_state, Some(BallState::Held(_))) {
+            continue;
+        }
+        // Players only feel wind in the air (it affects air control, not footing)
+        let factor = match grounded {
+            Some(grounded) if grounded.0 => continue,
+            Some(_) => WIND_PLAYER_FACTOR,
+            None => 1.0,
+        };
+        let wind = level.wind_at(transform.translation.truncate());
+        velocity.0 += wind * factor * dt;
+    }
+}
+
+/// Slide baskets along the level's motion path (no-op for static baskets).
+/// During overtime the path shifts inward with the walls.
+pub fn move_baskets(
+    current_level: Res<CurrentLevel>,
+    level_db: Res<LevelDatabase>,
+    time: Res<Time>,
+    overtime: Option<Res<Overtime>>,
+    mut baskets: Query<(&mut Transform, &Basket, Option<&Children>)>,
+    mut rims: Query<(&Transform, &mut GlobalTransform), (With<BasketRim>, Without<Basket>)>,
+) {
+    let Some(level) = level_db.get_by_id(&current_level.0) else {
+        return;
+    };
+    if level.basket_motion.is_none() {
+        return;
+    }
+
+    let t = time.elapsed_secs();
+    let inset = overtime.map_or(0.0, |o| o.inset);
+    for (mut transform, basket, children) in &mut baskets {
+        let left = *basket == Basket::Left;
+        let pos = level.basket_position(left, t);
+        transform.translation.x = if left { pos.x + inset } else { pos.x - inset };
+        transform.translation.y = pos.y;
+        sync_rim_globals(&transform, children, &mut rims);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_features() {
+        let db = crate::levels::LevelDatabase::parse(
+            "level: Features\nportal: -500 60 500 400\nbounce_pad: 0 24 80 900\nbounce_pad: 300 24\n",
+        );
+        let level = db.get_by_name("Features").unwrap();
+        assert_eq!(level.platforms.len(), 2);
+        assert!(level.platforms.iter().all(|p| !p.is_solid()));
+        match &level.platforms[1] {
+            PlatformDef::BouncePad { launch, .. } => assert_eq!(*launch, 900.0),
+            other => panic!("expected bounce pad, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_wind_zones_sum_and_average() {
+        let db = crate::levels::LevelDatabase::parse(
+            "level: Windy\nwind: 0 300 200 200 100 0\nwind: 0 300 200 200 0 50\n",
+        );
+        let level = db.get_by_name("Windy").unwrap();
+        let inside = Vec2::new(0.0, level.floor_y + 300.0);
+        assert_eq!(level.wind_at(inside), Vec2::new(100.0, 50.0));
+        assert_eq!(level.wind_at(Vec2::new(500.0, inside.y)), Vec2::ZERO);
+
+        // Half of a line through the zone is windy
+        let avg = level
+            .average_wind(Vec2::new(-200.0, inside.y), Vec2::new(200.0, inside.y))
+            .unwrap();
+        assert!(avg.x > 30.0 && avg.x < 70.0);
+    }
+
+    #[test]
+    fn test_basket_motion_mirrors_sides() {
+        let db = crate::levels::LevelDatabase::parse("level: Moving\nbasket_motion: 100 50 4\n");
+        let level = db.get_by_name("Moving").unwrap();
+
+        // Home at t = 0, quarter period is the peak offset (toward center, up)
+        assert_eq!(level.basket_position(false, 0.0), level.basket_home(false));
+        let right = level.basket_position(false, 1.0) - level.basket_home(false);
+        let left = level.basket_position(true, 1.0) - level.basket_home(true);
+        assert!((right - Vec2::new(-100.0, 50.0)).length() < 0.01);
+        assert!((left - Vec2::new(100.0, 50.0)).length() < 0.01);
+    }
+
+    #[test]
+    fn test_bounce_pad_mirrors_unless_centered() {
+        assert_eq!(bounce_pad_xs(0.0), vec![0.0]);
+        assert_eq!(bounce_pad_xs(300.0), vec![-300.0, 300.0]);
+    }
+
+    #[test]
+    fn test_bounce_pad_apex_matches_jump_height() {
+        let jump_apex = JUMP_VELOCITY * JUMP_VELOCITY / (2.0 * GRAVITY_RISE);
+        assert!((bounce_pad_apex(JUMP_VELOCITY) - jump_apex).abs() < 0.01);
+    }
+}
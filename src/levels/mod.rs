@@ -1,7 +1,11 @@
-//! Levels module - database and spawning
+//! Levels module - database, spawning, level features and ratings
 
 mod database;
+mod features;
+mod ratings;
 mod spawning;
 
 pub use database::*;
+pub use features::*;
+pub use ratings::*;
 pub use spawning::*;
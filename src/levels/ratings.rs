@@ -0,0 +1,127 @@
+//! Level ratings - difficulty and side fairness measured from simulations
+//!
+//! `analyze --level-ratings <DB>` rates every level it has simulated matches
+//! for and stores the result in `config/level_ratings.json`, keyed by level id.
+//! Levels load their rating from there (`LevelData::rating`), so the level
+//! select can show it next to the name. The file is generated - rerun the
+//! analysis instead of editing it.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+
+use crate::constants::*;
+
+/// Generated per-level ratings (level id -> rating)
+pub const LEVEL_RATINGS_FILE: &str = "config/level_ratings.json";
+
+/// Difficulty bucket from how long the AI takes to get a shot off and how often the ball gets stuck
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LevelDifficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl LevelDifficulty {
+    pub fn name(&self) -> &'static str {
+        match self {
+            LevelDifficulty::Easy => "easy",
+            LevelDifficulty::Medium => "medium",
+            LevelDifficulty::Hard => "hard",
+        }
+    }
+}
+
+/// Metrics for one level across its simulated matches
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LevelRating {
+    pub matches: u32,
+    /// Mean |score_left - score_right|
+    pub avg_margin: f32,
+    /// Left side's share of decided matches, minus 0.5 (positive = left favored)
+    pub left_win_bias: f32,
+    /// BallStuck events per match
+    pub stuck_per_match: f32,
+    /// Mean seconds until the first shot release (None = no match had a shot)
+    pub avg_first_shot_secs: Option<f32>,
+    pub difficulty: LevelDifficulty,
+}
+
+impl LevelRating {
+    /// Rate a level from its metrics
+    pub fn new(
+        matches: u32,
+        avg_margin: f32,
+        left_win_bias: f32,
+        stuck_per_match: f32,
+        avg_first_shot_secs: Option<f32>,
+    ) -> Self {
+        let first_shot = avg_first_shot_secs.unwrap_or(f32::INFINITY);
+        let difficulty = if first_shot >= LEVEL_RATING_HARD_FIRST_SHOT_SECS
+            || stuck_per_match >= LEVEL_RATING_HARD_STUCK_PER_MATCH
+        {
+            LevelDifficulty::Hard
+        } else if first_shot <= LEVEL_RATING_EASY_FIRST_SHOT_SECS
+            && stuck_per_match < LEVEL_RATING_HARD_STUCK_PER_MATCH / 2.0
+        {
+            LevelDifficulty::Easy
+        } else {
+            LevelDifficulty::Medium
+        };
+        Self {
+            matches,
+            avg_margin,
+            left_win_bias,
+            stuck_per_match,
+            avg_first_shot_secs,
+            difficulty,
+        }
+    }
+
+    /// Enough matches and one side wins noticeably more often
+    pub fn is_biased(&self) -> bool {
+        self.matches >= LEVEL_RATING_MIN_MATCHES
+            && self.left_win_bias.abs() > LEVEL_RATING_BIAS_THRESHOLD
+    }
+
+    /// Short tag for the level select, e.g. "hard, L+15%"
+    pub fn tag(&self) -> String {
+        if self.is_biased() {
+            format!(
+                "{}, {}+{:.0}%",
+                self.difficulty.name(),
+                if self.left_win_bias > 0.0 { "L" } else { "R" },
+                self.left_win_bias.abs() * 100.0
+            )
+        } else {
+            self.difficulty.name().to_string()
+        }
+    }
+}
+
+/// On-disk form of `LEVEL_RATINGS_FILE`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LevelRatings {
+    pub levels: HashMap<String, LevelRating>,
+}
+
+impl LevelRatings {
+    /// Load the ratings, or none if missing or unreadable
+    pub fn load() -> Self {
+        match fs::read_to_string(crate::config_dir::resolve(LEVEL_RATINGS_FILE)) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_default(),
+            Err(_) => LevelRatings::default(),
+        }
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(
+            crate::config_dir::resolve_for_write(LEVEL_RATINGS_FILE),
+            json,
+        )
+        .map_err(|e| e.to_string())
+    }
+}
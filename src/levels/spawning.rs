@@ -1,11 +1,13 @@
 //! Level platform spawning helpers
 
+use bevy::camera::ScalingMode;
 use bevy::prelude::*;
 
 use crate::constants::*;
-use crate::helpers::basket_x_from_offset;
-use crate::levels::database::{LevelDatabase, PlatformDef};
-use crate::world::{CornerRamp, LevelPlatform, Platform};
+use crate::levels::database::{ArenaDims, LevelDatabase, PlatformDef};
+use crate::levels::features::{spawn_level_feature, spawn_wind_particles};
+use crate::scoring::CurrentLevel;
+use crate::world::{ArenaFloor, ArenaWall, CornerRamp, LevelPlatform, Platform};
 
 /// Helper to spawn a platform mirrored on both sides (symmetric)
 pub fn spawn_mirrored_platform(commands: &mut Commands, x: f32, y: f32, width: f32, color: Color) {
@@ -40,6 +42,7 @@ pub fn spawn_center_platform(commands: &mut Commands, y: f32, width: f32, color:
 /// step_push_in is the distance from wall where stairs start (top step extends to wall)
 pub fn spawn_corner_ramps(
     commands: &mut Commands,
+    arena: &ArenaDims,
     step_count: usize,
     corner_height: f32,
     corner_width: f32,
@@ -51,8 +54,8 @@ pub fn spawn_corner_ramps(
     }
 
     // Wall inner edges
-    let left_wall_inner = -ARENA_WIDTH / 2.0 + WALL_THICKNESS;
-    let right_wall_inner = ARENA_WIDTH / 2.0 - WALL_THICKNESS;
+    let left_wall_inner = -arena.wall_inner();
+    let right_wall_inner = arena.wall_inner();
 
     // Step dimensions
     let step_height = corner_height / step_count as f32;
@@ -60,7 +63,7 @@ pub fn spawn_corner_ramps(
 
     // Left steps: go from wall (high) toward center (low)
     // Step 0 is highest (closest to wall), step N-1 is lowest (closest to center)
-    let floor_top = ARENA_FLOOR_Y + 20.0;
+    let floor_top = arena.floor_top();
     for i in 0..step_count {
         let step_num = (step_count - 1 - i) as f32; // Reverse so 0 is lowest
         let y = floor_top + step_height * (step_num + 0.5);
@@ -154,13 +157,16 @@ pub fn spawn_level_platforms(
     for platform in &level.platforms {
         match platform {
             PlatformDef::Mirror { x, y, width } => {
-                spawn_mirrored_platform(commands, *x, ARENA_FLOOR_Y + y, *width, platform_color);
+                spawn_mirrored_platform(commands, *x, level.floor_y + y, *width, platform_color);
             }
             PlatformDef::Center { y, width } => {
-                spawn_center_platform(commands, ARENA_FLOOR_Y + y, *width, platform_color);
+                spawn_center_platform(commands, level.floor_y + y, *width, platform_color);
             }
+            feature => spawn_level_feature(commands, feature, level.floor_y),
         }
     }
+
+    spawn_wind_particles(commands, level);
 }
 
 /// Reload all level geometry (platforms and corner ramps).
@@ -189,9 +195,11 @@ pub fn reload_level_geometry(
 
     // Spawn corner ramps and return basket positions
     let level = level_db.get_by_id(level_id)?;
+    let arena = level.arena();
 
     spawn_corner_ramps(
         commands,
+        &arena,
         level.step_count,
         level.corner_height,
         level.corner_width,
@@ -199,8 +207,59 @@ pub fn reload_level_geometry(
         platform_color,
     );
 
-    let basket_y = ARENA_FLOOR_Y + level.basket_height;
-    let (left_x, right_x) = basket_x_from_offset(level.basket_push_in);
+    let basket_y = arena.floor_y + level.basket_height;
+    let (left_x, right_x) = arena.basket_x_from_offset(level.basket_push_in);
 
     Some((left_x, right_x, basket_y))
 }
+
+/// Sync the `ArenaDims` resource, walls, floor, and camera framing to the active level.
+/// Runs on level change; geometry spawned by `reload_level_geometry` reads the level directly.
+pub fn apply_arena_dims_on_level_change(
+    current_level: Res<CurrentLevel>,
+    level_db: Res<LevelDatabase>,
+    mut arena: ResMut<ArenaDims>,
+    mut walls: Query<(&mut Transform, &ArenaWall), (Without<ArenaFloor>, Without<Camera2d>)>,
+    mut floors: Query<
+        (&mut Transform, &mut Sprite),
+        (With<ArenaFloor>, Without<ArenaWall>, Without<Camera2d>),
+    >,
+    mut cameras: Query<
+        (&mut Transform, &mut Projection),
+        (With<Camera2d>, Without<ArenaWall>, Without<ArenaFloor>),
+    >,
+) {
+    if !current_level.is_changed() {
+        return;
+    }
+
+    let dims = level_db
+        .get_by_id(&current_level.0)
+        .map(|l| l.arena())
+        .unwrap_or_default();
+    if *arena != dims {
+        *arena = dims;
+    }
+
+    for (mut transform, wall) in &mut walls {
+        let x = dims.wall_inner() + WALL_THICKNESS / 2.0;
+        transform.translation.x = match wall {
+            ArenaWall::Left => -x,
+            ArenaWall::Right => x,
+        };
+    }
+
+    for (mut transform, mut sprite) in &mut floors {
+        transform.translation.y = dims.floor_y;
+        sprite.custom_size = Some(Vec2::new(dims.width - WALL_THICKNESS * 2.0, 40.0));
+    }
+
+    for (mut transform, mut projection) in &mut cameras {
+        transform.translation.y = dims.center_y();
+        if let Projection::Orthographic(ortho) = projection.as_mut() {
+            ortho.scaling_mode = ScalingMode::FixedVertical {
+                viewport_height: dims.height,
+            };
+        }
+    }
+}
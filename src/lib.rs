@@ -4,19 +4,29 @@
 
 // Core modules
 pub mod analytics;
+pub mod cli;
+pub mod config;
+pub mod config_dir;
 pub mod config_watcher;
 pub mod constants;
 pub mod countdown;
+pub mod db_maintenance;
 pub mod debug_logging;
 pub mod generate;
 pub use debug_logging::DebugLogConfig;
+pub mod embed;
 pub mod events;
 pub mod helpers;
+pub mod momentum;
+pub mod overtime;
 pub mod replay;
+pub mod schedule;
+pub mod series;
 pub mod settings;
 pub mod simulation;
 pub mod snapshot;
 pub mod testing;
+pub mod time_dilation;
 pub mod training;
 
 // Game logic modules
@@ -30,6 +40,7 @@ pub mod presets;
 pub mod scoring;
 pub mod shooting;
 pub mod steal;
+pub mod teams;
 pub mod tuning;
 pub mod ui;
 pub mod world;
@@ -51,47 +62,68 @@ pub use countdown::{
     CountdownText, MatchCountdown, in_countdown, not_in_countdown, spawn_countdown_text,
     trigger_countdown_on_level_change, update_countdown,
 };
+pub use embed::{BallgamePlugins, EventLog, LevelSource, MatchRules, MatchStatus};
 pub use events::{
-    BusEvent, ControllerSource, EventBuffer, EventBus, GameConfig, GameEvent, LevelChangeTracker,
-    PlayerId, emit_level_change_events, update_event_bus_time,
+    BusEvent, ControllerSource, EventBuffer, EventBus, GameClock, GameConfig, GameEvent,
+    LevelChangeTracker, PlayerId, TimeBase, emit_level_change_events, tick_game_clock,
+    update_event_bus_time,
 };
 pub use helpers::*;
-pub use input::PlayerInput;
-pub use levels::{LevelData, LevelDatabase, PlatformDef};
+pub use input::{
+    InputBindings, InputDevice, InputDeviceAssignment, KeyBindings, PlayerInput, SecondPlayerInput,
+};
+pub use levels::{
+    ArenaDims, LevelData, LevelDatabase, PlatformDef, bounce_pad_apex, bounce_pad_xs, portal_ends,
+};
+pub use momentum::{Momentum, MomentumAura, update_momentum, update_momentum_aura};
+pub use overtime::{
+    Overtime, OvertimeBanner, shrink_court, spawn_overtime_banner, update_overtime_banner,
+};
 pub use palettes::{PALETTES_FILE, Palette, PaletteDatabase};
 pub use player::{
     CoyoteTimer, Facing, Grounded, HoldingBall, HumanControlTarget, HumanControlled, JumpState,
-    Player, TargetBasket, Team, Velocity,
+    Player, SecondHuman, TargetBasket, Team, TeamSize, Teammate, Velocity,
 };
 pub use presets::{
     BallPreset, CompositePreset, CurrentPresets, MovementPreset, PRESETS_FILE, PresetDatabase,
-    ShootingPreset, apply_composite_preset, apply_preset_to_tweaks,
+    ShootingPreset, StealPreset, apply_composite_preset, apply_preset_to_tweaks,
 };
 pub use replay::{
-    MatchInfo, ReplayData, ReplayMode, ReplayState, TickFrame, TimedEvent, not_replay_active,
-    replay_active, replay_input_handler, replay_playback, replay_setup, setup_replay_ui,
-    update_replay_ui,
+    MatchInfo, ReplayData, ReplayFocus, ReplayMode, ReplayState, TickFrame, TimedEvent,
+    not_replay_active, replay_active, replay_input_handler, replay_playback, replay_setup,
+    setup_replay_ui, update_replay_ui,
+};
+pub use schedule::{AiSet, InputSet, LoggingSet, MatchSetsPlugin, PhysicsSet, ScoringSet};
+pub use scoring::{CurrentLevel, PossessionArrow, Score};
+pub use series::{
+    SeriesBanner, VersusSeries, match_in_play, spawn_series_banner, update_series_banner,
+    update_versus_series,
 };
-pub use scoring::{CurrentLevel, Score};
 pub use settings::{CurrentSettings, InitSettings, save_settings_system};
-pub use shooting::{ChargingShot, LastShotInfo};
+pub use shooting::{ChargeFeedback, ChargingShot, LastShotInfo};
 pub use snapshot::{
-    BallSnapshot, GameSnapshot, PlayerSnapshot, ScoreSnapshot, ShotSnapshot, SnapshotConfig,
-    SnapshotTriggerState,
+    BallSnapshot, GameSnapshot, GifCapture, GifCaptureConfig, PlayerSnapshot, ScoreSnapshot,
+    ShotSnapshot, SnapshotConfig, SnapshotTriggerState, SpectateConfig, SpectatePlugin,
 };
-pub use steal::{StealContest, StealCooldown, StealTracker};
+pub use steal::{
+    LooseBall, StealContest, StealCooldown, StealDenial, StealDenialFeedback, StealTracker,
+};
+pub use teams::{TeamIdentity, TeamInfo, apply_team_colors};
 pub use training::{
     GameResult, GameSummary, SessionSummary, TrainingPhase, TrainingState, Winner,
     ensure_session_dir, print_session_summary, write_session_summary,
 };
 pub use tuning::{GAMEPLAY_TUNING_FILE, GameplayTuning, PhysicsTweaks};
 pub use ui::{
-    ChargeGaugeBackground, ChargeGaugeFill, CycleDirection, CycleIndicator, CycleSelection,
-    DebugSettings, DebugText, DownOption, RightOption, ScoreFlash, ScoreLevelText,
-    StealCooldownIndicator, StealFailFlash, StealOutOfRangeFlash, TweakPanel, TweakPanelState,
+    ChargeGaugeBackground, ChargeGaugeFill, ChargeIdealMarker, ChargeReleaseMarker, CycleDirection,
+    CycleIndicator, CycleSelection, DebugSettings, DebugText, DownOption, RightOption, ScoreFlash,
+    ScoreLevelText, StealCooldownIndicator, StealDenialFlash, TweakPanel, TweakPanelState,
     TweakRow, ViewportScale, VulnerableIndicator,
 };
-pub use world::{Basket, BasketRim, Collider, CornerRamp, LevelPlatform, Platform};
+pub use world::{
+    Basket, BasketRim, BouncePad, Collider, CornerRamp, LevelPlatform, Platform, PortalCooldown,
+    Teleporter,
+};
 
 // =============================================================================
 // TRAJECTORY CALCULATION (shared with tools like heatmap generator)
@@ -108,6 +140,18 @@ pub struct ShotTrajectory {
     pub distance_variance: f32,
 }
 
+impl ShotTrajectory {
+    /// Seconds for the ball to cover `dx` horizontally (ignores drag and wind)
+    pub fn flight_time(&self, dx: f32) -> f32 {
+        let vx = self.required_speed * self.angle.cos();
+        if vx.abs() < f32::EPSILON {
+            0.0
+        } else {
+            (dx / vx).abs()
+        }
+    }
+}
+
 /// Default variance per unit distance for trajectory calculation
 pub const SHOT_DISTANCE_VARIANCE: f32 = 0.00025;
 
@@ -117,6 +161,11 @@ pub const SHOT_DISTANCE_VARIANCE: f32 = 0.00025;
 ///
 /// `distance_variance_factor` controls how much distance affects accuracy.
 /// Use `SHOT_DISTANCE_VARIANCE` (0.00025) for the default value.
+///
+/// `wind` is an optional constant acceleration (e.g. `LevelData::average_wind`) added
+/// to gravity. The shot is solved in a frame rotated so the combined acceleration
+/// points straight down, then the angle is rotated back. Returns None if the wind
+/// cancels or overpowers gravity.
 pub fn calculate_shot_trajectory(
     shooter_x: f32,
     shooter_y: f32,
@@ -124,7 +173,34 @@ pub fn calculate_shot_trajectory(
     target_y: f32,
     gravity: f32,
     distance_variance_factor: f32,
+    wind: Option<bevy::math::Vec2>,
 ) -> Option<ShotTrajectory> {
+    if let Some(wind) = wind.filter(|w| w.length_squared() > f32::EPSILON) {
+        let accel = bevy::math::Vec2::new(wind.x, wind.y - gravity);
+        if accel.y >= 0.0 {
+            return None;
+        }
+        // Rotation that maps the net acceleration onto straight down
+        let rotation = -std::f32::consts::FRAC_PI_2 - accel.y.atan2(accel.x);
+        let target = bevy::math::Vec2::from_angle(rotation).rotate(bevy::math::Vec2::new(
+            target_x - shooter_x,
+            target_y - shooter_y,
+        ));
+        let traj = calculate_shot_trajectory(
+            0.0,
+            0.0,
+            target.x,
+            target.y,
+            accel.length(),
+            distance_variance_factor,
+            None,
+        )?;
+        return Some(ShotTrajectory {
+            angle: traj.angle - rotation,
+            ..traj
+        });
+    }
+
     let tx = target_x - shooter_x; // Positive = target is right, negative = left
     let ty = target_y - shooter_y; // Positive = target is above, negative = below
     let dx = tx.abs(); // Horizontal distance (always positive)
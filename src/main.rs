@@ -2,31 +2,38 @@
 //!
 //! Main entry point: app setup and system registration.
 
-use ballgame::ui::spawn_steal_indicators;
+use ballgame::cli::{self, Command};
+use ballgame::generate::integrity;
+use ballgame::simulation::{SimConfig, run_simulation};
+use ballgame::ui::{
+    AbilityHudAnchor, spawn_ability_cluster, spawn_charge_feedback_markers, spawn_steal_indicators,
+};
 use ballgame::{
-    AiCapabilities, AiGoal, AiNavState, AiProfileDatabase, AiState, Ball, BallPlayerContact,
+    AiCapabilities, AiGoal, AiNavState, AiProfileDatabase, AiSet, AiState, Ball, BallPlayerContact,
     BallPulse, BallRolling, BallShotGrace, BallSpin, BallState, BallStyle, BallTextures,
     ChargeGaugeBackground, ChargeGaugeFill, ChargingShot, ConfigWatcher, CoyoteTimer, CurrentLevel,
     CurrentPalette, CurrentPresets, CurrentSettings, CycleIndicator, CycleSelection,
-    DebugLogConfig, DebugSettings, DebugText, DisplayBallWave, EventBus, Facing, Grounded,
-    HumanControlTarget, HumanControlled, InputState, JumpState, LastShotInfo, LevelChangeTracker,
-    LevelDatabase, MatchCountdown, NavGraph, PALETTES_FILE, PRESETS_FILE, PaletteDatabase,
-    PhysicsTweaks, Player, PlayerId, PlayerInput, PresetDatabase, Score, ScoreLevelText,
-    SnapshotConfig, SnapshotTriggerState, StealContest, StealCooldown, StealTracker, StyleTextures,
-    TargetBasket, Team, TweakPanel, TweakPanelState, TweakRow, Velocity, ViewportScale, ai,
-    apply_preset_to_tweaks, ball, config_watcher, constants::*, countdown, display_ball_wave,
-    emit_level_change_events, input, levels, player, replay, save_settings_system, scoring,
-    shooting, snapshot, spawn_countdown_text, steal, tuning, ui, update_event_bus_time, world,
+    DebugLogConfig, DebugSettings, DebugText, DisplayBallWave, EventBus, Facing, GameClock,
+    GifCapture, GifCaptureConfig, Grounded, HumanControlTarget, HumanControlled, InputBindings,
+    InputSet, InputState, JumpState, LastShotInfo, LevelChangeTracker, LevelDatabase,
+    MatchCountdown, MatchSetsPlugin, Momentum, NavGraph, PALETTES_FILE, PRESETS_FILE,
+    PaletteDatabase, PhysicsSet, PhysicsTweaks, Player, PlayerId, PlayerInput, PossessionArrow,
+    PresetDatabase, Score, ScoreLevelText, ScoringSet, SecondHuman, SecondPlayerInput,
+    SnapshotConfig, SnapshotTriggerState, SpectateConfig, SpectatePlugin, StealContest,
+    StealCooldown, StealTracker, TargetBasket, Team, TweakPanel, TweakPanelState, TweakRow,
+    Velocity, ViewportScale, ai, apply_preset_to_tweaks, ball, config_watcher, constants::*,
+    countdown, db_maintenance, display_ball_wave, emit_level_change_events, input, levels,
+    momentum, player, replay, save_settings_system, scoring, series, shooting, snapshot,
+    spawn_countdown_text, steal, teams, tick_game_clock, time_dilation, tuning, ui,
+    update_event_bus_time, world,
 };
 use bevy::{camera::ScalingMode, diagnostic::FrameTimeDiagnosticsPlugin, prelude::*};
-use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 use world::{Basket, Collider};
 
 /// Path to ball options file
 const BALL_OPTIONS_FILE: &str = "config/ball_options.txt";
-const DEFAULT_REPLAY_DB: &str = "db/training.db";
 const DEFAULT_REPLAY_TIMEOUT_SECS: f32 = 5.0;
 
 /// Parse ball_options.txt to get list of style names
@@ -53,9 +60,54 @@ fn load_ball_style_names() -> Vec<String> {
     styles
 }
 
+/// Reduced ball texture sizes from ball_options.txt that have been generated
+fn load_ball_texture_sizes(style_names: &[String]) -> Vec<u32> {
+    let content = fs::read_to_string(BALL_OPTIONS_FILE).unwrap_or_default();
+    let Some(first_style) = style_names.first() else {
+        return Vec::new();
+    };
+    ball::ball_texture_sizes(&content)
+        .into_iter()
+        .filter(|&size| {
+            let generated = Path::new("assets")
+                .join(ball::ball_texture_path(first_style, 0, Some(size)))
+                .exists();
+            if !generated {
+                warn!(
+                    "Ball textures: {}px set not generated (cargo run --bin generate ball)",
+                    size
+                );
+            }
+            generated
+        })
+        .collect()
+}
+
 fn main() {
-    // Parse command-line arguments
-    let args: Vec<String> = std::env::args().collect();
+    match cli::parse().command {
+        Command::Play(play) => run_game(play.to_args()),
+        Command::Replay(replay) => run_game(replay.to_args()),
+        Command::Simulate(sim) => run_simulation(SimConfig::from_arg_list(&sim.to_args())),
+        Command::Generate { target } => target.run(),
+        Command::Seeds { action } => {
+            if let Some(args) = action.run() {
+                run_game(args);
+            }
+        }
+        Command::Profiles { action } => action.run(),
+        Command::Db { action } => action.run(),
+        Command::Train(train) => cli::run_tool(&train.to_args()),
+        Command::Heatmap(heatmap) => cli::run_tool(&heatmap.to_args()),
+        Command::Report(report) => {
+            let mut args = vec!["analyze".to_string()];
+            args.extend(report.rest);
+            cli::run_tool(&args)
+        }
+    }
+}
+
+/// Run the game with a legacy-style flag list (built by `cli::PlayArgs`/`ReplayArgs`)
+fn run_game(args: Vec<String>) {
     let screenshot_and_quit = args.iter().any(|a| a == "--screenshot-and-quit");
 
     // Check for --level <name> override (accepts level name, looked up at runtime)
@@ -80,6 +132,100 @@ fn main() {
     // Check for --freeze-countdown flag
     let freeze_countdown = args.iter().any(|a| a == "--freeze-countdown");
 
+    // Check for --local-versus flag (two humans sharing the keyboard)
+    let local_versus = args.iter().any(|a| a == "--local-versus");
+
+    // Check for --split-input flag (keyboard and each gamepad drive their own player)
+    let device_assignment = input::InputDeviceAssignment::from_args(&args);
+    if local_versus && device_assignment.enabled {
+        warn!("--split-input replaces --local-versus; ignoring --local-versus");
+    }
+    let local_versus = local_versus && !device_assignment.enabled;
+
+    // Check for --possession-arrow flag (dead balls alternate between teams)
+    let possession_arrow = args.iter().any(|a| a == "--possession-arrow");
+
+    // Check for --momentum flag (consecutive goals give the scorer a hot streak)
+    let momentum = args.iter().any(|a| a == "--momentum");
+
+    // Check for --loose-ball flag (successful steals knock the ball free)
+    let loose_ball = args.iter().any(|a| a == "--loose-ball");
+
+    // Check for --rim-camping-violation flag (defensive three seconds on your own rim)
+    let rim_camping_violation = args.iter().any(|a| a == "--rim-camping-violation");
+
+    // Check for --shot-clock <SECS> (holding the ball that long without shooting pops it loose)
+    let shot_clock = args
+        .iter()
+        .position(|a| a == "--shot-clock")
+        .and_then(|i| args.get(i + 1).and_then(|s| s.parse::<f32>().ok()));
+
+    // Check for --out-of-bounds flag (a ball leaving the court is a turnover throw-in)
+    let out_of_bounds = args.iter().any(|a| a == "--out-of-bounds");
+
+    // Check for --ball-reset <center|inbound|random> (where play restarts after a goal)
+    let ball_reset = args
+        .iter()
+        .position(|a| a == "--ball-reset")
+        .and_then(|i| args.get(i + 1))
+        .map(|s| {
+            scoring::BallResetMode::parse(s).unwrap_or_else(|| {
+                warn!(
+                    "--ball-reset expects center, inbound or random (got '{}')",
+                    s
+                );
+                scoring::BallResetMode::default()
+            })
+        })
+        .unwrap_or_default();
+
+    // Check for --nav-edge-penalty flag (AI routes around nav edges that keep failing)
+    let nav_edge_penalty = args.iter().any(|a| a == "--nav-edge-penalty");
+
+    // Check for --first-to <N> / --series <N> (matches that end, rematches, best-of-N series)
+    let versus_series = series::VersusSeries::from_args(&args);
+
+    // Check for --2v2 flag (an AI teammate on each side)
+    let team_size = player::TeamSize::from_args(&args);
+
+    // Check for --handicap-left / --handicap-right <head=N,shot=X,cooldown=X>
+    let handicap = scoring::Handicap::from_args(&args).unwrap_or_else(|e| {
+        warn!("{}; playing without handicaps", e);
+        scoring::Handicap::default()
+    });
+
+    // Check for --team-left / --team-right <name=Reds,color=#d03030>
+    let team_identity = teams::TeamIdentity::from_args(&args).unwrap_or_else(|e| {
+        warn!("{}; playing without team names", e);
+        teams::TeamIdentity::default()
+    });
+
+    // Check for --instant-replay flag (slow-motion replay after each goal)
+    let instant_replay = args.iter().any(|a| a == "--instant-replay");
+
+    // Check for --setup flag (run the first-run setup wizard again)
+    let rerun_setup = args.iter().any(|a| a == "--setup");
+
+    // Check for --ability-hud-corners flag (ability clusters in screen corners)
+    let ability_hud_corners = args.iter().any(|a| a == "--ability-hud-corners");
+
+    // Check for --ai-labels flag (AI goal labels above AI players)
+    let ai_labels = args.iter().any(|a| a == "--ai-labels");
+
+    // Check for --gif-secs <secs> (length of F6 GIF captures)
+    let gif_capture_config = GifCaptureConfig {
+        duration_secs: args
+            .iter()
+            .position(|a| a == "--gif-secs")
+            .and_then(|i| args.get(i + 1))
+            .and_then(|s| s.parse::<f32>().ok())
+            .unwrap_or(GifCaptureConfig::default().duration_secs),
+        ..default()
+    };
+
+    // Check for --spectate-file / --spectate-port (periodic JSON snapshots for dashboards)
+    let spectate_config = SpectateConfig::from_args(&args);
+
     // Check for replay mode: --replay-db <match_id>
     let replay_db_match_id = args
         .iter()
@@ -87,6 +233,47 @@ fn main() {
         .and_then(|i| args.get(i + 1))
         .and_then(|s| s.parse::<i64>().ok());
 
+    // Database for --replay-db: --replay-db-path <file> (default: training database)
+    let replay_db_path = args
+        .iter()
+        .position(|a| a == "--replay-db-path")
+        .and_then(|i| args.get(i + 1))
+        .map(std::path::PathBuf::from);
+
+    // Check for session playlist: --replay-session <id|latest> [--replay-filter underdog]
+    let replay_playlist = args
+        .iter()
+        .position(|a| a == "--replay-session")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|session| {
+            let filter = args
+                .iter()
+                .position(|a| a == "--replay-filter")
+                .and_then(|i| args.get(i + 1))
+                .map(|f| {
+                    replay::PlaylistFilter::parse(f).unwrap_or_else(|| {
+                        warn!("Unknown replay filter '{}'; playing all matches", f);
+                        replay::PlaylistFilter::All
+                    })
+                })
+                .unwrap_or_default();
+            let db_path = replay_db_path
+                .clone()
+                .unwrap_or_else(db_maintenance::latest_training_db);
+            replay::ReplayPlaylist::from_session(&db_path, session, filter)
+                .map_err(|e| error!("Replay playlist: {}", e))
+                .ok()
+        });
+    let replay_db_match_id =
+        replay_db_match_id.or_else(|| replay_playlist.as_ref()?.first_match_id());
+
+    // Check for clip replay: --replay-file <path> (saved with F5)
+    let replay_file = args
+        .iter()
+        .position(|a| a == "--replay-file")
+        .and_then(|i| args.get(i + 1))
+        .map(std::path::PathBuf::from);
+
     // Check for replay timeout: --replay-timeout <secs>
     let replay_timeout_secs = args.iter().position(|a| a == "--replay-timeout").map(|i| {
         args.get(i + 1)
@@ -94,6 +281,22 @@ fn main() {
             .unwrap_or(DEFAULT_REPLAY_TIMEOUT_SECS)
     });
 
+    // Check for replay focus: --replay-focus <left|right>, --replay-markers <type>
+    let flag_value = |flag: &str| {
+        args.iter()
+            .position(|a| a == flag)
+            .and_then(|i| args.get(i + 1))
+            .map(String::as_str)
+    };
+    let replay_focus = replay::ReplayFocus::from_args(
+        flag_value("--replay-focus"),
+        flag_value("--replay-markers"),
+    )
+    .unwrap_or_else(|e| {
+        warn!("{}; showing all players and markers", e);
+        replay::ReplayFocus::default()
+    });
+
     // Load persistent settings (uses defaults if file doesn't exist)
     let current_settings = CurrentSettings::default();
 
@@ -105,6 +308,24 @@ fn main() {
     // Load level database from file (needed for level name lookup)
     let level_db = LevelDatabase::load_from_file(LEVELS_FILE);
 
+    // Verify generated assets (ball textures, AI heatmaps) match the current config.
+    // --regenerate-assets rebuilds without asking; --skip-asset-check bypasses the check.
+    if !args.iter().any(|a| a == "--skip-asset-check") {
+        let report = integrity::check_assets(&level_db);
+        if !report.is_clean() {
+            report.print();
+            if args.iter().any(|a| a == "--regenerate-assets") || integrity::confirm_regenerate() {
+                if let Err(e) = integrity::regenerate(&report) {
+                    eprintln!("Asset regeneration failed: {}", e);
+                }
+            } else {
+                eprintln!(
+                    "Continuing with existing assets (run with --regenerate-assets to rebuild)"
+                );
+            }
+        }
+    }
+
     // Resolve level: CLI name override -> saved settings -> first level
     // Supports: level ID (16-char hex), level name, or level number (backward compat)
     let resolve_level_id = |input: &str| -> Option<String> {
@@ -141,12 +362,30 @@ fn main() {
                 .unwrap_or_default()
         });
 
+    // An unfinished --series carries on where it was left, on its own level
+    let mut versus_series = if replay_db_match_id.is_none() && replay_file.is_none() {
+        versus_series.resume_from(Path::new(series::VERSUS_SERIES_FILE))
+    } else {
+        versus_series
+    };
+    let loaded_level_id = if level_db.get_by_id(&versus_series.level).is_some() {
+        versus_series.level.clone()
+    } else {
+        loaded_level_id
+    };
+    versus_series.level = loaded_level_id.clone();
+
     // Extract values from loaded settings for resource initialization
     let loaded_viewport_index = current_settings.settings.viewport_index;
     let loaded_palette_index = palette_override.unwrap_or(current_settings.settings.palette_index);
     let loaded_active_direction = current_settings.settings.active_direction.clone();
     let loaded_down_option = current_settings.settings.down_option.clone();
     let loaded_right_option = current_settings.settings.right_option.clone();
+    let ability_hud_anchor = if ability_hud_corners {
+        AbilityHudAnchor::Corners
+    } else {
+        AbilityHudAnchor::from_str(&current_settings.settings.ability_hud_anchor)
+    };
 
     // Check if initial level is a regression level (for countdown freezing)
     let is_regression_level = level_db
@@ -157,6 +396,18 @@ fn main() {
     // Check if countdown should be frozen (regression level or explicit flag)
     let should_freeze_countdown = is_regression_level || freeze_countdown;
 
+    // First-run setup wizard (not for replays or scripted screenshots); it holds the
+    // countdown until it closes
+    let show_setup = (rerun_setup || !current_settings.settings.setup_complete)
+        && !screenshot_and_quit
+        && replay_db_match_id.is_none()
+        && replay_file.is_none();
+    let setup_wizard = ui::SetupWizard::new(show_setup, !should_freeze_countdown);
+    let mut assists = ui::Assists::from_settings(&current_settings.settings);
+    assists.ai_goal_labels |= ai_labels;
+    let primary_bindings = input::KeyBindings::from_preset(&current_settings.settings.key_bindings)
+        .unwrap_or_else(input::KeyBindings::solo);
+
     // Load palette database (creates default file if missing)
     let palette_db = PaletteDatabase::load_or_create(PALETTES_FILE);
 
@@ -178,7 +429,6 @@ fn main() {
         (w, h)
     };
 
-    let args: Vec<String> = std::env::args().collect();
     let debug_config = DebugLogConfig::load_with_args(&args);
     debug_config.apply_env();
 
@@ -207,15 +457,42 @@ fn main() {
         .insert_resource(level_db)
         .insert_resource(current_settings)
         .init_resource::<PlayerInput>()
+        .init_resource::<SecondPlayerInput>()
+        .insert_resource(device_assignment)
+        .insert_resource(if local_versus {
+            InputBindings::local_versus()
+        } else {
+            InputBindings {
+                primary: primary_bindings,
+                secondary: None,
+            }
+        })
+        .insert_resource(setup_wizard)
+        .insert_resource(assists)
         .init_resource::<DebugSettings>()
         .init_resource::<StealContest>()
         .init_resource::<StealTracker>()
         .init_resource::<Score>()
+        .insert_resource(PossessionArrow::new(possession_arrow))
+        .insert_resource(handicap)
+        .insert_resource(team_identity)
+        .insert_resource(Momentum::new(momentum))
+        .insert_resource(steal::LooseBall::new(loose_ball))
+        .insert_resource(scoring::RimCamping::new(rim_camping_violation))
+        .insert_resource(scoring::ShotClock::new(shot_clock))
+        .insert_resource(scoring::OutOfBoundsRule::new(out_of_bounds))
+        .insert_resource(scoring::BallReset::new(ball_reset))
+        .insert_resource(ai::NavEdgeStats::new(nav_edge_penalty))
+        .init_resource::<ai::TeamBlackboard>()
+        .insert_resource(versus_series)
+        .insert_resource(team_size)
         .insert_resource(CurrentLevel(loaded_level_id))
         .insert_resource(CurrentPalette(loaded_palette_index))
         .insert_resource(debug_config)
         .init_resource::<PhysicsTweaks>()
+        .add_plugins(ballgame::config::GameplayConfig::load("play"))
         .init_resource::<TweakPanelState>()
+        .init_resource::<ui::EntityInspector>()
         .init_resource::<LastShotInfo>()
         .insert_resource(ViewportScale {
             preset_index: loaded_viewport_index,
@@ -232,7 +509,12 @@ fn main() {
         .init_resource::<CurrentPresets>()
         .init_resource::<NavGraph>()
         .init_resource::<AiCapabilities>()
+        .init_resource::<ui::HudLayoutEditor>()
+        .insert_resource(ai::ContestedQuality::load_dir(std::path::Path::new(
+            ai::CONTESTED_QUALITY_DIR,
+        )))
         .init_resource::<ai::HeatmapBundle>()
+        .init_resource::<levels::ArenaDims>()
         // Event bus for cross-module communication
         .insert_resource(EventBus::new())
         // Human control target (initialized in setup based on settings)
@@ -246,9 +528,11 @@ fn main() {
             ..default()
         })
         .init_resource::<SnapshotTriggerState>()
+        .insert_resource(gif_capture_config)
+        .init_resource::<GifCapture>()
         .init_resource::<DisplayBallWave>()
         // Initialize countdown (frozen if regression level or --freeze-countdown flag)
-        .insert_resource(if should_freeze_countdown {
+        .insert_resource(if should_freeze_countdown || show_setup {
             let mut countdown = MatchCountdown::default();
             countdown.start_frozen();
             countdown
@@ -257,7 +541,12 @@ fn main() {
         })
         // Replay mode resources
         .insert_resource(if let Some(match_id) = replay_db_match_id {
-            replay::ReplayMode::new_db(match_id)
+            replay::ReplayMode {
+                db_path: replay_db_path,
+                ..replay::ReplayMode::new_db(match_id)
+            }
+        } else if let Some(path) = replay_file {
+            replay::ReplayMode::new_file(path)
         } else {
             replay::ReplayMode::default()
         })
@@ -266,6 +555,12 @@ fn main() {
             active: replay_timeout_secs.is_some(),
         })
         .init_resource::<replay::ReplayState>()
+        .init_resource::<replay::ReplayHeatmapOverlay>()
+        .insert_resource(replay_playlist.unwrap_or_default())
+        .insert_resource(replay_focus)
+        .insert_resource(replay::InstantReplay::new(instant_replay))
+        .insert_resource(ability_hud_anchor)
+        .init_resource::<time_dilation::TimeDilation>()
         // Startup system - use normal setup only when NOT in replay mode
         .add_systems(Startup, tuning::load_global_tuning_system)
         .add_systems(Startup, setup.run_if(replay::not_replay_active))
@@ -275,27 +570,88 @@ fn main() {
             Update,
             countdown::update_countdown.run_if(replay::not_replay_active),
         )
-        // Event bus time update (runs every frame for timestamping)
+        // Game clock only runs during play (not countdowns, goal replays, F8 freezes or
+        // after a versus match has ended), and the event bus stamps events with it
+        .init_resource::<GameClock>()
         .add_systems(
             Update,
-            update_event_bus_time.run_if(replay::not_replay_active),
+            tick_game_clock.run_if(
+                replay::not_replay_active
+                    .and(countdown::not_in_countdown)
+                    .and(replay::not_instant_replay)
+                    .and(series::match_in_play),
+            ),
+        )
+        .add_systems(
+            Update,
+            update_event_bus_time
+                .after(tick_game_clock)
+                .run_if(replay::not_replay_active),
+        )
+        // Rolling clip recorder (always on; F5 saves the last 30 seconds)
+        .init_resource::<replay::ClipRecorder>()
+        .add_systems(
+            Update,
+            (replay::record_clip_frame, replay::save_clip_hotkey)
+                .chain()
+                .after(update_event_bus_time)
+                .run_if(replay::not_replay_active),
+        )
+        // Match sets: input -> AI in Update, physics -> scoring in FixedUpdate
+        // Only run when NOT in countdown, NOT in replay mode, NOT in a goal replay
+        // and NOT waiting for a versus rematch
+        .add_plugins(MatchSetsPlugin)
+        .configure_sets(
+            Update,
+            (InputSet, AiSet).run_if(
+                replay::not_replay_active
+                    .and(countdown::not_in_countdown)
+                    .and(replay::not_instant_replay)
+                    .and(series::match_in_play),
+            ),
+        )
+        .configure_sets(
+            FixedUpdate,
+            (PhysicsSet, ScoringSet).run_if(
+                replay::not_replay_active
+                    .and(countdown::not_in_countdown)
+                    .and(replay::not_instant_replay)
+                    .and(series::match_in_play),
+            ),
+        )
+        // Input systems must run in order: capture -> copy -> swap, then nav graph -> nav -> AI
+        // (split input captures per device straight into InputState instead)
+        .add_systems(
+            Update,
+            (
+                (
+                    input::capture_input,
+                    input::capture_second_input,
+                    ai::copy_human_input,
+                    ai::copy_second_human_input,
+                    ai::swap_control,
+                )
+                    .chain()
+                    .run_if(not(input::split_input_active)),
+                input::capture_device_input.run_if(input::split_input_active),
+            )
+                .in_set(InputSet),
         )
-        // Input systems must run in order: capture -> copy -> swap -> nav graph -> nav -> AI
-        // Only runs when NOT in countdown and NOT in replay mode
         .add_systems(
             Update,
             (
-                input::capture_input,
-                ai::copy_human_input,
-                ai::swap_control,
                 ai::mark_nav_dirty_on_level_change,
                 ai::load_heatmaps_on_level_change,
                 ai::rebuild_nav_graph,
+                ai::sync_nav_edge_penalties,
+                ai::update_team_blackboard,
                 ai::ai_navigation_update,
                 ai::ai_decision_update,
+                ai::ai_mistake_update,
+                ai::ai_policy_update,
             )
                 .chain()
-                .run_if(replay::not_replay_active.and(countdown::not_in_countdown)),
+                .in_set(AiSet),
         )
         // Settings reset (double-click Start) - must run before respawn
         .add_systems(
@@ -307,6 +663,23 @@ fn main() {
             Update,
             player::respawn_player.run_if(replay::not_replay_active),
         )
+        // Versus matches: end at --first-to, rematch once R / Start resets the score
+        .add_systems(
+            Startup,
+            series::spawn_series_banner.run_if(replay::not_replay_active),
+        )
+        .add_systems(
+            Update,
+            (series::update_versus_series, series::update_series_banner)
+                .chain()
+                .after(player::respawn_player)
+                .run_if(replay::not_replay_active),
+        )
+        // Resize walls/floor/camera to the active level's arena (also used by replays)
+        .add_systems(
+            Update,
+            levels::apply_arena_dims_on_level_change.after(player::respawn_player),
+        )
         // Emit level change events for auditability (runs after systems that change level)
         .add_systems(
             Update,
@@ -324,7 +697,17 @@ fn main() {
         )
         .add_systems(
             Update,
-            (ui::update_debug_text, ui::update_score_level_text).run_if(replay::not_replay_active),
+            (
+                ui::update_debug_text,
+                (
+                    scoring::reset_possession_arrow,
+                    momentum::update_momentum,
+                    scoring::apply_handicap,
+                    ui::update_score_level_text,
+                )
+                    .chain(),
+            )
+                .run_if(replay::not_replay_active),
         )
         .add_systems(
             Update,
@@ -332,9 +715,17 @@ fn main() {
                 ui::animate_pickable_ball,
                 ui::animate_score_flash,
                 ui::update_charge_gauge,
+                ui::update_charge_feedback,
                 ui::update_steal_indicators,
+                ui::shake_camera_on_steal_denial,
+                ui::update_ability_hud,
+                ui::spawn_ai_goal_labels,
+                ui::toggle_ai_goal_labels,
+                ui::update_ai_goal_labels,
                 display_ball_wave,
                 player::manage_debug_display,
+                levels::animate_wind_particles,
+                momentum::update_momentum_aura,
             )
                 .run_if(replay::not_replay_active),
         )
@@ -344,17 +735,40 @@ fn main() {
             (
                 ui::toggle_tweak_panel,
                 ui::update_tweak_panel,
+                ui::update_entity_inspector,
                 ui::cycle_viewport,
                 ui::unified_cycle_system,
+                (ui::toggle_hud_layout_editor, ui::hud_layout_editor).chain(),
             )
+                .run_if(replay::not_replay_active.and(ui::setup_wizard_inactive)),
+        )
+        // HUD layout manager (after systems that position their own elements)
+        .add_systems(
+            Update,
+            ui::apply_hud_layout
+                .after(ui::update_ability_hud)
                 .run_if(replay::not_replay_active),
         )
+        // Entity inspector (F10, hidden until toggled)
+        .add_systems(
+            Startup,
+            ui::spawn_entity_inspector.run_if(replay::not_replay_active),
+        )
+        // First-run setup wizard (holds the countdown until closed)
+        .add_systems(Startup, ui::spawn_setup_wizard.after(setup))
+        .add_systems(
+            Update,
+            ui::update_setup_wizard.run_if(ui::setup_wizard_active),
+        )
         // Cycle indicator, palette application, and preset application
         .add_systems(
             Update,
             (
                 ui::update_cycle_indicator,
                 ui::apply_palette_colors,
+                teams::apply_team_colors.after(ui::apply_palette_colors),
+                ball::select_ball_texture_size,
+                ball::apply_ball_style_physics,
                 apply_preset_to_tweaks,
             )
                 .run_if(replay::not_replay_active),
@@ -370,6 +784,25 @@ fn main() {
             )
                 .run_if(replay::not_replay_active),
         )
+        // Time dilation debug controls (F7 slow-mo, F8 freeze, F9 step one tick)
+        .add_systems(
+            Startup,
+            time_dilation::spawn_time_dilation_text.run_if(replay::not_replay_active),
+        )
+        .add_systems(
+            Update,
+            (
+                time_dilation::time_dilation_input,
+                time_dilation::apply_time_dilation,
+                time_dilation::step_fixed_update,
+            )
+                .chain()
+                .run_if(replay::not_replay_active),
+        )
+        // F6 GIF capture (also works while watching a replay)
+        .add_systems(Update, snapshot::gif_capture_system)
+        // Spectate publisher (only with --spectate-* flags and the `spectate` feature)
+        .add_plugins(SpectatePlugin(spectate_config))
         // Settings persistence - save when dirty
         .add_systems(
             Update,
@@ -383,20 +816,51 @@ fn main() {
                 player::apply_gravity,
                 ball::ball_gravity,
                 ball::ball_spin,
+                levels::apply_wind_forces,
+                levels::move_baskets,
                 ball::apply_velocity,
                 player::check_collisions,
                 ball::ball_collisions,
+                ball::ball_rails,
+                levels::level_feature_traversal,
                 ball::ball_state_update,
                 ball::ball_player_collision,
                 ball::ball_follow_holder,
-                ball::pickup_ball,
+                (ball::pickup_ball, shooting::catch_pass).chain(),
                 steal::steal_cooldown_update,
                 shooting::update_shot_charge,
-                shooting::throw_ball,
+                (shooting::throw_ball, shooting::pass_ball).chain(),
+            )
+                .chain()
+                .in_set(PhysicsSet),
+        )
+        .add_systems(
+            FixedUpdate,
+            (
                 scoring::check_scoring,
+                scoring::shot_clock_update,
+                scoring::check_out_of_bounds,
+                ball::recover_stuck_ball,
             )
                 .chain()
-                .run_if(replay::not_replay_active.and(countdown::not_in_countdown)),
+                .in_set(ScoringSet),
+        )
+        // Goal replays: record live ticks around scoring, play back in Update
+        .add_systems(
+            FixedUpdate,
+            (
+                replay::record_instant_replay.before(scoring::check_scoring),
+                replay::start_instant_replay.after(scoring::check_scoring),
+            )
+                .run_if(
+                    replay::not_replay_active
+                        .and(countdown::not_in_countdown)
+                        .and(replay::not_instant_replay),
+                ),
+        )
+        .add_systems(
+            Update,
+            replay::play_instant_replay.run_if(replay::not_replay_active),
         )
         // =========== REPLAY MODE SYSTEMS ===========
         // Replay startup - load file, setup camera
@@ -404,7 +868,12 @@ fn main() {
         // Replay setup - spawn game world (runs after load, needs ReplayData)
         .add_systems(
             Startup,
-            (replay::replay_setup, replay::setup_replay_ui)
+            (
+                replay::replay_setup,
+                replay::setup_replay_ui,
+                replay::setup_replay_focus,
+                replay::setup_replay_heatmap,
+            )
                 .run_if(replay::replay_active)
                 .after(replay_load_file),
         )
@@ -414,7 +883,12 @@ fn main() {
             (
                 replay::replay_playback,
                 replay::replay_input_handler,
+                replay::replay_focus_input,
                 replay::update_replay_ui,
+                replay::update_replay_focus,
+                replay::advance_replay_playlist,
+                replay::replay_heatmap_input,
+                replay::update_replay_heatmap,
             )
                 .chain()
                 .run_if(replay::replay_active),
@@ -433,6 +907,8 @@ fn setup(
     current_settings: Res<CurrentSettings>,
     profile_db: Res<AiProfileDatabase>,
     mut human_target: ResMut<HumanControlTarget>,
+    bindings: Res<InputBindings>,
+    team_size: Res<player::TeamSize>,
 ) {
     // Camera - orthographic, shows entire arena
     // FixedVertical ensures the full arena height is always visible regardless of window size
@@ -467,8 +943,9 @@ fn setup(
         .map(|p| p.id.clone())
         .unwrap_or_else(|| profile_db.default_profile().id.clone());
 
-    // Determine if left player is human or AI based on settings
-    let left_is_human = current_settings.settings.left_ai_profile.is_none();
+    // Determine if left player is human or AI based on settings (always human in local versus)
+    let left_is_human =
+        bindings.is_local_versus() || current_settings.settings.left_ai_profile.is_none();
 
     // Initialize HumanControlTarget based on whether left player is human-controlled
     human_target.0 = if left_is_human {
@@ -484,7 +961,7 @@ fn setup(
     let left_player = commands
         .spawn((
             Sprite::from_color(initial_palette.left, PLAYER_SIZE),
-            Transform::from_translation(PLAYER_SPAWN_LEFT),
+            Transform::from_translation(level_db.player_spawn(&current_level.0, true)),
             (
                 Player,
                 Velocity::default(),
@@ -507,7 +984,7 @@ fn setup(
                     } else {
                         AiGoal::default()
                     },
-                    profile_id: left_ai_profile_id,
+                    profile_id: left_ai_profile_id.clone(),
                     ..default()
                 },
                 AiNavState::default(),
@@ -525,7 +1002,7 @@ fn setup(
     let right_player = commands
         .spawn((
             Sprite::from_color(initial_palette.right, PLAYER_SIZE),
-            Transform::from_translation(PLAYER_SPAWN_RIGHT),
+            Transform::from_translation(level_db.player_spawn(&current_level.0, false)),
             (
                 Player,
                 Velocity::default(),
@@ -545,7 +1022,7 @@ fn setup(
                     } else {
                         AiGoal::default()
                     },
-                    profile_id: right_ai_profile_id,
+                    profile_id: right_ai_profile_id.clone(),
                     ..default()
                 },
                 AiNavState::default(),
@@ -554,6 +1031,14 @@ fn setup(
         ))
         .id();
 
+    // Local versus: the second keyboard slot drives the right player
+    if bindings.is_local_versus() {
+        commands
+            .entity(right_player)
+            .insert((HumanControlled, SecondHuman));
+        info!("Local versus: WASD (left) vs arrows + numpad (right)");
+    }
+
     // Charge gauge - inside player, opposite side of ball
     // Start on left side (default facing is right, so ball is right, gauge is left)
     let gauge_x = -PLAYER_SIZE.x / 4.0;
@@ -611,29 +1096,58 @@ fn setup(
         .id();
     commands.entity(right_player).add_child(right_gauge_fill);
 
+    // Post-release charge markers (release point vs ideal charge)
+    spawn_charge_feedback_markers(&mut commands, left_player, gauge_x);
+    spawn_charge_feedback_markers(&mut commands, right_player, right_gauge_x);
+
     // Steal indicators for both players
     spawn_steal_indicators(&mut commands, left_player, 1.0); // Left player faces right
     spawn_steal_indicators(&mut commands, right_player, -1.0); // Right player faces left
 
+    // 2v2: an AI teammate behind each player
+    if team_size.has_teammates() {
+        let teammate_goal = if is_special_level {
+            AiGoal::Idle
+        } else {
+            AiGoal::default()
+        };
+        player::spawn_teammate(
+            &mut commands,
+            Team::Left,
+            level_db.player_spawn(&current_level.0, true),
+            initial_palette.left,
+            left_ai_profile_id,
+            teammate_goal,
+        );
+        player::spawn_teammate(
+            &mut commands,
+            Team::Right,
+            level_db.player_spawn(&current_level.0, false),
+            initial_palette.right,
+            right_ai_profile_id,
+            teammate_goal,
+        );
+    }
+
+    // Ability clusters (cooldown bars) for both players
+    spawn_ability_cluster(&mut commands, left_player);
+    spawn_ability_cluster(&mut commands, right_player);
+
     // Load ball style names from config file
     let style_names = load_ball_style_names();
+    commands.insert_resource(ball::BallStylePhysics::parse(
+        &fs::read_to_string(BALL_OPTIONS_FILE).unwrap_or_default(),
+    ));
     let num_palettes = palette_db.len();
 
-    // Load ball textures for all styles dynamically
-    let mut styles_map = HashMap::new();
-    for style_name in &style_names {
-        let textures = StyleTextures {
-            textures: (0..num_palettes)
-                .map(|i| asset_server.load(format!("textures/balls/ball_{}_{}.png", style_name, i)))
-                .collect(),
-        };
-        styles_map.insert(style_name.clone(), textures);
-    }
-
-    let ball_textures = BallTextures {
-        styles: styles_map,
-        style_order: style_names.clone(),
-    };
+    // Load ball textures for all styles dynamically (reduced sizes swap in per window size)
+    let ball_textures = BallTextures::load(
+        &asset_server,
+        &style_names,
+        num_palettes,
+        load_ball_texture_sizes(&style_names),
+        None,
+    );
     commands.insert_resource(ball_textures.clone());
 
     // Check if this is a debug level (spawns all ball styles, AI idle)
@@ -653,7 +1167,7 @@ fn setup(
                     custom_size: Some(BALL_SIZE),
                     ..default()
                 },
-                Transform::from_translation(BALL_SPAWN),
+                Transform::from_translation(level_db.ball_spawn(&current_level.0)),
                 Ball,
                 BallState::default(),
                 Velocity::default(),
@@ -683,7 +1197,7 @@ fn setup(
                     custom_size: Some(BALL_SIZE),
                     ..default()
                 },
-                Transform::from_translation(BALL_SPAWN),
+                Transform::from_translation(level_db.ball_spawn(&current_level.0)),
                 Ball,
                 BallState::default(),
                 Velocity::default(),
@@ -711,14 +1225,16 @@ fn setup(
 
     // Baskets with rims (shared spawning function)
     let initial_level = level_data;
+    let initial_arena = initial_level.map(|l| l.arena()).unwrap_or_default();
     let basket_y = initial_level
-        .map(|l| ARENA_FLOOR_Y + l.basket_height)
+        .map(|l| l.floor_y + l.basket_height)
         .unwrap_or(ARENA_FLOOR_Y + 400.0);
     let basket_push_in = initial_level
         .map(|l| l.basket_push_in)
         .unwrap_or(BASKET_PUSH_IN);
     world::spawn_baskets(
         &mut commands,
+        &initial_arena,
         basket_y,
         basket_push_in,
         initial_palette.left,
@@ -742,6 +1258,7 @@ fn setup(
         .unwrap_or(STEP_PUSH_IN);
     levels::spawn_corner_ramps(
         &mut commands,
+        &initial_arena,
         initial_step_count,
         initial_corner_height,
         initial_corner_width,
@@ -760,6 +1277,10 @@ fn setup(
         TextColor(TEXT_PRIMARY),
         Transform::from_xyz(0.0, ARENA_HEIGHT / 2.0 - 30.0, 1.0),
         ScoreLevelText,
+        ui::HudElement::at(
+            ui::HudElementKind::Scoreboard,
+            Vec2::new(0.0, ARENA_HEIGHT / 2.0 - 30.0),
+        ),
     ));
 
     // Debug UI - world space, centered on floor
@@ -772,6 +1293,10 @@ fn setup(
         TextColor(TEXT_PRIMARY),
         Transform::from_xyz(0.0, ARENA_FLOOR_Y + 10.0, 1.0),
         DebugText,
+        ui::HudElement::at(
+            ui::HudElementKind::DebugLine,
+            Vec2::new(0.0, ARENA_FLOOR_Y + 10.0),
+        ),
     ));
 
     // Cycle indicator - 4 separate lines for individual styling
@@ -782,6 +1307,7 @@ fn setup(
     let cycle_line_spacing = 22.0;
 
     for i in 0..4 {
+        let line_y = cycle_base_y - (i as f32 * cycle_line_spacing);
         commands.spawn((
             Text2d::new(""),
             TextFont {
@@ -790,12 +1316,12 @@ fn setup(
             },
             TextLayout::new_with_justify(Justify::Left),
             TextColor(TEXT_ACCENT),
-            Transform::from_xyz(
-                cycle_base_x,
-                cycle_base_y - (i as f32 * cycle_line_spacing),
-                1.0,
-            ),
+            Transform::from_xyz(cycle_base_x, line_y, 1.0),
             CycleIndicator(i),
+            ui::HudElement::at(
+                ui::HudElementKind::CycleMenu,
+                Vec2::new(cycle_base_x, line_y),
+            ),
         ));
     }
 
@@ -858,24 +1384,38 @@ fn setup(
 
     // Countdown text (3-2-1 before match starts)
     spawn_countdown_text(&mut commands);
+    replay::spawn_instant_replay_text(&mut commands);
 }
 
 /// Setup system for replay mode - loads replay data
 fn replay_load_file(mut commands: Commands, replay_mode: Res<replay::ReplayMode>) {
     let replay_result = if let Some(match_id) = replay_mode.match_id {
-        replay::load_replay_from_db(Path::new(DEFAULT_REPLAY_DB), match_id)
+        let db_path = replay_mode
+            .db_path
+            .clone()
+            .unwrap_or_else(db_maintenance::latest_training_db);
+        replay::load_replay_windowed(&db_path, match_id)
             .map_err(|e| format!("Failed to load replay from DB match {}: {}", match_id, e))
+    } else if let Some(path) = &replay_mode.file {
+        replay::load_replay_from_file(path)
     } else {
-        Err("Replay mode active but no match ID specified".to_string())
+        Err("Replay mode active but no match ID or clip file specified".to_string())
     };
 
     match replay_result {
         Ok(replay_data) => {
-            info!(
-                "Loaded replay: {} ticks, {} events",
-                replay_data.ticks.len(),
-                replay_data.events.len()
-            );
+            if replay_data.stream.is_some() {
+                info!(
+                    "Loaded replay: {} events, ticks streamed from the database",
+                    replay_data.events.len()
+                );
+            } else {
+                info!(
+                    "Loaded replay: {} ticks, {} events",
+                    replay_data.ticks.len(),
+                    replay_data.events.len()
+                );
+            }
             commands.insert_resource(replay_data);
         }
         Err(e) => {
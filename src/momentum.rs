@@ -0,0 +1,263 @@
+//! Momentum - hot streaks for consecutive goals (optional rule, off by default)
+//!
+//! A team that scores MOMENTUM_STREAK goals in a row without conceding heats up:
+//! for MOMENTUM_DURATION seconds its players move and charge shots slightly
+//! faster and glow with an aura. Each further goal renews the timer; conceding
+//! ends the run. Heating up and cooling off are logged as `Momentum` events, and
+//! AI defenders play tighter and more aggressively against a hot opponent.
+
+use bevy::prelude::*;
+
+use crate::ai::AiProfile;
+use crate::constants::*;
+use crate::events::{EventBus, GameEvent, PlayerId};
+use crate::player::{Player, Team};
+use crate::scoring::Score;
+
+/// Hot-streak state for the current game
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Momentum {
+    pub enabled: bool,
+    /// Team on the current scoring run
+    pub team: Option<Team>,
+    /// Consecutive goals in the current run
+    pub streak: u32,
+    /// Seconds of hot streak left (0 = not hot)
+    pub remaining: f32,
+}
+
+fn player_id(team: Team) -> PlayerId {
+    match team {
+        Team::Left => PlayerId::L,
+        Team::Right => PlayerId::R,
+    }
+}
+
+impl Momentum {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..default()
+        }
+    }
+
+    /// Record a goal. Returns an event when the scorer heats up or a hot
+    /// opponent's run is broken.
+    pub fn on_goal(&mut self, team: Team) -> Option<GameEvent> {
+        if !self.enabled {
+            return None;
+        }
+        if self.team == Some(team) {
+            self.streak += 1;
+        } else {
+            let broken = self.hot_team();
+            self.team = Some(team);
+            self.streak = 1;
+            self.remaining = 0.0;
+            if let Some(cooled) = broken {
+                return Some(GameEvent::Momentum {
+                    player: player_id(cooled),
+                    streak: 0,
+                });
+            }
+        }
+        if self.streak < MOMENTUM_STREAK {
+            return None;
+        }
+        self.remaining = MOMENTUM_DURATION;
+        Some(GameEvent::Momentum {
+            player: player_id(team),
+            streak: self.streak,
+        })
+    }
+
+    /// Run the hot-streak timer down. Returns an event when it runs out.
+    pub fn tick(&mut self, dt: f32) -> Option<GameEvent> {
+        let team = self.hot_team()?;
+        self.remaining = (self.remaining - dt).max(0.0);
+        (self.remaining == 0.0).then(|| GameEvent::Momentum {
+            player: player_id(team),
+            streak: 0,
+        })
+    }
+
+    /// Clear the run for a new game
+    pub fn reset(&mut self) {
+        *self = Self::new(self.enabled);
+    }
+
+    /// Team currently on a hot streak
+    pub fn hot_team(&self) -> Option<Team> {
+        self.team.filter(|_| self.remaining > 0.0)
+    }
+
+    pub fn is_hot(&self, team: Team) -> bool {
+        self.hot_team() == Some(team)
+    }
+
+    /// Move speed multiplier for `team`
+    pub fn move_multiplier(&self, team: Team) -> f32 {
+        if self.is_hot(team) {
+            1.0 + MOMENTUM_MOVE_BONUS
+        } else {
+            1.0
+        }
+    }
+
+    /// Shot charge rate multiplier for `team`
+    pub fn charge_multiplier(&self, team: Team) -> f32 {
+        if self.is_hot(team) {
+            1.0 + MOMENTUM_CHARGE_BONUS
+        } else {
+            1.0
+        }
+    }
+
+    /// Profile an AI on `team` should play with: tighter, more aggressive
+    /// defense while the opponent is hot, otherwise unchanged
+    pub fn adjust_profile(&self, team: Team, profile: &AiProfile) -> Option<AiProfile> {
+        let hot = self.hot_team()?;
+        if hot == team {
+            return None;
+        }
+        let mut adjusted = profile.clone();
+        adjusted.aggression = (adjusted.aggression + MOMENTUM_AI_AGGRESSION).min(1.0);
+        adjusted.pressure_distance *= MOMENTUM_AI_PRESSURE;
+        Some(adjusted)
+    }
+}
+
+/// Reset the run whenever the score goes back to 0-0 (match reset, level
+/// change, new training game) and run the hot-streak timer down.
+/// Chain before `apply_handicap` so a head start doesn't hide the reset.
+pub fn update_momentum(
+    time: Res<Time>,
+    score: Res<Score>,
+    momentum: Option<ResMut<Momentum>>,
+    mut event_bus: ResMut<EventBus>,
+) {
+    let Some(mut momentum) = momentum else {
+        return;
+    };
+    if !momentum.enabled {
+        return;
+    }
+    if score.is_changed() && score.left == 0 && score.right == 0 {
+        momentum.reset();
+        return;
+    }
+    // Use minimum dt for headless mode compatibility
+    if let Some(event) = momentum.tick(time.delta_secs().max(1.0 / 60.0)) {
+        event_bus.emit(event);
+    }
+}
+
+/// Glow behind a player whose team is on a hot streak (follows its owner)
+#[derive(Component)]
+pub struct MomentumAura(pub Entity);
+
+/// Spawn, move and pulse player auras; despawn auras whose player is gone
+pub fn update_momentum_aura(
+    mut commands: Commands,
+    time: Res<Time>,
+    momentum: Option<Res<Momentum>>,
+    players: Query<(Entity, &Transform, &Sprite, &Team), With<Player>>,
+    mut auras: Query<
+        (
+            Entity,
+            &MomentumAura,
+            &mut Transform,
+            &mut Sprite,
+            &mut Visibility,
+        ),
+        Without<Player>,
+    >,
+) {
+    let Some(momentum) = momentum.filter(|m| m.enabled) else {
+        return;
+    };
+    let pulse = 0.3 + 0.15 * (time.elapsed_secs() * 6.0).sin();
+
+    let mut has_aura = Vec::new();
+    for (aura_entity, aura, mut transform, mut sprite, mut visibility) in &mut auras {
+        let Ok((_, player_transform, player_sprite, team)) = players.get(aura.0) else {
+            commands.entity(aura_entity).despawn();
+            continue;
+        };
+        has_aura.push(aura.0);
+        transform.translation = player_transform.translation.truncate().extend(-0.5);
+        sprite.custom_size = Some(
+            player_sprite.custom_size.unwrap_or(PLAYER_SIZE)
+                + Vec2::splat(MOMENTUM_AURA_PADDING * 2.0),
+        );
+        sprite.color = Color::srgba(1.0, 0.65, 0.1, pulse);
+        *visibility = if momentum.is_hot(*team) {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+
+    for (entity, transform, _, _) in &players {
+        if !has_aura.contains(&entity) {
+            commands.spawn((
+                Sprite::from_color(Color::NONE, PLAYER_SIZE),
+                Transform::from_translation(transform.translation.truncate().extend(-0.5)),
+                Visibility::Hidden,
+                MomentumAura(entity),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_streak_heats_up_and_breaks() {
+        let mut off = Momentum::default();
+        assert!(off.on_goal(Team::Left).is_none());
+        assert!(off.on_goal(Team::Left).is_none());
+        assert!(off.hot_team().is_none());
+
+        let mut momentum = Momentum::new(true);
+        assert!(momentum.on_goal(Team::Left).is_none());
+        assert!(matches!(
+            momentum.on_goal(Team::Left),
+            Some(GameEvent::Momentum {
+                player: PlayerId::L,
+                streak: 2
+            })
+        ));
+        assert!(momentum.is_hot(Team::Left));
+        assert!(momentum.move_multiplier(Team::Left) > 1.0);
+        assert_eq!(momentum.charge_multiplier(Team::Right), 1.0);
+
+        // Opponent defends harder; the hot team's own AI is unchanged
+        let profile = crate::ai::AiProfileDatabase::default()
+            .default_profile()
+            .clone();
+        let adjusted = momentum.adjust_profile(Team::Right, &profile).unwrap();
+        assert!(adjusted.pressure_distance < profile.pressure_distance);
+        assert!(momentum.adjust_profile(Team::Left, &profile).is_none());
+
+        // Conceding breaks the run
+        assert!(matches!(
+            momentum.on_goal(Team::Right),
+            Some(GameEvent::Momentum {
+                player: PlayerId::L,
+                streak: 0
+            })
+        ));
+        assert!(momentum.hot_team().is_none());
+        assert_eq!(momentum.streak, 1);
+
+        // Running out of time cools off once
+        momentum.on_goal(Team::Right);
+        assert!(momentum.tick(MOMENTUM_DURATION / 2.0).is_none());
+        assert!(momentum.tick(MOMENTUM_DURATION).is_some());
+        assert!(momentum.tick(1.0).is_none());
+        assert_eq!(momentum.move_multiplier(Team::Right), 1.0);
+    }
+}
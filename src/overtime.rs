@@ -0,0 +1,259 @@
+//! Sudden-death overtime - the court shrinks until someone scores
+//!
+//! Started when regulation time runs out with the score tied. Walls creep inward
+//! (baskets and corner ramps ride along), level geometry swallowed by the walls is
+//! hidden and loses its collider, and the nav graph is rebuilt as the court narrows.
+//! The next goal wins; ending overtime puts the court back the way it was.
+
+use bevy::prelude::*;
+
+use crate::ai::NavGraph;
+use crate::constants::*;
+use crate::levels::ArenaDims;
+use crate::scoring::Score;
+use crate::world::{
+    ArenaWall, Basket, BasketRim, CornerRamp, LevelPlatform, Platform, sync_rim_globals,
+};
+
+/// Sudden-death overtime state
+#[derive(Resource, Default)]
+pub struct Overtime {
+    /// Whether overtime is in progress
+    pub active: bool,
+    /// Seconds since overtime started
+    pub elapsed: f32,
+    /// How far each wall has moved inward
+    pub inset: f32,
+    /// Inset when the nav graph was last invalidated
+    pub nav_inset: f32,
+    /// Score when overtime started (any change decides the match)
+    pub start_score: (u32, u32),
+}
+
+impl Overtime {
+    /// Begin overtime from the current (tied) score
+    pub fn start(&mut self, score: &Score) {
+        *self = Self {
+            active: true,
+            start_score: (score.left, score.right),
+            ..default()
+        };
+    }
+
+    /// Stop overtime; `shrink_court` then restores the court on its next run
+    pub fn end(&mut self) {
+        self.active = false;
+        self.elapsed = 0.0;
+    }
+
+    /// True once a goal has been scored since overtime started
+    pub fn is_decided(&self, score: &Score) -> bool {
+        self.active && (score.left, score.right) != self.start_score
+    }
+
+    /// Wall inset after `elapsed` seconds of overtime (capped so baskets stay in play)
+    pub fn inset_at(elapsed: f32) -> f32 {
+        (elapsed * OVERTIME_SHRINK_RATE).min(OVERTIME_MAX_INSET)
+    }
+
+    /// Arena dimensions narrowed by the current inset
+    pub fn narrow(&self, arena: ArenaDims) -> ArenaDims {
+        ArenaDims {
+            width: arena.width - 2.0 * self.inset,
+            ..arena
+        }
+    }
+}
+
+/// Level geometry hidden behind the overtime walls (restored when overtime ends)
+#[derive(Component)]
+pub struct Swallowed {
+    /// Whether the entity was a solid `Platform` before being swallowed
+    pub solid: bool,
+}
+
+/// Move walls inward as overtime goes on, carrying baskets and corner ramps along.
+/// Once overtime has ended, moves everything back and un-hides swallowed geometry.
+/// GlobalTransforms are written directly because ball collisions read them and
+/// headless sims never run transform propagation.
+#[allow(clippy::type_complexity)]
+pub fn shrink_court(
+    mut commands: Commands,
+    time: Res<Time>,
+    score: Res<Score>,
+    mut overtime: ResMut<Overtime>,
+    mut nav_graph: ResMut<NavGraph>,
+    mut walls: Query<(&mut Transform, &mut GlobalTransform, &ArenaWall)>,
+    mut anchored: Query<
+        (&mut Transform, &mut GlobalTransform, Option<&Children>),
+        (Or<(With<Basket>, With<CornerRamp>)>, Without<ArenaWall>),
+    >,
+    mut rims: Query<
+        (&Transform, &mut GlobalTransform),
+        (
+            With<BasketRim>,
+            Without<Basket>,
+            Without<CornerRamp>,
+            Without<ArenaWall>,
+        ),
+    >,
+    level_platforms: Query<
+        (Entity, &Transform, &Sprite, Has<Platform>),
+        (
+            With<LevelPlatform>,
+            Without<Swallowed>,
+            Without<ArenaWall>,
+            Without<Basket>,
+            Without<CornerRamp>,
+        ),
+    >,
+    swallowed: Query<(Entity, &Swallowed)>,
+) {
+    let inset = if overtime.active {
+        if overtime.is_decided(&score) {
+            return;
+        }
+        // Use minimum dt for headless mode compatibility
+        overtime.elapsed += time.delta_secs().max(1.0 / 60.0);
+        Overtime::inset_at(overtime.elapsed)
+    } else {
+        0.0
+    };
+    let delta = inset - overtime.inset;
+    if delta == 0.0 {
+        return;
+    }
+    overtime.inset = inset;
+
+    // Inward is +X on the left side, -X on the right
+    let mut wall_inner = f32::MAX;
+    for (mut transform, mut global, wall) in &mut walls {
+        transform.translation.x += match wall {
+            ArenaWall::Left => delta,
+            ArenaWall::Right => -delta,
+        };
+        *global = GlobalTransform::from(*transform);
+        wall_inner = wall_inner.min(transform.translation.x.abs() - WALL_THICKNESS / 2.0);
+    }
+
+    for (mut transform, mut global, children) in &mut anchored {
+        transform.translation.x += if transform.translation.x < 0.0 {
+            delta
+        } else {
+            -delta
+        };
+        *global = GlobalTransform::from(*transform);
+        sync_rim_globals(&transform, children, &mut rims);
+    }
+
+    if !overtime.active {
+        for (entity, swallowed) in &swallowed {
+            let mut entity = commands.entity(entity);
+            entity.remove::<Swallowed>().insert(Visibility::Inherited);
+            if swallowed.solid {
+                entity.insert(Platform);
+            }
+        }
+        overtime.nav_inset = 0.0;
+        nav_graph.dirty = true;
+        return;
+    }
+
+    // Swallow level geometry once a wall reaches its outer edge
+    let mut swallowed_any = false;
+    for (entity, transform, sprite, solid) in &level_platforms {
+        let half_width = sprite.custom_size.map_or(0.0, |size| size.x / 2.0);
+        if transform.translation.x.abs() + half_width > wall_inner {
+            commands
+                .entity(entity)
+                .insert((Swallowed { solid }, Visibility::Hidden))
+                .remove::<Platform>();
+            swallowed_any = true;
+        }
+    }
+
+    if swallowed_any || overtime.inset - overtime.nav_inset >= OVERTIME_NAV_REBUILD_STEP {
+        overtime.nav_inset = overtime.inset;
+        nav_graph.dirty = true;
+    }
+}
+
+/// Marker for the overtime banner text
+#[derive(Component)]
+pub struct OvertimeBanner;
+
+/// Spawn the (initially hidden) overtime banner near the top of the arena
+pub fn spawn_overtime_banner(commands: &mut Commands) {
+    commands.spawn((
+        Text2d::new("SUDDEN DEATH"),
+        TextFont {
+            font_size: 56.0,
+            ..default()
+        },
+        TextLayout::new_with_justify(bevy::text::Justify::Center),
+        TextColor(Color::srgb(1.0, 0.25, 0.2)),
+        Transform::from_xyz(0.0, ARENA_FLOOR_Y + ARENA_HEIGHT - 120.0, 100.0),
+        Visibility::Hidden,
+        OvertimeBanner,
+    ));
+}
+
+/// Show a pulsing overtime banner while overtime is in progress
+pub fn update_overtime_banner(
+    time: Res<Time>,
+    overtime: Res<Overtime>,
+    mut banners: Query<(&mut Text2d, &mut Visibility, &mut TextColor), With<OvertimeBanner>>,
+) {
+    for (mut text, mut visibility, mut color) in &mut banners {
+        if !overtime.active {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+        *visibility = Visibility::Visible;
+
+        text.0 = if overtime.inset >= OVERTIME_MAX_INSET {
+            "SUDDEN DEATH\nNOWHERE LEFT TO RUN".to_string()
+        } else {
+            "SUDDEN DEATH\nTHE WALLS ARE CLOSING IN".to_string()
+        };
+        // Pulse faster as the court closes
+        let urgency = 1.0 + 3.0 * overtime.inset / OVERTIME_MAX_INSET;
+        let pulse = 0.5 + 0.5 * (time.elapsed_secs() * urgency * std::f32::consts::TAU).sin();
+        *color = TextColor(Color::srgba(
+            1.0,
+            0.25 + 0.35 * pulse,
+            0.2,
+            0.7 + 0.3 * pulse,
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inset_grows_then_caps() {
+        assert_eq!(Overtime::inset_at(0.0), 0.0);
+        assert_eq!(Overtime::inset_at(1.0), OVERTIME_SHRINK_RATE);
+        assert_eq!(Overtime::inset_at(1.0e6), OVERTIME_MAX_INSET);
+    }
+
+    #[test]
+    fn test_any_goal_decides_overtime() {
+        let mut overtime = Overtime::default();
+        let mut score = Score { left: 2, right: 2 };
+        assert!(!overtime.is_decided(&score));
+
+        overtime.start(&score);
+        assert!(!overtime.is_decided(&score));
+        score.right += 1;
+        assert!(overtime.is_decided(&score));
+
+        // Ending keeps the inset so shrink_court can walk the walls back out
+        overtime.inset = 100.0;
+        overtime.end();
+        assert!(!overtime.active);
+        assert_eq!(overtime.inset, 100.0);
+    }
+}
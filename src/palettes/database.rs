@@ -63,6 +63,7 @@ pub const PALETTES_FILE: &str = "config/palettes.txt";
 impl PaletteDatabase {
     /// Load palettes from file, creating default file if it doesn't exist
     pub fn load_or_create(path: &str) -> Self {
+        let path = &crate::config_dir::resolve(path);
         // If file doesn't exist, create it with defaults
         if !Path::new(path).exists() {
             info!("Palettes file not found, creating default: {}", path);
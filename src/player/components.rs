@@ -48,11 +48,34 @@ pub enum Team {
     Right,
 }
 
+impl Team {
+    /// The other side
+    pub fn opponent(self) -> Self {
+        match self {
+            Team::Left => Team::Right,
+            Team::Right => Team::Left,
+        }
+    }
+
+    /// Event id for this side (teammates share it)
+    pub fn player_id(self) -> crate::events::PlayerId {
+        match self {
+            Team::Left => crate::events::PlayerId::L,
+            Team::Right => crate::events::PlayerId::R,
+        }
+    }
+}
+
 /// Marker for the player currently controlled by the human.
-/// Only ONE player has this at a time - AI controls the other.
+/// Only ONE player has this at a time - AI controls the other
+/// (except in local versus, where the second human also carries `SecondHuman`).
 #[derive(Component)]
 pub struct HumanControlled;
 
+/// Marks the human player fed by the second keyboard slot (local versus)
+#[derive(Component)]
+pub struct SecondHuman;
+
 /// Resource tracking which player (if any) is under human control.
 /// None = observer mode (both players AI-controlled).
 /// Used by event bus to route controller input events.
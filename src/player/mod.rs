@@ -2,6 +2,8 @@
 
 mod components;
 mod physics;
+mod teammate;
 
 pub use components::*;
 pub use physics::*;
+pub use teammate::*;
@@ -8,11 +8,14 @@ use crate::ball::{
     Ball, BallPlayerContact, BallPulse, BallRolling, BallShotGrace, BallSpin, BallState, BallStyle,
     BallTextures, CurrentPalette,
 };
+use crate::config::MovementConfig;
 use crate::constants::*;
 use crate::helpers::*;
 use crate::levels::{LevelDatabase, reload_level_geometry};
+use crate::momentum::Momentum;
 use crate::palettes::PaletteDatabase;
 use crate::player::components::*;
+use crate::player::teammate::{Teammate, spawn_for_slot};
 use crate::scoring::CurrentLevel;
 use crate::tuning::PhysicsTweaks;
 use crate::world::{Basket, BasketRim, CornerRamp, LevelPlatform, Platform};
@@ -21,6 +24,7 @@ use crate::world::{Basket, BasketRim, CornerRamp, LevelPlatform, Platform};
 /// All players read from their InputState component (human input is copied there).
 pub fn apply_input(
     tweaks: Res<PhysicsTweaks>,
+    movement: Res<MovementConfig>,
     mut players: Query<
         (
             &mut Velocity,
@@ -29,27 +33,35 @@ pub fn apply_input(
             &mut Facing,
             &Grounded,
             &mut InputState,
+            Option<&Team>,
         ),
         With<Player>,
     >,
     time: Res<Time>,
+    momentum: Option<Res<Momentum>>,
 ) {
     // Use a minimum dt for headless mode where time.delta_secs() returns 0 or tiny values
     // In windowed mode, this will use the actual delta. In headless, it enforces 60Hz behavior.
     let dt = time.delta_secs().max(1.0 / 60.0);
 
-    for (mut velocity, mut coyote, mut jump_state, mut facing, grounded, mut input) in &mut players
+    for (mut velocity, mut coyote, mut jump_state, mut facing, grounded, mut input, team) in
+        &mut players
     {
         let move_x = input.move_x;
         let jump_buffer_timer = input.jump_buffer_timer;
         let jump_held = input.jump_held;
 
         // Acceleration-based horizontal movement
-        let target_speed = move_x * tweaks.move_speed;
+        // Hot-streak teams move a little faster
+        let speed_scale = match (momentum.as_deref(), team) {
+            (Some(momentum), Some(team)) => momentum.move_multiplier(*team),
+            _ => 1.0,
+        };
+        let target_speed = move_x * tweaks.move_speed * speed_scale;
         let current_speed = velocity.0.x;
 
         // Determine if accelerating (toward input) or decelerating (stopping/reversing)
-        let has_input = move_x.abs() > STICK_DEADZONE;
+        let has_input = move_x.abs() > movement.stick_deadzone;
         let same_direction =
             target_speed.signum() == current_speed.signum() || current_speed.abs() < 1.0;
         let is_accelerating = has_input && same_direction;
@@ -72,15 +84,15 @@ pub fn apply_input(
         velocity.0.x = move_toward(current_speed, target_speed, rate * dt);
 
         // Update facing direction based on input (not velocity, so turning feels responsive)
-        if move_x > STICK_DEADZONE {
+        if move_x > movement.stick_deadzone {
             facing.0 = 1.0;
-        } else if move_x < -STICK_DEADZONE {
+        } else if move_x < -movement.stick_deadzone {
             facing.0 = -1.0;
         }
 
         // Update coyote timer
         if grounded.0 {
-            coyote.0 = COYOTE_TIME;
+            coyote.0 = movement.coyote_time;
             jump_state.is_jumping = false; // Reset jump state when grounded
         } else {
             coyote.0 = (coyote.0 - dt).max(0.0);
@@ -101,7 +113,7 @@ pub fn apply_input(
         // Variable jump height: cut velocity if button released while rising
         // Check: in a jump + rising + button NOT held = cut velocity
         if jump_state.is_jumping && velocity.0.y > 0.0 && !jump_held {
-            velocity.0.y *= JUMP_CUT_MULTIPLIER;
+            velocity.0.y *= movement.jump_cut_multiplier;
             jump_state.is_jumping = false; // Only cut once per jump
         }
     }
@@ -129,14 +141,56 @@ pub fn apply_gravity(
     }
 }
 
+/// Which face of a platform a player ran into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContactSide {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+/// Classify an overlap. `diff` is player center minus platform center, `reach` the
+/// summed half sizes, `travel` how far the player moved this frame. The axis that
+/// was still clear before the move is the one crossed, so fast falls onto a corner
+/// land instead of snagging sideways. Ambiguous cases use the smaller overlap.
+pub fn contact_side(diff: Vec2, reach: Vec2, travel: Vec2) -> ContactSide {
+    let overlap = reach - diff.abs();
+    let before = (diff - travel).abs();
+    let vertical = match (before.x >= reach.x, before.y >= reach.y) {
+        (false, true) => true,
+        (true, false) => false,
+        _ => overlap.y < overlap.x,
+    };
+    match (vertical, diff.y > 0.0, diff.x > 0.0) {
+        (true, true, _) => ContactSide::Top,
+        (true, false, _) => ContactSide::Bottom,
+        (false, _, true) => ContactSide::Right,
+        (false, _, false) => ContactSide::Left,
+    }
+}
+
 /// Check player collisions with platforms
 pub fn check_collisions(
+    time: Res<Time>,
     mut player_query: Query<(&mut Transform, &mut Velocity, &mut Grounded, &Sprite), With<Player>>,
     platform_query: Query<
         (&Transform, &Sprite),
         (With<Platform>, Without<Player>, Without<BasketRim>),
     >,
 ) {
+    // Same minimum dt as apply_velocity
+    let dt = time.delta_secs().max(1.0 / 60.0);
+
+    // (center, half size) of every platform
+    let platforms: Vec<(Vec2, Vec2)> = platform_query
+        .iter()
+        .map(|(transform, sprite)| {
+            let size = sprite.custom_size.unwrap_or(Vec2::new(100.0, 20.0));
+            (transform.translation.truncate(), size / 2.0)
+        })
+        .collect();
+
     for (mut player_transform, mut player_velocity, mut grounded, player_sprite) in
         &mut player_query
     {
@@ -146,14 +200,8 @@ pub fn check_collisions(
         // Assume not grounded until we find a floor beneath us
         grounded.0 = false;
 
-        for (platform_transform, platform_sprite) in &platform_query {
-            let platform_size = platform_sprite
-                .custom_size
-                .unwrap_or(Vec2::new(100.0, 20.0));
-            let platform_half = platform_size / 2.0;
-
+        for &(platform_pos, platform_half) in &platforms {
             let player_pos = player_transform.translation.truncate();
-            let platform_pos = platform_transform.translation.truncate();
 
             // Calculate overlap
             let diff = player_pos - platform_pos;
@@ -165,41 +213,97 @@ pub fn check_collisions(
                 continue;
             }
 
-            // Resolve collision along the smallest overlap axis
-            if overlap_y < overlap_x {
-                // Vertical collision
-                if diff.y > 0.0 {
-                    // Player is above - land on platform
+            let reach = player_half + platform_half;
+            let travel = player_velocity.0 * dt;
+            match contact_side(diff, reach, travel) {
+                ContactSide::Top => {
+                    // Land on platform
                     // Position slightly inside (EPSILON) so next frame still detects collision
-                    player_transform.translation.y =
-                        platform_pos.y + platform_half.y + player_half.y - COLLISION_EPSILON;
+                    player_transform.translation.y = platform_pos.y + reach.y - COLLISION_EPSILON;
                     if player_velocity.0.y <= 0.0 {
                         player_velocity.0.y = 0.0;
                         grounded.0 = true;
                     }
-                } else {
-                    // Player hit ceiling
-                    player_transform.translation.y =
-                        platform_pos.y - platform_half.y - player_half.y + COLLISION_EPSILON;
+                }
+                ContactSide::Bottom => {
+                    // Head bonk - the jump ends here instead of sliding up the ceiling
+                    player_transform.translation.y = platform_pos.y - reach.y + COLLISION_EPSILON;
                     if player_velocity.0.y > 0.0 {
                         player_velocity.0.y = 0.0;
                     }
                 }
-            } else {
-                // Horizontal collision - push player out
-                if diff.x > 0.0 {
-                    player_transform.translation.x =
-                        platform_pos.x + platform_half.x + player_half.x - COLLISION_EPSILON;
-                } else {
-                    player_transform.translation.x =
-                        platform_pos.x - platform_half.x - player_half.x + COLLISION_EPSILON;
+                ContactSide::Right => {
+                    // Side push - stop moving into the face, keep vertical speed
+                    // so the player still slides along walls
+                    player_transform.translation.x = platform_pos.x + reach.x - COLLISION_EPSILON;
+                    if player_velocity.0.x < 0.0 {
+                        player_velocity.0.x = 0.0;
+                    }
                 }
-                // Don't zero horizontal velocity - let player slide along walls
+                ContactSide::Left => {
+                    player_transform.translation.x = platform_pos.x - reach.x + COLLISION_EPSILON;
+                    if player_velocity.0.x > 0.0 {
+                        player_velocity.0.x = 0.0;
+                    }
+                }
+            }
+        }
+
+        // One platform at a time can push a player into the next (spawned inside
+        // geometry, squeezed into a tight gap). Push out of anything still embedded.
+        let player_pos = player_transform.translation.truncate();
+        let resolved = depenetrate(player_pos, player_half, &platforms);
+        if resolved != player_pos {
+            let push = resolved - player_pos;
+            if push.x != 0.0 {
+                player_velocity.0.x = 0.0;
             }
+            if push.y != 0.0 {
+                player_velocity.0.y = 0.0;
+            }
+            player_transform.translation.x = resolved.x;
+            player_transform.translation.y = resolved.y;
         }
     }
 }
 
+/// Move a box (center, half size) out of any platform it is embedded in, along
+/// the shortest exit that doesn't land it inside another platform. Contact within
+/// DEPENETRATION_TOLERANCE (normal resting/sliding) is left alone.
+pub fn depenetrate(center: Vec2, half: Vec2, platforms: &[(Vec2, Vec2)]) -> Vec2 {
+    let embedded_in = |pos: Vec2| {
+        platforms
+            .iter()
+            .copied()
+            .find(|(platform_pos, platform_half)| {
+                let overlap = half + *platform_half - (pos - *platform_pos).abs();
+                overlap.x > DEPENETRATION_TOLERANCE && overlap.y > DEPENETRATION_TOLERANCE
+            })
+    };
+
+    let mut pos = center;
+    for _ in 0..DEPENETRATION_MAX_STEPS {
+        let Some((platform_pos, platform_half)) = embedded_in(pos) else {
+            break;
+        };
+        let reach = half + platform_half;
+        let mut exits = [
+            Vec2::new(pos.x, platform_pos.y + reach.y),
+            Vec2::new(pos.x, platform_pos.y - reach.y),
+            Vec2::new(platform_pos.x - reach.x, pos.y),
+            Vec2::new(platform_pos.x + reach.x, pos.y),
+        ];
+        exits.sort_by(|a, b| a.distance_squared(pos).total_cmp(&b.distance_squared(pos)));
+        // Shortest exit into free space, else the shortest one (a later step moves on)
+        pos = exits
+            .iter()
+            .copied()
+            .find(|exit| embedded_in(*exit).is_none())
+            .unwrap_or(exits[0]);
+    }
+    pos
+}
+
 /// Handle double-click Start to reset settings to defaults
 /// Runs before respawn_player to detect double-click
 pub fn check_settings_reset(
@@ -268,6 +372,7 @@ pub fn respawn_player(
             &mut Velocity,
             Option<&HoldingBall>,
             &Team,
+            Has<Teammate>,
         ),
         With<Player>,
     >,
@@ -313,6 +418,7 @@ pub fn respawn_player(
     }
 
     let level_changed = next_level_pressed || prev_level_pressed || level_changed_externally;
+    let ball_spawn = level_db.ball_spawn(&current_level.0);
 
     // Reset: just reset positions and score, keep current palette/level
     if reset_pressed {
@@ -321,12 +427,12 @@ pub fn respawn_player(
         score.right = 0;
 
         // Reset player positions
-        for (player_entity, mut p_transform, mut p_velocity, holding, team) in &mut players {
+        for (player_entity, mut p_transform, mut p_velocity, holding, team, teammate) in
+            &mut players
+        {
             // Use Team component to determine spawn position
-            p_transform.translation = match team {
-                Team::Left => PLAYER_SPAWN_LEFT,
-                Team::Right => PLAYER_SPAWN_RIGHT,
-            };
+            let spawn = level_db.player_spawn(&current_level.0, *team == Team::Left);
+            p_transform.translation = spawn_for_slot(spawn, *team, teammate);
             p_velocity.0 = Vec2::ZERO;
 
             // Drop ball if holding
@@ -345,7 +451,13 @@ pub fn respawn_player(
             .map(|l| l.debug)
             .unwrap_or(false);
 
-        spawn_balls(&mut commands, &ball_textures, current_palette.0, is_debug);
+        spawn_balls(
+            &mut commands,
+            &ball_textures,
+            current_palette.0,
+            is_debug,
+            ball_spawn,
+        );
 
         // Randomize AI profile on reset
         let profiles: Vec<String> = profile_db.profiles().iter().map(|p| p.id.clone()).collect();
@@ -370,11 +482,11 @@ pub fn respawn_player(
             .expect("Palette index out of bounds");
 
         // Reset player positions
-        for (player_entity, mut p_transform, mut p_velocity, holding, team) in &mut players {
-            p_transform.translation = match team {
-                Team::Left => PLAYER_SPAWN_LEFT,
-                Team::Right => PLAYER_SPAWN_RIGHT,
-            };
+        for (player_entity, mut p_transform, mut p_velocity, holding, team, teammate) in
+            &mut players
+        {
+            let spawn = level_db.player_spawn(&current_level.0, *team == Team::Left);
+            p_transform.translation = spawn_for_slot(spawn, *team, teammate);
             p_velocity.0 = Vec2::ZERO;
 
             if holding.is_some() {
@@ -392,7 +504,13 @@ pub fn respawn_player(
             .map(|l| l.debug)
             .unwrap_or(false);
 
-        spawn_balls(&mut commands, &ball_textures, current_palette.0, is_debug);
+        spawn_balls(
+            &mut commands,
+            &ball_textures,
+            current_palette.0,
+            is_debug,
+            ball_spawn,
+        );
 
         // Reload level geometry (platforms + corner ramps)
         if let Some((left_x, right_x, basket_y)) = reload_level_geometry(
@@ -431,13 +549,14 @@ pub fn respawn_player(
     }
 }
 
-/// Helper to spawn a single playable ball at the starting position.
+/// Helper to spawn a single playable ball at the level's ball spawn.
 /// Debug level shelf displays are handled separately in main.rs setup.
 fn spawn_balls(
     commands: &mut Commands,
     ball_textures: &BallTextures,
     palette_index: usize,
     is_debug: bool,
+    spawn: Vec3,
 ) {
     // Pick ball style: random for debug level, default for normal levels
     let style_name = if is_debug {
@@ -449,27 +568,38 @@ fn spawn_balls(
     }
     .unwrap_or_else(|| "wedges".to_string());
 
-    if let Some(textures) = ball_textures.get(&style_name) {
-        if let Some(texture) = textures.textures.get(palette_index) {
-            commands.spawn((
-                Sprite {
-                    image: texture.clone(),
-                    custom_size: Some(BALL_SIZE),
-                    ..default()
-                },
-                Transform::from_translation(BALL_SPAWN),
-                Ball,
-                BallState::default(),
-                Velocity::default(),
-                BallPlayerContact::default(),
-                BallPulse::default(),
-                BallRolling::default(),
-                BallShotGrace::default(),
-                BallSpin::default(),
-                BallStyle::new(&style_name),
-            ));
-        }
-    }
+    spawn_ball(commands, ball_textures, &style_name, palette_index, spawn);
+}
+
+/// Spawn one free playable ball (None if the style/palette texture is missing)
+pub fn spawn_ball(
+    commands: &mut Commands,
+    ball_textures: &BallTextures,
+    style_name: &str,
+    palette_index: usize,
+    translation: Vec3,
+) -> Option<Entity> {
+    let texture = ball_textures.get(style_name)?.textures.get(palette_index)?;
+    let entity = commands
+        .spawn((
+            Sprite {
+                image: texture.clone(),
+                custom_size: Some(BALL_SIZE),
+                ..default()
+            },
+            Transform::from_translation(translation),
+            Ball,
+            BallState::default(),
+            Velocity::default(),
+            BallPlayerContact::default(),
+            BallPulse::default(),
+            BallRolling::default(),
+            BallShotGrace::default(),
+            BallSpin::default(),
+            BallStyle::new(style_name),
+        ))
+        .id();
+    Some(entity)
 }
 
 /// Manage debug level display entities when changing levels.
@@ -577,3 +707,42 @@ pub fn spawn_debug_display(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contact_side_uses_crossed_axis() {
+        let reach = Vec2::new(66.0, 42.0);
+        // Falling fast onto a corner: deeper vertically than horizontally, still a landing
+        let diff = Vec2::new(60.0, 30.0);
+        assert_eq!(
+            contact_side(diff, reach, Vec2::new(0.0, -15.0)),
+            ContactSide::Top
+        );
+        // Jumping up into the underside
+        let diff = Vec2::new(10.0, -38.0);
+        assert_eq!(
+            contact_side(diff, reach, Vec2::new(0.0, 8.0)),
+            ContactSide::Bottom
+        );
+        // Running into a ledge at head height
+        let diff = Vec2::new(-62.0, -30.0);
+        assert_eq!(
+            contact_side(diff, reach, Vec2::new(6.0, 0.0)),
+            ContactSide::Left
+        );
+        // Resting contact falls back to the smaller overlap
+        let diff = Vec2::new(0.0, reach.y - COLLISION_EPSILON);
+        assert_eq!(contact_side(diff, reach, Vec2::ZERO), ContactSide::Top);
+
+        // Embedded in a low platform with the floor underneath: out the top
+        let platforms = [
+            (Vec2::new(0.0, -450.0), Vec2::new(780.0, 20.0)),
+            (Vec2::new(0.0, -370.0), Vec2::new(100.0, 10.0)),
+        ];
+        let out = depenetrate(Vec2::new(0.0, -380.0), PLAYER_SIZE / 2.0, &platforms);
+        assert_eq!(out, Vec2::new(0.0, -328.0));
+    }
+}
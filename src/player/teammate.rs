@@ -0,0 +1,132 @@
+//! 2v2 teammates - a second, AI-controlled player on each side
+//!
+//! `--2v2` (game and training) spawns a `Teammate` on each side next to the
+//! regular player. Teammates are always AI. Systems that treat a side as one
+//! player (control swap, tick events, replays) skip them, so logs and
+//! analytics keep following the regular player of each side.
+
+use bevy::prelude::*;
+
+use super::components::*;
+use crate::ai::{AiGoal, AiNavState, AiState, InputState};
+use crate::constants::*;
+use crate::shooting::ChargingShot;
+use crate::steal::StealCooldown;
+use crate::ui::{ChargeGaugeBackground, ChargeGaugeFill, spawn_charge_feedback_markers};
+use crate::world::{Basket, Collider};
+
+/// Second player on a side (2v2 only, always AI-controlled)
+#[derive(Component)]
+pub struct Teammate;
+
+/// Players per side: 1, or 2 with `--2v2`
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TeamSize(pub u8);
+
+impl Default for TeamSize {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
+impl TeamSize {
+    pub fn from_args(args: &[String]) -> Self {
+        if args.iter().any(|a| a == "--2v2") {
+            Self(2)
+        } else {
+            Self::default()
+        }
+    }
+
+    pub fn has_teammates(self) -> bool {
+        self.0 > 1
+    }
+}
+
+/// Spawn point for a player: teammates start `TEAMMATE_SPAWN_OFFSET` behind
+/// the side's spawn, toward the basket they defend
+pub fn spawn_for_slot(spawn: Vec3, team: Team, teammate: bool) -> Vec3 {
+    if !teammate {
+        return spawn;
+    }
+    let back = match team {
+        Team::Left => -TEAMMATE_SPAWN_OFFSET,
+        Team::Right => TEAMMATE_SPAWN_OFFSET,
+    };
+    spawn + Vec3::new(back, 0.0, 0.0)
+}
+
+/// Spawn an AI teammate for `team` (with its charge gauge) at the side's `spawn`
+pub fn spawn_teammate(
+    commands: &mut Commands,
+    team: Team,
+    spawn: Vec3,
+    color: Color,
+    profile_id: String,
+    goal: AiGoal,
+) -> Entity {
+    let (facing, target) = match team {
+        Team::Left => (1.0, Basket::Right),
+        Team::Right => (-1.0, Basket::Left),
+    };
+    let teammate = commands
+        .spawn((
+            Sprite::from_color(color, PLAYER_SIZE),
+            Transform::from_translation(spawn_for_slot(spawn, team, true)),
+            (
+                Player,
+                Velocity::default(),
+                Grounded(false),
+                CoyoteTimer::default(),
+            ),
+            (
+                JumpState::default(),
+                Facing(facing),
+                ChargingShot::default(),
+            ),
+            TargetBasket(target),
+            Collider,
+            team,
+            Teammate,
+            (
+                InputState::default(),
+                AiState {
+                    current_goal: goal,
+                    profile_id,
+                    ..default()
+                },
+                AiNavState::default(),
+                StealCooldown::default(),
+            ),
+        ))
+        .id();
+
+    // Gauge sits opposite the ball, which starts on the facing side
+    let gauge_x = -facing * PLAYER_SIZE.x / 4.0;
+    let gauge_bg = commands
+        .spawn((
+            Sprite::from_color(
+                Color::BLACK,
+                Vec2::new(CHARGE_GAUGE_WIDTH, CHARGE_GAUGE_HEIGHT),
+            ),
+            Transform::from_xyz(gauge_x, 0.0, 0.5),
+            ChargeGaugeBackground,
+        ))
+        .id();
+    let gauge_fill = commands
+        .spawn((
+            Sprite::from_color(
+                Color::srgb(0.0, 0.8, 0.0),
+                Vec2::new(CHARGE_GAUGE_WIDTH - 2.0, CHARGE_GAUGE_HEIGHT - 2.0),
+            ),
+            Transform::from_xyz(gauge_x, 0.0, 0.6).with_scale(Vec3::new(1.0, 0.0, 1.0)),
+            ChargeGaugeFill,
+        ))
+        .id();
+    commands
+        .entity(teammate)
+        .add_children(&[gauge_bg, gauge_fill]);
+    spawn_charge_feedback_markers(commands, teammate, gauge_x);
+
+    teammate
+}
@@ -1,7 +1,9 @@
 //! Preset application system - copies preset values into PhysicsTweaks
+//! (and the steal preset into StealConfig)
 
 use bevy::prelude::*;
 
+use crate::config::StealConfig;
 use crate::presets::PresetDatabase;
 use crate::tuning::PhysicsTweaks;
 
@@ -11,6 +13,7 @@ pub struct CurrentPresets {
     pub movement: usize,
     pub ball: usize,
     pub shooting: usize,
+    pub steal: usize,
     pub composite: usize,
     /// Set to true to trigger a preset apply
     pub apply_pending: bool,
@@ -28,6 +31,7 @@ pub fn apply_preset_to_tweaks(
     preset_db: Res<PresetDatabase>,
     mut current: ResMut<CurrentPresets>,
     mut tweaks: ResMut<PhysicsTweaks>,
+    mut steal_config: ResMut<StealConfig>,
 ) {
     if !current.apply_pending {
         return;
@@ -71,8 +75,21 @@ pub fn apply_preset_to_tweaks(
         tweaks.shot_distance_variance = shooting.shot_distance_variance;
     }
 
+    // Apply steal preset
+    if let Some(steal) = preset_db.get_steal(current.steal) {
+        steal_config.range = steal.range;
+        steal_config.near_miss_range = steal.near_miss_range;
+        steal_config.success_chance = steal.success_chance;
+        steal_config.charging_bonus = steal.charging_bonus;
+        steal_config.pushback_strength = steal.pushback_strength;
+        steal_config.cooldown = steal.cooldown;
+        steal_config.fail_cooldown = steal.fail_cooldown;
+        steal_config.out_of_range_cooldown = steal.out_of_range_cooldown;
+        steal_config.victim_cooldown = steal.victim_cooldown;
+    }
+
     info!(
-        "Applied presets: movement={}, ball={}, shooting={}",
+        "Applied presets: movement={}, ball={}, shooting={}, steal={}",
         preset_db
             .get_movement(current.movement)
             .map(|p| p.name.as_str())
@@ -85,6 +102,10 @@ pub fn apply_preset_to_tweaks(
             .get_shooting(current.shooting)
             .map(|p| p.name.as_str())
             .unwrap_or("?"),
+        preset_db
+            .get_steal(current.steal)
+            .map(|p| p.name.as_str())
+            .unwrap_or("?"),
     );
 }
 
@@ -114,6 +135,15 @@ pub fn apply_composite_preset(
                 break;
             }
         }
+        // Composites without a steal preset keep the current one
+        if let Some(steal) = &composite.steal {
+            for (i, s) in preset_db.steal.iter().enumerate() {
+                if s.name == *steal {
+                    current.steal = i;
+                    break;
+                }
+            }
+        }
         current.composite = composite_index;
         current.mark_apply();
     }
@@ -5,7 +5,9 @@ use std::collections::HashMap;
 use std::fs;
 
 use crate::constants::*;
-use crate::presets::types::{BallPreset, CompositePreset, MovementPreset, ShootingPreset};
+use crate::presets::types::{
+    BallPreset, CompositePreset, MovementPreset, ShootingPreset, StealPreset,
+};
 
 /// Path to game presets file
 pub const PRESETS_FILE: &str = "config/game_presets.txt";
@@ -16,12 +18,14 @@ pub struct PresetDatabase {
     pub movement: Vec<MovementPreset>,
     pub ball: Vec<BallPreset>,
     pub shooting: Vec<ShootingPreset>,
+    pub steal: Vec<StealPreset>,
     pub composite: Vec<CompositePreset>,
 }
 
 impl PresetDatabase {
     /// Load presets from file, or return default presets if file doesn't exist
     pub fn load_from_file(path: &str) -> Self {
+        let path = &crate::config_dir::resolve(path);
         let content = match fs::read_to_string(path) {
             Ok(c) => c,
             Err(e) => {
@@ -74,6 +78,11 @@ impl PresetDatabase {
                             db.shooting.push(preset);
                         }
                     }
+                    "Steal" => {
+                        if let Some(preset) = Self::parse_steal(&name, &values) {
+                            db.steal.push(preset);
+                        }
+                    }
                     "Composite" => {
                         if let Some(preset) = Self::parse_composite(&name, &values) {
                             db.composite.push(preset);
@@ -94,15 +103,19 @@ impl PresetDatabase {
         if db.shooting.is_empty() {
             db.shooting.push(Self::default_shooting());
         }
+        if db.steal.is_empty() {
+            db.steal.push(Self::default_steal());
+        }
         if db.composite.is_empty() {
             db.composite.push(Self::default_composite());
         }
 
         info!(
-            "Loaded presets: {} movement, {} ball, {} shooting, {} composite",
+            "Loaded presets: {} movement, {} ball, {} shooting, {} steal, {} composite",
             db.movement.len(),
             db.ball.len(),
             db.shooting.len(),
+            db.steal.len(),
             db.composite.len()
         );
 
@@ -208,6 +221,27 @@ impl PresetDatabase {
         })
     }
 
+    fn parse_steal(name: &str, values: &HashMap<String, String>) -> Option<StealPreset> {
+        let or = |key: &str, default: f32| {
+            values
+                .get(key)
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default)
+        };
+        Some(StealPreset {
+            name: name.to_string(),
+            range: values.get("range")?.parse().ok()?,
+            near_miss_range: or("near_miss_range", STEAL_NEAR_MISS_RANGE),
+            success_chance: values.get("success_chance")?.parse().ok()?,
+            charging_bonus: or("charging_bonus", STEAL_CHARGING_BONUS),
+            pushback_strength: or("pushback_strength", STEAL_PUSHBACK_STRENGTH),
+            cooldown: values.get("cooldown")?.parse().ok()?,
+            fail_cooldown: or("fail_cooldown", STEAL_FAIL_COOLDOWN),
+            out_of_range_cooldown: or("out_of_range_cooldown", STEAL_OUT_OF_RANGE_COOLDOWN),
+            victim_cooldown: or("victim_cooldown", STEAL_VICTIM_COOLDOWN),
+        })
+    }
+
     fn parse_composite(name: &str, values: &HashMap<String, String>) -> Option<CompositePreset> {
         Some(CompositePreset {
             name: name.to_string(),
@@ -217,6 +251,7 @@ impl PresetDatabase {
             movement: values.get("movement")?.clone(),
             ball: values.get("ball")?.clone(),
             shooting: values.get("shooting")?.clone(),
+            steal: values.get("steal").cloned(),
         })
     }
 
@@ -226,6 +261,7 @@ impl PresetDatabase {
             movement: vec![Self::default_movement()],
             ball: vec![Self::default_ball()],
             shooting: vec![Self::default_shooting()],
+            steal: vec![Self::default_steal()],
             composite: vec![Self::default_composite()],
         }
     }
@@ -273,6 +309,21 @@ impl PresetDatabase {
         }
     }
 
+    fn default_steal() -> StealPreset {
+        StealPreset {
+            name: "Default".to_string(),
+            range: STEAL_RANGE,
+            near_miss_range: STEAL_NEAR_MISS_RANGE,
+            success_chance: STEAL_SUCCESS_CHANCE,
+            charging_bonus: STEAL_CHARGING_BONUS,
+            pushback_strength: STEAL_PUSHBACK_STRENGTH,
+            cooldown: STEAL_COOLDOWN,
+            fail_cooldown: STEAL_FAIL_COOLDOWN,
+            out_of_range_cooldown: STEAL_OUT_OF_RANGE_COOLDOWN,
+            victim_cooldown: STEAL_VICTIM_COOLDOWN,
+        }
+    }
+
     fn default_composite() -> CompositePreset {
         CompositePreset {
             name: "Default".to_string(),
@@ -282,6 +333,7 @@ impl PresetDatabase {
             movement: "Default".to_string(),
             ball: "Default".to_string(),
             shooting: "Default".to_string(),
+            steal: None,
         }
     }
 
@@ -300,6 +352,11 @@ impl PresetDatabase {
         self.shooting.get(index)
     }
 
+    /// Get steal preset by index
+    pub fn get_steal(&self, index: usize) -> Option<&StealPreset> {
+        self.steal.get(index)
+    }
+
     /// Get composite preset by index
     pub fn get_composite(&self, index: usize) -> Option<&CompositePreset> {
         self.composite.get(index)
@@ -320,6 +377,11 @@ impl PresetDatabase {
         self.shooting.iter().find(|p| p.name == name)
     }
 
+    /// Get steal preset by name
+    pub fn get_steal_by_name(&self, name: &str) -> Option<&StealPreset> {
+        self.steal.iter().find(|p| p.name == name)
+    }
+
     /// Number of movement presets
     pub fn movement_len(&self) -> usize {
         self.movement.len()
@@ -335,8 +397,45 @@ impl PresetDatabase {
         self.shooting.len()
     }
 
+    /// Number of steal presets
+    pub fn steal_len(&self) -> usize {
+        self.steal.len()
+    }
+
     /// Number of composite presets
     pub fn composite_len(&self) -> usize {
         self.composite.len()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steal_presets_parse_with_defaults() {
+        let db = PresetDatabase::parse(
+            "[Steal]\n\
+             Grabby: range=80, success_chance=0.35, cooldown=0.2, victim_cooldown=0.6\n\
+             Broken: range=80\n\
+             [Composite]\n\
+             Chaos: movement=Default, ball=Default, shooting=Default, steal=Grabby\n\
+             Plain: movement=Default, ball=Default, shooting=Default\n",
+        );
+        // Entries missing a required field are skipped
+        assert_eq!(db.steal_len(), 1);
+        let grabby = db.get_steal_by_name("Grabby").unwrap();
+        assert_eq!(grabby.range, 80.0);
+        assert_eq!(grabby.victim_cooldown, 0.6);
+        assert_eq!(grabby.fail_cooldown, STEAL_FAIL_COOLDOWN);
+        assert_eq!(
+            db.get_composite(0).unwrap().steal.as_deref(),
+            Some("Grabby")
+        );
+        assert_eq!(db.get_composite(1).unwrap().steal, None);
+
+        // A file without a [Steal] section still gets the constants as Default
+        let db = PresetDatabase::parse("[Movement]\n");
+        assert_eq!(db.get_steal(0).unwrap().range, STEAL_RANGE);
+    }
+}
@@ -1,6 +1,6 @@
 //! Presets module - game tuning preset system
 //!
-//! Provides hierarchical presets for movement, ball physics, and shooting.
+//! Provides hierarchical presets for movement, ball physics, shooting and steals.
 //! Presets can be loaded from config/game_presets.txt and hot-reloaded.
 
 mod apply;
@@ -9,4 +9,4 @@ mod types;
 
 pub use apply::{CurrentPresets, apply_composite_preset, apply_preset_to_tweaks};
 pub use database::{PRESETS_FILE, PresetDatabase};
-pub use types::{BallPreset, CompositePreset, MovementPreset, ShootingPreset};
+pub use types::{BallPreset, CompositePreset, MovementPreset, ShootingPreset, StealPreset};
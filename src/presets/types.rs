@@ -43,6 +43,23 @@ pub struct ShootingPreset {
     pub shot_distance_variance: f32,
 }
 
+/// Steal preset - steal range, cooldowns, success model and vulnerability windows
+#[derive(Debug, Clone)]
+pub struct StealPreset {
+    pub name: String,
+    pub range: f32,
+    pub near_miss_range: f32,
+    // Success model
+    pub success_chance: f32,
+    pub charging_bonus: f32,
+    pub pushback_strength: f32,
+    // Cooldowns / vulnerability windows
+    pub cooldown: f32,
+    pub fail_cooldown: f32,
+    pub out_of_range_cooldown: f32,
+    pub victim_cooldown: f32,
+}
+
 /// Global preset - combines all settings into one preset
 #[derive(Debug, Clone)]
 pub struct CompositePreset {
@@ -53,4 +70,5 @@ pub struct CompositePreset {
     pub movement: String,           // Name of MovementPreset
     pub ball: String,               // Name of BallPreset
     pub shooting: String,           // Name of ShootingPreset
+    pub steal: Option<String>,      // Name of StealPreset (None = keep current)
 }
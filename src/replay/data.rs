@@ -1,9 +1,19 @@
 //! Replay data structures shared by loaders and replay systems.
+//!
+//! Playback positions come straight from the tick frames around the current
+//! time, but the running state derived from events (score, each AI's goal)
+//! would need a scan from tick 0 on every seek. Loaders build a
+//! `ReplayKeyframe` every `KEYFRAME_INTERVAL_TICKS`, so lookups start at the
+//! nearest keyframe instead - which keeps seeks, reverse playback and
+//! stepping backwards as cheap as playing forward.
 
 use bevy::prelude::*;
 
 use super::MatchInfo;
-use crate::events::{GameEvent, PlayerId};
+use super::stream::TickStream;
+use crate::ball::{Ball, BallState, Velocity};
+use crate::events::{EventStr, GameEvent, PlayerId, TimeBase};
+use crate::player::{Player, Team, Teammate};
 
 /// A single tick frame with positions and velocities for interpolation.
 #[derive(Debug, Clone)]
@@ -28,6 +38,124 @@ pub struct TickFrame {
     pub ball_state: char,
 }
 
+impl TickFrame {
+    /// Snapshot the live players and ball (time and frame are left at 0 for the caller).
+    pub fn capture(
+        players: &Query<(&Transform, &Velocity, &Team), (With<Player>, Without<Teammate>)>,
+        balls: &Query<(&Transform, &Velocity, &BallState), With<Ball>>,
+    ) -> Option<Self> {
+        let (ball_transform, ball_velocity, ball_state) = balls.iter().next()?;
+        let mut frame = Self {
+            time_ms: 0,
+            frame: 0,
+            left_pos: Vec2::ZERO,
+            left_vel: Vec2::ZERO,
+            right_pos: Vec2::ZERO,
+            right_vel: Vec2::ZERO,
+            ball_pos: ball_transform.translation.truncate(),
+            ball_vel: ball_velocity.0,
+            ball_state: match ball_state {
+                BallState::Free => 'F',
+                BallState::Held(_) => 'H',
+                BallState::InFlight { .. } | BallState::Passed { .. } => 'I',
+            },
+        };
+        for (transform, velocity, team) in players {
+            let pos = transform.translation.truncate();
+            match team {
+                Team::Left => (frame.left_pos, frame.left_vel) = (pos, velocity.0),
+                Team::Right => (frame.right_pos, frame.right_vel) = (pos, velocity.0),
+            }
+        }
+        Some(frame)
+    }
+
+    /// As a `Tick` event (for writing to a log or clip file).
+    pub fn to_event(&self) -> GameEvent {
+        GameEvent::Tick {
+            frame: self.frame,
+            left_pos: self.left_pos.into(),
+            left_vel: self.left_vel.into(),
+            right_pos: self.right_pos.into(),
+            right_vel: self.right_vel.into(),
+            ball_pos: self.ball_pos.into(),
+            ball_vel: self.ball_vel.into(),
+            ball_state: self.ball_state,
+        }
+    }
+
+    /// From a `Tick` event (None for any other event).
+    pub fn from_event(time_ms: u32, event: &GameEvent) -> Option<Self> {
+        let GameEvent::Tick {
+            frame,
+            left_pos,
+            left_vel,
+            right_pos,
+            right_vel,
+            ball_pos,
+            ball_vel,
+            ball_state,
+        } = event
+        else {
+            return None;
+        };
+        Some(Self {
+            time_ms,
+            frame: *frame,
+            left_pos: Vec2::from(*left_pos),
+            left_vel: Vec2::from(*left_vel),
+            right_pos: Vec2::from(*right_pos),
+            right_vel: Vec2::from(*right_vel),
+            ball_pos: Vec2::from(*ball_pos),
+            ball_vel: Vec2::from(*ball_vel),
+            ball_state: *ball_state,
+        })
+    }
+}
+
+/// Ticks between keyframes (5 seconds at 20 Hz)
+pub const KEYFRAME_INTERVAL_TICKS: u32 = 100;
+
+/// Timeline distance between keyframes
+pub const KEYFRAME_INTERVAL_MS: u32 = KEYFRAME_INTERVAL_TICKS * 50;
+
+/// Playback state at one point of the timeline, derived from everything before it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReplayKeyframe {
+    pub time_ms: u32,
+    /// Last tick at or before `time_ms` (0 for streamed replays, which keep no ticks)
+    pub tick_index: usize,
+    /// Events at or before `time_ms` (the next one to apply)
+    pub event_index: usize,
+    /// Score (left, right)
+    pub score: (u32, u32),
+    /// Latest AI goal per side [left, right]
+    pub ai_goals: [Option<EventStr>; 2],
+}
+
+fn side_index(player: PlayerId) -> usize {
+    match player {
+        PlayerId::L => 0,
+        PlayerId::R => 1,
+    }
+}
+
+/// How far back "previous event" looks past events just played
+const KEY_EVENT_REWIND_GRACE_MS: u32 = 500;
+
+/// Events the replay viewer's next/previous-event keys stop at (AI goal and
+/// input noise is skipped).
+pub fn is_key_event(event: &GameEvent) -> bool {
+    matches!(
+        event,
+        GameEvent::Goal { .. }
+            | GameEvent::Pickup { .. }
+            | GameEvent::ContestedPickup { .. }
+            | GameEvent::ShotRelease { .. }
+            | GameEvent::StealSuccess { .. }
+    )
+}
+
 /// A timed game event (non-tick events like goals, pickups, AI goals).
 #[derive(Debug, Clone)]
 pub struct TimedEvent {
@@ -45,14 +173,115 @@ pub struct ReplayData {
     /// Match information (level, profiles, seed).
     pub match_info: MatchInfo,
     /// Tick frames for position interpolation (sampled at 50ms / 20 Hz).
+    /// Empty when `stream` is set.
     pub ticks: Vec<TickFrame>,
     /// Game events (goals, pickups, AI goals, steals, etc.).
     pub events: Vec<TimedEvent>,
     /// Total duration in milliseconds.
     pub duration_ms: u32,
+    /// Ticks loaded from SQLite on demand (long matches only).
+    pub stream: Option<TickStream>,
+    /// Snapshots every KEYFRAME_INTERVAL_TICKS (empty until `build_keyframes`).
+    pub keyframes: Vec<ReplayKeyframe>,
+    /// Clock the timestamps came from (legacy matches may be wall-clock).
+    pub time_base: TimeBase,
 }
 
 impl ReplayData {
+    /// Build keyframes from the loaded ticks and events (loaders call this
+    /// once `duration_ms` is known).
+    pub fn build_keyframes(&mut self) {
+        self.keyframes.clear();
+        let mut event_index = 0;
+        let mut score = (0, 0);
+        let mut ai_goals: [Option<EventStr>; 2] = [None, None];
+        let mut time_ms = 0;
+        loop {
+            while let Some(timed) = self.events.get(event_index)
+                && timed.time_ms <= time_ms
+            {
+                match &timed.event {
+                    GameEvent::Goal {
+                        score_left,
+                        score_right,
+                        ..
+                    } => score = (*score_left, *score_right),
+                    GameEvent::ResetScores => score = (0, 0),
+                    GameEvent::AiGoal { player, goal } => {
+                        ai_goals[side_index(*player)] = Some(goal.clone());
+                    }
+                    _ => {}
+                }
+                event_index += 1;
+            }
+            self.keyframes.push(ReplayKeyframe {
+                time_ms,
+                tick_index: self
+                    .ticks
+                    .partition_point(|t| t.time_ms <= time_ms)
+                    .saturating_sub(1),
+                event_index,
+                score,
+                ai_goals: ai_goals.clone(),
+            });
+            if time_ms >= self.duration_ms {
+                break;
+            }
+            // The last keyframe sits on the end of the replay
+            time_ms = (time_ms + KEYFRAME_INTERVAL_MS).min(self.duration_ms);
+        }
+    }
+
+    /// Latest keyframe at or before `time_ms`.
+    pub fn keyframe_at(&self, time_ms: u32) -> Option<&ReplayKeyframe> {
+        let idx = self.keyframes.partition_point(|k| k.time_ms <= time_ms);
+        idx.checked_sub(1).map(|i| &self.keyframes[i])
+    }
+
+    /// Events after the keyframe at `time_ms`, up to and including `time_ms`.
+    fn events_since_keyframe(&self, time_ms: u32) -> &[TimedEvent] {
+        let start = self.keyframe_at(time_ms).map_or(0, |k| k.event_index);
+        let end = start + self.events[start..].partition_point(|e| e.time_ms <= time_ms);
+        &self.events[start..end]
+    }
+
+    /// Time of the first keyframe after `time_ms` (seek forward).
+    pub fn next_keyframe_ms(&self, time_ms: u32) -> Option<u32> {
+        self.keyframes
+            .iter()
+            .map(|k| k.time_ms)
+            .find(|&t| t > time_ms)
+    }
+
+    /// Time of the last keyframe before `time_ms`, skipping the preceding
+    /// `KEY_EVENT_REWIND_GRACE_MS` like `prev_key_event_ms`.
+    pub fn prev_keyframe_ms(&self, time_ms: u32) -> Option<u32> {
+        let before = time_ms.saturating_sub(KEY_EVENT_REWIND_GRACE_MS);
+        self.keyframes
+            .iter()
+            .rev()
+            .map(|k| k.time_ms)
+            .find(|&t| t < before)
+    }
+
+    /// Score (left, right) at a given time.
+    pub fn score_at(&self, time_ms: u32) -> (u32, u32) {
+        let base = self.keyframe_at(time_ms).map_or((0, 0), |k| k.score);
+        self.events_since_keyframe(time_ms)
+            .iter()
+            .rev()
+            .find_map(|e| match e.event {
+                GameEvent::Goal {
+                    score_left,
+                    score_right,
+                    ..
+                } => Some((score_left, score_right)),
+                GameEvent::ResetScores => Some((0, 0)),
+                _ => None,
+            })
+            .unwrap_or(base)
+    }
+
     /// Get tick frames within a time range (for efficient lookup).
     pub fn ticks_in_range(&self, start_ms: u32, end_ms: u32) -> impl Iterator<Item = &TickFrame> {
         self.ticks
@@ -60,28 +289,40 @@ impl ReplayData {
             .filter(move |t| t.time_ms >= start_ms && t.time_ms <= end_ms)
     }
 
+    /// First tick frame (initial positions).
+    pub fn first_tick(&self) -> Option<TickFrame> {
+        match &self.stream {
+            Some(stream) => stream.first(),
+            None => self.ticks.first().cloned(),
+        }
+    }
+
     /// Find the two tick frames that bracket a given time for interpolation.
-    pub fn find_bracket(&self, time_ms: u32) -> Option<(&TickFrame, &TickFrame, f32)> {
+    pub fn find_bracket(&self, time_ms: u32) -> Option<(TickFrame, TickFrame, f32)> {
+        if let Some(stream) = &self.stream {
+            return stream.find_bracket(time_ms);
+        }
         if self.ticks.is_empty() {
             return None;
         }
 
-        // Binary search for the insertion point.
-        let idx = self.ticks.partition_point(|t| t.time_ms <= time_ms);
+        // Binary search for the insertion point, from the nearest keyframe.
+        let start = self.keyframe_at(time_ms).map_or(0, |k| k.tick_index);
+        let idx = start + self.ticks[start..].partition_point(|t| t.time_ms <= time_ms);
 
         if idx == 0 {
             // Before first tick.
             let first = &self.ticks[0];
-            return Some((first, first, 0.0));
+            return Some((first.clone(), first.clone(), 0.0));
         }
         if idx >= self.ticks.len() {
             // After last tick.
             let last = self.ticks.last().unwrap();
-            return Some((last, last, 1.0));
+            return Some((last.clone(), last.clone(), 1.0));
         }
 
-        let prev = &self.ticks[idx - 1];
-        let next = &self.ticks[idx];
+        let prev = self.ticks[idx - 1].clone();
+        let next = self.ticks[idx].clone();
 
         let t = if next.time_ms > prev.time_ms {
             (time_ms - prev.time_ms) as f32 / (next.time_ms - prev.time_ms) as f32
@@ -97,19 +338,113 @@ impl ReplayData {
         self.events.iter().filter(move |e| e.time_ms <= time_ms)
     }
 
+    /// Time of the first key event (see `is_key_event`) after `time_ms`.
+    pub fn next_key_event_ms(&self, time_ms: u32) -> Option<u32> {
+        self.events
+            .iter()
+            .filter(|e| is_key_event(&e.event))
+            .map(|e| e.time_ms)
+            .find(|&t| t > time_ms)
+    }
+
+    /// Time of the last key event before `time_ms`. Events in the preceding
+    /// `KEY_EVENT_REWIND_GRACE_MS` are skipped so repeated presses keep going back
+    /// while playback runs.
+    pub fn prev_key_event_ms(&self, time_ms: u32) -> Option<u32> {
+        let before = time_ms.saturating_sub(KEY_EVENT_REWIND_GRACE_MS);
+        self.events
+            .iter()
+            .rev()
+            .filter(|e| is_key_event(&e.event))
+            .map(|e| e.time_ms)
+            .find(|&t| t < before)
+    }
+
     /// Get the most recent AI goal for a player at a given time.
     pub fn current_ai_goal(&self, time_ms: u32, player: PlayerId) -> Option<&str> {
-        self.events
+        self.events_since_keyframe(time_ms)
             .iter()
-            .filter(|e| e.time_ms <= time_ms)
             .rev()
             .find_map(|e| {
                 if let GameEvent::AiGoal { player: p, goal } = &e.event {
                     if *p == player {
-                        return Some(goal.as_str());
+                        return Some(&**goal);
                     }
                 }
                 None
             })
+            .or_else(|| {
+                self.keyframe_at(time_ms)
+                    .and_then(|k| k.ai_goals[side_index(player)].as_deref())
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::intern;
+
+    #[test]
+    fn test_keyframes_match_a_scan_from_the_start() {
+        let timed = |time_ms: u32, event: GameEvent| TimedEvent { time_ms, event };
+        let goal = |player: PlayerId, goal: &str| GameEvent::AiGoal {
+            player,
+            goal: intern(goal),
+        };
+        let score = |score_left, score_right| GameEvent::Goal {
+            player: PlayerId::L,
+            score_left,
+            score_right,
+        };
+        let mut replay = ReplayData {
+            events: vec![
+                timed(0, goal(PlayerId::L, "ChaseBall")),
+                timed(3000, score(1, 0)),
+                timed(7000, goal(PlayerId::R, "AttemptSteal")),
+                timed(12_000, score(1, 1)),
+                timed(12_500, goal(PlayerId::L, "AttackWithBall")),
+            ],
+            duration_ms: 14_000,
+            ..Default::default()
+        };
+        // Without keyframes every lookup scans from the first event
+        let scanned: Vec<_> = [0, 2999, 3000, 9000, 12_400, 14_000]
+            .map(|t| {
+                (
+                    replay.score_at(t),
+                    replay.current_ai_goal(t, PlayerId::L).map(str::to_string),
+                    replay.current_ai_goal(t, PlayerId::R).map(str::to_string),
+                )
+            })
+            .to_vec();
+
+        replay.build_keyframes();
+        let times: Vec<u32> = replay.keyframes.iter().map(|k| k.time_ms).collect();
+        assert_eq!(times, vec![0, 5000, 10_000, 14_000]);
+        assert_eq!(replay.keyframes[2].score, (1, 0));
+        assert_eq!(replay.keyframes[2].event_index, 3);
+        assert_eq!(
+            replay.keyframes[2].ai_goals[1].as_deref(),
+            Some("AttemptSteal")
+        );
+
+        let keyed: Vec<_> = [0, 2999, 3000, 9000, 12_400, 14_000]
+            .map(|t| {
+                (
+                    replay.score_at(t),
+                    replay.current_ai_goal(t, PlayerId::L).map(str::to_string),
+                    replay.current_ai_goal(t, PlayerId::R).map(str::to_string),
+                )
+            })
+            .to_vec();
+        assert_eq!(keyed, scanned);
+        assert_eq!(keyed[4].0, (1, 1));
+        assert_eq!(keyed[4].1.as_deref(), Some("ChaseBall"));
+
+        // Seeking by keyframe, with the rewind grace on the way back
+        assert_eq!(replay.next_keyframe_ms(5000), Some(10_000));
+        assert_eq!(replay.prev_keyframe_ms(10_200), Some(5000));
+        assert_eq!(replay.prev_keyframe_ms(10_800), Some(10_000));
     }
 }
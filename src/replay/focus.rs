@@ -0,0 +1,276 @@
+//! Replay focus - audit one player in a crowded replay
+//!
+//! With a focused player the other player is dimmed, the focused player's recent
+//! path is drawn as a trail, and only their AI goal label and timeline markers
+//! are shown. Timeline markers can also be filtered to a single event type.
+//! P cycles the focused player, M cycles the marker filter.
+
+use bevy::prelude::*;
+
+use crate::constants::{ARENA_HEIGHT, ARENA_WIDTH, TEXT_ACCENT};
+use crate::events::PlayerId;
+use crate::player::{Player, Team};
+
+use super::ReplayData;
+use super::state::ReplayState;
+use super::ui::{EventMarkerType, PlayerGoalLabel, ReplayEventMarker};
+
+/// Trail length in milliseconds of replay time
+const TRAIL_MS: u32 = 3000;
+/// Dots in the trail (one per TRAIL_MS / TRAIL_DOTS)
+const TRAIL_DOTS: usize = 30;
+/// Alpha of the player that isn't focused
+const DIMMED_ALPHA: f32 = 0.25;
+
+/// Which player and timeline markers the replay emphasizes
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Default)]
+pub struct ReplayFocus {
+    /// Focused player (None = both players shown normally)
+    pub player: Option<Team>,
+    /// Only show timeline markers of this type (None = all types)
+    pub markers: Option<EventMarkerType>,
+}
+
+impl ReplayFocus {
+    /// Parse `--replay-focus` (left/right) and `--replay-markers` values
+    pub fn from_args(focus: Option<&str>, markers: Option<&str>) -> Result<Self, String> {
+        let player = match focus {
+            None => None,
+            Some("left") => Some(Team::Left),
+            Some("right") => Some(Team::Right),
+            Some(other) => return Err(format!("unknown replay focus '{}'", other)),
+        };
+        let markers = match markers {
+            None | Some("all") => None,
+            Some(name) => Some(
+                EventMarkerType::from_str(name)
+                    .ok_or_else(|| format!("unknown marker type '{}'", name))?,
+            ),
+        };
+        Ok(Self { player, markers })
+    }
+
+    /// None -> Left -> Right -> None
+    pub fn cycle_player(&mut self) {
+        self.player = match self.player {
+            None => Some(Team::Left),
+            Some(Team::Left) => Some(Team::Right),
+            Some(Team::Right) => None,
+        };
+    }
+
+    /// All -> each EventMarkerType in turn -> All
+    pub fn cycle_markers(&mut self) {
+        let all = EventMarkerType::ALL;
+        self.markers = match self.markers {
+            None => Some(all[0]),
+            Some(current) => all
+                .iter()
+                .position(|t| *t == current)
+                .and_then(|i| all.get(i + 1))
+                .copied(),
+        };
+    }
+
+    /// Whether a timeline marker passes the type filter and belongs to the focused player.
+    /// Markers without a player (none today) pass the player filter.
+    pub fn shows_marker(&self, marker: &ReplayEventMarker) -> bool {
+        let type_ok = self.markers.is_none_or(|t| t == marker.event_type);
+        let player_ok = match (self.player, marker.player) {
+            (Some(team), Some(player)) => team_player_id(team) == player,
+            _ => true,
+        };
+        type_ok && player_ok
+    }
+
+    /// Whether a player (or their label) is shown at full strength
+    pub fn is_emphasized(&self, team: Team) -> bool {
+        self.player.is_none_or(|focused| focused == team)
+    }
+
+    /// Short status for the replay UI ("" when nothing is filtered)
+    pub fn status_string(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(team) = self.player {
+            parts.push(format!("Focus: {:?}", team));
+        }
+        if let Some(markers) = self.markers {
+            parts.push(format!("Markers: {}", markers.as_str()));
+        }
+        parts.join(" | ")
+    }
+}
+
+fn team_player_id(team: Team) -> PlayerId {
+    match team {
+        Team::Left => PlayerId::L,
+        Team::Right => PlayerId::R,
+    }
+}
+
+/// One dot of the focused player's trail (index 0 is the oldest)
+#[derive(Component)]
+pub struct ReplayTrailDot(pub usize);
+
+/// Focus/filter status text under the speed display
+#[derive(Component)]
+pub struct ReplayFocusText;
+
+/// Spawn the (hidden) trail dots and focus status text
+pub fn setup_replay_focus(mut commands: Commands) {
+    for i in 0..TRAIL_DOTS {
+        // Older dots are smaller and fainter
+        let age = 1.0 - i as f32 / TRAIL_DOTS as f32;
+        commands.spawn((
+            Sprite {
+                color: Color::srgba(1.0, 1.0, 0.6, 0.7 - 0.5 * age),
+                custom_size: Some(Vec2::splat(6.0 - 3.0 * age)),
+                ..default()
+            },
+            Transform::from_xyz(0.0, 0.0, 0.5),
+            Visibility::Hidden,
+            ReplayTrailDot(i),
+        ));
+    }
+
+    commands.spawn((
+        Text2d::new(""),
+        TextFont {
+            font_size: 14.0,
+            ..default()
+        },
+        TextColor(TEXT_ACCENT),
+        Transform::from_xyz(ARENA_WIDTH / 2.0 - 120.0, ARENA_HEIGHT / 2.0 - 80.0, 10.0),
+        ReplayFocusText,
+    ));
+}
+
+/// P cycles the focused player, M cycles the timeline marker filter
+pub fn replay_focus_input(keyboard: Res<ButtonInput<KeyCode>>, mut focus: ResMut<ReplayFocus>) {
+    if keyboard.just_pressed(KeyCode::KeyP) {
+        focus.cycle_player();
+        info!("Replay focus: {:?}", focus.player);
+    }
+    if keyboard.just_pressed(KeyCode::KeyM) {
+        focus.cycle_markers();
+        info!(
+            "Replay markers: {}",
+            focus.markers.map_or("all", |m| m.as_str())
+        );
+    }
+}
+
+/// Apply the focus: dim players, filter markers and labels, position the trail
+#[allow(clippy::type_complexity)]
+pub fn update_replay_focus(
+    focus: Res<ReplayFocus>,
+    state: Res<ReplayState>,
+    replay_data: Res<ReplayData>,
+    mut players: Query<(&mut Sprite, &Team), With<Player>>,
+    mut markers: Query<(&ReplayEventMarker, &mut Visibility), Without<ReplayTrailDot>>,
+    mut labels: Query<
+        (&PlayerGoalLabel, &mut Visibility),
+        (Without<ReplayEventMarker>, Without<ReplayTrailDot>),
+    >,
+    mut dots: Query<(&ReplayTrailDot, &mut Transform, &mut Visibility), Without<Player>>,
+    mut status: Query<&mut Text2d, With<ReplayFocusText>>,
+) {
+    for (mut sprite, team) in &mut players {
+        let alpha = if focus.is_emphasized(*team) {
+            1.0
+        } else {
+            DIMMED_ALPHA
+        };
+        sprite.color.set_alpha(alpha);
+    }
+
+    for (marker, mut visibility) in &mut markers {
+        *visibility = if focus.shows_marker(marker) {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+
+    for (label, mut visibility) in &mut labels {
+        *visibility = if focus.is_emphasized(label.0) {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+
+    // Trail: sample the focused player's recorded position back through TRAIL_MS
+    let step_ms = TRAIL_MS / TRAIL_DOTS as u32;
+    for (dot, mut transform, mut visibility) in &mut dots {
+        let Some(team) = focus.player else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        let back_ms = (TRAIL_DOTS - dot.0) as u32 * step_ms;
+        let Some(time_ms) = state.current_time_ms.checked_sub(back_ms) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        let Some((prev, next, t)) = replay_data.find_bracket(time_ms) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        let pos = match team {
+            Team::Left => prev.left_pos.lerp(next.left_pos, t),
+            Team::Right => prev.right_pos.lerp(next.right_pos, t),
+        };
+        transform.translation.x = pos.x;
+        transform.translation.y = pos.y;
+        *visibility = Visibility::Inherited;
+    }
+
+    for mut text in &mut status {
+        **text = focus.status_string();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_focus_filters_markers_by_player_and_type() {
+        let steal_by_left = ReplayEventMarker {
+            time_ms: 100,
+            event_type: EventMarkerType::Steal,
+            player: Some(PlayerId::L),
+        };
+        let goal_by_right = ReplayEventMarker {
+            time_ms: 200,
+            event_type: EventMarkerType::Goal,
+            player: Some(PlayerId::R),
+        };
+
+        let mut focus = ReplayFocus::default();
+        assert!(focus.shows_marker(&steal_by_left) && focus.shows_marker(&goal_by_right));
+        assert_eq!(focus.status_string(), "");
+
+        focus.cycle_player();
+        assert_eq!(focus.player, Some(Team::Left));
+        assert!(focus.shows_marker(&steal_by_left));
+        assert!(!focus.shows_marker(&goal_by_right));
+        assert!(focus.is_emphasized(Team::Left) && !focus.is_emphasized(Team::Right));
+
+        focus.cycle_markers();
+        assert_eq!(focus.markers, Some(EventMarkerType::Goal));
+        assert!(!focus.shows_marker(&steal_by_left));
+
+        // Cycling wraps back to "all"
+        for _ in 0..EventMarkerType::ALL.len() {
+            focus.cycle_markers();
+        }
+        assert_eq!(focus.markers, None);
+
+        let parsed = ReplayFocus::from_args(Some("right"), Some("steal")).unwrap();
+        assert_eq!(parsed.player, Some(Team::Right));
+        assert_eq!(parsed.markers, Some(EventMarkerType::Steal));
+        assert!(ReplayFocus::from_args(Some("both"), None).is_err());
+        assert!(ReplayFocus::from_args(None, Some("jump")).is_err());
+    }
+}
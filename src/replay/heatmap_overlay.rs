@@ -0,0 +1,288 @@
+//! Replay heatmap underlay - judge positioning against expected shot quality
+//!
+//! H cycles a heatmap drawn beneath the playback: score toward the left basket,
+//! score toward the right basket, reachability, then off. Grids come from the
+//! showcase outputs in `showcase/heatmaps`. When the replayed level has none, the
+//! `heatmap` tool runs for just that level in the background and the underlay
+//! appears once it finishes.
+
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+
+use crate::ai::heatmaps::try_load_level_heatmap;
+use crate::constants::{ARENA_HEIGHT, ARENA_WIDTH, HEATMAP_CELL_SIZE, TEXT_ACCENT};
+use crate::levels::LevelDatabase;
+use crate::scoring::CurrentLevel;
+
+/// Underlay opacity (platforms and players stay readable on top)
+const TILE_ALPHA: f32 = 0.35;
+/// Below the level geometry (z 0) and players (z 1)
+const TILE_Z: f32 = -0.5;
+
+/// Which heatmap is drawn under the replay
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HeatmapUnderlay {
+    #[default]
+    Off,
+    ScoreLeft,
+    ScoreRight,
+    Reachability,
+}
+
+impl HeatmapUnderlay {
+    /// Off -> score (left) -> score (right) -> reachability -> Off
+    pub fn next(self) -> Self {
+        match self {
+            Self::Off => Self::ScoreLeft,
+            Self::ScoreLeft => Self::ScoreRight,
+            Self::ScoreRight => Self::Reachability,
+            Self::Reachability => Self::Off,
+        }
+    }
+
+    /// Heatmap kind and side as named in showcase/heatmaps (None when off)
+    pub fn file_kind(self) -> Option<(&'static str, Option<&'static str>)> {
+        match self {
+            Self::Off => None,
+            Self::ScoreLeft => Some(("score", Some("left"))),
+            Self::ScoreRight => Some(("score", Some("right"))),
+            Self::Reachability => Some(("reachability", None)),
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::ScoreLeft => "score (left basket)",
+            Self::ScoreRight => "score (right basket)",
+            Self::Reachability => "reachability",
+        }
+    }
+
+    /// `heatmap` tool arguments that generate this heatmap for one level
+    /// (score runs write both sides)
+    pub fn tool_args(self, level_id: &str) -> Option<Vec<String>> {
+        let args: &[&str] = match self {
+            Self::Off => return None,
+            Self::ScoreLeft | Self::ScoreRight => &["heatmap", "score", "--fast"],
+            Self::Reachability => &["heatmap", "--type", "reachability"],
+        };
+        let mut args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+        args.extend(["--level".to_string(), level_id.to_string()]);
+        Some(args)
+    }
+}
+
+/// Red (0) through yellow to green (1), translucent
+pub fn heat_color(value: f32) -> Color {
+    let v = value.clamp(0.0, 1.0);
+    let red = (2.0 * (1.0 - v)).min(1.0);
+    let green = (2.0 * v).min(1.0);
+    Color::srgba(red, green, 0.0, TILE_ALPHA)
+}
+
+/// Background run of the heatmap tool for one level
+struct HeatmapJob {
+    level_id: String,
+    underlay: HeatmapUnderlay,
+    result: Arc<Mutex<Option<Result<(), String>>>>,
+}
+
+impl HeatmapJob {
+    fn start(level_id: &str, underlay: HeatmapUnderlay) -> Option<Self> {
+        let args = underlay.tool_args(level_id)?;
+        let result = Arc::new(Mutex::new(None));
+        let slot = Arc::clone(&result);
+        std::thread::spawn(move || {
+            let outcome = crate::cli::run_tool_status(&args).and_then(|status| {
+                if status.success() {
+                    Ok(())
+                } else {
+                    Err(format!("heatmap tool exited with {}", status))
+                }
+            });
+            if let Ok(mut slot) = slot.lock() {
+                *slot = Some(outcome);
+            }
+        });
+        Some(Self {
+            level_id: level_id.to_string(),
+            underlay,
+            result,
+        })
+    }
+
+    fn take_result(&self) -> Option<Result<(), String>> {
+        self.result.lock().ok().and_then(|mut r| r.take())
+    }
+}
+
+/// Underlay selection and what is currently drawn
+#[derive(Resource, Default)]
+pub struct ReplayHeatmapOverlay {
+    pub underlay: HeatmapUnderlay,
+    /// (level id, underlay) the spawned tiles show
+    shown: Option<(String, HeatmapUnderlay)>,
+    generating: Option<HeatmapJob>,
+    /// Heatmaps the tool couldn't produce (not retried this session)
+    failed: Vec<(String, HeatmapUnderlay)>,
+}
+
+/// One grid cell of the underlay
+#[derive(Component)]
+pub struct ReplayHeatmapTile;
+
+/// Underlay status text under the focus status
+#[derive(Component)]
+pub struct ReplayHeatmapText;
+
+pub fn setup_replay_heatmap(mut commands: Commands) {
+    commands.spawn((
+        Text2d::new(""),
+        TextFont {
+            font_size: 14.0,
+            ..default()
+        },
+        TextColor(TEXT_ACCENT),
+        Transform::from_xyz(ARENA_WIDTH / 2.0 - 120.0, ARENA_HEIGHT / 2.0 - 100.0, 10.0),
+        ReplayHeatmapText,
+    ));
+}
+
+/// H cycles the underlay
+pub fn replay_heatmap_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut overlay: ResMut<ReplayHeatmapOverlay>,
+) {
+    if keyboard.just_pressed(KeyCode::KeyH) {
+        overlay.underlay = overlay.underlay.next();
+        info!("Replay heatmap: {}", overlay.underlay.label());
+    }
+}
+
+/// Draw the selected heatmap for the current level, generating it if missing.
+/// Rebuilds when the selection or the level (playlists) changes.
+pub fn update_replay_heatmap(
+    mut commands: Commands,
+    mut overlay: ResMut<ReplayHeatmapOverlay>,
+    current_level: Res<CurrentLevel>,
+    level_db: Res<LevelDatabase>,
+    tiles: Query<Entity, With<ReplayHeatmapTile>>,
+    mut status: Query<&mut Text2d, With<ReplayHeatmapText>>,
+) {
+    if let Some(result) = overlay
+        .generating
+        .as_ref()
+        .and_then(HeatmapJob::take_result)
+    {
+        let job = overlay.generating.take().expect("job checked above");
+        match result {
+            Ok(()) => info!(
+                "Replay heatmap: generated {} for level {}",
+                job.underlay.label(),
+                job.level_id
+            ),
+            Err(e) => {
+                warn!("Replay heatmap: {}", e);
+                overlay.failed.push((job.level_id, job.underlay));
+            }
+        }
+        overlay.shown = None;
+    }
+
+    let wanted = (current_level.0.clone(), overlay.underlay);
+    if overlay.shown.as_ref() == Some(&wanted) {
+        return;
+    }
+    let Some(level) = level_db.get_by_id(&wanted.0) else {
+        // Replay world not set up yet
+        return;
+    };
+
+    for tile in &tiles {
+        commands.entity(tile).despawn();
+    }
+    let underlay = overlay.underlay;
+    let mut set_status = |text: String| {
+        for mut status in &mut status {
+            **status = text.clone();
+        }
+    };
+
+    let Some((kind, side)) = underlay.file_kind() else {
+        set_status(String::new());
+        overlay.shown = Some(wanted);
+        return;
+    };
+    if overlay.generating.is_some() {
+        // Checked again when the run finishes
+        set_status(format!("Heatmap: generating {}...", underlay.label()));
+        return;
+    }
+
+    match try_load_level_heatmap(level, kind, side) {
+        Ok(grid) => {
+            let size = Vec2::splat(HEATMAP_CELL_SIZE as f32);
+            for (center, value) in grid.cells().filter(|(_, v)| *v > 0.0) {
+                commands.spawn((
+                    Sprite::from_color(heat_color(value), size),
+                    Transform::from_xyz(center.x, center.y, TILE_Z),
+                    ReplayHeatmapTile,
+                ));
+            }
+            set_status(format!("Heatmap: {}", underlay.label()));
+            overlay.shown = Some(wanted);
+        }
+        Err(_) if overlay.failed.contains(&wanted) => {
+            set_status(format!("Heatmap: no {} for this level", underlay.label()));
+            overlay.shown = Some(wanted);
+        }
+        Err(e) => {
+            info!("Replay heatmap: {} - generating", e);
+            set_status(format!("Heatmap: generating {}...", underlay.label()));
+            overlay.generating = HeatmapJob::start(&wanted.0, underlay);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_underlay_cycles_and_generates_per_level() {
+        let mut underlay = HeatmapUnderlay::default();
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            underlay = underlay.next();
+            seen.push(underlay);
+        }
+        assert_eq!(
+            seen,
+            [
+                HeatmapUnderlay::ScoreLeft,
+                HeatmapUnderlay::ScoreRight,
+                HeatmapUnderlay::Reachability,
+                HeatmapUnderlay::Off
+            ]
+        );
+
+        assert_eq!(HeatmapUnderlay::Off.file_kind(), None);
+        assert_eq!(HeatmapUnderlay::Off.tool_args("abc"), None);
+        assert_eq!(
+            HeatmapUnderlay::ScoreRight.file_kind(),
+            Some(("score", Some("right")))
+        );
+        assert_eq!(
+            HeatmapUnderlay::Reachability.tool_args("abc").unwrap(),
+            ["heatmap", "--type", "reachability", "--level", "abc"]
+        );
+
+        // Low cells red, high cells green
+        let low = heat_color(0.0).to_srgba();
+        let high = heat_color(1.0).to_srgba();
+        assert!(low.red > low.green && high.green > high.red);
+        assert_eq!(low.alpha, TILE_ALPHA);
+    }
+}
@@ -0,0 +1,276 @@
+//! Instant replay of goals during live matches
+//!
+//! Every fixed tick the live game appends player and ball positions to a small
+//! in-memory ring buffer (nothing goes to SQLite). When a goal is scored with the
+//! option on, gameplay pauses and the buffered run-up plays back in slow motion.
+//! Jump skips it. Live positions are then put back and the countdown for the next
+//! possession starts.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use super::data::{ReplayData, TickFrame};
+use crate::ball::{Ball, BallState, Velocity};
+use crate::constants::*;
+use crate::countdown::MatchCountdown;
+use crate::input::InputBindings;
+use crate::player::{Player, Team, Teammate};
+use crate::scoring::Score;
+
+/// Goal replay state: the tick ring buffer and the clip being played
+#[derive(Resource, Default)]
+pub struct InstantReplay {
+    pub enabled: bool,
+    /// Most recent live ticks, oldest first
+    buffer: VecDeque<TickFrame>,
+    /// Ticks recorded so far
+    ticks: u64,
+    /// Total goals (left + right) when last checked
+    goals_seen: u32,
+    /// Clip being played back
+    clip: Option<ReplayData>,
+    /// Real seconds into the clip
+    elapsed: f32,
+    /// Live positions to restore when playback ends
+    resume: Option<TickFrame>,
+}
+
+impl InstantReplay {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..default()
+        }
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.clip.is_some()
+    }
+
+    /// Append a live tick, dropping the oldest once the buffer is full
+    pub fn record(&mut self, mut frame: TickFrame) {
+        frame.frame = self.ticks;
+        frame.time_ms = (self.ticks * 1000 / 60) as u32;
+        self.ticks += 1;
+        if self.buffer.len() == INSTANT_REPLAY_BUFFER_TICKS {
+            self.buffer.pop_front();
+        }
+        self.buffer.push_back(frame);
+    }
+
+    /// Start playback if the total score went up since the last check.
+    /// `live` is the post-goal state to restore afterwards.
+    pub fn check_goal(&mut self, total_goals: u32, live: TickFrame) -> bool {
+        let scored = total_goals > self.goals_seen;
+        self.goals_seen = total_goals;
+        if !self.enabled || !scored || self.is_playing() || self.buffer.is_empty() {
+            return false;
+        }
+
+        let mut ticks: Vec<TickFrame> = self.buffer.drain(..).collect();
+        let start_ms = ticks[0].time_ms;
+        for tick in &mut ticks {
+            tick.time_ms -= start_ms;
+        }
+        self.clip = Some(ReplayData {
+            duration_ms: ticks.last().map_or(0, |t| t.time_ms),
+            ticks,
+            ..default()
+        });
+        self.elapsed = 0.0;
+        self.resume = Some(live);
+        true
+    }
+
+    /// Interpolated clip frame at the current playback position
+    pub fn current_frame(&self) -> Option<TickFrame> {
+        let clip = self.clip.as_ref()?;
+        let time_ms = (self.elapsed * INSTANT_REPLAY_SPEED * 1000.0) as u32;
+        let (prev, next, t) = clip.find_bracket(time_ms)?;
+        Some(TickFrame {
+            left_pos: prev.left_pos.lerp(next.left_pos, t),
+            right_pos: prev.right_pos.lerp(next.right_pos, t),
+            ball_pos: prev.ball_pos.lerp(next.ball_pos, t),
+            ..prev
+        })
+    }
+
+    /// Advance playback by `dt` real seconds. Returns the live state to restore
+    /// once the clip has played out or was skipped.
+    pub fn advance(&mut self, dt: f32, skip: bool) -> Option<TickFrame> {
+        let clip = self.clip.as_ref()?;
+        self.elapsed += dt;
+        let clip_done = self.elapsed * INSTANT_REPLAY_SPEED * 1000.0 > clip.duration_ms as f32;
+        if skip || clip_done || self.elapsed >= INSTANT_REPLAY_SECS {
+            self.clip = None;
+            return self.resume.take();
+        }
+        None
+    }
+}
+
+/// Run condition: no goal replay is playing
+pub fn not_instant_replay(replay: Res<InstantReplay>) -> bool {
+    !replay.is_playing()
+}
+
+/// Marker for the "REPLAY" banner shown during playback
+#[derive(Component)]
+pub struct InstantReplayText;
+
+/// Spawn the replay banner (hidden until a replay plays; called from setup)
+pub fn spawn_instant_replay_text(commands: &mut Commands) {
+    commands.spawn((
+        Text2d::new("REPLAY  [Jump: skip]"),
+        TextFont {
+            font_size: 48.0,
+            ..default()
+        },
+        TextLayout::new_with_justify(bevy::text::Justify::Center),
+        TextColor(Color::srgb(1.0, 0.3, 0.3)),
+        Transform::from_xyz(0.0, 250.0, 100.0),
+        Visibility::Hidden,
+        InstantReplayText,
+    ));
+}
+
+/// Record this tick into the replay buffer (runs before scoring so the shot's
+/// final tick is kept)
+pub fn record_instant_replay(
+    mut replay: ResMut<InstantReplay>,
+    players: Query<(&Transform, &Velocity, &Team), (With<Player>, Without<Teammate>)>,
+    balls: Query<(&Transform, &Velocity, &BallState), With<Ball>>,
+) {
+    if !replay.enabled {
+        return;
+    }
+    if let Some(frame) = TickFrame::capture(&players, &balls) {
+        replay.record(frame);
+    }
+}
+
+/// Start a replay when a goal was scored this tick (runs after scoring)
+pub fn start_instant_replay(
+    score: Res<Score>,
+    mut replay: ResMut<InstantReplay>,
+    players: Query<(&Transform, &Velocity, &Team), (With<Player>, Without<Teammate>)>,
+    balls: Query<(&Transform, &Velocity, &BallState), With<Ball>>,
+) {
+    if !score.is_changed() {
+        return;
+    }
+    let Some(live) = TickFrame::capture(&players, &balls) else {
+        return;
+    };
+    if replay.check_goal(score.left + score.right, live) {
+        info!("Instant replay: {}-{}", score.left, score.right);
+    }
+}
+
+/// Play the clip back, then restore live positions and start the countdown
+#[allow(clippy::too_many_arguments)]
+pub fn play_instant_replay(
+    time: Res<Time>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    bindings: Res<InputBindings>,
+    mut replay: ResMut<InstantReplay>,
+    mut countdown: ResMut<MatchCountdown>,
+    mut players: Query<(&mut Transform, &Team), (With<Player>, Without<Teammate>)>,
+    mut balls: Query<&mut Transform, (With<Ball>, Without<Player>)>,
+    mut banner: Query<&mut Visibility, With<InstantReplayText>>,
+) {
+    let playing = replay.is_playing();
+    for mut visibility in &mut banner {
+        *visibility = if playing {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+    if !playing {
+        return;
+    }
+
+    let skip = std::iter::once(&bindings.primary)
+        .chain(bindings.secondary.as_ref())
+        .any(|b| keyboard.any_just_pressed(b.jump.iter().copied()))
+        || gamepads
+            .iter()
+            .any(|gp| gp.just_pressed(GamepadButton::South));
+
+    let (frame, finished) = match replay.advance(time.delta_secs(), skip) {
+        Some(live) => (Some(live), true),
+        None => (replay.current_frame(), false),
+    };
+    if let Some(frame) = frame {
+        for (mut transform, team) in &mut players {
+            let pos = match team {
+                Team::Left => frame.left_pos,
+                Team::Right => frame.right_pos,
+            };
+            transform.translation.x = pos.x;
+            transform.translation.y = pos.y;
+        }
+        for mut transform in &mut balls {
+            transform.translation.x = frame.ball_pos.x;
+            transform.translation.y = frame.ball_pos.y;
+        }
+    }
+    if finished {
+        countdown.start();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame_at(x: f32) -> TickFrame {
+        TickFrame {
+            time_ms: 0,
+            frame: 0,
+            left_pos: Vec2::new(x, 0.0),
+            left_vel: Vec2::ZERO,
+            right_pos: Vec2::ZERO,
+            right_vel: Vec2::ZERO,
+            ball_pos: Vec2::new(x, 10.0),
+            ball_vel: Vec2::ZERO,
+            ball_state: 'I',
+        }
+    }
+
+    #[test]
+    fn test_instant_replay_buffers_and_plays_back() {
+        let mut replay = InstantReplay::new(true);
+        for i in 0..(INSTANT_REPLAY_BUFFER_TICKS + 30) {
+            replay.record(frame_at(i as f32));
+        }
+        assert_eq!(replay.buffer.len(), INSTANT_REPLAY_BUFFER_TICKS);
+
+        // No goal yet: nothing plays
+        assert!(!replay.check_goal(0, frame_at(-1.0)));
+        assert!(replay.check_goal(1, frame_at(-1.0)));
+        assert!(replay.is_playing());
+
+        // Clip starts at the oldest buffered tick, in slow motion
+        assert_eq!(replay.current_frame().unwrap().left_pos.x, 30.0);
+        assert!(replay.advance(1.0, false).is_none());
+        let x = replay.current_frame().unwrap().left_pos.x;
+        assert!((x - 60.0).abs() < 0.5, "0.5s of 60 Hz ticks in, got {}", x);
+
+        // Plays out after INSTANT_REPLAY_SECS and hands back the live state
+        let live = replay.advance(INSTANT_REPLAY_SECS, false).unwrap();
+        assert_eq!(live.left_pos.x, -1.0);
+        assert!(!replay.is_playing());
+
+        // Skip ends the next one early; a disabled replay never starts
+        replay.record(frame_at(0.0));
+        assert!(replay.check_goal(2, frame_at(-2.0)));
+        assert!(replay.advance(0.0, true).is_some());
+        let mut off = InstantReplay::new(false);
+        off.record(frame_at(0.0));
+        assert!(!off.check_goal(1, frame_at(0.0)));
+    }
+}
@@ -2,23 +2,54 @@
 //!
 //! The replay system loads recorded sessions from SQLite and plays them back
 //! with interpolated positions, variable speed control, and behavior observation overlays.
+//! `instant` replays goals in slow motion during live matches from an in-memory buffer;
+//! `focus` emphasizes one player and filters timeline markers for auditing.
+//! `recorder` keeps the last 30 seconds of the live game for saving as a clip file.
+//! `stream` pages the ticks of long matches in from SQLite a segment at a time.
+//! `playlist` plays a whole session's matches back-to-back.
+//! `heatmap_overlay` draws a score or reachability heatmap beneath the playback.
 
 mod data;
+mod focus;
+mod heatmap_overlay;
+mod instant;
+mod playlist;
+mod recorder;
 mod sqlite_loader;
 mod state;
+mod stream;
 mod systems;
 mod ui;
 
-pub use data::{ReplayData, TickFrame, TimedEvent};
-pub use sqlite_loader::load_replay_from_db;
+pub use data::{
+    KEYFRAME_INTERVAL_MS, KEYFRAME_INTERVAL_TICKS, ReplayData, ReplayKeyframe, TickFrame,
+    TimedEvent,
+};
+pub use focus::{ReplayFocus, replay_focus_input, setup_replay_focus, update_replay_focus};
+pub use heatmap_overlay::{
+    HeatmapUnderlay, ReplayHeatmapOverlay, replay_heatmap_input, setup_replay_heatmap,
+    update_replay_heatmap,
+};
+pub use instant::{
+    InstantReplay, InstantReplayText, not_instant_replay, play_instant_replay,
+    record_instant_replay, spawn_instant_replay_text, start_instant_replay,
+};
+pub use playlist::{
+    PLAYLIST_CARD_SECS, PlaylistFilter, ReplayPlaylist, ReplayPlaylistCard, advance_replay_playlist,
+};
+pub use recorder::{ClipRecorder, load_replay_from_file, record_clip_frame, save_clip_hotkey};
+pub use sqlite_loader::{load_replay_from_db, load_replay_windowed};
 pub use state::ReplayState;
 pub use systems::{replay_input_handler, replay_playback, replay_setup};
 pub use ui::{
-    PlayerGoalLabel, ReplayEventMarker, ReplaySpeedDisplay, ReplayTimeDisplay, ReplayTimeline,
-    setup_replay_ui, update_replay_ui,
+    EventMarkerType, PlayerGoalLabel, ReplayEventMarker, ReplaySpeedDisplay, ReplayTimeDisplay,
+    ReplayTimeline, setup_replay_ui, update_replay_ui,
 };
 
 use bevy::prelude::*;
+use std::path::PathBuf;
+
+use crate::events::TeamTags;
 
 /// Resource to control replay mode activation
 #[derive(Resource, Default)]
@@ -27,6 +58,10 @@ pub struct ReplayMode {
     pub active: bool,
     /// Match ID for SQLite replay
     pub match_id: Option<i64>,
+    /// Clip file to replay instead of a DB match
+    pub file: Option<PathBuf>,
+    /// Database holding `match_id` (None = the training database)
+    pub db_path: Option<PathBuf>,
 }
 
 impl ReplayMode {
@@ -34,6 +69,17 @@ impl ReplayMode {
         Self {
             active: true,
             match_id: Some(match_id),
+            file: None,
+            db_path: None,
+        }
+    }
+
+    pub fn new_file(path: PathBuf) -> Self {
+        Self {
+            active: true,
+            match_id: None,
+            file: Some(path),
+            db_path: None,
         }
     }
 }
@@ -56,4 +102,6 @@ pub struct MatchInfo {
     pub left_profile: String,
     pub right_profile: String,
     pub seed: u64,
+    /// Team names and colors (empty when the sides weren't named)
+    pub teams: TeamTags,
 }
@@ -0,0 +1,269 @@
+//! Replay playlist - watch every match of a session back-to-back.
+//!
+//! `--replay-session <id|latest>` queues the session's matches in order. When
+//! one finishes, a summary card (final score, what's next) shows for
+//! PLAYLIST_CARD_SECS and the next match loads in place. N skips ahead.
+//! `--replay-filter underdog` keeps only matches won by the profile with the
+//! lower win rate across the database.
+
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+
+use super::sqlite_loader::load_replay_windowed;
+use super::state::ReplayState;
+use super::systems::spawn_replay_world;
+use super::ui::{ReplayEventMarker, ReplayMatchInfoText, match_info_text, spawn_event_markers};
+use crate::analytics::underdog_wins;
+use crate::ball::Ball;
+use crate::constants::*;
+use crate::levels::{LevelDatabase, LevelPlatform};
+use crate::player::Player;
+use crate::scoring::CurrentLevel;
+use crate::simulation::{MatchSummary, SimDatabase};
+use crate::world::{Basket, Platform};
+
+/// Seconds the between-match summary card stays up
+pub const PLAYLIST_CARD_SECS: f32 = 4.0;
+
+/// Which of a session's matches to queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlaylistFilter {
+    #[default]
+    All,
+    /// Only matches the lower-win-rate profile won
+    UnderdogWins,
+}
+
+impl PlaylistFilter {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "all" => Some(Self::All),
+            "underdog" => Some(Self::UnderdogWins),
+            _ => None,
+        }
+    }
+}
+
+/// Queued matches and the card between them
+#[derive(Resource, Default)]
+pub struct ReplayPlaylist {
+    pub db_path: PathBuf,
+    pub entries: Vec<MatchSummary>,
+    /// Entry currently playing
+    pub index: usize,
+    /// Seconds left on the summary card (None = playing)
+    card_timer: Option<f32>,
+}
+
+/// Between-match summary card
+#[derive(Component)]
+pub struct ReplayPlaylistCard;
+
+impl ReplayPlaylist {
+    /// Queue a session's matches (`session` = "latest" for the newest session)
+    pub fn from_session(
+        db_path: &Path,
+        session: &str,
+        filter: PlaylistFilter,
+    ) -> Result<Self, String> {
+        let db = SimDatabase::open(db_path).map_err(|e| e.to_string())?;
+        let session_id = if session == "latest" {
+            db.get_latest_session()
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| format!("No sessions in {}", db_path.display()))?
+        } else {
+            session.to_string()
+        };
+        let mut entries = db
+            .get_session_matches(&session_id)
+            .map_err(|e| e.to_string())?;
+        if filter == PlaylistFilter::UnderdogWins {
+            entries = underdog_wins(&db, &entries)?;
+        }
+        if entries.is_empty() {
+            return Err(format!("Session {} has no matches to play", session_id));
+        }
+        Ok(Self {
+            db_path: db_path.to_path_buf(),
+            entries,
+            index: 0,
+            card_timer: None,
+        })
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.entries.is_empty()
+    }
+
+    pub fn first_match_id(&self) -> Option<i64> {
+        self.entries.first().map(|m| m.id)
+    }
+
+    fn has_next(&self) -> bool {
+        self.index + 1 < self.entries.len()
+    }
+
+    /// Result of the match just played and what's up next
+    pub fn summary_card(&self) -> String {
+        let Some(done) = self.entries.get(self.index) else {
+            return String::new();
+        };
+        let result = match done.winner.as_str() {
+            "left" => format!("{} wins", done.left_profile),
+            "right" => format!("{} wins", done.right_profile),
+            _ => "Tie".to_string(),
+        };
+        let mut card = format!(
+            "Match {}/{}: {} {} - {} {} on {}\n{}",
+            self.index + 1,
+            self.entries.len(),
+            done.left_profile,
+            done.score_left,
+            done.score_right,
+            done.right_profile,
+            done.level_name,
+            result
+        );
+        if let Some(next) = self.entries.get(self.index + 1) {
+            card.push_str(&format!(
+                "\n\nUp next: {} vs {} on {}\n(N: skip)",
+                next.left_profile, next.right_profile, next.level_name
+            ));
+        }
+        card
+    }
+}
+
+/// Show the summary card when a match ends, then load the next one
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn advance_replay_playlist(
+    mut commands: Commands,
+    time: Res<Time>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    level_db: Res<LevelDatabase>,
+    mut playlist: ResMut<ReplayPlaylist>,
+    mut state: ResMut<ReplayState>,
+    mut current_level: ResMut<CurrentLevel>,
+    mut info_text: Query<&mut Text2d, With<ReplayMatchInfoText>>,
+    replay_world: Query<
+        Entity,
+        Or<(
+            With<Player>,
+            With<Ball>,
+            With<Platform>,
+            With<Basket>,
+            With<LevelPlatform>,
+            With<ReplayEventMarker>,
+        )>,
+    >,
+    cards: Query<Entity, With<ReplayPlaylistCard>>,
+) {
+    if !playlist.is_active() {
+        return;
+    }
+    let skip = keyboard.just_pressed(KeyCode::KeyN);
+
+    let Some(remaining) = playlist.card_timer else {
+        if (state.finished || skip) && playlist.has_next() {
+            playlist.card_timer = Some(PLAYLIST_CARD_SECS);
+            commands.spawn((
+                Text2d::new(playlist.summary_card()),
+                TextFont {
+                    font_size: 24.0,
+                    ..default()
+                },
+                TextLayout::new_with_justify(Justify::Center),
+                TextColor(TEXT_PRIMARY),
+                Transform::from_xyz(0.0, 0.0, 20.0),
+                ReplayPlaylistCard,
+            ));
+        }
+        return;
+    };
+
+    let remaining = remaining - time.delta_secs();
+    if remaining > 0.0 && !skip {
+        playlist.card_timer = Some(remaining);
+        return;
+    }
+    playlist.card_timer = None;
+    for card in &cards {
+        commands.entity(card).despawn();
+    }
+
+    // Next loadable match (a bad one is skipped, not fatal)
+    while playlist.has_next() {
+        playlist.index += 1;
+        let match_id = playlist.entries[playlist.index].id;
+        let replay_data = match load_replay_windowed(&playlist.db_path, match_id) {
+            Ok(data) => data,
+            Err(e) => {
+                warn!("Playlist: skipping match {}: {}", match_id, e);
+                continue;
+            }
+        };
+
+        for entity in &replay_world {
+            commands.entity(entity).despawn();
+        }
+        spawn_replay_world(&mut commands, &replay_data, &level_db, &mut current_level);
+        spawn_event_markers(&mut commands, &replay_data);
+        for mut text in &mut info_text {
+            **text = match_info_text(&replay_data);
+        }
+
+        // Restart playback at the speed the viewer picked
+        let speed_index = state.speed_index;
+        *state = ReplayState::default();
+        state.set_speed_index(speed_index);
+        commands.insert_resource(replay_data);
+        return;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(id: i64, winner: &str) -> MatchSummary {
+        MatchSummary {
+            id,
+            level: 1,
+            level_name: "Arena".to_string(),
+            left_profile: "Aggressive".to_string(),
+            right_profile: "Defensive".to_string(),
+            score_left: 3,
+            score_right: 1,
+            duration: 60.0,
+            winner: winner.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_summary_card_and_filter_parse() {
+        let playlist = ReplayPlaylist {
+            entries: vec![summary(1, "left"), summary(2, "tie")],
+            ..default()
+        };
+        assert!(playlist.is_active());
+        assert_eq!(playlist.first_match_id(), Some(1));
+        let card = playlist.summary_card();
+        assert!(card.starts_with("Match 1/2: Aggressive 3 - 1 Defensive on Arena"));
+        assert!(card.contains("Aggressive wins"));
+        assert!(card.contains("Up next"));
+
+        let last = ReplayPlaylist {
+            index: 1,
+            ..playlist
+        };
+        assert!(!last.has_next());
+        assert!(!last.summary_card().contains("Up next"));
+
+        assert_eq!(
+            PlaylistFilter::parse("underdog"),
+            Some(PlaylistFilter::UnderdogWins)
+        );
+        assert_eq!(PlaylistFilter::parse("upset"), None);
+    }
+}
@@ -0,0 +1,294 @@
+//! Rolling clip recorder - "save the last 30 seconds"
+//!
+//! Always on in the live game. Keeps the last `CLIP_RECORDER_SECS` of ticks (20 Hz,
+//! like logged matches) and event bus events in memory. F5 writes them to a clip
+//! file in `clips/`: one event per line in the `T:ms|CODE|data` log format, times
+//! starting at 0, led by a `MatchStart` line. `cargo run -- --replay-file <clip>`
+//! plays it back in the normal replay viewer.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+
+use super::MatchInfo;
+use super::data::{ReplayData, TickFrame, TimedEvent};
+use crate::ai::{AiProfileDatabase, AiState};
+use crate::ball::{Ball, BallState, Velocity};
+use crate::constants::*;
+use crate::events::{EventBus, GameEvent, intern, parse_event, serialize_event};
+use crate::levels::LevelDatabase;
+use crate::player::{HumanControlled, Player, Team, Teammate};
+use crate::scoring::CurrentLevel;
+use crate::teams::TeamIdentity;
+
+/// In-memory ring buffer of the most recent live ticks and events
+#[derive(Resource, Default)]
+pub struct ClipRecorder {
+    ticks: VecDeque<TickFrame>,
+    events: VecDeque<TimedEvent>,
+    /// Bus events already copied (the live game never drains the bus)
+    bus_seen: usize,
+}
+
+impl ClipRecorder {
+    /// Add a tick if at least `CLIP_RECORDER_TICK_MS` passed since the last one
+    pub fn record_tick(&mut self, frame: TickFrame) {
+        if self
+            .ticks
+            .back()
+            .is_some_and(|last| frame.time_ms < last.time_ms + CLIP_RECORDER_TICK_MS)
+        {
+            return;
+        }
+        let now = frame.time_ms;
+        self.ticks.push_back(frame);
+        self.prune(now);
+    }
+
+    pub fn record_event(&mut self, time_ms: u32, event: GameEvent) {
+        self.events.push_back(TimedEvent { time_ms, event });
+        self.prune(time_ms);
+    }
+
+    /// Drop anything older than the recording window
+    fn prune(&mut self, now_ms: u32) {
+        let cutoff = now_ms.saturating_sub(CLIP_RECORDER_SECS * 1000);
+        while self.ticks.front().is_some_and(|t| t.time_ms < cutoff) {
+            self.ticks.pop_front();
+        }
+        while self.events.front().is_some_and(|e| e.time_ms < cutoff) {
+            self.events.pop_front();
+        }
+    }
+
+    /// Clip file contents: MatchStart, then ticks and events in time order, rebased to 0
+    pub fn clip_lines(&self, match_info: &MatchInfo) -> Vec<String> {
+        let start_ms = self
+            .ticks
+            .front()
+            .map(|t| t.time_ms)
+            .into_iter()
+            .chain(self.events.front().map(|e| e.time_ms))
+            .min()
+            .unwrap_or(0);
+        let mut timed: Vec<(u32, GameEvent)> = self
+            .ticks
+            .iter()
+            .map(|t| (t.time_ms, t.to_event()))
+            .chain(self.events.iter().map(|e| (e.time_ms, e.event.clone())))
+            .collect();
+        // Stable: ticks come before events at the same time
+        timed.sort_by_key(|(time_ms, _)| *time_ms);
+
+        let match_start = GameEvent::MatchStart {
+            level: match_info.level,
+            level_name: intern(&match_info.level_name),
+            left_profile: intern(&match_info.left_profile),
+            right_profile: intern(&match_info.right_profile),
+            seed: match_info.seed,
+            teams: match_info.teams.clone(),
+        };
+        std::iter::once(serialize_event(0, &match_start))
+            .chain(
+                timed
+                    .iter()
+                    .map(|(time_ms, event)| serialize_event(time_ms - start_ms, event)),
+            )
+            .collect()
+    }
+
+    /// Write the clip to `<dir>/clip_<timestamp>.evlog`
+    pub fn save_clip(&self, dir: &Path, match_info: &MatchInfo) -> Result<PathBuf, String> {
+        if self.ticks.is_empty() {
+            return Err("Nothing recorded yet".to_string());
+        }
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        let path = dir.join(format!("clip_{}.evlog", timestamp));
+        let mut contents = self.clip_lines(match_info).join("\n");
+        contents.push('\n');
+        fs::write(&path, contents)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        Ok(path)
+    }
+}
+
+/// Load a saved clip (or any event log in the same format) for the replay viewer
+pub fn load_replay_from_file(path: &Path) -> Result<ReplayData, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let mut replay = ReplayData {
+        session_id: path.display().to_string(),
+        ..default()
+    };
+    for line in contents.lines().filter(|l| !l.trim().is_empty()) {
+        let Some((time_ms, event)) = parse_event(line) else {
+            warn!("Skipping unparseable clip line: {}", line);
+            continue;
+        };
+        replay.duration_ms = replay.duration_ms.max(time_ms);
+        if let Some(tick) = TickFrame::from_event(time_ms, &event) {
+            replay.ticks.push(tick);
+            continue;
+        }
+        if let GameEvent::MatchStart {
+            level,
+            level_name,
+            left_profile,
+            right_profile,
+            seed,
+            teams,
+        } = &event
+        {
+            replay.match_info = MatchInfo {
+                level: *level,
+                level_name: level_name.to_string(),
+                left_profile: left_profile.to_string(),
+                right_profile: right_profile.to_string(),
+                seed: *seed,
+                teams: teams.clone(),
+            };
+        }
+        replay.events.push(TimedEvent { time_ms, event });
+    }
+    if replay.ticks.is_empty() {
+        return Err(format!("No ticks in {}", path.display()));
+    }
+    replay.build_keyframes();
+    Ok(replay)
+}
+
+/// Copy this frame's tick and any new bus events into the recorder
+pub fn record_clip_frame(
+    event_bus: Res<EventBus>,
+    mut recorder: ResMut<ClipRecorder>,
+    players: Query<(&Transform, &Velocity, &Team), (With<Player>, Without<Teammate>)>,
+    balls: Query<(&Transform, &Velocity, &BallState), With<Ball>>,
+) {
+    let pending = event_bus.peek();
+    if pending.len() < recorder.bus_seen {
+        // Someone drained the bus; everything left is new
+        recorder.bus_seen = 0;
+    }
+    let seen = recorder.bus_seen;
+    for bus_event in &pending[seen..] {
+        recorder.record_event(bus_event.time_ms, bus_event.event.clone());
+    }
+    recorder.bus_seen = pending.len();
+
+    if let Some(mut frame) = TickFrame::capture(&players, &balls) {
+        frame.time_ms = event_bus.elapsed_ms();
+        frame.frame = (frame.time_ms / CLIP_RECORDER_TICK_MS) as u64;
+        recorder.record_tick(frame);
+    }
+}
+
+/// Save the last `CLIP_RECORDER_SECS` to a clip file (F5 key)
+pub fn save_clip_hotkey(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    recorder: Res<ClipRecorder>,
+    current_level: Res<CurrentLevel>,
+    level_db: Res<LevelDatabase>,
+    profile_db: Res<AiProfileDatabase>,
+    teams: Option<Res<TeamIdentity>>,
+    players: Query<(&Team, &AiState, Option<&HumanControlled>), With<Player>>,
+) {
+    if !keyboard.just_pressed(KeyCode::F5) {
+        return;
+    }
+
+    let profile = |team: Team| {
+        players
+            .iter()
+            .find(|(t, _, _)| **t == team)
+            .map(|(_, ai_state, human)| match human {
+                Some(_) => "Human".to_string(),
+                None => profile_db
+                    .get_by_id(&ai_state.profile_id)
+                    .map(|p| p.name.clone())
+                    .unwrap_or_else(|| ai_state.profile_id.clone()),
+            })
+            .unwrap_or_default()
+    };
+    let match_info = MatchInfo {
+        level: level_db
+            .all()
+            .iter()
+            .position(|l| l.id == current_level.0)
+            .map_or(0, |i| i as u32 + 1),
+        level_name: level_db
+            .get_by_id(&current_level.0)
+            .map(|l| l.name.clone())
+            .unwrap_or_default(),
+        left_profile: profile(Team::Left),
+        right_profile: profile(Team::Right),
+        seed: 0,
+        teams: teams.map(|t| t.tags()).unwrap_or_default(),
+    };
+
+    match recorder.save_clip(Path::new(CLIPS_DIR), &match_info) {
+        Ok(path) => info!("Saved last {}s to {}", CLIP_RECORDER_SECS, path.display()),
+        Err(e) => warn!("Clip not saved: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::PlayerId;
+
+    fn tick(time_ms: u32) -> TickFrame {
+        TickFrame {
+            time_ms,
+            frame: 0,
+            left_pos: Vec2::new(time_ms as f32, -400.0),
+            left_vel: Vec2::ZERO,
+            right_pos: Vec2::new(100.0, -400.0),
+            right_vel: Vec2::ZERO,
+            ball_pos: Vec2::ZERO,
+            ball_vel: Vec2::ZERO,
+            ball_state: 'F',
+        }
+    }
+
+    #[test]
+    fn test_clip_keeps_window_and_round_trips() {
+        let mut recorder = ClipRecorder::default();
+        for time_ms in (0..40_000).step_by(10) {
+            recorder.record_tick(tick(time_ms));
+        }
+        recorder.record_event(
+            35_000,
+            GameEvent::Pickup {
+                player: PlayerId::L,
+            },
+        );
+        // 20 Hz over the last 30 seconds
+        assert_eq!(recorder.ticks.len(), 601);
+        assert_eq!(recorder.ticks.front().unwrap().time_ms, 9_950);
+
+        let dir = std::env::temp_dir().join(format!("ballgame_clip_test_{}", std::process::id()));
+        let info = MatchInfo {
+            level: 3,
+            level_name: "Arena".to_string(),
+            ..default()
+        };
+        let path = recorder.save_clip(&dir, &info).unwrap();
+        let replay = load_replay_from_file(&path).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(replay.match_info.level_name, "Arena");
+        assert_eq!(replay.ticks.len(), 601);
+        assert_eq!(replay.ticks[0].time_ms, 0);
+        assert_eq!(replay.duration_ms, 30_000);
+        assert!(
+            replay
+                .events
+                .iter()
+                .any(|e| e.time_ms == 25_050 && matches!(e.event, GameEvent::Pickup { .. }))
+        );
+    }
+}
@@ -2,10 +2,29 @@
 
 use std::path::Path;
 
+use super::stream::{STREAM_MIN_TICKS, TickStream};
 use crate::replay::ReplayData;
 use crate::simulation::SimDatabase;
 
 pub fn load_replay_from_db(db_path: &Path, match_id: i64) -> Result<ReplayData, String> {
     let db = SimDatabase::open(db_path).map_err(|e| e.to_string())?;
-    db.load_replay_data(match_id)
+    let mut replay = db.load_replay_data(match_id)?;
+    replay.build_keyframes();
+    Ok(replay)
+}
+
+/// Like `load_replay_from_db`, but long matches stream their ticks (see `stream`)
+/// instead of decoding them all up front.
+pub fn load_replay_windowed(db_path: &Path, match_id: i64) -> Result<ReplayData, String> {
+    let db = SimDatabase::open(db_path).map_err(|e| e.to_string())?;
+    let ticks = db.tick_count(match_id).map_err(|e| e.to_string())?;
+    let mut replay = if ticks <= STREAM_MIN_TICKS {
+        db.load_replay_data(match_id)?
+    } else {
+        let mut replay = db.load_replay_events(match_id)?;
+        replay.stream = Some(TickStream::open(db_path, match_id)?);
+        replay
+    };
+    replay.build_keyframes();
+    Ok(replay)
 }
@@ -2,18 +2,34 @@
 
 use bevy::prelude::*;
 
-/// Available playback speeds
-pub const PLAYBACK_SPEEDS: [f32; 5] = [0.25, 0.5, 1.0, 2.0, 4.0];
+/// Available playback speeds (number keys 1-7 select them directly)
+pub const PLAYBACK_SPEEDS: [f32; 7] = [0.1, 0.25, 0.5, 1.0, 2.0, 4.0, 8.0];
+
+/// Index of 1.0x in PLAYBACK_SPEEDS
+const NORMAL_SPEED_INDEX: usize = 3;
+
+/// Top speed while fast-forward is held
+pub const FAST_FORWARD_MAX_SPEED: f32 = 8.0;
+
+/// Fast-forward speed doubles every this many seconds while held
+const FAST_FORWARD_DOUBLING_SECS: f32 = 0.25;
+
+/// Length of one frame step (one tick)
+const STEP_MS: u32 = 50;
 
 /// Replay playback state
 #[derive(Resource)]
 pub struct ReplayState {
     /// Current playback time in milliseconds
     pub current_time_ms: u32,
-    /// Playback speed multiplier (0.25, 0.5, 1.0, 2.0, 4.0)
+    /// Playback speed multiplier (one of PLAYBACK_SPEEDS)
     pub playback_speed: f32,
+    /// Ramping speed while fast-forward is held (overrides playback_speed and pause)
+    pub fast_forward: Option<f32>,
     /// Whether playback is paused
     pub is_paused: bool,
+    /// Whether playback runs backwards (from keyframes, no re-simulation)
+    pub reverse: bool,
     /// Whether we're in frame-stepping mode
     pub is_stepping: bool,
     /// Index of current speed in PLAYBACK_SPEEDS
@@ -27,9 +43,11 @@ impl Default for ReplayState {
         Self {
             current_time_ms: 0,
             playback_speed: 1.0,
+            fast_forward: None,
             is_paused: false,
+            reverse: false,
             is_stepping: false,
-            speed_index: 2, // 1.0x
+            speed_index: NORMAL_SPEED_INDEX,
             finished: false,
         }
     }
@@ -52,25 +70,78 @@ impl ReplayState {
         }
     }
 
+    /// Jump straight to a speed preset (ignored if out of range)
+    pub fn set_speed_index(&mut self, index: usize) {
+        if let Some(&speed) = PLAYBACK_SPEEDS.get(index) {
+            self.speed_index = index;
+            self.playback_speed = speed;
+        }
+    }
+
+    /// Hold-to-fast-forward: starts just above the current speed and doubles
+    /// every FAST_FORWARD_DOUBLING_SECS up to FAST_FORWARD_MAX_SPEED
+    pub fn hold_fast_forward(&mut self, delta_secs: f32) {
+        let speed = match self.fast_forward {
+            Some(speed) => speed * 2f32.powf(delta_secs / FAST_FORWARD_DOUBLING_SECS),
+            None => (self.playback_speed * 2.0).max(1.0),
+        };
+        self.fast_forward = Some(speed.min(FAST_FORWARD_MAX_SPEED));
+    }
+
+    /// Release fast-forward, back to the selected speed
+    pub fn release_fast_forward(&mut self) {
+        self.fast_forward = None;
+    }
+
+    /// Speed playback advances at this frame
+    pub fn effective_speed(&self) -> f32 {
+        self.fast_forward.unwrap_or(self.playback_speed)
+    }
+
     /// Toggle pause state
     pub fn toggle_pause(&mut self) {
         self.is_paused = !self.is_paused;
     }
 
-    /// Step forward one tick (50ms)
-    pub fn step_forward(&mut self) {
-        if self.is_paused {
-            self.is_stepping = true;
-            self.current_time_ms = self.current_time_ms.saturating_add(50);
+    /// Toggle reverse playback (unpauses, so the toggle is visible at once)
+    pub fn toggle_reverse(&mut self) {
+        self.reverse = !self.reverse;
+        self.is_paused = false;
+        self.finished = false;
+    }
+
+    /// Advance the playback clock by `delta_ms` in the current direction,
+    /// pausing at either end of the replay
+    pub fn advance(&mut self, delta_ms: u32, duration_ms: u32) {
+        if self.reverse {
+            self.current_time_ms = self.current_time_ms.saturating_sub(delta_ms);
+            if self.current_time_ms == 0 {
+                self.is_paused = true;
+            }
+        } else {
+            self.current_time_ms = self.current_time_ms.saturating_add(delta_ms);
+            if self.current_time_ms >= duration_ms {
+                self.current_time_ms = duration_ms;
+                self.finished = true;
+                self.is_paused = true;
+            }
         }
     }
 
-    /// Step backward one tick (50ms)
+    /// Step forward one tick (pauses first if playing)
+    pub fn step_forward(&mut self, duration_ms: u32) {
+        self.is_paused = true;
+        self.is_stepping = true;
+        self.current_time_ms = (self.current_time_ms + STEP_MS).min(duration_ms);
+        self.finished = self.current_time_ms >= duration_ms;
+    }
+
+    /// Step backward one tick (pauses first if playing)
     pub fn step_backward(&mut self) {
-        if self.is_paused {
-            self.is_stepping = true;
-            self.current_time_ms = self.current_time_ms.saturating_sub(50);
-        }
+        self.is_paused = true;
+        self.is_stepping = true;
+        self.current_time_ms = self.current_time_ms.saturating_sub(STEP_MS);
+        self.finished = false;
     }
 
     /// Jump to start
@@ -93,8 +164,13 @@ impl ReplayState {
 
     /// Get formatted speed string for display
     pub fn speed_string(&self) -> String {
-        if self.is_paused {
+        let arrows = if self.reverse { "<<" } else { ">>" };
+        if let Some(speed) = self.fast_forward {
+            format!("{} {:.2}x", arrows, speed)
+        } else if self.is_paused {
             "PAUSED".to_string()
+        } else if self.reverse {
+            format!("<< {:.2}x", self.playback_speed)
         } else {
             format!("{:.2}x", self.playback_speed)
         }
@@ -107,3 +183,50 @@ impl ReplayState {
         format!("{:.1}s / {:.1}s", current_secs, total_secs)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_speed_presets_fast_forward_and_stepping() {
+        let mut state = ReplayState::default();
+        assert_eq!(state.playback_speed, PLAYBACK_SPEEDS[state.speed_index]);
+        state.set_speed_index(0);
+        assert_eq!(state.playback_speed, 0.1);
+        state.set_speed_index(PLAYBACK_SPEEDS.len());
+        assert_eq!(state.playback_speed, 0.1);
+        state.set_speed_index(6);
+        state.speed_up();
+        assert_eq!(state.playback_speed, 8.0);
+
+        // Ramps from the selected speed to the cap, then releases back to it
+        state.set_speed_index(NORMAL_SPEED_INDEX);
+        state.hold_fast_forward(0.0);
+        assert_eq!(state.effective_speed(), 2.0);
+        state.hold_fast_forward(FAST_FORWARD_DOUBLING_SECS);
+        assert!((state.effective_speed() - 4.0).abs() < 1e-4);
+        state.hold_fast_forward(10.0);
+        assert_eq!(state.effective_speed(), FAST_FORWARD_MAX_SPEED);
+        state.release_fast_forward();
+        assert_eq!(state.effective_speed(), 1.0);
+
+        // Stepping pauses and clamps to the replay length
+        state.current_time_ms = 980;
+        state.step_forward(1000);
+        assert!(state.is_paused && state.finished);
+        assert_eq!(state.current_time_ms, 1000);
+        state.step_backward();
+        assert_eq!(state.current_time_ms, 950);
+        assert!(!state.finished);
+
+        // Reverse playback runs the clock back and stops at the start
+        state.toggle_reverse();
+        assert!(!state.is_paused);
+        state.advance(900, 1000);
+        assert_eq!(state.current_time_ms, 50);
+        assert_eq!(state.speed_string(), "<< 1.00x");
+        state.advance(900, 1000);
+        assert!(state.current_time_ms == 0 && state.is_paused);
+    }
+}
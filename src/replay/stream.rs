@@ -0,0 +1,191 @@
+//! Windowed tick loading for long replays.
+//!
+//! Matches longer than STREAM_MIN_TICKS keep their ticks in SQLite. The
+//! timeline is cut into SEGMENT_MS windows, and a window is decoded the first
+//! time playback (or a seek) needs it. At most MAX_CACHED_SEGMENTS decoded
+//! windows stay in memory, and the least recently used one is dropped first.
+//! Events are small and stay fully loaded.
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use super::data::TickFrame;
+use crate::simulation::SimDatabase;
+
+/// Timeline length of one decoded segment (600 ticks at 20 Hz)
+pub const SEGMENT_MS: u32 = 30_000;
+
+/// Decoded segments kept in memory (~4 minutes of ticks)
+pub const MAX_CACHED_SEGMENTS: usize = 8;
+
+/// Matches with more ticks than this stream instead of loading up front (30 min)
+pub const STREAM_MIN_TICKS: u64 = 36_000;
+
+/// Tick frames of one replay, loaded a segment at a time
+pub struct TickStream {
+    db: Mutex<SimDatabase>,
+    match_id: i64,
+    /// Time of the match's last tick (lookups past it clamp here)
+    last_tick_ms: u32,
+    /// Most recently used last
+    cache: Mutex<Vec<(u32, Arc<Vec<TickFrame>>)>>,
+}
+
+impl TickStream {
+    pub fn open(db_path: &Path, match_id: i64) -> Result<Self, String> {
+        let db = SimDatabase::open(db_path).map_err(|e| e.to_string())?;
+        let last_tick_ms = db
+            .last_tick_ms(match_id)
+            .map_err(|e| e.to_string())?
+            .unwrap_or(0);
+        Ok(Self {
+            db: Mutex::new(db),
+            match_id,
+            last_tick_ms,
+            cache: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Ticks in segment `index` (decoded on first use)
+    fn segment(&self, index: u32) -> Arc<Vec<TickFrame>> {
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(pos) = cache.iter().position(|(i, _)| *i == index) {
+            let entry = cache.remove(pos);
+            let ticks = entry.1.clone();
+            cache.push(entry);
+            return ticks;
+        }
+
+        let start_ms = index * SEGMENT_MS;
+        let ticks = self
+            .db
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .load_tick_range(self.match_id, start_ms, start_ms + SEGMENT_MS)
+            .unwrap_or_else(|e| {
+                bevy::log::warn!("Replay segment at {}ms failed to load: {}", start_ms, e);
+                Vec::new()
+            });
+        let ticks = Arc::new(ticks);
+        if cache.len() >= MAX_CACHED_SEGMENTS {
+            cache.remove(0);
+        }
+        cache.push((index, ticks.clone()));
+        ticks
+    }
+
+    /// Segments currently decoded
+    pub fn cached_segments(&self) -> usize {
+        self.cache.lock().map(|c| c.len()).unwrap_or(0)
+    }
+
+    pub fn first(&self) -> Option<TickFrame> {
+        self.segment(0).first().cloned()
+    }
+
+    /// Same contract as `ReplayData::find_bracket`, across segment edges
+    pub fn find_bracket(&self, time_ms: u32) -> Option<(TickFrame, TickFrame, f32)> {
+        let time_ms = time_ms.min(self.last_tick_ms);
+        let index = time_ms / SEGMENT_MS;
+        let ticks = self.segment(index);
+        let idx = ticks.partition_point(|t| t.time_ms <= time_ms);
+
+        let prev = match idx {
+            0 if index > 0 => self.segment(index - 1).last().cloned(),
+            0 => None,
+            _ => Some(ticks[idx - 1].clone()),
+        };
+        let next = match ticks.get(idx) {
+            Some(next) => Some(next.clone()),
+            None => self.segment(index + 1).first().cloned(),
+        };
+
+        match (prev, next) {
+            (Some(prev), Some(next)) => {
+                let t = if next.time_ms > prev.time_ms {
+                    (time_ms - prev.time_ms) as f32 / (next.time_ms - prev.time_ms) as f32
+                } else {
+                    0.0
+                };
+                Some((prev, next, t))
+            }
+            // Before the first tick / after the last
+            (None, Some(first)) => Some((first.clone(), first, 0.0)),
+            (Some(last), None) => Some((last.clone(), last, 1.0)),
+            (None, None) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::GameEvent;
+    use crate::simulation::{MatchResult, PlayerStats};
+    use bevy::prelude::Vec2;
+
+    #[test]
+    fn test_stream_brackets_across_segments_and_caps_cache() {
+        let path = std::env::temp_dir().join(format!("replay_stream_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let db = SimDatabase::open(&path).unwrap();
+        let session = db.create_session("test", None).unwrap();
+        let result = MatchResult {
+            level: 1,
+            level_name: "Test".to_string(),
+            left_profile: "A".to_string(),
+            right_profile: "B".to_string(),
+            duration: 0.0,
+            score_left: 0,
+            score_right: 0,
+            winner: "tie".to_string(),
+            left_stats: PlayerStats::default(),
+            right_stats: PlayerStats::default(),
+            seed: 1,
+            degenerate: None,
+            events: Vec::new(),
+        };
+        let match_id = db.insert_match(&session, &result).unwrap();
+        // One tick per second for 10 segments
+        let events: Vec<(u32, GameEvent)> = (0..SEGMENT_MS * 10 / 1000)
+            .map(|s| {
+                let tick = TickFrame {
+                    time_ms: s * 1000,
+                    frame: s as u64,
+                    left_pos: Vec2::new(s as f32, 0.0),
+                    left_vel: Vec2::ZERO,
+                    right_pos: Vec2::ZERO,
+                    right_vel: Vec2::ZERO,
+                    ball_pos: Vec2::ZERO,
+                    ball_vel: Vec2::ZERO,
+                    ball_state: 'F',
+                };
+                (tick.time_ms, tick.to_event())
+            })
+            .collect();
+        db.insert_events_with_points(match_id, 300.0, &events)
+            .unwrap();
+
+        let stream = TickStream::open(&path, match_id).unwrap();
+        assert_eq!(stream.first().unwrap().time_ms, 0);
+
+        // Last tick of segment 0 to first tick of segment 1
+        let (prev, next, t) = stream.find_bracket(SEGMENT_MS - 500).unwrap();
+        assert_eq!(prev.time_ms, SEGMENT_MS - 1000);
+        assert_eq!(next.time_ms, SEGMENT_MS);
+        assert!((t - 0.5).abs() < 1e-5);
+
+        // Past the end clamps to the last tick
+        let (last, _, t) = stream.find_bracket(SEGMENT_MS * 20).unwrap();
+        assert_eq!(last.time_ms, SEGMENT_MS * 10 - 1000);
+        assert_eq!(t, 1.0);
+
+        for index in 0..10 {
+            stream.find_bracket(index * SEGMENT_MS + 100);
+        }
+        assert_eq!(stream.cached_segments(), MAX_CACHED_SEGMENTS);
+
+        drop(stream);
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -35,6 +35,17 @@ pub fn replay_setup(
     replay_data: Res<ReplayData>,
     level_db: Res<LevelDatabase>,
     mut current_level: ResMut<CurrentLevel>,
+) {
+    spawn_replay_world(&mut commands, &replay_data, &level_db, &mut current_level);
+}
+
+/// Spawn players, ball, arena and level geometry for a replay (also used when
+/// a playlist moves to its next match)
+pub(crate) fn spawn_replay_world(
+    commands: &mut Commands,
+    replay_data: &ReplayData,
+    level_db: &LevelDatabase,
+    current_level: &mut CurrentLevel,
 ) {
     info!(
         "Setting up replay: level {}, profiles {} vs {}",
@@ -55,9 +66,13 @@ pub fn replay_setup(
                 .map(|l| l.id.clone())
                 .unwrap_or_default()
         });
+    let arena = level_db
+        .get_by_id(&current_level.0)
+        .map(|l| l.arena())
+        .unwrap_or_default();
 
     // Get initial positions from first tick (or use defaults)
-    let (left_pos, right_pos, ball_pos) = if let Some(first) = replay_data.ticks.first() {
+    let (left_pos, right_pos, ball_pos) = if let Some(first) = replay_data.first_tick() {
         (first.left_pos, first.right_pos, first.ball_pos)
     } else {
         (
@@ -113,10 +128,10 @@ pub fn replay_setup(
     commands.spawn((
         Sprite {
             color: Color::srgb(0.3, 0.3, 0.3),
-            custom_size: Some(Vec2::new(ARENA_WIDTH - WALL_THICKNESS * 2.0, 40.0)),
+            custom_size: Some(Vec2::new(arena.width - WALL_THICKNESS * 2.0, 40.0)),
             ..default()
         },
-        Transform::from_xyz(0.0, ARENA_FLOOR_Y, 0.0),
+        Transform::from_xyz(0.0, arena.floor_y, 0.0),
         Platform,
     ));
 
@@ -127,7 +142,7 @@ pub fn replay_setup(
             custom_size: Some(Vec2::new(WALL_THICKNESS, 5000.0)),
             ..default()
         },
-        Transform::from_xyz(-ARENA_WIDTH / 2.0 + WALL_THICKNESS / 2.0, 2000.0, 0.0),
+        Transform::from_xyz(-arena.width / 2.0 + WALL_THICKNESS / 2.0, 2000.0, 0.0),
         Platform,
     ));
     commands.spawn((
@@ -136,7 +151,7 @@ pub fn replay_setup(
             custom_size: Some(Vec2::new(WALL_THICKNESS, 5000.0)),
             ..default()
         },
-        Transform::from_xyz(ARENA_WIDTH / 2.0 - WALL_THICKNESS / 2.0, 2000.0, 0.0),
+        Transform::from_xyz(arena.width / 2.0 - WALL_THICKNESS / 2.0, 2000.0, 0.0),
         Platform,
     ));
 
@@ -154,7 +169,7 @@ pub fn replay_setup(
                             custom_size: Some(Vec2::new(*width, 20.0)),
                             ..default()
                         },
-                        Transform::from_xyz(-x, ARENA_FLOOR_Y + y, 0.0),
+                        Transform::from_xyz(-x, arena.floor_y + y, 0.0),
                         Platform,
                         Collider,
                     ));
@@ -165,7 +180,7 @@ pub fn replay_setup(
                             custom_size: Some(Vec2::new(*width, 20.0)),
                             ..default()
                         },
-                        Transform::from_xyz(*x, ARENA_FLOOR_Y + y, 0.0),
+                        Transform::from_xyz(*x, arena.floor_y + y, 0.0),
                         Platform,
                         Collider,
                     ));
@@ -177,19 +192,18 @@ pub fn replay_setup(
                             custom_size: Some(Vec2::new(*width, 20.0)),
                             ..default()
                         },
-                        Transform::from_xyz(0.0, ARENA_FLOOR_Y + y, 0.0),
+                        Transform::from_xyz(0.0, arena.floor_y + y, 0.0),
                         Platform,
                         Collider,
                     ));
                 }
+                feature => crate::levels::spawn_level_feature(commands, feature, arena.floor_y),
             }
         }
 
         // Spawn baskets
-        let basket_y = ARENA_FLOOR_Y + level.basket_height;
-        let wall_inner = ARENA_WIDTH / 2.0 - WALL_THICKNESS;
-        let left_basket_x = -wall_inner + level.basket_push_in;
-        let right_basket_x = wall_inner - level.basket_push_in;
+        let basket_y = arena.floor_y + level.basket_height;
+        let (left_basket_x, right_basket_x) = arena.basket_x_from_offset(level.basket_push_in);
 
         commands.spawn((
             Sprite {
@@ -220,23 +234,17 @@ pub fn replay_playback(
     mut players: Query<(&mut Transform, &Team), With<Player>>,
     mut ball: Query<(&mut Transform, &mut BallState), (With<Ball>, Without<Player>)>,
 ) {
-    // Don't advance if paused (unless stepping)
-    if state.is_paused && !state.is_stepping {
+    // Don't advance if paused (unless stepping or fast-forwarding)
+    let advancing = !state.is_paused || state.fast_forward.is_some();
+    if !advancing && !state.is_stepping {
         return;
     }
     state.is_stepping = false;
 
-    // Advance time
-    if !state.is_paused {
-        let delta_ms = (time.delta_secs() * 1000.0 * state.playback_speed) as u32;
-        state.current_time_ms = state.current_time_ms.saturating_add(delta_ms);
-
-        // Check if we've reached the end
-        if state.current_time_ms >= replay_data.duration_ms {
-            state.current_time_ms = replay_data.duration_ms;
-            state.finished = true;
-            state.is_paused = true;
-        }
+    // Advance time (backwards in reverse; pauses at either end)
+    if advancing {
+        let delta_ms = (time.delta_secs() * 1000.0 * state.effective_speed()) as u32;
+        state.advance(delta_ms, replay_data.duration_ms);
     }
 
     // Find bracket for interpolation
@@ -309,9 +317,21 @@ pub fn replay_playback(
     }
 }
 
+/// Number keys selecting PLAYBACK_SPEEDS presets, slowest first
+const SPEED_PRESET_KEYS: [KeyCode; 7] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+];
+
 /// Input handler for replay controls
 pub fn replay_input_handler(
     keyboard: Res<ButtonInput<KeyCode>>,
+    time: Res<Time<Real>>,
     replay_data: Res<ReplayData>,
     mut state: ResMut<ReplayState>,
 ) {
@@ -328,16 +348,61 @@ pub fn replay_input_handler(
         state.speed_down();
     }
 
-    // Period (.): Step forward one tick (when paused)
+    // 1-7: Speed presets (0.1x to 8x)
+    for (index, key) in SPEED_PRESET_KEYS.into_iter().enumerate() {
+        if keyboard.just_pressed(key) {
+            state.set_speed_index(index);
+        }
+    }
+
+    // F (held): Fast-forward, ramping up to 8x; back to the selected speed on release
+    if keyboard.pressed(KeyCode::KeyF) {
+        state.hold_fast_forward(time.delta_secs());
+    } else if state.fast_forward.is_some() {
+        state.release_fast_forward();
+    }
+
+    // Period (.): Step forward one tick (pauses playback)
     if keyboard.just_pressed(KeyCode::Period) {
-        state.step_forward();
+        state.step_forward(replay_data.duration_ms);
     }
 
-    // Comma (,): Step backward one tick (when paused)
+    // Comma (,): Step backward one tick (pauses playback)
     if keyboard.just_pressed(KeyCode::Comma) {
         state.step_backward();
     }
 
+    // ]/[: Jump to next/previous key event (goal, pickup, shot, steal)
+    if keyboard.just_pressed(KeyCode::BracketRight)
+        && let Some(time_ms) = replay_data.next_key_event_ms(state.current_time_ms)
+    {
+        state.seek_to(time_ms, replay_data.duration_ms);
+    }
+    if keyboard.just_pressed(KeyCode::BracketLeft) {
+        let time_ms = replay_data
+            .prev_key_event_ms(state.current_time_ms)
+            .unwrap_or(0);
+        state.seek_to(time_ms, replay_data.duration_ms);
+    }
+
+    // R: Toggle reverse playback
+    if keyboard.just_pressed(KeyCode::KeyR) {
+        state.toggle_reverse();
+    }
+
+    // PageDown/PageUp: Seek to the next/previous keyframe (5s apart)
+    if keyboard.just_pressed(KeyCode::PageDown)
+        && let Some(time_ms) = replay_data.next_keyframe_ms(state.current_time_ms)
+    {
+        state.seek_to(time_ms, replay_data.duration_ms);
+    }
+    if keyboard.just_pressed(KeyCode::PageUp) {
+        let time_ms = replay_data
+            .prev_keyframe_ms(state.current_time_ms)
+            .unwrap_or(0);
+        state.seek_to(time_ms, replay_data.duration_ms);
+    }
+
     // Home: Jump to start
     if keyboard.just_pressed(KeyCode::Home) {
         state.jump_to_start();
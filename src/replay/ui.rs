@@ -3,12 +3,16 @@
 use bevy::prelude::*;
 
 use crate::constants::*;
-use crate::events::{GameEvent, PlayerId};
+use crate::events::{GameEvent, PlayerId, TimeBase};
 use crate::player::Team;
 
 use super::ReplayData;
 use super::state::ReplayState;
 
+/// Help footer listing the bindings handled by `replay_input_handler`
+const CONTROLS_HELP: &str = "SPACE: pause | R: reverse | </>: speed | 1-7: 0.1x-8x | hold F: fast-forward\n\
+                             ,/.: step | [/]: prev/next event | PgUp/PgDn: -/+5s | Home/End: jump | P: focus | M: markers | H: heatmap";
+
 /// Timeline bar at the bottom of screen
 #[derive(Component)]
 pub struct ReplayTimeline;
@@ -30,6 +34,8 @@ pub struct ReplaySpeedDisplay;
 pub struct ReplayEventMarker {
     pub time_ms: u32,
     pub event_type: EventMarkerType,
+    /// Player the event belongs to (used by the replay focus filter)
+    pub player: Option<PlayerId>,
 }
 
 /// Type of event marker for coloring
@@ -42,6 +48,26 @@ pub enum EventMarkerType {
 }
 
 impl EventMarkerType {
+    pub const ALL: [EventMarkerType; 4] = [
+        EventMarkerType::Goal,
+        EventMarkerType::Steal,
+        EventMarkerType::Pickup,
+        EventMarkerType::AiGoal,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventMarkerType::Goal => "goal",
+            EventMarkerType::Steal => "steal",
+            EventMarkerType::Pickup => "pickup",
+            EventMarkerType::AiGoal => "ai_goal",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.as_str() == s)
+    }
+
     pub fn color(&self) -> Color {
         match self {
             EventMarkerType::Goal => Color::srgb(1.0, 0.84, 0.0), // Gold
@@ -60,11 +86,19 @@ pub struct PlayerGoalLabel(pub Team);
 #[derive(Component)]
 pub struct ReplayControlsText;
 
+/// Match info line (top-left)
+#[derive(Component)]
+pub struct ReplayMatchInfoText;
+
+const TIMELINE_Y: f32 = ARENA_FLOOR_Y - 60.0;
+const TIMELINE_WIDTH: f32 = ARENA_WIDTH - 100.0;
+const TIMELINE_HEIGHT: f32 = 8.0;
+
 /// Setup the replay UI (called once when replay starts)
 pub fn setup_replay_ui(mut commands: Commands, replay_data: Res<ReplayData>) {
-    let timeline_y = ARENA_FLOOR_Y - 60.0;
-    let timeline_width = ARENA_WIDTH - 100.0;
-    let timeline_height = 8.0;
+    let timeline_y = TIMELINE_Y;
+    let timeline_width = TIMELINE_WIDTH;
+    let timeline_height = TIMELINE_HEIGHT;
 
     // Timeline background (dark bar)
     commands.spawn((
@@ -88,38 +122,7 @@ pub fn setup_replay_ui(mut commands: Commands, replay_data: Res<ReplayData>) {
         ReplayTimelineProgress,
     ));
 
-    // Event markers on timeline
-    let duration = replay_data.duration_ms as f32;
-    if duration > 0.0 {
-        for event in &replay_data.events {
-            let marker_type = match &event.event {
-                GameEvent::Goal { .. } => Some(EventMarkerType::Goal),
-                GameEvent::StealSuccess { .. } | GameEvent::StealFail { .. } => {
-                    Some(EventMarkerType::Steal)
-                }
-                GameEvent::Pickup { .. } => Some(EventMarkerType::Pickup),
-                GameEvent::AiGoal { .. } => Some(EventMarkerType::AiGoal),
-                _ => None,
-            };
-
-            if let Some(marker_type) = marker_type {
-                let x_offset =
-                    (event.time_ms as f32 / duration) * timeline_width - timeline_width / 2.0;
-                commands.spawn((
-                    Sprite {
-                        color: marker_type.color(),
-                        custom_size: Some(Vec2::new(3.0, timeline_height + 4.0)),
-                        ..default()
-                    },
-                    Transform::from_xyz(x_offset, timeline_y, 12.0),
-                    ReplayEventMarker {
-                        time_ms: event.time_ms,
-                        event_type: marker_type,
-                    },
-                ));
-            }
-        }
-    }
+    spawn_event_markers(&mut commands, &replay_data);
 
     // Time display (top-right)
     commands.spawn((
@@ -147,13 +150,13 @@ pub fn setup_replay_ui(mut commands: Commands, replay_data: Res<ReplayData>) {
 
     // Controls help text
     commands.spawn((
-        Text2d::new("SPACE: pause | </>: speed | ,/.: step | Home/End: jump"),
+        Text2d::new(CONTROLS_HELP),
         TextFont {
             font_size: 12.0,
             ..default()
         },
         TextColor(TEXT_SECONDARY),
-        Transform::from_xyz(0.0, timeline_y - 20.0, 10.0),
+        Transform::from_xyz(0.0, timeline_y - 28.0, 10.0),
         ReplayControlsText,
     ));
 
@@ -183,15 +186,8 @@ pub fn setup_replay_ui(mut commands: Commands, replay_data: Res<ReplayData>) {
     ));
 
     // Match info display (top-left)
-    let info_text = format!(
-        "{} vs {} on {} (seed: {})",
-        replay_data.match_info.left_profile,
-        replay_data.match_info.right_profile,
-        replay_data.match_info.level_name,
-        replay_data.match_info.seed
-    );
     commands.spawn((
-        Text2d::new(info_text),
+        Text2d::new(match_info_text(&replay_data)),
         TextFont {
             font_size: 14.0,
             ..default()
@@ -199,9 +195,66 @@ pub fn setup_replay_ui(mut commands: Commands, replay_data: Res<ReplayData>) {
         TextLayout::new_with_justify(Justify::Left),
         TextColor(TEXT_PRIMARY),
         Transform::from_xyz(-ARENA_WIDTH / 2.0 + 100.0, ARENA_HEIGHT / 2.0 - 30.0, 10.0),
+        ReplayMatchInfoText,
     ));
 }
 
+/// "Left vs Right on Level (seed: N)", with team names when recorded
+pub fn match_info_text(replay_data: &ReplayData) -> String {
+    let info = &replay_data.match_info;
+    let side = |profile: &str, team: &str| {
+        if team.is_empty() {
+            profile.to_string()
+        } else {
+            format!("{} ({})", team, profile)
+        }
+    };
+    format!(
+        "{} vs {} on {} (seed: {})",
+        side(&info.left_profile, &info.teams.left_name),
+        side(&info.right_profile, &info.teams.right_name),
+        info.level_name,
+        info.seed
+    )
+}
+
+/// Timeline markers for goals, steals, pickups and AI goals
+pub fn spawn_event_markers(commands: &mut Commands, replay_data: &ReplayData) {
+    let duration = replay_data.duration_ms as f32;
+    if duration <= 0.0 {
+        return;
+    }
+    for event in &replay_data.events {
+        let marker = match &event.event {
+            GameEvent::Goal { player, .. } => Some((EventMarkerType::Goal, *player)),
+            GameEvent::StealSuccess { attacker } | GameEvent::StealFail { attacker } => {
+                Some((EventMarkerType::Steal, *attacker))
+            }
+            GameEvent::Pickup { player } => Some((EventMarkerType::Pickup, *player)),
+            GameEvent::AiGoal { player, .. } => Some((EventMarkerType::AiGoal, *player)),
+            _ => None,
+        };
+
+        if let Some((marker_type, player)) = marker {
+            let x_offset =
+                (event.time_ms as f32 / duration) * TIMELINE_WIDTH - TIMELINE_WIDTH / 2.0;
+            commands.spawn((
+                Sprite {
+                    color: marker_type.color(),
+                    custom_size: Some(Vec2::new(3.0, TIMELINE_HEIGHT + 4.0)),
+                    ..default()
+                },
+                Transform::from_xyz(x_offset, TIMELINE_Y, 12.0),
+                ReplayEventMarker {
+                    time_ms: event.time_ms,
+                    event_type: marker_type,
+                    player: Some(player),
+                },
+            ));
+        }
+    }
+}
+
 /// Update the replay UI each frame
 pub fn update_replay_ui(
     state: Res<ReplayState>,
@@ -228,7 +281,19 @@ pub fn update_replay_ui(
 ) {
     // Update time display
     for mut text in &mut time_display {
-        **text = state.time_string(replay_data.duration_ms);
+        let (left, right) = replay_data.score_at(state.current_time_ms);
+        // Legacy matches count countdowns and pauses in their timeline
+        let clock = match replay_data.time_base {
+            TimeBase::Game => "",
+            TimeBase::Wall => "  (wall clock)",
+        };
+        **text = format!(
+            "{}  {}-{}{}",
+            state.time_string(replay_data.duration_ms),
+            left,
+            right,
+            clock
+        );
     }
 
     // Update speed display
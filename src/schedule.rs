@@ -0,0 +1,50 @@
+//! System sets for the match loop
+//!
+//! Mods and embedding hosts order their own systems against these sets instead
+//! of copying the `.chain()` blocks out of the binaries. Each app attaches its
+//! own run conditions (countdown, replay, match over) to the sets, so anything
+//! added to a set pauses along with the built-in systems.
+//!
+//! Ordering (set up by `MatchSetsPlugin`):
+//! - Update: `InputSet` (capture devices, copy into InputState, control swaps)
+//!   then `AiSet` (nav graph, navigation, decisions, mistakes)
+//! - FixedUpdate: `PhysicsSet` (input -> gravity -> velocity -> collisions ->
+//!   ball handling -> shooting) then `ScoringSet` (goals, resets, match rules)
+//! - PostUpdate: `LoggingSet` (EventBus export to SQLite or the host)
+//!
+//! ```ignore
+//! app.add_systems(FixedUpdate, gust_of_wind.in_set(PhysicsSet).before(apply_velocity));
+//! app.add_systems(FixedUpdate, bonus_points.after(ScoringSet));
+//! ```
+
+use bevy::prelude::*;
+
+/// Human device input and copying it into players' InputState
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InputSet;
+
+/// AI navigation and decisions (writes InputState for AI players)
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AiSet;
+
+/// Player and ball physics, pickups, steals and shooting
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PhysicsSet;
+
+/// Scoring, ball resets and match-rule checks (after physics)
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ScoringSet;
+
+/// Event export at the end of the frame
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LoggingSet;
+
+/// Orders the match sets. Run conditions are left to the app.
+pub struct MatchSetsPlugin;
+
+impl Plugin for MatchSetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.configure_sets(Update, (InputSet, AiSet).chain());
+        app.configure_sets(FixedUpdate, (PhysicsSet, ScoringSet).chain());
+    }
+}
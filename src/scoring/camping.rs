@@ -0,0 +1,141 @@
+//! Rim camping - defenders parked in their own basket's mouth
+//!
+//! Covering the rim blocks carry-ins (see `carry_in`), so a defender who never
+//! leaves their basket shuts the 2-pointer down for good. `RimCamping` times
+//! each side's unbroken stay on the rim. A stay that reaches RIM_CAMPING_SECS is
+//! logged once as a `RimCamping` event and counted for that side. With the
+//! defensive three-seconds rule (`--rim-camping-violation`, off by default) it
+//! is also a violation: the camper's coverage stops blocking carry-ins until
+//! they step off the rim. AI defenders leave on their own after
+//! RIM_CAMPING_AI_LEAVE_SECS unless a carry-in is coming.
+
+use bevy::prelude::*;
+
+use super::carry_in::defending_team;
+use crate::constants::*;
+use crate::events::GameEvent;
+use crate::player::Team;
+use crate::world::Basket;
+
+/// Time defenders have spent on their own rim, and how often they camped
+#[derive(Resource, Debug, Clone, Default)]
+pub struct RimCamping {
+    /// Defensive three seconds: camping voids the defender's rim coverage
+    pub enabled: bool,
+    /// Seconds of the current unbroken stay (left basket, right basket)
+    seconds: [f32; 2],
+    /// The current stay already reached RIM_CAMPING_SECS
+    camped: [bool; 2],
+    /// Stays that reached RIM_CAMPING_SECS, per defending side (left, right)
+    pub occurrences: [u32; 2],
+    /// Of those, stays called as violations (rule on)
+    pub violations: [u32; 2],
+}
+
+impl RimCamping {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..default()
+        }
+    }
+
+    fn index(basket: Basket) -> usize {
+        match basket {
+            Basket::Left => 0,
+            Basket::Right => 1,
+        }
+    }
+
+    /// Advance `dt` seconds: `covered` is whether a defender is on the rim now.
+    /// Returns the event when the current stay first reaches RIM_CAMPING_SECS.
+    pub fn update(&mut self, basket: Basket, covered: bool, dt: f32) -> Option<GameEvent> {
+        let i = Self::index(basket);
+        if !covered {
+            self.seconds[i] = 0.0;
+            self.camped[i] = false;
+            return None;
+        }
+        self.seconds[i] += dt;
+        if self.camped[i] || self.seconds[i] < RIM_CAMPING_SECS {
+            return None;
+        }
+        self.camped[i] = true;
+        self.occurrences[i] += 1;
+        if self.enabled {
+            self.violations[i] += 1;
+        }
+        Some(GameEvent::RimCamping {
+            player: defending_team(basket).player_id(),
+            violation: self.enabled,
+        })
+    }
+
+    /// Is the defender on this basket in violation (coverage doesn't block)?
+    pub fn voids(&self, basket: Basket) -> bool {
+        self.enabled && self.camped[Self::index(basket)]
+    }
+
+    /// Seconds of the current stay on this basket's rim
+    pub fn seconds_on_rim(&self, basket: Basket) -> f32 {
+        self.seconds[Self::index(basket)]
+    }
+
+    /// (camping stays, violations) for a defending side
+    pub fn counts(&self, team: Team) -> (u32, u32) {
+        let i = match team {
+            Team::Left => 0,
+            Team::Right => 1,
+        };
+        (self.occurrences[i], self.violations[i])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::PlayerId;
+
+    #[test]
+    fn test_camping_logged_once_per_stay() {
+        let dt = 1.0 / 60.0;
+        let mut camping = RimCamping::new(false);
+        let mut events = Vec::new();
+        for _ in 0..(RIM_CAMPING_SECS * 2.0 / dt) as u32 {
+            events.extend(camping.update(Basket::Left, true, dt));
+        }
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            events[0],
+            GameEvent::RimCamping {
+                player: PlayerId::L,
+                violation: false
+            }
+        ));
+        // Rule off: camping is counted but never voids the block
+        assert!(!camping.voids(Basket::Left));
+        assert_eq!(camping.counts(Team::Left), (1, 0));
+        assert_eq!(camping.counts(Team::Right), (0, 0));
+
+        // Stepping off ends the stay; the next one starts from zero
+        camping.enabled = true;
+        assert!(camping.update(Basket::Left, false, dt).is_none());
+        assert_eq!(camping.seconds_on_rim(Basket::Left), 0.0);
+        assert!(
+            camping
+                .update(Basket::Left, true, RIM_CAMPING_SECS / 2.0)
+                .is_none()
+        );
+        assert!(!camping.voids(Basket::Left));
+        assert!(
+            camping
+                .update(Basket::Left, true, RIM_CAMPING_SECS)
+                .is_some()
+        );
+        assert!(camping.voids(Basket::Left));
+        assert!(!camping.voids(Basket::Right));
+        assert_eq!(camping.counts(Team::Left), (2, 1));
+        camping.update(Basket::Left, false, dt);
+        assert!(!camping.voids(Basket::Left));
+    }
+}
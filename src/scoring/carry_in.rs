@@ -0,0 +1,153 @@
+//! Carry-in contest - defender counterplay for the 2-point carry-in
+//!
+//! A defender whose body covers their own basket's mouth is "on the rim". If a
+//! carrier reaches the basket within CARRY_IN_BLOCK_WINDOW_FRAMES of a defender
+//! being on the rim, the carry-in is blocked: the ball is fumbled out of the
+//! basket and no points are scored. A carrier who gets there after the block
+//! window but within CARRY_IN_CONTEST_WINDOW_FRAMES beat a late defender and
+//! scores. Both outcomes are logged as `RimContest` events; uncontested
+//! carry-ins log only the usual `Goal`.
+
+use bevy::prelude::*;
+
+use crate::constants::*;
+use crate::player::Team;
+use crate::world::Basket;
+
+/// How a carry-in was contested
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CarryInOutcome {
+    /// No defender on the rim recently
+    Uncontested,
+    /// A defender was on the rim inside the block window - fumble
+    Blocked,
+    /// A defender was on the rim recently, but not recently enough - score
+    Beaten,
+}
+
+impl CarryInOutcome {
+    /// Outcome string used in the `RH` event
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CarryInOutcome::Uncontested => "open",
+            CarryInOutcome::Blocked => "blocked",
+            CarryInOutcome::Beaten => "scored",
+        }
+    }
+}
+
+/// Team that defends a basket (and can block carry-ins into it)
+pub fn defending_team(basket: Basket) -> Team {
+    match basket {
+        Basket::Left => Team::Left,
+        Basket::Right => Team::Right,
+    }
+}
+
+/// Does a player body at `pos` cover the mouth of a basket at `basket_pos`?
+pub fn covers_rim(pos: Vec2, size: Vec2, basket_pos: Vec2, basket_size: Vec2) -> bool {
+    let reach = (size + basket_size) / 2.0 + Vec2::splat(CARRY_IN_BLOCK_MARGIN);
+    let diff = (pos - basket_pos).abs();
+    diff.x < reach.x && diff.y < reach.y
+}
+
+/// Spot a defender aims for to block a carry-in: just under the basket mouth
+pub fn rim_block_spot(basket_pos: Vec2) -> Vec2 {
+    Vec2::new(basket_pos.x, basket_pos.y - BASKET_SIZE.y / 2.0)
+}
+
+/// Is the carrier about to carry the ball in with the defender beaten?
+/// True when the carrier is within CARRY_IN_THREAT_RANGE of the basket and
+/// horizontally closer to it than the defender.
+pub fn carry_in_threat(carrier_pos: Vec2, defender_pos: Vec2, basket_pos: Vec2) -> bool {
+    carrier_pos.distance(basket_pos) < CARRY_IN_THREAT_RANGE
+        && (carrier_pos.x - basket_pos.x).abs() < (defender_pos.x - basket_pos.x).abs()
+}
+
+/// Frames since a defender last covered each basket's mouth
+#[derive(Debug, Clone, Copy)]
+pub struct RimGuard {
+    frames_since_block: [u32; 2],
+}
+
+impl Default for RimGuard {
+    fn default() -> Self {
+        Self {
+            frames_since_block: [u32::MAX; 2],
+        }
+    }
+}
+
+impl RimGuard {
+    fn index(basket: Basket) -> usize {
+        match basket {
+            Basket::Left => 0,
+            Basket::Right => 1,
+        }
+    }
+
+    /// Advance one frame: `covered` is whether a defender is on the rim now
+    pub fn update(&mut self, basket: Basket, covered: bool) {
+        let frames = &mut self.frames_since_block[Self::index(basket)];
+        *frames = if covered { 0 } else { frames.saturating_add(1) };
+    }
+
+    /// How a carry-in into this basket right now would be contested
+    pub fn outcome(&self, basket: Basket) -> CarryInOutcome {
+        match self.frames_since_block[Self::index(basket)] {
+            f if f <= CARRY_IN_BLOCK_WINDOW_FRAMES => CarryInOutcome::Blocked,
+            f if f <= CARRY_IN_CONTEST_WINDOW_FRAMES => CarryInOutcome::Beaten,
+            _ => CarryInOutcome::Uncontested,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rim_guard_windows() {
+        let mut guard = RimGuard::default();
+        assert_eq!(guard.outcome(Basket::Left), CarryInOutcome::Uncontested);
+
+        guard.update(Basket::Left, true);
+        assert_eq!(guard.outcome(Basket::Left), CarryInOutcome::Blocked);
+        // The other basket is tracked separately
+        assert_eq!(guard.outcome(Basket::Right), CarryInOutcome::Uncontested);
+
+        for _ in 0..CARRY_IN_BLOCK_WINDOW_FRAMES {
+            guard.update(Basket::Left, false);
+        }
+        assert_eq!(guard.outcome(Basket::Left), CarryInOutcome::Blocked);
+        guard.update(Basket::Left, false);
+        assert_eq!(guard.outcome(Basket::Left), CarryInOutcome::Beaten);
+        for _ in 0..CARRY_IN_CONTEST_WINDOW_FRAMES {
+            guard.update(Basket::Left, false);
+        }
+        assert_eq!(guard.outcome(Basket::Left), CarryInOutcome::Uncontested);
+
+        // Body overlapping the mouth (with margin) covers it
+        let basket = Vec2::new(600.0, 200.0);
+        let below = rim_block_spot(basket) - Vec2::new(0.0, PLAYER_SIZE.y / 2.0);
+        assert!(covers_rim(below, PLAYER_SIZE, basket, BASKET_SIZE));
+        assert!(!covers_rim(
+            Vec2::new(300.0, 200.0),
+            PLAYER_SIZE,
+            basket,
+            BASKET_SIZE
+        ));
+
+        // Beaten only when the carrier is near the basket and past the defender
+        assert!(carry_in_threat(
+            Vec2::new(500.0, 150.0),
+            Vec2::new(400.0, 150.0),
+            basket
+        ));
+        assert!(!carry_in_threat(
+            Vec2::new(400.0, 150.0),
+            Vec2::new(500.0, 150.0),
+            basket
+        ));
+    }
+}
@@ -0,0 +1,187 @@
+//! Per-side handicaps for asymmetric matches
+//!
+//! A side can start with points on the board, score more (or less) per basket,
+//! and recover from steal cooldowns faster or slower. Set with
+//! `--handicap-left` / `--handicap-right` (game and training) or
+//! `MatchRules::handicap` when embedding. Whenever the score resets to 0-0 the
+//! head start is applied and each handicapped side is logged as a `Handicap`
+//! event, so analytics can tell handicapped matches apart.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::events::{EventBus, GameEvent, PlayerId};
+use crate::player::Team;
+
+use super::Score;
+
+/// Handicap for one side
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SideHandicap {
+    /// Points on the board at 0-0
+    pub head_start: u32,
+    /// Multiplier on points per basket (rounded, at least 1)
+    pub shot_value: f32,
+    /// Multiplier on steal cooldown length (0.5 = recovers twice as fast)
+    pub cooldown: f32,
+}
+
+impl Default for SideHandicap {
+    fn default() -> Self {
+        Self {
+            head_start: 0,
+            shot_value: 1.0,
+            cooldown: 1.0,
+        }
+    }
+}
+
+impl SideHandicap {
+    /// Parse `head=2,shot=1.5,cooldown=0.8` (any subset, in any order)
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut handicap = Self::default();
+        for part in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("expected key=value, got '{}'", part))?;
+            let bad_value = || format!("bad value for {}: '{}'", key, value);
+            match key.trim() {
+                "head" => handicap.head_start = value.trim().parse().map_err(|_| bad_value())?,
+                "shot" => handicap.shot_value = value.trim().parse().map_err(|_| bad_value())?,
+                "cooldown" => handicap.cooldown = value.trim().parse().map_err(|_| bad_value())?,
+                other => {
+                    return Err(format!(
+                        "unknown handicap '{}' (use head, shot, cooldown)",
+                        other
+                    ));
+                }
+            }
+        }
+        if handicap.shot_value <= 0.0 || handicap.cooldown <= 0.0 {
+            return Err("shot and cooldown multipliers must be positive".to_string());
+        }
+        Ok(handicap)
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Points awarded for a basket worth `base`
+    pub fn points(&self, base: u32) -> u32 {
+        scaled_points(base, self.shot_value)
+    }
+}
+
+/// `base` points scaled by a shot value multiplier (rounded, never below 1)
+pub fn scaled_points(base: u32, shot_value: f32) -> u32 {
+    ((base as f32 * shot_value).round() as u32).max(1)
+}
+
+/// Handicaps for both sides (default: none)
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Handicap {
+    pub left: SideHandicap,
+    pub right: SideHandicap,
+}
+
+impl Handicap {
+    /// Read `--handicap-left <spec>` / `--handicap-right <spec>`
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let mut handicap = Self::default();
+        for (flag, side) in [
+            ("--handicap-left", &mut handicap.left),
+            ("--handicap-right", &mut handicap.right),
+        ] {
+            if let Some(i) = args.iter().position(|a| a == flag) {
+                let spec = args
+                    .get(i + 1)
+                    .ok_or_else(|| format!("{} expects a value", flag))?;
+                *side = SideHandicap::parse(spec).map_err(|e| format!("{}: {}", flag, e))?;
+            }
+        }
+        Ok(handicap)
+    }
+
+    pub fn side(&self, team: Team) -> &SideHandicap {
+        match team {
+            Team::Left => &self.left,
+            Team::Right => &self.right,
+        }
+    }
+
+    /// Handicap events for the sides that have one
+    pub fn events(&self) -> Vec<GameEvent> {
+        [(PlayerId::L, &self.left), (PlayerId::R, &self.right)]
+            .into_iter()
+            .filter(|(_, side)| !side.is_default())
+            .map(|(player, side)| GameEvent::Handicap {
+                player,
+                head_start: side.head_start,
+                shot_value: side.shot_value,
+                cooldown: side.cooldown,
+            })
+            .collect()
+    }
+}
+
+/// Apply head starts and log the handicap whenever the score goes back to 0-0
+/// (match start, reset, level change, new training game)
+pub fn apply_handicap(
+    handicap: Option<Res<Handicap>>,
+    mut score: ResMut<Score>,
+    mut event_bus: ResMut<EventBus>,
+) {
+    let Some(handicap) = handicap else {
+        return;
+    };
+    if !score.is_changed() || score.left != 0 || score.right != 0 {
+        return;
+    }
+    for event in handicap.events() {
+        event_bus.emit(event);
+    }
+    if handicap.left.head_start > 0 || handicap.right.head_start > 0 {
+        score.left = handicap.left.head_start;
+        score.right = handicap.right.head_start;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_scale() {
+        let side = SideHandicap::parse("head=2, shot=1.5").unwrap();
+        assert_eq!(side.head_start, 2);
+        assert_eq!(side.points(1), 2);
+        assert_eq!(side.points(2), 3);
+        assert_eq!(side.cooldown, 1.0);
+        assert_eq!(scaled_points(1, 0.5), 1);
+
+        assert!(SideHandicap::parse("").unwrap().is_default());
+        assert!(SideHandicap::parse("speed=2").is_err());
+        assert!(SideHandicap::parse("cooldown=0").is_err());
+        assert!(SideHandicap::parse("head").is_err());
+
+        let args: Vec<String> = ["game", "--handicap-right", "cooldown=0.5"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let handicap = Handicap::from_args(&args).unwrap();
+        assert!(handicap.left.is_default());
+        assert_eq!(handicap.side(Team::Right).cooldown, 0.5);
+        let events = handicap.events();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            events[0],
+            GameEvent::Handicap {
+                player: PlayerId::R,
+                ..
+            }
+        ));
+    }
+}
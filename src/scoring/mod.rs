@@ -5,12 +5,30 @@ use bevy::prelude::*;
 use crate::ai::{AiGoal, AiNavState, AiState, InputState};
 use crate::ball::{Ball, BallState, CurrentPalette, Velocity};
 use crate::constants::*;
-use crate::events::{EventBus, GameEvent, PlayerId};
+use crate::events::{EventBus, GameEvent, PlayerId, intern};
+use crate::levels::LevelDatabase;
+use crate::momentum::Momentum;
 use crate::palettes::PaletteDatabase;
 use crate::player::{HoldingBall, Player, Team};
 use crate::ui::ScoreFlash;
 use crate::world::Basket;
 
+pub mod camping;
+pub mod carry_in;
+pub mod handicap;
+pub mod out_of_bounds;
+pub mod reset;
+pub mod shot_clock;
+
+pub use camping::RimCamping;
+pub use carry_in::{
+    CarryInOutcome, RimGuard, carry_in_threat, covers_rim, defending_team, rim_block_spot,
+};
+pub use handicap::{Handicap, SideHandicap, apply_handicap};
+pub use out_of_bounds::{OutOfBoundsRule, check_out_of_bounds};
+pub use reset::{BallReset, BallResetMode, inbound_setup_x};
+pub use shot_clock::{ShotClock, shot_clock_update};
+
 /// Score resource tracking left/right team scores
 #[derive(Resource, Default)]
 pub struct Score {
@@ -18,6 +36,67 @@ pub struct Score {
     pub right: u32, // Right team's score
 }
 
+/// Alternating-possession arrow (optional rule, off by default).
+/// Dead balls - a stuck-ball reset or a simultaneous pickup - go to the team the
+/// arrow points at, and the arrow then flips to the other team.
+#[derive(Resource, Debug, Clone)]
+pub struct PossessionArrow {
+    pub enabled: bool,
+    /// Team awarded the next dead ball
+    pub next: Team,
+    /// Dead balls awarded so far
+    pub awarded: u32,
+}
+
+impl Default for PossessionArrow {
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
+
+impl PossessionArrow {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            next: Team::Left,
+            awarded: 0,
+        }
+    }
+
+    /// Award a dead ball: returns the team it goes to and flips the arrow
+    /// (None when the rule is off)
+    pub fn award(&mut self) -> Option<Team> {
+        if !self.enabled {
+            return None;
+        }
+        let team = self.next;
+        self.next = match team {
+            Team::Left => Team::Right,
+            Team::Right => Team::Left,
+        };
+        self.awarded += 1;
+        Some(team)
+    }
+
+    /// Point the arrow back at the left team for a new game
+    pub fn reset(&mut self) {
+        *self = Self::new(self.enabled);
+    }
+}
+
+/// Reset the possession arrow whenever the score goes back to 0-0 (match reset,
+/// level change, new training game)
+pub fn reset_possession_arrow(score: Res<Score>, arrow: Option<ResMut<PossessionArrow>>) {
+    if let Some(mut arrow) = arrow
+        && score.is_changed()
+        && score.left == 0
+        && score.right == 0
+        && arrow.awarded > 0
+    {
+        arrow.reset();
+    }
+}
+
 /// Current level (stores level ID)
 #[derive(Resource)]
 pub struct CurrentLevel(pub String);
@@ -31,21 +110,87 @@ impl Default for CurrentLevel {
 
 /// Check if ball entered a basket and award points.
 /// Emits Goal events to EventBus for auditability.
+/// Carry-ins into a basket a defender covered in the last few frames are
+/// fumbled instead of scored (see `carry_in`), unless the defender is camping
+/// in violation of the defensive three-seconds rule (see `camping`). Where the
+/// ball restarts follows the `BallReset` rule (center court when absent).
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn check_scoring(
     mut commands: Commands,
+    time: Res<Time>,
     mut score: ResMut<Score>,
     current_palette: Res<CurrentPalette>,
     palette_db: Res<PaletteDatabase>,
     mut event_bus: ResMut<EventBus>,
-    mut ball_query: Query<(&mut Transform, &mut Velocity, &mut BallState, &Sprite), With<Ball>>,
+    mut ball_query: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut Velocity,
+            &mut BallState,
+            &Sprite,
+        ),
+        With<Ball>,
+    >,
     basket_query: Query<(Entity, &Transform, &Basket, &Sprite), Without<Ball>>,
-    player_query: Query<(Entity, &Sprite, &Team), With<Player>>,
+    mut player_query: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut Velocity,
+            &Sprite,
+            &Team,
+            Option<&HoldingBall>,
+        ),
+        (With<Player>, Without<Ball>),
+    >,
     mut ai_query: Query<(&mut AiState, &mut AiNavState, &mut InputState), With<Player>>,
+    handicap: Option<Res<Handicap>>,
+    mut momentum: Option<ResMut<Momentum>>,
+    mut ball_reset: Option<ResMut<BallReset>>,
+    mut rim_guard: Local<RimGuard>,
+    mut camping: Option<ResMut<RimCamping>>,
+    (level_db, current_level): (Res<LevelDatabase>, Res<CurrentLevel>),
 ) {
+    let dt = time.delta_secs().max(1.0 / 60.0);
+    if let Some(reset) = ball_reset.as_deref_mut() {
+        reset.tick(dt);
+    }
+    let handicap = handicap.as_deref().copied().unwrap_or_default();
     let palette = palette_db
         .get(current_palette.0)
         .expect("Palette index out of bounds");
-    for (mut ball_transform, mut ball_velocity, mut ball_state, _ball_sprite) in &mut ball_query {
+
+    // Track defenders covering their own basket's mouth (and camping there)
+    for (_, basket_transform, basket, basket_sprite) in &basket_query {
+        let basket_size = basket_sprite.custom_size.unwrap_or(BASKET_SIZE);
+        let basket_pos = basket_transform.translation.truncate();
+        let covered = player_query
+            .iter()
+            .filter(|(_, _, _, _, team, holding)| {
+                **team == defending_team(*basket) && holding.is_none()
+            })
+            .any(|(_, transform, _, sprite, _, _)| {
+                covers_rim(
+                    transform.translation.truncate(),
+                    sprite.custom_size.unwrap_or(PLAYER_SIZE),
+                    basket_pos,
+                    basket_size,
+                )
+            });
+        let mut voided = false;
+        if let Some(camping) = camping.as_deref_mut() {
+            if let Some(event) = camping.update(*basket, covered, dt) {
+                event_bus.emit(event);
+            }
+            voided = camping.voids(*basket);
+        }
+        rim_guard.update(*basket, covered && !voided);
+    }
+
+    for (ball_entity, mut ball_transform, mut ball_velocity, mut ball_state, _ball_sprite) in
+        &mut ball_query
+    {
         let ball_pos = ball_transform.translation.truncate();
         let is_held = matches!(*ball_state, BallState::Held(_));
 
@@ -61,18 +206,59 @@ pub fn check_scoring(
                 && ball_pos.y < basket_pos.y + basket_half.y;
 
             if in_basket {
+                let carrier = match basket {
+                    Basket::Left => PlayerId::R,
+                    Basket::Right => PlayerId::L,
+                };
+                let contest = if is_held {
+                    rim_guard.outcome(*basket)
+                } else {
+                    CarryInOutcome::Uncontested
+                };
+                if contest != CarryInOutcome::Uncontested {
+                    event_bus.emit(GameEvent::RimContest {
+                        carrier,
+                        outcome: intern(contest.as_str()),
+                    });
+                }
+                if contest == CarryInOutcome::Blocked {
+                    // Fumble: knock the ball out of the basket, back toward the court
+                    if let BallState::Held(holder) = *ball_state {
+                        commands.entity(holder).remove::<HoldingBall>();
+                    }
+                    let away = if basket_pos.x > 0.0 { -1.0 } else { 1.0 };
+                    ball_transform.translation.x =
+                        basket_pos.x + away * (basket_half.x + BALL_SIZE.x);
+                    ball_transform.translation.y = basket_pos.y - basket_half.y - BALL_SIZE.y;
+                    ball_velocity.0 =
+                        Vec2::new(away * CARRY_IN_FUMBLE_SPEED, -CARRY_IN_FUMBLE_SPEED * 0.5);
+                    *ball_state = BallState::Free;
+                    info!("CARRY-IN BLOCKED: {} fumbles at the rim", carrier);
+                    commands.entity(basket_entity).insert(ScoreFlash {
+                        timer: 0.3,
+                        flash_color: Color::srgb(0.9, 0.2, 0.2), // Red for a blocked carry-in
+                        original_color: match basket {
+                            Basket::Left => palette.left,
+                            Basket::Right => palette.right,
+                        },
+                    });
+                    break;
+                }
+
                 // Determine points: 2 for carry-in, 1 for throw
                 let points = if is_held { 2 } else { 1 };
 
                 // Determine which team scored
-                let scoring_team = match basket {
+                let (scoring_team, scoring_side) = match basket {
                     Basket::Left => {
-                        score.right += points; // Right team scores in left basket
-                        PlayerId::R
+                        // Right team scores in left basket
+                        score.right += handicap.right.points(points);
+                        (PlayerId::R, Team::Right)
                     }
                     Basket::Right => {
-                        score.left += points; // Left team scores in right basket
-                        PlayerId::L
+                        // Left team scores in right basket
+                        score.left += handicap.left.points(points);
+                        (PlayerId::L, Team::Left)
                     }
                 };
 
@@ -82,6 +268,12 @@ pub fn check_scoring(
                     score_left: score.left,
                     score_right: score.right,
                 });
+                if let Some(event) = momentum
+                    .as_deref_mut()
+                    .and_then(|m| m.on_goal(scoring_side))
+                {
+                    event_bus.emit(event);
+                }
 
                 // Basket color based on its side (from current palette)
                 let basket_original_color = match basket {
@@ -103,7 +295,9 @@ pub fn check_scoring(
 
                 // If held, also flash the player who scored
                 if let BallState::Held(holder) = *ball_state {
-                    if let Ok((player_entity, _player_sprite, team)) = player_query.get(holder) {
+                    if let Ok((player_entity, _, _, _player_sprite, team, _)) =
+                        player_query.get(holder)
+                    {
                         // Player color based on team (from current palette)
                         let player_original_color = match team {
                             Team::Left => palette.left,
@@ -119,11 +313,41 @@ pub fn check_scoring(
                     }
                 }
 
-                // Reset ball to center
-                ball_transform.translation = BALL_SPAWN;
+                // Reset ball (level's ball spawn unless the match uses another reset rule)
+                let spawn = level_db.ball_spawn(&current_level.0);
+                ball_transform.translation = ball_reset
+                    .as_deref()
+                    .map(|r| r.reset_spot(spawn, basket_pos, &mut rand::thread_rng()))
+                    .unwrap_or(spawn);
                 ball_velocity.0 = Vec2::ZERO;
                 *ball_state = BallState::Free;
 
+                // Inbound: the conceding player takes the ball on their own baseline
+                let conceding = defending_team(*basket);
+                if let Some(reset) = ball_reset.as_deref_mut()
+                    && reset.mode == BallResetMode::Inbound
+                    && let Some((inbounder, mut transform, mut velocity, ..)) = player_query
+                        .iter_mut()
+                        .find(|(_, _, _, _, team, _)| **team == conceding)
+                {
+                    transform.translation.x = ball_transform.translation.x;
+                    transform.translation.y = level_db
+                        .player_spawn(&current_level.0, conceding == Team::Left)
+                        .y;
+                    velocity.0 = Vec2::ZERO;
+                    // Same as a pickup: ball_follow_holder moves it to the player next tick
+                    *ball_state = BallState::Held(inbounder);
+                    commands.entity(inbounder).insert(HoldingBall(ball_entity));
+                    reset.start_inbound(conceding);
+                    event_bus.emit(GameEvent::PossessionAwarded {
+                        player: match conceding {
+                            Team::Left => PlayerId::L,
+                            Team::Right => PlayerId::R,
+                        },
+                        reason: intern("inbound"),
+                    });
+                }
+
                 // Reset ALL AI state for all players - complete reset to chase ball
                 for (mut ai_state, mut nav_state, mut input_state) in &mut ai_query {
                     // Reset AI decision state
@@ -151,3 +375,25 @@ pub fn check_scoring(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_possession_arrow_alternates() {
+        let mut off = PossessionArrow::default();
+        assert_eq!(off.award(), None);
+        assert_eq!(off.next, Team::Left);
+
+        let mut arrow = PossessionArrow::new(true);
+        assert_eq!(arrow.award(), Some(Team::Left));
+        assert_eq!(arrow.award(), Some(Team::Right));
+        assert_eq!(arrow.award(), Some(Team::Left));
+        assert_eq!((arrow.next, arrow.awarded), (Team::Right, 3));
+
+        arrow.reset();
+        assert!(arrow.enabled);
+        assert_eq!((arrow.next, arrow.awarded), (Team::Left, 0));
+    }
+}
@@ -0,0 +1,229 @@
+//! Out of bounds - turnover throw-ins (optional rule, off by default)
+//!
+//! With `--out-of-bounds` the court is a `CourtBounds` region inside the arena:
+//! a COURT_BASELINE_DEPTH strip along each wall (behind the baskets) and the
+//! space above the visible arena are out. A live ball - loose, shot or passed -
+//! whose center leaves the court is called on the side that touched it last,
+//! logged as an `OutOfBounds` event, and the other side throws in: one of its
+//! players is put on its spawn point holding the ball, and the offending side
+//! drops back like it does for a post-goal inbound (`BallReset::start_inbound`).
+//! Carrying the ball into the strip isn't a call, so holders can still use the
+//! corner ramps. The court follows the arena, walls closing in during overtime
+//! included.
+
+use bevy::prelude::*;
+
+use crate::ai::{AiNavState, AiState, InputState};
+use crate::ball::{Ball, BallRolling, BallState, Velocity};
+use crate::events::{EventBus, GameEvent, intern};
+use crate::levels::{ArenaDims, LevelDatabase};
+use crate::overtime::Overtime;
+use crate::player::{HoldingBall, Player, Team};
+use crate::world::CourtBounds;
+
+use super::{BallReset, CurrentLevel};
+
+/// Out-of-bounds rule state: who touched the ball last, and calls per side
+#[derive(Resource, Debug, Clone, Default)]
+pub struct OutOfBoundsRule {
+    pub enabled: bool,
+    /// Side that last held, shot or passed the ball
+    last_touch: Option<Team>,
+    /// Balls put out of bounds, per offending side (left, right)
+    pub calls: [u32; 2],
+}
+
+impl OutOfBoundsRule {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..default()
+        }
+    }
+
+    pub fn touch(&mut self, team: Team) {
+        self.last_touch = Some(team);
+    }
+
+    /// Call the ball out: returns the offending side (None if nobody has touched
+    /// it yet, which plays on)
+    pub fn call(&mut self) -> Option<Team> {
+        let offender = self.last_touch.take()?;
+        self.calls[Self::index(offender)] += 1;
+        Some(offender)
+    }
+
+    pub fn calls_for(&self, team: Team) -> u32 {
+        self.calls[Self::index(team)]
+    }
+
+    fn index(team: Team) -> usize {
+        match team {
+            Team::Left => 0,
+            Team::Right => 1,
+        }
+    }
+}
+
+/// Keep the court matching the arena, track the last touch, and turn a ball
+/// that leaves the court into a throw-in for the other side.
+/// Runs after check_scoring.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn check_out_of_bounds(
+    mut commands: Commands,
+    rule: Option<ResMut<OutOfBoundsRule>>,
+    arena: Option<Res<ArenaDims>>,
+    overtime: Option<Res<Overtime>>,
+    mut event_bus: ResMut<EventBus>,
+    mut ball_reset: Option<ResMut<BallReset>>,
+    (level_db, current_level): (Res<LevelDatabase>, Res<CurrentLevel>),
+    mut court: Query<&mut CourtBounds>,
+    mut balls: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut Velocity,
+            &mut BallState,
+            &mut BallRolling,
+        ),
+        With<Ball>,
+    >,
+    mut players: Query<
+        (Entity, &mut Transform, &mut Velocity, &Team),
+        (With<Player>, Without<Ball>),
+    >,
+    mut ai_query: Query<(&mut AiState, &mut AiNavState, &mut InputState), With<Player>>,
+) {
+    let Some(mut rule) = rule.filter(|r| r.enabled) else {
+        return;
+    };
+
+    // Court follows the arena (and the overtime walls)
+    let arena = arena.as_deref().copied().unwrap_or_default();
+    let arena = overtime
+        .as_deref()
+        .map(|o| o.narrow(arena))
+        .unwrap_or(arena);
+    let bounds = CourtBounds::from_arena(&arena);
+    match court.single_mut() {
+        Ok(mut court) => {
+            if *court != bounds {
+                *court = bounds;
+            }
+        }
+        Err(_) => {
+            commands.spawn(bounds);
+        }
+    }
+
+    for (ball_entity, mut ball_transform, mut ball_velocity, mut ball_state, mut rolling) in
+        &mut balls
+    {
+        let toucher = match *ball_state {
+            BallState::Held(player) => Some(player),
+            BallState::InFlight { shooter, .. } => Some(shooter),
+            BallState::Passed { passer, .. } => Some(passer),
+            BallState::Free => None,
+        };
+        if let Some(team) = toucher
+            .and_then(|player| players.get(player).ok())
+            .map(|p| *p.3)
+        {
+            rule.touch(team);
+        }
+
+        let ball_pos = ball_transform.translation.truncate();
+        if matches!(*ball_state, BallState::Held(_)) || bounds.contains(ball_pos) {
+            continue;
+        }
+        let Some(offender) = rule.call() else {
+            continue;
+        };
+        let throwing = offender.opponent();
+        info!(
+            "OUT OF BOUNDS: off {:?} at ({:.0}, {:.0})",
+            offender, ball_pos.x, ball_pos.y
+        );
+        event_bus.emit(GameEvent::OutOfBounds {
+            player: offender.player_id(),
+            pos: (ball_pos.x, ball_pos.y),
+        });
+
+        // Throw-in: the other side's player nearest its spawn point takes the ball there
+        let spawn = level_db.player_spawn(&current_level.0, throwing == Team::Left);
+        let Some((inbounder, mut transform, mut velocity, _)) = players
+            .iter_mut()
+            .filter(|(_, _, _, team)| **team == throwing)
+            .min_by(|a, b| {
+                let da = a.1.translation.truncate().distance(spawn.truncate());
+                let db = b.1.translation.truncate().distance(spawn.truncate());
+                da.total_cmp(&db)
+            })
+        else {
+            continue;
+        };
+        transform.translation.x = spawn.x;
+        transform.translation.y = spawn.y;
+        velocity.0 = Vec2::ZERO;
+        ball_transform.translation.x = spawn.x;
+        ball_transform.translation.y = spawn.y;
+        ball_velocity.0 = Vec2::ZERO;
+        rolling.0 = false;
+        *ball_state = BallState::Held(inbounder);
+        commands.entity(inbounder).insert(HoldingBall(ball_entity));
+        rule.touch(throwing);
+        if let Some(reset) = ball_reset.as_deref_mut() {
+            reset.start_inbound(throwing);
+        }
+        event_bus.emit(GameEvent::PossessionAwarded {
+            player: throwing.player_id(),
+            reason: intern("throw_in"),
+        });
+
+        // Everyone was headed for a ball that isn't there anymore
+        for (mut ai_state, mut nav_state, mut input_state) in &mut ai_query {
+            ai_state.nav_target = None;
+            ai_state.shot_charge_target = 0.0;
+            ai_state.jump_shot_active = false;
+            nav_state.clear();
+            *input_state = InputState::default();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::*;
+
+    #[test]
+    fn test_court_bounds_and_calls() {
+        let arena = ArenaDims::default();
+        let court = CourtBounds::from_arena(&arena);
+        let edge = arena.wall_inner() - COURT_BASELINE_DEPTH;
+        assert!(court.contains(Vec2::new(0.0, arena.floor_top())));
+        assert!(court.contains(Vec2::new(edge - 1.0, arena.center_y())));
+        // Behind the baskets and over the top are out
+        assert!(!court.contains(Vec2::new(-edge - 1.0, arena.center_y())));
+        assert!(!court.contains(Vec2::new(edge + 1.0, arena.center_y())));
+        assert!(!court.contains(Vec2::new(0.0, arena.ceiling_y() + 1.0)));
+
+        // Overtime walls pull the baselines in with them
+        let overtime = Overtime {
+            inset: 100.0,
+            ..default()
+        };
+        let narrowed = CourtBounds::from_arena(&overtime.narrow(arena));
+        assert_eq!(narrowed.max.x, court.max.x - 100.0);
+
+        // Called on the last side to touch it; nobody touched it, play on
+        let mut rule = OutOfBoundsRule::new(true);
+        assert_eq!(rule.call(), None);
+        rule.touch(Team::Left);
+        rule.touch(Team::Right);
+        assert_eq!(rule.call(), Some(Team::Right));
+        assert_eq!(rule.call(), None);
+        assert_eq!(rule.calls_for(Team::Right), 1);
+        assert_eq!(rule.calls_for(Team::Left), 0);
+    }
+}
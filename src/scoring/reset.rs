@@ -0,0 +1,151 @@
+//! Post-goal ball reset - where play restarts after a score
+//!
+//! `center` (the default) drops the ball at center court. `random` drops it at
+//! a neutral spot within BALL_RESET_RANDOM_SPREAD of center. `inbound` hands it
+//! to the conceding team's player on their own baseline, INBOUND_BASELINE_OFFSET
+//! in from the basket they just gave up; for INBOUND_SETUP_TIME the scoring
+//! team's AI drops back to set up its defense instead of pressing the inbounder.
+
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::constants::*;
+use crate::player::Team;
+
+/// Where the ball goes after a goal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BallResetMode {
+    /// Center court jump ball
+    #[default]
+    Center,
+    /// Conceding team inbounds from its own baseline
+    Inbound,
+    /// Random neutral spot near center
+    Random,
+}
+
+impl BallResetMode {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "center" => Some(Self::Center),
+            "inbound" => Some(Self::Inbound),
+            "random" => Some(Self::Random),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Center => "center",
+            Self::Inbound => "inbound",
+            Self::Random => "random",
+        }
+    }
+}
+
+/// Post-goal reset rule plus the inbound in progress
+#[derive(Resource, Debug, Clone, Default)]
+pub struct BallReset {
+    pub mode: BallResetMode,
+    /// Team inbounding and seconds left before the defense presses
+    inbound: Option<(Team, f32)>,
+}
+
+impl BallReset {
+    pub fn new(mode: BallResetMode) -> Self {
+        Self {
+            mode,
+            inbound: None,
+        }
+    }
+
+    /// Ball position after a goal into the basket at `basket_pos`
+    /// (`spawn` = the level's ball spawn, used as center court)
+    pub fn reset_spot(&self, spawn: Vec3, basket_pos: Vec2, rng: &mut impl Rng) -> Vec3 {
+        match self.mode {
+            BallResetMode::Center => spawn,
+            BallResetMode::Inbound => Vec3::new(
+                basket_pos.x - basket_pos.x.signum() * INBOUND_BASELINE_OFFSET,
+                spawn.y,
+                spawn.z,
+            ),
+            BallResetMode::Random => Vec3::new(
+                rng.gen_range(-BALL_RESET_RANDOM_SPREAD..BALL_RESET_RANDOM_SPREAD),
+                spawn.y,
+                spawn.z,
+            ),
+        }
+    }
+
+    /// `team` has the ball on its baseline: hold the defense off for the setup time
+    pub fn start_inbound(&mut self, team: Team) {
+        self.inbound = Some((team, INBOUND_SETUP_TIME));
+    }
+
+    /// Team currently inbounding (None once the setup time is up)
+    pub fn inbounding(&self) -> Option<Team> {
+        self.inbound.map(|(team, _)| team)
+    }
+
+    pub fn tick(&mut self, dt: f32) {
+        if let Some((_, timer)) = &mut self.inbound {
+            *timer -= dt;
+            if *timer <= 0.0 {
+                self.inbound = None;
+            }
+        }
+    }
+}
+
+/// Where the scoring team sets up while the other side inbounds: part of the
+/// way back from center toward the basket it defends
+pub fn inbound_setup_x(own_basket_x: f32) -> f32 {
+    own_basket_x * INBOUND_SETUP_DEPTH
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reset_spots_and_inbound_window() {
+        let mut rng = rand::thread_rng();
+        let left_basket = Vec2::new(-600.0, 0.0);
+
+        let center = BallReset::default();
+        assert_eq!(
+            center.reset_spot(BALL_SPAWN, left_basket, &mut rng),
+            BALL_SPAWN
+        );
+
+        // Inbound starts just inside the conceding team's baseline
+        let mut inbound = BallReset::new(BallResetMode::Inbound);
+        let spot = inbound.reset_spot(BALL_SPAWN, left_basket, &mut rng);
+        assert_eq!(spot.x, -600.0 + INBOUND_BASELINE_OFFSET);
+        assert_eq!(spot.y, BALL_SPAWN.y);
+
+        let random = BallReset::new(BallResetMode::Random);
+        for _ in 0..20 {
+            assert!(
+                random.reset_spot(BALL_SPAWN, left_basket, &mut rng).x.abs()
+                    <= BALL_RESET_RANDOM_SPREAD
+            );
+        }
+
+        // Defense holds off for the setup time, then presses
+        inbound.start_inbound(Team::Left);
+        inbound.tick(INBOUND_SETUP_TIME / 2.0);
+        assert_eq!(inbound.inbounding(), Some(Team::Left));
+        inbound.tick(INBOUND_SETUP_TIME);
+        assert_eq!(inbound.inbounding(), None);
+
+        assert_eq!(
+            BallResetMode::parse("Inbound"),
+            Some(BallResetMode::Inbound)
+        );
+        assert_eq!(BallResetMode::parse("jump"), None);
+        assert!(inbound_setup_x(600.0) > 0.0 && inbound_setup_x(600.0) < 600.0);
+    }
+}
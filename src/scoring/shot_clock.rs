@@ -0,0 +1,209 @@
+//! Shot clock - a possession timer (optional rule, off by default)
+//!
+//! With `--shot-clock <SECS>` a side has that long from gaining the ball to get
+//! a shot off. Passes between teammates don't reset it; a shot release, a goal
+//! or the other side coming up with the ball do, and a free ball pauses it. When
+//! it runs out the ball pops loose from the holder (like a loose-ball steal) and
+//! a `ShotClockViolation` event is logged. The violating side picking it
+//! straight back up only gets SHOT_CLOCK_REGRAB_SECS. The HUD counts the last
+//! SHOT_CLOCK_HUD_SECS down under the score, and AI holders get their shot off
+//! in time (`GameSituation::shot_clock`).
+
+use bevy::prelude::*;
+
+use crate::ball::{Ball, BallRolling, BallShotGrace, BallState, Velocity};
+use crate::constants::*;
+use crate::events::{EventBus, GameEvent};
+use crate::player::{HoldingBall, Player, Team};
+use crate::steal::LooseBall;
+
+use super::Score;
+
+/// Possession timer state and violation counts
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ShotClock {
+    /// Seconds per possession (None = rule off)
+    pub limit: Option<f32>,
+    /// Side the clock is running for
+    team: Option<Team>,
+    remaining: f32,
+    /// Violations per side (left, right)
+    pub violations: [u32; 2],
+}
+
+impl ShotClock {
+    pub fn new(limit: Option<f32>) -> Self {
+        Self {
+            limit: limit.filter(|secs| *secs > 0.0),
+            ..default()
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.limit.is_some()
+    }
+
+    /// Advance `dt` seconds. `holder` is the side with the ball (held or being
+    /// passed), `shot` whether a shot is in the air. Returns true when the clock
+    /// just ran out on the holder.
+    pub fn update(&mut self, holder: Option<Team>, shot: bool, dt: f32) -> bool {
+        let Some(limit) = self.limit else {
+            return false;
+        };
+        if shot {
+            self.team = None;
+            return false;
+        }
+        let Some(holder) = holder else {
+            return false;
+        };
+        if self.team != Some(holder) {
+            self.team = Some(holder);
+            self.remaining = limit;
+        }
+        self.remaining -= dt;
+        if self.remaining > 0.0 {
+            return false;
+        }
+        self.violations[Self::index(holder)] += 1;
+        self.remaining = SHOT_CLOCK_REGRAB_SECS.min(limit);
+        true
+    }
+
+    /// Start over (after a goal): the next side to get the ball gets a full clock
+    pub fn reset(&mut self) {
+        self.team = None;
+    }
+
+    /// Seconds left for this side (None when off or it isn't their possession)
+    pub fn remaining_for(&self, team: Team) -> Option<f32> {
+        self.running()
+            .filter(|(running, _)| *running == team)
+            .map(|(_, remaining)| remaining)
+    }
+
+    /// Side the clock is running for and seconds left
+    pub fn running(&self) -> Option<(Team, f32)> {
+        self.limit?;
+        self.team.map(|team| (team, self.remaining.max(0.0)))
+    }
+
+    /// Violations by a side
+    pub fn violations_for(&self, team: Team) -> u32 {
+        self.violations[Self::index(team)]
+    }
+
+    fn index(team: Team) -> usize {
+        match team {
+            Team::Left => 0,
+            Team::Right => 1,
+        }
+    }
+}
+
+/// Run the shot clock and pop the ball loose from a holder who ran it out.
+/// Runs after check_scoring.
+#[allow(clippy::type_complexity)]
+pub fn shot_clock_update(
+    time: Res<Time>,
+    score: Res<Score>,
+    shot_clock: Option<ResMut<ShotClock>>,
+    mut event_bus: ResMut<EventBus>,
+    mut commands: Commands,
+    mut last_total: Local<Option<u32>>,
+    players: Query<&Team, With<Player>>,
+    mut balls: Query<
+        (
+            &mut BallState,
+            &mut Velocity,
+            &mut BallRolling,
+            &mut BallShotGrace,
+        ),
+        With<Ball>,
+    >,
+) {
+    let Some(mut shot_clock) = shot_clock.filter(|c| c.enabled()) else {
+        return;
+    };
+    // A goal restarts possession
+    let total = score.left + score.right;
+    if last_total.replace(total).is_some_and(|last| last != total) {
+        shot_clock.reset();
+    }
+
+    let dt = time.delta_secs().max(1.0 / 60.0);
+    for (mut ball_state, mut velocity, mut rolling, mut grace) in &mut balls {
+        let (holder, shot) = match *ball_state {
+            BallState::Held(player) => (Some(player), false),
+            BallState::Passed { passer, .. } => (Some(passer), false),
+            BallState::InFlight { .. } => (None, true),
+            BallState::Free => (None, false),
+        };
+        let holder_team = holder.and_then(|player| players.get(player).ok()).copied();
+        if !shot_clock.update(holder_team, shot, dt) {
+            continue;
+        }
+        let Some(team) = holder_team else {
+            continue;
+        };
+
+        // Turnover: the ball pops free
+        if let BallState::Held(player) = *ball_state {
+            commands.entity(player).remove::<HoldingBall>();
+            velocity.0 = LooseBall::pop_velocity(&mut rand::thread_rng());
+            rolling.0 = false;
+            grace.0 = LOOSE_BALL_GRACE;
+        }
+        *ball_state = BallState::Free;
+        info!(
+            "SHOT CLOCK: {:?} ran out the clock ({:.0}s)",
+            team,
+            shot_clock.limit.unwrap_or_default()
+        );
+        event_bus.emit(GameEvent::ShotClockViolation {
+            player: team.player_id(),
+            limit: shot_clock.limit.unwrap_or_default(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shot_clock_possessions() {
+        let dt = 1.0;
+        let mut clock = ShotClock::new(Some(5.0));
+        assert!(ShotClock::new(Some(0.0)).running().is_none());
+
+        // Passing around doesn't reset it; the fifth second is a violation
+        for _ in 0..4 {
+            assert!(!clock.update(Some(Team::Left), false, dt));
+        }
+        assert_eq!(clock.remaining_for(Team::Left), Some(1.0));
+        assert_eq!(clock.remaining_for(Team::Right), None);
+        assert!(clock.update(Some(Team::Left), false, dt));
+        assert_eq!(clock.violations_for(Team::Left), 1);
+
+        // A loose ball pauses it; grabbing it straight back only buys the regrab time
+        assert!(!clock.update(None, false, dt));
+        assert_eq!(
+            clock.remaining_for(Team::Left),
+            Some(SHOT_CLOCK_REGRAB_SECS)
+        );
+
+        // The other side gets a full clock, and a shot clears it
+        assert!(!clock.update(Some(Team::Right), false, dt));
+        assert_eq!(clock.remaining_for(Team::Right), Some(4.0));
+        assert!(!clock.update(None, true, dt));
+        assert!(clock.running().is_none());
+        assert!(!clock.update(Some(Team::Right), false, dt));
+        assert_eq!(clock.remaining_for(Team::Right), Some(4.0));
+
+        let mut off = ShotClock::new(None);
+        for _ in 0..100 {
+            assert!(!off.update(Some(Team::Left), false, dt));
+        }
+    }
+}
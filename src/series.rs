@@ -0,0 +1,391 @@
+//! Versus series - first-to-N matches, instant rematches and best-of-N series
+//!
+//! Free play has no end by default. `--first-to N` ends each match when a side
+//! reaches N points: play stops, a banner shows the result, and R / Start (the
+//! usual reset) starts an instant rematch on the same level with the same
+//! settings. `--series N` plays those matches as a best-of-N series (first to
+//! VERSUS_DEFAULT_FIRST_TO unless `--first-to` says otherwise) and keeps the
+//! series score across them.
+//!
+//! An unfinished series is saved to VERSUS_SERIES_FILE after every match, so
+//! quitting and starting again with the same `--series`/`--first-to` continues
+//! it on its level. A decided series (or a single match without `--series`) is
+//! filed as one row in the `versus_series` table of VERSUS_DB, and the save
+//! file is removed.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::constants::*;
+use crate::events::{EventBus, GameClock, GameEvent};
+use crate::levels::LevelDatabase;
+use crate::player::Team;
+use crate::scoring::{CurrentLevel, Score};
+use crate::settings::write_atomic;
+use crate::simulation::SimDatabase;
+use crate::teams::TeamIdentity;
+
+/// Unfinished series, resumed on the next start with the same rules
+pub const VERSUS_SERIES_FILE: &str = "config/versus_series.json";
+
+/// Finished series, one row each
+pub const VERSUS_DB: &str = "db/versus.db";
+
+/// Match and series state for versus play
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VersusSeries {
+    /// Points that win a match (0 = endless free play)
+    pub first_to: u32,
+    /// Series length in matches (1 = single matches with instant rematch)
+    pub best_of: u32,
+    /// Level ID the series is played on
+    pub level: String,
+    /// Final score of each finished match (left, right)
+    pub games: Vec<(u32, u32)>,
+    /// When the series started (RFC 3339)
+    pub started_at: String,
+    /// The current match is over; play stops until the rematch
+    #[serde(skip)]
+    pub match_over: bool,
+    /// Game clock reading when the current match started
+    #[serde(skip)]
+    pub match_started: f32,
+}
+
+impl VersusSeries {
+    /// Parse `--first-to N` and `--series N`. Without either, matches never end.
+    pub fn from_args(args: &[String]) -> Self {
+        let value = |flag: &str| {
+            let i = args.iter().position(|a| a == flag)?;
+            let parsed = args.get(i + 1).and_then(|s| s.parse::<u32>().ok());
+            if parsed.is_none_or(|n| n == 0) {
+                warn!("{} expects a positive number; ignoring it", flag);
+            }
+            parsed.filter(|n| *n > 0)
+        };
+        let best_of = value("--series");
+        let first_to = value("--first-to");
+        let best_of = match best_of {
+            Some(n) if n % 2 == 0 => {
+                warn!(
+                    "--series {} can't be decided by a majority; playing best of {}",
+                    n,
+                    n + 1
+                );
+                n + 1
+            }
+            Some(n) => n,
+            None => 1,
+        };
+        let first_to = first_to.unwrap_or(if best_of > 1 {
+            VERSUS_DEFAULT_FIRST_TO
+        } else {
+            0
+        });
+        Self {
+            first_to,
+            best_of,
+            started_at: chrono::Utc::now().to_rfc3339(),
+            ..default()
+        }
+    }
+
+    /// Whether matches end at all
+    pub fn enabled(&self) -> bool {
+        self.first_to > 0
+    }
+
+    /// Matches won by `team` so far
+    pub fn wins(&self, team: Team) -> u32 {
+        self.games
+            .iter()
+            .filter(|(left, right)| match team {
+                Team::Left => left > right,
+                Team::Right => right > left,
+            })
+            .count() as u32
+    }
+
+    /// Match wins that take the series
+    pub fn wins_needed(&self) -> u32 {
+        self.best_of.max(1) / 2 + 1
+    }
+
+    /// Side that has won the series, if it's decided
+    pub fn winner(&self) -> Option<Team> {
+        [Team::Left, Team::Right]
+            .into_iter()
+            .find(|team| self.wins(*team) >= self.wins_needed())
+    }
+
+    /// Side that has reached `first_to`, ending the match
+    pub fn match_winner(&self, score: &Score) -> Option<Team> {
+        if !self.enabled() {
+            None
+        } else if score.left >= self.first_to {
+            Some(Team::Left)
+        } else if score.right >= self.first_to {
+            Some(Team::Right)
+        } else {
+            None
+        }
+    }
+
+    /// Record the finished match and stop play
+    pub fn record(&mut self, score: &Score) {
+        self.games.push((score.left, score.right));
+        self.match_over = true;
+    }
+
+    /// Start the rematch: the next game of the series, or a new series once decided
+    pub fn start_next(&mut self, now: f32) {
+        if self.winner().is_some() {
+            self.games.clear();
+            self.started_at = chrono::Utc::now().to_rfc3339();
+        }
+        self.match_over = false;
+        self.match_started = now;
+    }
+
+    /// Continue the unfinished series saved at `path` when it was played with
+    /// the same rules; otherwise keep this one
+    pub fn resume_from(self, path: &Path) -> Self {
+        if self.best_of <= 1 {
+            return self;
+        }
+        let saved = fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<Self>(&content).ok());
+        match saved {
+            Some(saved)
+                if saved.first_to == self.first_to
+                    && saved.best_of == self.best_of
+                    && saved.winner().is_none() =>
+            {
+                info!(
+                    "Resuming best-of-{} series at {}-{}",
+                    saved.best_of,
+                    saved.wins(Team::Left),
+                    saved.wins(Team::Right)
+                );
+                saved
+            }
+            _ => self,
+        }
+    }
+
+    /// Save an unfinished series, or remove the save once it's decided
+    pub fn save(&self, path: &Path) -> Result<(), std::io::Error> {
+        if self.best_of <= 1 || self.winner().is_some() {
+            if path.exists() {
+                fs::remove_file(path)?;
+            }
+            return Ok(());
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        write_atomic(path, json.as_bytes())
+    }
+
+    /// Banner text once a match is over
+    pub fn result_text(&self, teams: &TeamIdentity) -> String {
+        let Some(&(left, right)) = self.games.last() else {
+            return String::new();
+        };
+        let match_winner = if left > right {
+            Team::Left
+        } else {
+            Team::Right
+        };
+        let name = |team| teams.display_name(team).to_uppercase();
+        let series = format!("{}-{}", self.wins(Team::Left), self.wins(Team::Right));
+        if self.best_of <= 1 {
+            format!(
+                "{} WINS {}-{}\nR / START: REMATCH",
+                name(match_winner),
+                left,
+                right
+            )
+        } else if let Some(winner) = self.winner() {
+            format!(
+                "{} WINS THE SERIES {}\nR / START: NEW SERIES",
+                name(winner),
+                series
+            )
+        } else {
+            format!(
+                "{} WINS GAME {} ({}-{})\nSERIES {} - BEST OF {}\nR / START: NEXT GAME",
+                name(match_winner),
+                self.games.len(),
+                left,
+                right,
+                series,
+                self.best_of
+            )
+        }
+    }
+}
+
+/// Run condition: gameplay continues unless a versus match has just ended
+pub fn match_in_play(series: Option<Res<VersusSeries>>) -> bool {
+    series.is_none_or(|series| !series.match_over)
+}
+
+/// End the match at `first_to`, and start the rematch once R / Start has reset the score
+pub fn update_versus_series(
+    mut series: ResMut<VersusSeries>,
+    score: Res<Score>,
+    current_level: Res<CurrentLevel>,
+    level_db: Res<LevelDatabase>,
+    game_clock: Res<GameClock>,
+    mut event_bus: ResMut<EventBus>,
+) {
+    if !series.enabled() {
+        return;
+    }
+    if series.match_over {
+        // The reset zeroes the score; that's the rematch
+        if score.left == 0 && score.right == 0 {
+            series.start_next(game_clock.elapsed);
+        }
+        return;
+    }
+    if series.match_winner(&score).is_none() {
+        return;
+    }
+
+    series.level = current_level.0.clone();
+    series.record(&score);
+    event_bus.emit(GameEvent::MatchEnd {
+        score_left: score.left,
+        score_right: score.right,
+        duration: game_clock.elapsed - series.match_started,
+    });
+    info!(
+        "Versus match over: {}-{} (series {}-{})",
+        score.left,
+        score.right,
+        series.wins(Team::Left),
+        series.wins(Team::Right)
+    );
+
+    if let Err(e) = series.save(Path::new(VERSUS_SERIES_FILE)) {
+        warn!("Failed to save {}: {}", VERSUS_SERIES_FILE, e);
+    }
+    if series.winner().is_some() {
+        let level_name = level_db
+            .get_by_id(&series.level)
+            .map(|l| l.name.clone())
+            .unwrap_or_default();
+        let filed = SimDatabase::open(Path::new(VERSUS_DB))
+            .and_then(|db| db.insert_versus_series(&series, &level_name));
+        if let Err(e) = filed {
+            warn!("Failed to record series in {}: {}", VERSUS_DB, e);
+        }
+    }
+}
+
+/// Marker for the match result banner
+#[derive(Component)]
+pub struct SeriesBanner;
+
+/// Spawn the (initially hidden) match result banner
+pub fn spawn_series_banner(mut commands: Commands) {
+    commands.spawn((
+        Text2d::new(""),
+        TextFont {
+            font_size: 40.0,
+            ..default()
+        },
+        TextLayout::new_with_justify(bevy::text::Justify::Center),
+        TextColor(Color::WHITE),
+        Transform::from_xyz(0.0, ARENA_FLOOR_Y + ARENA_HEIGHT / 2.0, 100.0),
+        Visibility::Hidden,
+        SeriesBanner,
+    ));
+}
+
+/// Show the result (and series score) while a match is over
+pub fn update_series_banner(
+    series: Res<VersusSeries>,
+    teams: Res<TeamIdentity>,
+    mut banners: Query<(&mut Text2d, &mut Visibility), With<SeriesBanner>>,
+) {
+    if !series.is_changed() && !teams.is_changed() {
+        return;
+    }
+    for (mut text, mut visibility) in &mut banners {
+        if series.match_over {
+            text.0 = series.result_text(&teams);
+            *visibility = Visibility::Visible;
+        } else {
+            *visibility = Visibility::Hidden;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_series_decides_and_resumes() {
+        assert!(!VersusSeries::from_args(&args(&["ballgame"])).enabled());
+        let single = VersusSeries::from_args(&args(&["ballgame", "--first-to", "3"]));
+        assert_eq!((single.first_to, single.best_of), (3, 1));
+
+        let mut series = VersusSeries::from_args(&args(&["ballgame", "--series", "4"]));
+        assert_eq!(series.best_of, 5);
+        assert_eq!(series.first_to, VERSUS_DEFAULT_FIRST_TO);
+        assert_eq!(series.wins_needed(), 3);
+
+        let limit = series.first_to;
+        let left_win = Score {
+            left: limit,
+            right: 1,
+        };
+        let right_win = Score {
+            left: 2,
+            right: limit,
+        };
+        assert_eq!(series.match_winner(&Score::default()), None);
+        assert_eq!(series.match_winner(&right_win), Some(Team::Right));
+        series.record(&left_win);
+        series.start_next(10.0);
+        series.record(&right_win);
+        assert!(series.match_over);
+        assert_eq!((series.wins(Team::Left), series.wins(Team::Right)), (1, 1));
+        assert_eq!(series.winner(), None);
+
+        // Quit mid-series: a fresh start with the same rules picks it back up
+        let path = std::env::temp_dir().join(format!("versus_series_{}.json", std::process::id()));
+        series.level = "arena".to_string();
+        series.save(&path).unwrap();
+        let fresh = VersusSeries::from_args(&args(&["ballgame", "--series", "5"]));
+        let mut resumed = fresh.clone().resume_from(&path);
+        assert_eq!(resumed.games, series.games);
+        assert_eq!(resumed.level, "arena");
+        assert!(!resumed.match_over);
+        let other_rules = VersusSeries::from_args(&args(&["ballgame", "--series", "3"]));
+        assert!(other_rules.resume_from(&path).games.is_empty());
+
+        // Deciding the series removes the save; the rematch starts a new series
+        resumed.record(&left_win);
+        resumed.start_next(0.0);
+        resumed.record(&left_win);
+        assert_eq!(resumed.winner(), Some(Team::Left));
+        resumed.save(&path).unwrap();
+        assert!(!path.exists());
+        resumed.start_next(0.0);
+        assert!(resumed.games.is_empty());
+        assert!(!resumed.match_over);
+    }
+}
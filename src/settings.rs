@@ -2,18 +2,33 @@
 //!
 //! Saves and loads user preferences (viewport size, palette, level, etc.)
 //! to/from an init_settings.json file in the config directory.
+//!
+//! The file carries a schema `version`. On load, older files are migrated step
+//! by step to `SETTINGS_VERSION`, then merged over the defaults so fields added
+//! since the file was written get their default values. Saves go to a temp file
+//! that is renamed over the original, so a crash mid-save can't corrupt it.
 
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
 use std::fs;
+use std::io::Write;
 use std::path::Path;
 
 /// Path to the settings file
 pub const SETTINGS_FILE: &str = "config/init_settings.json";
 
+/// Current settings schema version (bump and add a step to `migrate` when a
+/// field is renamed or changes type; new fields only need a default)
+pub const SETTINGS_VERSION: u32 = 2;
+
 /// Persistent settings that survive between sessions
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InitSettings {
+    /// Schema version the file was written with (0 = before versioning)
+    #[serde(default)]
+    pub version: u32,
     /// Viewport preset index (0-4)
     pub viewport_index: usize,
     /// Palette index (0-29)
@@ -33,11 +48,53 @@ pub struct InitSettings {
     pub down_option: String,
     /// Right menu sub-option
     pub right_option: String,
+    /// Ability HUD placement: "player" (above each player) or "corners"
+    #[serde(default = "default_ability_hud_anchor")]
+    pub ability_hud_anchor: String,
+    /// Primary key binding preset: "solo", "wasd" or "arrows" (see `KeyBindings::from_preset`)
+    pub key_bindings: String,
+    /// Assist: show where the last shot was released on the charge gauge
+    pub shot_feedback: bool,
+    /// Assist: show steal cooldown, vulnerability and denial indicators
+    pub steal_indicators: bool,
+    /// Assist: show each AI's current goal and nav step above its head
+    pub ai_goal_labels: bool,
+    /// Whether the first-run setup wizard has been completed (or skipped)
+    pub setup_complete: bool,
+    /// HUD layouts from the layout editor, by viewport preset label (see `ui::hud`)
+    #[serde(default)]
+    pub hud_layouts: BTreeMap<String, HudLayout>,
+}
+
+/// Placement of one HUD element, relative to where it's drawn by default
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HudElementLayout {
+    /// World-space offset from the default position
+    pub offset: [f32; 2],
+    pub scale: f32,
+}
+
+impl Default for HudElementLayout {
+    fn default() -> Self {
+        Self {
+            offset: [0.0, 0.0],
+            scale: 1.0,
+        }
+    }
+}
+
+/// One viewport's HUD layout: element key -> placement (missing elements use the default)
+pub type HudLayout = BTreeMap<String, HudElementLayout>;
+
+fn default_ability_hud_anchor() -> String {
+    "player".to_string()
 }
 
 impl Default for InitSettings {
     fn default() -> Self {
         Self {
+            version: SETTINGS_VERSION,
             viewport_index: 2,    // 1440p default
             palette_index: 0,     // Aurora (first palette)
             level: String::new(), // Empty = use first level
@@ -47,6 +104,13 @@ impl Default for InitSettings {
             active_direction: "Down".to_string(),
             down_option: "Composite".to_string(),
             right_option: "Level".to_string(),
+            ability_hud_anchor: default_ability_hud_anchor(),
+            key_bindings: "solo".to_string(),
+            shot_feedback: true,
+            steal_indicators: true,
+            ai_goal_labels: false,
+            setup_complete: false,
+            hud_layouts: BTreeMap::new(),
         }
     }
 }
@@ -54,46 +118,123 @@ impl Default for InitSettings {
 impl InitSettings {
     /// Load settings from file, or return defaults if file doesn't exist
     pub fn load() -> Self {
-        let path = Path::new(SETTINGS_FILE);
+        Self::load_from(Path::new(SETTINGS_FILE))
+    }
+
+    /// Load, migrate and default-merge settings from `path`
+    pub fn load_from(path: &Path) -> Self {
         if !path.exists() {
-            info!("No init_settings.json found, using defaults");
+            info!("No {} found, using defaults", path.display());
             return Self::default();
         }
 
         match fs::read_to_string(path) {
-            Ok(content) => match serde_json::from_str(&content) {
+            Ok(content) => match Self::parse(&content) {
                 Ok(settings) => {
-                    info!("Loaded settings from {}", SETTINGS_FILE);
+                    info!("Loaded settings from {}", path.display());
                     settings
                 }
                 Err(e) => {
-                    warn!("Failed to parse init_settings.json: {}, using defaults", e);
+                    warn!("Failed to parse {}: {}, using defaults", path.display(), e);
                     Self::default()
                 }
             },
             Err(e) => {
-                warn!("Failed to read init_settings.json: {}, using defaults", e);
+                warn!("Failed to read {}: {}, using defaults", path.display(), e);
                 Self::default()
             }
         }
     }
 
+    /// Parse settings JSON of any schema version
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let mut value: Value = serde_json::from_str(content).map_err(|e| e.to_string())?;
+        let Some(fields) = value.as_object() else {
+            return Err("settings must be a JSON object".to_string());
+        };
+        let version = fields.get("version").and_then(Value::as_u64).unwrap_or(0) as u32;
+        if version > SETTINGS_VERSION {
+            warn!(
+                "Settings file is version {} (this build knows {}); unknown fields are ignored",
+                version, SETTINGS_VERSION
+            );
+        }
+        for from in version..SETTINGS_VERSION {
+            migrate(&mut value, from);
+        }
+
+        // Merge over defaults so fields the file doesn't have keep their default
+        let mut merged = serde_json::to_value(Self::default()).map_err(|e| e.to_string())?;
+        if let (Some(merged), Some(loaded)) = (merged.as_object_mut(), value.as_object()) {
+            for (key, field) in loaded {
+                if merged.contains_key(key) {
+                    merged.insert(key.clone(), field.clone());
+                }
+            }
+        }
+        let mut settings: Self = serde_json::from_value(merged).map_err(|e| e.to_string())?;
+        settings.version = SETTINGS_VERSION;
+        Ok(settings)
+    }
+
     /// Save settings to file
     pub fn save(&self) -> Result<(), std::io::Error> {
+        self.save_to(Path::new(SETTINGS_FILE))
+    }
+
+    /// Save settings to `path` (temp file + rename)
+    pub fn save_to(&self, path: &Path) -> Result<(), std::io::Error> {
         let json = serde_json::to_string_pretty(self)
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
 
-        // Ensure assets directory exists
-        if let Some(parent) = Path::new(SETTINGS_FILE).parent() {
+        // Ensure config directory exists
+        if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)?;
         }
 
-        fs::write(SETTINGS_FILE, json)?;
-        info!("Saved settings to {}", SETTINGS_FILE);
+        write_atomic(path, json.as_bytes())?;
+        info!("Saved settings to {}", path.display());
         Ok(())
     }
 }
 
+/// Upgrade settings JSON from schema `from` to `from + 1`
+fn migrate(value: &mut Value, from: u32) {
+    let Some(fields) = value.as_object_mut() else {
+        return;
+    };
+    match from {
+        // v0 -> v1: level could be a bare level number, and "" meant a human left player
+        0 => {
+            if let Some(level) = fields.get("level").and_then(Value::as_u64) {
+                fields.insert("level".to_string(), Value::String(level.to_string()));
+            }
+            if fields.get("left_ai_profile").and_then(Value::as_str) == Some("") {
+                fields.insert("left_ai_profile".to_string(), Value::Null);
+            }
+        }
+        // v1 -> v2: files from before the setup wizard were already set up by hand
+        1 => {
+            fields.insert("setup_complete".to_string(), Value::Bool(true));
+        }
+        _ => {}
+    }
+    fields.insert("version".to_string(), Value::from(from + 1));
+}
+
+/// Write a file so readers see either the old or the new contents, never a partial write
+pub fn write_atomic(path: &Path, contents: &[u8]) -> Result<(), std::io::Error> {
+    let mut tmp_name = path.as_os_str().to_owned();
+    tmp_name.push(".tmp");
+    let tmp_path = Path::new(&tmp_name);
+
+    let mut file = fs::File::create(tmp_path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    drop(file);
+    fs::rename(tmp_path, path)
+}
+
 /// Resource tracking the current init settings (for change detection)
 #[derive(Resource)]
 pub struct CurrentSettings {
@@ -160,3 +301,46 @@ impl CurrentSettings {
 pub fn save_settings_system(mut settings: ResMut<CurrentSettings>) {
     settings.save_if_dirty();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_v0_settings_migrate_and_round_trip() {
+        // Pre-versioning file: numeric level, "" for human, no ability_hud_anchor
+        let v0 = r#"{
+            "viewport_index": 1,
+            "palette_index": 4,
+            "level": 3,
+            "ball_style": "stripes",
+            "left_ai_profile": "",
+            "right_ai_profile": "Aggressive",
+            "active_direction": "Right",
+            "down_option": "Composite",
+            "right_option": "Level",
+            "removed_field": true
+        }"#;
+        let settings = InitSettings::parse(v0).unwrap();
+        assert_eq!(settings.version, SETTINGS_VERSION);
+        assert_eq!(settings.level, "3");
+        assert_eq!(settings.left_ai_profile, None);
+        assert_eq!(settings.palette_index, 4);
+        assert_eq!(settings.ability_hud_anchor, "player");
+        assert_eq!(settings.key_bindings, "solo");
+        assert!(settings.setup_complete);
+        assert!(!InitSettings::default().setup_complete);
+
+        let dir =
+            std::env::temp_dir().join(format!("ballgame_settings_test_{}", std::process::id()));
+        let path = dir.join("init_settings.json");
+        settings.save_to(&path).unwrap();
+        assert!(!dir.join("init_settings.json.tmp").exists());
+        let reloaded = InitSettings::load_from(&path);
+        assert_eq!(reloaded.right_ai_profile, "Aggressive");
+        assert_eq!(reloaded.version, SETTINGS_VERSION);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(InitSettings::parse("[1, 2]").is_err());
+    }
+}
@@ -3,12 +3,45 @@
 use bevy::prelude::*;
 
 use crate::ai::InputState;
-use crate::player::Player;
+use crate::config::ShotConfig;
+use crate::constants::*;
+use crate::momentum::Momentum;
+use crate::player::{Grounded, Player, Team, Velocity};
+use crate::tuning::PhysicsTweaks;
+
+/// Largest angle error a shot can get from variance (at variance 1.0)
+pub const SHOT_MAX_ANGLE_VARIANCE_DEG: f32 = 30.0;
+
+/// How far a shot can land from the basket center and still go in
+const BASKET_LANDING_TOLERANCE: f32 = (BASKET_SIZE.x - BALL_SIZE.x) / 2.0;
 
 /// Charge time accumulator
 #[derive(Component, Default)]
 pub struct ChargingShot {
     pub charge_time: f32, // How long throw button has been held
+    /// Part of charge_time spent moving or airborne
+    pub unsteady_time: f32,
+}
+
+impl ChargingShot {
+    /// Share of the charge spent moving or airborne (0 = feet set the whole time)
+    pub fn unsteadiness(&self) -> f32 {
+        if self.charge_time > 0.0 {
+            (self.unsteady_time / self.charge_time).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.charge_time = 0.0;
+        self.unsteady_time = 0.0;
+    }
+}
+
+/// Whether a player charging right now counts as moving (airborne or over steady_speed)
+pub fn is_unsteady(velocity: Vec2, grounded: bool, shot_config: &ShotConfig) -> bool {
+    !grounded || velocity.x.abs() > shot_config.steady_speed
 }
 
 /// Information about the last shot taken (for debug display)
@@ -19,28 +52,184 @@ pub struct LastShotInfo {
     pub base_variance: f32,
     pub air_penalty: f32,
     pub move_penalty: f32,
+    /// Variance from charging while moving or airborne
+    pub charge_move_penalty: f32,
     pub distance_variance: f32,
     pub required_speed: f32,
     pub total_variance: f32,
     pub charge_pct: f32,
     pub shot_quality: f32,
     pub target: Option<crate::world::Basket>,
+    /// Ideal charge for this shot (see `ideal_charge_pct`)
+    pub ideal_charge_pct: f32,
+}
+
+/// Release feedback shown on a player's charge gauge after a shot
+#[derive(Component, Default)]
+pub struct ChargeFeedback {
+    pub release_pct: f32,
+    pub ideal_pct: f32,
+    /// Seconds left to show the markers
+    pub timer: f32,
+}
+
+/// Horizontal distance from the target where a shot lands when released `error`
+/// radians off its aim. None if it never comes back down to the target height.
+fn landing_miss(angle: f32, speed: f32, tx: f32, ty: f32, error: f32) -> Option<f32> {
+    let (sin, cos) = (angle + error).sin_cos();
+    let (vx, vy) = (speed * cos, speed * sin);
+    let discriminant = vy * vy - 2.0 * BALL_GRAVITY * ty;
+    if discriminant < 0.0 {
+        return None;
+    }
+    // Descending branch: the ball drops into the basket from above
+    let t = (vy + discriminant.sqrt()) / BALL_GRAVITY;
+    Some((vx * t - tx).abs())
+}
+
+/// Least charge that keeps a shot's whole angle spread inside the basket, given
+/// the air/move/distance variance already on it. `angle` and `speed` are the
+/// aimed trajectory, (tx, ty) the basket offset from the shooter; wind is ignored.
+/// Charging past this only costs time; releasing below it risks a miss. Never
+/// below the quick-shot threshold, where shots lose power.
+pub fn ideal_charge_pct(
+    tweaks: &PhysicsTweaks,
+    angle: f32,
+    speed: f32,
+    tx: f32,
+    ty: f32,
+    penalties: f32,
+) -> f32 {
+    let max_error = SHOT_MAX_ANGLE_VARIANCE_DEG.to_radians();
+    let lands = |error: f32| {
+        [error, -error].iter().all(|e| {
+            landing_miss(angle, speed, tx, ty, *e).is_some_and(|m| m <= BASKET_LANDING_TOLERANCE)
+        })
+    };
+
+    // Widest angle error (in 0.25 degree steps) that still goes in
+    let step = 0.25_f32.to_radians();
+    let mut tolerated = 0.0;
+    while tolerated + step <= max_error && lands(tolerated + step) {
+        tolerated += step;
+    }
+
+    let allowed_variance = tolerated / max_error - penalties;
+    let span = tweaks.shot_max_variance - tweaks.shot_min_variance;
+    let charge = if span > 0.0 {
+        (tweaks.shot_max_variance - allowed_variance) / span
+    } else {
+        0.0
+    };
+    let quick_floor = (tweaks.quick_power_threshold / tweaks.shot_charge_time).min(1.0);
+    charge.clamp(quick_floor, 1.0)
 }
 
 /// Update shot charge while throw button is held.
 /// All players read from their InputState component.
+#[allow(clippy::type_complexity)]
 pub fn update_shot_charge(
     time: Res<Time>,
-    mut player_query: Query<(&mut ChargingShot, &InputState), With<Player>>,
+    shot_config: Res<ShotConfig>,
+    mut player_query: Query<
+        (
+            &mut ChargingShot,
+            &InputState,
+            &Velocity,
+            &Grounded,
+            Option<&Team>,
+        ),
+        With<Player>,
+    >,
+    momentum: Option<Res<Momentum>>,
 ) {
     // Use minimum dt for headless mode compatibility
     let dt = time.delta_secs().max(1.0 / 60.0);
 
-    for (mut charging, input) in &mut player_query {
+    for (mut charging, input, velocity, grounded, team) in &mut player_query {
         if input.throw_held {
-            charging.charge_time += dt;
+            // Hot-streak teams charge a little faster
+            let rate = match (momentum.as_deref(), team) {
+                (Some(momentum), Some(team)) => momentum.charge_multiplier(*team),
+                _ => 1.0,
+            };
+            charging.charge_time += dt * rate;
+            if is_unsteady(velocity.0, grounded.0, &shot_config) {
+                charging.unsteady_time += dt * rate;
+            }
         }
         // Don't reset here - let throw_ball reset after using the charge
         // Otherwise charge resets to 0 before throw_ball can read it
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculate_shot_trajectory;
+
+    /// Ideal charge for a shot at (tx, ty), with its distance variance included
+    fn ideal_for(tx: f32, ty: f32, extra_penalty: f32) -> f32 {
+        let tweaks = PhysicsTweaks::default();
+        let traj = calculate_shot_trajectory(
+            0.0,
+            0.0,
+            tx,
+            ty,
+            BALL_GRAVITY,
+            tweaks.shot_distance_variance,
+            None,
+        )
+        .unwrap();
+        ideal_charge_pct(
+            &tweaks,
+            traj.angle,
+            traj.required_speed,
+            tx,
+            ty,
+            traj.distance_variance + extra_penalty,
+        )
+    }
+
+    #[test]
+    fn test_ideal_charge_grows_with_distance_and_penalties() {
+        let tweaks = PhysicsTweaks::default();
+        let floor = tweaks.quick_power_threshold / tweaks.shot_charge_time;
+
+        let close = ideal_for(150.0, 100.0, 0.0);
+        let mid = ideal_for(400.0, 0.0, 0.0);
+        let far = ideal_for(900.0, 100.0, 0.0);
+        assert!(close >= floor && far <= 1.0);
+        assert!(far > close);
+
+        // Shooting in the air needs more charge for the same shot
+        let airborne = ideal_for(400.0, 0.0, tweaks.shot_air_variance_penalty);
+        assert!(airborne > mid);
+        // Mirrored shot needs the same charge
+        assert!((ideal_for(-400.0, 0.0, 0.0) - mid).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_unsteadiness_tracks_share_of_charge_on_the_move() {
+        let config = ShotConfig::default();
+        assert!(!is_unsteady(Vec2::new(20.0, 0.0), true, &config));
+        assert!(is_unsteady(Vec2::new(MOVE_SPEED, 0.0), true, &config));
+        assert!(is_unsteady(Vec2::ZERO, false, &config));
+
+        let mut charging = ChargingShot::default();
+        assert_eq!(charging.unsteadiness(), 0.0);
+        charging.charge_time = 1.0;
+        charging.unsteady_time = 0.25;
+        assert!((charging.unsteadiness() - 0.25).abs() < 1e-6);
+        charging.reset();
+        assert_eq!(charging.unsteady_time, 0.0);
+
+        // Charging on the move costs quality
+        let basket = Vec2::new(-600.0, 0.0);
+        let spot = Vec2::new(-400.0, 150.0);
+        assert!(
+            crate::ai::evaluate_shot_quality(spot, basket, 1.0)
+                < crate::ai::evaluate_shot_quality(spot, basket, 0.0)
+        );
+    }
+}
@@ -1,7 +1,9 @@
-//! Shooting module - charge and throw systems
+//! Shooting module - charge, throw and pass systems
 
 mod charge;
+mod pass;
 mod throw;
 
 pub use charge::*;
+pub use pass::*;
 pub use throw::*;
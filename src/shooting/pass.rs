@@ -0,0 +1,195 @@
+//! Passing - the holder lobs the ball to a teammate (2v2)
+//!
+//! The pass button sends the ball on a lob to the nearest teammate as
+//! `BallState::Passed`. Anyone but the passer who gets within pickup reach
+//! catches it: the teammate completes the pass, an opponent intercepts it.
+//! A pass nobody catches turns Free once it slows down, like a missed shot.
+
+use bevy::prelude::*;
+
+use crate::ai::InputState;
+use crate::ball::{Ball, BallRolling, BallShotGrace, BallState, Scooping, Velocity};
+use crate::config::BallConfig;
+use crate::constants::*;
+use crate::events::{EventBus, GameEvent};
+use crate::player::{HoldingBall, Player, Team};
+use crate::shooting::ChargingShot;
+use crate::tuning::PhysicsTweaks;
+
+/// Launch velocity for a lob from `from` that lands on `to` under `gravity`,
+/// and its flight time. Longer passes fly longer (clamped), so they arc higher.
+pub fn pass_velocity(from: Vec2, to: Vec2, gravity: f32) -> (Vec2, f32) {
+    let delta = to - from;
+    let time = (delta.length() / PASS_SPEED).clamp(PASS_MIN_FLIGHT_TIME, PASS_MAX_FLIGHT_TIME);
+    let velocity = Vec2::new(delta.x / time, delta.y / time + 0.5 * gravity * time);
+    (velocity, time)
+}
+
+/// Pass to the nearest teammate when the pass button is pressed.
+/// Without a teammate (1v1) the press does nothing.
+pub fn pass_ball(
+    tweaks: Res<PhysicsTweaks>,
+    mut commands: Commands,
+    mut event_bus: ResMut<EventBus>,
+    mut passers: Query<
+        (
+            Entity,
+            &Transform,
+            &Team,
+            &mut InputState,
+            &mut ChargingShot,
+            Option<&HoldingBall>,
+        ),
+        With<Player>,
+    >,
+    receivers: Query<(Entity, &Transform, &Velocity, &Team), With<Player>>,
+    mut ball_query: Query<
+        (
+            &mut Velocity,
+            &mut BallState,
+            &mut BallRolling,
+            &mut BallShotGrace,
+        ),
+        (With<Ball>, Without<Player>),
+    >,
+) {
+    for (passer, transform, team, mut input, mut charging, holding) in &mut passers {
+        if !input.pass_pressed {
+            continue;
+        }
+        input.pass_pressed = false;
+
+        let Some(holding) = holding else {
+            continue;
+        };
+        let from = transform.translation.truncate();
+        let Some((target, target_pos, target_velocity)) = receivers
+            .iter()
+            .filter(|(entity, _, _, t)| *entity != passer && *t == team)
+            .map(|(entity, t, v, _)| (entity, t.translation.truncate(), v.0))
+            .min_by(|a, b| from.distance(a.1).total_cmp(&from.distance(b.1)))
+        else {
+            continue;
+        };
+        let Ok((mut ball_velocity, mut ball_state, mut rolling, mut grace)) =
+            ball_query.get_mut(holding.0)
+        else {
+            continue;
+        };
+        if *ball_state != BallState::Held(passer) {
+            continue;
+        }
+
+        // Lead the receiver by where they'll be when the ball arrives
+        let (_, time) = pass_velocity(from, target_pos, tweaks.ball_gravity);
+        let lead = Vec2::new(target_velocity.x * time, 0.0);
+        let (velocity, time) = pass_velocity(from, target_pos + lead, tweaks.ball_gravity);
+
+        ball_velocity.0 = velocity;
+        *ball_state = BallState::Passed { passer, target };
+        rolling.0 = false;
+        // No air friction for the whole flight, so the lob lands where aimed
+        grace.0 = time;
+        charging.reset();
+        commands.entity(passer).remove::<HoldingBall>();
+
+        let distance = from.distance(target_pos);
+        info!("PASS: {:?} passes {:.0}px", team, distance);
+        event_bus.emit(GameEvent::Pass {
+            player: team.player_id(),
+            distance,
+        });
+    }
+}
+
+/// Catch passes: the intended receiver if in reach, else the nearest player in
+/// reach (an interception when they're on the other side)
+pub fn catch_pass(
+    ball_config: Res<BallConfig>,
+    mut commands: Commands,
+    mut event_bus: ResMut<EventBus>,
+    mut players: Query<
+        (Entity, &Transform, &Team, &mut ChargingShot),
+        (With<Player>, Without<HoldingBall>),
+    >,
+    mut ball_query: Query<(Entity, &Transform, &mut BallState), (With<Ball>, Without<Player>)>,
+) {
+    for (ball_entity, ball_transform, mut ball_state) in &mut ball_query {
+        let BallState::Passed { passer, target } = *ball_state else {
+            continue;
+        };
+        let ball_pos = ball_transform.translation.truncate();
+        let in_reach = |pos: Vec2| pos.distance(ball_pos) < ball_config.pickup_radius;
+
+        let catcher = players
+            .iter()
+            .filter(|(entity, t, ..)| *entity != passer && in_reach(t.translation.truncate()))
+            .min_by(|a, b| {
+                // Intended receiver first, then the nearest
+                (a.0 != target).cmp(&(b.0 != target)).then(
+                    ball_pos
+                        .distance(a.1.translation.truncate())
+                        .total_cmp(&ball_pos.distance(b.1.translation.truncate())),
+                )
+            })
+            .map(|(entity, _, team, _)| (entity, *team));
+        let Some((catcher, team)) = catcher else {
+            continue;
+        };
+
+        let passer_team = players.get(passer).map(|(_, _, t, _)| *t).ok();
+        let intercepted = passer_team.is_some_and(|t| t != team);
+        *ball_state = BallState::Held(catcher);
+        commands
+            .entity(catcher)
+            .insert(HoldingBall(ball_entity))
+            .remove::<Scooping>();
+        if let Ok((_, _, _, mut charging)) = players.get_mut(catcher) {
+            charging.reset();
+        }
+
+        info!(
+            "PASS {}: {:?}",
+            if intercepted { "INTERCEPTED" } else { "CAUGHT" },
+            team
+        );
+        event_bus.emit(GameEvent::PassCaught {
+            player: team.player_id(),
+            intercepted,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pass_velocity_lands_on_target() {
+        let from = Vec2::new(-100.0, 0.0);
+        for to in [
+            Vec2::new(200.0, 0.0),
+            Vec2::new(-400.0, 150.0),
+            Vec2::new(-90.0, -20.0),
+        ] {
+            let (velocity, time) = pass_velocity(from, to, BALL_GRAVITY);
+            assert!((PASS_MIN_FLIGHT_TIME..=PASS_MAX_FLIGHT_TIME).contains(&time));
+            let landing = from + velocity * time - Vec2::new(0.0, 0.5 * BALL_GRAVITY * time * time);
+            assert!(
+                landing.distance(to) < 0.01,
+                "{:?} lands at {:?}",
+                to,
+                landing
+            );
+        }
+        // Long passes are capped at the slowest flight, short ones at the quickest
+        assert_eq!(
+            pass_velocity(from, Vec2::new(5000.0, 0.0), BALL_GRAVITY).1,
+            PASS_MAX_FLIGHT_TIME
+        );
+        assert_eq!(
+            pass_velocity(from, from, BALL_GRAVITY).1,
+            PASS_MIN_FLIGHT_TIME
+        );
+    }
+}
@@ -6,9 +6,14 @@ use rand::Rng;
 
 use crate::ai::{InputState, evaluate_shot_quality};
 use crate::ball::{Ball, BallRolling, BallShotGrace, BallState, Velocity};
+use crate::config::ShotConfig;
 use crate::constants::*;
-use crate::player::{Grounded, HoldingBall, Player, TargetBasket};
-use crate::shooting::{ChargingShot, LastShotInfo};
+use crate::levels::{LevelData, LevelDatabase};
+use crate::player::{Grounded, HoldingBall, HumanControlled, Player, TargetBasket};
+use crate::scoring::CurrentLevel;
+use crate::shooting::{
+    ChargeFeedback, ChargingShot, LastShotInfo, SHOT_MAX_ANGLE_VARIANCE_DEG, ideal_charge_pct,
+};
 use crate::tuning::PhysicsTweaks;
 use crate::world::Basket;
 
@@ -16,6 +21,10 @@ use crate::world::Basket;
 /// All players read from their InputState component.
 pub fn throw_ball(
     tweaks: Res<PhysicsTweaks>,
+    shot_config: Res<ShotConfig>,
+    time: Res<Time>,
+    current_level: Res<CurrentLevel>,
+    level_db: Res<LevelDatabase>,
     mut commands: Commands,
     mut shot_info: ResMut<LastShotInfo>,
     mut player_query: Query<
@@ -28,6 +37,7 @@ pub fn throw_ball(
             &mut ChargingShot,
             &mut InputState,
             Option<&HoldingBall>,
+            Has<HumanControlled>,
         ),
         With<Player>,
     >,
@@ -51,6 +61,7 @@ pub fn throw_ball(
         mut charging,
         mut input,
         holding,
+        is_human,
     ) in &mut player_query
     {
         if !input.throw_released {
@@ -62,7 +73,7 @@ pub fn throw_ball(
 
         let Some(holding_ball) = holding else {
             // Not holding a ball - reset charge since they released the button
-            charging.charge_time = 0.0;
+            charging.reset();
             continue;
         };
 
@@ -74,7 +85,7 @@ pub fn throw_ball(
 
         // Ball is being thrown - no longer rolling, start grace period
         rolling.0 = false;
-        grace.0 = SHOT_GRACE_PERIOD;
+        grace.0 = shot_config.grace_period;
 
         // Calculate charge percentage (0.0 to 1.0)
         let charge_pct = (charging.charge_time / tweaks.shot_charge_time).min(1.0);
@@ -94,15 +105,44 @@ pub fn throw_ball(
             .map(|(transform, _)| transform.translation.truncate());
 
         // Calculate optimal trajectory to basket
+        // AI compensates for wind along the shot line and leads moving baskets;
+        // humans have to read both themselves
+        let level = level_db.get_by_id(&current_level.0).filter(|_| !is_human);
         let trajectory = if let Some(basket_pos) = target_basket_pos {
+            let wind = level.and_then(|level| level.average_wind(player_pos, basket_pos));
+            let aim_pos = match level {
+                Some(level) if level.basket_motion.is_some() => lead_moving_basket(
+                    level,
+                    target.0 == Basket::Left,
+                    player_pos,
+                    basket_pos,
+                    time.elapsed_secs(),
+                    tweaks.shot_distance_variance,
+                    wind,
+                ),
+                _ => basket_pos,
+            };
             calculate_shot_trajectory(
                 player_pos.x,
                 player_pos.y,
-                basket_pos.x,
-                basket_pos.y,
+                aim_pos.x,
+                aim_pos.y,
                 BALL_GRAVITY,
                 tweaks.shot_distance_variance,
+                wind,
             )
+            .or_else(|| {
+                // Wind overpowering gravity has no ballistic solution - aim as if calm
+                calculate_shot_trajectory(
+                    player_pos.x,
+                    player_pos.y,
+                    basket_pos.x,
+                    basket_pos.y,
+                    BALL_GRAVITY,
+                    tweaks.shot_distance_variance,
+                    None,
+                )
+            })
         } else {
             None
         };
@@ -125,6 +165,12 @@ pub fn throw_ball(
             (player_velocity.0.x.abs() / MOVE_SPEED).min(1.0) * tweaks.shot_move_variance_penalty;
         variance += move_penalty;
 
+        // Charging on the move: penalty scales with the share of the charge spent
+        // moving or airborne (setting your feet before release pays off)
+        let unsteadiness = charging.unsteadiness();
+        let charge_move_penalty = unsteadiness * shot_config.unsteady_variance;
+        variance += charge_move_penalty;
+
         // Get base angle, required speed, and distance variance from trajectory
         let (base_angle, required_speed, distance_variance) = if let Some(traj) = &trajectory {
             (traj.angle, traj.required_speed, traj.distance_variance)
@@ -132,21 +178,21 @@ pub fn throw_ball(
             // Fallback (shouldn't happen now) - 60° toward target with max speed
             let fallback_angle = if let Some(basket_pos) = target_basket_pos {
                 if basket_pos.x >= player_pos.x {
-                    SHOT_DEFAULT_ANGLE.to_radians() // 60° right
+                    shot_config.default_angle.to_radians() // 60° right
                 } else {
-                    std::f32::consts::PI - SHOT_DEFAULT_ANGLE.to_radians() // 120° left
+                    std::f32::consts::PI - shot_config.default_angle.to_radians() // 120° left
                 }
             } else {
-                SHOT_DEFAULT_ANGLE.to_radians() // Default: 60° right
+                shot_config.default_angle.to_radians() // Default: 60° right
             };
-            (fallback_angle, SHOT_MAX_SPEED, 0.0)
+            (fallback_angle, shot_config.max_speed, 0.0)
         };
 
         // Add distance variance to total
         variance += distance_variance;
 
         // Apply variance to angle (max ±30° at full variance), no bias
-        let max_angle_variance = 30.0_f32.to_radians();
+        let max_angle_variance = SHOT_MAX_ANGLE_VARIANCE_DEG.to_radians();
         let angle_variance = rng.gen_range(-variance..variance) * max_angle_variance;
         let final_angle = base_angle + angle_variance;
 
@@ -173,11 +219,15 @@ pub fn throw_ball(
         // Apply distance multiplier and randomness
         let speed_randomness =
             rng.gen_range(tweaks.speed_randomness_min..tweaks.speed_randomness_max);
-        let uncapped_speed =
-            required_speed * distance_multiplier * speed_randomness * power_multiplier;
+        let unsteady_multiplier = 1.0 - unsteadiness * shot_config.unsteady_power_loss;
+        let uncapped_speed = required_speed
+            * distance_multiplier
+            * speed_randomness
+            * power_multiplier
+            * unsteady_multiplier;
 
-        // Hard cap at SHOT_HARD_CAP
-        let final_speed = uncapped_speed.min(SHOT_HARD_CAP);
+        // Hard cap (ShotConfig::hard_cap)
+        let final_speed = uncapped_speed.min(shot_config.hard_cap);
 
         // Convert angle + speed to velocity (simple and direct!)
         // Angle is absolute: 0=right, π/2=up, π=left
@@ -195,24 +245,76 @@ pub fn throw_ball(
 
         // Record shot info for debug display
         let shot_quality = target_basket_pos
-            .map(|pos| evaluate_shot_quality(player_pos, pos))
+            .map(|pos| evaluate_shot_quality(player_pos, pos, unsteadiness))
             .unwrap_or(0.0);
+        let ideal_charge = target_basket_pos
+            .map(|pos| {
+                ideal_charge_pct(
+                    &tweaks,
+                    base_angle,
+                    required_speed,
+                    pos.x - player_pos.x,
+                    pos.y - player_pos.y,
+                    air_penalty + move_penalty + charge_move_penalty + distance_variance,
+                )
+            })
+            .unwrap_or(1.0);
         *shot_info = LastShotInfo {
             angle_degrees: final_angle.to_degrees(),
             speed: final_speed,
             base_variance,
             air_penalty,
             move_penalty,
+            charge_move_penalty,
             distance_variance,
             required_speed,
             total_variance: variance,
             charge_pct,
             shot_quality,
             target: Some(target.0),
+            ideal_charge_pct: ideal_charge,
         };
 
-        // Reset charge and release ball
-        charging.charge_time = 0.0;
-        commands.entity(player_entity).remove::<HoldingBall>();
+        // Reset charge and release ball, showing where the release landed on the gauge
+        charging.reset();
+        commands
+            .entity(player_entity)
+            .remove::<HoldingBall>()
+            .insert(ChargeFeedback {
+                release_pct: charge_pct,
+                ideal_pct: ideal_charge,
+                timer: CHARGE_FEEDBACK_DURATION,
+            });
+    }
+}
+
+/// Predict where a moving basket will be when the ball arrives.
+/// Iterates flight time -> future basket position a few times; converges quickly
+/// because basket speeds are small compared to shot speeds.
+fn lead_moving_basket(
+    level: &LevelData,
+    left: bool,
+    shooter: Vec2,
+    basket_now: Vec2,
+    now: f32,
+    distance_variance: f32,
+    wind: Option<Vec2>,
+) -> Vec2 {
+    const ITERATIONS: usize = 3;
+    let mut aim = basket_now;
+    for _ in 0..ITERATIONS {
+        let Some(traj) = calculate_shot_trajectory(
+            shooter.x,
+            shooter.y,
+            aim.x,
+            aim.y,
+            BALL_GRAVITY,
+            distance_variance,
+            wind,
+        ) else {
+            break;
+        };
+        aim = level.basket_position(left, now + traj.flight_time(aim.x - shooter.x));
     }
+    aim
 }
@@ -9,6 +9,7 @@ use std::time::Duration;
 
 use crate::ai::{AiCapabilities, AiProfileDatabase, HeatmapBundle, NavGraph};
 use crate::ball::CurrentPalette;
+use crate::config::GameplayConfig;
 use crate::events::EventBus;
 use crate::levels::LevelDatabase;
 use crate::palettes::PaletteDatabase;
@@ -105,8 +106,8 @@ impl HeadlessAppBuilder {
     /// - Any additional resources
     pub fn build(self) -> App {
         use crate::ai::{
-            ai_decision_update, ai_navigation_update, load_heatmaps_on_level_change,
-            mark_nav_dirty_on_level_change, rebuild_nav_graph,
+            ai_decision_update, ai_mistake_update, ai_navigation_update,
+            load_heatmaps_on_level_change, mark_nav_dirty_on_level_change, rebuild_nav_graph,
         };
 
         let mut app = App::new();
@@ -143,6 +144,7 @@ impl HeadlessAppBuilder {
         app.init_resource::<StealTracker>();
         app.init_resource::<PhysicsTweaks>();
         let _ = tuning::apply_global_tuning(&mut app.world_mut().resource_mut::<PhysicsTweaks>());
+        app.add_plugins(GameplayConfig::load("simulate"));
         app.init_resource::<LastShotInfo>();
         app.insert_resource(CurrentPalette(0));
         app.init_resource::<PaletteDatabase>();
@@ -171,6 +173,7 @@ impl HeadlessAppBuilder {
                     rebuild_nav_graph,
                     ai_navigation_update,
                     ai_decision_update,
+                    ai_mistake_update,
                 )
                     .chain(),
             );
@@ -0,0 +1,510 @@
+//! Elimination brackets - knockout tournaments across AI profiles
+//!
+//! `--bracket [single|double]` seeds the profiles (`--seeding listed|random|ranked`)
+//! and plays them off in series of `--bracket-games N` matches. Single
+//! elimination knocks a profile out on its first lost series. Double elimination
+//! drops it to the losers bracket first, and the losers-bracket champion has to
+//! beat the winners-bracket champion twice in the grand final. Fields that
+//! aren't a power of two give the top seeds first-round byes.
+//!
+//! A series goes to the side with more wins, then the better goal difference,
+//! then the better seed. Every series is stored in `bracket_series` next to its
+//! matches, and `format_report` prints the rounds, champion and placings.
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+use super::metrics::MatchResult;
+
+/// Knockout format
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BracketFormat {
+    /// One lost series and you're out
+    #[default]
+    Single,
+    /// Out after two lost series (winners and losers brackets)
+    Double,
+}
+
+impl BracketFormat {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "single" => Some(Self::Single),
+            "double" => Some(Self::Double),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Single => "single",
+            Self::Double => "double",
+        }
+    }
+}
+
+/// How entrants are seeded
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BracketSeeding {
+    /// `--profiles` order (or the profile file's order)
+    #[default]
+    Listed,
+    /// Shuffled with the run's seed
+    Random,
+    /// Best win rate in the `--db` database first (profiles without history last)
+    Ranked,
+}
+
+impl BracketSeeding {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "listed" => Some(Self::Listed),
+            "random" => Some(Self::Random),
+            "ranked" => Some(Self::Ranked),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Listed => "listed",
+            Self::Random => "random",
+            Self::Ranked => "ranked",
+        }
+    }
+}
+
+/// Part of the bracket a series is played in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BracketSide {
+    Winners,
+    Losers,
+    GrandFinal,
+}
+
+impl BracketSide {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "winners" => Some(Self::Winners),
+            "losers" => Some(Self::Losers),
+            "final" => Some(Self::GrandFinal),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Winners => "winners",
+            Self::Losers => "losers",
+            Self::GrandFinal => "final",
+        }
+    }
+}
+
+/// Matches won by each side of a series, and the left side's goal difference
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SeriesScore {
+    pub left_wins: u32,
+    pub right_wins: u32,
+    pub goal_diff: i32,
+}
+
+impl SeriesScore {
+    /// Tally a series played with the left entrant on the left side
+    pub fn from_results(results: &[MatchResult]) -> Self {
+        let mut score = Self::default();
+        for result in results {
+            match result.winner.as_str() {
+                "left" => score.left_wins += 1,
+                "right" => score.right_wins += 1,
+                _ => {}
+            }
+            score.goal_diff += result.score_left as i32 - result.score_right as i32;
+        }
+        score
+    }
+
+    /// More wins, then goal difference; a dead heat goes to the left (better) seed
+    pub fn left_advances(&self) -> bool {
+        (self.left_wins, self.goal_diff) >= (self.right_wins, 0)
+    }
+}
+
+/// One played series. `left` is the better seed, except in the grand final,
+/// where it's the winners-bracket champion.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BracketSeries {
+    pub side: BracketSide,
+    /// 1-based round within its side (the grand final reset is round 2)
+    pub round: u32,
+    pub left: String,
+    pub left_seed: u32,
+    pub right: String,
+    pub right_seed: u32,
+    pub winner: String,
+    pub left_wins: u32,
+    pub right_wins: u32,
+}
+
+/// A played bracket
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BracketResult {
+    pub format: BracketFormat,
+    pub seeding: BracketSeeding,
+    /// Matches per series
+    pub games: u32,
+    /// Entrants in seed order (seed = index + 1)
+    pub seeds: Vec<String>,
+    pub series: Vec<BracketSeries>,
+    /// Champion first; everyone else by how late they went out
+    pub placings: Vec<String>,
+}
+
+/// Order `profiles` by `seeding`. `win_rate` is only asked for ranked seeding.
+pub fn seed_entrants(
+    profiles: &[String],
+    seeding: BracketSeeding,
+    rng_seed: u64,
+    win_rate: impl Fn(&str) -> Option<f64>,
+) -> Vec<String> {
+    let mut seeds = profiles.to_vec();
+    match seeding {
+        BracketSeeding::Listed => {}
+        BracketSeeding::Random => seeds.shuffle(&mut StdRng::seed_from_u64(rng_seed)),
+        BracketSeeding::Ranked => {
+            let mut ranked: Vec<(String, Option<f64>)> = seeds
+                .into_iter()
+                .map(|p| {
+                    let rate = win_rate(&p);
+                    (p, rate)
+                })
+                .collect();
+            // Stable: profiles without history keep their listed order at the end
+            ranked.sort_by(|a, b| match (a.1, b.1) {
+                (Some(a), Some(b)) => b.partial_cmp(&a).unwrap_or(std::cmp::Ordering::Equal),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            });
+            seeds = ranked.into_iter().map(|(p, _)| p).collect();
+        }
+    }
+    seeds
+}
+
+/// Seed numbers (1-based) in bracket order for a field of `size` (a power of
+/// two): 1 and 2 can only meet in the final, e.g. 1 8 4 5 2 7 3 6
+fn bracket_order(size: usize) -> Vec<usize> {
+    let mut order = vec![1];
+    while order.len() < size {
+        let pair_sum = order.len() * 2 + 1;
+        order = order.iter().flat_map(|&s| [s, pair_sum - s]).collect();
+    }
+    order
+}
+
+/// Play a bracket over `seeds` (best first). `play(side, round, left, right)`
+/// plays one series with `left` on the left side and returns its score.
+pub fn play_bracket(
+    seeds: Vec<String>,
+    format: BracketFormat,
+    seeding: BracketSeeding,
+    games: u32,
+    mut play: impl FnMut(BracketSide, u32, &str, &str) -> SeriesScore,
+) -> BracketResult {
+    let mut bracket = BracketResult {
+        format,
+        seeding,
+        games,
+        seeds,
+        ..Default::default()
+    };
+    // Entrants are seed indices from here on; returns (winner, loser)
+    let mut series = |bracket: &mut BracketResult,
+                      side: BracketSide,
+                      round: u32,
+                      left: usize,
+                      right: usize|
+     -> (usize, usize) {
+        let score = play(side, round, &bracket.seeds[left], &bracket.seeds[right]);
+        let (winner, loser) = if score.left_advances() {
+            (left, right)
+        } else {
+            (right, left)
+        };
+        bracket.series.push(BracketSeries {
+            side,
+            round,
+            left: bracket.seeds[left].clone(),
+            left_seed: left as u32 + 1,
+            right: bracket.seeds[right].clone(),
+            right_seed: right as u32 + 1,
+            winner: bracket.seeds[winner].clone(),
+            left_wins: score.left_wins,
+            right_wins: score.right_wins,
+        });
+        (winner, loser)
+    };
+
+    let count = bracket.seeds.len();
+    if count == 0 {
+        return bracket;
+    }
+    // None is a bye; byes only ever face the top seeds
+    let mut winners: Vec<Option<usize>> = bracket_order(count.next_power_of_two())
+        .into_iter()
+        .map(|seed| (seed <= count).then_some(seed - 1))
+        .collect();
+    let mut losers: Vec<usize> = Vec::new();
+    // In the order they went out
+    let mut eliminated: Vec<usize> = Vec::new();
+    let (mut winners_round, mut losers_round) = (0, 0);
+
+    while winners.len() > 1 {
+        winners_round += 1;
+        let mut next = Vec::new();
+        let mut dropped = Vec::new();
+        for pair in winners.chunks(2) {
+            match (pair[0], pair[1]) {
+                (Some(left), Some(right)) => {
+                    let (winner, loser) = series(
+                        &mut bracket,
+                        BracketSide::Winners,
+                        winners_round,
+                        left,
+                        right,
+                    );
+                    next.push(Some(winner));
+                    dropped.push(loser);
+                }
+                (left, right) => next.push(left.or(right)),
+            }
+        }
+        winners = next;
+        match format {
+            BracketFormat::Single => eliminated.extend(dropped),
+            BracketFormat::Double => {
+                losers.extend(dropped);
+                // Keep playing the losers bracket down once the winners side is done
+                while losers.len() > 1 && (winners.len() == 1 || losers_round < winners_round) {
+                    losers_round += 1;
+                    losers = losers_bracket_round(&mut losers, |left, right| {
+                        let (winner, loser) =
+                            series(&mut bracket, BracketSide::Losers, losers_round, left, right);
+                        eliminated.push(loser);
+                        winner
+                    });
+                }
+            }
+        }
+    }
+
+    let Some(mut champion) = winners[0] else {
+        return bracket;
+    };
+    if let Some(&challenger) = losers.first() {
+        // Grand final: the winners-bracket champion has no loss yet, so a
+        // losers-bracket win forces a deciding reset series
+        let unbeaten = champion;
+        champion = series(
+            &mut bracket,
+            BracketSide::GrandFinal,
+            1,
+            unbeaten,
+            challenger,
+        )
+        .0;
+        if champion == challenger {
+            champion = series(
+                &mut bracket,
+                BracketSide::GrandFinal,
+                2,
+                unbeaten,
+                challenger,
+            )
+            .0;
+        }
+        eliminated.push(if champion == challenger {
+            unbeaten
+        } else {
+            challenger
+        });
+    }
+
+    bracket.placings = std::iter::once(champion)
+        .chain(eliminated.into_iter().rev())
+        .map(|i| bracket.seeds[i].clone())
+        .collect();
+    bracket
+}
+
+/// One losers-bracket round: best remaining seed against worst, the best
+/// taking the bye when the pool is odd. Returns the survivors.
+fn losers_bracket_round(
+    pool: &mut [usize],
+    mut play: impl FnMut(usize, usize) -> usize,
+) -> Vec<usize> {
+    pool.sort();
+    let mut survivors = Vec::new();
+    let mut rest: &[usize] = pool;
+    if rest.len() % 2 == 1 {
+        survivors.push(rest[0]);
+        rest = &rest[1..];
+    }
+    for i in 0..rest.len() / 2 {
+        survivors.push(play(rest[i], rest[rest.len() - 1 - i]));
+    }
+    survivors
+}
+
+/// Matches a bracket of `entrants` plays at most (double elimination counts
+/// the grand final reset)
+pub fn planned_matches(entrants: usize, format: BracketFormat, games: u32) -> usize {
+    let series = match format {
+        BracketFormat::Single => entrants.saturating_sub(1),
+        BracketFormat::Double if entrants > 1 => entrants * 2 - 1,
+        BracketFormat::Double => 0,
+    };
+    series * games as usize
+}
+
+/// Seeds, every series by round, champion and placings
+pub fn format_report(bracket: &BracketResult) -> String {
+    let mut out = format!(
+        "\nBRACKET ({} elimination, {} profiles, {} seeding, {} game(s) per series)\n",
+        bracket.format.as_str(),
+        bracket.seeds.len(),
+        bracket.seeding.as_str(),
+        bracket.games
+    );
+    out.push_str("Seeds:\n");
+    for (i, profile) in bracket.seeds.iter().enumerate() {
+        out.push_str(&format!("  {:>2}. {}\n", i + 1, profile));
+    }
+
+    let mut heading = None;
+    for series in &bracket.series {
+        if heading != Some((series.side, series.round)) {
+            heading = Some((series.side, series.round));
+            out.push_str(&match series.side {
+                BracketSide::Winners if bracket.format == BracketFormat::Single => {
+                    format!("Round {}\n", series.round)
+                }
+                BracketSide::Winners => format!("Winners round {}\n", series.round),
+                BracketSide::Losers => format!("Losers round {}\n", series.round),
+                BracketSide::GrandFinal if series.round > 1 => "Grand final (reset)\n".to_string(),
+                BracketSide::GrandFinal => "Grand final\n".to_string(),
+            });
+        }
+        let (loser, loser_seed, winner_seed) = if series.winner == series.left {
+            (&series.right, series.right_seed, series.left_seed)
+        } else {
+            (&series.left, series.left_seed, series.right_seed)
+        };
+        let (high, low) = (
+            series.left_wins.max(series.right_wins),
+            series.left_wins.min(series.right_wins),
+        );
+        out.push_str(&format!(
+            "  {} ({}) def. {} ({})  {}-{}\n",
+            series.winner, winner_seed, loser, loser_seed, high, low
+        ));
+    }
+
+    if let Some(champion) = bracket.placings.first() {
+        out.push_str(&format!("Champion: {}\n", champion));
+        out.push_str("Placings:\n");
+        for (i, profile) in bracket.placings.iter().enumerate() {
+            out.push_str(&format!("  {:>2}. {}\n", i + 1, profile));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(count: usize) -> Vec<String> {
+        (1..=count).map(|i| format!("P{}", i)).collect()
+    }
+
+    /// Better seed always wins, except that `upset` beats everyone
+    fn favorite_wins(
+        upset: &'static str,
+    ) -> impl FnMut(BracketSide, u32, &str, &str) -> SeriesScore {
+        move |_: BracketSide, _: u32, _left: &str, right: &str| {
+            let left_wins = right != upset;
+            SeriesScore {
+                left_wins: left_wins as u32 * 2,
+                right_wins: !left_wins as u32 * 2,
+                goal_diff: 0,
+            }
+        }
+    }
+
+    #[test]
+    fn test_single_and_double_elimination() {
+        assert_eq!(bracket_order(8), [1, 8, 4, 5, 2, 7, 3, 6]);
+
+        // 6 entrants: seeds 1 and 2 get byes; 5 series, favorite takes it
+        let single = play_bracket(
+            names(6),
+            BracketFormat::Single,
+            BracketSeeding::Listed,
+            2,
+            favorite_wins(""),
+        );
+        assert_eq!(single.series.len(), 5);
+        assert_eq!(single.series[0].left, "P4");
+        assert_eq!(single.series[0].right, "P5");
+        assert_eq!(single.placings[0], "P1");
+        assert_eq!(single.placings[1], "P2");
+        assert_eq!(single.placings.len(), 6);
+
+        // Double: P3 beats everyone it meets as the right side; losing P1 drops
+        // it to the losers bracket, and the grand final is P3 vs the survivor
+        let double = play_bracket(
+            names(4),
+            BracketFormat::Double,
+            BracketSeeding::Listed,
+            2,
+            favorite_wins("P3"),
+        );
+        let finals: Vec<_> = double
+            .series
+            .iter()
+            .filter(|s| s.side == BracketSide::GrandFinal)
+            .collect();
+        assert_eq!(finals.len(), 1);
+        assert_eq!(finals[0].left, "P3");
+        assert_eq!(double.placings[0], "P3");
+        assert_eq!(double.placings.len(), 4);
+        assert!(double.series.len() <= planned_matches(4, BracketFormat::Double, 1));
+        // Nobody is knocked out before their second loss
+        for profile in &double.seeds[1..] {
+            let losses = double
+                .series
+                .iter()
+                .filter(|s| (s.left == *profile || s.right == *profile) && s.winner != *profile)
+                .count();
+            assert_eq!(losses, 2, "{} lost {} series", profile, losses);
+        }
+
+        // Ranked seeding puts known win rates first, unknown profiles last in order
+        let seeds = seed_entrants(&names(4), BracketSeeding::Ranked, 0, |p| match p {
+            "P3" => Some(0.9),
+            "P4" => Some(0.5),
+            _ => None,
+        });
+        assert_eq!(seeds, ["P3", "P4", "P1", "P2"]);
+        let shuffled = seed_entrants(&names(8), BracketSeeding::Random, 7, |_| None);
+        assert_eq!(
+            shuffled,
+            seed_entrants(&names(8), BracketSeeding::Random, 7, |_| None)
+        );
+        assert!(format_report(&double).contains("Champion: P3"));
+    }
+}
@@ -0,0 +1,339 @@
+//! Halftime coach - adjusts AI profiles between halves
+//!
+//! At half the match duration, the coach reads the first half's stats (where and
+//! how well the opponent shot, how the player's steals and shots went) and nudges
+//! the player's profile for the second half. Every change is a small, bounded step
+//! and is logged as a `CoachAdjustment` event, so coached matches in the DB can be
+//! compared against uncoached ones.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::ai::{AiProfile, AiProfileDatabase, AiState, SHOT_QUALITY_GOOD};
+use crate::events::{GameEvent, PlayerId, intern};
+use crate::player::{Player, TargetBasket, Team};
+use crate::world::Basket;
+
+use super::control::{SimControl, SimEventBuffer};
+use super::metrics::{PlayerStats, SimMetrics};
+
+/// Fewer shots/steals than this in a half aren't enough to react to
+const MIN_SAMPLE: u32 = 3;
+
+/// Which sides get a halftime coach
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CoachSides {
+    Left,
+    Right,
+    Both,
+}
+
+impl CoachSides {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "left" | "l" => Some(Self::Left),
+            "right" | "r" => Some(Self::Right),
+            "both" => Some(Self::Both),
+            _ => None,
+        }
+    }
+
+    pub fn covers(&self, team: Team) -> bool {
+        matches!(
+            (self, team),
+            (Self::Both, _) | (Self::Left, Team::Left) | (Self::Right, Team::Right)
+        )
+    }
+}
+
+/// One bounded change to a profile parameter
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoachAdjustment {
+    pub param: &'static str,
+    pub from: f32,
+    pub to: f32,
+    pub reason: String,
+}
+
+/// What the coach saw in the first half, from one player's point of view
+#[derive(Debug, Clone, Default)]
+pub struct HalfReport {
+    pub own_shots: u32,
+    pub own_makes: u32,
+    pub steal_attempts: u32,
+    pub steals_won: u32,
+    pub opp_shots: u32,
+    pub opp_avg_quality: f32,
+    /// Opponent's average horizontal shot distance from the basket we defend
+    pub opp_shot_distance: Option<f32>,
+}
+
+impl HalfReport {
+    pub fn from_stats(
+        own: &PlayerStats,
+        opp: &PlayerStats,
+        defended_basket_x: Option<f32>,
+    ) -> Self {
+        let opp_shots = opp.shots_attempted;
+        let avg = |sum: f32| {
+            if opp_shots > 0 {
+                sum / opp_shots as f32
+            } else {
+                0.0
+            }
+        };
+        Self {
+            own_shots: own.shots_attempted,
+            own_makes: own.shots_made,
+            steal_attempts: own.steals_attempted,
+            steals_won: own.steals_successful,
+            opp_shots,
+            opp_avg_quality: avg(opp.shot_quality_sum),
+            opp_shot_distance: defended_basket_x
+                .filter(|_| opp_shots > 0)
+                .map(|x| (avg(opp.shot_positions_sum_x) - x).abs()),
+        }
+    }
+}
+
+/// Decide this half's adjustments. Each parameter moves one fixed step, clamped
+/// to a sane range, and only when the first half gave enough evidence.
+pub fn plan_adjustments(profile: &AiProfile, half: &HalfReport) -> Vec<CoachAdjustment> {
+    let mut adjustments = Vec::new();
+    let mut adjust =
+        |param: &'static str, from: f32, step: f32, range: (f32, f32), reason: String| {
+            let to = (from + step).clamp(range.0, range.1);
+            if (to - from).abs() > f32::EPSILON {
+                adjustments.push(CoachAdjustment {
+                    param,
+                    from,
+                    to,
+                    reason,
+                });
+            }
+        };
+
+    // Opponent getting good looks: defend tighter and smarter
+    if half.opp_shots >= MIN_SAMPLE && half.opp_avg_quality >= SHOT_QUALITY_GOOD {
+        let reason = format!(
+            "opponent shot quality {:.2} over {} shots",
+            half.opp_avg_quality, half.opp_shots
+        );
+        adjust(
+            "defensive_iq",
+            profile.defensive_iq,
+            0.15,
+            (0.0, 1.0),
+            reason.clone(),
+        );
+        adjust(
+            "pressure_distance",
+            profile.pressure_distance,
+            -20.0,
+            (60.0, 250.0),
+            reason,
+        );
+    }
+
+    // Meet the opponent where they shoot from
+    if half.opp_shots >= MIN_SAMPLE
+        && let Some(distance) = half.opp_shot_distance
+    {
+        let step = if distance > profile.defense_offset + 100.0 {
+            50.0
+        } else if distance < profile.defense_offset - 100.0 {
+            -50.0
+        } else {
+            0.0
+        };
+        adjust(
+            "defense_offset",
+            profile.defense_offset,
+            step,
+            (150.0, 700.0),
+            format!("opponent shoots from {:.0}px out", distance),
+        );
+    }
+
+    // Steals: lean in when they work, back off when they don't
+    if half.steal_attempts >= MIN_SAMPLE {
+        let rate = half.steals_won as f32 / half.steal_attempts as f32;
+        let step = if rate < 0.25 {
+            -0.15
+        } else if rate > 0.5 {
+            0.15
+        } else {
+            0.0
+        };
+        adjust(
+            "aggression",
+            profile.aggression,
+            step,
+            (0.1, 1.0),
+            format!("steals {}/{}", half.steals_won, half.steal_attempts),
+        );
+    }
+
+    // Shooting: be pickier when missing, less picky when not shooting at all
+    if half.own_shots >= MIN_SAMPLE && (half.own_makes as f32 / half.own_shots as f32) < 0.2 {
+        adjust(
+            "min_shot_quality",
+            profile.min_shot_quality,
+            0.05,
+            (0.2, 0.8),
+            format!("made {}/{} shots", half.own_makes, half.own_shots),
+        );
+    } else if half.own_shots < 2 {
+        adjust(
+            "min_shot_quality",
+            profile.min_shot_quality,
+            -0.05,
+            (0.2, 0.8),
+            format!("only {} shots", half.own_shots),
+        );
+    }
+
+    adjustments
+}
+
+fn apply_adjustment(profile: &mut AiProfile, adjustment: &CoachAdjustment) {
+    let value = adjustment.to;
+    match adjustment.param {
+        "defensive_iq" => profile.defensive_iq = value,
+        "pressure_distance" => profile.pressure_distance = value,
+        "defense_offset" => profile.defense_offset = value,
+        "aggression" => profile.aggression = value,
+        "min_shot_quality" => profile.min_shot_quality = value,
+        _ => {}
+    }
+}
+
+/// Whether the halftime adjustments have been made this match
+#[derive(Resource, Default)]
+pub struct CoachState {
+    pub done: bool,
+}
+
+/// At half the duration limit, adjust each coached player's profile once
+pub fn sim_coach_halftime(
+    control: Res<SimControl>,
+    metrics: Res<SimMetrics>,
+    mut state: ResMut<CoachState>,
+    mut profile_db: ResMut<AiProfileDatabase>,
+    mut event_buffer: ResMut<SimEventBuffer>,
+    mut players: Query<(&Team, &TargetBasket, &mut AiState), With<Player>>,
+    baskets: Query<(&Transform, &Basket)>,
+) {
+    let Some(sides) = control.config.coach else {
+        return;
+    };
+    if state.done || metrics.elapsed < control.config.duration_limit / 2.0 {
+        return;
+    }
+    state.done = true;
+
+    // The basket each team's opponent shoots at is the one this team defends
+    let target_x = |team: Team| {
+        players
+            .iter()
+            .find(|(t, _, _)| **t == team)
+            .and_then(|(_, target, _)| baskets.iter().find(|(_, b)| **b == target.0))
+            .map(|(transform, _)| transform.translation.x)
+    };
+    let defended_x = [target_x(Team::Right), target_x(Team::Left)];
+
+    for (team, _, mut ai_state) in &mut players {
+        if !sides.covers(*team) {
+            continue;
+        }
+        let (own, opp, defended, player_id, suffix) = match team {
+            Team::Left => (
+                &metrics.left,
+                &metrics.right,
+                defended_x[0],
+                PlayerId::L,
+                "L",
+            ),
+            Team::Right => (
+                &metrics.right,
+                &metrics.left,
+                defended_x[1],
+                PlayerId::R,
+                "R",
+            ),
+        };
+        let Some(profile) = profile_db.get_by_id(&ai_state.profile_id).cloned() else {
+            continue;
+        };
+        let half = HalfReport::from_stats(own, opp, defended);
+        let adjustments = plan_adjustments(&profile, &half);
+        if adjustments.is_empty() {
+            continue;
+        }
+
+        // Profiles are resolved by ID, so the coached copy needs its own
+        let mut coached = profile.clone();
+        coached.name = format!("{}@coach{}", profile.name, suffix);
+        coached.id = format!("{:.12}c{}", profile.id, suffix);
+        for adjustment in &adjustments {
+            apply_adjustment(&mut coached, adjustment);
+            if event_buffer.enabled {
+                event_buffer.buffer.log(
+                    metrics.elapsed,
+                    GameEvent::CoachAdjustment {
+                        player: player_id,
+                        param: intern(adjustment.param),
+                        from: adjustment.from,
+                        to: adjustment.to,
+                        reason: adjustment.reason.as_str().into(),
+                    },
+                );
+            }
+        }
+        ai_state.profile_id = coached.id.clone();
+        profile_db.upsert(coached);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_adjustments_reacts_and_stays_bounded() {
+        let profile = AiProfile {
+            aggression: 0.2,
+            ..Default::default()
+        };
+        let half = HalfReport {
+            own_shots: 5,
+            own_makes: 0,
+            steal_attempts: 6,
+            steals_won: 0,
+            opp_shots: 4,
+            opp_avg_quality: 0.7,
+            opp_shot_distance: Some(400.0),
+        };
+        let adjustments = plan_adjustments(&profile, &half);
+        let params: Vec<_> = adjustments.iter().map(|a| a.param).collect();
+        assert_eq!(
+            params,
+            vec![
+                "defensive_iq",
+                "pressure_distance",
+                "aggression",
+                "min_shot_quality"
+            ]
+        );
+        // 0.2 - 0.15 is clamped to the 0.1 floor
+        assert_eq!(adjustments[2].to, 0.1);
+
+        // A quiet half gives no evidence to act on
+        let quiet = HalfReport {
+            own_shots: 3,
+            own_makes: 1,
+            ..Default::default()
+        };
+        assert!(plan_adjustments(&profile, &quiet).is_empty());
+    }
+}
@@ -2,6 +2,18 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::ai::DefenderPlacement;
+use crate::ai::contested_quality::default_placements;
+use crate::analytics::UploadConfig;
+use crate::db_maintenance::latest_training_db;
+use crate::debug_logging::{DebugLogConfig, DebugSamplePlayers};
+use crate::events::EventCategory;
+use crate::scoring::BallResetMode;
+use crate::snapshot::SpectateConfig;
+
+use super::bracket::{BracketFormat, BracketSeeding};
+use super::coach::CoachSides;
+
 /// Simulation mode
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub enum SimMode {
@@ -36,6 +48,32 @@ pub enum SimMode {
         /// Path to SQLite database with exploration data
         db_path: String,
     },
+    /// Self-play curriculum - promote candidates through staged difficulty
+    Curriculum {
+        /// Path to curriculum JSON (None = config/curriculum.json or built-in)
+        path: Option<String>,
+    },
+    /// Left/right symmetry check - each seed played twice with sides swapped
+    MirrorTest { pairs: u32 },
+    /// Scripted matchups in order, from a schedule file or the side profile lists
+    Schedule {
+        /// Path to schedule JSON (None = pair `left_profiles` with `right_profiles`)
+        path: Option<String>,
+        /// Times each matchup is played unless it sets its own `count`
+        count: u32,
+    },
+    /// Single- or double-elimination bracket across profiles
+    Bracket {
+        format: BracketFormat,
+        seeding: BracketSeeding,
+        /// Matches per series
+        games: u32,
+    },
+    /// Serve a gym env (JSON lines on stdin/stdout) for an external RL trainer
+    Gym {
+        /// Trainer controls the left side (else the right)
+        left: bool,
+    },
 }
 
 /// Configuration for a simulation run
@@ -49,6 +87,13 @@ pub struct SimConfig {
     pub left_profile: String,
     /// Right player AI profile name
     pub right_profile: String,
+    /// Left profiles for a scheduled run, paired by index with `right_profiles`
+    /// ("human" = a side no AI drives)
+    #[serde(default)]
+    pub left_profiles: Vec<String>,
+    /// Right profiles for a scheduled run
+    #[serde(default)]
+    pub right_profiles: Vec<String>,
     /// Match duration limit in seconds
     pub duration_limit: f32,
     /// Score limit (first to reach wins, 0 = no limit)
@@ -78,6 +123,66 @@ pub struct SimConfig {
     /// Enable debug sample logging
     #[serde(default)]
     pub debug_log: bool,
+    /// Override `tick_interval_ms` from config/debug_logging.json
+    #[serde(default)]
+    pub log_tick_ms: Option<u32>,
+    /// Override the persisted event categories from config/debug_logging.json
+    #[serde(default)]
+    pub log_categories: Option<Vec<EventCategory>>,
+    /// Override which players debug samples are recorded for
+    #[serde(default)]
+    pub debug_samples: Option<DebugSamplePlayers>,
+    /// Tied at the time limit -> sudden-death overtime with a shrinking court
+    #[serde(default)]
+    pub overtime: bool,
+    /// Dead balls (stuck-ball resets, simultaneous pickups) go by alternating possession arrow
+    #[serde(default)]
+    pub possession_arrow: bool,
+    /// Consecutive goals grant the scoring team a brief hot-streak buff
+    #[serde(default)]
+    pub momentum: bool,
+    /// Successful steals knock the ball loose and both players scramble for it
+    #[serde(default)]
+    pub loose_ball: bool,
+    /// Defensive three seconds: a defender camping on their own rim stops blocking carry-ins
+    #[serde(default)]
+    pub rim_camping_violation: bool,
+    /// Shot clock: seconds a side may keep the ball without shooting (None = off)
+    #[serde(default)]
+    pub shot_clock: Option<f32>,
+    /// A ball leaving the court is a turnover; the other side throws in
+    #[serde(default)]
+    pub out_of_bounds: bool,
+    /// Where play restarts after a goal (center, conceding-team inbound, random spot)
+    #[serde(default)]
+    pub ball_reset: BallResetMode,
+    /// Make nav edges the AI keeps failing more expensive for pathfinding
+    #[serde(default)]
+    pub nav_edge_penalty: bool,
+    /// Keep playing degenerate matches (no shots, both stuck, ball unreachable) to the end
+    #[serde(default)]
+    pub allow_degenerate: bool,
+    /// Publish periodic JSON snapshots for dashboards (needs the `spectate` feature)
+    #[serde(default)]
+    pub spectate: Option<SpectateConfig>,
+    /// Serve Prometheus metrics on this port (None = exporter off)
+    #[serde(default)]
+    pub metrics_port: Option<u16>,
+    /// Read batch control commands (pause, skip, status, ...) from stdin
+    #[serde(default)]
+    pub control: bool,
+    /// Also accept batch control commands on 127.0.0.1:PORT
+    #[serde(default)]
+    pub control_port: Option<u16>,
+    /// Post finished match results to a shared leaderboard
+    #[serde(default)]
+    pub upload: Option<UploadConfig>,
+    /// Halftime coach adjusts these sides' profiles for the second half
+    #[serde(default)]
+    pub coach: Option<CoachSides>,
+    /// Shot test: also shoot against a defender at each of these placements
+    #[serde(default)]
+    pub shot_defenders: Vec<DefenderPlacement>,
 }
 
 impl Default for SimConfig {
@@ -87,6 +192,8 @@ impl Default for SimConfig {
             level: None, // Random per match, excludes debug levels and Pit
             left_profile: "Balanced".to_string(),
             right_profile: "Balanced".to_string(),
+            left_profiles: Vec::new(),
+            right_profiles: Vec::new(),
             duration_limit: 60.0,
             score_limit: 0,
             seed: None,
@@ -100,6 +207,26 @@ impl Default for SimConfig {
             profiles: Vec::new(), // Empty = all profiles
             levels: Vec::new(),   // Empty = all non-debug levels
             debug_log: false,
+            log_tick_ms: None,
+            log_categories: None,
+            debug_samples: None,
+            overtime: false,
+            possession_arrow: false,
+            momentum: false,
+            loose_ball: false,
+            rim_camping_violation: false,
+            shot_clock: None,
+            out_of_bounds: false,
+            ball_reset: BallResetMode::Center,
+            nav_edge_penalty: false,
+            allow_degenerate: false,
+            spectate: None,
+            metrics_port: None,
+            control: false,
+            control_port: None,
+            upload: None,
+            coach: None,
+            shot_defenders: Vec::new(),
         }
     }
 }
@@ -134,8 +261,11 @@ impl SimConfig {
 
     /// Parse configuration from command line arguments
     pub fn from_args() -> Self {
-        let args: Vec<String> = std::env::args().collect();
+        Self::from_arg_list(&std::env::args().collect::<Vec<_>>())
+    }
 
+    /// Parse configuration from an argument list (first entry is the program name)
+    pub fn from_arg_list(args: &[String]) -> Self {
         // Start with config files as base
         let mut config = Self::from_config_files();
 
@@ -201,6 +331,34 @@ impl SimConfig {
                         i += 1;
                     }
                 }
+                "--left-profiles" | "--right-profiles" => {
+                    if i + 1 < args.len() {
+                        let list = args[i + 1]
+                            .split(',')
+                            .map(|s| s.trim().to_string())
+                            .filter(|s| !s.is_empty())
+                            .collect();
+                        if args[i] == "--left-profiles" {
+                            config.left_profiles = list;
+                        } else {
+                            config.right_profiles = list;
+                        }
+                        i += 1;
+                    }
+                }
+                "--schedule" => {
+                    if i + 1 < args.len() {
+                        let count = match config.mode {
+                            SimMode::MultiMatch { count } => count,
+                            _ => 1,
+                        };
+                        config.mode = SimMode::Schedule {
+                            path: Some(args[i + 1].clone()),
+                            count,
+                        };
+                        i += 1;
+                    }
+                }
                 "--duration" => {
                     if i + 1 < args.len() {
                         config.duration_limit = args[i + 1].parse().unwrap_or(60.0);
@@ -219,6 +377,90 @@ impl SimConfig {
                 "--debug-log" => {
                     config.debug_log = true;
                 }
+                "--log-tick-ms" => {
+                    if i + 1 < args.len() {
+                        config.log_tick_ms = args[i + 1].parse().ok().filter(|ms| *ms > 0);
+                        if config.log_tick_ms.is_none() {
+                            eprintln!("Warning: invalid --log-tick-ms '{}'", args[i + 1]);
+                        }
+                        i += 1;
+                    }
+                }
+                "--log-categories" => {
+                    if i + 1 < args.len() {
+                        match EventCategory::parse_list(&args[i + 1]) {
+                            Ok(categories) => config.log_categories = Some(categories),
+                            Err(e) => eprintln!("Warning: --log-categories: {}", e),
+                        }
+                        i += 1;
+                    }
+                }
+                "--debug-samples" => {
+                    if i + 1 < args.len() {
+                        config.debug_samples = DebugSamplePlayers::from_str(&args[i + 1]);
+                        if config.debug_samples.is_none() {
+                            eprintln!(
+                                "Warning: --debug-samples expects both, left, right or none (got '{}')",
+                                args[i + 1]
+                            );
+                        }
+                        i += 1;
+                    }
+                }
+                "--overtime" => {
+                    config.overtime = true;
+                }
+                "--possession-arrow" => {
+                    config.possession_arrow = true;
+                }
+                "--momentum" => {
+                    config.momentum = true;
+                }
+                "--loose-ball" => {
+                    config.loose_ball = true;
+                }
+                "--rim-camping-violation" => {
+                    config.rim_camping_violation = true;
+                }
+                "--shot-clock" => {
+                    if i + 1 < args.len() {
+                        config.shot_clock = args[i + 1].parse().ok();
+                        i += 1;
+                    }
+                }
+                "--out-of-bounds" => {
+                    config.out_of_bounds = true;
+                }
+                "--ball-reset" => {
+                    if i + 1 < args.len() {
+                        match BallResetMode::parse(&args[i + 1]) {
+                            Some(mode) => config.ball_reset = mode,
+                            None => eprintln!(
+                                "Warning: --ball-reset expects center, inbound or random (got '{}')",
+                                args[i + 1]
+                            ),
+                        }
+                        i += 1;
+                    }
+                }
+                "--nav-edge-penalty" => {
+                    config.nav_edge_penalty = true;
+                }
+                "--allow-degenerate" => {
+                    config.allow_degenerate = true;
+                }
+                "--coach" => {
+                    if i + 1 < args.len() {
+                        config.coach = CoachSides::parse(&args[i + 1]);
+                        if config.coach.is_none() {
+                            eprintln!(
+                                "Warning: --coach expects left, right or both (got '{}')",
+                                args[i + 1]
+                            );
+                        }
+                        i += 1;
+                    }
+                }
                 "--score-limit" => {
                     if i + 1 < args.len() {
                         config.score_limit = args[i + 1].parse().unwrap_or(0);
@@ -228,7 +470,10 @@ impl SimConfig {
                 "--matches" => {
                     if i + 1 < args.len() {
                         let count = args[i + 1].parse().unwrap_or(1);
-                        config.mode = SimMode::MultiMatch { count };
+                        config.mode = match config.mode {
+                            SimMode::Schedule { path, .. } => SimMode::Schedule { path, count },
+                            _ => SimMode::MultiMatch { count },
+                        };
                         i += 1;
                     }
                 }
@@ -243,6 +488,26 @@ impl SimConfig {
                         matches_per_pair: matches,
                     };
                 }
+                "--bracket" => {
+                    let format = match args.get(i + 1).filter(|a| !a.starts_with('-')) {
+                        Some(value) => {
+                            i += 1;
+                            BracketFormat::parse(value).unwrap_or_else(|| {
+                                eprintln!(
+                                    "Warning: unknown bracket format '{}' (single, double)",
+                                    value
+                                );
+                                BracketFormat::Single
+                            })
+                        }
+                        None => BracketFormat::Single,
+                    };
+                    config.mode = SimMode::Bracket {
+                        format,
+                        seeding: BracketSeeding::Listed,
+                        games: 3,
+                    };
+                }
                 "--level-sweep" => {
                     let matches = if i + 1 < args.len() && !args[i + 1].starts_with('-') {
                         i += 1;
@@ -268,6 +533,18 @@ impl SimConfig {
                         shots_per_position: shots,
                     };
                 }
+                "--defender" => {
+                    if i + 1 < args.len() {
+                        match DefenderPlacement::parse(&args[i + 1]) {
+                            Ok(placement) => config.shot_defenders.push(placement),
+                            Err(e) => eprintln!("Warning: --defender: {}", e),
+                        }
+                        i += 1;
+                    }
+                }
+                "--contested" => {
+                    config.shot_defenders.extend(default_placements());
+                }
                 "--ghost" => {
                     if i + 1 < args.len() {
                         config.mode = SimMode::GhostTrial {
@@ -276,6 +553,40 @@ impl SimConfig {
                         i += 1;
                     }
                 }
+                "--curriculum" => {
+                    let path = if i + 1 < args.len() && !args[i + 1].starts_with('-') {
+                        i += 1;
+                        Some(args[i].clone())
+                    } else {
+                        None
+                    };
+                    config.mode = SimMode::Curriculum { path };
+                }
+                "--mirror-test" => {
+                    let pairs = if i + 1 < args.len() && !args[i + 1].starts_with('-') {
+                        i += 1;
+                        args[i].parse().unwrap_or(20)
+                    } else {
+                        20
+                    };
+                    config.mode = SimMode::MirrorTest { pairs };
+                }
+                "--gym" => {
+                    let side = if i + 1 < args.len() && !args[i + 1].starts_with('-') {
+                        i += 1;
+                        args[i].as_str()
+                    } else {
+                        "left"
+                    };
+                    if side != "left" && side != "right" {
+                        eprintln!("Warning: --gym expects left or right (got '{}')", side);
+                    }
+                    config.mode = SimMode::Gym {
+                        left: side != "right",
+                    };
+                    // stdout carries the protocol
+                    config.quiet = true;
+                }
                 "--multihop-test" => {
                     config.mode = SimMode::MultihopTest;
                 }
@@ -285,7 +596,7 @@ impl SimConfig {
                     let db_path = config
                         .db_path
                         .clone()
-                        .unwrap_or_else(|| "db/training.db".to_string());
+                        .unwrap_or_else(|| latest_training_db().display().to_string());
                     config.mode = SimMode::ReachabilityTest { samples, db_path };
                 }
                 "--samples" => {
@@ -313,6 +624,21 @@ impl SimConfig {
                         i += 1;
                     }
                 }
+                "--metrics-port" => {
+                    if i + 1 < args.len() {
+                        config.metrics_port = args[i + 1].parse().ok();
+                        i += 1;
+                    }
+                }
+                "--control" => {
+                    config.control = true;
+                }
+                "--control-port" => {
+                    if i + 1 < args.len() {
+                        config.control_port = args[i + 1].parse().ok();
+                        i += 1;
+                    }
+                }
                 "--quiet" | "-q" => {
                     config.quiet = true;
                 }
@@ -345,8 +671,67 @@ impl SimConfig {
             i += 1;
         }
 
+        if let Some(spectate) = SpectateConfig::from_args(args) {
+            config.spectate = Some(spectate);
+        }
+        config.upload = UploadConfig::from_args(args);
+
+        // Bracket options can come before or after --bracket
+        if let SimMode::Bracket { seeding, games, .. } = &mut config.mode {
+            let value = |flag: &str| {
+                args.iter()
+                    .position(|a| a == flag)
+                    .and_then(|i| args.get(i + 1))
+            };
+            if let Some(count) = value("--bracket-games").and_then(|v| v.parse::<u32>().ok()) {
+                *games = count.max(1);
+            }
+            if let Some(mode) = value("--seeding") {
+                match BracketSeeding::parse(mode) {
+                    Some(mode) => *seeding = mode,
+                    None => eprintln!(
+                        "Warning: unknown --seeding '{}' (listed, random, ranked)",
+                        mode
+                    ),
+                }
+            }
+        }
+
+        // Side lists turn a single/multi-match run into a scheduled one
+        let has_lists = !config.left_profiles.is_empty() || !config.right_profiles.is_empty();
+        match config.mode {
+            SimMode::Single if has_lists => {
+                config.mode = SimMode::Schedule {
+                    path: None,
+                    count: 1,
+                };
+            }
+            SimMode::MultiMatch { count } if has_lists => {
+                config.mode = SimMode::Schedule { path: None, count };
+            }
+            _ => {}
+        }
+
         config
     }
+
+    /// config/debug_logging.json with this run's logging overrides applied
+    pub fn debug_log_config(&self) -> DebugLogConfig {
+        let mut debug_config = DebugLogConfig::load();
+        if self.debug_log {
+            debug_config.enabled = true;
+        }
+        if let Some(ms) = self.log_tick_ms {
+            debug_config.tick_interval_ms = ms;
+        }
+        if let Some(categories) = &self.log_categories {
+            debug_config.event_categories = categories.clone();
+        }
+        if let Some(players) = self.debug_samples {
+            debug_config.debug_sample_players = players;
+        }
+        debug_config
+    }
 }
 
 fn print_help() {
@@ -358,21 +743,55 @@ USAGE:
 
 OPTIONS:
     --settings <FILE>   Load settings from JSON file (CLI args override file settings)
+    --config-dir <DIR>  Use levels/profiles/tuning from DIR (missing files fall back to config/)
     --level <N>         Level number (1-12, default: random per match)
     --levels <LIST>     Comma-separated level numbers to use (e.g., "3,4,7,11")
     --profiles <LIST>   Comma-separated profile names for tournament (e.g., "v4_RP_Gamma,v4_Elite_A")
     --left <PROFILE>    Left player AI profile (default: Balanced)
     --right <PROFILE>   Right player AI profile (default: Balanced)
+    --left-profiles <LIST>  Left profiles, paired by index with --right-profiles (the
+    --right-profiles <LIST> shorter list repeats); each pairing plays --matches N times.
+                        "human" is a side no AI drives
+    --schedule <FILE>   Play the scripted matchups in FILE (per-match left, right, level,
+                        seed, duration_limit, score_limit, count; --matches N is the
+                        default count)
     --duration <SECS>   Match duration limit in seconds (default: 60)
     --est-run-time      Estimate runtime from prior sessions and exit
     --run-timeout <SECS> Wall-clock timeout for tournament run (default: 600)
     --score-limit <N>   End match when a player reaches N points (default: no limit)
+    --overtime          Play sudden-death overtime (shrinking court) if tied at the time limit
+    --possession-arrow  Award stuck balls and simultaneous pickups by alternating possession arrow
+    --momentum          Two goals in a row without conceding heat a team up (faster move/charge)
+    --loose-ball        Successful steals pop the ball loose; both players scramble for it
+    --rim-camping-violation
+                        Defensive three seconds: 3s camping on your own rim voids its carry-in block
+    --shot-clock <SECS> Holding the ball SECS without shooting pops it loose (passes don't reset it)
+    --out-of-bounds     A ball leaving the court (past either baseline or over the top) is a
+                        turnover: the other side throws in from its spawn point
+    --ball-reset <MODE> Restart after goals at center (default), inbound (conceding team's
+                        baseline) or random (neutral spot near center)
+    --nav-edge-penalty  Route AIs around nav edges that keep failing (jumps they miss, etc.)
+    --allow-degenerate  Don't end matches early when no one shoots for 60s, both AIs are stuck,
+                        or the ball stays loose for 30s (flagged matches skip win rates)
+    --coach <SIDE>      Halftime coach adjusts left, right or both profiles for the second half
     --matches <N>       Run N matches with same config
     --tournament [N]    Run all profile combinations (N matches each, default: 5)
+    --bracket [FORMAT]  Elimination bracket across profiles: single (default) or double
+    --bracket-games <N> Matches per bracket series (default: 3)
+    --seeding <MODE>    Bracket seeding: listed (--profiles order, default), random (from
+                        --seed) or ranked (win rate in the --db database)
     --level-sweep [N]   Test profile across all levels (N matches each, default: 3)
     --regression        Compare to baseline metrics
     --shot-test [N]     Shot accuracy test (N shots per position, default: 30)
+    --defender <SPEC>   Shot test against a defender at <distance>[,<height>[,stand|jump|hop]]
+                        in front of the shooter (repeatable)
+    --contested         Shot test against the standard set of defender placements
     --ghost <PATH>      Run ghost trials from file or directory
+    --curriculum [FILE] Run candidates (--profiles or --left) through self-play curriculum
+    --mirror-test [N]   Play N seeds with sides swapped (--left vs --right) and fail on
+                        left/right stat gaps beyond tolerance (default: 20)
+    --gym [SIDE]        Let an RL trainer play SIDE (left, default, or right) against
+                        --left/--right's AI: JSON lines on stdin/stdout, one tick per step
     --multihop-test     Test NavGraph multi-hop platform reachability
     --reachability-test Validate NavGraph against exploration data
     --samples <N>       Number of samples for reachability test (default: 50)
@@ -381,7 +800,21 @@ OPTIONS:
     --quiet, -q         Suppress progress output
     --parallel <N>      Run simulations in parallel with N threads
     --db <FILE>         Store results in SQLite database
+    --metrics-port <PORT> Serve Prometheus metrics at http://127.0.0.1:PORT/metrics
+    --control           Read batch commands on stdin: pause, resume, skip, status,
+                        parallel <N>, quit (finish running matches, then stop)
+    --control-port <PORT> Same commands over TCP on 127.0.0.1:PORT (e.g. with nc)
+    --upload <URL>      POST each match result as JSON to an http:// leaderboard endpoint
+    --upload-queue <FILE> Offline queue for failed uploads (default: db/upload_queue.jsonl)
     --debug-log         Enable debug sample logging (if supported)
+    --log-tick-ms <MS>  Interval between logged tick/input events (default: 50)
+    --log-categories <LIST> Event categories stored with --db, comma-separated or "all"
+                        (match, scoring, possession, shot, steal, movement, ai, input, tick;
+                        match and scoring are always stored)
+    --debug-samples <WHO> Record debug samples for both, left, right or none
+    --spectate-file <FILE>  Rewrite FILE with a JSON game snapshot (needs --features spectate)
+    --spectate-port <PORT>  Serve the latest snapshot at http://127.0.0.1:PORT/
+    --spectate-interval <SECS> Seconds between snapshots (default: 0.5)
     --help, -h          Show this help
 
 EXAMPLES:
@@ -394,12 +827,18 @@ EXAMPLES:
     # Load settings from file
     cargo run --bin simulate -- --settings sim_settings.json --tournament 3
 
+    # Scripted matchups for a regression comparison (same seeds every run)
+    cargo run --bin simulate -- --left-profiles "Balanced,Sniper" --right-profiles "Aggressive" --matches 10 --seed 1 --db regression.db
+
     # Test Sniper profile across all levels
     cargo run --bin simulate -- --level-sweep 5 --left Sniper
 
     # Run ghost trials against AI
     cargo run --bin simulate -- --ghost training_logs/session_xxx/ghost_trials/ --right Aggressive
 
+    # Promote candidates through the self-play curriculum
+    cargo run --bin simulate -- --curriculum --profiles "v4_Pat_50,v4_Elite_A" --output curriculum.json
+
     # Run matches with SQLite logging
     cargo run --bin simulate -- --tournament 5 --db training.db
 
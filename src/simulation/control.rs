@@ -1,7 +1,17 @@
 //! Simulation control resources
 //!
 //! Contains the core resources used to control simulation execution
-//! and event logging.
+//! and event logging, plus the batch control REPL (`--control` on stdin,
+//! `--control-port <PORT>` over TCP) for managing long unattended runs:
+//! pause/resume, skip the running match, dump status, change parallelism
+//! and quit after the current match.
+
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Condvar, Mutex, OnceLock};
+use std::time::Duration;
 
 use bevy::prelude::*;
 
@@ -38,7 +48,313 @@ impl Default for SimEventBuffer {
             enabled: false,
             emitter_state: EventEmitterState::with_config(EmitterConfig {
                 track_both_ai_goals: true,
+                ..Default::default()
             }),
         }
     }
 }
+
+impl SimControl {
+    /// End this match early if the batch REPL asked to skip it, and block while paused
+    pub fn poll_batch(&mut self) {
+        let batch = batch_control();
+        batch.wait_while_paused();
+        if batch.take_skip() {
+            self.should_exit = true;
+        }
+    }
+}
+
+/// A command typed at the batch control REPL (stdin or `--control-port`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ControlCommand {
+    /// Hold every running match at its current tick
+    Pause,
+    Resume,
+    /// End the running match(es) now, scored as they stand
+    Skip,
+    /// Print batch progress and the matches in flight
+    Status,
+    /// Cap how many matches run at once (parallel mode only)
+    Parallel(usize),
+    /// Let the running match(es) finish, then stop the batch
+    Quit,
+    Help,
+}
+
+impl ControlCommand {
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let mut words = line.split_whitespace();
+        let command = words.next().unwrap_or_default().to_ascii_lowercase();
+        let arg = words.next();
+        match command.as_str() {
+            "pause" | "p" => Ok(Self::Pause),
+            "resume" | "r" => Ok(Self::Resume),
+            "skip" | "s" => Ok(Self::Skip),
+            "status" | "dump" | "?" => Ok(Self::Status),
+            "parallel" | "j" => arg
+                .and_then(|n| n.parse().ok())
+                .filter(|&n| n > 0)
+                .map(Self::Parallel)
+                .ok_or_else(|| "usage: parallel <N> (N >= 1)".to_string()),
+            "quit" | "q" => Ok(Self::Quit),
+            "help" | "h" => Ok(Self::Help),
+            "" => Err("empty command".to_string()),
+            other => Err(format!("unknown command '{}' (try 'help')", other)),
+        }
+    }
+}
+
+const CONTROL_HELP: &str = "commands: pause, resume, skip, status, parallel <N>, quit, help";
+
+/// Batch progress shown by `status`
+#[derive(Debug, Default)]
+struct BatchStatus {
+    completed: usize,
+    /// Match seed -> "Left vs Right on level N: 1-0 at 12.0s"
+    running: BTreeMap<u64, String>,
+}
+
+/// Process-wide batch state shared by the REPL and every match loop
+#[derive(Debug, Default)]
+pub struct BatchControl {
+    paused: AtomicBool,
+    /// Pending skips; each running match consumes one
+    skips: AtomicUsize,
+    quit_after_current: AtomicBool,
+    /// Max matches running at once (0 = no cap)
+    parallel_limit: AtomicUsize,
+    /// Matches currently holding a parallel slot
+    slots_in_use: Mutex<usize>,
+    slot_freed: Condvar,
+    status: Mutex<BatchStatus>,
+}
+
+static BATCH_CONTROL: OnceLock<BatchControl> = OnceLock::new();
+
+/// The batch state (inert until a REPL changes it)
+pub fn batch_control() -> &'static BatchControl {
+    BATCH_CONTROL.get_or_init(BatchControl::default)
+}
+
+impl BatchControl {
+    /// Apply a command and return the reply for the REPL
+    pub fn apply(&self, command: ControlCommand) -> String {
+        match command {
+            ControlCommand::Pause => {
+                self.paused.store(true, Ordering::SeqCst);
+                "paused".to_string()
+            }
+            ControlCommand::Resume => {
+                self.paused.store(false, Ordering::SeqCst);
+                "resumed".to_string()
+            }
+            ControlCommand::Skip => {
+                let running = self.status.lock().map(|s| s.running.len()).unwrap_or(0);
+                self.skips.store(running.max(1), Ordering::SeqCst);
+                format!("skipping {} running match(es)", running)
+            }
+            ControlCommand::Status => self.status_report(),
+            ControlCommand::Parallel(n) => {
+                if self.parallel_limit.load(Ordering::SeqCst) == 0 {
+                    return "parallel: batch is sequential (start with --parallel N)".to_string();
+                }
+                self.parallel_limit.store(n, Ordering::SeqCst);
+                self.slot_freed.notify_all();
+                format!(
+                    "parallel limit {} (capped by the --parallel thread pool)",
+                    n
+                )
+            }
+            ControlCommand::Quit => {
+                self.quit_after_current.store(true, Ordering::SeqCst);
+                "quitting after the running match(es)".to_string()
+            }
+            ControlCommand::Help => CONTROL_HELP.to_string(),
+        }
+    }
+
+    fn status_report(&self) -> String {
+        let Ok(status) = self.status.lock() else {
+            return String::new();
+        };
+        let mut report = format!(
+            "completed {}, running {}, parallel limit {}{}{}",
+            status.completed,
+            status.running.len(),
+            self.parallel_limit.load(Ordering::SeqCst),
+            if self.paused.load(Ordering::SeqCst) {
+                ", PAUSED"
+            } else {
+                ""
+            },
+            if self.quit_requested() {
+                ", quitting"
+            } else {
+                ""
+            }
+        );
+        for (seed, line) in &status.running {
+            report.push_str(&format!("\n  seed {}: {}", seed, line));
+        }
+        report
+    }
+
+    /// Block the calling match while paused
+    pub fn wait_while_paused(&self) {
+        while self.paused.load(Ordering::SeqCst) {
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+
+    /// Consume one pending skip
+    pub fn take_skip(&self) -> bool {
+        self.skips
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok()
+    }
+
+    /// Batch loops check this before starting another match
+    pub fn quit_requested(&self) -> bool {
+        self.quit_after_current.load(Ordering::SeqCst)
+    }
+
+    /// Wait for a parallel slot; run the match, then call `finish_parallel`
+    pub fn start_parallel(&self) {
+        let Ok(mut in_use) = self.slots_in_use.lock() else {
+            return;
+        };
+        loop {
+            let limit = self.parallel_limit.load(Ordering::SeqCst);
+            if limit == 0 || *in_use < limit {
+                break;
+            }
+            in_use = match self.slot_freed.wait(in_use) {
+                Ok(guard) => guard,
+                Err(_) => return,
+            };
+        }
+        *in_use += 1;
+    }
+
+    pub fn finish_parallel(&self) {
+        if let Ok(mut in_use) = self.slots_in_use.lock() {
+            *in_use = in_use.saturating_sub(1);
+        }
+        self.slot_freed.notify_one();
+    }
+
+    /// Update (or add) a running match's status line
+    pub fn report_match(&self, seed: u64, line: String) {
+        if let Ok(mut status) = self.status.lock() {
+            status.running.insert(seed, line);
+        }
+    }
+
+    pub fn match_finished(&self, seed: u64) {
+        let Ok(mut status) = self.status.lock() else {
+            return;
+        };
+        status.running.remove(&seed);
+        status.completed += 1;
+        // A skip aimed at a match that ended on its own shouldn't hit the next one
+        if status.running.is_empty() {
+            self.skips.store(0, Ordering::SeqCst);
+        }
+    }
+}
+
+/// Start the batch control REPL on stdin and/or `127.0.0.1:<port>`
+///
+/// `parallel` is the batch's thread count (0 = sequential). Each input line is
+/// one command; the reply goes back to the same stream.
+pub fn start_control(stdin: bool, port: Option<u16>, parallel: usize) {
+    let batch = batch_control();
+    batch.parallel_limit.store(parallel, Ordering::SeqCst);
+
+    if stdin {
+        eprintln!("Control: type commands on stdin ({})", CONTROL_HELP);
+        std::thread::spawn(|| {
+            for line in std::io::stdin().lock().lines().map_while(Result::ok) {
+                eprintln!("{}", run_command(&line));
+            }
+        });
+    }
+
+    if let Some(port) = port {
+        let listener = match TcpListener::bind(("127.0.0.1", port)) {
+            Ok(listener) => listener,
+            Err(e) => {
+                eprintln!("Warning: control port {} failed to bind: {}", port, e);
+                return;
+            }
+        };
+        eprintln!("Control: nc 127.0.0.1 {} ({})", port, CONTROL_HELP);
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                std::thread::spawn(move || serve_control(stream));
+            }
+        });
+    }
+}
+
+fn run_command(line: &str) -> String {
+    match ControlCommand::parse(line) {
+        Ok(command) => batch_control().apply(command),
+        Err(e) => e,
+    }
+}
+
+fn serve_control(stream: TcpStream) {
+    let Ok(mut writer) = stream.try_clone() else {
+        return;
+    };
+    for line in BufReader::new(stream).lines().map_while(Result::ok) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if writeln!(writer, "{}", run_command(&line)).is_err() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_control_commands() {
+        assert_eq!(ControlCommand::parse("PAUSE"), Ok(ControlCommand::Pause));
+        assert_eq!(
+            ControlCommand::parse("parallel 4"),
+            Ok(ControlCommand::Parallel(4))
+        );
+        assert!(ControlCommand::parse("parallel 0").is_err());
+        assert!(ControlCommand::parse("explode").is_err());
+
+        // Fresh state rather than the global so other tests' matches can't interfere
+        let batch = BatchControl::default();
+        batch.report_match(7, "A vs B".to_string());
+        batch.report_match(8, "C vs D".to_string());
+        batch.apply(ControlCommand::Skip);
+        assert!(batch.take_skip());
+        assert!(batch.take_skip());
+        assert!(!batch.take_skip());
+
+        assert!(
+            batch
+                .apply(ControlCommand::Parallel(2))
+                .contains("sequential")
+        );
+        batch.parallel_limit.store(8, Ordering::SeqCst);
+        batch.apply(ControlCommand::Parallel(2));
+        assert!(batch.status_report().contains("parallel limit 2"));
+        assert!(batch.status_report().contains("seed 7: A vs B"));
+
+        batch.match_finished(7);
+        batch.apply(ControlCommand::Quit);
+        assert!(batch.quit_requested());
+        assert!(batch.status_report().starts_with("completed 1, running 1"));
+    }
+}
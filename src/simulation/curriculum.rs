@@ -0,0 +1,324 @@
+//! Self-play curriculum scheduler
+//!
+//! Sequences candidate profiles through increasingly difficult stages
+//! (simple levels → complex levels, no defense → full defense). A candidate
+//! is promoted to the next stage only when it meets the stage's target
+//! metrics; otherwise it stalls and the remaining stages are skipped.
+//! Every stage outcome is recorded in a per-candidate trace.
+
+use serde::{Deserialize, Serialize};
+
+use crate::ai::{AiProfile, AiProfileDatabase};
+use crate::levels::LevelDatabase;
+
+use super::config::SimConfig;
+use super::metrics::MatchResult;
+use super::runner::run_match;
+
+/// Default curriculum file (optional, built-in stages used if missing)
+pub const CURRICULUM_FILE: &str = "config/curriculum.json";
+
+/// A single stage of the curriculum
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurriculumStage {
+    /// Stage name for reports
+    pub name: String,
+    /// Level names played in this stage (round-robin across matches)
+    pub levels: Vec<String>,
+    /// Opponent profile name
+    pub opponent: String,
+    /// Opponent defense scale (0.0 = no defense, 1.0 = full profile defense)
+    pub defense_scale: f32,
+    /// Matches played per stage attempt
+    pub matches: u32,
+    /// Minimum candidate win rate (0.0-1.0) required for promotion
+    pub min_win_rate: f32,
+    /// Minimum average goals per match required for promotion
+    #[serde(default)]
+    pub min_avg_goals: f32,
+}
+
+/// Full curriculum definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Curriculum {
+    pub stages: Vec<CurriculumStage>,
+}
+
+impl Default for Curriculum {
+    fn default() -> Self {
+        let stage =
+            |name: &str, levels: &[&str], defense_scale: f32, min_win_rate: f32| CurriculumStage {
+                name: name.to_string(),
+                levels: levels.iter().map(|l| l.to_string()).collect(),
+                opponent: "Balanced".to_string(),
+                defense_scale,
+                matches: 6,
+                min_win_rate,
+                min_avg_goals: 1.0,
+            };
+        Self {
+            stages: vec![
+                stage("open_no_defense", &["Open Floor"], 0.0, 0.8),
+                stage("open_half_defense", &["Open Floor", "Arena"], 0.5, 0.6),
+                stage("platforms_half_defense", &["Islands", "Slopes"], 0.5, 0.55),
+                stage(
+                    "complex_full_defense",
+                    &["Tower", "Skyway", "Terraces", "Catwalk"],
+                    1.0,
+                    0.5,
+                ),
+            ],
+        }
+    }
+}
+
+impl Curriculum {
+    /// Load a curriculum from a JSON file
+    pub fn from_file(path: &str) -> Result<Self, String> {
+        let contents =
+            std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        serde_json::from_str(&contents).map_err(|e| format!("Failed to parse {}: {}", path, e))
+    }
+}
+
+/// Outcome of one stage attempt for one candidate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StageOutcome {
+    pub stage: String,
+    pub matches: u32,
+    pub wins: u32,
+    pub win_rate: f32,
+    pub avg_goals: f32,
+    pub avg_goals_against: f32,
+    pub promoted: bool,
+}
+
+impl StageOutcome {
+    fn from_results(stage: &CurriculumStage, results: &[MatchResult]) -> Self {
+        let matches = results.len() as u32;
+        let wins = results.iter().filter(|r| r.winner == "left").count() as u32;
+        let goals: u32 = results.iter().map(|r| r.score_left).sum();
+        let against: u32 = results.iter().map(|r| r.score_right).sum();
+        let denom = matches.max(1) as f32;
+        let win_rate = wins as f32 / denom;
+        let avg_goals = goals as f32 / denom;
+        Self {
+            stage: stage.name.clone(),
+            matches,
+            wins,
+            win_rate,
+            avg_goals,
+            avg_goals_against: against as f32 / denom,
+            promoted: matches > 0
+                && win_rate >= stage.min_win_rate
+                && avg_goals >= stage.min_avg_goals,
+        }
+    }
+}
+
+/// Curriculum trace for a single candidate profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurriculumTrace {
+    pub candidate: String,
+    pub stages: Vec<StageOutcome>,
+    /// Number of stages passed
+    pub stages_passed: usize,
+    /// True if every stage was passed
+    pub graduated: bool,
+}
+
+/// Build an opponent profile with defensive parameters scaled down.
+/// A scale of 0.0 makes the opponent ignore the ball carrier entirely.
+pub fn scale_defense(profile: &AiProfile, scale: f32) -> AiProfile {
+    let scale = scale.clamp(0.0, 1.0);
+    let mut scaled = profile.clone();
+    scaled.name = format!("{}@def{:.2}", profile.name, scale);
+    // Profiles are resolved by ID at spawn, so the scaled copy needs its own
+    scaled.id = format!("{:.12}d{:03}", profile.id, (scale * 100.0).round() as u32);
+    scaled.aggression *= scale;
+    scaled.defensive_iq *= scale;
+    scaled.steal_range *= scale;
+    // Slower reactions at low scale (up to 1s extra delay at zero defense)
+    scaled.steal_reaction_time += 1.0 - scale;
+    scaled
+}
+
+/// Run one candidate through the curriculum, stopping at the first failed stage
+pub fn run_candidate(
+    candidate: &str,
+    curriculum: &Curriculum,
+    base_config: &SimConfig,
+    base_seed: u64,
+    level_db: &LevelDatabase,
+    profile_db: &AiProfileDatabase,
+) -> CurriculumTrace {
+    let mut stages = Vec::new();
+
+    for (stage_idx, stage) in curriculum.stages.iter().enumerate() {
+        let opponent = profile_db
+            .get_by_name(&stage.opponent)
+            .unwrap_or_else(|| profile_db.default_profile());
+        let opponent = scale_defense(opponent, stage.defense_scale);
+
+        let mut stage_db = profile_db.clone();
+        stage_db.upsert(opponent.clone());
+
+        let levels: Vec<u32> = stage
+            .levels
+            .iter()
+            .filter_map(|name| level_db.index_of(name).map(|i| i as u32 + 1))
+            .collect();
+
+        let mut results = Vec::new();
+        for match_idx in 0..stage.matches {
+            let mut config = base_config.clone();
+            config.left_profile = candidate.to_string();
+            config.right_profile = opponent.name.clone();
+            config.level = if levels.is_empty() {
+                None
+            } else {
+                Some(levels[match_idx as usize % levels.len()])
+            };
+            let seed = base_seed
+                .wrapping_add((stage_idx as u64) * 10_000)
+                .wrapping_add(match_idx as u64);
+            results.push(run_match(&config, seed, level_db, &stage_db));
+        }
+
+        let outcome = StageOutcome::from_results(stage, &results);
+        let promoted = outcome.promoted;
+        stages.push(outcome);
+        if !promoted {
+            break;
+        }
+    }
+
+    let stages_passed = stages.iter().filter(|s| s.promoted).count();
+    CurriculumTrace {
+        candidate: candidate.to_string(),
+        graduated: stages_passed == curriculum.stages.len(),
+        stages,
+        stages_passed,
+    }
+}
+
+/// Run the curriculum for every candidate and print/write the traces
+pub fn run_curriculum(
+    config: &SimConfig,
+    curriculum_path: Option<&str>,
+    level_db: &LevelDatabase,
+    profile_db: &AiProfileDatabase,
+) -> Vec<CurriculumTrace> {
+    let curriculum = match curriculum_path {
+        Some(path) => Curriculum::from_file(path).unwrap_or_else(|e| {
+            eprintln!("Warning: {}, using built-in curriculum", e);
+            Curriculum::default()
+        }),
+        None => Curriculum::from_file(CURRICULUM_FILE).unwrap_or_default(),
+    };
+
+    let candidates: Vec<String> = if config.profiles.is_empty() {
+        vec![config.left_profile.clone()]
+    } else {
+        config.profiles.clone()
+    };
+    let base_seed = config.seed.unwrap_or(42);
+
+    let mut traces = Vec::new();
+    for candidate in &candidates {
+        if !config.quiet {
+            println!("Curriculum: {}", candidate);
+        }
+        let trace = run_candidate(
+            candidate,
+            &curriculum,
+            config,
+            base_seed,
+            level_db,
+            profile_db,
+        );
+        if !config.quiet {
+            for s in &trace.stages {
+                println!(
+                    "  {:<26} {:>2}/{:<2} wins ({:>5.1}%)  goals {:.1}-{:.1}  {}",
+                    s.stage,
+                    s.wins,
+                    s.matches,
+                    s.win_rate * 100.0,
+                    s.avg_goals,
+                    s.avg_goals_against,
+                    if s.promoted { "PROMOTED" } else { "STALLED" }
+                );
+            }
+        }
+        traces.push(trace);
+    }
+
+    let json = serde_json::to_string_pretty(&traces).unwrap();
+    if let Some(output_file) = &config.output_file {
+        std::fs::write(output_file, json).expect("Failed to write output");
+        println!("Curriculum traces written to {}", output_file);
+    } else if config.quiet {
+        println!("{}", json);
+    }
+
+    traces
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stage(min_win_rate: f32) -> CurriculumStage {
+        CurriculumStage {
+            name: "test".to_string(),
+            levels: Vec::new(),
+            opponent: "Balanced".to_string(),
+            defense_scale: 1.0,
+            matches: 2,
+            min_win_rate,
+            min_avg_goals: 1.0,
+        }
+    }
+
+    fn result(score_left: u32, score_right: u32) -> MatchResult {
+        let mut r = MatchResult {
+            level: 1,
+            level_name: String::new(),
+            left_profile: String::new(),
+            right_profile: String::new(),
+            duration: 0.0,
+            score_left,
+            score_right,
+            winner: String::new(),
+            left_stats: Default::default(),
+            right_stats: Default::default(),
+            seed: 0,
+            degenerate: None,
+            events: Vec::new(),
+        };
+        r.determine_winner();
+        r
+    }
+
+    #[test]
+    fn test_stage_promotion_thresholds() {
+        let results = vec![result(3, 1), result(0, 2)];
+        let outcome = StageOutcome::from_results(&stage(0.5), &results);
+        assert_eq!(outcome.wins, 1);
+        assert!(outcome.promoted);
+
+        let outcome = StageOutcome::from_results(&stage(0.6), &results);
+        assert!(!outcome.promoted);
+    }
+
+    #[test]
+    fn test_scale_defense_zero_disables_pressure() {
+        let profile = AiProfile::default();
+        let scaled = scale_defense(&profile, 0.0);
+        assert_eq!(scaled.aggression, 0.0);
+        assert_eq!(scaled.steal_range, 0.0);
+        assert_ne!(scaled.name, profile.name);
+        assert_ne!(scaled.id, profile.id);
+    }
+}
@@ -7,9 +7,14 @@ use bevy::prelude::Vec2;
 use rusqlite::{Connection, OptionalExtension, Result, params};
 use std::path::Path;
 
-use super::metrics::{MatchResult, PlayerStats};
-use crate::events::{GameEvent, parse_event, serialize_event};
+use super::bracket::{BracketSeries, BracketSide};
+use super::metrics::{MatchFailure, MatchResult, PlayerStats};
+use crate::events::{
+    GameEvent, TeamTags, TimeBase, match_time_base, migrate_time_base, parse_event, serialize_event,
+};
+use crate::player::Team;
 use crate::replay::{MatchInfo, ReplayData, TickFrame, TimedEvent};
+use crate::series::VersusSeries;
 
 /// Database wrapper for simulation results
 pub struct SimDatabase {
@@ -115,7 +120,8 @@ impl SimDatabase {
                 score_left INTEGER NOT NULL,
                 score_right INTEGER NOT NULL,
                 duration_secs REAL NOT NULL,
-                winner TEXT NOT NULL
+                winner TEXT NOT NULL,
+                degenerate_reason TEXT
             );
 
             CREATE TABLE IF NOT EXISTS points (
@@ -143,7 +149,50 @@ impl SimDatabase {
                 nav_paths_failed INTEGER NOT NULL,
                 avg_shot_x REAL NOT NULL DEFAULT 0.0,
                 avg_shot_y REAL NOT NULL DEFAULT 0.0,
-                avg_shot_quality REAL NOT NULL DEFAULT 0.0
+                avg_shot_quality REAL NOT NULL DEFAULT 0.0,
+                rim_camping INTEGER NOT NULL DEFAULT 0,
+                camping_violations INTEGER NOT NULL DEFAULT 0
+            );
+
+            -- Matches that panicked mid-batch (seed + config to reproduce)
+            CREATE TABLE IF NOT EXISTS match_failures (
+                id INTEGER PRIMARY KEY,
+                session_id TEXT REFERENCES sessions(id),
+                seed INTEGER NOT NULL,
+                level INTEGER NOT NULL,
+                left_profile TEXT NOT NULL,
+                right_profile TEXT NOT NULL,
+                message TEXT NOT NULL,
+                config_json TEXT NOT NULL
+            );
+
+            -- Elimination bracket series (--bracket), in the order they were played
+            CREATE TABLE IF NOT EXISTS bracket_series (
+                id INTEGER PRIMARY KEY,
+                session_id TEXT REFERENCES sessions(id),
+                side TEXT NOT NULL,
+                round INTEGER NOT NULL,
+                left_profile TEXT NOT NULL,
+                left_seed INTEGER NOT NULL,
+                right_profile TEXT NOT NULL,
+                right_seed INTEGER NOT NULL,
+                winner TEXT NOT NULL,
+                left_wins INTEGER NOT NULL,
+                right_wins INTEGER NOT NULL
+            );
+
+            -- Finished versus series (--series / --first-to), one row per series
+            CREATE TABLE IF NOT EXISTS versus_series (
+                id INTEGER PRIMARY KEY,
+                started_at TEXT NOT NULL,
+                finished_at TEXT NOT NULL,
+                level_name TEXT NOT NULL,
+                best_of INTEGER NOT NULL,
+                first_to INTEGER NOT NULL,
+                winner TEXT NOT NULL,
+                left_wins INTEGER NOT NULL,
+                right_wins INTEGER NOT NULL,
+                games TEXT NOT NULL
             );
 
             CREATE INDEX IF NOT EXISTS idx_matches_session ON matches(session_id);
@@ -223,9 +272,21 @@ impl SimDatabase {
         let _ = self
             .conn
             .execute("ALTER TABLE matches ADD COLUMN display_name TEXT", []);
+        let _ = self
+            .conn
+            .execute("ALTER TABLE matches ADD COLUMN degenerate_reason TEXT", []);
         let _ = self
             .conn
             .execute("ALTER TABLE events ADD COLUMN point_id INTEGER", []);
+        let _ = self.conn.execute(
+            "ALTER TABLE player_stats ADD COLUMN rim_camping INTEGER NOT NULL DEFAULT 0",
+            [],
+        );
+        let _ = self.conn.execute(
+            "ALTER TABLE player_stats ADD COLUMN camping_violations INTEGER NOT NULL DEFAULT 0",
+            [],
+        );
+        migrate_time_base(&self.conn);
         Ok(())
     }
 
@@ -372,8 +433,8 @@ impl SimDatabase {
         self.conn.execute(
             r#"INSERT INTO matches
                (session_id, display_name, seed, level, level_name, left_profile, right_profile,
-                score_left, score_right, duration_secs, winner)
-               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)"#,
+                score_left, score_right, duration_secs, winner, degenerate_reason, time_base)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)"#,
             params![
                 session_id,
                 display_name,
@@ -386,6 +447,8 @@ impl SimDatabase {
                 result.score_right,
                 result.duration,
                 result.winner,
+                result.degenerate.map(|r| r.code()),
+                TimeBase::Game.as_str(),
             ],
         )?;
 
@@ -404,8 +467,9 @@ impl SimDatabase {
             r#"INSERT INTO player_stats
                (match_id, side, goals, shots_attempted, shots_made, steals_attempted,
                 steals_successful, possession_time, distance_traveled, jumps,
-                nav_paths_completed, nav_paths_failed, avg_shot_x, avg_shot_y, avg_shot_quality)
-               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)"#,
+                nav_paths_completed, nav_paths_failed, avg_shot_x, avg_shot_y, avg_shot_quality,
+                rim_camping, camping_violations)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)"#,
             params![
                 match_id,
                 side,
@@ -422,12 +486,14 @@ impl SimDatabase {
                 stats.avg_shot_x,
                 stats.avg_shot_y,
                 stats.avg_shot_quality,
+                stats.rim_camping,
+                stats.camping_violations,
             ],
         )?;
         Ok(())
     }
 
-    /// Get aggregate stats for a profile
+    /// Get aggregate stats for a profile (degenerate matches excluded)
     pub fn get_profile_stats(&self, profile: &str) -> Result<ProfileStats> {
         let mut stmt = self.conn.prepare(
             r#"SELECT
@@ -438,7 +504,8 @@ impl SimDatabase {
                 AVG(CASE WHEN left_profile = ?1 THEN score_left ELSE score_right END) as avg_score,
                 AVG(CASE WHEN left_profile = ?1 THEN score_right ELSE score_left END) as avg_opp_score
                FROM matches
-               WHERE left_profile = ?1 OR right_profile = ?1"#,
+               WHERE (left_profile = ?1 OR right_profile = ?1)
+                 AND degenerate_reason IS NULL"#,
         )?;
 
         let result = stmt.query_row(params![profile], |row| {
@@ -455,6 +522,20 @@ impl SimDatabase {
         Ok(result)
     }
 
+    /// Rim-camping stays and violations for a profile, summed over its matches
+    /// (degenerate matches excluded)
+    pub fn get_profile_rim_camping(&self, profile: &str) -> Result<(u32, u32)> {
+        self.conn.query_row(
+            r#"SELECT COALESCE(SUM(ps.rim_camping), 0), COALESCE(SUM(ps.camping_violations), 0)
+               FROM player_stats ps JOIN matches m ON m.id = ps.match_id
+               WHERE ((ps.side = 'left' AND m.left_profile = ?1)
+                   OR (ps.side = 'right' AND m.right_profile = ?1))
+                 AND m.degenerate_reason IS NULL"#,
+            params![profile],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+    }
+
     /// Get match results with optional filtering
     pub fn query_matches(&self, filter: &MatchFilter) -> Result<Vec<MatchSummary>> {
         let mut sql = String::from(
@@ -501,6 +582,149 @@ impl SimDatabase {
         rows.collect()
     }
 
+    /// Matches flagged as degenerate, with what's needed to reproduce them
+    pub fn get_degenerate_matches(&self, session_id: &str) -> Result<Vec<DegenerateMatch>> {
+        let mut stmt = self.conn.prepare(
+            r#"SELECT id, seed, level, left_profile, right_profile, degenerate_reason
+               FROM matches
+               WHERE session_id = ?1 AND degenerate_reason IS NOT NULL
+               ORDER BY id"#,
+        )?;
+        let rows = stmt.query_map(params![session_id], |row| {
+            Ok(DegenerateMatch {
+                id: row.get(0)?,
+                seed: row.get::<_, i64>(1)? as u64,
+                level: row.get(2)?,
+                left_profile: row.get(3)?,
+                right_profile: row.get(4)?,
+                reason: row.get(5)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Record a match that panicked
+    pub fn insert_match_failure(&self, session_id: &str, failure: &MatchFailure) -> Result<()> {
+        self.conn.execute(
+            r#"INSERT INTO match_failures
+               (session_id, seed, level, left_profile, right_profile, message, config_json)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)"#,
+            params![
+                session_id,
+                failure.seed as i64,
+                failure.level,
+                failure.left_profile,
+                failure.right_profile,
+                failure.message,
+                failure.config_json,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Matches that panicked in a session
+    pub fn get_match_failures(&self, session_id: &str) -> Result<Vec<MatchFailure>> {
+        let mut stmt = self.conn.prepare(
+            r#"SELECT seed, level, left_profile, right_profile, message, config_json
+               FROM match_failures
+               WHERE session_id = ?1
+               ORDER BY id"#,
+        )?;
+        let rows = stmt.query_map(params![session_id], |row| {
+            Ok(MatchFailure {
+                seed: row.get::<_, i64>(0)? as u64,
+                level: row.get(1)?,
+                left_profile: row.get(2)?,
+                right_profile: row.get(3)?,
+                message: row.get(4)?,
+                config_json: row.get(5)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Record one bracket series
+    pub fn insert_bracket_series(&self, session_id: &str, series: &BracketSeries) -> Result<()> {
+        self.conn.execute(
+            r#"INSERT INTO bracket_series
+               (session_id, side, round, left_profile, left_seed, right_profile, right_seed,
+                winner, left_wins, right_wins)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"#,
+            params![
+                session_id,
+                series.side.as_str(),
+                series.round,
+                series.left,
+                series.left_seed,
+                series.right,
+                series.right_seed,
+                series.winner,
+                series.left_wins,
+                series.right_wins,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// A session's bracket series, in the order they were played
+    pub fn get_bracket_series(&self, session_id: &str) -> Result<Vec<BracketSeries>> {
+        let mut stmt = self.conn.prepare(
+            r#"SELECT side, round, left_profile, left_seed, right_profile, right_seed,
+                      winner, left_wins, right_wins
+               FROM bracket_series
+               WHERE session_id = ?1
+               ORDER BY id"#,
+        )?;
+        let rows = stmt.query_map(params![session_id], |row| {
+            let side: String = row.get(0)?;
+            Ok(BracketSeries {
+                side: BracketSide::parse(&side).unwrap_or(BracketSide::Winners),
+                round: row.get(1)?,
+                left: row.get(2)?,
+                left_seed: row.get(3)?,
+                right: row.get(4)?,
+                right_seed: row.get(5)?,
+                winner: row.get(6)?,
+                left_wins: row.get(7)?,
+                right_wins: row.get(8)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    /// Record a finished versus series. `games` holds each match's score, e.g. "5-3,2-5,5-4".
+    pub fn insert_versus_series(&self, series: &VersusSeries, level_name: &str) -> Result<()> {
+        let winner = match series.winner() {
+            Some(Team::Left) => "left",
+            Some(Team::Right) => "right",
+            None => "tie",
+        };
+        let games = series
+            .games
+            .iter()
+            .map(|(left, right)| format!("{}-{}", left, right))
+            .collect::<Vec<_>>()
+            .join(",");
+        self.conn.execute(
+            r#"INSERT INTO versus_series
+               (started_at, finished_at, level_name, best_of, first_to, winner, left_wins,
+                right_wins, games)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)"#,
+            params![
+                series.started_at,
+                chrono::Utc::now().to_rfc3339(),
+                level_name,
+                series.best_of,
+                series.first_to,
+                winner,
+                series.wins(Team::Left),
+                series.wins(Team::Right),
+                games,
+            ],
+        )?;
+        Ok(())
+    }
+
     /// Get match count
     pub fn match_count(&self) -> Result<u64> {
         self.conn
@@ -647,6 +871,80 @@ impl SimDatabase {
 
     /// Load replay data from SQLite for a match.
     pub fn load_replay_data(&self, match_id: i64) -> std::result::Result<ReplayData, String> {
+        self.load_replay(match_id, true)
+    }
+
+    /// Load a match's info and non-tick events; ticks are left for `load_tick_range`.
+    pub fn load_replay_events(&self, match_id: i64) -> std::result::Result<ReplayData, String> {
+        let mut replay = self.load_replay(match_id, false)?;
+        replay.duration_ms = self
+            .conn
+            .query_row(
+                "SELECT COALESCE(MAX(time_ms), 0) FROM events WHERE match_id = ?1",
+                params![match_id],
+                |row| row.get(0),
+            )
+            .map_err(|e| e.to_string())?;
+        Ok(replay)
+    }
+
+    /// Tick frames with `start_ms <= time_ms < end_ms`, in time order.
+    pub fn load_tick_range(
+        &self,
+        match_id: i64,
+        start_ms: u32,
+        end_ms: u32,
+    ) -> std::result::Result<Vec<TickFrame>, String> {
+        let mut stmt = self
+            .conn
+            .prepare_cached(
+                "SELECT time_ms, data FROM events WHERE match_id = ?1 AND event_type = 'T' \
+                 AND time_ms >= ?2 AND time_ms < ?3 ORDER BY time_ms ASC, id ASC",
+            )
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map(params![match_id, start_ms, end_ms], |row| {
+                Ok((row.get::<_, u32>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(|e| e.to_string())?;
+
+        let mut ticks = Vec::new();
+        for row in rows {
+            let (time_ms, data) = row.map_err(|e| e.to_string())?;
+            let Some((_, event)) = parse_event(&data) else {
+                return Err(format!(
+                    "Failed to parse tick at {}ms for match {}",
+                    time_ms, match_id
+                ));
+            };
+            ticks.extend(TickFrame::from_event(time_ms, &event));
+        }
+        Ok(ticks)
+    }
+
+    /// Time of the last tick frame logged for a match
+    pub fn last_tick_ms(&self, match_id: i64) -> Result<Option<u32>> {
+        self.conn.query_row(
+            "SELECT MAX(time_ms) FROM events WHERE match_id = ?1 AND event_type = 'T'",
+            params![match_id],
+            |row| row.get(0),
+        )
+    }
+
+    /// Number of tick frames logged for a match
+    pub fn tick_count(&self, match_id: i64) -> Result<u64> {
+        self.conn.query_row(
+            "SELECT COUNT(*) FROM events WHERE match_id = ?1 AND event_type = 'T'",
+            params![match_id],
+            |row| row.get(0),
+        )
+    }
+
+    fn load_replay(
+        &self,
+        match_id: i64,
+        with_ticks: bool,
+    ) -> std::result::Result<ReplayData, String> {
         let (session_id, level, level_name, left_profile, right_profile, seed) = self
             .conn
             .query_row(
@@ -667,9 +965,12 @@ impl SimDatabase {
 
         let mut stmt = self
             .conn
-            .prepare(
-                "SELECT id, time_ms, data FROM events WHERE match_id = ?1 ORDER BY time_ms ASC, id ASC",
-            )
+            .prepare(if with_ticks {
+                "SELECT id, time_ms, data FROM events WHERE match_id = ?1 ORDER BY time_ms ASC, id ASC"
+            } else {
+                "SELECT id, time_ms, data FROM events WHERE match_id = ?1 AND event_type != 'T' \
+                 ORDER BY time_ms ASC, id ASC"
+            })
             .map_err(|e| e.to_string())?;
 
         let rows = stmt
@@ -685,6 +986,7 @@ impl SimDatabase {
         let mut ticks = Vec::new();
         let mut events = Vec::new();
         let mut max_time_ms = 0u32;
+        let mut teams = TeamTags::default();
 
         for row in rows {
             let (event_id, time_ms, data) = row.map_err(|e| e.to_string())?;
@@ -720,6 +1022,9 @@ impl SimDatabase {
                     });
                 }
                 _ => {
+                    if let GameEvent::MatchStart { teams: tags, .. } = &event {
+                        teams = tags.clone();
+                    }
                     events.push(TimedEvent { time_ms, event });
                 }
             }
@@ -733,10 +1038,14 @@ impl SimDatabase {
                 left_profile,
                 right_profile,
                 seed: seed as u64,
+                teams,
             },
             ticks,
             events,
             duration_ms: max_time_ms,
+            stream: None,
+            keyframes: Vec::new(),
+            time_base: match_time_base(&self.conn, match_id),
         })
     }
 }
@@ -822,6 +1131,18 @@ pub struct MatchSummary {
     pub winner: String,
 }
 
+/// A match cut short as degenerate
+#[derive(Debug, Clone)]
+pub struct DegenerateMatch {
+    pub id: i64,
+    pub seed: u64,
+    pub level: u32,
+    pub left_profile: String,
+    pub right_profile: String,
+    /// Reason code (see `DegenerateReason::code`)
+    pub reason: String,
+}
+
 //=============================================================================
 // Analysis Data Structures
 //=============================================================================
@@ -1268,6 +1589,7 @@ mod tests {
             left_stats: PlayerStats::default(),
             right_stats: PlayerStats::default(),
             seed: 12345,
+            degenerate: None,
             events: Vec::new(),
         }
     }
@@ -1322,11 +1644,38 @@ mod tests {
             } else {
                 "right".to_string()
             };
+            result.left_stats.rim_camping = 1;
+            result.right_stats.rim_camping = 2;
+            result.right_stats.camping_violations = 1;
             db.insert_match(&session_id, &result).unwrap();
         }
 
         let stats = db.get_profile_stats("Balanced").unwrap();
         assert_eq!(stats.matches, 3);
         assert_eq!(stats.wins, 2); // left won matches 0 and 2
+        // Camping counts follow the side the profile played
+        assert_eq!(db.get_profile_rim_camping("Balanced").unwrap(), (3, 0));
+    }
+
+    #[test]
+    fn test_degenerate_matches_flagged_and_excluded() {
+        use crate::simulation::DegenerateReason;
+
+        let db = SimDatabase::open_in_memory().unwrap();
+        let session_id = db.create_session("test", None).unwrap();
+
+        db.insert_match(&session_id, &sample_result()).unwrap();
+        let mut stuck = sample_result();
+        stuck.seed = 777;
+        stuck.degenerate = Some(DegenerateReason::BothStuck);
+        db.insert_match(&session_id, &stuck).unwrap();
+
+        let stats = db.get_profile_stats("Balanced").unwrap();
+        assert_eq!(stats.matches, 1);
+
+        let flagged = db.get_degenerate_matches(&session_id).unwrap();
+        assert_eq!(flagged.len(), 1);
+        assert_eq!(flagged[0].seed, 777);
+        assert_eq!(flagged[0].reason, "both_stuck");
     }
 }
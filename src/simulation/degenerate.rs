@@ -0,0 +1,184 @@
+//! Degenerate match detection
+//!
+//! Flags matches that can't produce meaningful results: nobody shoots for a
+//! minute, both AIs stop moving, or the ball sits loose where nobody picks it
+//! up. Flagged matches end early, carry a reason code into `MatchResult` and
+//! the `matches` table, and are left out of tournament win rates.
+
+use bevy::prelude::Vec2;
+use serde::{Deserialize, Serialize};
+
+/// Seconds without any shot attempt
+pub const NO_SHOTS_SECS: f32 = 60.0;
+/// Seconds both players stay inside STUCK_RADIUS
+pub const BOTH_STUCK_SECS: f32 = 15.0;
+/// Movement (pixels) that counts as getting unstuck
+pub const STUCK_RADIUS: f32 = 24.0;
+/// Seconds the ball goes without anyone holding it
+pub const BALL_UNREACHABLE_SECS: f32 = 30.0;
+
+/// Why a match was flagged as degenerate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DegenerateReason {
+    /// No shot attempts for NO_SHOTS_SECS
+    NoShots,
+    /// Both AIs parked in place for BOTH_STUCK_SECS
+    BothStuck,
+    /// Ball loose for BALL_UNREACHABLE_SECS
+    BallUnreachable,
+}
+
+impl DegenerateReason {
+    /// Reason code stored in the database
+    pub fn code(&self) -> &'static str {
+        match self {
+            DegenerateReason::NoShots => "no_shots",
+            DegenerateReason::BothStuck => "both_stuck",
+            DegenerateReason::BallUnreachable => "ball_unreachable",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "no_shots" => Some(DegenerateReason::NoShots),
+            "both_stuck" => Some(DegenerateReason::BothStuck),
+            "ball_unreachable" => Some(DegenerateReason::BallUnreachable),
+            _ => None,
+        }
+    }
+}
+
+/// Running timers for degenerate detection (one per match)
+#[derive(Debug, Clone, Default)]
+pub struct DegenerateMonitor {
+    pub time_since_shot: f32,
+    pub time_since_possession: f32,
+    /// Where each player was when they last moved more than STUCK_RADIUS
+    anchors: [Option<Vec2>; 2],
+    /// How long each player has stayed near their anchor
+    still_time: [f32; 2],
+    /// First reason detected (sticky)
+    pub reason: Option<DegenerateReason>,
+}
+
+impl DegenerateMonitor {
+    /// Advance the timers by `dt` and return the reason once one trips
+    pub fn update(
+        &mut self,
+        dt: f32,
+        shot_released: bool,
+        ball_held: bool,
+        positions: [Option<Vec2>; 2],
+    ) -> Option<DegenerateReason> {
+        if self.reason.is_some() {
+            return self.reason;
+        }
+
+        self.time_since_shot = if shot_released {
+            0.0
+        } else {
+            self.time_since_shot + dt
+        };
+        self.time_since_possession = if ball_held {
+            0.0
+        } else {
+            self.time_since_possession + dt
+        };
+        for (i, pos) in positions.iter().enumerate() {
+            let Some(pos) = *pos else { continue };
+            match self.anchors[i] {
+                Some(anchor) if anchor.distance(pos) <= STUCK_RADIUS => self.still_time[i] += dt,
+                _ => {
+                    self.anchors[i] = Some(pos);
+                    self.still_time[i] = 0.0;
+                }
+            }
+        }
+
+        self.reason = if self.still_time.iter().all(|t| *t >= BOTH_STUCK_SECS) {
+            Some(DegenerateReason::BothStuck)
+        } else if self.time_since_possession >= BALL_UNREACHABLE_SECS {
+            Some(DegenerateReason::BallUnreachable)
+        } else if self.time_since_shot >= NO_SHOTS_SECS {
+            Some(DegenerateReason::NoShots)
+        } else {
+            None
+        };
+        self.reason
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DT: f32 = 1.0 / 60.0;
+
+    fn run(
+        monitor: &mut DegenerateMonitor,
+        secs: f32,
+        shot: bool,
+        held: bool,
+        positions: impl Fn(usize) -> [Option<Vec2>; 2],
+    ) -> Option<DegenerateReason> {
+        let ticks = (secs / DT) as usize;
+        let mut reason = None;
+        for tick in 0..ticks {
+            reason = monitor.update(DT, shot, held, positions(tick));
+        }
+        reason
+    }
+
+    #[test]
+    fn test_detects_each_reason() {
+        // Players running around, ball held, shooting every tick: healthy
+        let moving = |tick: usize| {
+            let x = (tick % 2) as f32 * 100.0;
+            [Some(Vec2::new(x, 0.0)), Some(Vec2::new(-x, 0.0))]
+        };
+        let mut monitor = DegenerateMonitor::default();
+        assert_eq!(run(&mut monitor, 90.0, true, true, moving), None);
+
+        // Never shooting trips after a minute
+        let mut monitor = DegenerateMonitor::default();
+        assert_eq!(
+            run(&mut monitor, NO_SHOTS_SECS + 1.0, false, true, moving),
+            Some(DegenerateReason::NoShots)
+        );
+
+        // Both standing still
+        let parked = |_: usize| [Some(Vec2::ZERO), Some(Vec2::new(200.0, 0.0))];
+        let mut monitor = DegenerateMonitor::default();
+        assert_eq!(
+            run(&mut monitor, BOTH_STUCK_SECS + 1.0, true, true, parked),
+            Some(DegenerateReason::BothStuck)
+        );
+
+        // Ball never picked up
+        let mut monitor = DegenerateMonitor::default();
+        assert_eq!(
+            run(
+                &mut monitor,
+                BALL_UNREACHABLE_SECS + 1.0,
+                true,
+                false,
+                moving
+            ),
+            Some(DegenerateReason::BallUnreachable)
+        );
+        // Sticky once set
+        assert_eq!(
+            monitor.update(DT, true, true, moving(0)),
+            Some(DegenerateReason::BallUnreachable)
+        );
+
+        for reason in [
+            DegenerateReason::NoShots,
+            DegenerateReason::BothStuck,
+            DegenerateReason::BallUnreachable,
+        ] {
+            assert_eq!(DegenerateReason::from_code(reason.code()), Some(reason));
+        }
+    }
+}
@@ -0,0 +1,213 @@
+//! Prometheus-style metrics exporter for long simulation runs
+//!
+//! Started with `--metrics-port <PORT>`; serves `GET http://127.0.0.1:<PORT>/metrics`
+//! in the Prometheus text format. Counts completed matches, throughput, SQLite write
+//! latency, and win/loss/tie results per AI profile. Recording is a no-op when the
+//! exporter isn't running.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use super::metrics::MatchResult;
+
+/// Counters accumulated since the exporter started
+#[derive(Debug, Default)]
+struct ExporterStats {
+    matches_completed: u64,
+    db_writes: u64,
+    db_write_secs: f64,
+    db_write_max_secs: f64,
+    /// (profile, "win" | "loss" | "tie") -> count
+    profile_results: BTreeMap<(String, &'static str), u64>,
+}
+
+struct Exporter {
+    started: Instant,
+    stats: Mutex<ExporterStats>,
+}
+
+static EXPORTER: OnceLock<Exporter> = OnceLock::new();
+
+/// Start serving metrics on `127.0.0.1:<port>` (later calls are ignored)
+pub fn start(port: u16) {
+    if EXPORTER.get().is_some() {
+        return;
+    }
+    let listener = match TcpListener::bind(("127.0.0.1", port)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!(
+                "Warning: metrics exporter failed to bind port {}: {}",
+                port, e
+            );
+            return;
+        }
+    };
+    let _ = EXPORTER.set(Exporter {
+        started: Instant::now(),
+        stats: Mutex::new(ExporterStats::default()),
+    });
+    eprintln!("Metrics: http://127.0.0.1:{}/metrics", port);
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            serve_metrics(stream);
+        }
+    });
+}
+
+/// Count a finished match and its per-profile result
+pub fn record_match(result: &MatchResult) {
+    let Some(exporter) = EXPORTER.get() else {
+        return;
+    };
+    let Ok(mut stats) = exporter.stats.lock() else {
+        return;
+    };
+    stats.matches_completed += 1;
+    let (left, right) = match result.winner.as_str() {
+        "left" => ("win", "loss"),
+        "right" => ("loss", "win"),
+        _ => ("tie", "tie"),
+    };
+    for (profile, outcome) in [(&result.left_profile, left), (&result.right_profile, right)] {
+        *stats
+            .profile_results
+            .entry((profile.clone(), outcome))
+            .or_default() += 1;
+    }
+}
+
+/// Record how long one match took to write to the database
+pub fn record_db_write(elapsed: Duration) {
+    let Some(exporter) = EXPORTER.get() else {
+        return;
+    };
+    let Ok(mut stats) = exporter.stats.lock() else {
+        return;
+    };
+    let secs = elapsed.as_secs_f64();
+    stats.db_writes += 1;
+    stats.db_write_secs += secs;
+    stats.db_write_max_secs = stats.db_write_max_secs.max(secs);
+}
+
+fn serve_metrics(mut stream: TcpStream) {
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(1)));
+    let mut request = [0u8; 1024];
+    let _ = stream.read(&mut request);
+
+    let Some(exporter) = EXPORTER.get() else {
+        return;
+    };
+    let body = match exporter.stats.lock() {
+        Ok(stats) => render(&stats, exporter.started.elapsed().as_secs_f64()),
+        Err(_) => String::new(),
+    };
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n{}",
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes());
+}
+
+/// Prometheus text exposition of the current counters
+fn render(stats: &ExporterStats, uptime_secs: f64) -> String {
+    let mut out = String::new();
+    let mut metric = |name: &str, kind: &str, help: &str, value: f64| {
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        let _ = writeln!(out, "# TYPE {} {}", name, kind);
+        let _ = writeln!(out, "{} {}", name, value);
+    };
+    metric(
+        "ballgame_sim_uptime_seconds",
+        "gauge",
+        "Seconds since the exporter started",
+        uptime_secs,
+    );
+    metric(
+        "ballgame_sim_matches_completed_total",
+        "counter",
+        "Matches completed",
+        stats.matches_completed as f64,
+    );
+    metric(
+        "ballgame_sim_matches_per_second",
+        "gauge",
+        "Average matches completed per second since start",
+        if uptime_secs > 0.0 {
+            stats.matches_completed as f64 / uptime_secs
+        } else {
+            0.0
+        },
+    );
+    metric(
+        "ballgame_sim_db_write_max_seconds",
+        "gauge",
+        "Slowest single match write to the database",
+        stats.db_write_max_secs,
+    );
+
+    let _ = writeln!(
+        out,
+        "# HELP ballgame_sim_db_write_seconds Time spent writing match results to the database"
+    );
+    let _ = writeln!(out, "# TYPE ballgame_sim_db_write_seconds summary");
+    let _ = writeln!(
+        out,
+        "ballgame_sim_db_write_seconds_sum {}",
+        stats.db_write_secs
+    );
+    let _ = writeln!(
+        out,
+        "ballgame_sim_db_write_seconds_count {}",
+        stats.db_writes
+    );
+
+    let _ = writeln!(
+        out,
+        "# HELP ballgame_sim_profile_results_total Match results per AI profile"
+    );
+    let _ = writeln!(out, "# TYPE ballgame_sim_profile_results_total counter");
+    for ((profile, outcome), count) in &stats.profile_results {
+        let _ = writeln!(
+            out,
+            "ballgame_sim_profile_results_total{{profile=\"{}\",result=\"{}\"}} {}",
+            profile.replace('\\', "\\\\").replace('"', "\\\""),
+            outcome,
+            count
+        );
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_counts_and_profile_labels() {
+        let mut stats = ExporterStats {
+            matches_completed: 10,
+            db_writes: 2,
+            db_write_secs: 0.5,
+            ..Default::default()
+        };
+        stats
+            .profile_results
+            .insert(("v3_Rush_Smart".to_string(), "win"), 7);
+
+        let text = render(&stats, 5.0);
+        assert!(text.contains("ballgame_sim_matches_completed_total 10\n"));
+        assert!(text.contains("ballgame_sim_matches_per_second 2\n"));
+        assert!(text.contains("ballgame_sim_db_write_seconds_count 2\n"));
+        assert!(text.contains(
+            "ballgame_sim_profile_results_total{profile=\"v3_Rush_Smart\",result=\"win\"} 7\n"
+        ));
+    }
+}
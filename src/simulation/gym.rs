@@ -0,0 +1,304 @@
+//! Gym bridge - drive a headless match one tick at a time from outside
+//!
+//! `GymEnv` wraps the same app `run_match` plays, with one side handed to the
+//! caller: its players are marked `HumanControlled` so the AI leaves them
+//! alone, and `step` writes the given actions into their `InputState` before
+//! advancing a single 60Hz tick. Observations and actions are flat `f32`
+//! vectors, so an RL trainer needs no Bevy knowledge. `simulate --gym <SIDE>`
+//! serves the env as JSON lines on stdin/stdout (see `serve`), which a Python
+//! trainer can drive through a subprocess pipe.
+//!
+//! Observations and actions use the `ai::policy` layout, so a model trained
+//! here can be dropped into a profile as `controller: policy:<path>`.
+
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Write};
+
+use crate::ai::policy::{
+    Observation, ObservationFrame, PolicyAction, PolicyBall, PolicyPlayer, PolicyPlayerData,
+    side_mirror,
+};
+use crate::ai::{AiProfileDatabase, InputState};
+use crate::ball::{Ball, BallState};
+use crate::levels::{ArenaDims, LevelDatabase};
+use crate::player::{HumanControlled, Player, Team, Velocity};
+use crate::scoring::Score;
+use crate::tuning::PhysicsTweaks;
+
+use super::config::SimConfig;
+use super::control::SimControl;
+use super::metrics::SimMetrics;
+use super::runner::{build_match_app, step_match_app};
+
+/// A headless match with one side controlled by the caller
+pub struct GymEnv {
+    config: SimConfig,
+    level_db: LevelDatabase,
+    profile_db: AiProfileDatabase,
+    team: Team,
+    base_seed: u64,
+    episodes: u64,
+    app: Option<App>,
+    /// Controlled players in spawn order
+    controlled: Vec<Entity>,
+    last_score: (u32, u32),
+}
+
+impl GymEnv {
+    pub fn new(
+        config: SimConfig,
+        team: Team,
+        level_db: LevelDatabase,
+        profile_db: AiProfileDatabase,
+    ) -> Self {
+        let mut config = config;
+        // Progress output would corrupt a stdout protocol
+        config.quiet = true;
+        let base_seed = config.seed.unwrap_or_else(|| rand::thread_rng().r#gen());
+        Self {
+            config,
+            level_db,
+            profile_db,
+            team,
+            base_seed,
+            episodes: 0,
+            app: None,
+            controlled: Vec::new(),
+            last_score: (0, 0),
+        }
+    }
+
+    /// Start a new match (next seed) and return the first observation
+    pub fn reset(&mut self) -> Observation {
+        let seed = self.base_seed.wrapping_add(self.episodes);
+        self.episodes += 1;
+        let (mut app, _level) =
+            build_match_app(&self.config, seed, &self.level_db, &self.profile_db);
+
+        let world = app.world_mut();
+        let mut players = world.query_filtered::<(Entity, &Team), With<Player>>();
+        let mut controlled: Vec<Entity> = players
+            .iter(world)
+            .filter(|(_, team)| **team == self.team)
+            .map(|(entity, _)| entity)
+            .collect();
+        controlled.sort();
+        for &entity in &controlled {
+            world.entity_mut(entity).insert(HumanControlled);
+        }
+
+        self.controlled = controlled;
+        self.last_score = (0, 0);
+        self.app = Some(app);
+        self.observe()
+    }
+
+    /// Players the caller controls (expected length of `step`'s actions)
+    pub fn num_controlled(&self) -> usize {
+        self.controlled.len()
+    }
+
+    /// Apply one action per controlled player (missing = idle), advance one tick.
+    /// Reward is goals for minus goals against this tick. Once done, further
+    /// steps don't advance the match.
+    pub fn step(&mut self, actions: &[PolicyAction]) -> (Observation, f32, bool) {
+        if self.app.is_none() {
+            self.reset();
+        }
+        if self.is_done() {
+            return (self.observe(), 0.0, true);
+        }
+        let Some(app) = self.app.as_mut() else {
+            return (Vec::new(), 0.0, true);
+        };
+
+        let dt = 1.0 / 60.0;
+        let mirror = side_mirror(self.team);
+        for (i, &entity) in self.controlled.iter().enumerate() {
+            let action = actions.get(i).copied().unwrap_or_default();
+            if let Some(mut input) = app.world_mut().get_mut::<InputState>(entity) {
+                action.apply(&mut input, dt, mirror);
+            }
+        }
+        step_match_app(app);
+
+        let (left, right) = {
+            let score = app.world().resource::<Score>();
+            (score.left, score.right)
+        };
+        let (own, opponent) = self.side_score(left, right);
+        let reward =
+            (own as f32 - self.last_score.0 as f32) - (opponent as f32 - self.last_score.1 as f32);
+        self.last_score = (own, opponent);
+        (self.observe(), reward, self.is_done())
+    }
+
+    /// The match hit an end condition (time, score limit, stalemate)
+    pub fn is_done(&self) -> bool {
+        self.app
+            .as_ref()
+            .is_none_or(|app| app.world().resource::<SimControl>().should_exit)
+    }
+
+    /// (own, opponent) from (left, right)
+    fn side_score(&self, left: u32, right: u32) -> (u32, u32) {
+        match self.team {
+            Team::Left => (left, right),
+            Team::Right => (right, left),
+        }
+    }
+
+    fn observe(&mut self) -> Observation {
+        let team = self.team;
+        let duration_limit = self.config.duration_limit;
+        let Some(app) = self.app.as_mut() else {
+            return Vec::new();
+        };
+        let world = app.world_mut();
+
+        let charge_time = world.resource::<PhysicsTweaks>().shot_charge_time;
+        let mut players = world.query_filtered::<PolicyPlayerData, With<Player>>();
+        let players: Vec<PolicyPlayer> = players
+            .iter(world)
+            .map(|data| PolicyPlayer::from_data(data, charge_time))
+            .collect();
+        let mut balls = world.query_filtered::<(&Transform, &Velocity, &BallState), With<Ball>>();
+        let ball = balls.iter(world).next().map(PolicyBall::from_data);
+        let score = world.resource::<Score>();
+        let elapsed = world.resource::<SimMetrics>().elapsed;
+
+        ObservationFrame {
+            score: (score.left, score.right),
+            time_left: (1.0 - elapsed / duration_limit.max(1.0)).max(0.0),
+            arena: world
+                .get_resource::<ArenaDims>()
+                .copied()
+                .unwrap_or_default(),
+            ball,
+            players: &players,
+        }
+        .observe(team, None)
+    }
+}
+
+/// A request line for `serve`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "cmd", rename_all = "lowercase")]
+pub enum GymRequest {
+    Reset,
+    /// One `[move_x, jump, pickup, throw, pass]` vector per controlled player
+    Step {
+        #[serde(default)]
+        actions: Vec<Vec<f32>>,
+    },
+    Close,
+}
+
+/// A response line from `serve`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GymResponse {
+    pub obs: Observation,
+    pub reward: f32,
+    pub done: bool,
+    /// Controlled players (sent on reset)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub players: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Answer JSON-line requests until `close` or end of input:
+/// `{"cmd":"reset"}`, `{"cmd":"step","actions":[[1,0,0,0,0]]}`, `{"cmd":"close"}`
+pub fn serve(env: &mut GymEnv, input: impl BufRead, mut output: impl Write) -> std::io::Result<()> {
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<GymRequest>(&line) {
+            Ok(GymRequest::Reset) => GymResponse {
+                obs: env.reset(),
+                players: Some(env.num_controlled()),
+                ..Default::default()
+            },
+            Ok(GymRequest::Step { actions }) => {
+                let actions: Vec<PolicyAction> = actions
+                    .iter()
+                    .map(|a| PolicyAction::from_slice(a))
+                    .collect();
+                let (obs, reward, done) = env.step(&actions);
+                GymResponse {
+                    obs,
+                    reward,
+                    done,
+                    ..Default::default()
+                }
+            }
+            Ok(GymRequest::Close) => break,
+            Err(e) => GymResponse {
+                error: Some(format!("Bad request: {}", e)),
+                ..Default::default()
+            },
+        };
+        let json = serde_json::to_string(&response).unwrap_or_else(|_| "{}".to_string());
+        writeln!(output, "{}", json)?;
+        output.flush()?;
+    }
+    Ok(())
+}
+
+/// `simulate --gym <SIDE>`: serve a gym env on stdin/stdout
+pub fn run_gym(
+    config: &SimConfig,
+    left: bool,
+    level_db: &LevelDatabase,
+    profile_db: &AiProfileDatabase,
+) {
+    let team = if left { Team::Left } else { Team::Right };
+    let mut env = GymEnv::new(config.clone(), team, level_db.clone(), profile_db.clone());
+    let stdin = std::io::stdin();
+    if let Err(e) = serve(&mut env, stdin.lock(), std::io::stdout()) {
+        eprintln!("Gym: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::JUMP_BUFFER_TIME;
+
+    #[test]
+    fn test_actions_and_requests() {
+        let mut input = InputState::default();
+        let charge = PolicyAction::from_slice(&[2.0, 1.0, 0.0, 1.0]);
+        assert_eq!(charge.move_x, 1.0);
+        assert!(charge.jump && charge.throw && !charge.pickup && !charge.pass);
+
+        charge.apply(&mut input, 1.0 / 60.0, 1.0);
+        assert_eq!(input.jump_buffer_timer, JUMP_BUFFER_TIME);
+        assert!(input.throw_held && !input.throw_released);
+        // Holding jump doesn't re-buffer; releasing throw shoots
+        PolicyAction {
+            jump: true,
+            ..Default::default()
+        }
+        .apply(&mut input, 0.05, 1.0);
+        assert!((input.jump_buffer_timer - (JUMP_BUFFER_TIME - 0.05)).abs() < 1e-6);
+        assert!(!input.throw_held && input.throw_released);
+
+        assert_eq!(
+            serde_json::from_str::<GymRequest>(r#"{"cmd":"step","actions":[[0.5,0,0,1,0]]}"#)
+                .unwrap(),
+            GymRequest::Step {
+                actions: vec![vec![0.5, 0.0, 0.0, 1.0, 0.0]]
+            }
+        );
+        assert_eq!(
+            serde_json::from_str::<GymRequest>(r#"{"cmd":"reset"}"#).unwrap(),
+            GymRequest::Reset
+        );
+        assert!(serde_json::from_str::<GymRequest>(r#"{"cmd":"jump"}"#).is_err());
+    }
+}
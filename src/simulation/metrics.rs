@@ -7,6 +7,8 @@ use std::collections::HashMap;
 use crate::ai::AiGoal;
 use crate::events::GameEvent;
 
+use super::degenerate::{DegenerateMonitor, DegenerateReason};
+
 /// Statistics for a single player during a match
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PlayerStats {
@@ -40,6 +42,12 @@ pub struct PlayerStats {
     pub avg_shot_y: f32,
     /// Average shot quality at release (finalized after match)
     pub avg_shot_quality: f32,
+    /// Stays on own rim long enough to count as camping (see `RimCamping`)
+    #[serde(default)]
+    pub rim_camping: u32,
+    /// Camping stays called as defensive three-seconds violations
+    #[serde(default)]
+    pub camping_violations: u32,
     /// Internal: Sum of shot X positions (for computing average)
     #[serde(skip)]
     pub shot_positions_sum_x: f32,
@@ -56,6 +64,16 @@ impl PlayerStats {
         Self::default()
     }
 
+    /// Add time spent in an AI goal (allocates only the first time a goal is seen)
+    pub fn add_goal_time(&mut self, goal: &str, dt: f32) {
+        match self.goal_time.get_mut(goal) {
+            Some(time) => *time += dt,
+            None => {
+                self.goal_time.insert(goal.to_string(), dt);
+            }
+        }
+    }
+
     /// Calculate derived statistics
     pub fn finalize(&mut self) {
         if self.shots_attempted > 0 {
@@ -91,11 +109,27 @@ pub struct MatchResult {
     pub right_stats: PlayerStats,
     /// RNG seed used
     pub seed: u64,
+    /// Set when the match was cut short as degenerate (excluded from win rates)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub degenerate: Option<DegenerateReason>,
     /// Logged events for this match (used for DB persistence)
     #[serde(skip)]
     pub events: Vec<(u32, GameEvent)>,
 }
 
+/// A match that panicked instead of finishing (parallel batches keep going)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchFailure {
+    pub seed: u64,
+    pub level: u32,
+    pub left_profile: String,
+    pub right_profile: String,
+    /// Panic payload, when it was a string
+    pub message: String,
+    /// The match's SimConfig as JSON, for reproducing it
+    pub config_json: String,
+}
+
 impl MatchResult {
     pub fn determine_winner(&mut self) {
         self.winner = if self.score_left > self.score_right {
@@ -126,7 +160,7 @@ impl TournamentResult {
         Self::default()
     }
 
-    /// Calculate win rates from match results
+    /// Calculate win rates from match results (degenerate matches don't count)
     pub fn calculate_win_rates(&mut self) {
         // Count wins per matchup
         let mut wins: HashMap<String, HashMap<String, u32>> = HashMap::new();
@@ -134,7 +168,7 @@ impl TournamentResult {
         let mut profile_wins: HashMap<String, u32> = HashMap::new();
         let mut profile_total: HashMap<String, u32> = HashMap::new();
 
-        for result in &self.matches {
+        for result in self.matches.iter().filter(|r| r.degenerate.is_none()) {
             // Initialize if needed
             wins.entry(result.left_profile.clone())
                 .or_default()
@@ -284,6 +318,8 @@ pub struct SimMetrics {
     pub prev_nav_path_len: [usize; 2],
     /// Previous ball holder entity (for detecting shot release)
     pub prev_ball_holder: Option<Entity>,
+    /// No-shot / stuck / loose-ball timers for degenerate match detection
+    pub degenerate: DegenerateMonitor,
 }
 
 impl Default for SimMetrics {
@@ -305,6 +341,7 @@ impl Default for SimMetrics {
             prev_nav_active: [false, false],
             prev_nav_path_len: [0, 0],
             prev_ball_holder: None,
+            degenerate: DegenerateMonitor::default(),
         }
     }
 }
@@ -0,0 +1,332 @@
+//! Mirror test - left/right symmetry check for the AI
+//!
+//! Plays every seed twice with the sides swapped (left vs right, then right vs
+//! left on the same level). Swapping cancels out any difference between the two
+//! profiles, so whatever gap is left between the left and right side totals
+//! comes from side-dependent code: hard-coded coordinates, sign flips that only
+//! cover one team, fallbacks that assume a basket position. Pairs with a
+//! degenerate match are left out.
+
+use serde::Serialize;
+
+use crate::ai::AiProfileDatabase;
+use crate::levels::LevelDatabase;
+
+use super::config::SimConfig;
+use super::metrics::{MatchResult, PlayerStats};
+use super::runner::run_match;
+
+/// Largest allowed gap between left and right win rates
+pub const MIRROR_WIN_RATE_TOLERANCE: f32 = 0.15;
+/// Largest allowed relative gap for per-match averages (goals, shots, steals, distance)
+pub const MIRROR_RATE_TOLERANCE: f32 = 0.25;
+/// Per-match averages below this on both sides are too rare to compare
+const MIN_COMPARABLE: f32 = 0.5;
+
+/// Totals for one side of the court across all mirrored matches
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SideTotals {
+    pub matches: u32,
+    pub wins: u32,
+    pub goals: u32,
+    pub shots_attempted: u32,
+    pub steals_successful: u32,
+    pub distance_traveled: f32,
+}
+
+impl SideTotals {
+    fn add(&mut self, stats: &PlayerStats, won: bool) {
+        self.matches += 1;
+        self.wins += won as u32;
+        self.goals += stats.goals;
+        self.shots_attempted += stats.shots_attempted;
+        self.steals_successful += stats.steals_successful;
+        self.distance_traveled += stats.distance_traveled;
+    }
+
+    fn per_match(&self, total: f32) -> f32 {
+        if self.matches == 0 {
+            0.0
+        } else {
+            total / self.matches as f32
+        }
+    }
+}
+
+/// One left-vs-right comparison
+#[derive(Debug, Clone, Serialize)]
+pub struct MirrorCheck {
+    pub metric: String,
+    pub left: f32,
+    pub right: f32,
+    /// Absolute gap for win rate, relative gap for averages
+    pub gap: f32,
+    pub tolerance: f32,
+    pub passed: bool,
+}
+
+impl MirrorCheck {
+    fn absolute(metric: &str, left: f32, right: f32, tolerance: f32) -> Self {
+        let gap = (left - right).abs();
+        Self {
+            metric: metric.to_string(),
+            left,
+            right,
+            gap,
+            tolerance,
+            passed: gap <= tolerance,
+        }
+    }
+
+    fn relative(metric: &str, left: f32, right: f32, tolerance: f32) -> Self {
+        let larger = left.max(right);
+        let gap = if larger < MIN_COMPARABLE {
+            0.0
+        } else {
+            (left - right).abs() / larger
+        };
+        Self {
+            metric: metric.to_string(),
+            left,
+            right,
+            gap,
+            tolerance,
+            passed: gap <= tolerance,
+        }
+    }
+}
+
+/// Result of a mirror test run
+#[derive(Debug, Clone, Serialize)]
+pub struct MirrorReport {
+    pub profile_a: String,
+    pub profile_b: String,
+    /// Mirrored pairs compared
+    pub pairs: u32,
+    /// Pairs dropped because a match was degenerate
+    pub skipped: u32,
+    pub left: SideTotals,
+    pub right: SideTotals,
+    pub checks: Vec<MirrorCheck>,
+    pub passed: bool,
+}
+
+impl MirrorReport {
+    /// Compare side totals over mirrored pairs (each pair is the same seed
+    /// played A-left then B-left)
+    pub fn from_pairs(
+        profile_a: &str,
+        profile_b: &str,
+        pairs: &[(MatchResult, MatchResult)],
+    ) -> Self {
+        let mut left = SideTotals::default();
+        let mut right = SideTotals::default();
+        let mut skipped = 0;
+        for (first, second) in pairs {
+            if first.degenerate.is_some() || second.degenerate.is_some() {
+                skipped += 1;
+                continue;
+            }
+            for result in [first, second] {
+                left.add(&result.left_stats, result.winner == "left");
+                right.add(&result.right_stats, result.winner == "right");
+            }
+        }
+
+        let win_rate = |side: &SideTotals| side.per_match(side.wins as f32);
+        let checks = vec![
+            MirrorCheck::absolute(
+                "win_rate",
+                win_rate(&left),
+                win_rate(&right),
+                MIRROR_WIN_RATE_TOLERANCE,
+            ),
+            MirrorCheck::relative(
+                "goals",
+                left.per_match(left.goals as f32),
+                right.per_match(right.goals as f32),
+                MIRROR_RATE_TOLERANCE,
+            ),
+            MirrorCheck::relative(
+                "shots",
+                left.per_match(left.shots_attempted as f32),
+                right.per_match(right.shots_attempted as f32),
+                MIRROR_RATE_TOLERANCE,
+            ),
+            MirrorCheck::relative(
+                "steals",
+                left.per_match(left.steals_successful as f32),
+                right.per_match(right.steals_successful as f32),
+                MIRROR_RATE_TOLERANCE,
+            ),
+            MirrorCheck::relative(
+                "distance",
+                left.per_match(left.distance_traveled),
+                right.per_match(right.distance_traveled),
+                MIRROR_RATE_TOLERANCE,
+            ),
+        ];
+        let compared = pairs.len() as u32 - skipped;
+        Self {
+            profile_a: profile_a.to_string(),
+            profile_b: profile_b.to_string(),
+            pairs: compared,
+            skipped,
+            passed: compared > 0 && checks.iter().all(|c| c.passed),
+            left,
+            right,
+            checks,
+        }
+    }
+
+    pub fn format(&self) -> String {
+        let mut out = format!(
+            "=== Mirror Test: {} vs {} ({} pairs, {} skipped) ===\n",
+            self.profile_a, self.profile_b, self.pairs, self.skipped
+        );
+        out.push_str(&format!(
+            "  {:<10} {:>10} {:>10} {:>8} {:>8}\n",
+            "metric", "left", "right", "gap", "limit"
+        ));
+        for check in &self.checks {
+            out.push_str(&format!(
+                "  {:<10} {:>10.2} {:>10.2} {:>8.2} {:>8.2}  {}\n",
+                check.metric,
+                check.left,
+                check.right,
+                check.gap,
+                check.tolerance,
+                if check.passed { "ok" } else { "ASYMMETRIC" }
+            ));
+        }
+        out.push_str(if self.passed {
+            "PASS: left and right sides are symmetric within tolerance\n"
+        } else if self.pairs == 0 {
+            "FAIL: no usable pairs (all degenerate)\n"
+        } else {
+            "FAIL: side bias detected - check AI code for side-dependent coordinates\n"
+        });
+        out
+    }
+}
+
+/// Play `pairs` mirrored pairs of the configured matchup and report side symmetry.
+/// Exits with status 1 when a check fails.
+pub fn run_mirror_test(
+    config: &SimConfig,
+    pairs: u32,
+    level_db: &LevelDatabase,
+    profile_db: &AiProfileDatabase,
+) -> MirrorReport {
+    let profile_a = config.left_profile.clone();
+    let profile_b = config.right_profile.clone();
+    let base_seed = config.seed.unwrap_or(42);
+
+    let mut swapped = config.clone();
+    swapped.left_profile = profile_b.clone();
+    swapped.right_profile = profile_a.clone();
+
+    let mut results = Vec::new();
+    for i in 0..pairs {
+        if !config.quiet {
+            print!("\rMirror pair {}/{}...", i + 1, pairs);
+            use std::io::Write;
+            std::io::stdout().flush().ok();
+        }
+        let seed = base_seed.wrapping_add(i as u64);
+        results.push((
+            run_match(config, seed, level_db, profile_db),
+            run_match(&swapped, seed, level_db, profile_db),
+        ));
+    }
+    if !config.quiet {
+        println!();
+    }
+
+    let report = MirrorReport::from_pairs(&profile_a, &profile_b, &results);
+    print!("{}", report.format());
+
+    if let Some(output_file) = &config.output_file {
+        let json = serde_json::to_string_pretty(&report).unwrap();
+        std::fs::write(output_file, json).expect("Failed to write output");
+        println!("Mirror report written to {}", output_file);
+    }
+
+    if !report.passed {
+        std::process::exit(1);
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::DegenerateReason;
+
+    fn result(score_left: u32, score_right: u32, shots: (u32, u32)) -> MatchResult {
+        let stats = |goals: u32, shots: u32| PlayerStats {
+            goals,
+            shots_attempted: shots,
+            distance_traveled: 1000.0,
+            ..Default::default()
+        };
+        MatchResult {
+            level: 1,
+            level_name: "Test".to_string(),
+            left_profile: "A".to_string(),
+            right_profile: "B".to_string(),
+            duration: 60.0,
+            score_left,
+            score_right,
+            winner: if score_left > score_right {
+                "left"
+            } else if score_right > score_left {
+                "right"
+            } else {
+                "tie"
+            }
+            .to_string(),
+            left_stats: stats(score_left, shots.0),
+            right_stats: stats(score_right, shots.1),
+            seed: 1,
+            degenerate: None,
+            events: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_mirror_report_flags_side_bias() {
+        // The stronger profile wins from either side: symmetric
+        let fair = vec![
+            (result(3, 1, (6, 4)), result(1, 3, (4, 6))),
+            (result(2, 2, (5, 5)), result(2, 2, (5, 5))),
+        ];
+        let report = MirrorReport::from_pairs("A", "B", &fair);
+        assert!(report.passed, "{}", report.format());
+        assert_eq!(report.left.matches, 4);
+
+        // Left wins no matter who plays there: biased
+        let biased = vec![
+            (result(3, 0, (8, 2)), result(3, 0, (8, 2))),
+            (result(2, 1, (6, 3)), result(4, 1, (7, 2))),
+        ];
+        let report = MirrorReport::from_pairs("A", "B", &biased);
+        assert!(!report.passed);
+        let failed: Vec<_> = report
+            .checks
+            .iter()
+            .filter(|c| !c.passed)
+            .map(|c| c.metric.as_str())
+            .collect();
+        assert!(failed.contains(&"win_rate"));
+        assert!(failed.contains(&"shots"));
+        assert!(!failed.contains(&"distance"));
+
+        // Degenerate pairs are dropped; nothing left to compare fails
+        let mut stuck = result(0, 0, (0, 0));
+        stuck.degenerate = Some(DegenerateReason::BothStuck);
+        let report = MirrorReport::from_pairs("A", "B", &[(stuck, result(0, 0, (0, 0)))]);
+        assert_eq!(report.skipped, 1);
+        assert!(!report.passed);
+    }
+}
@@ -4,23 +4,41 @@
 //! on AI behavior, performance, and decision-making.
 
 pub mod app_builder;
+pub mod bracket;
+pub mod coach;
 pub mod config;
 pub mod control;
+pub mod curriculum;
 pub mod db;
+pub mod degenerate;
+pub mod exporter;
 pub mod ghost;
+pub mod gym;
 pub mod metrics;
+pub mod mirror;
 pub mod multihop_test;
 pub mod parallel;
 pub mod reachability_test;
 pub mod runner;
+pub mod schedule;
+pub mod seed_bank;
 pub mod setup;
 pub mod shot_test;
 
 pub use app_builder::HeadlessAppBuilder;
+pub use bracket::{
+    BracketFormat, BracketResult, BracketSeeding, BracketSeries, BracketSide, SeriesScore,
+    play_bracket,
+};
+pub use coach::CoachSides;
 pub use config::{SimConfig, SimMode};
 pub use control::{SimControl, SimEventBuffer};
+pub use curriculum::{
+    Curriculum, CurriculumStage, CurriculumTrace, StageOutcome, run_curriculum,
+};
 pub use db::{
     ClosestMoment,
+    DegenerateMatch,
     // Analysis types
     DistanceAnalysis,
     EventRecord,
@@ -37,7 +55,12 @@ pub use ghost::{
     GhostOutcome, GhostPlaybackState, GhostTrial, GhostTrialResult, InputSample,
     ghost_check_end_conditions, ghost_input_system, load_ghost_trial, max_tick,
 };
-pub use metrics::{MatchResult, PlayerStats, SimMetrics, TournamentResult};
-pub use runner::{run_match, run_simulation};
+pub use degenerate::DegenerateReason;
+pub use gym::{GymEnv, GymRequest, GymResponse, serve as serve_gym};
+pub use metrics::{MatchFailure, MatchResult, PlayerStats, SimMetrics, TournamentResult};
+pub use mirror::{MirrorCheck, MirrorReport, SideTotals, run_mirror_test};
+pub use runner::{build_match_app, run_match, run_simulation, step_match_app};
+pub use schedule::{HUMAN_SLOT, MatchSchedule, ScheduledMatch, is_human_slot};
+pub use seed_bank::{SEED_BANK_FILE, SeedBank, SeedBankEntry};
 pub use setup::{level_geometry_setup, sim_setup, spawn_corner_steps};
 pub use shot_test::{ShotOutcome, run_shot_test};
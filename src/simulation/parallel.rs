@@ -2,7 +2,11 @@
 //!
 //! Uses Rayon to run multiple simulations concurrently.
 //! Each simulation runs in its own Bevy app with minimal threading
-//! to avoid hitting OS thread limits.
+//! to avoid hitting OS thread limits. A match that panics is caught and
+//! reported as a `MatchFailure` (seed + config) instead of taking down the
+//! batch; the runner stores failures in the `match_failures` table.
+
+use std::panic::{AssertUnwindSafe, catch_unwind};
 
 use rayon::prelude::*;
 
@@ -10,7 +14,8 @@ use crate::ai::AiProfileDatabase;
 use crate::levels::LevelDatabase;
 
 use super::config::SimConfig;
-use super::metrics::MatchResult;
+use super::control::batch_control;
+use super::metrics::{MatchFailure, MatchResult};
 use super::runner::run_match;
 
 /// Configuration for parallel execution
@@ -37,25 +42,91 @@ pub fn init_parallel(threads: usize) {
     // If threads == 0, use Rayon's default (auto-detect)
 }
 
+/// Finished matches and panicked ones from a parallel batch
+#[derive(Debug, Default)]
+pub struct ParallelResults {
+    /// In config order
+    pub results: Vec<MatchResult>,
+    pub failures: Vec<MatchFailure>,
+}
+
 /// Run multiple matches in parallel
 ///
 /// Each match gets a unique seed derived from the base seed.
-/// Returns results in the same order as configs.
+/// Returns results in the same order as configs. The batch control REPL can
+/// lower how many run at once, and matches not yet started when it quits are
+/// dropped from the results.
 pub fn run_matches_parallel(
     configs: &[MatchConfig],
     level_db: &LevelDatabase,
     profile_db: &AiProfileDatabase,
-) -> Vec<MatchResult> {
-    configs
+) -> ParallelResults {
+    let batch = batch_control();
+    let outcomes: Vec<_> = configs
         .par_iter()
-        .map(|cfg| {
+        .filter_map(|cfg| {
+            batch.start_parallel();
+            if batch.quit_requested() {
+                batch.finish_parallel();
+                return None;
+            }
             let mut sim_config = cfg.base_config.clone();
             sim_config.level = Some(cfg.level);
             sim_config.left_profile = cfg.left_profile.clone();
             sim_config.right_profile = cfg.right_profile.clone();
-            run_match(&sim_config, cfg.seed, level_db, profile_db)
+            let outcome = run_isolated(&sim_config, cfg, || {
+                run_match(&sim_config, cfg.seed, level_db, profile_db)
+            });
+            batch.finish_parallel();
+            Some(outcome)
         })
-        .collect()
+        .collect();
+
+    let mut parallel = ParallelResults::default();
+    for outcome in outcomes {
+        match outcome {
+            Ok(result) => parallel.results.push(result),
+            Err(failure) => parallel.failures.push(failure),
+        }
+    }
+    parallel
+}
+
+/// Run one match, turning a panic into a `MatchFailure`
+fn run_isolated(
+    sim_config: &SimConfig,
+    cfg: &MatchConfig,
+    run: impl FnOnce() -> MatchResult,
+) -> Result<MatchResult, MatchFailure> {
+    catch_unwind(AssertUnwindSafe(run)).map_err(|payload| {
+        // The panicked match never reached its own cleanup
+        batch_control().match_finished(cfg.seed);
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "non-string panic payload".to_string());
+        eprintln!(
+            "WARNING: match panicked on level {} ({} vs {}): {}. \
+             Reproduce with: --level {} --left {} --right {} --seed {}",
+            cfg.level,
+            cfg.left_profile,
+            cfg.right_profile,
+            message,
+            cfg.level,
+            cfg.left_profile,
+            cfg.right_profile,
+            cfg.seed
+        );
+        MatchFailure {
+            seed: cfg.seed,
+            level: cfg.level,
+            left_profile: cfg.left_profile.clone(),
+            right_profile: cfg.right_profile.clone(),
+            message,
+            config_json: serde_json::to_string(sim_config).unwrap_or_default(),
+        }
+    })
 }
 
 /// Configuration for a single match in a parallel batch
@@ -82,7 +153,7 @@ pub fn run_tournament_parallel(
     base_seed: u64,
     level_db: &LevelDatabase,
     profile_db: &AiProfileDatabase,
-) -> Vec<MatchResult> {
+) -> ParallelResults {
     // Use config profiles if specified, otherwise use all profiles from database
     let profiles: Vec<String> = if base_config.profiles.is_empty() {
         profile_db
@@ -154,7 +225,7 @@ pub fn run_multi_match_parallel(
     base_seed: u64,
     level_db: &LevelDatabase,
     profile_db: &AiProfileDatabase,
-) -> Vec<MatchResult> {
+) -> ParallelResults {
     // Use config levels if specified, otherwise build list excluding debug levels and Pit
     let valid_levels: Vec<u32> = if base_config.levels.is_empty() {
         (1..=level_db.len() as u32)
@@ -199,7 +270,7 @@ pub fn run_level_sweep_parallel(
     base_seed: u64,
     level_db: &LevelDatabase,
     profile_db: &AiProfileDatabase,
-) -> Vec<MatchResult> {
+) -> ParallelResults {
     let mut configs = Vec::new();
     let mut match_num = 0u64;
 
@@ -242,4 +313,21 @@ mod tests {
         assert_eq!(cloned.level, 3);
         assert_eq!(cloned.seed, 12345);
     }
+
+    #[test]
+    fn test_panicking_match_becomes_failure() {
+        let cfg = MatchConfig {
+            base_config: SimConfig::default(),
+            level: 2,
+            left_profile: "Test".to_string(),
+            right_profile: "Other".to_string(),
+            seed: 99,
+        };
+        let failure = run_isolated(&cfg.base_config, &cfg, || panic!("nav graph empty"))
+            .expect_err("panic should be caught");
+        assert_eq!(failure.seed, 99);
+        assert_eq!(failure.level, 2);
+        assert_eq!(failure.message, "nav graph empty");
+        assert!(failure.config_json.contains("\"duration_limit\""));
+    }
 }
@@ -6,36 +6,59 @@ use rand::Rng;
 use std::time::Duration;
 
 use crate::ai::{
-    AiCapabilities, AiNavState, AiProfileDatabase, AiState, HeatmapBundle, InputState, NavGraph,
-    ai_decision_update, ai_navigation_update, load_heatmaps_on_level_change,
-    mark_nav_dirty_on_level_change, rebuild_nav_graph, shot_quality::evaluate_shot_quality,
+    AiCapabilities, AiNavState, AiProfileDatabase, AiState, CONTESTED_QUALITY_DIR,
+    ContestedQuality, HeatmapBundle, InputState, MatchClock, NavEdgeStats, NavGraph,
+    ai_decision_update, ai_mistake_update, ai_navigation_update, ai_policy_update,
+    load_heatmaps_on_level_change, mark_nav_dirty_on_level_change, rebuild_nav_graph,
+    shot_quality::evaluate_shot_quality, sync_nav_edge_penalties,
 };
+use crate::analytics::upload_match_results;
 use crate::ball::{
     Ball, BallState, CurrentPalette, Velocity, apply_velocity, ball_collisions, ball_follow_holder,
-    ball_gravity, ball_player_collision, ball_spin, ball_state_update, pickup_ball,
+    ball_gravity, ball_player_collision, ball_rails, ball_spin, ball_state_update, pickup_ball,
+    recover_stuck_ball,
 };
+use crate::config::GameplayConfig;
 use crate::constants::*;
-use crate::debug_logging::DebugLogConfig;
 use crate::events::{
-    EmitterConfig, EventBuffer, EventBus, EventEmitterState, GameConfig, GameEvent,
-    emit_game_events, snapshot_ball, snapshot_player,
+    BasketSnapshot, EmitterConfig, EventBuffer, EventBus, EventEmitterState, GameConfig,
+    GameEvent, SnapshotPool, TeamTags, emit_game_events, intern, snapshot_ball,
+    snapshot_player,
 };
-use crate::levels::LevelDatabase;
+use crate::levels::{LevelDatabase, apply_wind_forces, level_feature_traversal, move_baskets};
+use crate::momentum::{Momentum, update_momentum};
+use crate::overtime::{Overtime, shrink_court};
 use crate::palettes::PaletteDatabase;
 use crate::player::TargetBasket;
 use crate::player::{
     HoldingBall, JumpState, Player, Team, apply_gravity, apply_input, check_collisions,
 };
-use crate::scoring::{CurrentLevel, Score, check_scoring};
+use crate::scoring::{
+    BallReset, CurrentLevel, OutOfBoundsRule, PossessionArrow, RimCamping, Score, ShotClock,
+    check_out_of_bounds, check_scoring, reset_possession_arrow, shot_clock_update,
+};
 use crate::shooting::{ChargingShot, LastShotInfo, throw_ball, update_shot_charge};
-use crate::steal::{StealContest, StealCooldown, StealTracker, steal_cooldown_update};
+use crate::snapshot::SpectatePlugin;
+use crate::steal::{
+    LooseBall, StealContest, StealCooldown, StealTracker, steal_cooldown_update,
+};
 use crate::tuning::{self, PhysicsTweaks};
 use crate::world::Basket;
 
+use super::bracket::{
+    SeriesScore, format_report as format_bracket_report, planned_matches, play_bracket,
+    seed_entrants,
+};
 use super::config::SimConfig;
-use super::control::{SimControl, SimEventBuffer};
+use super::coach::{CoachState, sim_coach_halftime};
+use super::control::{SimControl, SimEventBuffer, batch_control, start_control};
+use super::curriculum::run_curriculum;
 use super::db::{RunStats, SimDatabase};
-use super::metrics::{MatchResult, SimMetrics};
+use super::exporter;
+use super::gym::run_gym;
+use super::metrics::{MatchFailure, MatchResult, SimMetrics};
+use super::mirror::run_mirror_test;
+use super::schedule::MatchSchedule;
 use super::setup::sim_setup;
 use super::shot_test::run_shot_test;
 
@@ -75,13 +98,14 @@ fn get_effective_level(config: &SimConfig, level_db: &LevelDatabase, seed: u64)
     valid_levels[idx]
 }
 
-/// Run a single match and return the result
-pub fn run_match(
+/// Build the headless app for one match and run its Startup.
+/// Returns the app and the effective level; advance it with `step_match_app`.
+pub fn build_match_app(
     config: &SimConfig,
     seed: u64,
     level_db: &LevelDatabase,
     profile_db: &AiProfileDatabase,
-) -> MatchResult {
+) -> (App, u32) {
     // Determine effective level (random if not specified)
     let level = get_effective_level(config, level_db, seed);
 
@@ -120,12 +144,29 @@ pub fn run_match(
     app.init_resource::<StealTracker>();
     app.init_resource::<NavGraph>();
     app.init_resource::<AiCapabilities>();
+    app.insert_resource(ContestedQuality::load_dir(std::path::Path::new(
+        CONTESTED_QUALITY_DIR,
+    )));
     app.init_resource::<HeatmapBundle>();
     app.init_resource::<PhysicsTweaks>();
+    let gameplay = GameplayConfig::load("simulate");
+    app.add_plugins(gameplay.clone());
     app.init_resource::<LastShotInfo>();
     app.insert_resource(CurrentPalette(0)); // Use first palette for simulation
     app.init_resource::<PaletteDatabase>();
     app.insert_resource(EventBus::new());
+    app.insert_resource(PossessionArrow::new(config.possession_arrow));
+    app.insert_resource(Momentum::new(config.momentum));
+    app.insert_resource(LooseBall::new(config.loose_ball));
+    app.insert_resource(RimCamping::new(config.rim_camping_violation));
+    app.insert_resource(ShotClock::new(config.shot_clock));
+    app.insert_resource(OutOfBoundsRule::new(config.out_of_bounds));
+    app.insert_resource(BallReset::new(config.ball_reset));
+    app.insert_resource(MatchClock::new(Some(config.duration_limit)));
+    app.insert_resource(NavEdgeStats::new(config.nav_edge_penalty));
+
+    let debug_config = config.debug_log_config();
+    debug_config.apply_env();
 
     // Event logging buffer
     let mut event_buffer = SimEventBuffer {
@@ -133,6 +174,7 @@ pub fn run_match(
         enabled: config.db_path.is_some(),
         emitter_state: EventEmitterState::with_config(EmitterConfig {
             track_both_ai_goals: true,
+            tick_interval_ms: debug_config.tick_interval_ms,
         }),
     };
 
@@ -150,10 +192,11 @@ pub fn run_match(
             0.0,
             GameEvent::MatchStart {
                 level,
-                level_name,
-                left_profile: config.left_profile.clone(),
-                right_profile: config.right_profile.clone(),
+                level_name: intern(&level_name),
+                left_profile: intern(&config.left_profile),
+                right_profile: intern(&config.right_profile),
                 seed,
+                teams: TeamTags::default(),
             },
         );
 
@@ -172,33 +215,33 @@ pub fn run_match(
                 ball_gravity: BALL_GRAVITY,
                 ball_bounce: BALL_BOUNCE,
                 ball_air_friction: BALL_AIR_FRICTION,
-                ball_ground_friction: BALL_GROUND_FRICTION,
+                ball_ground_friction: gameplay.ball.ground_friction,
                 // Shooting
                 shot_max_power: SHOT_MAX_POWER,
-                shot_max_speed: SHOT_MAX_SPEED,
+                shot_max_speed: gameplay.shot.max_speed,
                 shot_charge_time: SHOT_CHARGE_TIME,
                 shot_max_variance: SHOT_MAX_VARIANCE,
                 shot_min_variance: SHOT_MIN_VARIANCE,
                 // Steal
-                steal_range: STEAL_RANGE,
-                steal_success_chance: STEAL_SUCCESS_CHANCE,
-                steal_cooldown: STEAL_COOLDOWN,
+                steal_range: gameplay.steal.range,
+                steal_success_chance: gameplay.steal.success_chance,
+                steal_cooldown: gameplay.steal.cooldown,
+                steal_fail_cooldown: gameplay.steal.fail_cooldown,
+                steal_victim_cooldown: gameplay.steal.victim_cooldown,
+                steal_charging_bonus: gameplay.steal.charging_bonus,
                 // Presets not tracked in simulation (uses defaults)
                 preset_movement: None,
                 preset_ball: None,
                 preset_shooting: None,
                 preset_composite: None,
+                preset_steal: None,
+                ball_style: None,
+                ball_physics: None,
             }),
         );
     }
     app.insert_resource(event_buffer);
 
-    let mut debug_config = DebugLogConfig::load();
-    if config.debug_log {
-        debug_config.enabled = true;
-    }
-    debug_config.apply_env();
-
     // Simulation resources
     app.insert_resource(SimControl {
         config: config.clone(),
@@ -207,6 +250,8 @@ pub fn run_match(
     });
     app.insert_resource(SimMetrics::new());
     app.insert_resource(debug_config);
+    app.init_resource::<Overtime>();
+    app.init_resource::<CoachState>();
 
     // Startup system
     app.add_systems(Startup, (tuning::load_global_tuning_system, sim_setup));
@@ -222,14 +267,19 @@ pub fn run_match(
             mark_nav_dirty_on_level_change,
             load_heatmaps_on_level_change,
             rebuild_nav_graph,
+            sync_nav_edge_penalties,
             ai_navigation_update,
             ai_decision_update,
+            ai_mistake_update,
+            ai_policy_update,
         )
             .chain(),
     );
 
     // Note: steal_cooldown_update is only in FixedUpdate to avoid double-ticking
     app.add_systems(Update, (metrics_update, emit_simulation_events));
+    app.add_systems(Update, sim_coach_halftime.after(metrics_update));
+    app.add_plugins(SpectatePlugin(config.spectate.clone()));
 
     app.add_systems(
         FixedUpdate,
@@ -238,9 +288,13 @@ pub fn run_match(
             apply_gravity,
             ball_gravity,
             ball_spin,
+            apply_wind_forces,
+            shrink_court,
+            move_baskets,
             apply_velocity,
             check_collisions,
-            ball_collisions,
+            (ball_collisions, ball_rails).chain(),
+            level_feature_traversal,
             ball_state_update,
             ball_player_collision,
             ball_follow_holder,
@@ -248,7 +302,15 @@ pub fn run_match(
             steal_cooldown_update,
             update_shot_charge,
             throw_ball,
-            check_scoring,
+            (
+                check_scoring,
+                shot_clock_update,
+                check_out_of_bounds,
+                reset_possession_arrow,
+                update_momentum,
+                recover_stuck_ball,
+            )
+                .chain(),
             sim_check_end_conditions,
         )
             .chain(),
@@ -259,35 +321,75 @@ pub fn run_match(
     app.cleanup();
     app.update(); // This runs Startup, First, etc.
 
-    // Then run simulation loop with manual scheduling at 60Hz
+    (app, level)
+}
+
+/// Advance a match app by one 60Hz tick: AI decisions (Update), then physics (FixedUpdate)
+pub fn step_match_app(app: &mut App) {
     let fixed_dt = Duration::from_secs_f32(1.0 / 60.0);
 
-    loop {
-        // Advance all time resources consistently
-        app.world_mut()
-            .resource_mut::<Time<Virtual>>()
-            .advance_by(fixed_dt);
-        app.world_mut()
-            .resource_mut::<Time<Real>>()
-            .advance_by(fixed_dt);
-        app.world_mut()
-            .resource_mut::<Time<Fixed>>()
-            .advance_by(fixed_dt);
-
-        // Run Update schedule (AI decisions)
-        app.world_mut().run_schedule(Update);
-
-        // Run FixedUpdate schedule (physics)
-        app.world_mut().run_schedule(FixedUpdate);
+    // Advance all time resources consistently
+    app.world_mut()
+        .resource_mut::<Time<Virtual>>()
+        .advance_by(fixed_dt);
+    app.world_mut()
+        .resource_mut::<Time<Real>>()
+        .advance_by(fixed_dt);
+    app.world_mut()
+        .resource_mut::<Time<Fixed>>()
+        .advance_by(fixed_dt);
+
+    // Run Update schedule (AI decisions)
+    app.world_mut().run_schedule(Update);
+
+    // Run FixedUpdate schedule (physics)
+    app.world_mut().run_schedule(FixedUpdate);
+}
 
-        let control = app.world().resource::<SimControl>();
+/// Run a single match and return the result
+pub fn run_match(
+    config: &SimConfig,
+    seed: u64,
+    level_db: &LevelDatabase,
+    profile_db: &AiProfileDatabase,
+) -> MatchResult {
+    let (mut app, level) = build_match_app(config, seed, level_db, profile_db);
+
+    // Then run simulation loop with manual scheduling at 60Hz
+    let batch = batch_control();
+    let match_label = format!(
+        "{} vs {} on level {}",
+        config.left_profile, config.right_profile, level
+    );
+    batch.report_match(seed, match_label.clone());
+    let mut frame = 0u32;
+
+    loop {
+        step_match_app(&mut app);
+
+        // Batch REPL: refresh status once a second, honor pause/skip
+        frame += 1;
+        if frame % 60 == 0 {
+            let score = app.world().resource::<Score>();
+            let elapsed = app.world().resource::<SimMetrics>().elapsed;
+            batch.report_match(
+                seed,
+                format!(
+                    "{}: {}-{} at {:.0}s",
+                    match_label, score.left, score.right, elapsed
+                ),
+            );
+        }
+        let mut control = app.world_mut().resource_mut::<SimControl>();
+        control.poll_batch();
         if control.should_exit {
             break;
         }
     }
+    batch.match_finished(seed);
 
     // Extract results - clone the values we need to avoid borrow conflicts
-    let (elapsed, score_left, score_right, left_stats, right_stats) = {
+    let (elapsed, score_left, score_right, mut left_stats, mut right_stats, degenerate) = {
         let metrics = app.world().resource::<SimMetrics>();
         let score = app.world().resource::<Score>();
         (
@@ -296,8 +398,29 @@ pub fn run_match(
             score.right,
             metrics.left.clone(),
             metrics.right.clone(),
+            metrics.degenerate.reason,
         )
     };
+    if let Some(camping) = app.world().get_resource::<RimCamping>() {
+        (left_stats.rim_camping, left_stats.camping_violations) = camping.counts(Team::Left);
+        (right_stats.rim_camping, right_stats.camping_violations) = camping.counts(Team::Right);
+    }
+
+    if let Some(reason) = degenerate {
+        eprintln!(
+            "WARNING: degenerate match ({}) at {:.1}s on level {} ({} vs {}). \
+             Reproduce with: --level {} --left {} --right {} --seed {}",
+            reason.code(),
+            elapsed,
+            level,
+            config.left_profile,
+            config.right_profile,
+            level,
+            config.left_profile,
+            config.right_profile,
+            seed
+        );
+    }
 
     // Warn about broken AI behavior but don't fail (for debugging)
     let total_shots = left_stats.shots_attempted + right_stats.shots_attempted;
@@ -350,12 +473,14 @@ pub fn run_match(
         left_stats,
         right_stats,
         seed,
+        degenerate,
         events: Vec::new(),
     };
 
     result.left_stats.finalize();
     result.right_stats.finalize();
     result.determine_winner();
+    exporter::record_match(&result);
 
     if let Some(mut event_buffer) = app.world_mut().get_resource_mut::<SimEventBuffer>() {
         if event_buffer.enabled {
@@ -394,12 +519,17 @@ fn metrics_update(
     balls: Query<(&Transform, &BallState), With<Ball>>,
     baskets: Query<(&Transform, &Basket)>,
     score: Res<Score>,
+    clock: Option<ResMut<MatchClock>>,
 ) {
     // Use fixed timestep for consistent headless simulation (60 FPS)
     const FIXED_DT: f32 = 1.0 / 60.0;
     let dt = FIXED_DT;
     metrics.elapsed += dt;
+    if let Some(mut clock) = clock {
+        clock.elapsed = metrics.elapsed;
+    }
     metrics.time_since_score += dt;
+    let shots_before = metrics.left.shots_attempted + metrics.right.shots_attempted;
 
     // Detect shot release (ball transitions from Held to InFlight)
     for (_ball_transform, ball_state) in &balls {
@@ -420,7 +550,7 @@ fn metrics_update(
                             .map(|(t, _)| t.translation.truncate())
                             .unwrap_or_default();
 
-                        let quality = evaluate_shot_quality(pos, basket_pos);
+                        let quality = evaluate_shot_quality(pos, basket_pos, 0.0);
 
                         match shooter_team {
                             Team::Left => {
@@ -443,7 +573,7 @@ fn metrics_update(
             BallState::Held(holder) => {
                 metrics.prev_ball_holder = Some(*holder);
             }
-            BallState::Free => {
+            BallState::Free | BallState::Passed { .. } => {
                 metrics.prev_ball_holder = None;
             }
         }
@@ -454,7 +584,7 @@ fn metrics_update(
         &players
     {
         let pos = transform.translation.truncate();
-        let goal_name = format!("{:?}", ai_state.current_goal);
+        let goal_name = ai_state.current_goal.name();
         let has_ball = holding.is_some();
 
         // Get player index for array access
@@ -514,7 +644,7 @@ fn metrics_update(
                 }
 
                 // Track goal time
-                *metrics.left.goal_time.entry(goal_name).or_insert(0.0) += dt;
+                metrics.left.add_goal_time(goal_name, dt);
             }
             Team::Right => {
                 // Track distance
@@ -529,7 +659,7 @@ fn metrics_update(
                 }
 
                 // Track goal time
-                *metrics.right.goal_time.entry(goal_name).or_insert(0.0) += dt;
+                metrics.right.add_goal_time(goal_name, dt);
             }
         }
     }
@@ -548,18 +678,47 @@ fn metrics_update(
         metrics.right.shots_made += 1;
         metrics.time_since_score = 0.0;
     }
+
+    // Degenerate match detection
+    let shot_released = metrics.left.shots_attempted + metrics.right.shots_attempted > shots_before;
+    let ball_held = balls
+        .iter()
+        .any(|(_, state)| matches!(state, BallState::Held(_)));
+    let positions = [metrics.last_pos_left, metrics.last_pos_right];
+    metrics
+        .degenerate
+        .update(dt, shot_released, ball_held, positions);
 }
 
 /// Check end conditions for simulation
 fn sim_check_end_conditions(
     metrics: Res<SimMetrics>,
     mut control: ResMut<SimControl>,
+    mut overtime: ResMut<Overtime>,
     score: Res<Score>,
 ) {
     let config = &control.config;
 
-    // Time limit
+    // Sudden death: first goal wins; give up as a tie after another full regulation
+    if overtime.active {
+        if overtime.is_decided(&score) || overtime.elapsed >= config.duration_limit {
+            control.should_exit = true;
+        }
+        return;
+    }
+
+    // Time limit (tied matches go to overtime if enabled)
     if metrics.elapsed >= config.duration_limit {
+        if config.overtime && score.left == score.right {
+            overtime.start(&score);
+            if !config.quiet {
+                println!(
+                    "Tied {}-{} - sudden death overtime",
+                    score.left, score.right
+                );
+            }
+            return;
+        }
         control.should_exit = true;
         return;
     }
@@ -575,6 +734,12 @@ fn sim_check_end_conditions(
     // Stalemate
     if metrics.time_since_score >= config.stalemate_timeout && (score.left > 0 || score.right > 0) {
         control.should_exit = true;
+        return;
+    }
+
+    // Degenerate (no shots, both stuck, ball unreachable)
+    if !config.allow_degenerate && metrics.degenerate.reason.is_some() {
+        control.should_exit = true;
     }
 }
 
@@ -604,6 +769,7 @@ fn emit_simulation_events(
     >,
     baskets: Query<(&Transform, &Basket)>,
     balls: Query<(&Transform, &Velocity, &BallState), With<Ball>>,
+    mut pool: Local<SnapshotPool>,
 ) {
     if !event_buffer.enabled {
         return;
@@ -611,11 +777,27 @@ fn emit_simulation_events(
 
     let time = metrics.elapsed;
 
-    // Convert query results to snapshots
-    let player_snapshots: Vec<_> = players
-        .iter()
-        .map(
-            |(
+    // Convert query results to snapshots (reusing last frame's vectors)
+    let SnapshotPool {
+        players: ref mut player_snapshots,
+        baskets: ref mut basket_snapshots,
+    } = *pool;
+    player_snapshots.clear();
+    basket_snapshots.clear();
+    player_snapshots.extend(players.iter().map(
+        |(
+            entity,
+            team,
+            transform,
+            velocity,
+            target,
+            charging,
+            ai_state,
+            steal_cooldown,
+            holding,
+            input_state,
+        )| {
+            snapshot_player(
                 entity,
                 team,
                 transform,
@@ -626,30 +808,13 @@ fn emit_simulation_events(
                 steal_cooldown,
                 holding,
                 input_state,
-            )| {
-                snapshot_player(
-                    entity,
-                    team,
-                    transform,
-                    velocity,
-                    target,
-                    charging,
-                    ai_state,
-                    steal_cooldown,
-                    holding,
-                    input_state,
-                )
-            },
-        )
-        .collect();
-
-    let basket_snapshots: Vec<_> = baskets
-        .iter()
-        .map(|(transform, basket)| crate::events::BasketSnapshot {
-            basket: *basket,
-            position: (transform.translation.x, transform.translation.y),
-        })
-        .collect();
+            )
+        },
+    ));
+    basket_snapshots.extend(baskets.iter().map(|(transform, basket)| BasketSnapshot {
+        basket: *basket,
+        position: (transform.translation.x, transform.translation.y),
+    }));
 
     let ball_snapshot = balls
         .iter()
@@ -670,8 +835,8 @@ fn emit_simulation_events(
         time,
         &score,
         &steal_contest,
-        &player_snapshots,
-        &basket_snapshots,
+        player_snapshots,
+        basket_snapshots,
         ball_snapshot.as_ref(),
         Some(&shot_info),
     );
@@ -688,6 +853,14 @@ pub fn run_simulation(config: SimConfig) {
         super::parallel::init_parallel(config.parallel);
     }
 
+    if let Some(port) = config.metrics_port {
+        exporter::start(port);
+    }
+
+    if config.control || config.control_port.is_some() {
+        start_control(config.control, config.control_port, config.parallel);
+    }
+
     // Initialize database if requested
     // For tournaments, auto-generate a timestamped db to ensure fresh data (unless estimating)
     let db_path = if config.est_run_time {
@@ -867,10 +1040,12 @@ pub fn run_simulation(config: SimConfig) {
                     db,
                     "single",
                     std::slice::from_ref(&result),
+                    &[],
                     &config,
                     Some(&run_stats),
                 );
             }
+            upload_results(&config, std::slice::from_ref(&result));
         }
 
         super::config::SimMode::MultiMatch { count } => {
@@ -893,20 +1068,26 @@ pub fn run_simulation(config: SimConfig) {
             }
 
             let base_seed = config.seed.unwrap_or_else(|| rand::thread_rng().r#gen());
+            let mut failures = Vec::new();
 
             let results = if parallel_mode {
                 // Parallel execution
-                super::parallel::run_multi_match_parallel(
+                let parallel = super::parallel::run_multi_match_parallel(
                     &config,
                     *count,
                     base_seed,
                     &level_db,
                     &profile_db,
-                )
+                );
+                failures = parallel.failures;
+                parallel.results
             } else {
                 // Sequential execution
                 let mut results = Vec::new();
                 for i in 0..*count {
+                    if batch_control().quit_requested() {
+                        break;
+                    }
                     if !config.quiet {
                         print!("\rMatch {}/{}...", i + 1, count);
                         use std::io::Write;
@@ -920,8 +1101,10 @@ pub fn run_simulation(config: SimConfig) {
                 results
             };
 
+            // Fewer than planned if the batch was quit early
+            let played = results.len() as u32;
             if !config.quiet {
-                println!("\rCompleted {} matches.", count);
+                println!("\rCompleted {} matches.", played);
             }
 
             // Aggregate results
@@ -934,14 +1117,14 @@ pub fn run_simulation(config: SimConfig) {
                 "\nResults: {} wins - {} ties - {} losses",
                 wins,
                 ties,
-                count - wins - ties
+                played - wins - ties
             );
             println!(
                 "Total score: {} - {} (avg: {:.1} - {:.1})",
                 total_left,
                 total_right,
-                total_left as f32 / *count as f32,
-                total_right as f32 / *count as f32
+                total_left as f32 / played.max(1) as f32,
+                total_right as f32 / played.max(1) as f32
             );
 
             // Store in database if enabled
@@ -959,8 +1142,16 @@ pub fn run_simulation(config: SimConfig) {
                     None,
                     effective_run_timeout,
                 );
-                store_results_in_db(db, "multi_match", &results, &config, Some(&run_stats));
+                store_results_in_db(
+                    db,
+                    "multi_match",
+                    &results,
+                    &failures,
+                    &config,
+                    Some(&run_stats),
+                );
             }
+            upload_results(&config, &results);
 
             if let Some(output_file) = &config.output_file {
                 let json = serde_json::to_string_pretty(&results).unwrap();
@@ -995,16 +1186,19 @@ pub fn run_simulation(config: SimConfig) {
 
             let base_seed = config.seed.unwrap_or_else(|| rand::thread_rng().r#gen());
             let mut tournament = super::metrics::TournamentResult::new();
+            let mut failures = Vec::new();
 
             if parallel_mode {
                 // Parallel execution
-                tournament.matches = super::parallel::run_tournament_parallel(
+                let parallel = super::parallel::run_tournament_parallel(
                     &config,
                     *matches_per_pair,
                     base_seed,
                     &level_db,
                     &profile_db,
                 );
+                tournament.matches = parallel.results;
+                failures = parallel.failures;
             } else {
                 // Sequential execution
                 let mut match_num = 0;
@@ -1015,6 +1209,9 @@ pub fn run_simulation(config: SimConfig) {
                         }
 
                         for _i in 0..*matches_per_pair {
+                            if batch_control().quit_requested() {
+                                break;
+                            }
                             if let Some(timeout) = effective_run_timeout {
                                 if start.elapsed().as_secs_f32() > timeout {
                                     eprintln!(
@@ -1057,7 +1254,10 @@ pub fn run_simulation(config: SimConfig) {
             }
 
             if !config.quiet {
-                println!("\rTournament complete. {} matches played.", total_matches);
+                println!(
+                    "\rTournament complete. {} matches played.",
+                    tournament.matches.len()
+                );
             }
 
             tournament.calculate_win_rates();
@@ -1082,10 +1282,12 @@ pub fn run_simulation(config: SimConfig) {
                     db,
                     "tournament",
                     &tournament.matches,
+                    &failures,
                     &config,
                     Some(&run_stats),
                 );
             }
+            upload_results(&config, &tournament.matches);
 
             if let Some(output_file) = &config.output_file {
                 let json = serde_json::to_string_pretty(&tournament).unwrap();
@@ -1116,17 +1318,19 @@ pub fn run_simulation(config: SimConfig) {
 
             let mut sweep = super::metrics::LevelSweepResult::new(&config.left_profile);
             let base_seed = config.seed.unwrap_or_else(|| rand::thread_rng().r#gen());
+            let mut failures = Vec::new();
 
             if parallel_mode {
                 // Parallel execution - run all matches then group by level
-                let results = super::parallel::run_level_sweep_parallel(
+                let parallel = super::parallel::run_level_sweep_parallel(
                     &config,
                     *matches_per_level,
                     base_seed,
                     &level_db,
                     &profile_db,
                 );
-                for result in results {
+                failures = parallel.failures;
+                for result in parallel.results {
                     sweep
                         .results_by_level
                         .entry(result.level)
@@ -1143,6 +1347,9 @@ pub fn run_simulation(config: SimConfig) {
                     }
 
                     for i in 0..*matches_per_level {
+                        if batch_control().quit_requested() {
+                            break;
+                        }
                         match_num += 1;
                         if !config.quiet {
                             print!("\rLevel {} match {}/{}...", level, i + 1, matches_per_level);
@@ -1172,13 +1379,14 @@ pub fn run_simulation(config: SimConfig) {
             sweep.calculate_stats();
             println!("{}", sweep.format_table(&level_names));
 
+            let all_results: Vec<_> = sweep
+                .results_by_level
+                .values()
+                .flat_map(|v| v.iter().cloned())
+                .collect();
+
             // Store in database if enabled
             if let Some(ref db) = db {
-                let all_results: Vec<_> = sweep
-                    .results_by_level
-                    .values()
-                    .flat_map(|v| v.iter().cloned())
-                    .collect();
                 let planned = (valid_levels.len() as i64) * (*matches_per_level as i64);
                 let run_stats = build_run_stats(
                     "level_sweep",
@@ -1193,8 +1401,16 @@ pub fn run_simulation(config: SimConfig) {
                     Some(*matches_per_level as i64),
                     effective_run_timeout,
                 );
-                store_results_in_db(db, "level_sweep", &all_results, &config, Some(&run_stats));
+                store_results_in_db(
+                    db,
+                    "level_sweep",
+                    &all_results,
+                    &failures,
+                    &config,
+                    Some(&run_stats),
+                );
             }
+            upload_results(&config, &all_results);
 
             if let Some(output_file) = &config.output_file {
                 let json = serde_json::to_string_pretty(&sweep).unwrap();
@@ -1223,6 +1439,187 @@ pub fn run_simulation(config: SimConfig) {
         super::config::SimMode::ReachabilityTest { samples, db_path } => {
             run_reachability_tests(&config, *samples, db_path, &level_db, &profile_db);
         }
+
+        super::config::SimMode::Curriculum { path } => {
+            run_curriculum(&config, path.as_deref(), &level_db, &profile_db);
+        }
+
+        super::config::SimMode::MirrorTest { pairs } => {
+            run_mirror_test(&config, *pairs, &level_db, &profile_db);
+        }
+
+        super::config::SimMode::Gym { left } => {
+            run_gym(&config, *left, &level_db, &profile_db);
+        }
+
+        super::config::SimMode::Schedule { path, count } => {
+            let run_started_at = chrono::Utc::now().to_rfc3339();
+            let start = std::time::Instant::now();
+            let schedule = match path {
+                Some(path) => match MatchSchedule::load(path) {
+                    Ok(schedule) => schedule,
+                    Err(e) => {
+                        eprintln!("Error: {}", e);
+                        return;
+                    }
+                },
+                None => MatchSchedule::from_lists(&config.left_profiles, &config.right_profiles),
+            };
+            let base_seed = config.seed.unwrap_or_else(|| rand::thread_rng().r#gen());
+            let runs = schedule.expand(&config, base_seed, *count);
+            if !config.quiet {
+                println!(
+                    "Running {} scheduled matches ({} matchups)",
+                    runs.len(),
+                    schedule.matches.len()
+                );
+            }
+
+            let mut results = Vec::new();
+            for (i, (match_config, seed)) in runs.iter().enumerate() {
+                if batch_control().quit_requested() {
+                    break;
+                }
+                if !config.quiet {
+                    print!("\rMatch {}/{}...", i + 1, runs.len());
+                    use std::io::Write;
+                    std::io::stdout().flush().ok();
+                }
+                results.push(run_match(match_config, *seed, &level_db, &profile_db));
+            }
+            if !config.quiet {
+                println!("\rCompleted {} matches.", results.len());
+            }
+            print!("{}", super::schedule::format_summary(&results));
+
+            if let Some(ref db) = db {
+                let run_stats = build_run_stats(
+                    "schedule",
+                    &config,
+                    run_started_at,
+                    start.elapsed().as_secs_f64(),
+                    runs.len() as i64,
+                    results.len() as i64,
+                    profiles_count,
+                    levels_count,
+                    None,
+                    None,
+                    effective_run_timeout,
+                );
+                store_results_in_db(db, "schedule", &results, &[], &config, Some(&run_stats));
+            }
+            upload_results(&config, &results);
+
+            if let Some(output_file) = &config.output_file {
+                let json = serde_json::to_string_pretty(&results).unwrap();
+                std::fs::write(output_file, json).expect("Failed to write output");
+                println!("Results written to {}", output_file);
+            }
+        }
+
+        super::config::SimMode::Bracket {
+            format,
+            seeding,
+            games,
+        } => {
+            let run_started_at = chrono::Utc::now().to_rfc3339();
+            let start = std::time::Instant::now();
+            if profiles.len() < 2 {
+                eprintln!("Error: a bracket needs at least 2 profiles");
+                return;
+            }
+            if config.parallel > 0 {
+                eprintln!("Warning: bracket series are played in order; --parallel is ignored");
+            }
+
+            let base_seed = config.seed.unwrap_or_else(|| rand::thread_rng().r#gen());
+            // Ranked seeding reads prior results from --db (no history = seeded last)
+            let seeds = seed_entrants(&profiles, *seeding, base_seed, |profile| {
+                db.as_ref()
+                    .and_then(|db| db.get_profile_stats(profile).ok())
+                    .filter(|stats| stats.matches > 0)
+                    .map(|stats| stats.win_rate())
+            });
+            let planned = planned_matches(seeds.len(), *format, *games);
+            if !config.quiet {
+                println!(
+                    "Running {} elimination bracket: {} profiles, {} seeding, {} match(es) per series",
+                    format.as_str(),
+                    seeds.len(),
+                    seeding.as_str(),
+                    games
+                );
+            }
+
+            let mut results = Vec::new();
+            let bracket = play_bracket(
+                seeds,
+                *format,
+                *seeding,
+                *games,
+                |side, round, left, right| {
+                    let mut match_config = config.clone();
+                    match_config.left_profile = left.to_string();
+                    match_config.right_profile = right.to_string();
+                    let first = results.len();
+                    for _ in 0..*games {
+                        if batch_control().quit_requested() {
+                            break;
+                        }
+                        if !config.quiet {
+                            print!(
+                                "\r{} round {}: {} vs {}...",
+                                side.as_str(),
+                                round,
+                                left,
+                                right
+                            );
+                            use std::io::Write;
+                            std::io::stdout().flush().ok();
+                        }
+                        let seed = base_seed.wrapping_add(results.len() as u64);
+                        results.push(run_match(&match_config, seed, &level_db, &profile_db));
+                    }
+                    SeriesScore::from_results(&results[first..])
+                },
+            );
+            if !config.quiet {
+                println!("\rBracket complete. {} matches played.", results.len());
+            }
+            print!("{}", format_bracket_report(&bracket));
+
+            if let Some(ref db) = db {
+                let run_stats = build_run_stats(
+                    "bracket",
+                    &config,
+                    run_started_at,
+                    start.elapsed().as_secs_f64(),
+                    planned as i64,
+                    results.len() as i64,
+                    profiles_count,
+                    levels_count,
+                    Some(*games as i64),
+                    None,
+                    effective_run_timeout,
+                );
+                if let Some(session_id) =
+                    store_results_in_db(db, "bracket", &results, &[], &config, Some(&run_stats))
+                {
+                    for series in &bracket.series {
+                        if let Err(e) = db.insert_bracket_series(&session_id, series) {
+                            eprintln!("Warning: Failed to store bracket series: {}", e);
+                        }
+                    }
+                }
+            }
+            upload_results(&config, &results);
+
+            if let Some(output_file) = &config.output_file {
+                let json = serde_json::to_string_pretty(&bracket).unwrap();
+                std::fs::write(output_file, json).expect("Failed to write output");
+                println!("Results written to {}", output_file);
+            }
+        }
     }
 }
 
@@ -1272,6 +1669,18 @@ fn plan_run(
         super::config::SimMode::ReachabilityTest { .. } => {
             ("reachability_test".to_string(), 0, None, None)
         }
+        super::config::SimMode::Curriculum { .. } => ("curriculum".to_string(), 0, None, None),
+        super::config::SimMode::MirrorTest { pairs } => {
+            ("mirror_test".to_string(), *pairs as i64 * 2, None, None)
+        }
+        super::config::SimMode::Schedule { .. } => ("schedule".to_string(), 0, None, None),
+        super::config::SimMode::Gym { .. } => ("gym".to_string(), 0, None, None),
+        super::config::SimMode::Bracket { format, games, .. } => (
+            "bracket".to_string(),
+            planned_matches(profiles_count as usize, *format, *games) as i64,
+            Some(*games as i64),
+            None,
+        ),
     }
 }
 
@@ -1306,33 +1715,49 @@ fn build_run_stats(
     }
 }
 
-/// Store match results in the database
+/// Post match results to the `--upload` leaderboard endpoint, if one is set
+fn upload_results(config: &SimConfig, results: &[MatchResult]) {
+    if let Some(upload) = &config.upload {
+        upload_match_results(upload, results);
+    }
+}
+
+/// Store match results (and matches that panicked) in the database.
+/// Returns the new session's ID.
 fn store_results_in_db(
     db: &SimDatabase,
     session_type: &str,
     results: &[MatchResult],
+    failures: &[MatchFailure],
     config: &SimConfig,
     run_stats: Option<&RunStats>,
-) {
+) -> Option<String> {
     // Create session
     let config_json = serde_json::to_string(config).ok();
     let session_id = match db.create_session(session_type, config_json.as_deref()) {
         Ok(id) => id,
         Err(e) => {
             eprintln!("Warning: Failed to create database session: {}", e);
-            return;
+            return None;
         }
     };
 
     // Insert each result
+    let debug_config = config.debug_log_config();
     let mut stored = 0;
     for result in results {
+        let write_start = std::time::Instant::now();
         match db.insert_match(&session_id, result) {
             Ok(match_id) => {
                 stored += 1;
-                if !result.events.is_empty() {
-                    if let Err(e) =
-                        db.insert_events_with_points(match_id, result.duration, &result.events)
+                let events: Vec<_> = result
+                    .events
+                    .iter()
+                    .filter(|(_, event)| debug_config.persists(event))
+                    .cloned()
+                    .collect();
+                if !events.is_empty() {
+                    if let Err(e) = db.insert_events_with_points(match_id, result.duration, &events)
                     {
                         eprintln!("Warning: Failed to store match events: {}", e);
                     }
@@ -1340,10 +1765,30 @@ fn store_results_in_db(
             }
             Err(e) => eprintln!("Warning: Failed to store match result: {}", e),
         }
+        exporter::record_db_write(write_start.elapsed());
+    }
+
+    for failure in failures {
+        if let Err(e) = db.insert_match_failure(&session_id, failure) {
+            eprintln!("Warning: Failed to store match failure: {}", e);
+        }
+    }
+    if !failures.is_empty() {
+        eprintln!(
+            "{} match(es) panicked (see match_failures for seeds and configs)",
+            failures.len()
+        );
     }
 
     if !config.quiet && stored > 0 {
         println!("Stored {} results in database", stored);
+        let degenerate = results.iter().filter(|r| r.degenerate.is_some()).count();
+        if degenerate > 0 {
+            println!(
+                "  {} flagged degenerate (excluded from win rates, see matches.degenerate_reason)",
+                degenerate
+            );
+        }
     }
 
     if let Some(stats) = run_stats {
@@ -1351,6 +1796,7 @@ fn store_results_in_db(
             eprintln!("Warning: Failed to store run stats: {}", e);
         }
     }
+    Some(session_id)
 }
 
 /// Run multi-hop platform reachability tests for all levels
@@ -1568,6 +2014,7 @@ mod tests {
             left_stats: PlayerStats::default(),
             right_stats: PlayerStats::default(),
             seed: 123,
+            degenerate: None,
             events: Vec::new(),
         };
         result.events.push((0, GameEvent::ResetScores));
@@ -1576,6 +2023,7 @@ mod tests {
             &db,
             "test",
             std::slice::from_ref(&result),
+            &[],
             &SimConfig::default(),
             None,
         );
@@ -1744,7 +2192,9 @@ pub fn run_ghost_trial(
     app.init_resource::<NavGraph>();
     app.init_resource::<HeatmapBundle>();
     app.init_resource::<PhysicsTweaks>();
+    app.add_plugins(GameplayConfig::load("simulate"));
     app.init_resource::<LastShotInfo>();
+    app.insert_resource(EventBus::new());
     app.insert_resource(CurrentPalette(0));
     app.init_resource::<PaletteDatabase>();
 
@@ -1780,6 +2230,7 @@ pub fn run_ghost_trial(
             mark_nav_dirty_on_level_change,
             load_heatmaps_on_level_change,
             rebuild_nav_graph,
+            sync_nav_edge_penalties,
             ai_navigation_update,
             ai_decision_update,
         )
@@ -1798,9 +2249,12 @@ pub fn run_ghost_trial(
             apply_gravity,
             ball_gravity,
             ball_spin,
+            apply_wind_forces,
+            move_baskets,
             apply_velocity,
             check_collisions,
             ball_collisions,
+            level_feature_traversal,
             ball_state_update,
             ball_player_collision,
             ball_follow_holder,
@@ -1864,6 +2318,11 @@ fn ghost_trial_setup(
     use crate::world::{Basket, Collider, Platform};
 
     let config = &control.config;
+    let arena = level_db
+        .get_by_id(&current_level.0)
+        .map(|l| l.arena())
+        .unwrap_or_default();
+    commands.insert_resource(arena);
 
     // Find AI profile ID for right player
     let right_profile_id = profile_db
@@ -1872,9 +2331,10 @@ fn ghost_trial_setup(
         .unwrap_or_else(|| profile_db.default_profile().id.clone());
 
     // Spawn left player (Ghost controlled - no AI)
+    let left_spawn = level_db.player_spawn(&current_level.0, true);
     let left_player = commands
         .spawn((
-            Transform::from_translation(PLAYER_SPAWN_LEFT),
+            Transform::from_translation(left_spawn),
             Sprite {
                 custom_size: Some(PLAYER_SIZE),
                 ..default()
@@ -1897,7 +2357,7 @@ fn ghost_trial_setup(
     // Spawn right player (AI controlled)
     commands
         .spawn((
-            Transform::from_translation(PLAYER_SPAWN_RIGHT),
+            Transform::from_translation(level_db.player_spawn(&current_level.0, false)),
             Sprite {
                 custom_size: Some(PLAYER_SIZE),
                 ..default()
@@ -1927,7 +2387,7 @@ fn ghost_trial_setup(
     // Spawn ball - give it to the ghost (left player)
     let ball_entity = commands
         .spawn((
-            Transform::from_translation(PLAYER_SPAWN_LEFT + Vec3::new(10.0, 0.0, 0.0)),
+            Transform::from_translation(left_spawn + Vec3::new(10.0, 0.0, 0.0)),
             Sprite {
                 custom_size: Some(BALL_SIZE),
                 ..default()
@@ -1953,10 +2413,10 @@ fn ghost_trial_setup(
     // Spawn floor
     commands.spawn((
         Sprite {
-            custom_size: Some(Vec2::new(ARENA_WIDTH + 200.0, 40.0)),
+            custom_size: Some(Vec2::new(arena.width + 200.0, 40.0)),
             ..default()
         },
-        Transform::from_xyz(0.0, ARENA_FLOOR_Y - 20.0, 0.0),
+        Transform::from_xyz(0.0, arena.floor_y - 20.0, 0.0),
         Platform,
         Collider,
     ));
@@ -1967,7 +2427,7 @@ fn ghost_trial_setup(
             custom_size: Some(Vec2::new(WALL_THICKNESS, 5000.0)),
             ..default()
         },
-        Transform::from_xyz(-ARENA_WIDTH / 2.0 + WALL_THICKNESS / 2.0, 2000.0, 0.0),
+        Transform::from_xyz(-arena.width / 2.0 + WALL_THICKNESS / 2.0, 2000.0, 0.0),
         Platform,
         Collider,
     ));
@@ -1976,7 +2436,7 @@ fn ghost_trial_setup(
             custom_size: Some(Vec2::new(WALL_THICKNESS, 5000.0)),
             ..default()
         },
-        Transform::from_xyz(ARENA_WIDTH / 2.0 - WALL_THICKNESS / 2.0, 2000.0, 0.0),
+        Transform::from_xyz(arena.width / 2.0 - WALL_THICKNESS / 2.0, 2000.0, 0.0),
         Platform,
         Collider,
     ));
@@ -1992,7 +2452,7 @@ fn ghost_trial_setup(
                             custom_size: Some(Vec2::new(*width, 20.0)),
                             ..default()
                         },
-                        Transform::from_xyz(-x, ARENA_FLOOR_Y + y, 0.0),
+                        Transform::from_xyz(-x, arena.floor_y + y, 0.0),
                         Platform,
                         Collider,
                         crate::world::LevelPlatform,
@@ -2003,7 +2463,7 @@ fn ghost_trial_setup(
                             custom_size: Some(Vec2::new(*width, 20.0)),
                             ..default()
                         },
-                        Transform::from_xyz(*x, ARENA_FLOOR_Y + y, 0.0),
+                        Transform::from_xyz(*x, arena.floor_y + y, 0.0),
                         Platform,
                         Collider,
                         crate::world::LevelPlatform,
@@ -2015,12 +2475,15 @@ fn ghost_trial_setup(
                             custom_size: Some(Vec2::new(*width, 20.0)),
                             ..default()
                         },
-                        Transform::from_xyz(0.0, ARENA_FLOOR_Y + y, 0.0),
+                        Transform::from_xyz(0.0, arena.floor_y + y, 0.0),
                         Platform,
                         Collider,
                         crate::world::LevelPlatform,
                     ));
                 }
+                feature => {
+                    crate::levels::spawn_level_feature(&mut commands, feature, arena.floor_y)
+                }
             }
         }
 
@@ -2028,6 +2491,7 @@ fn ghost_trial_setup(
         if level.step_count > 0 {
             super::setup::spawn_corner_steps(
                 &mut commands,
+                &arena,
                 level.step_count,
                 level.corner_height,
                 level.corner_width,
@@ -2036,10 +2500,8 @@ fn ghost_trial_setup(
         }
 
         // Spawn baskets (baskets need Sprite for scoring check)
-        let basket_y = ARENA_FLOOR_Y + level.basket_height;
-        let wall_inner = ARENA_WIDTH / 2.0 - WALL_THICKNESS;
-        let left_basket_x = -wall_inner + level.basket_push_in;
-        let right_basket_x = wall_inner - level.basket_push_in;
+        let basket_y = arena.floor_y + level.basket_height;
+        let (left_basket_x, right_basket_x) = arena.basket_x_from_offset(level.basket_push_in);
 
         commands.spawn((
             Sprite {
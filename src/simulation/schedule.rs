@@ -0,0 +1,222 @@
+//! Match schedules - scripted matchups for regression comparisons
+//!
+//! A schedule is a list of matches, each overriding any of the base config's
+//! profiles, level, seed, duration and score limit. It comes from a JSON file
+//! (`--schedule FILE`) or from explicit side lists (`--left-profiles A,B
+//! --right-profiles C,D`, paired by index). Either side can be the `human`
+//! slot: a player no AI drives, which stands in for a person (headless runs
+//! have no input device, so it stays put).
+//!
+//! ```json
+//! {
+//!   "matches": [
+//!     { "left": "Balanced", "right": "Sniper", "level": 3, "count": 5 },
+//!     { "left": "human", "right": "Aggressive", "seed": 42 }
+//!   ]
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use super::config::SimConfig;
+use super::metrics::MatchResult;
+
+/// Profile name for a side no AI drives
+pub const HUMAN_SLOT: &str = "human";
+
+pub fn is_human_slot(profile: &str) -> bool {
+    profile.eq_ignore_ascii_case(HUMAN_SLOT)
+}
+
+/// One scheduled matchup; unset fields come from the base config
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScheduledMatch {
+    #[serde(default)]
+    pub left: Option<String>,
+    #[serde(default)]
+    pub right: Option<String>,
+    /// Level number (1-based)
+    #[serde(default)]
+    pub level: Option<u32>,
+    /// Seed of the first match (later repeats count up from it)
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(default)]
+    pub duration_limit: Option<f32>,
+    #[serde(default)]
+    pub score_limit: Option<u32>,
+    /// How many times to play it (default: the run's `--matches`, else 1)
+    #[serde(default)]
+    pub count: Option<u32>,
+}
+
+impl ScheduledMatch {
+    /// The base config with this entry's overrides applied
+    pub fn apply(&self, base: &SimConfig) -> SimConfig {
+        let mut config = base.clone();
+        if let Some(left) = &self.left {
+            config.left_profile = left.clone();
+        }
+        if let Some(right) = &self.right {
+            config.right_profile = right.clone();
+        }
+        if self.level.is_some() {
+            config.level = self.level;
+        }
+        if let Some(duration) = self.duration_limit {
+            config.duration_limit = duration;
+        }
+        if let Some(limit) = self.score_limit {
+            config.score_limit = limit;
+        }
+        config
+    }
+}
+
+/// Scripted matchups, played in order
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MatchSchedule {
+    pub matches: Vec<ScheduledMatch>,
+}
+
+impl MatchSchedule {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let contents =
+            std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        serde_json::from_str(&contents).map_err(|e| format!("Failed to parse {}: {}", path, e))
+    }
+
+    /// Pair `left[i]` with `right[i]` (the shorter list cycles; an empty one
+    /// keeps the base profile)
+    pub fn from_lists(left: &[String], right: &[String]) -> Self {
+        let pick =
+            |list: &[String], i: usize| (!list.is_empty()).then(|| list[i % list.len()].clone());
+        let matches = (0..left.len().max(right.len()))
+            .map(|i| ScheduledMatch {
+                left: pick(left, i),
+                right: pick(right, i),
+                ..Default::default()
+            })
+            .collect();
+        Self { matches }
+    }
+
+    /// Every match to play, as (config, seed). Entries without a `count` play
+    /// `default_count` times; entries without a seed count up from `base_seed`
+    /// across the whole schedule.
+    pub fn expand(
+        &self,
+        base: &SimConfig,
+        base_seed: u64,
+        default_count: u32,
+    ) -> Vec<(SimConfig, u64)> {
+        let mut runs = Vec::new();
+        for entry in &self.matches {
+            let config = entry.apply(base);
+            for repeat in 0..entry.count.unwrap_or(default_count) {
+                let seed = match entry.seed {
+                    Some(seed) => seed.wrapping_add(repeat as u64),
+                    None => base_seed.wrapping_add(runs.len() as u64),
+                };
+                runs.push((config.clone(), seed));
+            }
+        }
+        runs
+    }
+}
+
+/// Results of one matchup, from the left side's view
+struct Tally<'a> {
+    left: &'a str,
+    right: &'a str,
+    level: u32,
+    wins: u32,
+    ties: u32,
+    losses: u32,
+    goals_left: u32,
+    goals_right: u32,
+}
+
+/// One line per matchup (profiles and level): wins-ties-losses and goals
+pub fn format_summary(results: &[MatchResult]) -> String {
+    let mut tallies: Vec<Tally> = Vec::new();
+    for result in results {
+        let (left, right) = (result.left_profile.as_str(), result.right_profile.as_str());
+        let index = match tallies
+            .iter()
+            .position(|t| (t.left, t.right, t.level) == (left, right, result.level))
+        {
+            Some(index) => index,
+            None => {
+                tallies.push(Tally {
+                    left,
+                    right,
+                    level: result.level,
+                    wins: 0,
+                    ties: 0,
+                    losses: 0,
+                    goals_left: 0,
+                    goals_right: 0,
+                });
+                tallies.len() - 1
+            }
+        };
+        let tally = &mut tallies[index];
+        match result.winner.as_str() {
+            "left" => tally.wins += 1,
+            "tie" => tally.ties += 1,
+            _ => tally.losses += 1,
+        }
+        tally.goals_left += result.score_left;
+        tally.goals_right += result.score_right;
+    }
+
+    tallies
+        .iter()
+        .map(|t| {
+            format!(
+                "{} vs {} (level {}): {}-{}-{}, goals {}-{}\n",
+                t.left, t.right, t.level, t.wins, t.ties, t.losses, t.goals_left, t.goals_right
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_expands_overrides_and_seeds() {
+        let names = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let schedule = MatchSchedule::from_lists(&names(&["A", "B", "C"]), &names(&["human", "D"]));
+        assert_eq!(schedule.matches.len(), 3);
+        assert_eq!(schedule.matches[2].left.as_deref(), Some("C"));
+        assert_eq!(schedule.matches[2].right.as_deref(), Some("human"));
+        assert!(is_human_slot("Human"));
+
+        let base = SimConfig::default();
+        let runs = schedule.expand(&base, 100, 2);
+        assert_eq!(runs.len(), 6);
+        assert_eq!(runs[5].1, 105);
+        assert_eq!(
+            (
+                runs[3].0.left_profile.as_str(),
+                runs[3].0.right_profile.as_str()
+            ),
+            ("B", "D")
+        );
+
+        let parsed: MatchSchedule = serde_json::from_str(
+            r#"{"matches": [{"right": "Sniper", "level": 3, "seed": 42, "count": 2}, {}]}"#,
+        )
+        .unwrap();
+        let runs = parsed.expand(&base, 7, 1);
+        assert_eq!(runs.len(), 3);
+        assert_eq!((runs[0].1, runs[1].1, runs[2].1), (42, 43, 9));
+        assert_eq!(runs[1].0.left_profile, base.left_profile);
+        assert_eq!(runs[1].0.right_profile, "Sniper");
+        assert_eq!(runs[1].0.level, Some(3));
+        assert_eq!(runs[2].0.level, base.level);
+    }
+}
@@ -0,0 +1,179 @@
+//! Seed bank - curated seeds that reproduce interesting or problematic matches
+//!
+//! Entries live in `config/seed_bank.json` (level, profiles, seed and duration
+//! limit are all a single simulated match needs to replay identically). `ballgame seeds add`
+//! copies them straight out of a simulation database, typically from a match
+//! flagged degenerate; `ballgame seeds run` plays one back.
+
+use rusqlite::{OptionalExtension, params};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use super::config::{SimConfig, SimMode};
+use super::db::SimDatabase;
+
+/// Seed bank file (resolved through the active config dir)
+pub const SEED_BANK_FILE: &str = "config/seed_bank.json";
+
+/// One reproducible match
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeedBankEntry {
+    /// Unique name used on the command line
+    pub name: String,
+    pub seed: u64,
+    /// Level number (1-based)
+    pub level: u32,
+    pub left_profile: String,
+    pub right_profile: String,
+    /// Match duration limit (None = simulation default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration: Option<f32>,
+    /// Degenerate reason code of the source match, if it was flagged
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// What to look for
+    #[serde(default)]
+    pub note: String,
+}
+
+impl SeedBankEntry {
+    /// Build an entry from a match in a simulation database.
+    /// The duration limit comes from the session's saved SimConfig, when present.
+    pub fn from_match(db: &SimDatabase, match_id: i64, name: &str) -> Result<Self, String> {
+        let (mut entry, config_json) = db
+            .conn()
+            .query_row(
+                "SELECT m.seed, m.level, m.left_profile, m.right_profile,
+                        m.degenerate_reason, s.config_json
+                 FROM matches m LEFT JOIN sessions s ON s.id = m.session_id
+                 WHERE m.id = ?1",
+                params![match_id],
+                |row| {
+                    let entry = SeedBankEntry {
+                        name: name.to_string(),
+                        seed: row.get::<_, i64>(0)? as u64,
+                        level: row.get(1)?,
+                        left_profile: row.get(2)?,
+                        right_profile: row.get(3)?,
+                        duration: None,
+                        reason: row.get(4)?,
+                        note: String::new(),
+                    };
+                    Ok((entry, row.get::<_, Option<String>>(5)?))
+                },
+            )
+            .optional()
+            .map_err(|e| format!("failed to read match {}: {}", match_id, e))?
+            .ok_or_else(|| format!("no match {} in database", match_id))?;
+
+        let default_duration = SimConfig::default().duration_limit;
+        entry.duration = config_json
+            .and_then(|json| serde_json::from_str::<SimConfig>(&json).ok())
+            .map(|config| config.duration_limit)
+            .filter(|duration| *duration != default_duration);
+        Ok(entry)
+    }
+
+    /// Single-match simulation settings that replay this entry
+    pub fn sim_config(&self) -> SimConfig {
+        let mut config = SimConfig {
+            mode: SimMode::Single,
+            level: Some(self.level),
+            left_profile: self.left_profile.clone(),
+            right_profile: self.right_profile.clone(),
+            seed: Some(self.seed),
+            ..Default::default()
+        };
+        if let Some(duration) = self.duration {
+            config.duration_limit = duration;
+        }
+        config
+    }
+}
+
+/// All curated entries, in file order
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SeedBank {
+    pub entries: Vec<SeedBankEntry>,
+}
+
+impl SeedBank {
+    /// Load the seed bank (a missing file is an empty bank)
+    pub fn load() -> Result<Self, String> {
+        let path = crate::config_dir::resolve(SEED_BANK_FILE);
+        match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).map_err(|e| format!("{}: {}", path, e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("{}: {}", path, e)),
+        }
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = crate::config_dir::resolve_for_write(SEED_BANK_FILE);
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(&path, json + "\n").map_err(|e| format!("{}: {}", path, e))
+    }
+
+    pub fn get(&self, name: &str) -> Option<&SeedBankEntry> {
+        self.entries.iter().find(|e| e.name == name)
+    }
+
+    /// Append an entry; names must be unique
+    pub fn add(&mut self, entry: SeedBankEntry) -> Result<(), String> {
+        if self.get(&entry.name).is_some() {
+            return Err(format!(
+                "seed bank already has an entry named '{}'",
+                entry.name
+            ));
+        }
+        self.entries.push(entry);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::{DegenerateReason, MatchResult, PlayerStats};
+
+    #[test]
+    fn test_entry_from_flagged_match() {
+        let db = SimDatabase::open_in_memory().unwrap();
+        let session_id = db.create_session("test", None).unwrap();
+        let result = MatchResult {
+            level: 4,
+            level_name: "Test".to_string(),
+            left_profile: "Balanced".to_string(),
+            right_profile: "Aggressive".to_string(),
+            duration: 15.0,
+            score_left: 0,
+            score_right: 0,
+            winner: "tie".to_string(),
+            left_stats: PlayerStats::default(),
+            right_stats: PlayerStats::default(),
+            seed: 98765,
+            degenerate: Some(DegenerateReason::BothStuck),
+            events: Vec::new(),
+        };
+        let match_id = db.insert_match(&session_id, &result).unwrap();
+
+        let entry = SeedBankEntry::from_match(&db, match_id, "corner_lock").unwrap();
+        assert_eq!(entry.seed, 98765);
+        assert_eq!(entry.level, 4);
+        assert_eq!(entry.reason.as_deref(), Some("both_stuck"));
+        assert!(SeedBankEntry::from_match(&db, match_id + 1, "missing").is_err());
+
+        let config = entry.sim_config();
+        assert_eq!(config.seed, Some(98765));
+        assert_eq!(config.level, Some(4));
+        assert_eq!(config.right_profile, "Aggressive");
+
+        let mut bank = SeedBank::default();
+        bank.add(entry.clone()).unwrap();
+        assert!(bank.add(entry).is_err());
+
+        let json = serde_json::to_string(&bank).unwrap();
+        let parsed: SeedBank = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.entries, bank.entries);
+    }
+}
@@ -10,14 +10,17 @@ use crate::ball::{
     Velocity,
 };
 use crate::constants::*;
-use crate::levels::LevelDatabase;
-use crate::player::{CoyoteTimer, Facing, Grounded, JumpState, Player, TargetBasket, Team};
+use crate::levels::{ArenaDims, LevelDatabase};
+use crate::player::{
+    CoyoteTimer, Facing, Grounded, HumanControlled, JumpState, Player, TargetBasket, Team,
+};
 use crate::scoring::CurrentLevel;
 use crate::shooting::ChargingShot;
 use crate::steal::StealCooldown;
 use crate::world::{Basket, Collider, CornerRamp, Platform};
 
 use super::control::SimControl;
+use super::schedule::is_human_slot;
 
 /// Setup system for simulation
 pub fn sim_setup(
@@ -28,6 +31,11 @@ pub fn sim_setup(
     current_level: Res<CurrentLevel>,
 ) {
     let config = &control.config;
+    let arena = level_db
+        .get_by_id(&current_level.0)
+        .map(|l| l.arena())
+        .unwrap_or_default();
+    commands.insert_resource(arena);
 
     // Find profile IDs
     let left_profile_id = profile_db
@@ -40,9 +48,9 @@ pub fn sim_setup(
         .unwrap_or_else(|| profile_db.default_profile().id.clone());
 
     // Spawn left player (AI controlled)
-    commands
+    let left = commands
         .spawn((
-            Transform::from_translation(PLAYER_SPAWN_LEFT),
+            Transform::from_translation(level_db.player_spawn(&current_level.0, true)),
             Sprite {
                 custom_size: Some(PLAYER_SIZE),
                 ..default()
@@ -67,12 +75,13 @@ pub fn sim_setup(
             },
             AiNavState::default(),
             StealCooldown::default(),
-        ));
+        ))
+        .id();
 
     // Spawn right player (AI controlled)
-    commands
+    let right = commands
         .spawn((
-            Transform::from_translation(PLAYER_SPAWN_RIGHT),
+            Transform::from_translation(level_db.player_spawn(&current_level.0, false)),
             Sprite {
                 custom_size: Some(PLAYER_SIZE),
                 ..default()
@@ -97,11 +106,19 @@ pub fn sim_setup(
             },
             AiNavState::default(),
             StealCooldown::default(),
-        ));
+        ))
+        .id();
+
+    // "human" slots (match schedules): no AI drives the player
+    for (player, profile) in [(left, &config.left_profile), (right, &config.right_profile)] {
+        if is_human_slot(profile) {
+            commands.entity(player).insert(HumanControlled);
+        }
+    }
 
     // Spawn ball
     commands.spawn((
-        Transform::from_translation(BALL_SPAWN),
+        Transform::from_translation(level_db.ball_spawn(&current_level.0)),
         Sprite {
             custom_size: Some(BALL_SIZE),
             ..default()
@@ -120,10 +137,10 @@ pub fn sim_setup(
     // Spawn arena floor
     commands.spawn((
         Sprite {
-            custom_size: Some(Vec2::new(ARENA_WIDTH - WALL_THICKNESS * 2.0, 40.0)),
+            custom_size: Some(Vec2::new(arena.width - WALL_THICKNESS * 2.0, 40.0)),
             ..default()
         },
-        Transform::from_xyz(0.0, ARENA_FLOOR_Y, 0.0),
+        Transform::from_xyz(0.0, arena.floor_y, 0.0),
         Platform,
         Collider,
     ));
@@ -134,7 +151,7 @@ pub fn sim_setup(
             custom_size: Some(Vec2::new(WALL_THICKNESS, 5000.0)),
             ..default()
         },
-        Transform::from_xyz(-ARENA_WIDTH / 2.0 + WALL_THICKNESS / 2.0, 2000.0, 0.0),
+        Transform::from_xyz(-arena.width / 2.0 + WALL_THICKNESS / 2.0, 2000.0, 0.0),
         Platform,
         Collider,
     ));
@@ -143,7 +160,7 @@ pub fn sim_setup(
             custom_size: Some(Vec2::new(WALL_THICKNESS, 5000.0)),
             ..default()
         },
-        Transform::from_xyz(ARENA_WIDTH / 2.0 - WALL_THICKNESS / 2.0, 2000.0, 0.0),
+        Transform::from_xyz(arena.width / 2.0 - WALL_THICKNESS / 2.0, 2000.0, 0.0),
         Platform,
         Collider,
     ));
@@ -159,7 +176,7 @@ pub fn sim_setup(
                             custom_size: Some(Vec2::new(*width, 20.0)),
                             ..default()
                         },
-                        Transform::from_xyz(-x, ARENA_FLOOR_Y + y, 0.0),
+                        Transform::from_xyz(-x, arena.floor_y + y, 0.0),
                         Platform,
                         Collider,
                         crate::world::LevelPlatform,
@@ -170,7 +187,7 @@ pub fn sim_setup(
                             custom_size: Some(Vec2::new(*width, 20.0)),
                             ..default()
                         },
-                        Transform::from_xyz(*x, ARENA_FLOOR_Y + y, 0.0),
+                        Transform::from_xyz(*x, arena.floor_y + y, 0.0),
                         Platform,
                         Collider,
                         crate::world::LevelPlatform,
@@ -182,12 +199,15 @@ pub fn sim_setup(
                             custom_size: Some(Vec2::new(*width, 20.0)),
                             ..default()
                         },
-                        Transform::from_xyz(0.0, ARENA_FLOOR_Y + y, 0.0),
+                        Transform::from_xyz(0.0, arena.floor_y + y, 0.0),
                         Platform,
                         Collider,
                         crate::world::LevelPlatform,
                     ));
                 }
+                feature => {
+                    crate::levels::spawn_level_feature(&mut commands, feature, arena.floor_y)
+                }
             }
         }
 
@@ -195,6 +215,7 @@ pub fn sim_setup(
         if level.step_count > 0 {
             spawn_corner_steps(
                 &mut commands,
+                &arena,
                 level.step_count,
                 level.corner_height,
                 level.corner_width,
@@ -203,10 +224,8 @@ pub fn sim_setup(
         }
 
         // Spawn baskets (baskets need Sprite for scoring check)
-        let basket_y = ARENA_FLOOR_Y + level.basket_height;
-        let wall_inner = ARENA_WIDTH / 2.0 - WALL_THICKNESS;
-        let left_basket_x = -wall_inner + level.basket_push_in;
-        let right_basket_x = wall_inner - level.basket_push_in;
+        let basket_y = arena.floor_y + level.basket_height;
+        let (left_basket_x, right_basket_x) = arena.basket_x_from_offset(level.basket_push_in);
 
         commands.spawn((
             Sprite {
@@ -234,13 +253,19 @@ pub fn level_geometry_setup(
     level_db: Res<LevelDatabase>,
     current_level: Res<CurrentLevel>,
 ) {
+    let arena = level_db
+        .get_by_id(&current_level.0)
+        .map(|l| l.arena())
+        .unwrap_or_default();
+    commands.insert_resource(arena);
+
     // Spawn arena floor
     commands.spawn((
         Sprite {
-            custom_size: Some(Vec2::new(ARENA_WIDTH - WALL_THICKNESS * 2.0, 40.0)),
+            custom_size: Some(Vec2::new(arena.width - WALL_THICKNESS * 2.0, 40.0)),
             ..default()
         },
-        Transform::from_xyz(0.0, ARENA_FLOOR_Y, 0.0),
+        Transform::from_xyz(0.0, arena.floor_y, 0.0),
         Platform,
         Collider,
     ));
@@ -251,7 +276,7 @@ pub fn level_geometry_setup(
             custom_size: Some(Vec2::new(WALL_THICKNESS, 5000.0)),
             ..default()
         },
-        Transform::from_xyz(-ARENA_WIDTH / 2.0 + WALL_THICKNESS / 2.0, 2000.0, 0.0),
+        Transform::from_xyz(-arena.width / 2.0 + WALL_THICKNESS / 2.0, 2000.0, 0.0),
         Platform,
         Collider,
     ));
@@ -260,7 +285,7 @@ pub fn level_geometry_setup(
             custom_size: Some(Vec2::new(WALL_THICKNESS, 5000.0)),
             ..default()
         },
-        Transform::from_xyz(ARENA_WIDTH / 2.0 - WALL_THICKNESS / 2.0, 2000.0, 0.0),
+        Transform::from_xyz(arena.width / 2.0 - WALL_THICKNESS / 2.0, 2000.0, 0.0),
         Platform,
         Collider,
     ));
@@ -276,7 +301,7 @@ pub fn level_geometry_setup(
                             custom_size: Some(Vec2::new(*width, 20.0)),
                             ..default()
                         },
-                        Transform::from_xyz(-x, ARENA_FLOOR_Y + y, 0.0),
+                        Transform::from_xyz(-x, arena.floor_y + y, 0.0),
                         Platform,
                         Collider,
                         crate::world::LevelPlatform,
@@ -287,7 +312,7 @@ pub fn level_geometry_setup(
                             custom_size: Some(Vec2::new(*width, 20.0)),
                             ..default()
                         },
-                        Transform::from_xyz(*x, ARENA_FLOOR_Y + y, 0.0),
+                        Transform::from_xyz(*x, arena.floor_y + y, 0.0),
                         Platform,
                         Collider,
                         crate::world::LevelPlatform,
@@ -299,12 +324,15 @@ pub fn level_geometry_setup(
                             custom_size: Some(Vec2::new(*width, 20.0)),
                             ..default()
                         },
-                        Transform::from_xyz(0.0, ARENA_FLOOR_Y + y, 0.0),
+                        Transform::from_xyz(0.0, arena.floor_y + y, 0.0),
                         Platform,
                         Collider,
                         crate::world::LevelPlatform,
                     ));
                 }
+                feature => {
+                    crate::levels::spawn_level_feature(&mut commands, feature, arena.floor_y)
+                }
             }
         }
 
@@ -312,6 +340,7 @@ pub fn level_geometry_setup(
         if level.step_count > 0 {
             spawn_corner_steps(
                 &mut commands,
+                &arena,
                 level.step_count,
                 level.corner_height,
                 level.corner_width,
@@ -320,10 +349,8 @@ pub fn level_geometry_setup(
         }
 
         // Spawn baskets (baskets need Sprite for scoring check)
-        let basket_y = ARENA_FLOOR_Y + level.basket_height;
-        let wall_inner = ARENA_WIDTH / 2.0 - WALL_THICKNESS;
-        let left_basket_x = -wall_inner + level.basket_push_in;
-        let right_basket_x = wall_inner - level.basket_push_in;
+        let basket_y = arena.floor_y + level.basket_height;
+        let (left_basket_x, right_basket_x) = arena.basket_x_from_offset(level.basket_push_in);
 
         commands.spawn((
             Sprite {
@@ -347,21 +374,22 @@ pub fn level_geometry_setup(
 /// Spawn corner steps for simulation (matching the main game's behavior)
 pub fn spawn_corner_steps(
     commands: &mut Commands,
+    arena: &ArenaDims,
     step_count: usize,
     corner_height: f32,
     corner_width: f32,
     step_push_in: f32,
 ) {
     // Wall inner edges
-    let left_wall_inner = -ARENA_WIDTH / 2.0 + WALL_THICKNESS;
-    let right_wall_inner = ARENA_WIDTH / 2.0 - WALL_THICKNESS;
+    let left_wall_inner = -arena.wall_inner();
+    let right_wall_inner = arena.wall_inner();
 
     // Step dimensions
     let step_height = corner_height / step_count as f32;
     let step_width = corner_width / step_count as f32;
 
     // Floor top surface
-    let floor_top = ARENA_FLOOR_Y + 20.0;
+    let floor_top = arena.floor_top();
 
     // Left steps: go from wall (high) toward center (low)
     for i in 0..step_count {
@@ -5,20 +5,37 @@
 //!
 //! Refactored to reuse a single Bevy app per position, resetting
 //! entity state between shots instead of creating new apps.
+//!
+//! When the basket sits above standing reach, a second pass takes jump shots
+//! the way the AI does (`AiCapabilities::plan_jump_shot`): jump, charge for the
+//! planned release height, release. It also reports how far the actual release
+//! height was from the plan.
+//!
+//! With `--defender` / `--contested`, the standing shots are repeated with a
+//! defender placed in front of the shooter (distance, height, jumping pattern).
+//! The make rates go into the level's contested quality table
+//! (`ai::contested_quality`), which the AI reads when a defender is in the way.
 
 use bevy::app::ScheduleRunnerPlugin;
 use bevy::prelude::*;
 use std::time::Duration;
 
-use crate::ai::InputState;
+use crate::ai::{
+    AiCapabilities, CONTESTED_QUALITY_DIR, ContestedQualityTable, ContestedRate, DefenderPattern,
+    DefenderPlacement, InputState, JUMP_SHOT_CHARGE_DELAY, JumpShotPlan,
+};
+use crate::analytics::{TUNING_TARGETS_FILE, default_targets, load_targets};
 use crate::ball::{
     Ball, BallPlayerContact, BallPulse, BallRolling, BallShotGrace, BallSpin, BallState, BallStyle,
     CurrentPalette, Velocity, apply_velocity, ball_collisions, ball_gravity, ball_player_collision,
     ball_spin, ball_state_update,
 };
+use crate::config::{GameplayConfig, ShotConfig};
 use crate::constants::*;
 use crate::events::EventBus;
-use crate::levels::LevelDatabase;
+use crate::levels::{
+    ArenaDims, LevelDatabase, apply_wind_forces, level_feature_traversal, move_baskets,
+};
 use crate::palettes::PaletteDatabase;
 use crate::player::{
     CoyoteTimer, Facing, Grounded, HoldingBall, JumpState, Player, TargetBasket, Team,
@@ -46,6 +63,10 @@ struct PositionResult {
     goals: u32,
     overshoots: u32,
     undershoots: u32,
+    /// Mean |actual - planned| release height (jump shots)
+    release_error: f32,
+    /// No jump shot plan reaches the basket from here
+    no_plan: bool,
 }
 
 impl PositionResult {
@@ -73,14 +94,30 @@ struct ShotTestControl {
     ball_max_y: f32,
     frame_count: u32,
     settle_start_frame: u32,
+    /// Take jump shots instead of standing shots
+    jump_shot: bool,
+    /// Plan for the current jump shot, and seconds since takeoff
+    jump_plan: Option<JumpShotPlan>,
+    jump_timer: f32,
+    /// Contesting defender, and whether it has jumped for the current shot
+    defender: Option<DefenderPlacement>,
+    defender_jumped: bool,
     // Accumulated results
     goals: u32,
     overshoots: u32,
     undershoots: u32,
+    release_error_sum: f32,
+    no_plan: bool,
     // Exit flag
     all_done: bool,
 }
 
+/// The contesting defender (not driven by the shooter's control system)
+#[derive(Component)]
+struct ShotTestDefender {
+    start: Vec3,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum ShotTestPhase {
     Setup,
@@ -103,8 +140,13 @@ pub fn run_shot_test(config: &SimConfig, shots_per_position: u32, level_db: &Lev
     let level_idx = (level - 1) as usize;
     let basket_y = level_db
         .get(level_idx)
-        .map(|l| ARENA_FLOOR_Y + l.basket_height)
+        .map(|l| l.floor_y + l.basket_height)
         .unwrap_or(ARENA_FLOOR_Y + 150.0);
+    let floor_y = level_db
+        .get(level_idx)
+        .map(|l| l.floor_y)
+        .unwrap_or(ARENA_FLOOR_Y);
+    let standing_y = floor_y + 20.0 + PLAYER_SIZE.y / 2.0;
 
     println!(
         "Testing from {} positions, {} shots each",
@@ -122,7 +164,15 @@ pub fn run_shot_test(config: &SimConfig, shots_per_position: u32, level_db: &Lev
         use std::io::Write;
         std::io::stdout().flush().ok();
 
-        let result = run_shots_at_position(pos_x, basket_y, shots_per_position, level_db, level);
+        let result = run_shots_at_position(
+            pos_x,
+            basket_y,
+            shots_per_position,
+            level_db,
+            level,
+            false,
+            None,
+        );
 
         println!(
             "G:{} O:{} U:{} (over/under: {:.0}%)",
@@ -175,6 +225,94 @@ pub fn run_shot_test(config: &SimConfig, shots_per_position: u32, level_db: &Lev
             println!("  -> Shots are undershooting too often");
         }
     }
+
+    // Elevated basket: the AI takes jump shots here (same rule as ai::decision)
+    if basket_y - standing_y > PLAYER_SIZE.y {
+        println!("\nJump shots (basket above standing reach):");
+        for (pos_idx, &pos_x) in positions.iter().enumerate() {
+            print!("Position {} (x={:+.0}): ", pos_idx + 1, pos_x);
+            use std::io::Write;
+            std::io::stdout().flush().ok();
+
+            let result = run_shots_at_position(
+                pos_x,
+                basket_y,
+                shots_per_position,
+                level_db,
+                level,
+                true,
+                None,
+            );
+            if result.no_plan {
+                println!("no jump shot reaches the basket");
+                continue;
+            }
+            println!(
+                "G:{} O:{} U:{} (release height off by {:.1}px)",
+                result.goals, result.overshoots, result.undershoots, result.release_error
+            );
+        }
+    }
+
+    if config.shot_defenders.is_empty() {
+        return;
+    }
+    println!(
+        "\nContested shots (open: {:.0}%):",
+        100.0 * total_goals as f32 / total_shots.max(1) as f32
+    );
+    let mut rates = Vec::new();
+    for placement in &config.shot_defenders {
+        print!("  {:<24} ", placement.label());
+        use std::io::Write;
+        std::io::stdout().flush().ok();
+
+        let mut rate = ContestedRate {
+            placement: *placement,
+            shots: 0,
+            goals: 0,
+        };
+        for &pos_x in &positions {
+            let result = run_shots_at_position(
+                pos_x,
+                basket_y,
+                shots_per_position,
+                level_db,
+                level,
+                false,
+                Some(*placement),
+            );
+            rate.shots += result.total();
+            rate.goals += result.goals;
+        }
+        println!(
+            "G:{}/{} ({:.0}%)",
+            rate.goals,
+            rate.shots,
+            rate.make_rate() * 100.0
+        );
+        rates.push(rate);
+    }
+
+    let Some(level_def) = level_db.get(level_idx) else {
+        return;
+    };
+    let table = ContestedQualityTable {
+        level: level_def.name.clone(),
+        created: chrono::Local::now().format("%Y%m%d_%H%M%S").to_string(),
+        open_shots: total_shots,
+        open_goals: total_goals,
+        rates,
+    };
+    match table.save(std::path::Path::new(CONTESTED_QUALITY_DIR)) {
+        Ok(path) => println!("\n  Contested table written to {}", path.display()),
+        Err(e) => eprintln!("Warning: {}", e),
+    }
+    let targets =
+        load_targets(std::path::Path::new(TUNING_TARGETS_FILE)).unwrap_or_else(default_targets);
+    if let Some(target) = &targets.contested_make_rate {
+        println!("{}", target.check(table.contested_rate() * 100.0).format());
+    }
 }
 
 /// Run all shots at a single position using ONE app instance
@@ -184,6 +322,8 @@ fn run_shots_at_position(
     shots: u32,
     level_db: &LevelDatabase,
     level: u32,
+    jump_shot: bool,
+    defender: Option<DefenderPlacement>,
 ) -> PositionResult {
     // Create ONE app for all shots at this position
     let mut app = App::new();
@@ -214,6 +354,7 @@ fn run_shots_at_position(
     app.init_resource::<StealTracker>();
     app.init_resource::<PhysicsTweaks>();
     let _ = tuning::apply_global_tuning(&mut app.world_mut().resource_mut::<PhysicsTweaks>());
+    app.add_plugins(GameplayConfig::load("test"));
     app.init_resource::<LastShotInfo>();
     app.insert_resource(CurrentPalette(0));
     app.init_resource::<PaletteDatabase>();
@@ -228,9 +369,16 @@ fn run_shots_at_position(
         ball_max_y: f32::MIN,
         frame_count: 0,
         settle_start_frame: 0,
+        jump_shot,
+        jump_plan: None,
+        jump_timer: 0.0,
+        defender,
+        defender_jumped: false,
         goals: 0,
         overshoots: 0,
         undershoots: 0,
+        release_error_sum: 0.0,
+        no_plan: false,
         all_done: false,
     });
 
@@ -240,7 +388,7 @@ fn run_shots_at_position(
     app.add_systems(
         Startup,
         move |commands: Commands, level_db: Res<LevelDatabase>| {
-            shot_test_setup(commands, &level_db, player_x_clone, level_clone);
+            shot_test_setup(commands, &level_db, player_x_clone, level_clone, defender);
         },
     );
 
@@ -248,14 +396,17 @@ fn run_shots_at_position(
     app.add_systems(
         FixedUpdate,
         (
-            shot_test_control_system,
+            (shot_test_control_system, shot_test_defender_system).chain(),
             apply_input,
             apply_gravity,
             ball_gravity,
             ball_spin,
+            apply_wind_forces,
+            move_baskets,
             apply_velocity,
             check_collisions,
             ball_collisions,
+            level_feature_traversal,
             ball_state_update,
             ball_player_collision,
             crate::ball::ball_follow_holder,
@@ -286,19 +437,30 @@ fn run_shots_at_position(
 
     // Extract accumulated results
     let control = app.world().resource::<ShotTestControl>();
+    let taken = shots - control.shots_remaining;
     PositionResult {
         goals: control.goals,
         overshoots: control.overshoots,
         undershoots: control.undershoots,
+        release_error: control.release_error_sum / taken.max(1) as f32,
+        no_plan: control.no_plan,
     }
 }
 
 /// Setup for shot test - spawns arena and initial player/ball
-fn shot_test_setup(mut commands: Commands, level_db: &LevelDatabase, player_x: f32, level: u32) {
+fn shot_test_setup(
+    mut commands: Commands,
+    level_db: &LevelDatabase,
+    player_x: f32,
+    level: u32,
+    defender: Option<DefenderPlacement>,
+) {
     let level_idx = (level - 1) as usize;
     let level_def = level_db.get(level_idx);
+    let arena = level_def.map(|l| l.arena()).unwrap_or_default();
+    commands.insert_resource(arena);
 
-    let floor_y = ARENA_FLOOR_Y;
+    let floor_y = arena.floor_y;
     let player_y = floor_y + 20.0 + PLAYER_SIZE.y / 2.0;
 
     // Spawn player
@@ -348,10 +510,51 @@ fn shot_test_setup(mut commands: Commands, level_db: &LevelDatabase, player_x: f
         .entity(player_entity)
         .insert(HoldingBall(ball_entity));
 
+    // Defender in front of the shooter (toward the right basket), on a ledge if raised
+    if let Some(placement) = defender {
+        let start = Vec3::new(
+            player_x + placement.distance,
+            player_y + placement.height,
+            0.0,
+        );
+        commands.spawn((
+            Transform::from_translation(start),
+            Sprite {
+                custom_size: Some(PLAYER_SIZE),
+                ..default()
+            },
+            Player,
+            Velocity::default(),
+            Grounded(true),
+            CoyoteTimer::default(),
+            JumpState::default(),
+            Facing(-1.0),
+            ChargingShot::default(),
+            TargetBasket(Basket::Left),
+            Collider,
+            Team::Right,
+            InputState::default(),
+            StealCooldown::default(),
+            ShotTestDefender { start },
+        ));
+        if placement.height > 0.0 {
+            let ledge_top = start.y - PLAYER_SIZE.y / 2.0;
+            commands.spawn((
+                Sprite {
+                    custom_size: Some(Vec2::new(PLAYER_SIZE.x * 2.0, 20.0)),
+                    ..default()
+                },
+                Transform::from_xyz(start.x, ledge_top - 10.0, 0.0),
+                Platform,
+                Collider,
+            ));
+        }
+    }
+
     // Spawn floor
     commands.spawn((
         Sprite {
-            custom_size: Some(Vec2::new(ARENA_WIDTH - WALL_THICKNESS * 2.0, 40.0)),
+            custom_size: Some(Vec2::new(arena.width - WALL_THICKNESS * 2.0, 40.0)),
             ..default()
         },
         Transform::from_xyz(0.0, floor_y, 0.0),
@@ -365,7 +568,7 @@ fn shot_test_setup(mut commands: Commands, level_db: &LevelDatabase, player_x: f
             custom_size: Some(Vec2::new(WALL_THICKNESS, 5000.0)),
             ..default()
         },
-        Transform::from_xyz(-ARENA_WIDTH / 2.0 + WALL_THICKNESS / 2.0, 2000.0, 0.0),
+        Transform::from_xyz(-arena.width / 2.0 + WALL_THICKNESS / 2.0, 2000.0, 0.0),
         Platform,
         Collider,
     ));
@@ -374,7 +577,7 @@ fn shot_test_setup(mut commands: Commands, level_db: &LevelDatabase, player_x: f
             custom_size: Some(Vec2::new(WALL_THICKNESS, 5000.0)),
             ..default()
         },
-        Transform::from_xyz(ARENA_WIDTH / 2.0 - WALL_THICKNESS / 2.0, 2000.0, 0.0),
+        Transform::from_xyz(arena.width / 2.0 - WALL_THICKNESS / 2.0, 2000.0, 0.0),
         Platform,
         Collider,
     ));
@@ -382,9 +585,7 @@ fn shot_test_setup(mut commands: Commands, level_db: &LevelDatabase, player_x: f
     // Spawn baskets
     if let Some(level_def) = level_def {
         let basket_y = floor_y + level_def.basket_height;
-        let wall_inner = ARENA_WIDTH / 2.0 - WALL_THICKNESS;
-        let right_basket_x = wall_inner - level_def.basket_push_in;
-        let left_basket_x = -wall_inner + level_def.basket_push_in;
+        let (left_basket_x, right_basket_x) = arena.basket_x_from_offset(level_def.basket_push_in);
 
         commands.spawn((
             Sprite {
@@ -406,15 +607,70 @@ fn shot_test_setup(mut commands: Commands, level_db: &LevelDatabase, player_x: f
 }
 
 /// Control system - manages charging and releasing shots
+#[allow(clippy::too_many_arguments)]
 fn shot_test_control_system(
     mut control: ResMut<ShotTestControl>,
-    mut players: Query<(&mut InputState, &ChargingShot), With<Player>>,
+    time: Res<Time>,
+    tweaks: Res<PhysicsTweaks>,
+    shot_config: Res<ShotConfig>,
+    arena: Res<ArenaDims>,
+    mut players: Query<
+        (&mut InputState, &ChargingShot, &Grounded, &Transform),
+        (With<Player>, Without<ShotTestDefender>),
+    >,
     balls: Query<&BallState, With<Ball>>,
+    baskets: Query<(&Transform, &Basket), Without<Player>>,
 ) {
     control.frame_count += 1;
 
-    for (mut input, charging) in &mut players {
+    for (mut input, charging, grounded, transform) in &mut players {
         match control.phase {
+            ShotTestPhase::Setup if control.jump_shot => {
+                // Take off from the ground, as the AI does
+                if !grounded.0 {
+                    continue;
+                }
+                let Some((basket, _)) = baskets.iter().find(|(_, b)| **b == Basket::Right) else {
+                    continue;
+                };
+                let plan = AiCapabilities::default().plan_jump_shot(
+                    &tweaks,
+                    transform.translation.truncate(),
+                    basket.translation.truncate(),
+                    (0.0, tweaks.shot_charge_time),
+                    tweaks.shot_air_variance_penalty + shot_config.unsteady_variance,
+                );
+                if plan.is_none() {
+                    control.no_plan = true;
+                    control.all_done = true;
+                    continue;
+                }
+                control.jump_plan = plan;
+                control.jump_timer = 0.0;
+                input.jump_buffer_timer = JUMP_BUFFER_TIME;
+                input.jump_held = true;
+                control.phase = ShotTestPhase::Charging;
+            }
+            ShotTestPhase::Charging if control.jump_shot => {
+                let Some(plan) = control.jump_plan else {
+                    continue;
+                };
+                control.jump_timer += time.delta_secs();
+                input.jump_held = control.jump_timer < plan.hold;
+                if control.jump_timer <= JUMP_SHOT_CHARGE_DELAY {
+                    continue;
+                }
+                input.throw_held = true;
+                if charging.charge_time >= plan.charge {
+                    input.throw_held = false;
+                    input.throw_released = true;
+                    let standing_y = arena.floor_y + 20.0 + PLAYER_SIZE.y / 2.0;
+                    let height = transform.translation.y - standing_y;
+                    control.release_error_sum += (height - plan.release_height).abs();
+                    control.phase = ShotTestPhase::InFlight;
+                    control.ball_max_y = f32::MIN;
+                }
+            }
             ShotTestPhase::Setup => {
                 input.throw_held = true;
                 control.phase = ShotTestPhase::Charging;
@@ -452,6 +708,33 @@ fn shot_test_control_system(
     }
 }
 
+/// Drive the defender's jumps for its pattern
+fn shot_test_defender_system(
+    mut control: ResMut<ShotTestControl>,
+    mut defenders: Query<(&mut InputState, &Grounded), With<ShotTestDefender>>,
+) {
+    let Some(placement) = control.defender else {
+        return;
+    };
+    let shot_up = matches!(
+        control.phase,
+        ShotTestPhase::Charging | ShotTestPhase::InFlight
+    );
+    for (mut input, grounded) in &mut defenders {
+        let jump = match placement.pattern {
+            DefenderPattern::Stand => false,
+            DefenderPattern::Jump => shot_up && !control.defender_jumped,
+            DefenderPattern::Hop => true,
+        };
+        if jump && grounded.0 {
+            input.jump_buffer_timer = JUMP_BUFFER_TIME;
+            control.defender_jumped = true;
+        }
+        // Full-height jumps
+        input.jump_held = jump || !grounded.0;
+    }
+}
+
 /// Track ball's maximum height during flight
 fn shot_test_track_ball(
     mut control: ResMut<ShotTestControl>,
@@ -472,6 +755,7 @@ fn shot_test_track_ball(
 /// Reset system - records result and resets for next shot
 fn shot_test_reset_system(
     mut control: ResMut<ShotTestControl>,
+    arena: Res<ArenaDims>,
     mut score: ResMut<Score>,
     mut players: Query<
         (
@@ -482,7 +766,16 @@ fn shot_test_reset_system(
             &mut InputState,
             Option<&HoldingBall>,
         ),
-        (With<Player>, Without<Ball>),
+        (With<Player>, Without<Ball>, Without<ShotTestDefender>),
+    >,
+    mut defenders: Query<
+        (
+            &ShotTestDefender,
+            &mut Transform,
+            &mut Velocity,
+            &mut InputState,
+        ),
+        Without<Ball>,
     >,
     mut balls: Query<
         (
@@ -519,7 +812,7 @@ fn shot_test_reset_system(
     }
 
     // Reset for next shot
-    let floor_y = ARENA_FLOOR_Y;
+    let floor_y = arena.floor_y;
     let player_y = floor_y + 20.0 + PLAYER_SIZE.y / 2.0;
     let player_x = control.player_x;
 
@@ -533,9 +826,11 @@ fn shot_test_reset_system(
     {
         transform.translation = Vec3::new(player_x, player_y, 0.0);
         velocity.0 = Vec2::ZERO;
-        charging.charge_time = 0.0;
+        charging.reset();
         input.throw_held = false;
         input.throw_released = false;
+        input.jump_held = false;
+        input.jump_buffer_timer = 0.0;
 
         // Remove HoldingBall if present (will re-add below)
         if holding.is_some() {
@@ -543,6 +838,14 @@ fn shot_test_reset_system(
         }
     }
 
+    // Reset defender
+    for (defender, mut transform, mut velocity, mut input) in &mut defenders {
+        transform.translation = defender.start;
+        velocity.0 = Vec2::ZERO;
+        input.jump_buffer_timer = 0.0;
+        input.jump_held = false;
+    }
+
     // Reset ball
     for (
         ball_entity,
@@ -572,4 +875,6 @@ fn shot_test_reset_system(
     // Reset control state
     control.phase = ShotTestPhase::Setup;
     control.ball_max_y = f32::MIN;
+    control.jump_plan = None;
+    control.defender_jumped = false;
 }
@@ -0,0 +1,191 @@
+//! Live GIF capture - record the game window to an animated GIF (F6)
+//!
+//! Press F6 to record the next few seconds of gameplay; press it again to stop
+//! early. Frames are screenshots of the primary window taken at a fixed rate,
+//! downscaled as they arrive, then encoded on a background thread into
+//! `showcase/gifs/`.
+
+use bevy::prelude::*;
+use bevy::render::view::screenshot::{Screenshot, ScreenshotCaptured};
+use chrono::Local;
+use image::RgbaImage;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use crate::generate::gif::{downscale, encode_gif};
+
+/// Directory where captured GIFs are saved
+pub const GIF_DIR: &str = "showcase/gifs";
+
+/// Frames to wait for outstanding screenshots after recording stops
+const DRAIN_TIMEOUT_FRAMES: u32 = 120;
+
+/// Capture settings
+#[derive(Resource, Clone)]
+pub struct GifCaptureConfig {
+    /// Recording length in seconds
+    pub duration_secs: f32,
+    /// Frames per second in the GIF
+    pub fps: u32,
+    /// Frames wider than this are downscaled (keeps aspect ratio)
+    pub max_width: u32,
+}
+
+impl Default for GifCaptureConfig {
+    fn default() -> Self {
+        Self {
+            duration_secs: 5.0,
+            fps: 15,
+            max_width: 480,
+        }
+    }
+}
+
+/// Frames received so far, tagged with their request index (screenshots can arrive out of order)
+type FrameBuffer = Arc<Mutex<Vec<(u32, RgbaImage)>>>;
+
+struct Recording {
+    frames: FrameBuffer,
+    /// Screenshots requested so far
+    requested: u32,
+    /// Real seconds since recording started
+    elapsed: f32,
+    next_frame_at: f32,
+    /// Still requesting frames (false once time is up or F6 is pressed again)
+    capturing: bool,
+    /// Frames spent waiting for outstanding screenshots
+    drain_frames: u32,
+    path: PathBuf,
+}
+
+/// Recording state (None when idle)
+#[derive(Resource, Default)]
+pub struct GifCapture {
+    recording: Option<Recording>,
+}
+
+impl GifCapture {
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+}
+
+/// Output path for a capture started now
+fn gif_path() -> PathBuf {
+    let timestamp = Local::now().format("%Y%m%d_%H%M%S").to_string();
+    PathBuf::from(format!("{}/capture_{}.gif", GIF_DIR, timestamp))
+}
+
+/// Request one screenshot; the observer downscales it into the frame buffer
+fn request_frame(commands: &mut Commands, frames: &FrameBuffer, index: u32, max_width: u32) {
+    let frames = frames.clone();
+    commands.spawn(Screenshot::primary_window()).observe(
+        move |captured: On<ScreenshotCaptured>| match captured.image.clone().try_into_dynamic() {
+            Ok(img) => {
+                let img = downscale(&img.to_rgba8(), max_width);
+                if let Ok(mut frames) = frames.lock() {
+                    frames.push((index, img));
+                }
+            }
+            Err(e) => warn!("GIF capture: frame {} dropped: {:?}", index, e),
+        },
+    );
+}
+
+/// F6 starts/stops a recording; requests frames while recording and encodes when done
+pub fn gif_capture_system(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    time: Res<Time<Real>>,
+    config: Res<GifCaptureConfig>,
+    mut capture: ResMut<GifCapture>,
+) {
+    if keyboard.just_pressed(KeyCode::F6) {
+        match &mut capture.recording {
+            Some(recording) if recording.capturing => {
+                recording.capturing = false;
+                info!("GIF capture: stopped early");
+            }
+            Some(_) => info!("GIF capture: still finishing the previous recording"),
+            None => {
+                let path = gif_path();
+                info!(
+                    "GIF capture: recording {:.0}s to {}",
+                    config.duration_secs,
+                    path.display()
+                );
+                capture.recording = Some(Recording {
+                    frames: Arc::default(),
+                    requested: 0,
+                    elapsed: 0.0,
+                    next_frame_at: 0.0,
+                    capturing: true,
+                    drain_frames: 0,
+                    path,
+                });
+            }
+        }
+    }
+
+    let Some(recording) = &mut capture.recording else {
+        return;
+    };
+
+    if recording.capturing {
+        recording.elapsed += time.delta_secs();
+        let interval = 1.0 / config.fps.max(1) as f32;
+        // One frame per interval; a slow frame doesn't queue a burst of screenshots
+        if recording.elapsed >= recording.next_frame_at {
+            request_frame(
+                &mut commands,
+                &recording.frames,
+                recording.requested,
+                config.max_width,
+            );
+            recording.requested += 1;
+            recording.next_frame_at += interval;
+            if recording.next_frame_at < recording.elapsed {
+                recording.next_frame_at = recording.elapsed + interval;
+            }
+        }
+        if recording.elapsed >= config.duration_secs {
+            recording.capturing = false;
+        }
+        return;
+    }
+
+    // Wait for outstanding screenshots before encoding
+    let received = recording.frames.lock().map(|f| f.len() as u32).unwrap_or(0);
+    recording.drain_frames += 1;
+    if received < recording.requested && recording.drain_frames < DRAIN_TIMEOUT_FRAMES {
+        return;
+    }
+
+    let Some(recording) = capture.recording.take() else {
+        return;
+    };
+    let mut frames =
+        std::mem::take(&mut *recording.frames.lock().unwrap_or_else(|e| e.into_inner()));
+    frames.sort_by_key(|(index, _)| *index);
+    let frames: Vec<RgbaImage> = frames.into_iter().map(|(_, img)| img).collect();
+    let frame_ms = 1000 / config.fps.max(1);
+    let path = recording.path;
+    info!("GIF capture: encoding {} frames", frames.len());
+    std::thread::spawn(move || match encode_gif(frames, frame_ms, &path) {
+        Ok(()) => info!("GIF capture: saved {}", path.display()),
+        Err(e) => warn!("GIF capture: not saved: {}", e),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capture_path_lands_in_gif_dir() {
+        let path = gif_path();
+        assert!(path.starts_with(GIF_DIR));
+        assert_eq!(path.extension().and_then(|e| e.to_str()), Some("gif"));
+        assert!(!GifCapture::default().is_recording());
+    }
+}
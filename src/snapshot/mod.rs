@@ -2,6 +2,14 @@
 //!
 //! Provides automated capture of game state (JSON) and optional screenshots
 //! triggered by game events like scoring, steals, and level changes.
+//! With the `spectate` feature, `spectate` also publishes snapshots periodically
+//! for external dashboards. `gif_capture` records the window to an animated GIF.
+
+mod gif_capture;
+mod spectate;
+
+pub use gif_capture::{GIF_DIR, GifCapture, GifCaptureConfig, gif_capture_system};
+pub use spectate::{SpectateConfig, SpectatePlugin};
 
 use bevy::prelude::*;
 use bevy::render::view::screenshot::{Screenshot, save_to_disk};
@@ -136,6 +144,45 @@ pub struct ShotSnapshot {
     pub target: Option<String>,
 }
 
+/// Snapshot of one player (AI goal only for AI-controlled players)
+fn player_snapshot(
+    transform: &Transform,
+    velocity: &Velocity,
+    team: &Team,
+    human: Option<&HumanControlled>,
+    holding: Option<&HoldingBall>,
+    ai_state: &AiState,
+) -> PlayerSnapshot {
+    PlayerSnapshot {
+        team: format!("{:?}", team),
+        position: (transform.translation.x, transform.translation.y),
+        velocity: (velocity.0.x, velocity.0.y),
+        is_human: human.is_some(),
+        holding_ball: holding.is_some(),
+        ai_goal: if human.is_none() {
+            Some(format!("{:?}", ai_state.current_goal))
+        } else {
+            None
+        },
+    }
+}
+
+/// Snapshot of the last shot (None before the first shot)
+fn shot_snapshot(last_shot: &LastShotInfo) -> Option<ShotSnapshot> {
+    last_shot.target.map(|basket| ShotSnapshot {
+        angle_degrees: last_shot.angle_degrees,
+        speed: last_shot.speed,
+        total_variance: last_shot.total_variance,
+        target: Some(
+            match basket {
+                Basket::Left => "Left",
+                Basket::Right => "Right",
+            }
+            .to_string(),
+        ),
+    })
+}
+
 /// System that detects events and triggers snapshots
 #[allow(clippy::too_many_arguments)]
 pub fn snapshot_trigger_system(
@@ -244,35 +291,10 @@ pub fn snapshot_trigger_system(
 
         let players: Vec<PlayerSnapshot> = player_query
             .iter()
-            .map(
-                |(transform, velocity, team, human, holding, ai_state)| PlayerSnapshot {
-                    team: format!("{:?}", team),
-                    position: (transform.translation.x, transform.translation.y),
-                    velocity: (velocity.0.x, velocity.0.y),
-                    is_human: human.is_some(),
-                    holding_ball: holding.is_some(),
-                    ai_goal: if human.is_none() {
-                        Some(format!("{:?}", ai_state.current_goal))
-                    } else {
-                        None
-                    },
-                },
-            )
-            .collect();
-
-        let shot_snapshot = if last_shot.target.is_some() {
-            Some(ShotSnapshot {
-                angle_degrees: last_shot.angle_degrees,
-                speed: last_shot.speed,
-                total_variance: last_shot.total_variance,
-                target: last_shot.target.map(|b| match b {
-                    Basket::Left => "Left".to_string(),
-                    Basket::Right => "Right".to_string(),
-                }),
+            .map(|(transform, velocity, team, human, holding, ai_state)| {
+                player_snapshot(transform, velocity, team, human, holding, ai_state)
             })
-        } else {
-            None
-        };
+            .collect();
 
         // Screenshot path (if enabled)
         let screenshot_filename = format!("{}_{}.png", timestamp, trigger_name);
@@ -294,7 +316,7 @@ pub fn snapshot_trigger_system(
             palette: current_palette.0,
             ball: ball_snapshot,
             players,
-            last_shot: shot_snapshot,
+            last_shot: shot_snapshot(&last_shot),
             screenshot_path: screenshot_path.clone(),
         };
 
@@ -424,35 +446,10 @@ pub fn manual_snapshot(
 
     let players: Vec<PlayerSnapshot> = player_query
         .iter()
-        .map(
-            |(transform, velocity, team, human, holding, ai_state)| PlayerSnapshot {
-                team: format!("{:?}", team),
-                position: (transform.translation.x, transform.translation.y),
-                velocity: (velocity.0.x, velocity.0.y),
-                is_human: human.is_some(),
-                holding_ball: holding.is_some(),
-                ai_goal: if human.is_none() {
-                    Some(format!("{:?}", ai_state.current_goal))
-                } else {
-                    None
-                },
-            },
-        )
-        .collect();
-
-    let shot_snapshot = if last_shot.target.is_some() {
-        Some(ShotSnapshot {
-            angle_degrees: last_shot.angle_degrees,
-            speed: last_shot.speed,
-            total_variance: last_shot.total_variance,
-            target: last_shot.target.map(|b| match b {
-                Basket::Left => "Left".to_string(),
-                Basket::Right => "Right".to_string(),
-            }),
+        .map(|(transform, velocity, team, human, holding, ai_state)| {
+            player_snapshot(transform, velocity, team, human, holding, ai_state)
         })
-    } else {
-        None
-    };
+        .collect();
 
     let screenshot_filename = format!("{}_{}.png", timestamp, trigger_name);
     let screenshot_path = if config.save_screenshots {
@@ -473,7 +470,7 @@ pub fn manual_snapshot(
         palette: current_palette.0,
         ball: ball_snapshot,
         players,
-        last_shot: shot_snapshot,
+        last_shot: shot_snapshot(&last_shot),
         screenshot_path: screenshot_path.clone(),
     };
 
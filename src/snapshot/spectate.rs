@@ -0,0 +1,266 @@
+//! Spectate API - periodic JSON snapshots for external dashboards
+//!
+//! Publishes a `GameSnapshot` (trigger "spectate") every `interval_secs` of wall-clock
+//! time, to a file and/or a local HTTP endpoint (`GET http://127.0.0.1:<port>/`).
+//! Used by the game and by headless simulations. The publisher is only compiled with
+//! `--features spectate`; without it the flags are accepted and ignored with a warning.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "spectate")]
+use super::{BallSnapshot, GameSnapshot, PlayerSnapshot, ScoreSnapshot};
+#[cfg(feature = "spectate")]
+use crate::ai::AiState;
+#[cfg(feature = "spectate")]
+use crate::ball::{Ball, BallState, CurrentPalette};
+#[cfg(feature = "spectate")]
+use crate::player::{HoldingBall, HumanControlled, Player, Team, Velocity};
+#[cfg(feature = "spectate")]
+use crate::scoring::{CurrentLevel, Score};
+#[cfg(feature = "spectate")]
+use crate::shooting::LastShotInfo;
+#[cfg(feature = "spectate")]
+use std::io::{Read, Write};
+#[cfg(feature = "spectate")]
+use std::net::{TcpListener, TcpStream};
+#[cfg(feature = "spectate")]
+use std::sync::{Mutex, OnceLock};
+#[cfg(feature = "spectate")]
+use std::time::{Duration, Instant};
+
+/// Default seconds between published snapshots
+pub const DEFAULT_SPECTATE_INTERVAL: f32 = 0.5;
+
+/// Where and how often spectate snapshots are published
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+pub struct SpectateConfig {
+    /// JSON file rewritten with each snapshot
+    pub file: Option<String>,
+    /// Local HTTP port serving the latest snapshot
+    pub port: Option<u16>,
+    /// Wall-clock seconds between snapshots
+    pub interval_secs: f32,
+}
+
+impl SpectateConfig {
+    /// Parse `--spectate-file <path>`, `--spectate-port <port>` and `--spectate-interval <secs>`.
+    /// Returns None unless a file or a port is given.
+    pub fn from_args(args: &[String]) -> Option<Self> {
+        let value = |flag: &str| {
+            args.iter()
+                .position(|a| a == flag)
+                .and_then(|i| args.get(i + 1))
+        };
+        let config = Self {
+            file: value("--spectate-file").cloned(),
+            port: value("--spectate-port").and_then(|s| s.parse().ok()),
+            interval_secs: value("--spectate-interval")
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(DEFAULT_SPECTATE_INTERVAL),
+        };
+        (config.file.is_some() || config.port.is_some()).then_some(config)
+    }
+}
+
+/// Adds the spectate publisher to an app (does nothing when the config is None)
+pub struct SpectatePlugin(pub Option<SpectateConfig>);
+
+impl Plugin for SpectatePlugin {
+    fn build(&self, app: &mut App) {
+        let Some(config) = self.0.clone() else {
+            return;
+        };
+
+        #[cfg(feature = "spectate")]
+        {
+            if let Some(port) = config.port {
+                start_server(port);
+            }
+            app.insert_resource(config)
+                .init_resource::<SpectateState>()
+                .add_systems(Update, publish_spectate_snapshot);
+        }
+
+        #[cfg(not(feature = "spectate"))]
+        {
+            let _ = (app, config);
+            eprintln!("Warning: built without the `spectate` feature, ignoring --spectate-* flags");
+        }
+    }
+}
+
+/// Latest published snapshot (served over HTTP)
+#[cfg(feature = "spectate")]
+static LATEST_SNAPSHOT: Mutex<String> = Mutex::new(String::new());
+
+/// Port the HTTP server was started on (one server per process; simulations reuse it)
+#[cfg(feature = "spectate")]
+static SERVER_PORT: OnceLock<u16> = OnceLock::new();
+
+#[cfg(feature = "spectate")]
+#[derive(Resource, Default)]
+struct SpectateState {
+    last_publish: Option<Instant>,
+    frame: u64,
+}
+
+#[cfg(feature = "spectate")]
+fn start_server(port: u16) {
+    if SERVER_PORT.set(port).is_err() {
+        return;
+    }
+    let listener = match TcpListener::bind(("127.0.0.1", port)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!(
+                "Warning: spectate server failed to bind port {}: {}",
+                port, e
+            );
+            return;
+        }
+    };
+    eprintln!("Spectate: serving snapshots at http://127.0.0.1:{}/", port);
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            serve_latest(stream);
+        }
+    });
+}
+
+/// Answer any request with the latest snapshot (503 until the first one is published)
+#[cfg(feature = "spectate")]
+fn serve_latest(mut stream: TcpStream) {
+    let _ = stream.set_read_timeout(Some(Duration::from_secs(1)));
+    let mut request = [0u8; 1024];
+    let _ = stream.read(&mut request);
+
+    let body = LATEST_SNAPSHOT
+        .lock()
+        .map(|latest| latest.clone())
+        .unwrap_or_default();
+    let (status, body) = if body.is_empty() {
+        ("503 Service Unavailable", "{}".to_string())
+    } else {
+        ("200 OK", body)
+    };
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         Access-Control-Allow-Origin: *\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes());
+}
+
+/// Publish a snapshot every `interval_secs` (wall clock, so fast simulations don't flood)
+#[cfg(feature = "spectate")]
+#[allow(clippy::too_many_arguments)]
+fn publish_spectate_snapshot(
+    config: Res<SpectateConfig>,
+    mut state: ResMut<SpectateState>,
+    score: Res<Score>,
+    current_level: Res<CurrentLevel>,
+    current_palette: Res<CurrentPalette>,
+    last_shot: Res<LastShotInfo>,
+    ball_query: Query<(&Transform, &Velocity, &BallState), With<Ball>>,
+    player_query: Query<
+        (
+            &Transform,
+            &Velocity,
+            &Team,
+            Option<&HumanControlled>,
+            Option<&HoldingBall>,
+            &AiState,
+        ),
+        With<Player>,
+    >,
+) {
+    state.frame += 1;
+    let now = Instant::now();
+    if state
+        .last_publish
+        .is_some_and(|last| now.duration_since(last).as_secs_f32() < config.interval_secs)
+    {
+        return;
+    }
+    state.last_publish = Some(now);
+
+    let players: Vec<PlayerSnapshot> = player_query
+        .iter()
+        .map(|(transform, velocity, team, human, holding, ai_state)| {
+            super::player_snapshot(transform, velocity, team, human, holding, ai_state)
+        })
+        .collect();
+    let holder_team = players
+        .iter()
+        .find(|p| p.holding_ball)
+        .map(|p| p.team.clone());
+    let ball = ball_query
+        .iter()
+        .next()
+        .map(|(transform, velocity, ball_state)| BallSnapshot {
+            position: (transform.translation.x, transform.translation.y),
+            velocity: (velocity.0.x, velocity.0.y),
+            state: format!("{:?}", ball_state),
+            holder_team,
+        });
+
+    let snapshot = GameSnapshot {
+        timestamp: chrono::Local::now().format("%Y%m%d_%H%M%S_%3f").to_string(),
+        frame: state.frame,
+        trigger: "spectate".to_string(),
+        score: ScoreSnapshot {
+            left: score.left,
+            right: score.right,
+        },
+        level_id: current_level.0.clone(),
+        palette: current_palette.0,
+        ball,
+        players,
+        last_shot: super::shot_snapshot(&last_shot),
+        screenshot_path: None,
+    };
+    let json = match serde_json::to_string(&snapshot) {
+        Ok(json) => json,
+        Err(e) => {
+            eprintln!("Failed to serialize spectate snapshot: {}", e);
+            return;
+        }
+    };
+
+    // Write then rename so pollers never read a half-written file
+    if let Some(path) = &config.file {
+        let tmp_path = format!("{}.tmp", path);
+        if let Err(e) =
+            std::fs::write(&tmp_path, &json).and_then(|_| std::fs::rename(&tmp_path, path))
+        {
+            eprintln!("Failed to write spectate snapshot {}: {}", path, e);
+        }
+    }
+    if config.port.is_some()
+        && let Ok(mut latest) = LATEST_SNAPSHOT.lock()
+    {
+        *latest = json;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spectate_needs_file_or_port() {
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert!(
+            SpectateConfig::from_args(&args(&["ballgame", "--spectate-interval", "2"])).is_none()
+        );
+
+        let config =
+            SpectateConfig::from_args(&args(&["simulate", "--spectate-port", "8765"])).unwrap();
+        assert_eq!(config.port, Some(8765));
+        assert_eq!(config.file, None);
+        assert_eq!(config.interval_secs, DEFAULT_SPECTATE_INTERVAL);
+    }
+}
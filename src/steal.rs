@@ -1,8 +1,14 @@
 //! Steal system - instant steal attempts with cooldown
 
 use bevy::prelude::*;
+use rand::Rng;
 
+use crate::constants::{
+    LOOSE_BALL_POP_X, LOOSE_BALL_POP_Y_MAX, LOOSE_BALL_POP_Y_MIN, LOOSE_BALL_SCRAMBLE_TIME,
+    STEAL_FAIL_FLASH_DURATION, STEAL_OUT_OF_RANGE_FLASH_DURATION,
+};
 use crate::player::Team;
+use crate::scoring::Handicap;
 
 /// Threshold at which graduated steal difficulty starts applying.
 /// Below this differential, steal chances are normal.
@@ -11,6 +17,60 @@ pub const STEAL_DIFFICULTY_THRESHOLD: i32 = 2;
 /// Maximum steal differential at which leader has 0% chance and trailer has 100%.
 pub const STEAL_DIFFICULTY_MAX: i32 = 8;
 
+/// Why a steal press didn't take the ball
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StealDenial {
+    /// Pressed near the holder but outside `STEAL_RANGE`
+    OutOfRange,
+    /// Pressed while the attacker's own steal cooldown was running
+    OnCooldown,
+    /// Holder could not be stolen from (difficulty modifier took the chance to zero)
+    HolderInvulnerable,
+    /// In range and rolled, but lost the roll
+    ContestLost,
+}
+
+impl StealDenial {
+    /// Reason string used in the `SD` event
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StealDenial::OutOfRange => "out_of_range",
+            StealDenial::OnCooldown => "cooldown",
+            StealDenial::HolderInvulnerable => "invulnerable",
+            StealDenial::ContestLost => "contest_lost",
+        }
+    }
+
+    /// How long the denial flash stays up
+    pub fn flash_duration(self) -> f32 {
+        match self {
+            StealDenial::OutOfRange => STEAL_OUT_OF_RANGE_FLASH_DURATION,
+            StealDenial::OnCooldown => 0.15,
+            StealDenial::HolderInvulnerable => 0.3,
+            StealDenial::ContestLost => STEAL_FAIL_FLASH_DURATION,
+        }
+    }
+
+    /// Camera shake amplitude in pixels when the human player is denied
+    pub fn shake_amplitude(self) -> f32 {
+        match self {
+            StealDenial::OutOfRange => 2.0,
+            StealDenial::OnCooldown => 0.0,
+            StealDenial::HolderInvulnerable => 4.0,
+            StealDenial::ContestLost => 6.0,
+        }
+    }
+}
+
+/// Active denial feedback for one player
+#[derive(Debug, Clone, Copy)]
+pub struct StealDenialFeedback {
+    pub entity: Entity,
+    pub reason: StealDenial,
+    /// Seconds remaining (counts down)
+    pub timer: f32,
+}
+
 /// Steal feedback resource - tracks last attempt result for visual feedback
 #[derive(Resource, Default)]
 pub struct StealContest {
@@ -28,6 +88,50 @@ pub struct StealContest {
     pub cooldown_blocked_timer: f32,
     /// Entity that pressed steal while on cooldown
     pub cooldown_blocked_entity: Option<Entity>,
+    /// Per-player denial feedback (at most one entry per player)
+    pub denials: Vec<StealDenialFeedback>,
+}
+
+impl StealContest {
+    /// Record a denied steal press, replacing any feedback already showing for that player.
+    /// Also sets the matching legacy fields so event detection keeps working.
+    pub fn deny(&mut self, entity: Entity, reason: StealDenial) {
+        let timer = reason.flash_duration();
+        match reason {
+            StealDenial::OutOfRange => {
+                self.out_of_range_timer = timer;
+                self.out_of_range_entity = Some(entity);
+            }
+            StealDenial::OnCooldown => {
+                self.cooldown_blocked_timer = timer;
+                self.cooldown_blocked_entity = Some(entity);
+            }
+            StealDenial::HolderInvulnerable | StealDenial::ContestLost => {
+                self.last_attempt_failed = true;
+                self.fail_flash_timer = timer;
+                self.fail_flash_entity = Some(entity);
+            }
+        }
+        self.denials.retain(|d| d.entity != entity);
+        self.denials.push(StealDenialFeedback {
+            entity,
+            reason,
+            timer,
+        });
+    }
+
+    /// Denial feedback currently showing for a player
+    pub fn denial_for(&self, entity: Entity) -> Option<&StealDenialFeedback> {
+        self.denials.iter().find(|d| d.entity == entity)
+    }
+
+    /// Tick denial timers, dropping any that ran out
+    pub fn tick_denials(&mut self, dt: f32) {
+        for denial in &mut self.denials {
+            denial.timer -= dt;
+        }
+        self.denials.retain(|d| d.timer > 0.0);
+    }
 }
 
 /// Resource tracking steal attempts and successes per team for differential enforcement
@@ -134,6 +238,51 @@ impl StealTracker {
     }
 }
 
+/// Loose-ball steals (optional rule, off by default): a successful steal knocks
+/// the ball free with a small random pop instead of handing it to the attacker,
+/// and both players scramble for it.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct LooseBall {
+    pub enabled: bool,
+    /// Seconds left in the current scramble (0 = none)
+    pub scramble: f32,
+}
+
+impl LooseBall {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..default()
+        }
+    }
+
+    /// Ball velocity for a steal that knocks it loose
+    pub fn pop_velocity(rng: &mut impl Rng) -> Vec2 {
+        Vec2::new(
+            rng.gen_range(-LOOSE_BALL_POP_X..=LOOSE_BALL_POP_X),
+            rng.gen_range(LOOSE_BALL_POP_Y_MIN..=LOOSE_BALL_POP_Y_MAX),
+        )
+    }
+
+    /// A steal just knocked the ball loose
+    pub fn start_scramble(&mut self) {
+        self.scramble = LOOSE_BALL_SCRAMBLE_TIME;
+    }
+
+    /// Someone came up with the ball
+    pub fn end_scramble(&mut self) {
+        self.scramble = 0.0;
+    }
+
+    pub fn is_scrambling(&self) -> bool {
+        self.scramble > 0.0
+    }
+
+    pub fn tick(&mut self, dt: f32) {
+        self.scramble = (self.scramble - dt).max(0.0);
+    }
+}
+
 /// Cooldown timer preventing steal spam (seconds remaining)
 #[derive(Component, Default)]
 pub struct StealCooldown(pub f32);
@@ -141,15 +290,22 @@ pub struct StealCooldown(pub f32);
 /// Tick down steal cooldowns and fail flash timer
 pub fn steal_cooldown_update(
     time: Res<Time>,
-    mut cooldowns: Query<&mut StealCooldown>,
+    mut cooldowns: Query<(&mut StealCooldown, Option<&Team>)>,
     mut steal_contest: ResMut<StealContest>,
+    handicap: Option<Res<Handicap>>,
+    loose_ball: Option<ResMut<LooseBall>>,
 ) {
     // Use minimum dt for headless mode compatibility
     let dt = time.delta_secs().max(1.0 / 60.0);
 
-    for mut cooldown in &mut cooldowns {
+    for (mut cooldown, team) in &mut cooldowns {
         if cooldown.0 > 0.0 {
-            cooldown.0 -= dt;
+            // A cooldown handicap stretches the cooldown by draining it slower
+            let scale = match (&handicap, team) {
+                (Some(handicap), Some(team)) => handicap.side(*team).cooldown,
+                _ => 1.0,
+            };
+            cooldown.0 -= dt / scale;
         }
     }
 
@@ -177,4 +333,61 @@ pub fn steal_cooldown_update(
             steal_contest.cooldown_blocked_entity = None;
         }
     }
+
+    steal_contest.tick_denials(dt);
+
+    if let Some(mut loose_ball) = loose_ball.filter(|l| l.is_scrambling()) {
+        loose_ball.tick(dt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deny_replaces_feedback_per_player_and_expires() {
+        let mut contest = StealContest::default();
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+
+        contest.deny(a, StealDenial::OutOfRange);
+        contest.deny(b, StealDenial::OnCooldown);
+        contest.deny(a, StealDenial::HolderInvulnerable);
+
+        assert_eq!(contest.denials.len(), 2);
+        assert_eq!(
+            contest.denial_for(a).map(|d| d.reason),
+            Some(StealDenial::HolderInvulnerable)
+        );
+        assert!(contest.last_attempt_failed);
+        assert_eq!(contest.cooldown_blocked_entity, Some(b));
+
+        contest.tick_denials(0.2);
+        assert!(contest.denial_for(b).is_none());
+        assert!(contest.denial_for(a).is_some());
+    }
+
+    #[test]
+    fn loose_ball_pops_up_and_scramble_runs_out() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let pop = LooseBall::pop_velocity(&mut rng);
+            assert!(pop.x.abs() <= LOOSE_BALL_POP_X);
+            assert!((LOOSE_BALL_POP_Y_MIN..=LOOSE_BALL_POP_Y_MAX).contains(&pop.y));
+        }
+
+        let mut loose_ball = LooseBall::new(true);
+        assert!(!loose_ball.is_scrambling());
+        loose_ball.start_scramble();
+        loose_ball.tick(LOOSE_BALL_SCRAMBLE_TIME / 2.0);
+        assert!(loose_ball.is_scrambling());
+        loose_ball.tick(LOOSE_BALL_SCRAMBLE_TIME);
+        assert!(!loose_ball.is_scrambling());
+
+        loose_ball.start_scramble();
+        loose_ball.end_scramble();
+        assert!(!loose_ball.is_scrambling());
+    }
 }
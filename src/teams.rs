@@ -0,0 +1,212 @@
+//! Team names and colors
+//!
+//! `--team-left name=Reds,color=#d03030` / `--team-right ...` (game and
+//! training) or `BallgamePlugins::teams` when embedding name a side and give it a
+//! color that overrides the palette's side color. Names replace L/R on the score
+//! HUD, and both go into the `MatchStart` event, so analytics and leaderboards
+//! can show who played. Sides left unset keep the palette color and show as
+//! plain L/R.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::ball::CurrentPalette;
+use crate::events::{TeamTags, intern};
+use crate::player::{Player, Team};
+use crate::world::Basket;
+
+/// Longest team name kept (longer names are cut)
+pub const TEAM_NAME_MAX_CHARS: usize = 16;
+
+/// Parse `#rrggbb` (the `#` is optional)
+pub fn parse_hex_color(hex: &str) -> Result<Color, String> {
+    let digits = hex.trim().trim_start_matches('#');
+    if digits.len() != 6 || !digits.is_ascii() {
+        return Err(format!("expected a color like #d03030, got '{}'", hex));
+    }
+    let channel = |i: usize| {
+        u8::from_str_radix(&digits[i..i + 2], 16).map_err(|_| format!("bad hex color '{}'", hex))
+    };
+    Ok(Color::srgb_u8(channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// Name and color for one side
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TeamInfo {
+    pub name: Option<String>,
+    /// Hex color, e.g. `#d03030`
+    pub color: Option<String>,
+}
+
+impl TeamInfo {
+    /// Parse `name=Reds,color=#d03030` (either part may be left out)
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut info = Self::default();
+        for part in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("expected key=value, got '{}'", part))?;
+            let value = value.trim();
+            match key.trim() {
+                // '|' separates event fields
+                "name" if value.contains('|') => {
+                    return Err(format!("team name can't contain '|': '{}'", value));
+                }
+                "name" => info.name = Some(value.chars().take(TEAM_NAME_MAX_CHARS).collect()),
+                "color" => {
+                    parse_hex_color(value)?;
+                    info.color = Some(value.to_string());
+                }
+                other => return Err(format!("unknown team option '{}' (use name, color)", other)),
+            }
+        }
+        Ok(info)
+    }
+
+    pub fn color(&self) -> Option<Color> {
+        self.color.as_deref().and_then(|c| parse_hex_color(c).ok())
+    }
+}
+
+/// Names and colors for both sides (default: none, palette colors)
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TeamIdentity {
+    pub left: TeamInfo,
+    pub right: TeamInfo,
+}
+
+impl TeamIdentity {
+    /// Read `--team-left <spec>` / `--team-right <spec>`
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let mut teams = Self::default();
+        for (flag, side) in [
+            ("--team-left", &mut teams.left),
+            ("--team-right", &mut teams.right),
+        ] {
+            if let Some(i) = args.iter().position(|a| a == flag) {
+                let spec = args
+                    .get(i + 1)
+                    .ok_or_else(|| format!("{} expects a value", flag))?;
+                *side = TeamInfo::parse(spec).map_err(|e| format!("{}: {}", flag, e))?;
+            }
+        }
+        Ok(teams)
+    }
+
+    pub fn side(&self, team: Team) -> &TeamInfo {
+        match team {
+            Team::Left => &self.left,
+            Team::Right => &self.right,
+        }
+    }
+
+    pub fn has_names(&self) -> bool {
+        self.left.name.is_some() || self.right.name.is_some()
+    }
+
+    /// Name to show for a side ("Left"/"Right" when unnamed)
+    pub fn display_name(&self, team: Team) -> &str {
+        self.side(team).name.as_deref().unwrap_or(match team {
+            Team::Left => "Left",
+            Team::Right => "Right",
+        })
+    }
+
+    /// Names and colors as recorded in MatchStart
+    pub fn tags(&self) -> TeamTags {
+        let tag = |value: &Option<String>| intern(value.as_deref().unwrap_or(""));
+        TeamTags {
+            left_name: tag(&self.left.name),
+            right_name: tag(&self.right.name),
+            left_color: tag(&self.left.color),
+            right_color: tag(&self.right.color),
+        }
+    }
+}
+
+/// Recolor players and baskets of sides with a custom color. Runs after
+/// apply_palette_colors, and again for newly spawned players.
+#[allow(clippy::type_complexity)]
+pub fn apply_team_colors(
+    teams: Option<Res<TeamIdentity>>,
+    palette: Option<Res<CurrentPalette>>,
+    added: Query<(), Added<Player>>,
+    mut players: Query<(&mut Sprite, &Team), (With<Player>, Without<Basket>)>,
+    mut baskets: Query<(&mut Sprite, &Basket), Without<Player>>,
+) {
+    let Some(teams) = teams else {
+        return;
+    };
+    let (left, right) = (teams.left.color(), teams.right.color());
+    if left.is_none() && right.is_none() {
+        return;
+    }
+    let palette_changed = palette.is_some_and(|p| p.is_changed());
+    if !teams.is_changed() && !palette_changed && added.is_empty() {
+        return;
+    }
+
+    for (mut sprite, team) in &mut players {
+        let color = match team {
+            Team::Left => left,
+            Team::Right => right,
+        };
+        if let Some(color) = color {
+            sprite.color = color;
+        }
+    }
+    for (mut sprite, basket) in &mut baskets {
+        let color = match basket {
+            Basket::Left => left,
+            Basket::Right => right,
+        };
+        if let Some(color) = color {
+            sprite.color = color;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{GameEvent, parse_event, serialize_event};
+
+    #[test]
+    fn test_parse_team_spec_and_tags() {
+        let info = TeamInfo::parse("name=Reds, color=#d03030").unwrap();
+        assert_eq!(info.name.as_deref(), Some("Reds"));
+        assert_eq!(info.color(), Some(Color::srgb_u8(0xd0, 0x30, 0x30)));
+        assert!(TeamInfo::parse("color=#d030").is_err());
+        assert!(TeamInfo::parse("name=A|B").is_err());
+        assert!(TeamInfo::parse("logo=x").is_err());
+
+        let args: Vec<String> = ["game", "--team-right", "name=Blues"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let teams = TeamIdentity::from_args(&args).unwrap();
+        assert!(teams.has_names());
+        assert_eq!(teams.display_name(Team::Left), "Left");
+        assert_eq!(teams.display_name(Team::Right), "Blues");
+        let tags = teams.tags();
+        assert_eq!(&*tags.right_name, "Blues");
+        assert!(tags.left_name.is_empty() && tags.right_color.is_empty());
+        assert!(TeamIdentity::default().tags().is_empty());
+
+        // Tags survive the event log, and old MatchStart lines parse without them
+        let event = GameEvent::MatchStart {
+            level: 1,
+            level_name: intern("Open Floor"),
+            left_profile: intern("Player"),
+            right_profile: intern("Balanced"),
+            seed: 7,
+            teams: tags.clone(),
+        };
+        let (_, parsed) = parse_event(&serialize_event(0, &event)).unwrap();
+        assert!(matches!(parsed, GameEvent::MatchStart { teams, .. } if teams == tags));
+        let (_, old) = parse_event("T:00000|MS|1|Open Floor|Balanced|Balanced|7").unwrap();
+        assert!(matches!(old, GameEvent::MatchStart { teams, .. } if teams.is_empty()));
+    }
+}
@@ -47,6 +47,10 @@ impl CapturedEvent {
                 ("StealOutOfRange".to_string(), Some(attacker))
             }
             GameEvent::Goal { player, .. } => ("Goal".to_string(), Some(player)),
+            GameEvent::StealDenied { attacker, .. } => ("StealDenied".to_string(), Some(attacker)),
+            GameEvent::ContestedPickup { winner, .. } => {
+                ("ContestedPickup".to_string(), Some(winner))
+            }
             _ => return None,
         };
 
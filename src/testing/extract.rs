@@ -0,0 +1,455 @@
+//! Scenario extraction - turn a moment from a logged match into a scenario test
+//!
+//! Builds a `TestDefinition` from a replay and a tick range: entity states from the
+//! first tick, scripted inputs from logged ControllerInput events (or the sampled
+//! Input events simulations log), and the ball/steal/goal events that followed as the
+//! expected sequence. The result is written as TOML next to the hand-written scenarios.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+
+use super::parser::{
+    EntityDef, ExpectedEvent, FrameInput, InputSnapshot, StateAssertion, TestDefinition,
+    TestExpectations, TestSetup,
+};
+use crate::constants::LEVELS_FILE;
+use crate::events::{GameEvent, PlayerId};
+use crate::replay::{ReplayData, TickFrame};
+
+/// Directory extracted scenarios are written to by default
+pub const EXTRACTED_SCENARIOS_DIR: &str = "tests/scenarios/extracted";
+
+/// Expected events may land this many frames either side of the logged frame
+const EVENT_FRAME_SLACK: u64 = 15;
+
+/// Build a scenario from ticks `from_tick..=to_tick` of a logged match
+pub fn extract_scenario(
+    replay: &ReplayData,
+    match_id: i64,
+    from_tick: u64,
+    to_tick: u64,
+) -> Result<TestDefinition, String> {
+    if to_tick <= from_tick {
+        return Err(format!("Empty tick range {}-{}", from_tick, to_tick));
+    }
+    let start = replay
+        .ticks
+        .iter()
+        .find(|t| t.frame >= from_tick)
+        .ok_or_else(|| format!("Match {} has no ticks at or after {}", match_id, from_tick))?;
+    let end = replay
+        .ticks
+        .iter()
+        .take_while(|t| t.frame <= to_tick)
+        .last()
+        .filter(|t| t.time_ms >= start.time_ms)
+        .unwrap_or(start);
+    let (start_ms, end_ms) = (start.time_ms, end.time_ms);
+    let to_frame = |time_ms: u32| ((time_ms - start_ms) as u64 * 60 + 500) / 1000;
+    let in_range = |time_ms: u32| time_ms >= start_ms && time_ms <= end_ms;
+
+    let info = &replay.match_info;
+    Ok(TestDefinition {
+        name: format!("match_{}_{}_{}", match_id, from_tick, to_tick),
+        description: Some(format!(
+            "Extracted from match {} ({}, {} vs {}), ticks {}-{}",
+            match_id, info.level_name, info.left_profile, info.right_profile, from_tick, to_tick
+        )),
+        setup: TestSetup {
+            level: info.level_name.clone(),
+            levels_file: Some(LEVELS_FILE.to_string()),
+            seed: Some(info.seed),
+            entities: initial_entities(start),
+        },
+        input: scripted_inputs(replay, &in_range, &to_frame),
+        expect: TestExpectations {
+            sequence: replay
+                .events
+                .iter()
+                .filter(|e| in_range(e.time_ms))
+                .filter_map(|e| {
+                    let (event, player) = expected_event_name(&e.event)?;
+                    let frame = to_frame(e.time_ms);
+                    Some(ExpectedEvent {
+                        event: event.to_string(),
+                        player: Some(player_entity_id(player).to_string()),
+                        frame_min: Some(frame.saturating_sub(EVENT_FRAME_SLACK)),
+                        frame_max: Some(frame + EVENT_FRAME_SLACK),
+                        tolerance: EVENT_FRAME_SLACK,
+                    })
+                })
+                .collect(),
+            state: vec![StateAssertion {
+                after_frame: to_frame(end.time_ms),
+                checks: vec![format!("ball.state = {}", ball_state_name(end.ball_state))],
+            }],
+        },
+    })
+}
+
+/// Players (plus the ball when nobody holds it) as they were at `tick`
+fn initial_entities(tick: &TickFrame) -> Vec<EntityDef> {
+    // Held balls carry no owner in ticks; the closer player is the holder
+    let held = tick.ball_state == 'H';
+    let left_holds =
+        tick.left_pos.distance(tick.ball_pos) <= tick.right_pos.distance(tick.ball_pos);
+
+    let mut entities = Vec::new();
+    for (id, pos, vel, holding) in [
+        ("left", tick.left_pos, tick.left_vel, held && left_holds),
+        ("right", tick.right_pos, tick.right_vel, held && !left_holds),
+    ] {
+        let default_facing = if id == "left" { 1.0 } else { -1.0 };
+        entities.push(EntityDef::Player {
+            id: id.to_string(),
+            team: id.to_string(),
+            x: pos.x,
+            y: pos.y,
+            velocity_x: vel.x,
+            velocity_y: vel.y,
+            facing: if vel.x.abs() > 1.0 {
+                vel.x.signum()
+            } else {
+                default_facing
+            },
+            holding_ball: holding,
+        });
+    }
+    if !held {
+        entities.push(EntityDef::Ball {
+            x: tick.ball_pos.x,
+            y: tick.ball_pos.y,
+            velocity_x: tick.ball_vel.x,
+            velocity_y: tick.ball_vel.y,
+        });
+    }
+    entities
+}
+
+/// Inputs per frame, keeping only frames where a player's input changed or a button was pressed.
+/// Prefers ControllerInput (every frame, exact presses); falls back to sampled Input events.
+fn scripted_inputs(
+    replay: &ReplayData,
+    in_range: &impl Fn(u32) -> bool,
+    to_frame: &impl Fn(u32) -> u64,
+) -> Vec<FrameInput> {
+    let has_controller_inputs = replay
+        .events
+        .iter()
+        .any(|e| in_range(e.time_ms) && matches!(e.event, GameEvent::ControllerInput { .. }));
+
+    let mut frames: BTreeMap<u64, HashMap<String, InputSnapshot>> = BTreeMap::new();
+    let mut last_sent: HashMap<&str, InputSnapshot> = HashMap::new();
+    let mut jump_held: HashMap<&str, bool> = HashMap::new();
+
+    for timed in replay.events.iter().filter(|e| in_range(e.time_ms)) {
+        let (player, snapshot) = match timed.event {
+            GameEvent::ControllerInput {
+                player,
+                move_x,
+                jump,
+                jump_pressed,
+                throw,
+                pickup,
+                ..
+            } if has_controller_inputs => (
+                player,
+                input_snapshot(move_x, jump, jump_pressed, throw, pickup),
+            ),
+            GameEvent::Input {
+                player,
+                move_x,
+                jump,
+                throw,
+                pickup,
+            } if !has_controller_inputs => {
+                let was_held = jump_held
+                    .insert(player_entity_id(player), jump)
+                    .unwrap_or(false);
+                (
+                    player,
+                    input_snapshot(move_x, jump, jump && !was_held, throw, pickup),
+                )
+            }
+            _ => continue,
+        };
+
+        let id = player_entity_id(player);
+        let pressed = snapshot.jump == Some(true) || snapshot.pickup == Some(true);
+        if !pressed && last_sent.get(id) == Some(&snapshot) {
+            continue;
+        }
+        last_sent.insert(id, snapshot.clone());
+
+        let entry = frames
+            .entry(to_frame(timed.time_ms))
+            .or_default()
+            .entry(id.to_string())
+            .or_default();
+        // Several events can land on one frame; keep any press among them
+        let jump = if entry.jump == Some(true) {
+            entry.jump
+        } else {
+            snapshot.jump
+        };
+        let pickup = entry.pickup.or(snapshot.pickup);
+        *entry = InputSnapshot {
+            jump,
+            pickup,
+            ..snapshot
+        };
+    }
+
+    frames
+        .into_iter()
+        .map(|(frame, inputs)| FrameInput { frame, inputs })
+        .collect()
+}
+
+/// Scripted input for one logged sample (jump: press, release, or keep holding)
+fn input_snapshot(
+    move_x: f32,
+    jump: bool,
+    jump_pressed: bool,
+    throw: bool,
+    pickup: bool,
+) -> InputSnapshot {
+    InputSnapshot {
+        move_x: Some(move_x),
+        jump: if jump_pressed {
+            Some(true)
+        } else if !jump {
+            Some(false)
+        } else {
+            None
+        },
+        pickup: pickup.then_some(true),
+        throw_held: Some(throw),
+        ..Default::default()
+    }
+}
+
+/// Event name and player for events the scenario runner can check
+fn expected_event_name(event: &GameEvent) -> Option<(&'static str, PlayerId)> {
+    Some(match *event {
+        GameEvent::Pickup { player } => ("Pickup", player),
+        GameEvent::Drop { player } => ("Drop", player),
+        GameEvent::ShotStart { player, .. } => ("ShotStart", player),
+        GameEvent::ShotRelease { player, .. } => ("ShotRelease", player),
+        GameEvent::StealAttempt { attacker } => ("StealAttempt", attacker),
+        GameEvent::StealSuccess { attacker } => ("StealSuccess", attacker),
+        GameEvent::StealFail { attacker } => ("StealFail", attacker),
+        GameEvent::StealOutOfRange { attacker } => ("StealOutOfRange", attacker),
+        GameEvent::Goal { player, .. } => ("Goal", player),
+        _ => return None,
+    })
+}
+
+fn player_entity_id(player: PlayerId) -> &'static str {
+    match player {
+        PlayerId::L => "left",
+        PlayerId::R => "right",
+    }
+}
+
+fn ball_state_name(state: char) -> &'static str {
+    match state {
+        'H' => "Held",
+        'I' => "InFlight",
+        _ => "Free",
+    }
+}
+
+/// Write a scenario in the same layout as the hand-written TOML files
+pub fn to_toml(test: &TestDefinition) -> String {
+    let mut out = String::new();
+    if let Some(description) = &test.description {
+        let _ = writeln!(out, "# {}", description);
+        let _ = writeln!(
+            out,
+            "# Expected events allow +/-{} frames; tighten them once the bug is understood.\n",
+            EVENT_FRAME_SLACK
+        );
+    }
+    let _ = writeln!(out, "name = {:?}", test.name);
+    if let Some(description) = &test.description {
+        let _ = writeln!(out, "description = {:?}", description);
+    }
+
+    let setup = &test.setup;
+    let _ = writeln!(out, "\n[setup]\nlevel = {:?}", setup.level);
+    if let Some(levels_file) = &setup.levels_file {
+        let _ = writeln!(out, "levels_file = {:?}", levels_file);
+    }
+    if let Some(seed) = setup.seed {
+        let _ = writeln!(out, "seed = {}", seed);
+    }
+    for entity in &setup.entities {
+        let _ = writeln!(out, "\n[[setup.entities]]");
+        match entity {
+            EntityDef::Player {
+                id,
+                team,
+                x,
+                y,
+                velocity_x,
+                velocity_y,
+                facing,
+                holding_ball,
+            } => {
+                let _ = writeln!(out, "type = \"player\"\nid = {:?}\nteam = {:?}", id, team);
+                let _ = writeln!(out, "x = {:.1}\ny = {:.1}", x, y);
+                let _ = writeln!(
+                    out,
+                    "velocity_x = {:.1}\nvelocity_y = {:.1}\nfacing = {:.1}",
+                    velocity_x, velocity_y, facing
+                );
+                if *holding_ball {
+                    let _ = writeln!(out, "holding_ball = true");
+                }
+            }
+            EntityDef::Ball {
+                x,
+                y,
+                velocity_x,
+                velocity_y,
+            } => {
+                let _ = writeln!(out, "type = \"ball\"\nx = {:.1}\ny = {:.1}", x, y);
+                let _ = writeln!(
+                    out,
+                    "velocity_x = {:.1}\nvelocity_y = {:.1}",
+                    velocity_x, velocity_y
+                );
+            }
+        }
+    }
+
+    for frame_input in &test.input {
+        let _ = writeln!(out, "\n[[input]]\nframe = {}", frame_input.frame);
+        let mut ids: Vec<_> = frame_input.inputs.keys().collect();
+        ids.sort();
+        for id in ids {
+            let input = &frame_input.inputs[id];
+            let mut fields = Vec::new();
+            if let Some(move_x) = input.move_x {
+                fields.push(format!("move_x = {:.2}", move_x));
+            }
+            if let Some(jump) = input.jump {
+                fields.push(format!("jump = {}", jump));
+            }
+            if let Some(pickup) = input.pickup {
+                fields.push(format!("pickup = {}", pickup));
+            }
+            if let Some(throw_held) = input.throw_held {
+                fields.push(format!("throw_held = {}", throw_held));
+            }
+            let _ = writeln!(out, "{} = {{ {} }}", id, fields.join(", "));
+        }
+    }
+
+    if test.expect.sequence.is_empty() && test.expect.state.is_empty() {
+        let _ = writeln!(out, "\n[expect]");
+    }
+    for expected in &test.expect.sequence {
+        let _ = writeln!(out, "\n[[expect.sequence]]\nevent = {:?}", expected.event);
+        if let Some(player) = &expected.player {
+            let _ = writeln!(out, "player = {:?}", player);
+        }
+        if let Some(frame_min) = expected.frame_min {
+            let _ = writeln!(out, "frame_min = {}", frame_min);
+        }
+        if let Some(frame_max) = expected.frame_max {
+            let _ = writeln!(out, "frame_max = {}", frame_max);
+        }
+    }
+    for state in &test.expect.state {
+        let _ = writeln!(
+            out,
+            "\n[[expect.state]]\nafter_frame = {}\nchecks = [",
+            state.after_frame
+        );
+        for check in &state.checks {
+            let _ = writeln!(out, "    {:?},", check);
+        }
+        let _ = writeln!(out, "]");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::ControllerSource;
+    use crate::replay::{MatchInfo, TimedEvent};
+    use bevy::prelude::Vec2;
+
+    fn tick(frame: u64, ball_state: char) -> TickFrame {
+        TickFrame {
+            time_ms: frame as u32 * 50,
+            frame,
+            left_pos: Vec2::new(-200.0, -398.0),
+            left_vel: Vec2::ZERO,
+            right_pos: Vec2::new(200.0, -398.0),
+            right_vel: Vec2::new(-150.0, 0.0),
+            ball_pos: Vec2::new(-190.0, -390.0),
+            ball_vel: Vec2::ZERO,
+            ball_state,
+        }
+    }
+
+    #[test]
+    fn test_extracted_scenario_round_trips_through_toml() {
+        let controller = |time_ms, move_x, pickup| TimedEvent {
+            time_ms,
+            event: GameEvent::ControllerInput {
+                player: PlayerId::R,
+                source: ControllerSource::Ai,
+                move_x,
+                jump: false,
+                jump_pressed: false,
+                throw: false,
+                throw_released: false,
+                pickup,
+            },
+        };
+        let replay = ReplayData {
+            match_info: MatchInfo {
+                level_name: "Arena".to_string(),
+                ..Default::default()
+            },
+            ticks: (10..=20).map(|f| tick(f, 'H')).collect(),
+            events: vec![
+                controller(500, -1.0, false),
+                controller(550, -1.0, false), // unchanged, dropped
+                controller(600, 0.0, true),
+                TimedEvent {
+                    time_ms: 600,
+                    event: GameEvent::StealAttempt {
+                        attacker: PlayerId::R,
+                    },
+                },
+            ],
+            ..Default::default()
+        };
+
+        let test = extract_scenario(&replay, 7, 10, 20).unwrap();
+        let parsed: TestDefinition = toml::from_str(&to_toml(&test)).unwrap();
+
+        assert_eq!(parsed.setup.levels_file.as_deref(), Some(LEVELS_FILE));
+        // Ball is held by the closer (left) player, so no free ball entity
+        assert_eq!(parsed.setup.entities.len(), 2);
+        assert!(matches!(
+            parsed.setup.entities[0],
+            EntityDef::Player {
+                holding_ball: true,
+                ..
+            }
+        ));
+        let frames: Vec<u64> = parsed.input.iter().map(|f| f.frame).collect();
+        assert_eq!(frames, vec![0, 6]);
+        assert_eq!(parsed.input[1].inputs["right"].pickup, Some(true));
+        assert_eq!(parsed.expect.sequence[0].event, "StealAttempt");
+        assert_eq!(parsed.expect.sequence[0].player.as_deref(), Some("right"));
+        assert_eq!(parsed.expect.state[0].after_frame, 30);
+    }
+}
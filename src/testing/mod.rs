@@ -4,11 +4,13 @@
 //! headless simulation to verify game mechanics.
 
 pub mod assertions;
+pub mod extract;
 pub mod input;
 pub mod parser;
 pub mod runner;
 
 pub use assertions::{AssertionError, check_sequence, check_state};
+pub use extract::{EXTRACTED_SCENARIOS_DIR, extract_scenario};
 pub use input::{ScriptedInputs, TestEntityId};
 pub use parser::{
     EntityDef, ExpectedEvent, FrameInput, InputSnapshot, StateAssertion, TestDefinition,
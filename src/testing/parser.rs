@@ -20,6 +20,8 @@ pub struct TestDefinition {
 #[derive(Debug, Deserialize)]
 pub struct TestSetup {
     pub level: String,
+    /// Level file to look `level` up in (None = test levels)
+    pub levels_file: Option<String>,
     pub seed: Option<u64>,
     #[serde(default)]
     pub entities: Vec<EntityDef>,
@@ -35,6 +37,10 @@ pub enum EntityDef {
         team: String,
         x: f32,
         y: f32,
+        #[serde(default)]
+        velocity_x: f32,
+        #[serde(default)]
+        velocity_y: f32,
         #[serde(default = "default_facing")]
         facing: f32,
         #[serde(default)]
@@ -64,7 +70,7 @@ pub struct FrameInput {
 }
 
 /// Snapshot of input state for one entity
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
 pub struct InputSnapshot {
     #[serde(default)]
     pub move_x: Option<f32>,
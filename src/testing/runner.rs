@@ -11,10 +11,11 @@ use crate::ball::{
     CurrentPalette, Velocity, apply_velocity, ball_collisions, ball_follow_holder, ball_gravity,
     ball_player_collision, ball_spin, ball_state_update, pickup_ball,
 };
+use crate::config::GameplayConfig;
 use crate::constants::*;
 use crate::debug_logging::DebugLogConfig;
-use crate::events::EventBus;
-use crate::levels::LevelDatabase;
+use crate::events::{EventBus, GameEvent};
+use crate::levels::{LevelDatabase, apply_wind_forces, level_feature_traversal, move_baskets};
 use crate::palettes::PaletteDatabase;
 use crate::player::{
     CoyoteTimer, Facing, Grounded, HoldingBall, JumpState, Player, TargetBasket, Team,
@@ -75,8 +76,13 @@ struct StateAssertionResult {
 
 /// Run a single test and return the result
 pub fn run_test(test: &TestDefinition, debug_config: DebugLogConfig) -> TestResult {
-    // Load test levels
-    let level_db = LevelDatabase::load_from_file(TEST_LEVELS_FILE);
+    // Load test levels (extracted scenarios point at the game's level file)
+    let level_db = LevelDatabase::load_from_file(
+        test.setup
+            .levels_file
+            .as_deref()
+            .unwrap_or(TEST_LEVELS_FILE),
+    );
 
     // Find the level by name
     let level_name = &test.setup.level;
@@ -142,6 +148,7 @@ pub fn run_test(test: &TestDefinition, debug_config: DebugLogConfig) -> TestResu
     app.init_resource::<StealTracker>();
     app.init_resource::<PhysicsTweaks>();
     let _ = tuning::apply_global_tuning(&mut app.world_mut().resource_mut::<PhysicsTweaks>());
+    app.add_plugins(GameplayConfig::load("test"));
     app.init_resource::<LastShotInfo>();
     app.insert_resource(CurrentPalette(0));
     app.init_resource::<PaletteDatabase>();
@@ -199,9 +206,12 @@ pub fn run_test(test: &TestDefinition, debug_config: DebugLogConfig) -> TestResu
             apply_gravity,
             ball_gravity,
             ball_spin,
+            apply_wind_forces,
+            move_baskets,
             apply_velocity,
             check_collisions,
             ball_collisions,
+            level_feature_traversal,
             ball_state_update,
             ball_player_collision,
             ball_follow_holder,
@@ -303,6 +313,7 @@ fn test_setup(
 
     // Level platforms and baskets
     if let Some(level) = level_db.get_by_id(&current_level.0) {
+        let arena = level.arena();
         for platform in &level.platforms {
             match platform {
                 crate::levels::PlatformDef::Mirror { x, y, width } => {
@@ -311,7 +322,7 @@ fn test_setup(
                             custom_size: Some(Vec2::new(*width, 20.0)),
                             ..default()
                         },
-                        Transform::from_xyz(-x, ARENA_FLOOR_Y + y, 0.0),
+                        Transform::from_xyz(-x, arena.floor_y + y, 0.0),
                         Platform,
                         Collider,
                     ));
@@ -320,7 +331,7 @@ fn test_setup(
                             custom_size: Some(Vec2::new(*width, 20.0)),
                             ..default()
                         },
-                        Transform::from_xyz(*x, ARENA_FLOOR_Y + y, 0.0),
+                        Transform::from_xyz(*x, arena.floor_y + y, 0.0),
                         Platform,
                         Collider,
                     ));
@@ -331,20 +342,24 @@ fn test_setup(
                             custom_size: Some(Vec2::new(*width, 20.0)),
                             ..default()
                         },
-                        Transform::from_xyz(0.0, ARENA_FLOOR_Y + y, 0.0),
+                        Transform::from_xyz(0.0, arena.floor_y + y, 0.0),
                         Platform,
                         Collider,
                     ));
                 }
+                feature => {
+                    crate::levels::spawn_level_feature(&mut commands, feature, arena.floor_y)
+                }
             }
         }
 
         // Baskets with rims using shared function
-        let basket_y = ARENA_FLOOR_Y + level.basket_height;
+        let basket_y = arena.floor_y + level.basket_height;
         let basket_color = Color::srgb(0.5, 0.5, 0.5);
         let rim_color = Color::srgb(0.4, 0.4, 0.4);
         spawn_baskets(
             &mut commands,
+            &arena,
             basket_y,
             level.basket_push_in,
             basket_color,
@@ -364,6 +379,8 @@ fn test_setup(
                 team,
                 x,
                 y,
+                velocity_x,
+                velocity_y,
                 facing,
                 holding_ball,
             } => {
@@ -386,7 +403,7 @@ fn test_setup(
                             ..default()
                         },
                         Player,
-                        Velocity::default(),
+                        Velocity(Vec2::new(*velocity_x, *velocity_y)),
                         Grounded(false),
                         CoyoteTimer::default(),
                         JumpState::default(),
@@ -508,11 +525,26 @@ fn event_capture(
     mut capture: ResMut<EventCapture>,
     score: Res<Score>,
     steal_contest: Res<StealContest>,
+    mut event_bus: ResMut<EventBus>,
     players: Query<(Entity, &ChargingShot, &StealCooldown, Option<&HoldingBall>), With<Player>>,
     balls: Query<&BallState, With<Ball>>,
 ) {
     let frame = control.current_frame;
 
+    // Events only gameplay systems know about (pickup contests, steal denials); the rest are
+    // inferred from state changes below
+    for (_, event) in event_bus.export_events() {
+        if matches!(
+            event,
+            GameEvent::ContestedPickup { .. } | GameEvent::StealDenied { .. }
+        )
+            && let Some(captured) =
+                CapturedEvent::from_game_event(frame, &event, &capture.entity_map)
+        {
+            capture.events.push(captured);
+        }
+    }
+
     // Detect score changes (Goal events)
     if score.left > capture.prev_score_left {
         capture.events.push(CapturedEvent {
@@ -682,6 +714,7 @@ fn extract_world_state(world: &mut World) -> WorldState {
                 BallState::Free => "Free".to_string(),
                 BallState::Held(_) => "Held".to_string(),
                 BallState::InFlight { .. } => "InFlight".to_string(),
+                BallState::Passed { .. } => "Passed".to_string(),
             },
         });
 
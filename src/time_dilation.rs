@@ -0,0 +1,198 @@
+//! Time dilation debug controls - slow motion and fixed-tick stepping
+//!
+//! F7 cycles game speed 1x -> 0.5x -> 0.25x. F8 freezes gameplay; while frozen,
+//! F9 advances FixedUpdate by exactly one tick. Rendering and UI keep running.
+//! Everything goes through `Time<Virtual>`, so every gameplay system (Update
+//! timers and FixedUpdate physics alike) sees the same scaled clock.
+
+use bevy::app::FixedMain;
+use bevy::prelude::*;
+
+use crate::constants::TEXT_ACCENT;
+
+/// Game speeds F7 cycles through
+pub const TIME_SCALES: [f32; 3] = [1.0, 0.5, 0.25];
+
+/// Current time scale and frame-step state
+#[derive(Resource, Debug, Default)]
+pub struct TimeDilation {
+    /// Index into TIME_SCALES
+    pub scale_index: usize,
+    /// Gameplay paused; ticks only advance on request
+    pub frozen: bool,
+    /// Fixed ticks requested while frozen, run on the next frame
+    pub pending_steps: u32,
+}
+
+impl TimeDilation {
+    pub fn scale(&self) -> f32 {
+        TIME_SCALES[self.scale_index % TIME_SCALES.len()]
+    }
+
+    pub fn cycle_scale(&mut self) {
+        self.scale_index = (self.scale_index + 1) % TIME_SCALES.len();
+    }
+
+    pub fn toggle_frozen(&mut self) {
+        self.frozen = !self.frozen;
+        self.pending_steps = 0;
+    }
+
+    /// Queue one fixed tick (ignored unless frozen)
+    pub fn request_step(&mut self) {
+        if self.frozen {
+            self.pending_steps += 1;
+        }
+    }
+
+    /// Whether the game runs at normal speed
+    pub fn is_normal(&self) -> bool {
+        !self.frozen && self.scale() == 1.0
+    }
+
+    /// Short status for the on-screen indicator ("" at normal speed)
+    pub fn status_string(&self) -> String {
+        if self.frozen {
+            "FROZEN (F9: step, F8: resume)".to_string()
+        } else if self.is_normal() {
+            String::new()
+        } else {
+            format!("SLOW-MO {}x (F7)", self.scale())
+        }
+    }
+}
+
+/// On-screen time dilation indicator
+#[derive(Component)]
+pub struct TimeDilationText;
+
+pub fn spawn_time_dilation_text(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextFont {
+            font_size: 16.0,
+            ..default()
+        },
+        TextColor(TEXT_ACCENT),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(8.0),
+            left: Val::Percent(45.0),
+            ..default()
+        },
+        TimeDilationText,
+    ));
+}
+
+/// F7 cycles speed, F8 freezes/resumes, F9 steps one tick while frozen
+pub fn time_dilation_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut dilation: ResMut<TimeDilation>,
+) {
+    if keyboard.just_pressed(KeyCode::F7) {
+        dilation.cycle_scale();
+        info!("Time scale: {}x", dilation.scale());
+    }
+    if keyboard.just_pressed(KeyCode::F8) {
+        dilation.toggle_frozen();
+        info!(
+            "Gameplay {}",
+            if dilation.frozen { "frozen" } else { "resumed" }
+        );
+    }
+    if keyboard.just_pressed(KeyCode::F9) {
+        dilation.request_step();
+    }
+}
+
+/// Push the scale and freeze state into `Time<Virtual>`, and refresh the indicator
+pub fn apply_time_dilation(
+    dilation: Res<TimeDilation>,
+    mut time: ResMut<Time<Virtual>>,
+    mut texts: Query<&mut Text, With<TimeDilationText>>,
+) {
+    if !dilation.is_changed() {
+        return;
+    }
+    time.set_relative_speed(dilation.scale());
+    if dilation.frozen {
+        time.pause();
+    } else {
+        time.unpause();
+    }
+    for mut text in &mut texts {
+        **text = dilation.status_string();
+    }
+}
+
+/// Run requested fixed ticks while virtual time is paused.
+/// Mirrors one iteration of Bevy's fixed loop: advance `Time<Fixed>` by a
+/// timestep and expose it as the generic `Time` for the duration of the run.
+pub fn step_fixed_update(world: &mut World) {
+    let steps = std::mem::take(&mut world.resource_mut::<TimeDilation>().pending_steps);
+    for _ in 0..steps {
+        let timestep = world.resource::<Time<Fixed>>().timestep();
+        world.resource_mut::<Time<Fixed>>().advance_by(timestep);
+        let fixed = world.resource::<Time<Fixed>>().as_generic();
+        let previous = std::mem::replace(&mut *world.resource_mut::<Time>(), fixed);
+        world.run_schedule(FixedMain);
+        *world.resource_mut::<Time>() = previous;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scale_cycle_and_stepping() {
+        let mut dilation = TimeDilation::default();
+        assert!(dilation.is_normal());
+        assert_eq!(dilation.status_string(), "");
+
+        dilation.cycle_scale();
+        assert_eq!(dilation.scale(), 0.5);
+        dilation.cycle_scale();
+        assert_eq!(dilation.scale(), 0.25);
+        dilation.cycle_scale();
+        assert_eq!(dilation.scale(), 1.0);
+
+        // Steps only queue while frozen, and resuming drops leftovers
+        dilation.request_step();
+        assert_eq!(dilation.pending_steps, 0);
+        dilation.toggle_frozen();
+        dilation.request_step();
+        dilation.request_step();
+        assert_eq!(dilation.pending_steps, 2);
+        assert!(dilation.status_string().starts_with("FROZEN"));
+        dilation.toggle_frozen();
+        assert_eq!(dilation.pending_steps, 0);
+    }
+
+    #[test]
+    fn test_step_runs_one_fixed_tick() {
+        #[derive(Resource, Default)]
+        struct Ticks(u32);
+
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        world.init_resource::<Time<Fixed>>();
+        world.init_resource::<Ticks>();
+        world.insert_resource(TimeDilation {
+            frozen: true,
+            pending_steps: 1,
+            ..default()
+        });
+        let mut schedule = Schedule::new(FixedMain);
+        schedule.add_systems(|mut ticks: ResMut<Ticks>| ticks.0 += 1);
+        world.add_schedule(schedule);
+
+        step_fixed_update(&mut world);
+        assert_eq!(world.resource::<Ticks>().0, 1);
+        assert_eq!(world.resource::<TimeDilation>().pending_steps, 0);
+        assert_eq!(
+            world.resource::<Time<Fixed>>().elapsed(),
+            world.resource::<Time<Fixed>>().timestep()
+        );
+    }
+}
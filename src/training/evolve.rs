@@ -0,0 +1,567 @@
+//! Profile evolution - a genetic algorithm over AI profile parameters
+//!
+//! Generation 0 is mutated copies of the seed profiles. Each generation every
+//! candidate plays `matches` headless matches (in parallel, alternating sides)
+//! against the opponent pool and is scored on win rate, with goal difference
+//! as a tiebreak. The best `elite` carry over unchanged. The rest of the next
+//! generation is bred from tournament-selected parents: uniform crossover, then
+//! per-gene mutation clamped to the gene's range. The winners are appended to
+//! `ai_profiles.txt` with their lineage (parents, seed profiles, fitness) in
+//! comments above each profile. `ballgame profiles evolve` runs it.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fs::OpenOptions;
+use std::io::Write;
+
+use crate::ai::{AiProfile, AiProfileDatabase, generate_uuid_from_name};
+use crate::levels::LevelDatabase;
+use crate::simulation::parallel::{MatchConfig, run_matches_parallel};
+use crate::simulation::{MatchResult, SimConfig};
+
+/// Profile parameters that evolve, with the range each is kept in.
+/// `shoot_range_skill` (0 = off) and `difficulty` (injected mistakes) are left alone.
+const GENES: [(&str, f32, f32); 16] = [
+    ("position_tolerance", 5.0, 60.0),
+    ("shoot_range", 150.0, 800.0),
+    ("charge_min", 0.1, 1.0),
+    ("charge_max", 0.3, 1.6),
+    ("steal_range", 40.0, 200.0),
+    ("defense_offset", 100.0, 700.0),
+    ("min_shot_quality", 0.0, 0.8),
+    ("pressure_distance", 40.0, 300.0),
+    ("aggression", 0.0, 1.0),
+    ("defensive_iq", 0.0, 1.0),
+    ("steal_reaction_time", 0.05, 0.6),
+    ("button_presses_per_sec", 4.0, 20.0),
+    ("position_patience", -0.5, 2.0),
+    ("seek_threshold", 0.0, 0.5),
+    ("trailing_risk", 0.0, 1.0),
+    ("leading_caution", 0.0, 1.0),
+];
+
+/// Candidates drawn per tournament selection
+const TOURNAMENT_SIZE: usize = 3;
+/// Fitness per goal of average margin (a 1-goal margin is worth 5% win rate)
+const GOAL_DIFF_WEIGHT: f32 = 0.05;
+
+fn gene<'a>(profile: &'a mut AiProfile, name: &str) -> &'a mut f32 {
+    match name {
+        "position_tolerance" => &mut profile.position_tolerance,
+        "shoot_range" => &mut profile.shoot_range,
+        "charge_min" => &mut profile.charge_min,
+        "charge_max" => &mut profile.charge_max,
+        "steal_range" => &mut profile.steal_range,
+        "defense_offset" => &mut profile.defense_offset,
+        "min_shot_quality" => &mut profile.min_shot_quality,
+        "pressure_distance" => &mut profile.pressure_distance,
+        "aggression" => &mut profile.aggression,
+        "defensive_iq" => &mut profile.defensive_iq,
+        "steal_reaction_time" => &mut profile.steal_reaction_time,
+        "button_presses_per_sec" => &mut profile.button_presses_per_sec,
+        "position_patience" => &mut profile.position_patience,
+        "seek_threshold" => &mut profile.seek_threshold,
+        "trailing_risk" => &mut profile.trailing_risk,
+        "leading_caution" => &mut profile.leading_caution,
+        _ => unreachable!("unknown gene {}", name),
+    }
+}
+
+/// Evolution run settings
+#[derive(Debug, Clone)]
+pub struct EvolveConfig {
+    pub generations: u32,
+    pub population: usize,
+    /// Matches each candidate plays per generation
+    pub matches: u32,
+    /// Best candidates carried into the next generation unchanged
+    pub elite: usize,
+    /// Chance each gene of a child mutates (0-1)
+    pub mutation_rate: f32,
+    /// Largest mutation step, as a fraction of the gene's range
+    pub mutation_scale: f32,
+    pub seed: u64,
+    /// Seed profiles for generation 0 (empty = every profile)
+    pub parents: Vec<String>,
+    /// Opponent pool, played round-robin (empty = the seed profiles)
+    pub opponents: Vec<String>,
+    /// Evolved profile names are `<tag>_g<generation>_<n>`
+    pub tag: String,
+}
+
+impl Default for EvolveConfig {
+    fn default() -> Self {
+        Self {
+            generations: 8,
+            population: 12,
+            matches: 4,
+            elite: 2,
+            mutation_rate: 0.3,
+            mutation_scale: 0.15,
+            seed: 42,
+            parents: Vec::new(),
+            opponents: Vec::new(),
+            tag: "evo".to_string(),
+        }
+    }
+}
+
+/// A candidate's results for one generation
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Fitness {
+    /// Non-degenerate matches played
+    pub matches: u32,
+    pub wins: u32,
+    /// Goals scored minus conceded, summed over the matches
+    pub goal_diff: i32,
+}
+
+impl Fitness {
+    /// Tally `profile`'s matches (either side); degenerate matches don't count
+    pub fn from_results<'a>(
+        profile: &str,
+        results: impl IntoIterator<Item = &'a MatchResult>,
+    ) -> Self {
+        let mut fitness = Fitness::default();
+        for result in results {
+            if result.degenerate.is_some() {
+                continue;
+            }
+            let (side, scored, conceded) = if result.left_profile == profile {
+                ("left", result.score_left, result.score_right)
+            } else if result.right_profile == profile {
+                ("right", result.score_right, result.score_left)
+            } else {
+                continue;
+            };
+            fitness.matches += 1;
+            if result.winner == side {
+                fitness.wins += 1;
+            }
+            fitness.goal_diff += scored as i32 - conceded as i32;
+        }
+        fitness
+    }
+
+    pub fn win_rate(&self) -> f32 {
+        self.wins as f32 / self.matches.max(1) as f32
+    }
+
+    pub fn avg_goal_diff(&self) -> f32 {
+        self.goal_diff as f32 / self.matches.max(1) as f32
+    }
+
+    /// Selection score: win rate, nudged by average margin
+    pub fn score(&self) -> f32 {
+        self.win_rate() + GOAL_DIFF_WEIGHT * self.avg_goal_diff()
+    }
+}
+
+/// One member of the population
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub profile: AiProfile,
+    /// Generation it was bred in
+    pub generation: u32,
+    /// Profiles it was bred from (one for a mutated seed profile)
+    pub parents: Vec<String>,
+    /// Seed profiles at the root of its lineage
+    pub origins: Vec<String>,
+    /// Latest generation's results
+    pub fitness: Fitness,
+}
+
+impl Candidate {
+    /// Comment lines recording where this profile came from
+    pub fn lineage_comment(&self) -> String {
+        format!(
+            "# lineage: generation {}, parents {}, from {}\n\
+             # fitness: {:.2} (win {:.0}% over {} matches, goal diff {:+.1})\n",
+            self.generation,
+            self.parents.join(" x "),
+            self.origins.join(" + "),
+            self.fitness.score(),
+            self.fitness.win_rate() * 100.0,
+            self.fitness.matches,
+            self.fitness.avg_goal_diff()
+        )
+    }
+}
+
+/// Mutate each gene with probability `rate` by up to `scale` of its range
+pub fn mutate(profile: &mut AiProfile, rate: f32, scale: f32, rng: &mut StdRng) {
+    for (name, min, max) in GENES {
+        if rng.gen_bool(rate.clamp(0.0, 1.0) as f64) {
+            // Triangular step: small changes are more likely than big ones
+            let step = (rng.gen_range(0.0_f32..1.0) + rng.gen_range(0.0_f32..1.0) - 1.0) * scale;
+            let value = gene(profile, name);
+            *value = (*value + step * (max - min)).clamp(min, max);
+        }
+    }
+    if profile.charge_max < profile.charge_min {
+        std::mem::swap(&mut profile.charge_min, &mut profile.charge_max);
+    }
+}
+
+/// Uniform crossover: each gene from either parent (everything else from `a`)
+pub fn crossover(a: &AiProfile, b: &AiProfile, rng: &mut StdRng) -> AiProfile {
+    let mut child = a.clone();
+    let mut donor = b.clone();
+    for (name, _, _) in GENES {
+        if rng.gen_bool(0.5) {
+            *gene(&mut child, name) = *gene(&mut donor, name);
+        }
+    }
+    if child.charge_max < child.charge_min {
+        std::mem::swap(&mut child.charge_min, &mut child.charge_max);
+    }
+    child
+}
+
+/// Population and breeding state across generations
+pub struct Evolution {
+    pub config: EvolveConfig,
+    pub generation: u32,
+    pub population: Vec<Candidate>,
+    rng: StdRng,
+}
+
+impl Evolution {
+    /// Generation 0: mutated copies of the seed profiles, round-robin
+    pub fn new(config: EvolveConfig, seeds: &[AiProfile]) -> Self {
+        let mut evolution = Self {
+            rng: StdRng::seed_from_u64(config.seed),
+            config,
+            generation: 0,
+            population: Vec::new(),
+        };
+        for (index, seed) in seeds
+            .iter()
+            .cycle()
+            .take(evolution.config.population)
+            .enumerate()
+        {
+            let mut profile = seed.clone();
+            mutate(
+                &mut profile,
+                evolution.config.mutation_rate,
+                evolution.config.mutation_scale,
+                &mut evolution.rng,
+            );
+            let candidate = evolution.candidate(index, profile, vec![seed.name.clone()]);
+            evolution.population.push(Candidate {
+                origins: vec![seed.name.clone()],
+                ..candidate
+            });
+        }
+        evolution
+    }
+
+    fn candidate(&self, index: usize, mut profile: AiProfile, parents: Vec<String>) -> Candidate {
+        profile.name = format!("{}_g{}_{}", self.config.tag, self.generation, index);
+        profile.id = generate_uuid_from_name(&profile.name);
+        Candidate {
+            profile,
+            generation: self.generation,
+            parents,
+            origins: Vec::new(),
+            fitness: Fitness::default(),
+        }
+    }
+
+    /// Population sorted by fitness, best first
+    pub fn ranked(&self) -> Vec<&Candidate> {
+        let mut ranked: Vec<&Candidate> = self.population.iter().collect();
+        ranked.sort_by(|a, b| b.fitness.score().total_cmp(&a.fitness.score()));
+        ranked
+    }
+
+    /// Best of TOURNAMENT_SIZE random candidates
+    fn select(&mut self) -> Candidate {
+        let mut best: Option<&Candidate> = None;
+        for _ in 0..TOURNAMENT_SIZE {
+            let pick = &self.population[self.rng.gen_range(0..self.population.len())];
+            if best.is_none_or(|b| pick.fitness.score() > b.fitness.score()) {
+                best = Some(pick);
+            }
+        }
+        best.expect("population is empty").clone()
+    }
+
+    /// Replace the population with the next generation (needs fitness set)
+    pub fn breed(&mut self) {
+        let elite: Vec<Candidate> = self
+            .ranked()
+            .into_iter()
+            .take(self.config.elite.min(self.config.population))
+            .cloned()
+            .collect();
+        self.generation += 1;
+
+        let mut next = elite;
+        while next.len() < self.config.population {
+            let a = self.select();
+            let b = self.select();
+            let mut profile = crossover(&a.profile, &b.profile, &mut self.rng);
+            mutate(
+                &mut profile,
+                self.config.mutation_rate,
+                self.config.mutation_scale,
+                &mut self.rng,
+            );
+            let mut origins: Vec<String> = a.origins.iter().chain(&b.origins).cloned().collect();
+            origins.sort();
+            origins.dedup();
+            let child = self.candidate(
+                next.len(),
+                profile,
+                vec![a.profile.name.clone(), b.profile.name.clone()],
+            );
+            next.push(Candidate { origins, ..child });
+        }
+        self.population = next;
+    }
+
+    /// Play this generation's matches and set every candidate's fitness
+    pub fn evaluate(
+        &mut self,
+        base_config: &SimConfig,
+        opponents: &[String],
+        levels: &[u32],
+        level_db: &LevelDatabase,
+        profile_db: &AiProfileDatabase,
+    ) {
+        let mut db = profile_db.clone();
+        for candidate in &self.population {
+            db.upsert(candidate.profile.clone());
+        }
+
+        let mut configs = Vec::new();
+        for (index, candidate) in self.population.iter().enumerate() {
+            for m in 0..self.config.matches as usize {
+                let seed = self
+                    .config
+                    .seed
+                    .wrapping_add(self.generation as u64 * 100_000)
+                    .wrapping_add(index as u64 * 1_000)
+                    .wrapping_add(m as u64);
+                let opponent = opponents[(index + m) % opponents.len()].clone();
+                let name = candidate.profile.name.clone();
+                // Alternate sides so a level's side bias doesn't pick the winners
+                let (left_profile, right_profile) = if m % 2 == 0 {
+                    (name, opponent)
+                } else {
+                    (opponent, name)
+                };
+                configs.push(MatchConfig {
+                    base_config: base_config.clone(),
+                    level: base_config
+                        .level
+                        .unwrap_or(levels[seed as usize % levels.len()]),
+                    left_profile,
+                    right_profile,
+                    seed,
+                });
+            }
+        }
+
+        let results = run_matches_parallel(&configs, level_db, &db).results;
+        for candidate in &mut self.population {
+            candidate.fitness = Fitness::from_results(&candidate.profile.name, &results);
+        }
+    }
+}
+
+/// Run every generation and return the final population, best first
+pub fn run_evolution(
+    config: EvolveConfig,
+    base_config: &SimConfig,
+    level_db: &LevelDatabase,
+    profile_db: &AiProfileDatabase,
+) -> Result<Vec<Candidate>, String> {
+    let lookup = |names: &[String]| -> Result<Vec<AiProfile>, String> {
+        names
+            .iter()
+            .map(|name| {
+                profile_db
+                    .get_by_name(name)
+                    .cloned()
+                    .ok_or_else(|| format!("unknown profile '{}'", name))
+            })
+            .collect()
+    };
+    let seeds = if config.parents.is_empty() {
+        profile_db.profiles().to_vec()
+    } else {
+        lookup(&config.parents)?
+    };
+    let opponents: Vec<String> = if config.opponents.is_empty() {
+        seeds.iter().map(|p| p.name.clone()).collect()
+    } else {
+        lookup(&config.opponents)?
+            .into_iter()
+            .map(|p| p.name)
+            .collect()
+    };
+    // Same pool as tournaments: no debug levels, no Pit
+    let levels: Vec<u32> = (1..=level_db.len() as u32)
+        .filter(|&level| {
+            level_db
+                .get((level - 1) as usize)
+                .is_some_and(|l| !l.debug && l.name != "Pit")
+        })
+        .collect();
+    if seeds.is_empty() || levels.is_empty() || config.population == 0 {
+        return Err("nothing to evolve (no seed profiles, levels or population)".to_string());
+    }
+
+    let generations = config.generations.max(1);
+    let mut evolution = Evolution::new(config, &seeds);
+    loop {
+        evolution.evaluate(base_config, &opponents, &levels, level_db, profile_db);
+        let ranked = evolution.ranked();
+        let mean = ranked.iter().map(|c| c.fitness.win_rate()).sum::<f32>() / ranked.len() as f32;
+        println!(
+            "Generation {}: best {} win {:.0}% ({:+.1} goals), population mean {:.0}%",
+            evolution.generation,
+            ranked[0].profile.name,
+            ranked[0].fitness.win_rate() * 100.0,
+            ranked[0].fitness.avg_goal_diff(),
+            mean * 100.0
+        );
+        if evolution.generation + 1 >= generations {
+            break;
+        }
+        evolution.breed();
+    }
+    Ok(evolution.ranked().into_iter().cloned().collect())
+}
+
+/// Append evolved profiles (with lineage comments) to a profiles file
+pub fn append_evolved_profiles(
+    path: &str,
+    candidates: &[Candidate],
+    config: &EvolveConfig,
+) -> Result<(), String> {
+    let rule = "# =============================================================================\n";
+    let mut out = format!(
+        "\n{}# EVOLVED {} (seed {}, {} generations of {}, {} matches each)\n{}",
+        rule,
+        chrono::Local::now().format("%Y-%m-%d %H:%M"),
+        config.seed,
+        config.generations,
+        config.population,
+        config.matches,
+        rule
+    );
+    for candidate in candidates {
+        out.push('\n');
+        out.push_str(&candidate.lineage_comment());
+        out.push_str(&candidate.profile.to_config());
+    }
+
+    let path = crate::config_dir::resolve_for_write(path);
+    let mut file = OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open {}: {}", path, e))?;
+    file.write_all(out.as_bytes())
+        .map_err(|e| format!("Failed to write {}: {}", path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(left: &str, right: &str, score_left: u32, score_right: u32) -> MatchResult {
+        MatchResult {
+            level: 1,
+            level_name: "Arena".into(),
+            left_profile: left.into(),
+            right_profile: right.into(),
+            duration: 60.0,
+            score_left,
+            score_right,
+            winner: if score_left > score_right {
+                "left"
+            } else {
+                "right"
+            }
+            .into(),
+            left_stats: Default::default(),
+            right_stats: Default::default(),
+            seed: 0,
+            degenerate: None,
+            events: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_breeding_keeps_elite_bounds_and_lineage() {
+        let results = [
+            result("evo_g0_0", "Rush", 3, 1),
+            result("Rush", "evo_g0_0", 2, 1),
+            result("Balanced", "evo_g0_0", 0, 4),
+        ];
+        let fitness = Fitness::from_results("evo_g0_0", &results);
+        assert_eq!(
+            (fitness.matches, fitness.wins, fitness.goal_diff),
+            (3, 2, 5)
+        );
+        assert!((fitness.score() - (2.0 / 3.0 + GOAL_DIFF_WEIGHT * 5.0 / 3.0)).abs() < 1e-6);
+
+        let rush = AiProfile {
+            name: "Rush".into(),
+            aggression: 1.0,
+            ..Default::default()
+        };
+        let config = EvolveConfig {
+            population: 6,
+            mutation_rate: 1.0,
+            mutation_scale: 1.0,
+            ..Default::default()
+        };
+        let mut evolution = Evolution::new(config, &[AiProfile::default(), rush]);
+        assert_eq!(evolution.population.len(), 6);
+        assert_eq!(evolution.population[1].origins, ["Rush"]);
+        for (i, candidate) in evolution.population.iter_mut().enumerate() {
+            candidate.fitness = Fitness {
+                matches: 4,
+                wins: i as u32 % 5,
+                goal_diff: 0,
+            };
+        }
+
+        evolution.breed();
+        assert_eq!(evolution.generation, 1);
+        // The two best (4 and 3 wins) carry over unchanged
+        assert_eq!(evolution.population[0].profile.name, "evo_g0_4");
+        assert_eq!(evolution.population[1].profile.name, "evo_g0_3");
+        let child = evolution.population[2].clone();
+        assert_eq!(child.profile.name, "evo_g1_2");
+        assert_eq!(child.parents.len(), 2);
+        assert!(!child.origins.is_empty());
+        for candidate in &mut evolution.population {
+            for (name, min, max) in GENES {
+                let value = *gene(&mut candidate.profile, name);
+                assert!((min..=max).contains(&value), "{} = {}", name, value);
+            }
+            assert!(candidate.profile.charge_min <= candidate.profile.charge_max);
+        }
+
+        // The written block parses back to the same profile
+        let path = std::env::temp_dir().join(format!("evolve_test_{}.txt", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let path = path.to_str().unwrap();
+        append_evolved_profiles(path, &evolution.population[2..3], &evolution.config).unwrap();
+        let contents = std::fs::read_to_string(path).unwrap();
+        assert!(contents.contains("# lineage: generation 1, parents evo_g0_"));
+        let db = AiProfileDatabase::load_from_file(path);
+        let _ = std::fs::remove_file(path);
+        let parsed = db.get_by_name("evo_g1_2").unwrap();
+        assert_eq!(parsed.id, child.profile.id);
+        assert_eq!(parsed.aggression, child.profile.aggression);
+        assert_eq!(parsed.seek_threshold, child.profile.seek_threshold);
+    }
+}
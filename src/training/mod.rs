@@ -1,19 +1,31 @@
 //! Training mode for playing against AI and collecting analysis data
 
 mod analysis;
+mod evolve;
+mod props;
 mod protocol;
 mod session;
 mod settings;
 mod state;
+mod telemetry;
 
 pub use analysis::{
     PursuitAnalysis, PursuitIterationStats, SessionAnalysis, analyze_pursuit_session_from_db,
     analyze_session_from_db, format_pursuit_analysis_markdown, generate_analysis_request,
     write_analysis_files,
 };
+pub use evolve::{
+    Candidate, Evolution, EvolveConfig, Fitness, append_evolved_profiles, crossover, mutate,
+    run_evolution,
+};
+pub use props::{
+    BallFeeder, DummySpec, FeederSpec, PracticeDummy, PracticeProps, dummy_contest_shots,
+    lob_velocity, move_practice_dummies, run_ball_feeders, spawn_practice_props,
+};
 pub use protocol::{ProtocolConfig, TrainingProtocol};
 pub use session::{
     GameSummary, SessionSummary, ensure_session_dir, print_session_summary, write_session_summary,
 };
 pub use settings::{LevelSelector, TrainingMode, TrainingSettings};
 pub use state::{GameResult, ReachabilityCollector, TrainingPhase, TrainingState, Winner};
+pub use telemetry::{DRILL_ROLLING_WINDOW, DrillStat, DrillStats, DrillTelemetry};
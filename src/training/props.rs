@@ -0,0 +1,312 @@
+//! Practice props - dummy defenders and ball-feeder machines
+//!
+//! Defined in the `props` section of the training settings file. A dummy is a
+//! player-sized stand-in that contests shots passing through it (same drag as
+//! a real defender) and can patrol back and forth. A feeder lobs a ball to its
+//! target spot every `interval_secs`; once it has `max_balls` out it recycles
+//! the oldest one that nobody is holding.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::ball::{Ball, BallRolling, BallState, BallStyle, BallTextures, CurrentPalette};
+use crate::config::BallConfig;
+use crate::constants::*;
+use crate::player::{Velocity, spawn_ball};
+use crate::tuning::PhysicsTweaks;
+
+/// Dummy defender color (translucent so it reads as a prop, not a player)
+const DUMMY_COLOR: Color = Color::srgba(0.55, 0.55, 0.6, 0.7);
+/// Feeder machine color and size
+const FEEDER_COLOR: Color = Color::srgb(0.35, 0.35, 0.4);
+const FEEDER_SIZE: Vec2 = Vec2::new(40.0, 30.0);
+
+fn default_dummy_speed() -> f32 {
+    120.0
+}
+
+fn default_feed_interval() -> f32 {
+    3.0
+}
+
+fn default_flight_secs() -> f32 {
+    1.0
+}
+
+fn default_max_balls() -> usize {
+    3
+}
+
+/// Props section of the training settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PracticeProps {
+    #[serde(default)]
+    pub dummies: Vec<DummySpec>,
+    #[serde(default)]
+    pub feeders: Vec<FeederSpec>,
+}
+
+impl PracticeProps {
+    pub fn is_empty(&self) -> bool {
+        self.dummies.is_empty() && self.feeders.is_empty()
+    }
+}
+
+/// Dummy defender placement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DummySpec {
+    pub x: f32,
+    pub y: f32,
+    /// Half-width of the back-and-forth patrol (0 = stationary)
+    #[serde(default)]
+    pub patrol: f32,
+    /// Patrol speed (pixels/sec)
+    #[serde(default = "default_dummy_speed")]
+    pub speed: f32,
+}
+
+/// Ball feeder placement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeederSpec {
+    pub x: f32,
+    pub y: f32,
+    /// Where lobbed balls come down
+    pub target: [f32; 2],
+    /// Seconds between lobs
+    #[serde(default = "default_feed_interval")]
+    pub interval_secs: f32,
+    /// Seconds each lob is in the air
+    #[serde(default = "default_flight_secs")]
+    pub flight_secs: f32,
+    /// Balls out at once before the oldest is recycled
+    #[serde(default = "default_max_balls")]
+    pub max_balls: usize,
+}
+
+/// Patrolling (or stationary) dummy defender
+#[derive(Component)]
+pub struct PracticeDummy {
+    origin_x: f32,
+    patrol: f32,
+    speed: f32,
+    direction: f32,
+    /// Balls overlapping last frame (contest applies on first contact only)
+    touching: Vec<Entity>,
+}
+
+/// Ball feeder machine
+#[derive(Component)]
+pub struct BallFeeder {
+    spec: FeederSpec,
+    timer: f32,
+    /// Balls this feeder put out, oldest first
+    fed: VecDeque<Entity>,
+}
+
+/// Launch velocity that lands a ball at `to` after `flight_secs` under `gravity`
+/// (air friction ignored, so long lobs land a little short)
+pub fn lob_velocity(from: Vec2, to: Vec2, flight_secs: f32, gravity: f32) -> Vec2 {
+    let t = flight_secs.max(0.1);
+    let d = to - from;
+    Vec2::new(d.x / t, d.y / t + 0.5 * gravity * t)
+}
+
+/// Spawn the props listed in the training settings
+pub fn spawn_practice_props(commands: &mut Commands, props: &PracticeProps) {
+    for dummy in &props.dummies {
+        commands.spawn((
+            Sprite::from_color(DUMMY_COLOR, PLAYER_SIZE),
+            Transform::from_xyz(dummy.x, dummy.y, 0.5),
+            PracticeDummy {
+                origin_x: dummy.x,
+                patrol: dummy.patrol.max(0.0),
+                speed: dummy.speed,
+                direction: 1.0,
+                touching: Vec::new(),
+            },
+        ));
+    }
+    for feeder in &props.feeders {
+        commands.spawn((
+            Sprite::from_color(FEEDER_COLOR, FEEDER_SIZE),
+            Transform::from_xyz(feeder.x, feeder.y, 0.5),
+            BallFeeder {
+                spec: feeder.clone(),
+                timer: feeder.interval_secs,
+                fed: VecDeque::new(),
+            },
+        ));
+    }
+    if !props.is_empty() {
+        info!(
+            "Practice props: {} dummies, {} feeders",
+            props.dummies.len(),
+            props.feeders.len()
+        );
+    }
+}
+
+/// Walk patrolling dummies back and forth around their spawn point
+pub fn move_practice_dummies(
+    time: Res<Time>,
+    mut dummies: Query<(&mut Transform, &mut PracticeDummy)>,
+) {
+    let dt = time.delta_secs();
+    for (mut transform, mut dummy) in &mut dummies {
+        if dummy.patrol <= 0.0 {
+            continue;
+        }
+        let mut x = transform.translation.x + dummy.direction * dummy.speed * dt;
+        let offset = x - dummy.origin_x;
+        if offset.abs() >= dummy.patrol {
+            x = dummy.origin_x + offset.signum() * dummy.patrol;
+            dummy.direction = -offset.signum();
+        }
+        transform.translation.x = x;
+    }
+}
+
+/// Shots passing through a dummy lose speed like they hit a real defender
+pub fn dummy_contest_shots(
+    ball_config: Res<BallConfig>,
+    mut balls: Query<(Entity, &Transform, &mut Velocity, &BallState), With<Ball>>,
+    mut dummies: Query<(&Transform, &mut PracticeDummy), Without<Ball>>,
+) {
+    for (dummy_transform, mut dummy) in &mut dummies {
+        let dummy_pos = dummy_transform.translation.truncate();
+        let reach = (PLAYER_SIZE + BALL_SIZE) / 2.0;
+        let mut touching = Vec::new();
+
+        for (ball_entity, ball_transform, mut velocity, state) in &mut balls {
+            if !matches!(state, BallState::InFlight { .. }) {
+                continue;
+            }
+            let diff = (ball_transform.translation.truncate() - dummy_pos).abs();
+            if diff.x >= reach.x || diff.y >= reach.y {
+                continue;
+            }
+            touching.push(ball_entity);
+            if !dummy.touching.contains(&ball_entity) {
+                velocity.0.x *= ball_config.player_drag_x;
+                velocity.0.y *= ball_config.player_drag_y;
+            }
+        }
+        dummy.touching = touching;
+    }
+}
+
+/// Lob a ball from each feeder on its interval
+#[allow(clippy::type_complexity)]
+pub fn run_ball_feeders(
+    mut commands: Commands,
+    time: Res<Time>,
+    tweaks: Res<PhysicsTweaks>,
+    palette: Res<CurrentPalette>,
+    ball_textures: Option<Res<BallTextures>>,
+    mut feeders: Query<(&Transform, &mut BallFeeder), Without<Ball>>,
+    mut balls: Query<
+        (
+            &mut Transform,
+            &mut Velocity,
+            &mut BallState,
+            &mut BallRolling,
+            &BallStyle,
+        ),
+        With<Ball>,
+    >,
+) {
+    let Some(ball_textures) = ball_textures else {
+        return;
+    };
+    let dt = time.delta_secs();
+
+    for (feeder_transform, mut feeder) in &mut feeders {
+        feeder.timer -= dt;
+        if feeder.timer > 0.0 {
+            continue;
+        }
+        feeder.timer = feeder.spec.interval_secs.max(0.1);
+
+        let from = feeder_transform.translation.truncate() + Vec2::Y * FEEDER_SIZE.y;
+        let to = Vec2::from(feeder.spec.target);
+        let launch = lob_velocity(from, to, feeder.spec.flight_secs, tweaks.ball_gravity);
+
+        // Forget balls that were despawned (level change, restart)
+        feeder.fed.retain(|entity| balls.contains(*entity));
+
+        let recycle = if feeder.fed.len() >= feeder.spec.max_balls.max(1) {
+            let free = feeder.fed.iter().position(|entity| {
+                balls
+                    .get(*entity)
+                    .is_ok_and(|(_, _, state, _, _)| !matches!(*state, BallState::Held(_)))
+            });
+            match free {
+                Some(pos) => feeder.fed.remove(pos),
+                // Every fed ball is in someone's hands
+                None => continue,
+            }
+        } else {
+            None
+        };
+
+        let ball = match recycle {
+            Some(entity) => entity,
+            None => {
+                let style = balls
+                    .iter()
+                    .next()
+                    .map(|(_, _, _, _, style)| style.name().to_string())
+                    .or_else(|| ball_textures.default_style().cloned())
+                    .unwrap_or_else(|| "wedges".to_string());
+                let spawn = from.extend(BALL_SPAWN.z);
+                match spawn_ball(&mut commands, &ball_textures, &style, palette.0, spawn) {
+                    Some(entity) => {
+                        // Spawned this frame: velocity goes on with the insert
+                        commands.entity(entity).insert(Velocity(launch));
+                        feeder.fed.push_back(entity);
+                        continue;
+                    }
+                    None => continue,
+                }
+            }
+        };
+
+        if let Ok((mut transform, mut velocity, mut state, mut rolling, _)) = balls.get_mut(ball) {
+            transform.translation.x = from.x;
+            transform.translation.y = from.y;
+            velocity.0 = launch;
+            *state = BallState::Free;
+            rolling.0 = false;
+            feeder.fed.push_back(ball);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lob_lands_on_target_and_props_parse() {
+        let from = Vec2::new(-600.0, -300.0);
+        let to = Vec2::new(-200.0, -350.0);
+        let gravity = 800.0;
+        let t = 1.2;
+        let v = lob_velocity(from, to, t, gravity);
+        let landing = from + v * t + Vec2::new(0.0, -0.5 * gravity * t * t);
+        assert!(landing.distance(to) < 1e-2);
+        assert!(v.y > 0.0, "lob should go up first");
+
+        let props: PracticeProps = serde_json::from_str(
+            r#"{"dummies": [{"x": 100, "y": -400, "patrol": 80}],
+                "feeders": [{"x": -600, "y": -300, "target": [-200, -350]}]}"#,
+        )
+        .unwrap();
+        assert_eq!(props.dummies[0].speed, 120.0);
+        assert_eq!(props.feeders[0].interval_secs, 3.0);
+        assert_eq!(props.feeders[0].max_balls, 3);
+        assert!(PracticeProps::default().is_empty());
+    }
+}
@@ -6,6 +6,7 @@ use std::io::Write;
 use std::path::Path;
 
 use super::state::{TrainingState, Winner};
+use super::telemetry::DrillStats;
 
 /// Session summary for JSON output
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,6 +38,8 @@ pub struct GameSummary {
     pub match_id: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub notes: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drill: Option<DrillStats>,
 }
 
 impl SessionSummary {
@@ -57,6 +60,7 @@ impl SessionSummary {
                 duration_secs: r.duration_secs,
                 match_id: r.match_id,
                 notes: r.notes.clone(),
+                drill: r.drill.clone(),
             })
             .collect();
 
@@ -143,6 +147,45 @@ pub fn print_session_summary(state: &TrainingState) {
                 .map(|id| format!("match {}", id))
                 .unwrap_or_else(|| "match ?".to_string())
         );
+        if let Some(drill) = &result.drill {
+            println!(
+                "          steals {}/{} (best streak {}), shots {}/{} (best streak {})",
+                drill.steals,
+                drill.steal_attempts,
+                drill.best_steal_streak,
+                drill.shots_made,
+                drill.shot_attempts,
+                drill.best_shot_streak
+            );
+        }
+    }
+
+    let drills: Vec<&DrillStats> = state
+        .game_results
+        .iter()
+        .filter_map(|r| r.drill.as_ref())
+        .collect();
+    if !drills.is_empty() {
+        let total = |f: fn(&DrillStats) -> u32| drills.iter().map(|d| f(d)).sum::<u32>();
+        let pct = |made: u32, attempts: u32| {
+            if attempts > 0 {
+                format!("{:.0}%", made as f32 / attempts as f32 * 100.0)
+            } else {
+                "-".to_string()
+            }
+        };
+        let (steals, steal_attempts) = (total(|d| d.steals), total(|d| d.steal_attempts));
+        let (shots_made, shot_attempts) = (total(|d| d.shots_made), total(|d| d.shot_attempts));
+        println!();
+        println!(
+            "  DRILLS: steals {}/{} ({}), shots {}/{} ({})",
+            steals,
+            steal_attempts,
+            pct(steals, steal_attempts),
+            shots_made,
+            shot_attempts,
+            pct(shots_made, shot_attempts)
+        );
     }
 
     println!();
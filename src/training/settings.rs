@@ -8,7 +8,10 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
 
+use super::props::PracticeProps;
 use super::protocol::TrainingProtocol;
+use crate::scoring::{BallResetMode, Handicap, SideHandicap};
+use crate::teams::{TeamIdentity, TeamInfo};
 
 /// Path to local settings file (gitignored)
 pub const SETTINGS_FILE: &str = "config/training_settings.json";
@@ -42,6 +45,10 @@ impl std::fmt::Display for LevelSelector {
     }
 }
 
+fn default_db_gc() -> bool {
+    true
+}
+
 /// Training session settings
 #[derive(Debug, Clone, Resource, Serialize, Deserialize)]
 pub struct TrainingSettings {
@@ -79,6 +86,54 @@ pub struct TrainingSettings {
     /// Drive mode (start with ball, regain on loss, first point wins)
     #[serde(default)]
     pub drive_mode: bool,
+    /// Tied when the time limit expires -> sudden-death overtime (shrinking court)
+    #[serde(default)]
+    pub overtime: bool,
+    /// Dead balls (stuck-ball resets, simultaneous pickups) go by alternating possession arrow
+    #[serde(default)]
+    pub possession_arrow: bool,
+    /// Consecutive goals give the scoring side a brief hot-streak buff
+    #[serde(default)]
+    pub momentum: bool,
+    /// Successful steals knock the ball loose and both players scramble for it
+    #[serde(default)]
+    pub loose_ball: bool,
+    /// Defensive three seconds: a defender camping on their own rim stops blocking carry-ins
+    #[serde(default)]
+    pub rim_camping_violation: bool,
+    /// Shot clock: seconds a side may keep the ball without shooting (null = off)
+    #[serde(default)]
+    pub shot_clock_secs: Option<f32>,
+    /// A ball leaving the court is a turnover; the other side throws in
+    #[serde(default)]
+    pub out_of_bounds: bool,
+    /// Where play restarts after a goal (center, conceding-team inbound, random spot)
+    #[serde(default)]
+    pub ball_reset: BallResetMode,
+    /// Make nav edges the AI keeps failing more expensive for pathfinding
+    #[serde(default)]
+    pub nav_edge_penalty: bool,
+    /// Head start / shot value / cooldown handicaps (left = human, right = AI)
+    #[serde(default)]
+    pub handicap: Handicap,
+    /// Team names and colors (left = human, right = AI)
+    #[serde(default)]
+    pub teams: TeamIdentity,
+    /// Show the AI's current goal and nav step above its head (L toggles)
+    #[serde(default)]
+    pub ai_labels: bool,
+    /// Show the input viewer: live inputs and a tick-counted history (I toggles)
+    #[serde(default)]
+    pub input_viewer: bool,
+    /// Prune and compress old databases in db/ when the session closes
+    #[serde(default = "default_db_gc")]
+    pub db_gc: bool,
+    /// Don't create a database for this session at all (CLI only, never saved)
+    #[serde(skip)]
+    pub incognito: bool,
+    /// Dummy defenders and ball feeders to spawn on the court
+    #[serde(default)]
+    pub props: PracticeProps,
 }
 
 impl Default for TrainingSettings {
@@ -99,6 +154,22 @@ impl Default for TrainingSettings {
             palette_index: 0,
             ball_style: None,
             drive_mode: false,
+            overtime: false,
+            possession_arrow: false,
+            momentum: false,
+            loose_ball: false,
+            rim_camping_violation: false,
+            shot_clock_secs: None,
+            out_of_bounds: false,
+            ball_reset: BallResetMode::Center,
+            nav_edge_penalty: false,
+            handicap: Handicap::default(),
+            teams: TeamIdentity::default(),
+            ai_labels: false,
+            input_viewer: false,
+            db_gc: true,
+            incognito: false,
+            props: PracticeProps::default(),
         }
     }
 }
@@ -273,6 +344,79 @@ impl TrainingSettings {
                     self.iterations = 1;
                     self.win_score = 1;
                 }
+                "--overtime" => {
+                    self.overtime = true;
+                }
+                "--possession-arrow" => {
+                    self.possession_arrow = true;
+                }
+                "--momentum" => {
+                    self.momentum = true;
+                }
+                "--loose-ball" => {
+                    self.loose_ball = true;
+                }
+                "--rim-camping-violation" => {
+                    self.rim_camping_violation = true;
+                }
+                "--shot-clock" => {
+                    if let Some(val) = args.get(i + 1) {
+                        if let Ok(n) = val.parse() {
+                            self.shot_clock_secs = Some(n);
+                        }
+                        i += 1;
+                    }
+                }
+                "--out-of-bounds" => {
+                    self.out_of_bounds = true;
+                }
+                "--ball-reset" => {
+                    if let Some(val) = args.get(i + 1) {
+                        match BallResetMode::parse(val) {
+                            Some(mode) => self.ball_reset = mode,
+                            None => eprintln!(
+                                "Warning: --ball-reset expects center, inbound or random (got '{}')",
+                                val
+                            ),
+                        }
+                        i += 1;
+                    }
+                }
+                "--nav-edge-penalty" => {
+                    self.nav_edge_penalty = true;
+                }
+                "--ai-labels" => {
+                    self.ai_labels = true;
+                }
+                "--input-viewer" => {
+                    self.input_viewer = true;
+                }
+                "--no-db-gc" => {
+                    self.db_gc = false;
+                }
+                "--incognito" => {
+                    self.incognito = true;
+                }
+                "--handicap-left" | "--handicap-right" => {
+                    if let Some(val) = args.get(i + 1) {
+                        match SideHandicap::parse(val) {
+                            Ok(side) if args[i] == "--handicap-left" => self.handicap.left = side,
+                            Ok(side) => self.handicap.right = side,
+                            Err(e) => eprintln!("Warning: {}: {}", args[i], e),
+                        }
+                        i += 1;
+                    }
+                }
+                "--team-left" | "--team-right" => {
+                    if let Some(val) = args.get(i + 1) {
+                        match TeamInfo::parse(val) {
+                            Ok(team) if args[i] == "--team-left" => self.teams.left = team,
+                            Ok(team) => self.teams.right = team,
+                            Err(e) => eprintln!("Warning: {}: {}", args[i], e),
+                        }
+                        i += 1;
+                    }
+                }
                 "--help" | "-h" => {
                     print_help();
                     std::process::exit(0);
@@ -319,10 +463,36 @@ OPTIONS:
     -s, --seed N               RNG seed for determinism (default: random)
     -t, --time-limit SECS      Time limit per iteration (default: none or protocol default)
     --first-point-timeout SECS End if no score within SECS (default: none)
+    --overtime                 Sudden-death overtime if tied at the time limit
+    --possession-arrow         Stuck balls and simultaneous pickups go by alternating arrow
+    --momentum                 Two goals in a row heat a side up (faster move/charge, aura)
+    --loose-ball               Successful steals pop the ball loose for a scramble
+    --rim-camping-violation    3s camping on your own rim voids its carry-in block
+    --shot-clock SECS          Holding the ball SECS without shooting pops it loose
+    --out-of-bounds            A ball leaving the court is a turnover throw-in
+    --ball-reset MODE          Restart after goals at center, inbound or random
+    --nav-edge-penalty         AI routes around nav edges it keeps failing
+    --2v2                      Two per side: you and the AI each get an AI teammate
+    --ai-labels                Show the AI's goal and nav step above its head (L toggles)
+    --input-viewer             Show your live inputs and a tick-counted input history (I toggles)
+    --no-db-gc                 Skip pruning/compressing old databases on exit
+    --incognito                Don't write a training database (HUD still live)
+    --webhook URL              POST match events as JSON to a local http:// endpoint
+    --webhook-events LIST      Events to post (default: MatchStart,Goal,MatchEnd)
+    --upload URL               POST the session summary to a shared leaderboard at the end
+    --upload-queue FILE        Offline queue for failed uploads (default: db/upload_queue.jsonl)
+    --handicap-left SPEC       Human handicap, e.g. head=2,shot=1.5,cooldown=0.8
+    --handicap-right SPEC      AI handicap (same format; head start counts toward win score)
+    --team-left SPEC           Human team, e.g. name=Reds,color=#d03030
+    --team-right SPEC          AI team (same format)
     --viewport N               Viewport preset index (default: 2)
     --palette N                Color palette index (default: 0)
+    --config-dir DIR           Use levels/profiles/tuning from DIR (falls back to config/)
     --ball-style NAME          Ball visual style (default: random)
     --debug-log                Enable debug sample logging to SQLite
+    --log-tick-ms MS           Interval between logged tick/input events (default: 50)
+    --log-categories LIST      Event categories to store, comma-separated (default: all)
+    --debug-samples WHO        Debug samples for both, left, right or none (default: both)
     -h, --help                 Show this help
 
 SETTINGS FILES:
@@ -331,6 +501,10 @@ SETTINGS FILES:
 
     CLI arguments override file settings.
 
+PRACTICE PROPS (settings file "props" section):
+    "dummies": [{"x": 200, "y": -400, "patrol": 150, "speed": 120}]
+    "feeders": [{"x": -700, "y": -300, "target": [-200, -350], "interval_secs": 3}]
+
 AI PROFILES:
     Balanced, Aggressive, Defensive, Sniper, Rusher,
     Turtle, Chaotic, Patient, Hunter, Goalie
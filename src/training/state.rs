@@ -6,6 +6,7 @@ use std::path::PathBuf;
 use std::time::Instant;
 
 use super::protocol::TrainingProtocol;
+use super::telemetry::DrillStats;
 
 /// Collects position data for reachability heatmap export
 pub struct ReachabilityCollector {
@@ -77,6 +78,9 @@ pub struct GameResult {
     pub match_id: Option<i64>,
     /// Optional notes entered by player after the game
     pub notes: Option<String>,
+    /// Steal/shot drill numbers for this game
+    #[serde(default)]
+    pub drill: Option<DrillStats>,
 }
 
 /// Main training session state resource
@@ -106,7 +110,7 @@ pub struct TrainingState {
     pub phase: TrainingPhase,
     /// Time game started (for duration tracking)
     pub game_start_time: Option<Instant>,
-    /// Elapsed game time in seconds
+    /// Elapsed game time in seconds (gameplay only: no countdowns or pauses)
     pub game_elapsed: f32,
     /// AI profile name being trained against
     pub ai_profile: String,
@@ -193,10 +197,10 @@ impl TrainingState {
         self.phase = TrainingPhase::Playing;
     }
 
-    /// Update elapsed time
-    pub fn update_elapsed(&mut self) {
-        if let Some(start) = self.game_start_time {
-            self.game_elapsed = start.elapsed().as_secs_f32();
+    /// Add a frame of gameplay to the elapsed time
+    pub fn update_elapsed(&mut self, delta_secs: f32) {
+        if self.game_start_time.is_some() {
+            self.game_elapsed += delta_secs;
         }
     }
 
@@ -218,6 +222,7 @@ impl TrainingState {
             duration_secs: self.game_elapsed,
             match_id,
             notes: None,
+            drill: None,
         };
 
         self.game_results.push(result);
@@ -0,0 +1,226 @@
+//! Live steal and shot telemetry for training drills
+//!
+//! Tracks the human player's steal and shot attempts per iteration: totals,
+//! current and best streaks, and a rolling success rate over the last few
+//! attempts. Shown in the drill panel and copied into each game's result for
+//! the post-session summary.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::steal::StealTracker;
+
+/// Attempts counted in the rolling success rate
+pub const DRILL_ROLLING_WINDOW: usize = 10;
+
+/// Running tally for one kind of attempt (steals or shots)
+#[derive(Debug, Clone, Default)]
+pub struct DrillStat {
+    pub attempts: u32,
+    pub successes: u32,
+    /// Consecutive successes ending at the latest attempt
+    pub streak: u32,
+    pub best_streak: u32,
+    recent: VecDeque<bool>,
+}
+
+impl DrillStat {
+    pub fn record(&mut self, success: bool) {
+        self.attempts += 1;
+        if success {
+            self.successes += 1;
+            self.streak += 1;
+            self.best_streak = self.best_streak.max(self.streak);
+        } else {
+            self.streak = 0;
+        }
+        if self.recent.len() == DRILL_ROLLING_WINDOW {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(success);
+    }
+
+    /// Success rate over all attempts (None before the first attempt)
+    pub fn rate(&self) -> Option<f32> {
+        (self.attempts > 0).then(|| self.successes as f32 / self.attempts as f32)
+    }
+
+    /// Success rate over the last `DRILL_ROLLING_WINDOW` attempts
+    pub fn rolling_rate(&self) -> Option<f32> {
+        (!self.recent.is_empty())
+            .then(|| self.recent.iter().filter(|s| **s).count() as f32 / self.recent.len() as f32)
+    }
+
+    /// One-line panel text, e.g. "Steals 3/7 (43%) | last 10: 40% | streak 2 (best 3)"
+    pub fn panel_line(&self, label: &str) -> String {
+        let pct = |rate: Option<f32>| {
+            rate.map(|r| format!("{:.0}%", r * 100.0))
+                .unwrap_or_else(|| "-".to_string())
+        };
+        format!(
+            "{} {}/{} ({}) | last {}: {} | streak {} (best {})",
+            label,
+            self.successes,
+            self.attempts,
+            pct(self.rate()),
+            DRILL_ROLLING_WINDOW,
+            pct(self.rolling_rate()),
+            self.streak,
+            self.best_streak
+        )
+    }
+}
+
+/// Per-game drill numbers kept in the session results
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DrillStats {
+    pub steal_attempts: u32,
+    pub steals: u32,
+    pub best_steal_streak: u32,
+    pub shot_attempts: u32,
+    pub shots_made: u32,
+    pub best_shot_streak: u32,
+}
+
+/// Human player's drill telemetry for the current iteration
+#[derive(Resource, Debug, Clone, Default)]
+pub struct DrillTelemetry {
+    pub steals: DrillStat,
+    pub shots: DrillStat,
+    /// Released human shot waiting for a goal or the next pickup
+    pending_shot: bool,
+    /// StealTracker counts already folded in
+    seen_steal_attempts: i32,
+    seen_steals: i32,
+}
+
+impl DrillTelemetry {
+    /// Fold in new left-team (human) steal attempts from the tracker
+    pub fn observe_steals(&mut self, tracker: &StealTracker) {
+        // Tracker was reset under us: start counting from its new values
+        if tracker.left_attempts < self.seen_steal_attempts
+            || tracker.left_steals < self.seen_steals
+        {
+            self.seen_steal_attempts = 0;
+            self.seen_steals = 0;
+        }
+        let new_steals = (tracker.left_steals - self.seen_steals).max(0);
+        let new_attempts = (tracker.left_attempts - self.seen_steal_attempts).max(new_steals);
+        for _ in 0..new_steals {
+            self.steals.record(true);
+        }
+        for _ in 0..(new_attempts - new_steals) {
+            self.steals.record(false);
+        }
+        self.seen_steal_attempts = tracker.left_attempts;
+        self.seen_steals = tracker.left_steals;
+    }
+
+    /// The human released a shot; an unresolved earlier shot counts as a miss
+    pub fn shot_released(&mut self) {
+        if self.pending_shot {
+            self.shots.record(false);
+        }
+        self.pending_shot = true;
+    }
+
+    /// A goal was scored; it resolves the pending shot as made if the human scored
+    pub fn goal_scored(&mut self, by_human: bool) {
+        if std::mem::take(&mut self.pending_shot) {
+            self.shots.record(by_human);
+        }
+    }
+
+    /// Someone picked the ball up before the pending shot went in
+    pub fn ball_picked_up(&mut self) {
+        if std::mem::take(&mut self.pending_shot) {
+            self.shots.record(false);
+        }
+    }
+
+    /// Snapshot for the game result (a shot still in the air counts as a miss)
+    pub fn game_stats(&self) -> DrillStats {
+        let mut shots = self.shots.clone();
+        if self.pending_shot {
+            shots.record(false);
+        }
+        DrillStats {
+            steal_attempts: self.steals.attempts,
+            steals: self.steals.successes,
+            best_steal_streak: self.steals.best_streak,
+            shot_attempts: shots.attempts,
+            shots_made: shots.successes,
+            best_shot_streak: shots.best_streak,
+        }
+    }
+
+    /// Clear everything for a new iteration
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Two-line drill panel text
+    pub fn panel_text(&self) -> String {
+        format!(
+            "{}\n{}",
+            self.steals.panel_line("Steals"),
+            self.shots.panel_line("Shots")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drill_telemetry_streaks_and_rates() {
+        let mut telemetry = DrillTelemetry::default();
+        let mut tracker = StealTracker::default();
+
+        // Two attempts, one success, then a second success
+        tracker.left_attempts = 2;
+        tracker.left_steals = 1;
+        telemetry.observe_steals(&tracker);
+        tracker.left_attempts = 3;
+        tracker.left_steals = 2;
+        telemetry.observe_steals(&tracker);
+        // Right-team steals are the AI's and don't count
+        tracker.right_attempts = 5;
+        telemetry.observe_steals(&tracker);
+        assert_eq!(telemetry.steals.attempts, 3);
+        assert_eq!(telemetry.steals.successes, 2);
+        assert_eq!(telemetry.steals.streak, 1);
+
+        // Make, miss (picked up), make, make
+        telemetry.shot_released();
+        telemetry.goal_scored(true);
+        telemetry.shot_released();
+        telemetry.ball_picked_up();
+        telemetry.shot_released();
+        telemetry.goal_scored(true);
+        telemetry.shot_released();
+        telemetry.goal_scored(true);
+        // AI goal with no human shot pending changes nothing
+        telemetry.goal_scored(false);
+        assert_eq!(telemetry.shots.attempts, 4);
+        assert_eq!(telemetry.shots.streak, 2);
+        assert_eq!(telemetry.shots.best_streak, 2);
+        assert_eq!(telemetry.shots.rolling_rate(), Some(0.75));
+
+        // A shot in the air at game end counts as a miss in the result
+        telemetry.shot_released();
+        let stats = telemetry.game_stats();
+        assert_eq!((stats.shots_made, stats.shot_attempts), (3, 5));
+
+        // Tracker reset between iterations starts a fresh baseline
+        telemetry.reset();
+        tracker = StealTracker::default();
+        tracker.left_attempts = 1;
+        telemetry.observe_steals(&tracker);
+        assert_eq!(telemetry.steals.attempts, 1);
+        assert_eq!(telemetry.shots.rate(), None);
+    }
+}
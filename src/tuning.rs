@@ -348,6 +348,7 @@ impl PhysicsTweaks {
 }
 
 pub fn load_gameplay_tuning_from_file(path: &str) -> Result<GameplayTuning, String> {
+    let path = &crate::config_dir::resolve(path);
     let contents =
         std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
     serde_json::from_str(&contents).map_err(|e| format!("Failed to parse {}: {}", path, e))
@@ -0,0 +1,244 @@
+//! Ability HUD cluster - compact cooldown bars per player
+//!
+//! Each player gets a small panel of bars, one per ability. Only the steal
+//! cooldown exists today; new abilities add an `AbilityKind` variant and a
+//! fill source in `update_ability_hud`. The panel either follows its player
+//! or sits in that team's top corner of the arena.
+
+use bevy::prelude::*;
+
+use crate::constants::PLAYER_SIZE;
+use crate::levels::ArenaDims;
+use crate::player::{Player, Team};
+use crate::steal::StealCooldown;
+
+use super::hud::{HudElement, HudElementKind};
+
+/// Bar width in world pixels
+const BAR_WIDTH: f32 = 36.0;
+/// Bar height in world pixels
+const BAR_HEIGHT: f32 = 4.0;
+/// Gap between stacked bars
+const BAR_GAP: f32 = 2.0;
+/// Padding between the panel edge and its bars
+const PANEL_PAD: f32 = 2.0;
+/// Distance from the arena corner when anchored to corners
+const CORNER_MARGIN: f32 = 40.0;
+
+const PANEL_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.45);
+const BAR_BG_COLOR: Color = Color::srgba(0.2, 0.2, 0.25, 0.8);
+const STEAL_READY_COLOR: Color = Color::srgba(0.3, 0.9, 0.4, 0.9);
+const STEAL_COOLING_COLOR: Color = Color::srgba(0.3, 0.6, 0.9, 0.9);
+
+/// Where ability clusters are drawn
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AbilityHudAnchor {
+    /// Above each player's head
+    #[default]
+    FollowPlayer,
+    /// Fixed in the team's top corner (left team top-left, right team top-right)
+    Corners,
+}
+
+impl AbilityHudAnchor {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AbilityHudAnchor::FollowPlayer => "player",
+            AbilityHudAnchor::Corners => "corners",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Self {
+        match s {
+            "corners" => AbilityHudAnchor::Corners,
+            _ => AbilityHudAnchor::FollowPlayer,
+        }
+    }
+}
+
+/// One row in the cluster
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AbilityKind {
+    Steal,
+}
+
+impl AbilityKind {
+    /// Rows in display order (top to bottom)
+    pub const ALL: [AbilityKind; 1] = [AbilityKind::Steal];
+}
+
+/// Panel root for one player's cluster
+#[derive(Component)]
+pub struct AbilityCluster {
+    pub player: Entity,
+    /// Longest steal cooldown seen since it was last ready, so the bar drains from full
+    /// whatever the cooldown length (success, fail, out of range, victim)
+    pub steal_peak: f32,
+}
+
+/// Fill sprite of one bar inside a cluster
+#[derive(Component)]
+pub struct AbilityBarFill(pub AbilityKind);
+
+/// Bar fill (0..=1) for a cooldown: 1.0 when ready, draining to 0 right after it starts
+pub fn cooldown_fill(remaining: f32, peak: f32) -> f32 {
+    if remaining <= 0.0 || peak <= 0.0 {
+        1.0
+    } else {
+        (1.0 - remaining / peak).clamp(0.0, 1.0)
+    }
+}
+
+/// Cluster center for a player under the given anchor
+pub fn cluster_position(
+    anchor: AbilityHudAnchor,
+    team: Team,
+    player_pos: Vec2,
+    arena: &ArenaDims,
+) -> Vec2 {
+    match anchor {
+        AbilityHudAnchor::FollowPlayer => player_pos + Vec2::new(0.0, PLAYER_SIZE.y / 2.0 + 30.0),
+        AbilityHudAnchor::Corners => {
+            let x = arena.wall_inner() - CORNER_MARGIN - BAR_WIDTH / 2.0;
+            let y = arena.ceiling_y() - CORNER_MARGIN;
+            match team {
+                Team::Left => Vec2::new(-x, y),
+                Team::Right => Vec2::new(x, y),
+            }
+        }
+    }
+}
+
+/// Spawn an ability cluster for a player (a top-level entity, positioned each frame)
+pub fn spawn_ability_cluster(commands: &mut Commands, player_entity: Entity) {
+    let rows = AbilityKind::ALL.len() as f32;
+    let panel_height = rows * BAR_HEIGHT + (rows - 1.0) * BAR_GAP + PANEL_PAD * 2.0;
+
+    let panel = commands
+        .spawn((
+            Sprite::from_color(
+                PANEL_COLOR,
+                Vec2::new(BAR_WIDTH + PANEL_PAD * 2.0, panel_height),
+            ),
+            Transform::from_xyz(0.0, 0.0, 5.0),
+            Visibility::Hidden,
+            AbilityCluster {
+                player: player_entity,
+                steal_peak: 0.0,
+            },
+            HudElement::positioned(HudElementKind::AbilityGauges),
+        ))
+        .id();
+
+    for (i, kind) in AbilityKind::ALL.into_iter().enumerate() {
+        let y =
+            panel_height / 2.0 - PANEL_PAD - BAR_HEIGHT / 2.0 - i as f32 * (BAR_HEIGHT + BAR_GAP);
+        let bg = commands
+            .spawn((
+                Sprite::from_color(BAR_BG_COLOR, Vec2::new(BAR_WIDTH, BAR_HEIGHT)),
+                Transform::from_xyz(0.0, y, 0.1),
+            ))
+            .id();
+        let fill = commands
+            .spawn((
+                Sprite::from_color(STEAL_READY_COLOR, Vec2::new(BAR_WIDTH, BAR_HEIGHT)),
+                Transform::from_xyz(0.0, y, 0.2),
+                AbilityBarFill(kind),
+            ))
+            .id();
+        commands.entity(panel).add_children(&[bg, fill]);
+    }
+}
+
+/// Position clusters and update their bars
+#[allow(clippy::type_complexity)]
+pub fn update_ability_hud(
+    anchor: Res<AbilityHudAnchor>,
+    arena: Res<ArenaDims>,
+    players: Query<(&Transform, &Team, &StealCooldown), With<Player>>,
+    mut clusters: Query<
+        (
+            &mut AbilityCluster,
+            &mut Transform,
+            &mut Visibility,
+            &Children,
+        ),
+        Without<Player>,
+    >,
+    mut fills: Query<
+        (&AbilityBarFill, &mut Sprite, &mut Transform),
+        (Without<AbilityCluster>, Without<Player>),
+    >,
+) {
+    for (mut cluster, mut transform, mut visibility, children) in &mut clusters {
+        let Ok((player_transform, team, steal_cooldown)) = players.get(cluster.player) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        *visibility = Visibility::Inherited;
+
+        let pos = cluster_position(
+            *anchor,
+            *team,
+            player_transform.translation.truncate(),
+            &arena,
+        );
+        transform.translation.x = pos.x;
+        transform.translation.y = pos.y;
+
+        if steal_cooldown.0 <= 0.0 {
+            cluster.steal_peak = 0.0;
+        } else if steal_cooldown.0 > cluster.steal_peak {
+            cluster.steal_peak = steal_cooldown.0;
+        }
+
+        for child in children.iter() {
+            let Ok((bar, mut sprite, mut bar_transform)) = fills.get_mut(child) else {
+                continue;
+            };
+            let (fill, color) = match bar.0 {
+                AbilityKind::Steal => {
+                    let fill = cooldown_fill(steal_cooldown.0, cluster.steal_peak);
+                    let color = if fill >= 1.0 {
+                        STEAL_READY_COLOR
+                    } else {
+                        STEAL_COOLING_COLOR
+                    };
+                    (fill, color)
+                }
+            };
+            // Fill grows from the left edge
+            let width = BAR_WIDTH * fill;
+            sprite.custom_size = Some(Vec2::new(width, BAR_HEIGHT));
+            sprite.color = color;
+            bar_transform.translation.x = (width - BAR_WIDTH) / 2.0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cooldown_fill_and_corner_anchor() {
+        assert_eq!(cooldown_fill(0.0, 0.5), 1.0);
+        assert_eq!(cooldown_fill(0.5, 0.5), 0.0);
+        assert!((cooldown_fill(0.25, 1.0) - 0.75).abs() < 1e-6);
+
+        let arena = ArenaDims::default();
+        let player = Vec2::new(100.0, -300.0);
+        let left = cluster_position(AbilityHudAnchor::Corners, Team::Left, player, &arena);
+        let right = cluster_position(AbilityHudAnchor::Corners, Team::Right, player, &arena);
+        assert!(left.x < 0.0 && right.x > 0.0);
+        assert_eq!(left.y, right.y);
+
+        let follow = cluster_position(AbilityHudAnchor::FollowPlayer, Team::Left, player, &arena);
+        assert_eq!(follow.x, player.x);
+        assert!(follow.y > player.y);
+        assert_eq!(
+            AbilityHudAnchor::from_str(AbilityHudAnchor::Corners.as_str()),
+            AbilityHudAnchor::Corners
+        );
+    }
+}
@@ -0,0 +1,145 @@
+//! AI goal labels - a short line above each AI player saying what it's doing
+//!
+//! Lighter than the Tab debug overlay: just the current goal in plain words and
+//! the navigation step it's on ("Chasing ball - jump 2/3"). An assist, off by
+//! default; `--ai-labels` or the L key turns it on.
+
+use bevy::prelude::*;
+
+use crate::ai::{AiGoal, AiNavState, AiState, NavAction};
+use crate::constants::{PLAYER_SIZE, TEXT_SECONDARY};
+use crate::player::{HumanControlled, Player};
+
+use super::Assists;
+
+/// Height of the label's center above the player's center
+const LABEL_OFFSET_Y: f32 = PLAYER_SIZE.y / 2.0 + 34.0;
+const LABEL_FONT_SIZE: f32 = 13.0;
+
+/// Label text above a player (hidden for human-controlled players)
+#[derive(Component)]
+pub struct AiGoalLabel;
+
+/// What the AI is doing, in words a player reads at a glance
+pub fn goal_label(goal: AiGoal) -> &'static str {
+    match goal {
+        AiGoal::Idle => "Idle",
+        AiGoal::ChaseBall => "Chasing ball",
+        AiGoal::AttackWithBall => "Attacking",
+        AiGoal::ChargeShot => "Charging shot",
+        AiGoal::AttemptSteal => "Going for steal",
+        AiGoal::InterceptDefense => "Cutting off shot",
+        AiGoal::PressureDefense => "Pressuring",
+        AiGoal::GetOpen => "Getting open",
+    }
+}
+
+/// Current navigation step, e.g. "jump 2/3" (None when not navigating)
+pub fn nav_status(nav: &AiNavState) -> Option<String> {
+    if !nav.active {
+        return None;
+    }
+    let step = match nav.current_action()? {
+        NavAction::WalkTo { .. } => "walk",
+        NavAction::JumpAt { .. } => "jump",
+        NavAction::DropFrom { .. } | NavAction::WalkOffEdge { .. } => "drop",
+        NavAction::EnterPortal { .. } => "portal",
+        NavAction::LaunchAt { .. } => "bounce pad",
+    };
+    Some(format!(
+        "{} {}/{}",
+        step,
+        nav.path_index + 1,
+        nav.current_path.len()
+    ))
+}
+
+/// Full label text
+pub fn ai_label_text(goal: AiGoal, nav: &AiNavState) -> String {
+    match nav_status(nav) {
+        Some(status) => format!("{} - {}", goal_label(goal), status),
+        None => goal_label(goal).to_string(),
+    }
+}
+
+/// Give each new player a (hidden) label
+pub fn spawn_ai_goal_labels(mut commands: Commands, players: Query<Entity, Added<Player>>) {
+    for player in &players {
+        let label = commands
+            .spawn((
+                Text2d::new(""),
+                TextFont {
+                    font_size: LABEL_FONT_SIZE,
+                    ..default()
+                },
+                TextColor(TEXT_SECONDARY),
+                Transform::from_xyz(0.0, LABEL_OFFSET_Y, 1.0),
+                Visibility::Hidden,
+                AiGoalLabel,
+            ))
+            .id();
+        commands.entity(player).add_child(label);
+    }
+}
+
+/// L toggles the labels for this session
+pub fn toggle_ai_goal_labels(keyboard: Res<ButtonInput<KeyCode>>, mut assists: ResMut<Assists>) {
+    if keyboard.just_pressed(KeyCode::KeyL) {
+        assists.ai_goal_labels = !assists.ai_goal_labels;
+    }
+}
+
+/// Refresh label text; show labels on AI players only
+#[allow(clippy::type_complexity)]
+pub fn update_ai_goal_labels(
+    assists: Res<Assists>,
+    players: Query<(&AiState, &AiNavState, Has<HumanControlled>, &Children), With<Player>>,
+    mut labels: Query<(&mut Text2d, &mut Visibility), With<AiGoalLabel>>,
+) {
+    for (ai_state, nav, human, children) in &players {
+        for child in children.iter() {
+            let Ok((mut text, mut visibility)) = labels.get_mut(child) else {
+                continue;
+            };
+            if !assists.ai_goal_labels || human {
+                *visibility = Visibility::Hidden;
+                continue;
+            }
+            *visibility = Visibility::Inherited;
+            let label = ai_label_text(ai_state.current_goal, nav);
+            if text.0 != label {
+                text.0 = label;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ai_label_text_shows_goal_and_nav_step() {
+        let mut nav = AiNavState::default();
+        assert_eq!(ai_label_text(AiGoal::ChaseBall, &nav), "Chasing ball");
+
+        nav.active = true;
+        nav.current_path = vec![
+            NavAction::WalkTo { x: 0.0 },
+            NavAction::JumpAt {
+                x: 10.0,
+                hold_duration: 0.3,
+            },
+            NavAction::DropFrom { x: 40.0 },
+        ];
+        nav.path_index = 1;
+        assert_eq!(
+            ai_label_text(AiGoal::InterceptDefense, &nav),
+            "Cutting off shot - jump 2/3"
+        );
+
+        // Active but past the end of the path: goal only
+        nav.path_index = 3;
+        assert_eq!(nav_status(&nav), None);
+    }
+}
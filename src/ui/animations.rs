@@ -2,8 +2,8 @@
 
 use bevy::prelude::*;
 
-use crate::ball::{Ball, BallPulse, BallState};
-use crate::constants::{BALL_PICKUP_RADIUS, BALL_SIZE};
+use crate::ball::{Ball, BallPhysics, BallPulse, BallState};
+use crate::constants::BALL_PICKUP_RADIUS;
 use crate::player::{HoldingBall, Player};
 
 /// Score flash animation component
@@ -50,13 +50,22 @@ pub fn animate_score_flash(
 pub fn animate_pickable_ball(
     time: Res<Time>,
     players: Query<(&Transform, Option<&HoldingBall>), With<Player>>,
-    mut ball_query: Query<(&Transform, &BallState, &mut Sprite, &mut BallPulse), With<Ball>>,
+    mut ball_query: Query<
+        (
+            &Transform,
+            &BallState,
+            &mut Sprite,
+            &mut BallPulse,
+            &BallPhysics,
+        ),
+        With<Ball>,
+    >,
 ) {
-    for (ball_transform, ball_state, mut sprite, mut pulse) in &mut ball_query {
+    for (ball_transform, ball_state, mut sprite, mut pulse, physics) in &mut ball_query {
         // Only pulse if ball is Free
         if *ball_state != BallState::Free {
             // Reset to normal when not free (white = no tint)
-            sprite.custom_size = Some(BALL_SIZE);
+            sprite.custom_size = Some(physics.size());
             sprite.color = Color::WHITE;
             pulse.timer = 0.0;
             continue;
@@ -85,7 +94,7 @@ pub fn animate_pickable_ball(
 
             // Size pulse: 100% to 103%
             let scale = 1.0 + 0.03 * pulse_factor;
-            sprite.custom_size = Some(BALL_SIZE * scale);
+            sprite.custom_size = Some(physics.size() * scale);
 
             // Gold tint pulse (white → gold → white)
             // Gold tint reduces green and blue to create warm golden glow
@@ -96,7 +105,7 @@ pub fn animate_pickable_ball(
             );
         } else {
             // Reset to normal (white = no tint)
-            sprite.custom_size = Some(BALL_SIZE);
+            sprite.custom_size = Some(physics.size());
             sprite.color = Color::WHITE;
             pulse.timer = 0.0;
         }
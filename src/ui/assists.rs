@@ -0,0 +1,39 @@
+//! Assists - optional feedback overlays the player can turn off
+//!
+//! Chosen in the setup wizard and stored in `init_settings.json`. Systems that
+//! draw an assist read this resource and keep their sprites hidden when it's off.
+
+use bevy::prelude::*;
+
+use crate::settings::InitSettings;
+
+/// Which assists are on
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Assists {
+    /// Release vs ideal markers on the charge gauge after a shot
+    pub shot_feedback: bool,
+    /// Steal cooldown, vulnerability and denial indicators
+    pub steal_indicators: bool,
+    /// Current goal and nav step above AI players
+    pub ai_goal_labels: bool,
+}
+
+impl Default for Assists {
+    fn default() -> Self {
+        Self {
+            shot_feedback: true,
+            steal_indicators: true,
+            ai_goal_labels: false,
+        }
+    }
+}
+
+impl Assists {
+    pub fn from_settings(settings: &InitSettings) -> Self {
+        Self {
+            shot_feedback: settings.shot_feedback,
+            steal_indicators: settings.steal_indicators,
+            ai_goal_labels: settings.ai_goal_labels,
+        }
+    }
+}
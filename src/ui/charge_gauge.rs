@@ -4,9 +4,11 @@ use bevy::prelude::*;
 
 use crate::constants::*;
 use crate::player::{Facing, HoldingBall, Player};
-use crate::shooting::ChargingShot;
+use crate::shooting::{ChargeFeedback, ChargingShot};
 use crate::tuning::PhysicsTweaks;
 
+use super::Assists;
+
 /// Charge gauge background component
 #[derive(Component)]
 pub struct ChargeGaugeBackground;
@@ -15,6 +17,51 @@ pub struct ChargeGaugeBackground;
 #[derive(Component)]
 pub struct ChargeGaugeFill;
 
+/// Post-release marker at the charge the shot was released with
+#[derive(Component)]
+pub struct ChargeReleaseMarker;
+
+/// Post-release marker at the ideal charge for that shot
+#[derive(Component)]
+pub struct ChargeIdealMarker;
+
+/// Release within this much of the ideal charge counts as on target
+const CHARGE_FEEDBACK_TOLERANCE: f32 = 0.1;
+
+const RELEASE_ON_TARGET_COLOR: Color = Color::srgb(0.2, 0.9, 0.3); // Green - well calibrated
+const RELEASE_OVER_COLOR: Color = Color::srgb(1.0, 0.8, 0.0); // Yellow - charged longer than needed
+const RELEASE_UNDER_COLOR: Color = Color::srgb(0.9, 0.2, 0.2); // Red - released too early
+const IDEAL_MARKER_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.8);
+/// Fill tint for a charge spent on the move (RGB, blended in by unsteadiness)
+const UNSTEADY_FILL_RGB: [f32; 3] = [0.3, 0.45, 1.0];
+
+/// Spawn the post-release markers for a player's charge gauge
+pub fn spawn_charge_feedback_markers(commands: &mut Commands, player_entity: Entity, gauge_x: f32) {
+    // Release marker: wider than the gauge so it reads as a ring around it
+    let release = commands
+        .spawn((
+            Sprite::from_color(
+                RELEASE_ON_TARGET_COLOR,
+                Vec2::new(CHARGE_GAUGE_WIDTH + 6.0, 3.0),
+            ),
+            Transform::from_xyz(gauge_x, 0.0, 0.7),
+            Visibility::Hidden,
+            ChargeReleaseMarker,
+        ))
+        .id();
+    commands.entity(player_entity).add_child(release);
+
+    let ideal = commands
+        .spawn((
+            Sprite::from_color(IDEAL_MARKER_COLOR, Vec2::new(CHARGE_GAUGE_WIDTH, 2.0)),
+            Transform::from_xyz(gauge_x, 0.0, 0.65),
+            Visibility::Hidden,
+            ChargeIdealMarker,
+        ))
+        .id();
+    commands.entity(player_entity).add_child(ideal);
+}
+
 /// Update charge gauge display
 pub fn update_charge_gauge(
     tweaks: Res<PhysicsTweaks>,
@@ -55,10 +102,72 @@ pub fn update_charge_gauge(
                     let y_offset = -fill_height / 2.0 * (1.0 - charge_pct);
                     transform.translation.y = y_offset;
 
-                    // Color transition: green (0%) -> red (100%)
-                    let r = charge_pct * 0.9;
-                    let g = (1.0 - charge_pct) * 0.8;
-                    sprite.color = Color::srgb(r, g, 0.0);
+                    // Color transition: green (0%) -> red (100%), shifted toward blue
+                    // the more of the charge was spent moving or airborne
+                    let steady = [charge_pct * 0.9, (1.0 - charge_pct) * 0.8, 0.0];
+                    let u = charging.unsteadiness();
+                    let [r, g, b] =
+                        [0, 1, 2].map(|i| steady[i] + (UNSTEADY_FILL_RGB[i] - steady[i]) * u);
+                    sprite.color = Color::srgb(r, g, b);
+                }
+            }
+        }
+    }
+}
+
+/// Show where the last shot was released against its ideal charge, then fade out
+#[allow(clippy::type_complexity)]
+pub fn update_charge_feedback(
+    time: Res<Time>,
+    assists: Res<Assists>,
+    mut player_query: Query<(&Facing, &Children, Option<&mut ChargeFeedback>), With<Player>>,
+    mut release_query: Query<
+        (&mut Sprite, &mut Transform, &mut Visibility),
+        (With<ChargeReleaseMarker>, Without<ChargeIdealMarker>),
+    >,
+    mut ideal_query: Query<
+        (&mut Transform, &mut Visibility),
+        (With<ChargeIdealMarker>, Without<ChargeReleaseMarker>),
+    >,
+) {
+    let fill_height = CHARGE_GAUGE_HEIGHT - 2.0;
+    // Gauge runs bottom (0%) to top (100%)
+    let level_y = |pct: f32| -fill_height / 2.0 + pct.clamp(0.0, 1.0) * fill_height;
+
+    for (facing, children, feedback) in &mut player_query {
+        let gauge_x = -facing.0 * (PLAYER_SIZE.x / 4.0);
+        let shown = feedback.and_then(|mut feedback| {
+            feedback.timer -= time.delta_secs();
+            (feedback.timer > 0.0).then_some((feedback.release_pct, feedback.ideal_pct))
+        });
+        let shown = shown.filter(|_| assists.shot_feedback);
+        let visibility = if shown.is_some() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+
+        for child in children.iter() {
+            if let Ok((mut sprite, mut transform, mut vis)) = release_query.get_mut(child) {
+                *vis = visibility;
+                if let Some((release, ideal)) = shown {
+                    transform.translation.x = gauge_x;
+                    transform.translation.y = level_y(release);
+                    let delta = release - ideal;
+                    sprite.color = if delta.abs() <= CHARGE_FEEDBACK_TOLERANCE {
+                        RELEASE_ON_TARGET_COLOR
+                    } else if delta > 0.0 {
+                        RELEASE_OVER_COLOR
+                    } else {
+                        RELEASE_UNDER_COLOR
+                    };
+                }
+            }
+            if let Ok((mut transform, mut vis)) = ideal_query.get_mut(child) {
+                *vis = visibility;
+                if let Some((_, ideal)) = shown {
+                    transform.translation.x = gauge_x;
+                    transform.translation.y = level_y(ideal);
                 }
             }
         }
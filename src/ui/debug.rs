@@ -58,6 +58,7 @@ pub enum DownOption {
     Movement,
     Ball,
     Shooting,
+    Steal,
 }
 
 impl DownOption {
@@ -66,7 +67,8 @@ impl DownOption {
             DownOption::Composite => DownOption::Movement,
             DownOption::Movement => DownOption::Ball,
             DownOption::Ball => DownOption::Shooting,
-            DownOption::Shooting => DownOption::Composite,
+            DownOption::Shooting => DownOption::Steal,
+            DownOption::Steal => DownOption::Composite,
         }
     }
 
@@ -76,6 +78,7 @@ impl DownOption {
             DownOption::Movement => "Movement",
             DownOption::Ball => "Ball",
             DownOption::Shooting => "Shooting",
+            DownOption::Steal => "Steal",
         }
     }
 
@@ -84,6 +87,7 @@ impl DownOption {
             "Movement" => DownOption::Movement,
             "Ball" => DownOption::Ball,
             "Shooting" => DownOption::Shooting,
+            "Steal" => DownOption::Steal,
             _ => DownOption::Composite, // Default
         }
     }
@@ -243,12 +247,13 @@ pub fn update_debug_text(
             None => "?",
         };
         **text = format!(
-            "Last Shot: {:.0}deg {:.0}u/s | Variance: base {:.0}% + air {:.0}% + move {:.0}% + dist {:.0}% = {:.0}% | Req speed: {:.0} | Target: {}{}",
+            "Last Shot: {:.0}deg {:.0}u/s | Variance: base {:.0}% + air {:.0}% + move {:.0}% + charge-move {:.0}% + dist {:.0}% = {:.0}% | Req speed: {:.0} | Target: {}{}",
             shot_info.angle_degrees,
             shot_info.speed,
             shot_info.base_variance * 100.0,
             shot_info.air_penalty * 100.0,
             shot_info.move_penalty * 100.0,
+            shot_info.charge_move_penalty * 100.0,
             shot_info.distance_variance * 100.0,
             shot_info.total_variance * 100.0,
             shot_info.required_speed,
@@ -273,7 +278,10 @@ pub fn cycle_viewport(
 }
 
 /// Apply current viewport scale to window (camera uses FixedVertical scaling mode)
-fn apply_viewport(viewport_scale: &ViewportScale, window_query: &mut Query<&mut Window>) {
+pub(super) fn apply_viewport(
+    viewport_scale: &ViewportScale,
+    window_query: &mut Query<&mut Window>,
+) {
     let (width, height, label) = viewport_scale.current();
 
     // Change window size - use scale_factor_override 1.0 for consistent HiDPI behavior
@@ -424,7 +432,7 @@ pub fn unified_cycle_system(
             current_settings.mark_dirty();
         }
         CycleDirection::Down => {
-            // Presets: Composite, Movement, Ball, Shooting
+            // Presets: Composite, Movement, Ball, Shooting, Steal
             match cycle_selection.down_option {
                 DownOption::Composite => {
                     let num = preset_db.composite_len();
@@ -500,6 +508,18 @@ pub fn unified_cycle_system(
                         info!("Shooting: {}", p.name);
                     }
                 }
+                DownOption::Steal => {
+                    let num = preset_db.steal_len();
+                    if cycle_next {
+                        current_presets.steal = (current_presets.steal + 1) % num;
+                    } else if cycle_prev {
+                        current_presets.steal = (current_presets.steal + num - 1) % num;
+                    }
+                    current_presets.mark_apply();
+                    if let Some(p) = preset_db.get_steal(current_presets.steal) {
+                        info!("Steal: {}", p.name);
+                    }
+                }
             }
         }
         CycleDirection::Left => {
@@ -658,6 +678,10 @@ pub fn update_cycle_indicator(
             .get_shooting(current_presets.shooting)
             .map(|p| p.name.clone())
             .unwrap_or_else(|| "?".to_string()),
+        DownOption::Steal => preset_db
+            .get_steal(current_presets.steal)
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| "?".to_string()),
     };
 
     // AI profiles
@@ -697,17 +721,24 @@ pub fn update_cycle_indicator(
 
     let right_value = match cycle_selection.right_option {
         RightOption::Level => {
-            let level_name = level_db
-                .get_by_id(&current_level.0)
-                .map(|l| l.name.as_str())
-                .unwrap_or("?");
+            let level = level_db.get_by_id(&current_level.0);
+            let level_name = level.map(|l| l.name.as_str()).unwrap_or("?");
             let level_ids: Vec<&str> = level_db.all().iter().map(|l| l.id.as_str()).collect();
             let display_num = level_ids
                 .iter()
                 .position(|id| *id == current_level.0)
                 .map(|i| i + 1)
                 .unwrap_or(0);
-            format!("{}/{} {}", display_num, level_db.len(), level_name)
+            match level.and_then(|l| l.rating.as_ref()) {
+                Some(rating) => format!(
+                    "{}/{} {} ({})",
+                    display_num,
+                    level_db.len(),
+                    level_name,
+                    rating.tag()
+                ),
+                None => format!("{}/{} {}", display_num, level_db.len(), level_name),
+            }
         }
         RightOption::Palette => format!("{}", current_palette.0),
         RightOption::BallStyle => ball_query
@@ -1,21 +1,364 @@
-//! HUD components and systems (score/level display)
+//! HUD components and systems (score/level display) and the HUD layout manager
+//!
+//! F11 opens the layout editor: HUD elements (scoreboard, cycle menu, debug
+//! line, ability gauges) can be dragged and scaled with the mouse. Layouts are
+//! saved per viewport preset in the init settings, and `apply_hud_layout` places
+//! the elements for the current preset every frame.
 
 use bevy::prelude::*;
 
-use crate::scoring::Score;
+use crate::constants::SHOT_CLOCK_HUD_SECS;
+use crate::player::Team;
+use crate::scoring::{PossessionArrow, Score, ShotClock};
+use crate::settings::{CurrentSettings, HudElementLayout, HudLayout};
+use crate::teams::TeamIdentity;
+
+use super::ViewportScale;
 
 /// Score and level text component
 #[derive(Component)]
 pub struct ScoreLevelText;
 
+/// Score text, with the possession arrow on the side it points to when that rule is on,
+/// and team names beside their scores when any side is named
+pub fn score_text(
+    score: &Score,
+    arrow: Option<&PossessionArrow>,
+    teams: Option<&TeamIdentity>,
+) -> String {
+    let (left, right) = match teams.filter(|t| t.has_names()) {
+        Some(teams) => (
+            format!("{} {}", teams.display_name(Team::Left), score.left),
+            format!("{} {}", score.right, teams.display_name(Team::Right)),
+        ),
+        None => (score.left.to_string(), score.right.to_string()),
+    };
+    match arrow.filter(|a| a.enabled).map(|a| a.next) {
+        Some(Team::Left) => format!("< {} - {}", left, right),
+        Some(Team::Right) => format!("{} - {} >", left, right),
+        None => format!("{} - {}", left, right),
+    }
+}
+
+/// Shot clock countdown for the line under the score, pointing at the side
+/// whose clock it is (only for the last SHOT_CLOCK_HUD_SECS)
+pub fn shot_clock_text(shot_clock: &ShotClock) -> Option<String> {
+    let (team, remaining) = shot_clock
+        .running()
+        .filter(|(_, remaining)| *remaining <= SHOT_CLOCK_HUD_SECS)?;
+    let secs = remaining.ceil() as u32;
+    Some(match team {
+        Team::Left => format!("< {}", secs),
+        Team::Right => format!("{} >", secs),
+    })
+}
+
 /// Update score display
 pub fn update_score_level_text(
     score: Res<Score>,
+    arrow: Option<Res<PossessionArrow>>,
+    teams: Option<Res<TeamIdentity>>,
+    shot_clock: Option<Res<ShotClock>>,
     mut text_query: Query<&mut Text2d, With<ScoreLevelText>>,
 ) {
     let Ok(mut text) = text_query.single_mut() else {
         return;
     };
 
-    **text = format!("{} - {}", score.left, score.right);
+    let score = score_text(&score, arrow.as_deref(), teams.as_deref());
+    **text = match shot_clock.as_deref().and_then(shot_clock_text) {
+        Some(clock) => format!("{}\n{}", score, clock),
+        None => score,
+    };
+}
+
+/// HUD pieces the layout editor can move and scale
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HudElementKind {
+    /// Score text above the arena
+    Scoreboard,
+    /// D-pad cycle menu lines (top left)
+    CycleMenu,
+    /// Last-shot debug line on the floor
+    DebugLine,
+    /// Ability cooldown gauges (moved relative to where their anchor puts them)
+    AbilityGauges,
+}
+
+impl HudElementKind {
+    pub const ALL: [HudElementKind; 4] = [
+        HudElementKind::Scoreboard,
+        HudElementKind::CycleMenu,
+        HudElementKind::DebugLine,
+        HudElementKind::AbilityGauges,
+    ];
+
+    /// Key in the saved layout
+    pub fn key(self) -> &'static str {
+        match self {
+            HudElementKind::Scoreboard => "scoreboard",
+            HudElementKind::CycleMenu => "cycle_menu",
+            HudElementKind::DebugLine => "debug_line",
+            HudElementKind::AbilityGauges => "ability_gauges",
+        }
+    }
+
+    /// Click target size for elements without a sized sprite (text)
+    fn hit_size(self) -> Vec2 {
+        match self {
+            HudElementKind::Scoreboard => Vec2::new(260.0, 32.0),
+            HudElementKind::CycleMenu => Vec2::new(220.0, 20.0),
+            HudElementKind::DebugLine => Vec2::new(900.0, 18.0),
+            HudElementKind::AbilityGauges => Vec2::new(60.0, 20.0),
+        }
+    }
+}
+
+/// A HUD entity the layout manager places. `home` is its default position; None
+/// for elements their own system positions every frame (the offset is added after).
+#[derive(Component)]
+pub struct HudElement {
+    pub kind: HudElementKind,
+    pub home: Option<Vec2>,
+}
+
+impl HudElement {
+    pub fn at(kind: HudElementKind, home: Vec2) -> Self {
+        Self {
+            kind,
+            home: Some(home),
+        }
+    }
+
+    pub fn positioned(kind: HudElementKind) -> Self {
+        Self { kind, home: None }
+    }
+}
+
+/// Smallest and largest HUD element scale
+pub const HUD_SCALE_RANGE: (f32, f32) = (0.5, 2.5);
+/// Scale change per mouse wheel notch
+const HUD_SCALE_STEP: f32 = 1.1;
+
+const EDITOR_OUTLINE_COLOR: Color = Color::srgba(1.0, 1.0, 1.0, 0.4);
+const EDITOR_SELECTED_COLOR: Color = Color::srgb(1.0, 0.8, 0.0);
+
+/// HUD layout editor state (F11)
+#[derive(Resource, Default)]
+pub struct HudLayoutEditor {
+    pub active: bool,
+    pub selected: Option<HudElementKind>,
+    /// Cursor position at the last drag step
+    drag_from: Option<Vec2>,
+    /// Layout changed since the editor opened
+    changed: bool,
+}
+
+/// Placement of `kind` in a layout (default when not saved)
+pub fn element_layout(layout: Option<&HudLayout>, kind: HudElementKind) -> HudElementLayout {
+    layout
+        .and_then(|layout| layout.get(kind.key()))
+        .copied()
+        .unwrap_or_default()
+}
+
+/// Move an element by `delta` in a layout
+pub fn drag_element(layout: &mut HudLayout, kind: HudElementKind, delta: Vec2) {
+    let entry = layout.entry(kind.key().to_string()).or_default();
+    entry.offset[0] += delta.x;
+    entry.offset[1] += delta.y;
+}
+
+/// Scale an element by `factor` in a layout (clamped to HUD_SCALE_RANGE)
+pub fn scale_element(layout: &mut HudLayout, kind: HudElementKind, factor: f32) {
+    let entry = layout.entry(kind.key().to_string()).or_default();
+    entry.scale = (entry.scale * factor).clamp(HUD_SCALE_RANGE.0, HUD_SCALE_RANGE.1);
+}
+
+/// F11 opens/closes the layout editor; closing saves the layout
+pub fn toggle_hud_layout_editor(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut editor: ResMut<HudLayoutEditor>,
+    mut settings: ResMut<CurrentSettings>,
+    viewport: Res<ViewportScale>,
+) {
+    if !keyboard.just_pressed(KeyCode::F11) {
+        return;
+    }
+    editor.active = !editor.active;
+    editor.selected = None;
+    editor.drag_from = None;
+    if editor.active {
+        info!(
+            "HUD layout editor: {} (drag to move, wheel to scale, R to reset)",
+            viewport.current().2
+        );
+    } else if editor.changed {
+        editor.changed = false;
+        settings.mark_dirty();
+        info!("HUD layout saved for {}", viewport.current().2);
+    }
+}
+
+/// Layout editor: click an element to select it, drag to move it, mouse wheel
+/// to scale it, R to put it back. Edits the current viewport's layout.
+#[allow(clippy::too_many_arguments)]
+pub fn hud_layout_editor(
+    mouse: Res<ButtonInput<MouseButton>>,
+    scroll: Res<bevy::input::mouse::AccumulatedMouseScroll>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut editor: ResMut<HudLayoutEditor>,
+    mut settings: ResMut<CurrentSettings>,
+    viewport: Res<ViewportScale>,
+    windows: Query<&Window>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    elements: Query<(&HudElement, &Transform, Option<&Sprite>, &Visibility)>,
+    mut gizmos: Gizmos,
+) {
+    if !editor.active {
+        return;
+    }
+    let viewport_label = viewport.current().2;
+    let hit_box = |element: &HudElement, transform: &Transform, sprite: Option<&Sprite>| {
+        let size = sprite
+            .and_then(|s| s.custom_size)
+            .unwrap_or(element.kind.hit_size());
+        Rect::from_center_size(transform.translation.truncate(), size * transform.scale.x)
+    };
+
+    let visible = || {
+        elements
+            .iter()
+            .filter(|(.., visibility)| **visibility != Visibility::Hidden)
+    };
+    for (element, transform, sprite, _) in visible() {
+        let color = if editor.selected == Some(element.kind) {
+            EDITOR_SELECTED_COLOR
+        } else {
+            EDITOR_OUTLINE_COLOR
+        };
+        let rect = hit_box(element, transform, sprite);
+        gizmos.rect_2d(
+            Isometry2d::from_translation(rect.center()),
+            rect.size(),
+            color,
+        );
+    }
+
+    let cursor = windows
+        .iter()
+        .find_map(|w| w.cursor_position())
+        .and_then(|pos| {
+            let (camera, camera_transform) = cameras.iter().next()?;
+            camera.viewport_to_world_2d(camera_transform, pos).ok()
+        });
+
+    let layout = settings
+        .settings
+        .hud_layouts
+        .entry(viewport_label.to_string())
+        .or_default();
+
+    if mouse.just_pressed(MouseButton::Left) {
+        editor.selected = cursor.and_then(|cursor| {
+            visible()
+                .find(|(element, transform, sprite, _)| {
+                    hit_box(element, transform, *sprite).contains(cursor)
+                })
+                .map(|(element, ..)| element.kind)
+        });
+        editor.drag_from = cursor.filter(|_| editor.selected.is_some());
+    }
+    if !mouse.pressed(MouseButton::Left) {
+        editor.drag_from = None;
+    }
+
+    let Some(kind) = editor.selected else {
+        return;
+    };
+    let drag = editor.drag_from.zip(cursor).filter(|(from, to)| from != to);
+    if let Some((from, to)) = drag {
+        drag_element(layout, kind, to - from);
+        editor.drag_from = Some(to);
+        editor.changed = true;
+    }
+    if scroll.delta.y != 0.0 {
+        scale_element(layout, kind, HUD_SCALE_STEP.powf(scroll.delta.y.signum()));
+        editor.changed = true;
+    }
+    if keyboard.just_pressed(KeyCode::KeyR) {
+        layout.remove(kind.key());
+        editor.changed = true;
+    }
+}
+
+/// Layout manager: place HUD elements per the current viewport's saved layout.
+/// Runs after the systems that position elements themselves (hidden ones are
+/// skipped, as their system doesn't reposition them).
+pub fn apply_hud_layout(
+    settings: Res<CurrentSettings>,
+    viewport: Res<ViewportScale>,
+    mut elements: Query<(&HudElement, &mut Transform, &Visibility)>,
+) {
+    let layout = settings.settings.hud_layouts.get(viewport.current().2);
+    for (element, mut transform, visibility) in &mut elements {
+        if element.home.is_none() && *visibility == Visibility::Hidden {
+            continue;
+        }
+        let placement = element_layout(layout, element.kind);
+        let offset = Vec2::from(placement.offset);
+        let base = element
+            .home
+            .unwrap_or_else(|| transform.translation.truncate());
+        let pos = base + offset;
+        transform.translation.x = pos.x;
+        transform.translation.y = pos.y;
+        transform.scale = Vec3::splat(placement.scale);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_edits_per_element() {
+        let mut layout = HudLayout::new();
+        assert_eq!(
+            element_layout(Some(&layout), HudElementKind::Scoreboard),
+            HudElementLayout::default()
+        );
+
+        drag_element(
+            &mut layout,
+            HudElementKind::Scoreboard,
+            Vec2::new(10.0, -5.0),
+        );
+        drag_element(&mut layout, HudElementKind::Scoreboard, Vec2::new(5.0, 0.0));
+        for _ in 0..20 {
+            scale_element(&mut layout, HudElementKind::CycleMenu, HUD_SCALE_STEP);
+        }
+        let scoreboard = element_layout(Some(&layout), HudElementKind::Scoreboard);
+        assert_eq!(scoreboard.offset, [15.0, -5.0]);
+        assert_eq!(scoreboard.scale, 1.0);
+        assert_eq!(
+            element_layout(Some(&layout), HudElementKind::CycleMenu).scale,
+            HUD_SCALE_RANGE.1
+        );
+        assert_eq!(
+            element_layout(Some(&layout), HudElementKind::DebugLine),
+            HudElementLayout::default()
+        );
+
+        // Layouts are saved per viewport and survive a settings round trip
+        let mut settings = crate::settings::InitSettings::default();
+        settings.hud_layouts.insert("1080p".to_string(), layout);
+        let json = serde_json::to_string(&settings).unwrap();
+        let loaded = crate::settings::InitSettings::parse(&json).unwrap();
+        assert_eq!(loaded.hud_layouts, settings.hud_layouts);
+        assert_eq!(
+            element_layout(loaded.hud_layouts.get("720p"), HudElementKind::Scoreboard),
+            HudElementLayout::default()
+        );
+    }
 }
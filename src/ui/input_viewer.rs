@@ -0,0 +1,228 @@
+//! Input viewer - the human's live inputs and a fighting-game style history strip
+//!
+//! A practice aid for training mode: the top line shows what the game reads
+//! this tick (direction, jump / pickup / throw / pass, a buffered jump and the
+//! shot charge), and below it each distinct input state is listed with how many
+//! physics ticks it was held, newest first. Sampled in FixedUpdate right before
+//! `apply_input`, so the counts are the ticks the game actually saw - handy for
+//! learning jump buffering and charge timing. `--input-viewer` or I toggles it.
+
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+use crate::ai::InputState;
+use crate::constants::*;
+use crate::player::HumanControlled;
+use crate::shooting::ChargingShot;
+use crate::tuning::PhysicsTweaks;
+
+/// History rows kept on screen
+const INPUT_HISTORY_ROWS: usize = 16;
+/// Tick counts are capped here (shown as "99+")
+const INPUT_HISTORY_MAX_TICKS: u32 = 99;
+
+/// One input state as the game reads it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InputFrame {
+    /// -1 left, 0 neutral, 1 right
+    pub direction: i8,
+    pub jump: bool,
+    pub pickup: bool,
+    pub throw: bool,
+    pub pass: bool,
+}
+
+impl InputFrame {
+    pub fn from_input(input: &InputState) -> Self {
+        Self {
+            direction: if input.move_x < -0.1 {
+                -1
+            } else if input.move_x > 0.1 {
+                1
+            } else {
+                0
+            },
+            jump: input.jump_held || input.jump_buffer_timer > 0.0,
+            pickup: input.pickup_pressed || input.pickup_held,
+            throw: input.throw_held,
+            pass: input.pass_pressed,
+        }
+    }
+
+    /// Compact row, e.g. "> J . T ."
+    pub fn label(&self) -> String {
+        let button = |on: bool, key: char| if on { key } else { '.' };
+        format!(
+            "{} {} {} {} {}",
+            match self.direction {
+                -1 => '<',
+                1 => '>',
+                _ => '-',
+            },
+            button(self.jump, 'J'),
+            button(self.pickup, 'P'),
+            button(self.throw, 'T'),
+            button(self.pass, 'S'),
+        )
+    }
+}
+
+/// Input viewer state (toggle and history)
+#[derive(Resource, Debug, Clone, Default)]
+pub struct InputViewer {
+    pub visible: bool,
+    /// Distinct input states with the ticks each was held, newest first
+    pub history: VecDeque<(InputFrame, u32)>,
+    /// Latest sample
+    pub live: InputFrame,
+    /// Ticks of jump buffer left (0 = nothing buffered)
+    pub buffered_ticks: u32,
+    /// Shot charge, 0-1
+    pub charge: f32,
+}
+
+impl InputViewer {
+    pub fn new(visible: bool) -> Self {
+        Self {
+            visible,
+            ..default()
+        }
+    }
+
+    /// Add one tick's input: extends the newest row or starts a new one
+    pub fn record(&mut self, frame: InputFrame) {
+        self.live = frame;
+        match self.history.front_mut() {
+            Some((newest, ticks)) if *newest == frame => {
+                *ticks = (*ticks + 1).min(INPUT_HISTORY_MAX_TICKS + 1);
+            }
+            _ => {
+                self.history.push_front((frame, 1));
+                self.history.truncate(INPUT_HISTORY_ROWS);
+            }
+        }
+    }
+
+    /// Overlay text: live line, then the history strip
+    pub fn text(&self) -> String {
+        let mut lines = vec![format!("INPUT (I)   {}", self.live.label())];
+        let mut status = Vec::new();
+        if self.buffered_ticks > 0 {
+            status.push(format!("jump buffered {}f", self.buffered_ticks));
+        }
+        if self.charge > 0.0 {
+            status.push(format!("charge {:.0}%", self.charge * 100.0));
+        }
+        lines.push(status.join("  "));
+        for (frame, ticks) in &self.history {
+            let ticks = if *ticks > INPUT_HISTORY_MAX_TICKS {
+                format!("{}+", INPUT_HISTORY_MAX_TICKS)
+            } else {
+                ticks.to_string()
+            };
+            lines.push(format!("{:>3}  {}", ticks, frame.label()));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Marker for the input viewer text
+#[derive(Component)]
+pub struct InputViewerText;
+
+/// Spawn the (initially hidden) input viewer in the top-left of the arena
+pub fn spawn_input_viewer(commands: &mut Commands) {
+    commands.spawn((
+        Text2d::new(""),
+        TextFont {
+            font_size: 14.0,
+            ..default()
+        },
+        TextLayout::new_with_justify(bevy::text::Justify::Left),
+        TextColor(TEXT_PRIMARY),
+        Transform::from_xyz(-ARENA_WIDTH / 2.0 + 120.0, ARENA_HEIGHT / 2.0 - 230.0, 1.0),
+        Visibility::Hidden,
+        InputViewerText,
+    ));
+}
+
+/// I toggles the input viewer
+pub fn toggle_input_viewer(keyboard: Res<ButtonInput<KeyCode>>, mut viewer: ResMut<InputViewer>) {
+    if keyboard.just_pressed(KeyCode::KeyI) {
+        viewer.visible = !viewer.visible;
+    }
+}
+
+/// Sample the human's input for this tick (run before `apply_input`)
+pub fn record_input_history(
+    time: Res<Time>,
+    tweaks: Res<PhysicsTweaks>,
+    mut viewer: ResMut<InputViewer>,
+    humans: Query<(&InputState, Option<&ChargingShot>), With<HumanControlled>>,
+) {
+    let Some((input, charging)) = humans.iter().next() else {
+        return;
+    };
+    viewer.record(InputFrame::from_input(input));
+    // Time is the fixed timestep here, so this is whole ticks
+    viewer.buffered_ticks =
+        (input.jump_buffer_timer / time.delta_secs().max(1.0 / 240.0)).ceil() as u32;
+    viewer.charge = charging.map_or(0.0, |c| (c.charge_time / tweaks.shot_charge_time).min(1.0));
+}
+
+/// Refresh the overlay text while it's shown
+pub fn update_input_viewer(
+    viewer: Res<InputViewer>,
+    mut texts: Query<(&mut Text2d, &mut Visibility), With<InputViewerText>>,
+) {
+    for (mut text, mut visibility) in &mut texts {
+        if !viewer.visible {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+        *visibility = Visibility::Visible;
+        let content = viewer.text();
+        if text.0 != content {
+            text.0 = content;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_groups_held_inputs() {
+        let mut viewer = InputViewer::new(true);
+        let right = InputFrame {
+            direction: 1,
+            ..default()
+        };
+        let right_jump = InputFrame {
+            jump: true,
+            ..right
+        };
+        for _ in 0..5 {
+            viewer.record(right);
+        }
+        viewer.record(right_jump);
+        viewer.record(right_jump);
+        assert_eq!(viewer.history, [(right_jump, 2), (right, 5)]);
+        assert_eq!(right_jump.label(), "> J . . .");
+        assert!(viewer.text().contains("  2  > J . . .\n  5  > . . . ."));
+
+        // Long holds cap; old rows scroll off
+        for _ in 0..200 {
+            viewer.record(right);
+        }
+        assert!(viewer.text().contains("99+  > . . . ."));
+        for i in 0..INPUT_HISTORY_ROWS * 2 {
+            viewer.record(InputFrame {
+                throw: i % 2 == 0,
+                ..default()
+            });
+        }
+        assert_eq!(viewer.history.len(), INPUT_HISTORY_ROWS);
+    }
+}
@@ -0,0 +1,468 @@
+//! Entity inspector - live component values for players and ball (F10)
+//!
+//! Lists the left player, right player and ball. Enter expands or collapses the
+//! selected entity; expanded entries show Velocity, JumpState, AiState and
+//! BallState values updated every frame. Left/Right edit the selected numeric
+//! field in place (Shift for 10x steps) or flip a bool. Physics overwrites most
+//! values the next tick, so edits are easiest to follow with gameplay frozen (F8).
+
+use bevy::prelude::*;
+
+use crate::ai::AiState;
+use crate::ball::{Ball, BallState};
+use crate::constants::*;
+use crate::player::{JumpState, Player, Team, Velocity};
+
+use super::TweakPanelState;
+
+/// Entities the inspector lists, in display order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InspectorSection {
+    LeftPlayer,
+    RightPlayer,
+    Ball,
+}
+
+impl InspectorSection {
+    pub const ALL: [InspectorSection; 3] = [
+        InspectorSection::LeftPlayer,
+        InspectorSection::RightPlayer,
+        InspectorSection::Ball,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            InspectorSection::LeftPlayer => "Left player",
+            InspectorSection::RightPlayer => "Right player",
+            InspectorSection::Ball => "Ball",
+        }
+    }
+
+    pub fn fields(&self) -> &'static [InspectorField] {
+        match self {
+            InspectorSection::LeftPlayer | InspectorSection::RightPlayer => &PLAYER_FIELDS,
+            InspectorSection::Ball => &BALL_FIELDS,
+        }
+    }
+
+    fn team(&self) -> Option<Team> {
+        match self {
+            InspectorSection::LeftPlayer => Some(Team::Left),
+            InspectorSection::RightPlayer => Some(Team::Right),
+            InspectorSection::Ball => None,
+        }
+    }
+}
+
+/// One component value shown under an expanded entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InspectorField {
+    VelocityX,
+    VelocityY,
+    Jumping,
+    AiGoal,
+    ShotChargeTarget,
+    JumpShotActive,
+    JumpShotTimer,
+    StuckTimer,
+    BallState,
+    ShotPower,
+}
+
+const PLAYER_FIELDS: [InspectorField; 8] = [
+    InspectorField::VelocityX,
+    InspectorField::VelocityY,
+    InspectorField::Jumping,
+    InspectorField::AiGoal,
+    InspectorField::ShotChargeTarget,
+    InspectorField::JumpShotActive,
+    InspectorField::JumpShotTimer,
+    InspectorField::StuckTimer,
+];
+
+const BALL_FIELDS: [InspectorField; 4] = [
+    InspectorField::VelocityX,
+    InspectorField::VelocityY,
+    InspectorField::BallState,
+    InspectorField::ShotPower,
+];
+
+impl InspectorField {
+    pub fn label(&self) -> &'static str {
+        match self {
+            InspectorField::VelocityX => "Velocity.x",
+            InspectorField::VelocityY => "Velocity.y",
+            InspectorField::Jumping => "JumpState.is_jumping",
+            InspectorField::AiGoal => "AiState.current_goal",
+            InspectorField::ShotChargeTarget => "AiState.shot_charge_target",
+            InspectorField::JumpShotActive => "AiState.jump_shot_active",
+            InspectorField::JumpShotTimer => "AiState.jump_shot_timer",
+            InspectorField::StuckTimer => "AiState.stuck_timer",
+            InspectorField::BallState => "BallState",
+            InspectorField::ShotPower => "BallState.power",
+        }
+    }
+
+    /// Step for Left/Right edits (None = read-only, bools flip on any edit)
+    pub fn step(&self) -> Option<f32> {
+        match self {
+            InspectorField::VelocityX | InspectorField::VelocityY => Some(50.0),
+            InspectorField::ShotPower => Some(10.0),
+            InspectorField::ShotChargeTarget | InspectorField::JumpShotTimer => Some(0.05),
+            InspectorField::StuckTimer => Some(0.1),
+            InspectorField::Jumping | InspectorField::JumpShotActive => Some(1.0),
+            InspectorField::AiGoal | InspectorField::BallState => None,
+        }
+    }
+}
+
+/// Inspector visibility, selection and expanded entities
+#[derive(Resource, Debug, Default)]
+pub struct EntityInspector {
+    pub visible: bool,
+    /// Index into `rows()`
+    pub selected: usize,
+    /// Expanded flag per InspectorSection::ALL entry
+    pub expanded: [bool; 3],
+}
+
+impl EntityInspector {
+    /// Visible rows: each entity header, followed by its fields when expanded
+    pub fn rows(&self) -> Vec<(InspectorSection, Option<InspectorField>)> {
+        let mut rows = Vec::new();
+        for (i, section) in InspectorSection::ALL.into_iter().enumerate() {
+            rows.push((section, None));
+            if self.expanded[i] {
+                rows.extend(section.fields().iter().map(|field| (section, Some(*field))));
+            }
+        }
+        rows
+    }
+
+    /// Expand/collapse the selected entity (from its header or any of its fields)
+    pub fn toggle_selected(&mut self) {
+        let rows = self.rows();
+        let Some((section, _)) = rows.get(self.selected) else {
+            return;
+        };
+        let index = InspectorSection::ALL
+            .iter()
+            .position(|s| s == section)
+            .unwrap_or(0);
+        self.expanded[index] = !self.expanded[index];
+        // Keep the selection on the header so collapsing never strands it
+        self.selected = self
+            .rows()
+            .iter()
+            .position(|(s, field)| s == section && field.is_none())
+            .unwrap_or(0);
+    }
+
+    pub fn move_selection(&mut self, down: bool) {
+        let len = self.rows().len();
+        self.selected = if down {
+            (self.selected + 1) % len
+        } else {
+            (self.selected + len - 1) % len
+        };
+    }
+}
+
+/// Inspector panel root
+#[derive(Component)]
+pub struct EntityInspectorPanel;
+
+/// Inspector text (one line per row)
+#[derive(Component)]
+pub struct EntityInspectorText;
+
+/// Spawn the (hidden) inspector panel
+pub fn spawn_entity_inspector(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(10.0),
+                top: Val::Px(60.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(10.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.9)),
+            Visibility::Hidden,
+            EntityInspectorPanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("Entity Inspector (F10 to close)"),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(TEXT_PRIMARY),
+            ));
+            parent.spawn((
+                Text::new("Up/Down: select | Enter: expand | Left/Right: edit (Shift: x10)"),
+                TextFont {
+                    font_size: 12.0,
+                    ..default()
+                },
+                TextColor(TEXT_SECONDARY),
+            ));
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 13.0,
+                    ..default()
+                },
+                TextColor(TEXT_PRIMARY),
+                EntityInspectorText,
+            ));
+        });
+}
+
+/// Apply a Left/Right edit to a numeric value
+fn nudge(value: &mut f32, step: f32, direction: f32) {
+    *value += step * direction;
+}
+
+/// Toggle, navigate, edit and redraw the inspector
+#[allow(clippy::type_complexity)]
+pub fn update_entity_inspector(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    tweak_panel: Res<TweakPanelState>,
+    mut inspector: ResMut<EntityInspector>,
+    mut players: Query<
+        (
+            Entity,
+            &Team,
+            &mut Velocity,
+            &mut JumpState,
+            Option<&mut AiState>,
+        ),
+        With<Player>,
+    >,
+    mut balls: Query<(Entity, &mut Velocity, &mut BallState), (With<Ball>, Without<Player>)>,
+    mut panels: Query<&mut Visibility, With<EntityInspectorPanel>>,
+    mut texts: Query<&mut Text, With<EntityInspectorText>>,
+) {
+    if keyboard.just_pressed(KeyCode::F10) {
+        inspector.visible = !inspector.visible;
+        for mut visibility in &mut panels {
+            *visibility = if inspector.visible {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+        }
+    }
+    if !inspector.visible {
+        return;
+    }
+
+    // The tweak panel owns the arrow keys while it is open
+    if !tweak_panel.panel_visible {
+        if keyboard.just_pressed(KeyCode::ArrowUp) {
+            inspector.move_selection(false);
+        }
+        if keyboard.just_pressed(KeyCode::ArrowDown) {
+            inspector.move_selection(true);
+        }
+        if keyboard.just_pressed(KeyCode::Enter) {
+            inspector.toggle_selected();
+        }
+
+        let direction = if keyboard.just_pressed(KeyCode::ArrowRight) {
+            1.0
+        } else if keyboard.just_pressed(KeyCode::ArrowLeft) {
+            -1.0
+        } else {
+            0.0
+        };
+        let scale = if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight)
+        {
+            10.0
+        } else {
+            1.0
+        };
+        let selected = inspector.rows().get(inspector.selected).copied();
+        if direction != 0.0
+            && let Some((section, Some(field))) = selected
+            && let Some(step) = field.step()
+        {
+            let step = step * scale;
+            match section.team() {
+                Some(team) => {
+                    for (_, player_team, mut velocity, mut jump, ai) in &mut players {
+                        if *player_team != team {
+                            continue;
+                        }
+                        match (field, ai) {
+                            (InspectorField::VelocityX, _) => {
+                                nudge(&mut velocity.0.x, step, direction)
+                            }
+                            (InspectorField::VelocityY, _) => {
+                                nudge(&mut velocity.0.y, step, direction)
+                            }
+                            (InspectorField::Jumping, _) => jump.is_jumping = !jump.is_jumping,
+                            (InspectorField::ShotChargeTarget, Some(mut ai)) => {
+                                nudge(&mut ai.shot_charge_target, step, direction)
+                            }
+                            (InspectorField::JumpShotActive, Some(mut ai)) => {
+                                ai.jump_shot_active = !ai.jump_shot_active
+                            }
+                            (InspectorField::JumpShotTimer, Some(mut ai)) => {
+                                nudge(&mut ai.jump_shot_timer, step, direction)
+                            }
+                            (InspectorField::StuckTimer, Some(mut ai)) => {
+                                nudge(&mut ai.stuck_timer, step, direction)
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                None => {
+                    for (_, mut velocity, mut state) in &mut balls {
+                        match field {
+                            InspectorField::VelocityX => nudge(&mut velocity.0.x, step, direction),
+                            InspectorField::VelocityY => nudge(&mut velocity.0.y, step, direction),
+                            InspectorField::ShotPower => {
+                                if let BallState::InFlight { power, .. } = &mut *state {
+                                    nudge(power, step, direction);
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Redraw
+    let mut lines = Vec::new();
+    for (row, (section, field)) in inspector.rows().into_iter().enumerate() {
+        let marker = if row == inspector.selected { ">" } else { " " };
+        let Some(field) = field else {
+            let index = InspectorSection::ALL
+                .iter()
+                .position(|s| *s == section)
+                .unwrap_or(0);
+            let arrow = if inspector.expanded[index] { "-" } else { "+" };
+            let entity = match section.team() {
+                Some(team) => players
+                    .iter()
+                    .find(|(_, t, ..)| **t == team)
+                    .map(|(entity, ..)| entity),
+                None => balls.iter().next().map(|(entity, ..)| entity),
+            };
+            let entity = entity.map_or("missing".to_string(), |e| format!("{}", e));
+            lines.push(format!(
+                "{} {} {} ({})",
+                marker,
+                arrow,
+                section.title(),
+                entity
+            ));
+            continue;
+        };
+
+        let value =
+            match section.team() {
+                Some(team) => players.iter().find(|(_, t, ..)| **t == team).map(
+                    |(_, _, velocity, jump, ai)| match field {
+                        InspectorField::VelocityX => format!("{:.1}", velocity.0.x),
+                        InspectorField::VelocityY => format!("{:.1}", velocity.0.y),
+                        InspectorField::Jumping => jump.is_jumping.to_string(),
+                        _ => match ai {
+                            None => "-".to_string(),
+                            Some(ai) => match field {
+                                InspectorField::AiGoal => format!("{:?}", ai.current_goal),
+                                InspectorField::ShotChargeTarget => {
+                                    format!("{:.2}", ai.shot_charge_target)
+                                }
+                                InspectorField::JumpShotActive => ai.jump_shot_active.to_string(),
+                                InspectorField::JumpShotTimer => {
+                                    format!("{:.2}", ai.jump_shot_timer)
+                                }
+                                InspectorField::StuckTimer => format!("{:.2}", ai.stuck_timer),
+                                _ => "-".to_string(),
+                            },
+                        },
+                    },
+                ),
+                None => balls.iter().next().map(|(_, velocity, state)| match field {
+                    InspectorField::VelocityX => format!("{:.1}", velocity.0.x),
+                    InspectorField::VelocityY => format!("{:.1}", velocity.0.y),
+                    InspectorField::BallState => match state {
+                        BallState::Free => "Free".to_string(),
+                        BallState::Held(holder) => format!("Held({})", holder),
+                        BallState::InFlight { shooter, .. } => format!("InFlight({})", shooter),
+                        BallState::Passed { passer, .. } => format!("Passed({})", passer),
+                    },
+                    InspectorField::ShotPower => match state {
+                        BallState::InFlight { power, .. } => format!("{:.1}", power),
+                        _ => "-".to_string(),
+                    },
+                    _ => "-".to_string(),
+                }),
+            };
+        let editable = if field.step().is_some() {
+            ""
+        } else {
+            " (read-only)"
+        };
+        lines.push(format!(
+            "{}     {}: {}{}",
+            marker,
+            field.label(),
+            value.unwrap_or_else(|| "-".to_string()),
+            editable
+        ));
+    }
+    for mut text in &mut texts {
+        **text = lines.join("\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rows_follow_expansion_and_selection() {
+        let mut inspector = EntityInspector::default();
+        assert_eq!(inspector.rows().len(), 3);
+
+        // Expand the right player
+        inspector.move_selection(true);
+        inspector.toggle_selected();
+        let rows = inspector.rows();
+        assert_eq!(rows.len(), 3 + PLAYER_FIELDS.len());
+        assert_eq!(rows[1], (InspectorSection::RightPlayer, None));
+        assert_eq!(
+            rows[2],
+            (
+                InspectorSection::RightPlayer,
+                Some(InspectorField::VelocityX)
+            )
+        );
+
+        // Collapsing from a field row lands back on its header
+        inspector.move_selection(true);
+        inspector.move_selection(true);
+        inspector.toggle_selected();
+        assert_eq!(inspector.selected, 1);
+        assert_eq!(inspector.rows().len(), 3);
+
+        // Selection wraps
+        inspector.move_selection(false);
+        inspector.move_selection(false);
+        assert_eq!(inspector.selected, 2);
+
+        assert!(InspectorField::AiGoal.step().is_none());
+        let mut value = 100.0;
+        nudge(&mut value, 50.0, -1.0);
+        assert_eq!(value, 50.0);
+    }
+}
@@ -1,15 +1,29 @@
-//! UI module - debug, HUD, animations, charge gauge, tweak panel, and steal indicators
+//! UI module - debug, HUD, animations, charge gauge, tweak panel, entity inspector, steal
+//! indicators, AI goal labels, ability HUD, assists, the input viewer and the first-run setup
+//! wizard
 
+mod ability_hud;
+mod ai_labels;
 mod animations;
+mod assists;
 mod charge_gauge;
 mod debug;
 mod hud;
+mod input_viewer;
+mod inspector;
+mod setup_wizard;
 mod steal_indicators;
 mod tweak_panel;
 
+pub use ability_hud::*;
+pub use ai_labels::*;
 pub use animations::*;
+pub use assists::*;
 pub use charge_gauge::*;
 pub use debug::*;
 pub use hud::*;
+pub use input_viewer::*;
+pub use inspector::*;
+pub use setup_wizard::*;
 pub use steal_indicators::*;
 pub use tweak_panel::*;
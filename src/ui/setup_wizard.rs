@@ -0,0 +1,377 @@
+//! First-run setup wizard
+//!
+//! Shown on first launch (no `setup_complete` in `init_settings.json`) or with
+//! `--setup`. Pages: detected controllers, key binding preset, viewport preset,
+//! palette and assists. Changes apply live; finishing (or Esc to skip) marks
+//! setup complete and writes the settings file. The match countdown stays
+//! frozen until the wizard closes.
+//!
+//! Keyboard: Left/Right change, Up/Down pick an assist, Enter next, Backspace back.
+//! Gamepad: D-pad the same, A next, B back.
+
+use bevy::prelude::*;
+
+use crate::ball::CurrentPalette;
+use crate::constants::*;
+use crate::countdown::MatchCountdown;
+use crate::input::{InputBindings, KeyBindings};
+use crate::palettes::PaletteDatabase;
+use crate::settings::CurrentSettings;
+
+use super::Assists;
+use super::debug::{ViewportScale, apply_viewport};
+
+/// Wizard pages in order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WizardPage {
+    #[default]
+    Controllers,
+    Bindings,
+    Viewport,
+    Palette,
+    Assists,
+    Done,
+}
+
+impl WizardPage {
+    pub const ALL: [WizardPage; 6] = [
+        WizardPage::Controllers,
+        WizardPage::Bindings,
+        WizardPage::Viewport,
+        WizardPage::Palette,
+        WizardPage::Assists,
+        WizardPage::Done,
+    ];
+
+    fn index(&self) -> usize {
+        Self::ALL.iter().position(|p| p == self).unwrap_or(0)
+    }
+
+    /// Following page (None after Done)
+    pub fn next(&self) -> Option<Self> {
+        Self::ALL.get(self.index() + 1).copied()
+    }
+
+    /// Preceding page (stays on the first page)
+    pub fn prev(&self) -> Self {
+        Self::ALL[self.index().saturating_sub(1)]
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            WizardPage::Controllers => "Controllers",
+            WizardPage::Bindings => "Key Bindings",
+            WizardPage::Viewport => "Window Size",
+            WizardPage::Palette => "Palette",
+            WizardPage::Assists => "Assists",
+            WizardPage::Done => "Ready",
+        }
+    }
+}
+
+/// Controls line under every page
+const WIZARD_HELP: &str = "Left/Right: change | Enter/A: next | Backspace/B: back | Esc: skip";
+
+/// Assist rows on the Assists page
+const ASSIST_LABELS: [&str; 3] = [
+    "Shot feedback markers",
+    "Steal indicators",
+    "AI goal labels",
+];
+
+/// Wizard state
+#[derive(Resource, Debug, Default)]
+pub struct SetupWizard {
+    pub active: bool,
+    pub page: WizardPage,
+    /// Selected row on the Assists page
+    pub assist_row: usize,
+    /// Restart the countdown on close (false if it was frozen for other reasons)
+    pub resume_countdown: bool,
+}
+
+impl SetupWizard {
+    pub fn new(active: bool, resume_countdown: bool) -> Self {
+        Self {
+            active,
+            resume_countdown,
+            ..default()
+        }
+    }
+}
+
+/// Run condition: wizard is showing
+pub fn setup_wizard_active(wizard: Res<SetupWizard>) -> bool {
+    wizard.active
+}
+
+/// Run condition: wizard is not showing
+pub fn setup_wizard_inactive(wizard: Res<SetupWizard>) -> bool {
+    !wizard.active
+}
+
+/// Wizard panel root
+#[derive(Component)]
+pub struct SetupWizardPanel;
+
+/// Page text inside the panel
+#[derive(Component)]
+pub struct SetupWizardText;
+
+/// Step `index` one place forward/back through `len` options, wrapping
+fn cycle_index(index: usize, len: usize, forward: bool) -> usize {
+    if len == 0 {
+        return 0;
+    }
+    if forward {
+        (index + 1) % len
+    } else {
+        (index + len - 1) % len
+    }
+}
+
+/// Spawn the wizard panel (startup, only when the wizard is active)
+pub fn spawn_setup_wizard(mut commands: Commands, wizard: Res<SetupWizard>) {
+    if !wizard.active {
+        return;
+    }
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(25.0),
+                top: Val::Percent(20.0),
+                width: Val::Percent(50.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(20.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.05, 0.05, 0.08, 0.95)),
+            GlobalZIndex(10),
+            SetupWizardPanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(TEXT_PRIMARY),
+                SetupWizardText,
+            ));
+        });
+}
+
+/// Handle wizard input, apply choices live, and finish/skip
+#[allow(clippy::too_many_arguments)]
+pub fn update_setup_wizard(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<(&Gamepad, Option<&Name>)>,
+    palette_db: Res<PaletteDatabase>,
+    mut wizard: ResMut<SetupWizard>,
+    mut current_settings: ResMut<CurrentSettings>,
+    mut bindings: ResMut<InputBindings>,
+    mut viewport_scale: ResMut<ViewportScale>,
+    mut current_palette: ResMut<CurrentPalette>,
+    mut assists: ResMut<Assists>,
+    mut countdown: ResMut<MatchCountdown>,
+    mut window_query: Query<&mut Window>,
+    panels: Query<Entity, With<SetupWizardPanel>>,
+    mut texts: Query<&mut Text, With<SetupWizardText>>,
+) {
+    let gamepad_pressed = |button: GamepadButton| {
+        gamepads
+            .iter()
+            .any(|(gamepad, _)| gamepad.just_pressed(button))
+    };
+    let left =
+        keyboard.just_pressed(KeyCode::ArrowLeft) || gamepad_pressed(GamepadButton::DPadLeft);
+    let right =
+        keyboard.just_pressed(KeyCode::ArrowRight) || gamepad_pressed(GamepadButton::DPadRight);
+    let up = keyboard.just_pressed(KeyCode::ArrowUp) || gamepad_pressed(GamepadButton::DPadUp);
+    let down =
+        keyboard.just_pressed(KeyCode::ArrowDown) || gamepad_pressed(GamepadButton::DPadDown);
+    let confirm = keyboard.just_pressed(KeyCode::Enter) || gamepad_pressed(GamepadButton::South);
+    let back = keyboard.just_pressed(KeyCode::Backspace) || gamepad_pressed(GamepadButton::East);
+    let skip = keyboard.just_pressed(KeyCode::Escape);
+
+    let settings = &mut current_settings.settings;
+    if left || right {
+        match wizard.page {
+            WizardPage::Controllers | WizardPage::Done => {}
+            WizardPage::Bindings => {
+                let presets = KeyBindings::PRESETS;
+                let current = presets
+                    .iter()
+                    .position(|(name, _)| *name == settings.key_bindings)
+                    .unwrap_or(0);
+                let (name, _) = presets[cycle_index(current, presets.len(), right)];
+                settings.key_bindings = name.to_string();
+                // Local versus keeps its fixed split-keyboard layout
+                if !bindings.is_local_versus()
+                    && let Some(preset) = KeyBindings::from_preset(name)
+                {
+                    bindings.primary = preset;
+                }
+            }
+            WizardPage::Viewport => {
+                if right {
+                    viewport_scale.cycle_next();
+                } else {
+                    viewport_scale.cycle_prev();
+                }
+                apply_viewport(&viewport_scale, &mut window_query);
+                settings.viewport_index = viewport_scale.preset_index;
+            }
+            WizardPage::Palette => {
+                current_palette.0 = cycle_index(current_palette.0, palette_db.len(), right);
+                settings.palette_index = current_palette.0;
+            }
+            WizardPage::Assists => match wizard.assist_row {
+                0 => {
+                    assists.shot_feedback = !assists.shot_feedback;
+                    settings.shot_feedback = assists.shot_feedback;
+                }
+                1 => {
+                    assists.steal_indicators = !assists.steal_indicators;
+                    settings.steal_indicators = assists.steal_indicators;
+                }
+                _ => {
+                    assists.ai_goal_labels = !assists.ai_goal_labels;
+                    settings.ai_goal_labels = assists.ai_goal_labels;
+                }
+            },
+        }
+    }
+    if wizard.page == WizardPage::Assists && (up || down) {
+        wizard.assist_row = cycle_index(wizard.assist_row, ASSIST_LABELS.len(), down);
+    }
+    if back {
+        wizard.page = wizard.page.prev();
+    }
+
+    let finished = if confirm {
+        match wizard.page.next() {
+            Some(page) => {
+                wizard.page = page;
+                false
+            }
+            None => true,
+        }
+    } else {
+        skip
+    };
+    if finished {
+        current_settings.settings.setup_complete = true;
+        current_settings.mark_dirty();
+        wizard.active = false;
+        if wizard.resume_countdown {
+            countdown.start();
+        }
+        for panel in &panels {
+            commands.entity(panel).despawn();
+        }
+        info!("Setup complete");
+        return;
+    }
+
+    // Page text
+    let settings = &current_settings.settings;
+    let body = match wizard.page {
+        WizardPage::Controllers => {
+            let names: Vec<String> = gamepads
+                .iter()
+                .enumerate()
+                .map(|(i, (_, name))| {
+                    name.map(|n| n.as_str().to_string())
+                        .unwrap_or_else(|| format!("Gamepad {}", i + 1))
+                })
+                .collect();
+            if names.is_empty() {
+                "No gamepads detected - keyboard only.\nPlug one in now to see it here.".to_string()
+            } else {
+                format!("Detected:\n  {}", names.join("\n  "))
+            }
+        }
+        WizardPage::Bindings => {
+            let lines: Vec<String> = KeyBindings::PRESETS
+                .iter()
+                .map(|(name, description)| {
+                    let marker = if *name == settings.key_bindings {
+                        ">"
+                    } else {
+                        " "
+                    };
+                    format!("{} {}: {}", marker, name, description)
+                })
+                .collect();
+            format!("{}\nGamepads always work.", lines.join("\n"))
+        }
+        WizardPage::Viewport => format!("< {} >", viewport_scale.current().2),
+        WizardPage::Palette => format!(
+            "< {} >",
+            palette_db
+                .get(current_palette.0)
+                .map(|p| p.name.as_str())
+                .unwrap_or("?")
+        ),
+        WizardPage::Assists => ASSIST_LABELS
+            .iter()
+            .zip([
+                assists.shot_feedback,
+                assists.steal_indicators,
+                assists.ai_goal_labels,
+            ])
+            .enumerate()
+            .map(|(row, (label, on))| {
+                let marker = if row == wizard.assist_row { ">" } else { " " };
+                format!("{} {}: {}", marker, label, if on { "on" } else { "off" })
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        WizardPage::Done => "Settings will be saved to init_settings.json.\n\
+                             Change them later with the D-pad menu or --setup."
+            .to_string(),
+    };
+    let step = wizard.page.index() + 1;
+    let total = WizardPage::ALL.len();
+    for mut text in &mut texts {
+        **text = format!(
+            "Setup ({}/{}): {}\n\n{}\n\n{}",
+            step,
+            total,
+            wizard.page.title(),
+            body,
+            WIZARD_HELP
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wizard_pages_and_cycling() {
+        let mut page = WizardPage::default();
+        let mut visited = vec![page];
+        while let Some(next) = page.next() {
+            page = next;
+            visited.push(page);
+        }
+        assert_eq!(visited, WizardPage::ALL);
+        assert_eq!(WizardPage::Controllers.prev(), WizardPage::Controllers);
+        assert_eq!(WizardPage::Done.prev(), WizardPage::Assists);
+
+        assert_eq!(cycle_index(2, 3, true), 0);
+        assert_eq!(cycle_index(0, 3, false), 2);
+        assert_eq!(cycle_index(0, 0, true), 0);
+
+        for (name, _) in KeyBindings::PRESETS {
+            assert!(KeyBindings::from_preset(name).is_some());
+        }
+        assert!(!SetupWizard::default().active);
+    }
+}
@@ -1,11 +1,14 @@
-//! Steal visual indicators - cooldown gauge and fail flash
+//! Steal visual indicators - cooldown gauge, denial flashes, and camera shake
 
 use bevy::prelude::*;
 
-use crate::constants::{PLAYER_SIZE, STEAL_COOLDOWN, STEAL_INDICATOR_SIZE};
-use crate::player::Player;
+use crate::config::StealConfig;
+use crate::constants::{PLAYER_SIZE, STEAL_INDICATOR_SIZE};
+use crate::player::{HumanControlled, Player};
 use crate::shooting::ChargingShot;
-use crate::steal::{StealContest, StealCooldown};
+use crate::steal::{StealContest, StealCooldown, StealDenial};
+
+use super::Assists;
 
 // =============================================================================
 // COMPONENTS
@@ -15,31 +18,34 @@ use crate::steal::{StealContest, StealCooldown};
 #[derive(Component)]
 pub struct StealCooldownIndicator;
 
-/// Marker for fail flash indicator (brief red flash when steal fails)
-#[derive(Component)]
-pub struct StealFailFlash;
-
-/// Marker for out-of-range flash indicator (orange flash when steal attempt is too far)
+/// Outline flash shown when a steal press is denied for this reason
 #[derive(Component)]
-pub struct StealOutOfRangeFlash;
+pub struct StealDenialFlash(pub StealDenial);
 
 /// Marker for vulnerable indicator (shows when player is charging and exposed)
 #[derive(Component)]
 pub struct VulnerableIndicator;
 
-/// Marker for cooldown-blocked flash (shows when steal pressed but on cooldown)
-#[derive(Component)]
-pub struct CooldownBlockedFlash;
-
 // =============================================================================
 // COLORS
 // =============================================================================
 
 const COOLDOWN_COLOR: Color = Color::srgba(0.4, 0.4, 0.5, 0.8);
-const FAIL_FLASH_COLOR: Color = Color::srgba(0.9, 0.2, 0.2, 0.7); // Red - steal failed
+const FAIL_FLASH_COLOR: Color = Color::srgba(0.9, 0.2, 0.2, 0.7); // Red - lost the roll
 const OUT_OF_RANGE_COLOR: Color = Color::srgba(1.0, 0.5, 0.0, 0.6); // Orange - too far
 const VULNERABLE_COLOR: Color = Color::srgba(1.0, 0.8, 0.0, 0.6); // Yellow warning
 const COOLDOWN_BLOCKED_COLOR: Color = Color::srgba(0.3, 0.6, 0.9, 0.7); // Blue - on cooldown
+const INVULNERABLE_COLOR: Color = Color::srgba(0.85, 0.85, 0.95, 0.8); // White - untouchable
+
+/// Flash color, outline padding, and z offset for each denial reason
+fn denial_flash_style(reason: StealDenial) -> (Color, f32, f32) {
+    match reason {
+        StealDenial::ContestLost => (FAIL_FLASH_COLOR, 6.0, -0.1),
+        StealDenial::OutOfRange => (OUT_OF_RANGE_COLOR, 10.0, -0.15),
+        StealDenial::OnCooldown => (COOLDOWN_BLOCKED_COLOR, 12.0, -0.2),
+        StealDenial::HolderInvulnerable => (INVULNERABLE_COLOR, 14.0, -0.25),
+    }
+}
 
 // =============================================================================
 // SPAWNING
@@ -58,33 +64,24 @@ pub fn spawn_steal_indicators(commands: &mut Commands, player_entity: Entity, _f
         .id();
     commands.entity(player_entity).add_child(cooldown_indicator);
 
-    // Fail flash indicator: brief red outline when steal fails
-    let fail_flash = commands
-        .spawn((
-            Sprite::from_color(
-                FAIL_FLASH_COLOR,
-                Vec2::new(PLAYER_SIZE.x + 6.0, PLAYER_SIZE.y + 6.0),
-            ),
-            Transform::from_xyz(0.0, 0.0, -0.1),
-            Visibility::Hidden,
-            StealFailFlash,
-        ))
-        .id();
-    commands.entity(player_entity).add_child(fail_flash);
-
-    // Out-of-range flash: orange outline when steal attempted but too far
-    let out_of_range = commands
-        .spawn((
-            Sprite::from_color(
-                OUT_OF_RANGE_COLOR,
-                Vec2::new(PLAYER_SIZE.x + 10.0, PLAYER_SIZE.y + 10.0),
-            ),
-            Transform::from_xyz(0.0, 0.0, -0.15),
-            Visibility::Hidden,
-            StealOutOfRangeFlash,
-        ))
-        .id();
-    commands.entity(player_entity).add_child(out_of_range);
+    // Denial flashes: one outline per reason, each a different color and size
+    for reason in [
+        StealDenial::ContestLost,
+        StealDenial::OutOfRange,
+        StealDenial::OnCooldown,
+        StealDenial::HolderInvulnerable,
+    ] {
+        let (color, pad, z) = denial_flash_style(reason);
+        let flash = commands
+            .spawn((
+                Sprite::from_color(color, Vec2::new(PLAYER_SIZE.x + pad, PLAYER_SIZE.y + pad)),
+                Transform::from_xyz(0.0, 0.0, z),
+                Visibility::Hidden,
+                StealDenialFlash(reason),
+            ))
+            .id();
+        commands.entity(player_entity).add_child(flash);
+    }
 
     // Vulnerable indicator: outline around player when charging and exposed
     let vulnerable = commands
@@ -99,20 +96,6 @@ pub fn spawn_steal_indicators(commands: &mut Commands, player_entity: Entity, _f
         ))
         .id();
     commands.entity(player_entity).add_child(vulnerable);
-
-    // Cooldown-blocked flash: blue pulse when steal pressed but on cooldown
-    let cooldown_blocked = commands
-        .spawn((
-            Sprite::from_color(
-                COOLDOWN_BLOCKED_COLOR,
-                Vec2::new(PLAYER_SIZE.x + 12.0, PLAYER_SIZE.y + 12.0),
-            ),
-            Transform::from_xyz(0.0, 0.0, -0.2),
-            Visibility::Hidden,
-            CooldownBlockedFlash,
-        ))
-        .id();
-    commands.entity(player_entity).add_child(cooldown_blocked);
 }
 
 // =============================================================================
@@ -123,35 +106,22 @@ pub fn spawn_steal_indicators(commands: &mut Commands, player_entity: Entity, _f
 #[allow(clippy::type_complexity)]
 pub fn update_steal_indicators(
     steal_contest: Res<StealContest>,
+    steal_config: Res<StealConfig>,
+    assists: Res<Assists>,
     player_query: Query<(Entity, &StealCooldown, &ChargingShot, &Children), With<Player>>,
     mut cooldown_query: Query<
         (&mut Sprite, &mut Transform, &mut Visibility),
         (
             With<StealCooldownIndicator>,
-            Without<StealFailFlash>,
-            Without<StealOutOfRangeFlash>,
-            Without<VulnerableIndicator>,
-            Without<CooldownBlockedFlash>,
-        ),
-    >,
-    mut fail_flash_query: Query<
-        &mut Visibility,
-        (
-            With<StealFailFlash>,
-            Without<StealCooldownIndicator>,
-            Without<StealOutOfRangeFlash>,
+            Without<StealDenialFlash>,
             Without<VulnerableIndicator>,
-            Without<CooldownBlockedFlash>,
         ),
     >,
-    mut out_of_range_query: Query<
-        &mut Visibility,
+    mut denial_query: Query<
+        (&StealDenialFlash, &mut Sprite, &mut Visibility),
         (
-            With<StealOutOfRangeFlash>,
             Without<StealCooldownIndicator>,
-            Without<StealFailFlash>,
             Without<VulnerableIndicator>,
-            Without<CooldownBlockedFlash>,
         ),
     >,
     mut vulnerable_query: Query<
@@ -159,29 +129,20 @@ pub fn update_steal_indicators(
         (
             With<VulnerableIndicator>,
             Without<StealCooldownIndicator>,
-            Without<StealFailFlash>,
-            Without<StealOutOfRangeFlash>,
-            Without<CooldownBlockedFlash>,
-        ),
-    >,
-    mut cooldown_blocked_query: Query<
-        &mut Visibility,
-        (
-            With<CooldownBlockedFlash>,
-            Without<StealCooldownIndicator>,
-            Without<StealFailFlash>,
-            Without<StealOutOfRangeFlash>,
-            Without<VulnerableIndicator>,
+            Without<StealDenialFlash>,
         ),
     >,
 ) {
+    let shown = assists.steal_indicators;
     for (player_entity, cooldown, charging, children) in &player_query {
+        let denial = steal_contest.denial_for(player_entity).filter(|_| shown);
+
         for child in children.iter() {
             // Update cooldown indicator
             if let Ok((mut sprite, mut transform, mut visibility)) = cooldown_query.get_mut(child) {
-                if cooldown.0 > 0.0 {
+                if shown && cooldown.0 > 0.0 {
                     *visibility = Visibility::Inherited;
-                    let fill_pct = cooldown.0 / STEAL_COOLDOWN;
+                    let fill_pct = cooldown.0 / steal_config.cooldown;
                     let height = STEAL_INDICATOR_SIZE * fill_pct;
                     sprite.custom_size = Some(Vec2::new(STEAL_INDICATOR_SIZE, height));
                     // Centered above player, anchor at bottom
@@ -191,51 +152,54 @@ pub fn update_steal_indicators(
                 }
             }
 
-            // Update fail flash indicator
-            if let Ok(mut visibility) = fail_flash_query.get_mut(child) {
-                // Show when this player just failed a steal
-                if steal_contest.last_attempt_failed
-                    && steal_contest.fail_flash_entity == Some(player_entity)
-                {
-                    *visibility = Visibility::Inherited;
-                } else {
-                    *visibility = Visibility::Hidden;
-                }
-            }
-
-            // Update out-of-range flash indicator
-            if let Ok(mut visibility) = out_of_range_query.get_mut(child) {
-                // Show when this player attempted steal but was too far
-                if steal_contest.out_of_range_timer > 0.0
-                    && steal_contest.out_of_range_entity == Some(player_entity)
-                {
-                    *visibility = Visibility::Inherited;
-                } else {
-                    *visibility = Visibility::Hidden;
+            // Update denial flashes - only the one matching this player's reason shows,
+            // fading out as its timer runs down
+            if let Ok((flash, mut sprite, mut visibility)) = denial_query.get_mut(child) {
+                match denial.filter(|d| d.reason == flash.0) {
+                    Some(d) => {
+                        *visibility = Visibility::Inherited;
+                        let (color, _, _) = denial_flash_style(flash.0);
+                        let fade = (d.timer / d.reason.flash_duration()).clamp(0.0, 1.0);
+                        sprite.color = color.with_alpha(color.alpha() * fade.max(0.3));
+                    }
+                    None => *visibility = Visibility::Hidden,
                 }
             }
 
             // Update vulnerable indicator
             if let Ok(mut visibility) = vulnerable_query.get_mut(child) {
                 // Show when charging (vulnerable to steal)
-                if charging.charge_time > 0.0 {
+                if shown && charging.charge_time > 0.0 {
                     *visibility = Visibility::Inherited;
                 } else {
                     *visibility = Visibility::Hidden;
                 }
             }
+        }
+    }
+}
 
-            // Update cooldown-blocked flash indicator
-            if let Ok(mut visibility) = cooldown_blocked_query.get_mut(child) {
-                // Show when this player pressed steal but was on cooldown
-                if steal_contest.cooldown_blocked_timer > 0.0
-                    && steal_contest.cooldown_blocked_entity == Some(player_entity)
-                {
-                    *visibility = Visibility::Inherited;
-                } else {
-                    *visibility = Visibility::Hidden;
-                }
-            }
+/// Shake the camera horizontally while the human player's steal denial is showing.
+/// Strength depends on the reason; the camera settles back to x = 0 afterwards.
+pub fn shake_camera_on_steal_denial(
+    time: Res<Time>,
+    steal_contest: Res<StealContest>,
+    humans: Query<Entity, (With<Player>, With<HumanControlled>)>,
+    mut cameras: Query<&mut Transform, With<Camera2d>>,
+) {
+    let offset = humans
+        .iter()
+        .filter_map(|entity| steal_contest.denial_for(entity))
+        .map(|d| {
+            let fade = (d.timer / d.reason.flash_duration()).clamp(0.0, 1.0);
+            d.reason.shake_amplitude() * fade * (time.elapsed_secs() * 90.0).sin()
+        })
+        .next()
+        .unwrap_or(0.0);
+
+    for mut transform in &mut cameras {
+        if transform.translation.x != offset {
+            transform.translation.x = offset;
         }
     }
 }
@@ -3,6 +3,7 @@
 use bevy::prelude::*;
 
 use crate::constants::*;
+use crate::levels::ArenaDims;
 
 /// Marker for collidable entities
 #[derive(Component, Default)]
@@ -25,6 +26,65 @@ pub struct BasketRim;
 #[derive(Component)]
 pub struct CornerRamp;
 
+/// Arena side wall (repositioned when arena dimensions change)
+#[derive(Component, Clone, Copy, PartialEq)]
+pub enum ArenaWall {
+    Left,
+    Right,
+}
+
+/// Arena floor (resized when arena dimensions change)
+#[derive(Component)]
+pub struct ArenaFloor;
+
+/// In-bounds region for the out-of-bounds rule. A live ball whose center
+/// leaves it - into the baseline strip along either wall, or over the top of
+/// the visible arena - is out.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct CourtBounds {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl CourtBounds {
+    /// Court for an arena: wall to wall minus COURT_BASELINE_DEPTH, floor to ceiling
+    pub fn from_arena(arena: &ArenaDims) -> Self {
+        let half_width = (arena.wall_inner() - COURT_BASELINE_DEPTH).max(0.0);
+        Self {
+            min: Vec2::new(-half_width, arena.floor_y),
+            max: Vec2::new(half_width, arena.ceiling_y()),
+        }
+    }
+
+    pub fn contains(&self, pos: Vec2) -> bool {
+        pos.cmpge(self.min).all() && pos.cmple(self.max).all()
+    }
+}
+
+/// Portal end - entities entering are moved to `exit` (the linked end)
+#[derive(Component, Clone, Copy)]
+pub struct Teleporter {
+    pub exit: Vec2,
+}
+
+/// Bounce pad - grounded players and falling balls on it are launched upward
+#[derive(Component, Clone, Copy)]
+pub struct BouncePad {
+    pub launch: f32,
+}
+
+/// Blocks re-teleporting until the timer runs out and the entity has left all portals
+#[derive(Component, Default)]
+pub struct PortalCooldown(pub f32);
+
+/// Visual particle drifting inside a wind zone (wraps around the zone bounds)
+#[derive(Component, Clone, Copy)]
+pub struct WindParticle {
+    pub min: Vec2,
+    pub max: Vec2,
+    pub velocity: Vec2,
+}
+
 /// Basket scoring zone
 #[derive(Component, Clone, Copy, PartialEq)]
 pub enum Basket {
@@ -32,6 +92,23 @@ pub enum Basket {
     Right,
 }
 
+/// Recompute rim GlobalTransforms after moving a basket.
+/// Ball collisions read GlobalTransform, which headless sims never propagate.
+pub fn sync_rim_globals<F: bevy::ecs::query::QueryFilter>(
+    basket: &Transform,
+    children: Option<&Children>,
+    rims: &mut Query<(&Transform, &mut GlobalTransform), F>,
+) {
+    let Some(children) = children else {
+        return;
+    };
+    for child in children.iter() {
+        if let Ok((rim_transform, mut rim_global)) = rims.get_mut(child) {
+            *rim_global = GlobalTransform::from(basket.mul_transform(*rim_transform));
+        }
+    }
+}
+
 // ============================================================================
 // Arena spawning functions (shared between main game and test runner)
 // ============================================================================
@@ -42,6 +119,7 @@ pub fn spawn_floor(commands: &mut Commands, color: Color) {
         Sprite::from_color(color, Vec2::new(ARENA_WIDTH - WALL_THICKNESS * 2.0, 40.0)),
         Transform::from_xyz(0.0, ARENA_FLOOR_Y, 0.0),
         Platform,
+        ArenaFloor,
     ));
 }
 
@@ -52,12 +130,14 @@ pub fn spawn_walls(commands: &mut Commands, color: Color) {
         Sprite::from_color(color, Vec2::new(WALL_THICKNESS, 5000.0)),
         Transform::from_xyz(-ARENA_WIDTH / 2.0 + WALL_THICKNESS / 2.0, 2000.0, 0.0),
         Platform,
+        ArenaWall::Left,
     ));
     // Right wall
     commands.spawn((
         Sprite::from_color(color, Vec2::new(WALL_THICKNESS, 5000.0)),
         Transform::from_xyz(ARENA_WIDTH / 2.0 - WALL_THICKNESS / 2.0, 2000.0, 0.0),
         Platform,
+        ArenaWall::Right,
     ));
 }
 
@@ -123,6 +203,7 @@ pub fn spawn_basket_with_rims(
 /// Spawn both baskets with rims at specified positions
 pub fn spawn_baskets(
     commands: &mut Commands,
+    arena: &ArenaDims,
     basket_y: f32,
     basket_push_in: f32,
     left_basket_color: Color,
@@ -130,9 +211,7 @@ pub fn spawn_baskets(
     left_rim_color: Color,
     right_rim_color: Color,
 ) {
-    let wall_inner = ARENA_WIDTH / 2.0 - WALL_THICKNESS;
-    let left_x = -wall_inner + basket_push_in;
-    let right_x = wall_inner - basket_push_in;
+    let (left_x, right_x) = arena.basket_x_from_offset(basket_push_in);
 
     spawn_basket_with_rims(
         commands,
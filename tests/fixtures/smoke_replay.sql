@@ -0,0 +1,73 @@
+-- Smoke-test replay fixture: one 3-second match on Arena (level 3).
+-- Loaded by `cargo run --bin smoke` into an in-memory SimDatabase; the schema
+-- comes from SimDatabase itself. Regenerate by hand if the event format changes.
+INSERT INTO sessions (id, created_at, session_type) VALUES ('smoke', '2026-01-01T00:00:00Z', 'smoke');
+INSERT INTO matches (id, session_id, seed, level, level_name, left_profile, right_profile, score_left, score_right, duration_secs, winner)
+VALUES (1, 'smoke', 42, 3, 'Arena', 'Balanced', 'Rusher', 1, 0, 3.0, 'left');
+INSERT INTO events (match_id, time_ms, event_type, data) VALUES
+(1, 0, 'MS', 'T:00000|MS|3|Arena|Balanced|Rusher|42'),
+(1, 0, 'T', 'T:00000|T|0|-300.0,-398.0|100.0,0.0|300.0,-398.0|-40.0,0.0|-240.0,-400.0|0.0,0.0|F'),
+(1, 50, 'T', 'T:00050|T|3|-295.0,-398.0|100.0,0.0|298.0,-398.0|-40.0,0.0|-240.0,-400.0|0.0,0.0|F'),
+(1, 100, 'T', 'T:00100|T|6|-290.0,-398.0|100.0,0.0|296.0,-398.0|-40.0,0.0|-240.0,-400.0|0.0,0.0|F'),
+(1, 150, 'T', 'T:00150|T|9|-285.0,-398.0|100.0,0.0|294.0,-398.0|-40.0,0.0|-240.0,-400.0|0.0,0.0|F'),
+(1, 200, 'T', 'T:00200|T|12|-280.0,-398.0|100.0,0.0|292.0,-398.0|-40.0,0.0|-240.0,-400.0|0.0,0.0|F'),
+(1, 250, 'T', 'T:00250|T|15|-275.0,-398.0|100.0,0.0|290.0,-398.0|-40.0,0.0|-240.0,-400.0|0.0,0.0|F'),
+(1, 300, 'T', 'T:00300|T|18|-270.0,-398.0|100.0,0.0|288.0,-398.0|-40.0,0.0|-240.0,-400.0|0.0,0.0|F'),
+(1, 350, 'T', 'T:00350|T|21|-265.0,-398.0|100.0,0.0|286.0,-398.0|-40.0,0.0|-240.0,-400.0|0.0,0.0|F'),
+(1, 400, 'PU', 'T:00400|PU|L'),
+(1, 400, 'T', 'T:00400|T|24|-260.0,-398.0|100.0,0.0|284.0,-398.0|-40.0,0.0|-240.0,-380.0|0.0,0.0|H'),
+(1, 450, 'T', 'T:00450|T|27|-255.0,-398.0|100.0,0.0|282.0,-398.0|-40.0,0.0|-235.0,-380.0|0.0,0.0|H'),
+(1, 500, 'T', 'T:00500|T|30|-250.0,-398.0|100.0,0.0|280.0,-398.0|-40.0,0.0|-230.0,-380.0|0.0,0.0|H'),
+(1, 550, 'T', 'T:00550|T|33|-245.0,-398.0|100.0,0.0|278.0,-398.0|-40.0,0.0|-225.0,-380.0|0.0,0.0|H'),
+(1, 600, 'T', 'T:00600|T|36|-240.0,-398.0|100.0,0.0|276.0,-398.0|-40.0,0.0|-220.0,-380.0|0.0,0.0|H'),
+(1, 650, 'T', 'T:00650|T|39|-235.0,-398.0|100.0,0.0|274.0,-398.0|-40.0,0.0|-215.0,-380.0|0.0,0.0|H'),
+(1, 700, 'T', 'T:00700|T|42|-230.0,-398.0|100.0,0.0|272.0,-398.0|-40.0,0.0|-210.0,-380.0|0.0,0.0|H'),
+(1, 750, 'T', 'T:00750|T|45|-225.0,-398.0|100.0,0.0|270.0,-398.0|-40.0,0.0|-205.0,-380.0|0.0,0.0|H'),
+(1, 800, 'T', 'T:00800|T|48|-220.0,-398.0|100.0,0.0|268.0,-398.0|-40.0,0.0|-200.0,-380.0|0.0,0.0|H'),
+(1, 850, 'T', 'T:00850|T|51|-215.0,-398.0|100.0,0.0|266.0,-398.0|-40.0,0.0|-195.0,-380.0|0.0,0.0|H'),
+(1, 900, 'T', 'T:00900|T|54|-210.0,-398.0|100.0,0.0|264.0,-398.0|-40.0,0.0|-190.0,-380.0|0.0,0.0|H'),
+(1, 950, 'T', 'T:00950|T|57|-205.0,-398.0|100.0,0.0|262.0,-398.0|-40.0,0.0|-185.0,-380.0|0.0,0.0|H'),
+(1, 1000, 'T', 'T:01000|T|60|-200.0,-398.0|100.0,0.0|260.0,-398.0|-40.0,0.0|-180.0,-380.0|0.0,0.0|H'),
+(1, 1050, 'T', 'T:01050|T|63|-195.0,-398.0|100.0,0.0|258.0,-398.0|-40.0,0.0|-175.0,-380.0|0.0,0.0|H'),
+(1, 1100, 'T', 'T:01100|T|66|-190.0,-398.0|100.0,0.0|256.0,-398.0|-40.0,0.0|-170.0,-380.0|0.0,0.0|H'),
+(1, 1150, 'T', 'T:01150|T|69|-185.0,-398.0|100.0,0.0|254.0,-398.0|-40.0,0.0|-165.0,-380.0|0.0,0.0|H'),
+(1, 1200, 'T', 'T:01200|T|72|-180.0,-398.0|100.0,0.0|252.0,-398.0|-40.0,0.0|-160.0,-380.0|0.0,0.0|H'),
+(1, 1250, 'T', 'T:01250|T|75|-175.0,-398.0|100.0,0.0|250.0,-398.0|-40.0,0.0|-155.0,-380.0|0.0,0.0|H'),
+(1, 1300, 'T', 'T:01300|T|78|-170.0,-398.0|100.0,0.0|248.0,-398.0|-40.0,0.0|-150.0,-380.0|0.0,0.0|H'),
+(1, 1350, 'T', 'T:01350|T|81|-165.0,-398.0|100.0,0.0|246.0,-398.0|-40.0,0.0|-145.0,-380.0|0.0,0.0|H'),
+(1, 1400, 'T', 'T:01400|T|84|-160.0,-398.0|100.0,0.0|244.0,-398.0|-40.0,0.0|-140.0,-380.0|0.0,0.0|H'),
+(1, 1450, 'T', 'T:01450|T|87|-155.0,-398.0|100.0,0.0|242.0,-398.0|-40.0,0.0|-135.0,-380.0|0.0,0.0|H'),
+(1, 1500, 'T', 'T:01500|T|90|-150.0,-398.0|100.0,0.0|240.0,-398.0|-40.0,0.0|-130.0,-380.0|0.0,0.0|H'),
+(1, 1550, 'T', 'T:01550|T|93|-145.0,-398.0|100.0,0.0|238.0,-398.0|-40.0,0.0|-125.0,-380.0|0.0,0.0|H'),
+(1, 1600, 'T', 'T:01600|T|96|-140.0,-398.0|100.0,0.0|236.0,-398.0|-40.0,0.0|-120.0,-380.0|0.0,0.0|H'),
+(1, 1650, 'T', 'T:01650|T|99|-135.0,-398.0|100.0,0.0|234.0,-398.0|-40.0,0.0|-115.0,-380.0|0.0,0.0|H'),
+(1, 1700, 'T', 'T:01700|T|102|-130.0,-398.0|100.0,0.0|232.0,-398.0|-40.0,0.0|-110.0,-380.0|0.0,0.0|H'),
+(1, 1750, 'T', 'T:01750|T|105|-125.0,-398.0|100.0,0.0|230.0,-398.0|-40.0,0.0|-105.0,-380.0|0.0,0.0|H'),
+(1, 1800, 'T', 'T:01800|T|108|-120.0,-398.0|100.0,0.0|228.0,-398.0|-40.0,0.0|-100.0,-380.0|0.0,0.0|H'),
+(1, 1850, 'T', 'T:01850|T|111|-115.0,-398.0|100.0,0.0|226.0,-398.0|-40.0,0.0|-95.0,-380.0|0.0,0.0|H'),
+(1, 1900, 'T', 'T:01900|T|114|-110.0,-398.0|100.0,0.0|224.0,-398.0|-40.0,0.0|-90.0,-380.0|0.0,0.0|H'),
+(1, 1950, 'T', 'T:01950|T|117|-105.0,-398.0|100.0,0.0|222.0,-398.0|-40.0,0.0|-85.0,-380.0|0.0,0.0|H'),
+(1, 2000, 'T', 'T:02000|T|120|-100.0,-398.0|100.0,0.0|220.0,-398.0|-40.0,0.0|-80.0,-380.0|0.0,0.0|I'),
+(1, 2000, 'SR', 'T:02000|SR|L|0.80|60.0|700.0'),
+(1, 2050, 'T', 'T:02050|T|123|-95.0,-398.0|100.0,0.0|218.0,-398.0|-40.0,0.0|-55.0,-365.5|0.0,0.0|I'),
+(1, 2100, 'T', 'T:02100|T|126|-90.0,-398.0|100.0,0.0|216.0,-398.0|-40.0,0.0|-30.0,-352.0|0.0,0.0|I'),
+(1, 2150, 'T', 'T:02150|T|129|-85.0,-398.0|100.0,0.0|214.0,-398.0|-40.0,0.0|-5.0,-339.5|0.0,0.0|I'),
+(1, 2200, 'T', 'T:02200|T|132|-80.0,-398.0|100.0,0.0|212.0,-398.0|-40.0,0.0|20.0,-328.0|0.0,0.0|I'),
+(1, 2250, 'T', 'T:02250|T|135|-75.0,-398.0|100.0,0.0|210.0,-398.0|-40.0,0.0|45.0,-317.5|0.0,0.0|I'),
+(1, 2300, 'T', 'T:02300|T|138|-70.0,-398.0|100.0,0.0|208.0,-398.0|-40.0,0.0|70.0,-308.0|0.0,0.0|I'),
+(1, 2350, 'T', 'T:02350|T|141|-65.0,-398.0|100.0,0.0|206.0,-398.0|-40.0,0.0|95.0,-299.5|0.0,0.0|I'),
+(1, 2400, 'T', 'T:02400|T|144|-60.0,-398.0|100.0,0.0|204.0,-398.0|-40.0,0.0|120.0,-292.0|0.0,0.0|I'),
+(1, 2450, 'T', 'T:02450|T|147|-55.0,-398.0|100.0,0.0|202.0,-398.0|-40.0,0.0|145.0,-285.5|0.0,0.0|I'),
+(1, 2500, 'T', 'T:02500|T|150|-50.0,-398.0|100.0,0.0|200.0,-398.0|-40.0,0.0|170.0,-280.0|0.0,0.0|I'),
+(1, 2550, 'T', 'T:02550|T|153|-45.0,-398.0|100.0,0.0|198.0,-398.0|-40.0,0.0|195.0,-275.5|0.0,0.0|I'),
+(1, 2600, 'T', 'T:02600|T|156|-40.0,-398.0|100.0,0.0|196.0,-398.0|-40.0,0.0|220.0,-272.0|0.0,0.0|F'),
+(1, 2600, 'G', 'T:02600|G|L|1|0'),
+(1, 2650, 'T', 'T:02650|T|159|-35.0,-398.0|100.0,0.0|194.0,-398.0|-40.0,0.0|245.0,-269.5|0.0,0.0|F'),
+(1, 2700, 'T', 'T:02700|T|162|-30.0,-398.0|100.0,0.0|192.0,-398.0|-40.0,0.0|270.0,-268.0|0.0,0.0|F'),
+(1, 2750, 'T', 'T:02750|T|165|-25.0,-398.0|100.0,0.0|190.0,-398.0|-40.0,0.0|295.0,-267.5|0.0,0.0|F'),
+(1, 2800, 'T', 'T:02800|T|168|-20.0,-398.0|100.0,0.0|188.0,-398.0|-40.0,0.0|320.0,-268.0|0.0,0.0|F'),
+(1, 2850, 'T', 'T:02850|T|171|-15.0,-398.0|100.0,0.0|186.0,-398.0|-40.0,0.0|345.0,-269.5|0.0,0.0|F'),
+(1, 2900, 'T', 'T:02900|T|174|-10.0,-398.0|100.0,0.0|184.0,-398.0|-40.0,0.0|370.0,-272.0|0.0,0.0|F'),
+(1, 2950, 'T', 'T:02950|T|177|-5.0,-398.0|100.0,0.0|182.0,-398.0|-40.0,0.0|395.0,-275.5|0.0,0.0|F'),
+(1, 3000, 'T', 'T:03000|T|180|0.0,-398.0|100.0,0.0|180.0,-398.0|-40.0,0.0|420.0,-280.0|0.0,0.0|F'),
+(1, 3000, 'ME', 'T:03000|ME|1|0|3.0');